@@ -0,0 +1,723 @@
+use clap::{Parser, Subcommand};
+use clap_complete::engine::{ArgValueCandidates, CompletionCandidate};
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::Config;
+use crate::domain::action_policy::{ActionPolicy, PolicyAction};
+use crate::domain::latency::Latency;
+use crate::domain::service::Service;
+use crate::domain::service_repository::ServiceRepository;
+use crate::domain::service_query::ServiceQuery;
+use crate::domain::session_recording::{RecordedEvent, Recording};
+use crate::domain::snapshot::Snapshot;
+use crate::errors::ServiceError;
+use crate::infrastructure::backend::Backend;
+use crate::infrastructure::export::{serialize_services, ExportFormat};
+use crate::infrastructure::http_server::{random_token, respond, HttpRequest};
+use crate::infrastructure::merged_service_adapter::MergedServiceAdapter;
+use crate::infrastructure::snapshot_store::{latest_snapshot, load_snapshot, save_snapshot, snapshots_dir};
+use crate::infrastructure::systemd_service_adapter::{ConnectionType, SystemdServiceAdapter};
+use crate::terminal::headless::parse_size;
+use crate::usecases::services_manager::{ServicesManager, LOG_LINES};
+
+/// Environment variable holding the token clients of `--serve` must send; without it a token
+/// is made up and printed when the server starts.
+const TOKEN_VARIABLE: &str = "SYSTEMD_MANAGER_TUI_TOKEN";
+
+/// Longest wait between two events of a replay, the idle time of the session being cut short.
+const MAX_REPLAY_PAUSE: Duration = Duration::from_secs(2);
+
+/// Manage systemd units from a TUI, or from scripts through subcommands.
+#[derive(Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Make subcommands talk to the user service manager instead of the system one.
+    #[arg(long, global = true)]
+    pub user: bool,
+
+    /// Init system to manage, overriding the `backend` setting.
+    #[arg(long, value_enum, global = true)]
+    pub backend: Option<Backend>,
+
+    /// Show example units kept in memory instead of managing this machine, like `--backend demo`.
+    #[arg(long, global = true, conflicts_with = "backend")]
+    pub demo: bool,
+
+    /// With --demo, the `[[units]]` and `[[failures]]` of this TOML file instead of the examples.
+    #[arg(long, requires = "demo")]
+    pub fixture: Option<PathBuf>,
+
+    /// Run the TUI without a terminal, pressing these keys one after the other (e.g. "down enter"),
+    /// and print the last screen as text.
+    #[arg(long, allow_hyphen_values = true)]
+    pub screenshot: Option<String>,
+
+    /// With --screenshot, the columns and rows of the screen.
+    #[arg(long, requires = "screenshot", default_value = "100x30", value_parser = parse_size)]
+    pub size: (u16, u16),
+
+    /// Render the TUI as plain, label-prefixed lines, for screen readers and braille displays.
+    #[arg(long)]
+    pub accessible: bool,
+
+    /// Draw the TUI without colors, like the NO_COLOR environment variable.
+    #[arg(long)]
+    pub no_color: bool,
+
+    /// Draw the TUI with ASCII characters only, for terminals lacking box drawing and symbols.
+    #[arg(long)]
+    pub ascii: bool,
+
+    /// Open the TUI on the failed units view.
+    #[arg(long)]
+    pub failed: bool,
+
+    /// Open the TUI on a profile of the config instead of the one used last.
+    #[arg(long)]
+    pub profile: Option<String>,
+
+    /// Show the units of several repositories in one table, overriding the `sources` setting,
+    /// e.g. "system,session,containers".
+    #[arg(long, value_delimiter = ',', global = true)]
+    pub sources: Vec<String>,
+
+    /// Make subcommands manage another machine over ssh, e.g. "admin@web-1".
+    #[arg(long, short = 'H', global = true)]
+    pub host: Option<String>,
+
+    /// Append the duration of each listing, properties and journal call, filter and drawing of
+    /// the TUI to this file, overriding the `trace` setting.
+    #[arg(long)]
+    pub trace: Option<PathBuf>,
+
+    /// Record the keys pressed and the screens drawn to this file, to play them back with the
+    /// replay subcommand, e.g. to reproduce a bug or review what was done during an incident.
+    #[arg(long)]
+    pub record: Option<PathBuf>,
+
+    /// Refuse every action changing units or their files, overriding the `read_only` setting.
+    #[arg(long, global = true)]
+    pub read_only: bool,
+
+    /// Critical units a subcommand may stop, restart, disable or freeze outside of the
+    /// maintenance windows, like typing their names in the TUI, e.g. "postgresql,nginx".
+    #[arg(long, value_delimiter = ',', global = true)]
+    pub confirm: Vec<String>,
+
+    /// Watch the units without the TUI, writing each state change as a JSON line until the
+    /// connection to the manager is lost.
+    #[arg(long)]
+    pub watch: bool,
+
+    /// With --watch, only watch units whose name contains this text; `state:failed`, `enabled:no`...
+    /// conditions filter on their state, like in the TUI.
+    #[arg(long, requires = "watch")]
+    pub watch_filter: Option<String>,
+
+    /// With --watch, append the transitions to this file instead of writing them to stdout.
+    #[arg(long, requires = "watch")]
+    pub watch_output: Option<PathBuf>,
+
+    /// Serve the units over an HTTP JSON API on this address, e.g. "127.0.0.1:8080", for
+    /// dashboards and scripts. Clients authenticate with the token of SYSTEMD_MANAGER_TUI_TOKEN.
+    #[arg(long, conflicts_with = "watch")]
+    pub serve: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// List units with their state.
+    List {
+        /// Print the list as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Export units, including their properties, as JSON or YAML.
+    Export {
+        #[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+        /// Only export units whose name contains this text; `state:failed`, `enabled:no`...
+        /// conditions filter on their state, like in the TUI.
+        #[arg(long)]
+        filter: Option<String>,
+        /// Write to this file instead of stdout.
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Start a unit.
+    Start {
+        #[arg(add = ArgValueCandidates::new(unit_candidates))]
+        unit: String,
+    },
+    /// Stop a unit.
+    Stop {
+        #[arg(add = ArgValueCandidates::new(unit_candidates))]
+        unit: String,
+    },
+    /// Restart a unit.
+    Restart {
+        #[arg(add = ArgValueCandidates::new(unit_candidates))]
+        unit: String,
+    },
+    /// Reload the configuration of a unit without restarting it.
+    Reload {
+        #[arg(add = ArgValueCandidates::new(unit_candidates))]
+        unit: String,
+    },
+    /// Reload a unit when it can, restart it otherwise.
+    ReloadOrRestart {
+        #[arg(add = ArgValueCandidates::new(unit_candidates))]
+        unit: String,
+    },
+    /// Enable a unit.
+    Enable {
+        #[arg(add = ArgValueCandidates::new(unit_candidates))]
+        unit: String,
+    },
+    /// Disable a unit.
+    Disable {
+        #[arg(add = ArgValueCandidates::new(unit_candidates))]
+        unit: String,
+    },
+    /// Print the journal of a unit, oldest entries first.
+    Logs {
+        #[arg(add = ArgValueCandidates::new(unit_candidates))]
+        unit: String,
+        /// Number of journal lines to print.
+        #[arg(long, default_value_t = LOG_LINES)]
+        lines: u32,
+    },
+    /// Save the state of every unit, to compare with `diff` later, e.g. across an upgrade.
+    Snapshot {
+        /// Directory to write the snapshot to instead of the state directory.
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Print the units whose state changed since a snapshot, the last one of the machine by default.
+    Diff {
+        snapshot: Option<PathBuf>,
+        /// Print the changes as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Measure the latency of listing units and fetching their properties, for performance reports.
+    Bench {
+        /// Number of times each measurement is repeated.
+        #[arg(long, default_value_t = 5)]
+        iterations: u32,
+        /// Number of units whose properties are fetched.
+        #[arg(long, default_value_t = 10)]
+        units: usize,
+    },
+    /// Play back a session recorded with --record, at the pace it was recorded.
+    Replay {
+        file: PathBuf,
+        /// Play faster (2) or slower (0.5).
+        #[arg(long, default_value_t = 1.0)]
+        speed: f64,
+        /// Print the keys pressed instead, to press them again with --screenshot.
+        #[arg(long, conflicts_with = "cast")]
+        keys: bool,
+        /// Write the screens to this file as an asciinema cast instead.
+        #[arg(long)]
+        cast: Option<PathBuf>,
+    },
+}
+
+impl Cli {
+    pub fn connection_type(&self) -> ConnectionType {
+        if self.user {
+            ConnectionType::Session
+        } else {
+            ConnectionType::System
+        }
+    }
+}
+
+/// Units of the system and session managers, offered by the shell for the unit of subcommands,
+/// e.g. `restart ng<TAB>`. Nothing is offered when neither manager can be reached.
+fn unit_candidates() -> Vec<CompletionCandidate> {
+    let Ok(config) = Config::load() else {
+        return Vec::new();
+    };
+    let mut services: Vec<Service> = [ConnectionType::System, ConnectionType::Session]
+        .into_iter()
+        .filter_map(|connection_type| config.backend.create_repository(connection_type, config.unit_types.clone()).ok())
+        .filter_map(|repository| repository.list_services().ok())
+        .flatten()
+        .collect();
+    services.sort_by(|a, b| a.name().cmp(b.name()));
+    services.dedup_by(|a, b| a.name() == b.name());
+    services
+        .into_iter()
+        .map(|service| {
+            let description = service.description().to_string();
+            CompletionCandidate::new(service.name()).help(Some(description.into()))
+        })
+        .collect()
+}
+
+fn connect(
+    host: Option<String>,
+    sources: &[String],
+    backend: Backend,
+    connection_type: ConnectionType,
+    unit_types: Vec<String>,
+) -> Result<Box<dyn ServiceRepository>, Box<dyn Error>> {
+    Ok(match host {
+        Some(host) => Box::new(SystemdServiceAdapter::remote(host, connection_type, unit_types)?),
+        None if !sources.is_empty() => Box::new(MergedServiceAdapter::new(sources, backend, unit_types)?),
+        None => backend.create_repository(connection_type, unit_types)?,
+    })
+}
+
+/// The units whose name contains the filter text, or one of its `|` alternatives, and whose
+/// state matches its conditions.
+fn filtered_services(usecase: &ServicesManager, filter: Option<String>) -> Result<Vec<Service>, Box<dyn Error>> {
+    let query = ServiceQuery::parse(&filter.unwrap_or_default());
+    let names: Vec<String> = query.names().map(str::to_lowercase).collect();
+    Ok(usecase
+        .list_services()?
+        .into_iter()
+        .filter(|service| {
+            let name = service.formatted_name().to_lowercase();
+            names.iter().any(|alternative| name.contains(alternative.as_str())) && query.matches_state(service)
+        })
+        .collect())
+}
+
+/// Watches the units matching `filter` without starting the TUI, writing each state change as
+/// a JSON line to `output` or stdout, for use as a monitoring agent.
+pub fn watch(
+    filter: Option<String>,
+    output: Option<PathBuf>,
+    host: Option<String>,
+    connection_type: ConnectionType,
+    config: &Config,
+) -> Result<(), Box<dyn Error>> {
+    let repository = connect(host.clone(), &config.sources, config.backend, connection_type, config.unit_types.clone())?;
+    let mut usecase = ServicesManager::new(repository);
+    usecase.set_hooks(config.hooks.clone());
+    let units: Vec<String> = filtered_services(&usecase, filter)?
+        .into_iter()
+        // Watching loads the unit, which would bring the installed but unused ones into the manager.
+        .filter(|service| service.state().load() != "not-loaded")
+        .map(|service| service.name().to_string())
+        .collect();
+    if units.is_empty() {
+        return Err("No unit matches the filter".into());
+    }
+
+    let mut writer: Box<dyn Write> = match &output {
+        Some(path) => Box::new(
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|e| format!("{}: {}", path.display(), e))?,
+        ),
+        None => Box::new(io::stdout()),
+    };
+    let (sender, receiver) = mpsc::channel();
+    usecase.watch_transitions(sender);
+    let count = units.len();
+    usecase.watch_units(units)?;
+    eprintln!("Watching {} units, stop with Ctrl+C", count);
+
+    // The watch thread drops its sender when the connection closes, ending the loop.
+    for transition in receiver {
+        let event = serde_json::json!({
+            "time": transition.at().to_rfc3339(),
+            "host": host.as_deref().unwrap_or("localhost"),
+            "unit": transition.unit(),
+            "from": transition.from(),
+            "to": transition.to(),
+            "sub_state": transition.sub_state(),
+        });
+        writeln!(writer, "{}", event)?;
+        writer.flush()?;
+        usecase.unit_changed(&transition);
+    }
+    Err("Lost the connection to the service manager".into())
+}
+
+/// Answers the requests of the HTTP API on `address` one after the other until stopped:
+/// `GET /units`, `/units/<unit>` and `/units/<unit>/logs`, and `POST /units/<unit>/start`,
+/// `stop`, `restart` and `reload`, each with `Authorization: Bearer <token>`.
+pub fn serve(
+    address: String,
+    host: Option<String>,
+    connection_type: ConnectionType,
+    config: &Config,
+) -> Result<(), Box<dyn Error>> {
+    let token = match std::env::var(TOKEN_VARIABLE) {
+        Ok(token) if !token.is_empty() => token,
+        _ => {
+            let token = random_token()?;
+            eprintln!("{} is not set, clients must send the token {}", TOKEN_VARIABLE, token);
+            token
+        }
+    };
+    let repository = connect(host, &config.sources, config.backend, connection_type, config.unit_types.clone())?;
+    let mut usecase = ServicesManager::new(repository);
+    usecase.set_read_only(config.read_only);
+    usecase.set_policy(ActionPolicy::new(config.policy.clone()));
+    usecase.set_maintenance(config.maintenance.clone());
+    usecase.set_hooks(config.hooks.clone());
+    usecase.set_call_limits(config.call_limits());
+
+    let listener = TcpListener::bind(&address).map_err(|e| format!("{}: {}", address, e))?;
+    eprintln!("Serving the units on http://{}, stop with Ctrl+C", address);
+    // Each connection is read and answered on its own thread, so a slow client holds up no
+    // other; the manager stays on this one, routing the requests in turn.
+    let (request_sender, requests) = mpsc::channel::<(HttpRequest, mpsc::Sender<(u16, serde_json::Value)>)>();
+    let connections = Arc::new(AtomicUsize::new(0));
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else {
+                continue;
+            };
+            // Refused before anything is read, so that clients holding connections open cannot
+            // pile up threads.
+            if connections.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                connections.fetch_sub(1, Ordering::SeqCst);
+                respond(stream, 503, &serde_json::json!({ "error": "Too many connections, retry later" }));
+                continue;
+            }
+            let connections = connections.clone();
+            let request_sender = request_sender.clone();
+            let token = token.clone();
+            thread::spawn(move || {
+                let _slot = ConnectionSlot(connections);
+                let (status, body) = match HttpRequest::read(&stream) {
+                    Err(e) => (400, serde_json::json!({ "error": e })),
+                    Ok(request) if !request.is_authorized(&token) => {
+                        (401, serde_json::json!({ "error": "Missing or wrong bearer token" }))
+                    }
+                    Ok(request) => {
+                        let (answer_sender, answer) = mpsc::channel();
+                        if request_sender.send((request, answer_sender)).is_err() {
+                            return;
+                        }
+                        let Ok(answer) = answer.recv() else {
+                            return;
+                        };
+                        answer
+                    }
+                };
+                respond(stream, status, &body);
+            });
+        }
+    });
+    for (request, answer) in requests {
+        let _ = answer.send(match route(&usecase, &request) {
+            Ok(body) => (200, body),
+            Err((status, error)) => (status, serde_json::json!({ "error": error })),
+        });
+    }
+    Ok(())
+}
+
+/// Connections served at once by `serve`, the next ones answered 503.
+const MAX_CONNECTIONS: usize = 16;
+
+/// A connection counted by `serve`, given back when its thread ends.
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// The answer of the API to an authorized request, or its status and error message.
+fn route(usecase: &ServicesManager, request: &HttpRequest) -> Result<serde_json::Value, (u16, String)> {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    let method = match segments.as_slice() {
+        ["units"] | ["units", _] | ["units", _, "logs"] => "GET",
+        ["units", _, _] => "POST",
+        _ => return Err((404, format!("No endpoint at {}", request.path))),
+    };
+    if request.method != method {
+        return Err((405, format!("{} expects {}", request.path, method)));
+    }
+    match segments.as_slice() {
+        ["units"] => {
+            let services = filtered_services(usecase, request.query.get("filter").cloned()).map_err(|e| {
+                match e.downcast_ref::<ServiceError>() {
+                    Some(error) => failure(error),
+                    None => (500, e.to_string()),
+                }
+            })?;
+            serde_json::to_value(services).map_err(|e| (500, e.to_string()))
+        }
+        ["units", unit] => {
+            let service = usecase.find_service(unit).map_err(|e| failure(&e))?;
+            serde_json::to_value(&usecase.with_properties(&[service])[0]).map_err(|e| (500, e.to_string()))
+        }
+        ["units", unit, "logs"] => {
+            let lines = match request.query.get("lines") {
+                Some(lines) => lines.parse().map_err(|_| (400, format!("Not a number of lines: {}", lines)))?,
+                None => LOG_LINES,
+            };
+            let service = usecase.find_service(unit).map_err(|e| failure(&e))?;
+            let logs = usecase.get_log_lines(&service, lines).map_err(|e| failure(&e))?;
+            Ok(serde_json::json!({ "unit": service.name(), "logs": logs }))
+        }
+        ["units", unit, action] => {
+            let service = usecase.find_service(unit).map_err(|e| failure(&e))?;
+            // A critical unit outside of the maintenance windows is confirmed by naming it.
+            if let Some(policy_action) = action_policy(action)
+                && request.query.get("confirm").is_some_and(|name| full_unit_name(name) == service.name())
+            {
+                let needed = usecase.needing_confirmation(policy_action, &[service.name()]);
+                usecase.confirm_critical(&needed);
+            }
+            let job = match *action {
+                "start" => usecase.start_service(&service),
+                "stop" => usecase.stop_service(&service),
+                "restart" => usecase.restart_service(&service),
+                "reload" => usecase.reload_service(&service),
+                _ => return Err((404, format!("No action named {}", action))),
+            }
+            .map_err(|e| failure(&e))?;
+            Ok(serde_json::json!({ "unit": service.name(), "action": action, "job": job }))
+        }
+        _ => unreachable!("routed above"),
+    }
+}
+
+/// The action of the policy an endpoint runs.
+fn action_policy(action: &str) -> Option<PolicyAction> {
+    match action {
+        "start" => Some(PolicyAction::Start),
+        "stop" => Some(PolicyAction::Stop),
+        "restart" => Some(PolicyAction::Restart),
+        "reload" => Some(PolicyAction::Reload),
+        _ => None,
+    }
+}
+
+/// A unit named like on the command line, "nginx" standing for "nginx.service".
+fn full_unit_name(name: &str) -> String {
+    if name.contains('.') { name.to_string() } else { format!("{}.service", name) }
+}
+
+/// The status answering a call the manager failed, with its message.
+fn failure(error: &ServiceError) -> (u16, String) {
+    let status = match error {
+        ServiceError::NotFound(_) => 404,
+        ServiceError::PermissionDenied(_) => 403,
+        ServiceError::Unsupported(_) => 501,
+        ServiceError::Unavailable(_) | ServiceError::Timeout(_) => 503,
+        _ => 500,
+    };
+    (status, error.to_string())
+}
+
+/// Runs a subcommand without starting the TUI.
+pub fn run(
+    command: Command,
+    host: Option<String>,
+    connection_type: ConnectionType,
+    confirmed: &[String],
+    config: &Config,
+) -> Result<(), Box<dyn Error>> {
+    // Replaying needs no manager.
+    if let Command::Replay { file, speed, keys, cast } = command {
+        return replay(&file, speed, keys, cast);
+    }
+    let user = matches!(connection_type, ConnectionType::Session);
+    let started = Instant::now();
+    let repository = connect(host.clone(), &config.sources, config.backend, connection_type, config.unit_types.clone())?;
+    let connection = started.elapsed();
+    let mut usecase = ServicesManager::new(repository);
+    usecase.set_read_only(config.read_only);
+    usecase.set_policy(ActionPolicy::new(config.policy.clone()));
+    usecase.set_maintenance(config.maintenance.clone());
+    usecase.set_hooks(config.hooks.clone());
+    usecase.set_call_limits(config.call_limits());
+    let confirmed: Vec<String> = confirmed.iter().map(|unit| full_unit_name(unit)).collect();
+    usecase.confirm_critical(&confirmed);
+
+    match command {
+        Command::List { json } => {
+            let services = usecase.list_services()?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&services)?);
+            } else {
+                for service in services {
+                    let state = service.state();
+                    println!(
+                        "{}\t{}\t{}\t{}\t{}",
+                        service.name(),
+                        state.active(),
+                        state.sub(),
+                        state.file(),
+                        service.description()
+                    );
+                }
+            }
+        }
+        Command::Export { format, filter, output } => {
+            let services = filtered_services(&usecase, filter)?;
+            let content = serialize_services(&usecase.with_properties(&services), format)?;
+            match output {
+                Some(path) => fs::write(&path, content).map_err(|e| format!("{}: {}", path.display(), e))?,
+                None => print!("{}", content),
+            }
+        }
+        Command::Start { unit } => {
+            usecase.start_service(&usecase.find_service(&unit)?)?;
+        }
+        Command::Stop { unit } => {
+            usecase.stop_service(&usecase.find_service(&unit)?)?;
+        }
+        Command::Restart { unit } => {
+            usecase.restart_service(&usecase.find_service(&unit)?)?;
+        }
+        Command::Reload { unit } => {
+            usecase.reload_service(&usecase.find_service(&unit)?)?;
+        }
+        Command::ReloadOrRestart { unit } => {
+            usecase.reload_or_restart_service(&usecase.find_service(&unit)?)?;
+        }
+        Command::Enable { unit } => usecase.enable_service(&usecase.find_service(&unit)?)?,
+        Command::Disable { unit } => usecase.disable_service(&usecase.find_service(&unit)?)?,
+        Command::Logs { unit, lines } => {
+            let service = usecase.find_service(&unit)?;
+            println!("{}", usecase.get_log_lines(&service, lines)?);
+        }
+        Command::Snapshot { output } => {
+            let directory = match output {
+                Some(directory) => directory,
+                None => snapshots_dir()?,
+            };
+            let snapshot = Snapshot::new(&usecase.list_services()?, chrono::Local::now().to_rfc3339());
+            let path = save_snapshot(&directory, host.as_deref(), user, &snapshot)?;
+            println!("{}", path.display());
+        }
+        Command::Diff { snapshot, json } => {
+            let path = match snapshot {
+                Some(path) => path,
+                None => latest_snapshot(&snapshots_dir()?, host.as_deref(), user)?
+                    .ok_or("No snapshot of this machine yet, take one with the snapshot subcommand")?,
+            };
+            let before = load_snapshot(&path)?;
+            let current = Snapshot::new(&usecase.list_services()?, chrono::Local::now().to_rfc3339());
+            let changes = before.diff(&current);
+            if json {
+                println!("{}", serde_json::to_string_pretty(&changes)?);
+            } else if changes.is_empty() {
+                println!("No unit changed since {} ({})", before.taken_at, path.display());
+            } else {
+                println!("Changes since {} ({})", before.taken_at, path.display());
+                for change in changes {
+                    println!("{}\t{}\t{}", change.kind.label(), change.unit, change.describe());
+                }
+            }
+        }
+        Command::Bench { iterations, units } => bench(&usecase, connection, iterations.max(1), units)?,
+        Command::Replay { .. } => unreachable!("replayed before connecting"),
+    }
+    Ok(())
+}
+
+/// Prints the latency of each step of a refresh: connecting, the listing call, the per-unit
+/// state calls, the whole refresh and the property fetches of the details view.
+fn bench(usecase: &ServicesManager, connection: Duration, iterations: u32, units: usize) -> Result<(), Box<dyn Error>> {
+    let mut list_calls = Vec::new();
+    let mut unit_calls = Vec::new();
+    let mut refreshes = Vec::new();
+    let mut services = Vec::new();
+    for _ in 0..iterations {
+        let timings = usecase.time_listing()?;
+        list_calls.push(timings.list_call());
+        unit_calls.extend_from_slice(timings.unit_calls());
+
+        let started = Instant::now();
+        services = usecase.list_services()?;
+        refreshes.push(started.elapsed());
+    }
+
+    let mut property_calls = Vec::new();
+    let mut property_error = None;
+    for _ in 0..iterations {
+        for service in services.iter().take(units) {
+            let mut service = service.clone();
+            let started = Instant::now();
+            match usecase.update_properties(&mut service) {
+                Ok(()) => property_calls.push(started.elapsed()),
+                Err(e) => property_error = Some(e.to_string()),
+            }
+        }
+    }
+
+    let unit_latency = Latency::from_samples(&unit_calls);
+    println!("{} units, {} iterations", services.len(), iterations);
+    println!("{:<18}    {:>9.3} ms", "connection", connection.as_secs_f64() * 1000.0);
+    println!("{:<18}{}", "listing call", Latency::from_samples(&list_calls));
+    println!(
+        "{:<18}{}  ({:.3} ms per listing)",
+        "unit state calls",
+        unit_latency,
+        unit_latency.total().as_secs_f64() * 1000.0 / f64::from(iterations)
+    );
+    println!("{:<18}{}", "full refresh", Latency::from_samples(&refreshes));
+    match property_error {
+        Some(error) if property_calls.is_empty() => println!("{:<18}unavailable: {}", "properties", error.trim()),
+        _ => {
+            let latency = Latency::from_samples(&property_calls);
+            println!("{:<18}{}  ({} fetches)", "properties", latency, latency.samples());
+        }
+    }
+    Ok(())
+}
+
+/// Plays a recording of `--record` in the terminal, each screen under the last key pressed
+/// before it; or prints its keys, or converts it to an asciinema cast.
+fn replay(file: &Path, speed: f64, keys: bool, cast: Option<PathBuf>) -> Result<(), Box<dyn Error>> {
+    let content = fs::read_to_string(file).map_err(|e| format!("{}: {}", file.display(), e))?;
+    let recording = Recording::parse(&content).map_err(|e| format!("{}: {}", file.display(), e))?;
+    if keys {
+        println!("{}", recording.key_script());
+        return Ok(());
+    }
+    if let Some(path) = cast {
+        fs::write(&path, recording.to_cast()).map_err(|e| format!("{}: {}", path.display(), e))?;
+        return Ok(());
+    }
+    if !speed.is_finite() || speed <= 0.0 {
+        return Err("--speed must be a number above 0".into());
+    }
+
+    let mut stdout = io::stdout();
+    let mut last_key = String::new();
+    let mut previous = 0.0;
+    for event in &recording.events {
+        let pause = Duration::try_from_secs_f64(((event.time() - previous) / speed).max(0.0))
+            .map_err(|_| format!("{}: the pause before the event at {}s is out of range at this --speed", file.display(), event.time()))?;
+        thread::sleep(pause.min(MAX_REPLAY_PAUSE));
+        previous = event.time();
+        match event {
+            RecordedEvent::Key { key, .. } => last_key = key.clone(),
+            RecordedEvent::Screen { time, screen } => {
+                write!(stdout, "\x1b[H\x1b[2J{}\n\x1b[7m {:.3}s  last key: {} \x1b[0m", screen, time, last_key)?;
+                stdout.flush()?;
+            }
+        }
+    }
+    println!();
+    Ok(())
+}
@@ -0,0 +1,1418 @@
+use chrono::{Datelike, NaiveDateTime, NaiveTime, Weekday};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::Constraint;
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::domain::action_policy::PolicyRule;
+use crate::domain::health::HealthProbe;
+use crate::domain::hook::Hook;
+use crate::domain::service_group::ServiceGroup;
+use crate::domain::unit_template::UnitTemplate;
+use crate::infrastructure::backend::Backend;
+use crate::infrastructure::call_limits::CallLimits;
+use crate::infrastructure::export::ExportFormat;
+use crate::terminal::keychord::ChordAction;
+use crate::terminal::locale::{Locale, LANGUAGES};
+use crate::terminal::theme::{Theme, THEME_NAMES};
+
+const CONFIG_DIR: &str = "services-manager-tui";
+const CONFIG_FILE: &str = "config.toml";
+/// Directory of the organization's unit templates, next to the config file.
+const TEMPLATES_DIR: &str = "templates";
+
+/// Bindings of the vim keymap, under the `[keybindings]` and `[chords]` of the config file.
+const VIM_KEYMAP: &str = r#"
+[keybindings]
+up = "k"
+down = "j"
+page_up = "ctrl+u"
+page_down = "ctrl+d"
+previous_tab = "h"
+next_tab = "l"
+filter = "/"
+kill = "K"
+next_host = "H"
+
+[chords]
+go_bottom = "G"
+"#;
+
+/// A single key (optionally combined with Ctrl/Alt) as written in the config file,
+/// e.g. "s", "ctrl+r", "pagedown" or "f5".
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    pub const fn new(code: KeyCode) -> Self {
+        Self {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    const fn char(c: char) -> Self {
+        Self::new(KeyCode::Char(c))
+    }
+
+    /// The key press this binding stands for, e.g. to script the TUI.
+    pub fn key_event(&self) -> KeyEvent {
+        KeyEvent::new(self.code, self.modifiers)
+    }
+
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        let mut modifiers = key.modifiers;
+        if matches!(key.code, KeyCode::Char(_)) {
+            // Shift is already reflected in the character itself.
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        self.code == key.code && self.modifiers == modifiers
+    }
+}
+
+impl TryFrom<String> for KeyBinding {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut key = value.as_str();
+
+        loop {
+            let lower = key.to_lowercase();
+            if lower.starts_with("ctrl+") && key.len() > 5 {
+                modifiers.insert(KeyModifiers::CONTROL);
+                key = &key[5..];
+            } else if lower.starts_with("alt+") && key.len() > 4 {
+                modifiers.insert(KeyModifiers::ALT);
+                key = &key[4..];
+            } else {
+                break;
+            }
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_lowercase().as_str() {
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "space" => KeyCode::Char(' '),
+                "backspace" => KeyCode::Backspace,
+                "delete" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                other => match other.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key \"{}\"", value)),
+                },
+            },
+        };
+
+        Ok(Self { code, modifiers })
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl + ")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt + ")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            other => write!(f, "{}", other),
+        }
+    }
+}
+
+/// Keys pressed one after the other, separated by spaces in the config file, e.g. "g f".
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct KeySequence(Vec<KeyBinding>);
+
+impl KeySequence {
+    pub fn keys(&self) -> &[KeyBinding] {
+        &self.0
+    }
+}
+
+impl TryFrom<String> for KeySequence {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let keys = value
+            .split_whitespace()
+            .map(|key| KeyBinding::try_from(key.to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        if keys.is_empty() {
+            return Err("empty key sequence".to_string());
+        }
+        Ok(Self(keys))
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, key) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", key)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeyBindings {
+    pub up: KeyBinding,
+    pub down: KeyBinding,
+    pub page_up: KeyBinding,
+    pub page_down: KeyBinding,
+    pub next_tab: KeyBinding,
+    pub previous_tab: KeyBinding,
+    pub start: KeyBinding,
+    pub stop: KeyBinding,
+    pub restart: KeyBinding,
+    pub reload: KeyBinding,
+    pub enable: KeyBinding,
+    pub disable: KeyBinding,
+    pub refresh: KeyBinding,
+    pub view_logs: KeyBinding,
+    pub properties: KeyBinding,
+    pub filter: KeyBinding,
+    pub auto_refresh: KeyBinding,
+    pub back: KeyBinding,
+    pub cycle_theme: KeyBinding,
+    pub search: KeyBinding,
+    pub log_priority: KeyBinding,
+    pub log_since: KeyBinding,
+    pub log_boot: KeyBinding,
+    pub log_expression: KeyBinding,
+    pub save_log: KeyBinding,
+    pub older_log: KeyBinding,
+    pub wrap_lines: KeyBinding,
+    pub line_numbers: KeyBinding,
+    pub timestamps: KeyBinding,
+    pub log_top: KeyBinding,
+    pub log_bottom: KeyBinding,
+    pub copy_lines: KeyBinding,
+    pub next_match: KeyBinding,
+    pub previous_match: KeyBinding,
+    pub mark: KeyBinding,
+    pub clear_marks: KeyBinding,
+    pub path_lookup: KeyBinding,
+    pub search_unit_files: KeyBinding,
+    pub jump: KeyBinding,
+    pub top_talkers: KeyBinding,
+    pub export: KeyBinding,
+    pub toggle_dry_run: KeyBinding,
+    pub next_host: KeyBinding,
+    pub kill: KeyBinding,
+    pub freeze: KeyBinding,
+    pub error_history: KeyBinding,
+    pub failed_units: KeyBinding,
+    pub targets: KeyBinding,
+    pub session_changes: KeyBinding,
+    pub watch: KeyBinding,
+    pub pin: KeyBinding,
+    pub notifications: KeyBinding,
+    pub reset_failed: KeyBinding,
+    pub new_unit: KeyBinding,
+    pub run_transient: KeyBinding,
+    pub clone_unit: KeyBinding,
+    pub instantiate: KeyBinding,
+    pub undo: KeyBinding,
+    pub type_ahead: KeyBinding,
+    pub pick_columns: KeyBinding,
+    pub next_profile: KeyBinding,
+    pub scroll_left: KeyBinding,
+    pub scroll_right: KeyBinding,
+    pub sort: KeyBinding,
+    pub run_now: KeyBinding,
+    pub set_environment: KeyBinding,
+    pub describe: KeyBinding,
+    pub shell: KeyBinding,
+    pub edit_limits: KeyBinding,
+    pub documentation: KeyBinding,
+    pub thaw: KeyBinding,
+    pub pause_refresh: KeyBinding,
+    pub longer_refresh: KeyBinding,
+    pub shorter_refresh: KeyBinding,
+    pub command: KeyBinding,
+    pub crash_loops: KeyBinding,
+    pub snapshots: KeyBinding,
+    pub boot_performance: KeyBinding,
+    pub security_overview: KeyBinding,
+    pub slices: KeyBinding,
+    pub groups: KeyBinding,
+    pub ordered_restart: KeyBinding,
+    pub schedules: KeyBinding,
+    pub reload_diff: KeyBinding,
+    pub verify: KeyBinding,
+    pub next_section: KeyBinding,
+    pub previous_section: KeyBinding,
+    pub split_view: KeyBinding,
+    pub grow_list: KeyBinding,
+    pub shrink_list: KeyBinding,
+    pub grow_graphs: KeyBinding,
+    pub shrink_graphs: KeyBinding,
+    pub save_layout: KeyBinding,
+    pub toggle_timings: KeyBinding,
+    pub help: KeyBinding,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            up: KeyBinding::new(KeyCode::Up),
+            down: KeyBinding::new(KeyCode::Down),
+            page_up: KeyBinding::new(KeyCode::PageUp),
+            page_down: KeyBinding::new(KeyCode::PageDown),
+            next_tab: KeyBinding::new(KeyCode::Right),
+            previous_tab: KeyBinding::new(KeyCode::Left),
+            start: KeyBinding::char('s'),
+            stop: KeyBinding::char('x'),
+            restart: KeyBinding::char('r'),
+            reload: KeyBinding::char('b'),
+            enable: KeyBinding::char('e'),
+            disable: KeyBinding::char('d'),
+            refresh: KeyBinding::char('u'),
+            view_logs: KeyBinding::char('v'),
+            properties: KeyBinding::char('p'),
+            filter: KeyBinding::char('i'),
+            auto_refresh: KeyBinding::char('a'),
+            back: KeyBinding::char('q'),
+            cycle_theme: KeyBinding::char('t'),
+            search: KeyBinding::char('/'),
+            log_priority: KeyBinding::char('L'),
+            log_since: KeyBinding::char('D'),
+            log_boot: KeyBinding::char('Q'),
+            log_expression: KeyBinding::char(';'),
+            save_log: KeyBinding::char('W'),
+            older_log: KeyBinding::char('H'),
+            wrap_lines: KeyBinding {
+                code: KeyCode::Char('w'),
+                modifiers: KeyModifiers::CONTROL,
+            },
+            line_numbers: KeyBinding::char('#'),
+            timestamps: KeyBinding {
+                code: KeyCode::Char('t'),
+                modifiers: KeyModifiers::CONTROL,
+            },
+            log_top: KeyBinding::new(KeyCode::Home),
+            log_bottom: KeyBinding::new(KeyCode::End),
+            copy_lines: KeyBinding::char('Y'),
+            next_match: KeyBinding::char('n'),
+            previous_match: KeyBinding::char('N'),
+            mark: KeyBinding::char(' '),
+            clear_marks: KeyBinding::char('c'),
+            path_lookup: KeyBinding::char('w'),
+            search_unit_files: KeyBinding::char('U'),
+            jump: KeyBinding::char('g'),
+            top_talkers: KeyBinding::char('T'),
+            export: KeyBinding::char('o'),
+            toggle_dry_run: KeyBinding::char('y'),
+            next_host: KeyBinding::char('h'),
+            kill: KeyBinding::char('k'),
+            freeze: KeyBinding::char('z'),
+            error_history: KeyBinding::char('E'),
+            failed_units: KeyBinding::char('F'),
+            targets: KeyBinding::char('O'),
+            session_changes: KeyBinding::char('C'),
+            watch: KeyBinding::char('m'),
+            pin: KeyBinding::char('^'),
+            notifications: KeyBinding::char('I'),
+            reset_failed: KeyBinding::char('f'),
+            new_unit: KeyBinding::char('+'),
+            run_transient: KeyBinding::char('X'),
+            clone_unit: KeyBinding::char('*'),
+            instantiate: KeyBinding::char('@'),
+            undo: KeyBinding {
+                code: KeyCode::Char('z'),
+                modifiers: KeyModifiers::CONTROL,
+            },
+            type_ahead: KeyBinding::char('J'),
+            pick_columns: KeyBinding::char('|'),
+            next_profile: KeyBinding {
+                code: KeyCode::Char('p'),
+                modifiers: KeyModifiers::CONTROL,
+            },
+            scroll_left: KeyBinding::char('<'),
+            scroll_right: KeyBinding::char('>'),
+            sort: KeyBinding::char('S'),
+            run_now: KeyBinding::char('R'),
+            set_environment: KeyBinding::char('V'),
+            describe: KeyBinding::char('~'),
+            shell: KeyBinding::char('$'),
+            edit_limits: KeyBinding::char('M'),
+            documentation: KeyBinding::char('K'),
+            thaw: KeyBinding::char('Z'),
+            pause_refresh: KeyBinding::char('P'),
+            longer_refresh: KeyBinding::char('='),
+            shorter_refresh: KeyBinding::char('-'),
+            command: KeyBinding::char(':'),
+            crash_loops: KeyBinding::char('!'),
+            snapshots: KeyBinding::char('B'),
+            boot_performance: KeyBinding::char('A'),
+            security_overview: KeyBinding::char('%'),
+            slices: KeyBinding::char('&'),
+            groups: KeyBinding::char('.'),
+            ordered_restart: KeyBinding::char('_'),
+            schedules: KeyBinding::char(','),
+            reload_diff: KeyBinding::char('\''),
+            verify: KeyBinding {
+                code: KeyCode::Char('v'),
+                modifiers: KeyModifiers::CONTROL,
+            },
+            next_section: KeyBinding::char(']'),
+            previous_section: KeyBinding::char('['),
+            split_view: KeyBinding::char('\\'),
+            grow_list: KeyBinding {
+                code: KeyCode::Right,
+                modifiers: KeyModifiers::CONTROL,
+            },
+            shrink_list: KeyBinding {
+                code: KeyCode::Left,
+                modifiers: KeyModifiers::CONTROL,
+            },
+            grow_graphs: KeyBinding {
+                code: KeyCode::Up,
+                modifiers: KeyModifiers::CONTROL,
+            },
+            shrink_graphs: KeyBinding {
+                code: KeyCode::Down,
+                modifiers: KeyModifiers::CONTROL,
+            },
+            save_layout: KeyBinding {
+                code: KeyCode::Char('s'),
+                modifiers: KeyModifiers::CONTROL,
+            },
+            toggle_timings: KeyBinding::new(KeyCode::F(12)),
+            help: KeyBinding::char('?'),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Every action with its key, in the order of the help overlay.
+    pub fn entries(&self) -> Vec<(&'static str, &KeyBinding)> {
+        vec![
+            ("Move up", &self.up),
+            ("Move down", &self.down),
+            ("Page up", &self.page_up),
+            ("Page down", &self.page_down),
+            ("Next tab", &self.next_tab),
+            ("Previous tab", &self.previous_tab),
+            ("Go back / close", &self.back),
+            ("Start", &self.start),
+            ("Stop", &self.stop),
+            ("Restart", &self.restart),
+            ("Reload", &self.reload),
+            ("Enable", &self.enable),
+            ("Disable", &self.disable),
+            ("Freeze", &self.freeze),
+            ("Thaw", &self.thaw),
+            ("Kill", &self.kill),
+            ("Reset failed state", &self.reset_failed),
+            ("Run timer now", &self.run_now),
+            ("Set an environment variable", &self.set_environment),
+            ("Change the description", &self.describe),
+            ("Shell in the working directory", &self.shell),
+            ("Resource limits", &self.edit_limits),
+            ("Documentation", &self.documentation),
+            ("Refresh all", &self.refresh),
+            ("View logs", &self.view_logs),
+            ("Properties", &self.properties),
+            ("Filter", &self.filter),
+            ("Command line", &self.command),
+            ("Mark", &self.mark),
+            ("Clear marks", &self.clear_marks),
+            ("Sort", &self.sort),
+            ("Jump to unit", &self.jump),
+            ("What manages a path", &self.path_lookup),
+            ("Search the unit files", &self.search_unit_files),
+            ("Top talkers", &self.top_talkers),
+            ("Errors", &self.error_history),
+            ("Failed units", &self.failed_units),
+            ("Targets", &self.targets),
+            ("Crash loops", &self.crash_loops),
+            ("Session changes", &self.session_changes),
+            ("Watch unit", &self.watch),
+            ("Pin unit", &self.pin),
+            ("Notifications", &self.notifications),
+            ("Changes since the last snapshot", &self.snapshots),
+            ("Boot performance", &self.boot_performance),
+            ("Security exposure of the services", &self.security_overview),
+            ("Slices", &self.slices),
+            ("Groups", &self.groups),
+            ("Restart the marked services in dependency order", &self.ordered_restart),
+            ("Scheduled actions", &self.schedules),
+            ("Unit files compared with the loaded unit", &self.reload_diff),
+            ("Verify the unit file", &self.verify),
+            ("Next section of the unit file, next link of the status tab", &self.next_section),
+            ("Previous section of the unit file, previous link of the status tab", &self.previous_section),
+            ("Details of the selected unit beside the list", &self.split_view),
+            ("Widen the list of the split view", &self.grow_list),
+            ("Narrow the list of the split view", &self.shrink_list),
+            ("Taller usage graphs", &self.grow_graphs),
+            ("Shorter usage graphs", &self.shrink_graphs),
+            ("Save the layout as a profile of the config", &self.save_layout),
+            ("New service", &self.new_unit),
+            ("Run a command as a service", &self.run_transient),
+            ("Clone the selected unit", &self.clone_unit),
+            ("Start an instance of a template", &self.instantiate),
+            ("Undo the last enable, disable or override", &self.undo),
+            ("Jump to a unit by name", &self.type_ahead),
+            ("Columns", &self.pick_columns),
+            ("Next profile", &self.next_profile),
+            ("Scroll columns left", &self.scroll_left),
+            ("Scroll columns right", &self.scroll_right),
+            ("Export", &self.export),
+            ("Dry run", &self.toggle_dry_run),
+            ("Next host", &self.next_host),
+            ("Pause list refresh", &self.pause_refresh),
+            ("Refresh list less often", &self.longer_refresh),
+            ("Refresh list more often", &self.shorter_refresh),
+            ("Log auto-refresh", &self.auto_refresh),
+            ("Search logs", &self.search),
+            ("Next match", &self.next_match),
+            ("Previous match", &self.previous_match),
+            ("Log minimum priority", &self.log_priority),
+            ("Log time range", &self.log_since),
+            ("Log of a boot", &self.log_boot),
+            ("Filter log lines", &self.log_expression),
+            ("Save log", &self.save_log),
+            ("Older log entries", &self.older_log),
+            ("Wrap log lines", &self.wrap_lines),
+            ("Log line numbers", &self.line_numbers),
+            ("Log timestamps", &self.timestamps),
+            ("Top of the log", &self.log_top),
+            ("Bottom of the log", &self.log_bottom),
+            ("Copy log lines, the unit name or, in the details, a part of the unit", &self.copy_lines),
+            ("Theme", &self.cycle_theme),
+            ("Timings of the listing, properties, journal, filter and drawing", &self.toggle_timings),
+            ("Help", &self.help),
+        ]
+    }
+}
+
+/// Multi-key bindings of the service list. A chord starting with a single-key binding
+/// delays that binding until the next key or the timeout.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChordBindings {
+    /// Milliseconds to wait for the next key of a chord.
+    pub timeout: u64,
+    pub go_failed: KeySequence,
+    pub go_top: KeySequence,
+    pub go_bottom: KeySequence,
+    pub quick_actions: KeySequence,
+}
+
+impl Default for ChordBindings {
+    fn default() -> Self {
+        Self {
+            timeout: 1000,
+            go_failed: KeySequence(vec![KeyBinding::char('g'), KeyBinding::char('f')]),
+            go_top: KeySequence(vec![KeyBinding::char('g'), KeyBinding::char('g')]),
+            go_bottom: KeySequence(vec![KeyBinding::char('g'), KeyBinding::char('G')]),
+            quick_actions: KeySequence(vec![KeyBinding::char(' '), KeyBinding::char(' ')]),
+        }
+    }
+}
+
+impl ChordBindings {
+    pub fn sequences(&self) -> [(ChordAction, &KeySequence); 4] {
+        [
+            (ChordAction::GoFailed, &self.go_failed),
+            (ChordAction::GoTop, &self.go_top),
+            (ChordAction::GoBottom, &self.go_bottom),
+            (ChordAction::QuickActions, &self.quick_actions),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Column {
+    Name,
+    Active,
+    State,
+    Preset,
+    Load,
+    Description,
+    /// Number of units depending on the unit, recursively.
+    Impact,
+    /// Time since the main process of a service started.
+    Uptime,
+    /// Automatic restarts of a service since it was last started by hand.
+    Restarts,
+    /// Source of the unit in a merged view, e.g. "session" or "containers".
+    Source,
+    /// Sub state, e.g. "running" or "exited".
+    Sub,
+    /// Memory of the control group of a service.
+    Memory,
+    /// Answer of the health probe of the unit, blank without one.
+    Health,
+}
+
+impl Column {
+    /// Every column, in the order the column picker offers them.
+    pub const ALL: [Column; 13] = [
+        Column::Name,
+        Column::Active,
+        Column::Health,
+        Column::Sub,
+        Column::State,
+        Column::Preset,
+        Column::Load,
+        Column::Description,
+        Column::Impact,
+        Column::Uptime,
+        Column::Restarts,
+        Column::Memory,
+        Column::Source,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Column::Name => "Name",
+            Column::Active => "Active",
+            Column::State => "State",
+            Column::Preset => "Preset",
+            Column::Load => "Load",
+            Column::Description => "Description",
+            Column::Impact => "Impact",
+            Column::Uptime => "Uptime",
+            Column::Restarts => "Restarts",
+            Column::Source => "Source",
+            Column::Sub => "Sub",
+            Column::Memory => "Memory",
+            Column::Health => "Health",
+        }
+    }
+
+    pub fn constraint(&self) -> Constraint {
+        match self {
+            Column::Name => Constraint::Percentage(15),
+            Column::Active => Constraint::Length(20),
+            Column::State => Constraint::Length(10),
+            Column::Preset => Constraint::Length(12),
+            Column::Load => Constraint::Length(10),
+            Column::Description => Constraint::Min(0),
+            Column::Impact => Constraint::Length(8),
+            Column::Uptime => Constraint::Length(8),
+            Column::Restarts => Constraint::Length(9),
+            Column::Source => Constraint::Length(12),
+            Column::Sub => Constraint::Length(10),
+            Column::Memory => Constraint::Length(8),
+            Column::Health => Constraint::Length(9),
+        }
+    }
+}
+
+/// A color written as a name ("cyan", "lightred"), an ANSI index ("208") or hex ("#ffa500").
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(try_from = "String")]
+pub struct ThemeColor(pub Color);
+
+impl TryFrom<String> for ThemeColor {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Color::from_str(&value)
+            .map(ThemeColor)
+            .map_err(|_| format!("unknown color \"{}\"", value))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HostTable {
+    name: String,
+    #[serde(default)]
+    accent: Option<ThemeColor>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum HostEntry {
+    Destination(String),
+    Table(HostTable),
+}
+
+/// A machine to switch to, written as its ssh destination or as a table with the accent color
+/// of its borders and headers, e.g. `{ name = "admin@prod-1", accent = "red" }`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(from = "HostEntry")]
+pub struct HostProfile {
+    pub name: String,
+    pub accent: Option<ThemeColor>,
+}
+
+impl From<HostEntry> for HostProfile {
+    fn from(entry: HostEntry) -> Self {
+        match entry {
+            HostEntry::Destination(name) => Self { name, accent: None },
+            HostEntry::Table(table) => Self {
+                name: table.name,
+                accent: table.accent,
+            },
+        }
+    }
+}
+
+/// User-defined colors replacing the ones of the selected theme.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ColorOverrides {
+    pub text: Option<ThemeColor>,
+    pub accent: Option<ThemeColor>,
+    pub header: Option<ThemeColor>,
+    pub title: Option<ThemeColor>,
+    pub selection_fg: Option<ThemeColor>,
+    pub selection_bg: Option<ThemeColor>,
+    pub inactive_selection_bg: Option<ThemeColor>,
+    pub active: Option<ThemeColor>,
+    pub activating: Option<ThemeColor>,
+    pub failed: Option<ThemeColor>,
+    pub border: Option<ThemeColor>,
+    pub highlight_border: Option<ThemeColor>,
+    pub error: Option<ThemeColor>,
+    pub warning: Option<ThemeColor>,
+    pub muted: Option<ThemeColor>,
+}
+
+impl ColorOverrides {
+    pub fn apply(&self, theme: &mut Theme) {
+        let pairs = [
+            (self.text, &mut theme.text),
+            (self.accent, &mut theme.accent),
+            (self.header, &mut theme.header),
+            (self.title, &mut theme.title),
+            (self.selection_fg, &mut theme.selection_fg),
+            (self.selection_bg, &mut theme.selection_bg),
+            (self.inactive_selection_bg, &mut theme.inactive_selection_bg),
+            (self.active, &mut theme.active),
+            (self.activating, &mut theme.activating),
+            (self.failed, &mut theme.failed),
+            (self.border, &mut theme.border),
+            (self.highlight_border, &mut theme.highlight_border),
+            (self.error, &mut theme.error),
+            (self.warning, &mut theme.warning),
+            (self.muted, &mut theme.muted),
+        ];
+        for (color, slot) in pairs {
+            if let Some(ThemeColor(color)) = color {
+                *slot = color;
+            }
+        }
+    }
+}
+
+/// Icons put before the states of the service list, e.g. Nerd Font glyphs. Off by default,
+/// not every terminal font having them.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IconConfig {
+    pub show: bool,
+    pub active: String,
+    /// Also deactivating and reloading units.
+    pub activating: String,
+    pub failed: String,
+    pub inactive: String,
+    pub frozen: String,
+    pub enabled: String,
+    pub disabled: String,
+    pub masked: String,
+}
+
+impl Default for IconConfig {
+    fn default() -> Self {
+        Self {
+            show: false,
+            active: "●".to_string(),
+            activating: "◐".to_string(),
+            failed: "✗".to_string(),
+            inactive: "○".to_string(),
+            frozen: "❄".to_string(),
+            enabled: "✓".to_string(),
+            disabled: "·".to_string(),
+            masked: "⊘".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LockConfig {
+    /// Minutes without any key press before the screen locks; 0 disables the lock.
+    pub idle_minutes: u64,
+    /// When empty, the screen is unlocked with `unlock_key` instead of a passphrase.
+    pub passphrase: String,
+    pub unlock_key: KeyBinding,
+}
+
+impl Default for LockConfig {
+    fn default() -> Self {
+        Self {
+            idle_minutes: 0,
+            passphrase: String::new(),
+            unlock_key: KeyBinding {
+                code: KeyCode::Char('u'),
+                modifiers: KeyModifiers::CONTROL,
+            },
+        }
+    }
+}
+
+impl LockConfig {
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        (self.idle_minutes > 0).then(|| Duration::from_secs(self.idle_minutes.saturating_mul(60)))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TopTalkersConfig {
+    /// Periodically rank units by journal volume in the background.
+    pub enabled: bool,
+    /// Seconds between two background checks.
+    pub interval: u64,
+    /// Entries per hour above which a unit is flagged in the service list.
+    pub threshold: u64,
+}
+
+impl Default for TopTalkersConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: 300,
+            threshold: 1000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MaintenanceWindowEntry {
+    #[serde(default)]
+    days: Vec<String>,
+    start: String,
+    end: String,
+}
+
+/// A weekly time range, e.g. saturdays from 02:00 to 04:00. Ranges ending before they start
+/// run past midnight; no days means every day.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "MaintenanceWindowEntry")]
+pub struct MaintenanceWindow {
+    days: Vec<Weekday>,
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl TryFrom<MaintenanceWindowEntry> for MaintenanceWindow {
+    type Error = String;
+
+    fn try_from(entry: MaintenanceWindowEntry) -> Result<Self, Self::Error> {
+        let days = entry
+            .days
+            .iter()
+            .map(|day| Weekday::from_str(day).map_err(|_| format!("unknown day \"{}\"", day)))
+            .collect::<Result<_, _>>()?;
+        let time = |value: &str| {
+            NaiveTime::parse_from_str(value, "%H:%M")
+                .map_err(|_| format!("invalid time \"{}\", expected HH:MM", value))
+        };
+        Ok(Self {
+            days,
+            start: time(&entry.start)?,
+            end: time(&entry.end)?,
+        })
+    }
+}
+
+impl MaintenanceWindow {
+    pub fn contains(&self, at: NaiveDateTime) -> bool {
+        let runs_on = |day: Weekday| self.days.is_empty() || self.days.contains(&day);
+        let time = at.time();
+        if self.start <= self.end {
+            runs_on(at.weekday()) && self.start <= time && time < self.end
+        } else {
+            (runs_on(at.weekday()) && time >= self.start) || (runs_on(at.weekday().pred()) && time < self.end)
+        }
+    }
+}
+
+/// Answers of the first-run setup, written as the first config file.
+#[derive(Debug, Clone, Serialize)]
+pub struct InitialSettings {
+    pub theme: String,
+    pub keymap: Keymap,
+    pub session: bool,
+    pub dry_run: bool,
+    pub read_only: bool,
+}
+
+/// A layout of the service list, e.g. "web" showing `nginx|php|redis` sorted by state.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    pub name: String,
+    /// Filter of the list, empty for every unit.
+    pub filter: String,
+    /// Column sorted by, the order of the service manager when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<Column>,
+    /// Columns shown, the `columns` setting when empty.
+    pub columns: Vec<Column>,
+    /// Whether the details are shown beside the list, the `split_view` setting when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub split_view: Option<bool>,
+    /// Percent of the width the list takes in the split view, the `split_ratio` setting when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub split_ratio: Option<u16>,
+    /// Rows of the usage graphs of the status tab, the `graphs_height` setting when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub graphs_height: Option<u16>,
+}
+
+impl Config {
+    pub fn profile(&self, name: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    pub fn call_limits(&self) -> CallLimits {
+        CallLimits::new((self.call_timeout > 0).then(|| Duration::from_secs(self.call_timeout)))
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MaintenanceConfig {
+    /// Units for which stop, restart and disable must be confirmed outside the windows.
+    pub critical_units: Vec<String>,
+    pub windows: Vec<MaintenanceWindow>,
+}
+
+/// Whether a unit name from the config designates `unit`, "nginx" standing for "nginx.service".
+pub fn names_unit(name: &str, unit: &str) -> bool {
+    name == unit || (!name.contains('.') && format!("{}.service", name) == unit)
+}
+
+impl MaintenanceConfig {
+    pub fn is_critical(&self, unit: &str) -> bool {
+        self.critical_units.iter().any(|critical| names_unit(critical, unit))
+    }
+
+    pub fn in_window(&self, at: NaiveDateTime) -> bool {
+        self.windows.iter().any(|window| window.contains(at))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AlertsConfig {
+    /// Periodically check for units entering the failed state.
+    pub enabled: bool,
+    /// Seconds between two checks.
+    pub interval: u64,
+    /// Units to watch, every unit when empty.
+    pub units: Vec<String>,
+    /// Ring the terminal bell, which tmux and most terminals flag on the pane or tab.
+    pub bell: bool,
+    /// Show a desktop notification, through org.freedesktop.Notifications.
+    pub desktop: bool,
+    /// Shell command run for each failure, with the unit name in `$UNIT`.
+    pub command: String,
+    /// Only alert while the terminal is not focused, for terminals reporting focus changes.
+    pub only_unfocused: bool,
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: 10,
+            units: Vec::new(),
+            bell: true,
+            desktop: true,
+            command: String::new(),
+            only_unfocused: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CrashLoopConfig {
+    /// A unit restarted more than this many times within `minutes` is crash-looping; 0 disables it.
+    pub restarts: u32,
+    pub minutes: u64,
+}
+
+impl Default for CrashLoopConfig {
+    fn default() -> Self {
+        Self {
+            restarts: 5,
+            minutes: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HealthConfig {
+    /// Seconds between two rounds of probes.
+    pub interval: u64,
+    /// Seconds a probe may take before the unit counts as down.
+    pub timeout: u64,
+    pub probes: Vec<HealthProbe>,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            interval: 30,
+            timeout: 5,
+            probes: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UptimeConfig {
+    /// Units whose observed availability is tracked across sessions, none when empty.
+    pub units: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UsageConfig {
+    /// Seconds between two samples of CPUUsageNSec and MemoryCurrent.
+    pub interval: u64,
+    /// Minutes of samples shown in the graphs of the details.
+    pub minutes: u64,
+    /// Units sampled in the background, besides the one shown in the details.
+    pub units: Vec<String>,
+}
+
+impl Default for UsageConfig {
+    fn default() -> Self {
+        Self {
+            interval: 5,
+            minutes: 10,
+            units: Vec::new(),
+        }
+    }
+}
+
+impl AlertsConfig {
+    pub fn is_watched(&self, unit: &str) -> bool {
+        self.units.is_empty() || self.units.iter().any(|watched| names_unit(watched, unit))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExportConfig {
+    pub format: ExportFormat,
+    /// Directory the list view writes its exports to; relative paths start from the working directory.
+    pub directory: PathBuf,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            format: ExportFormat::Json,
+            directory: PathBuf::from("."),
+        }
+    }
+}
+
+/// Set of bindings the `[keybindings]` and `[chords]` tables of the config file start from.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Keymap {
+    Default,
+    /// j/k to move, h/l to switch tabs, / to filter, gg/G to jump to the top or bottom.
+    Vim,
+}
+
+impl Keymap {
+    fn preset(&self) -> Option<&'static str> {
+        match self {
+            Keymap::Default => None,
+            Keymap::Vim => Some(VIM_KEYMAP),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub keymap: Keymap,
+    pub keybindings: KeyBindings,
+    pub chords: ChordBindings,
+    /// Filter applied to the service list at startup.
+    pub default_filter: String,
+    /// Auto-refresh interval of the log view and the properties tab, in milliseconds.
+    pub refresh_interval: u64,
+    /// Auto-refresh interval of the service list, in milliseconds; 0 starts it paused.
+    pub list_refresh_interval: u64,
+    /// One of "dark", "light" or "high-contrast".
+    pub theme: String,
+    /// Language of the UI: "en", "pt", or "auto" to follow the locale of the environment.
+    pub language: String,
+    /// The language the `language` setting resolves to.
+    #[serde(skip)]
+    pub locale: Locale,
+    pub colors: ColorOverrides,
+    pub icons: IconConfig,
+    /// Unit types listed in the table, without the leading dot (e.g. "service", "timer").
+    pub unit_types: Vec<String>,
+    pub columns: Vec<Column>,
+    /// Widths of the columns in characters, replacing their defaults, e.g. { name = 30 }.
+    pub column_widths: HashMap<Column, u16>,
+    /// Machines that can be switched to besides the local one, by ssh destination (e.g. "admin@web-1").
+    pub hosts: Vec<HostProfile>,
+    /// Init system to manage: "auto", "systemd", "openrc" or "containers".
+    pub backend: Backend,
+    /// Repositories shown together in one table, e.g. ["system", "session", "containers",
+    /// "admin@web-1"]; empty to show the backend alone.
+    pub sources: Vec<String>,
+    /// Render every screen as plain, label-prefixed lines instead of boxed layouts, for screen readers.
+    pub accessible: bool,
+    /// Draw without colors, the selection in reverse video; also set by the NO_COLOR variable.
+    pub no_color: bool,
+    /// Draw only ASCII characters: borders, arrows, graphs and state icons included.
+    pub ascii: bool,
+    /// Describe the selected unit in plain words on a line of its own, e.g. for screen readers.
+    pub announce: bool,
+    /// Start with the list and the details of the selected unit side by side, on wide terminals.
+    pub split_view: bool,
+    /// Percent of the width the list takes in the split view, from 20 to 80.
+    pub split_ratio: u16,
+    /// Rows of the CPU and memory graphs of the status tab, from 5 to 15.
+    pub graphs_height: u16,
+    /// Start on the session services tab instead of the system one, when no tab was left open.
+    pub session: bool,
+    /// File the duration of every listing, properties and journal call, filter and drawing is
+    /// appended to, to diagnose slow hosts.
+    pub trace: Option<PathBuf>,
+    /// Start with the dry-run mode on: actions show what they would do and ask before executing.
+    pub dry_run: bool,
+    /// Refuse every action changing units or their files, e.g. to explore production hosts safely.
+    pub read_only: bool,
+    /// Actions allowed or denied per unit glob, e.g. never stopping sshd.service.
+    pub policy: Vec<PolicyRule>,
+    /// Commands and webhooks run when units fail or are acted upon, e.g. a Slack alert.
+    pub hooks: Vec<Hook>,
+    /// Named filter, sort and column layouts of the service list, switched with next_profile.
+    pub profiles: Vec<Profile>,
+    /// Units managed together in the groups view, e.g. a web stack.
+    pub groups: Vec<ServiceGroup>,
+    /// Also group the other units sharing the start of their names, e.g. "systemd*".
+    pub derive_groups: bool,
+    /// Seconds an error or info notification stays on screen.
+    pub toast_duration: u64,
+    /// Seconds listing the units or reading the journal may take before failing; 0 waits as
+    /// long as it takes. Esc cancels them sooner.
+    pub call_timeout: u64,
+    pub lock: LockConfig,
+    pub top_talkers: TopTalkersConfig,
+    pub export: ExportConfig,
+    pub maintenance: MaintenanceConfig,
+    pub alerts: AlertsConfig,
+    pub uptime: UptimeConfig,
+    pub usage: UsageConfig,
+    pub crash_loop: CrashLoopConfig,
+    pub health: HealthConfig,
+    /// Unit templates of the templates directory, offered after the built-in ones.
+    #[serde(skip)]
+    pub templates: Vec<UnitTemplate>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            keymap: Keymap::Default,
+            keybindings: KeyBindings::default(),
+            chords: ChordBindings::default(),
+            default_filter: String::new(),
+            refresh_interval: 1000,
+            list_refresh_interval: 5000,
+            theme: "dark".to_string(),
+            language: "auto".to_string(),
+            locale: Locale::from_setting("auto").unwrap_or_default(),
+            colors: ColorOverrides::default(),
+            icons: IconConfig::default(),
+            unit_types: vec!["service".to_string()],
+            columns: vec![
+                Column::Name,
+                Column::Active,
+                Column::State,
+                Column::Preset,
+                Column::Load,
+                Column::Description,
+            ],
+            column_widths: HashMap::new(),
+            hosts: Vec::new(),
+            backend: Backend::Auto,
+            sources: Vec::new(),
+            accessible: false,
+            no_color: false,
+            ascii: false,
+            announce: false,
+            split_view: false,
+            split_ratio: 50,
+            graphs_height: 7,
+            session: false,
+            trace: None,
+            dry_run: false,
+            read_only: false,
+            policy: Vec::new(),
+            hooks: Vec::new(),
+            profiles: Vec::new(),
+            groups: Vec::new(),
+            derive_groups: true,
+            toast_duration: 5,
+            call_timeout: 30,
+            lock: LockConfig::default(),
+            top_talkers: TopTalkersConfig::default(),
+            export: ExportConfig::default(),
+            maintenance: MaintenanceConfig::default(),
+            alerts: AlertsConfig::default(),
+            uptime: UptimeConfig::default(),
+            usage: UsageConfig::default(),
+            crash_loop: CrashLoopConfig::default(),
+            health: HealthConfig::default(),
+            templates: Vec::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Width of the column in the service list, the configured one or its default.
+    pub fn column_constraint(&self, column: &Column) -> Constraint {
+        self.column_widths
+            .get(column)
+            .map_or_else(|| column.constraint(), |width| Constraint::Length(*width))
+    }
+
+    /// Loads the configuration file, falling back to the defaults when it does not exist.
+    pub fn load() -> Result<Self, Box<dyn Error>> {
+        let Some(path) = Self::path() else {
+            return Ok(Self::default());
+        };
+
+        let templates = Self::load_templates(&path.with_file_name(TEMPLATES_DIR))?;
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Self {
+                    templates,
+                    ..Self::default()
+                });
+            }
+            Err(e) => return Err(format!("{}: {}", path.display(), e).into()),
+        };
+
+        let mut config: Config = Self::parse(&content).map_err(|e| format!("{}: {}", path.display(), e))?;
+        config.templates = templates;
+
+        if Theme::from_name(&config.theme).is_none() {
+            return Err(format!(
+                "{}: unknown theme \"{}\", expected one of {}",
+                path.display(),
+                config.theme,
+                THEME_NAMES.join(", ")
+            )
+            .into());
+        }
+        config.locale = Locale::from_setting(&config.language).ok_or_else(|| {
+            format!(
+                "{}: unknown language \"{}\", expected auto or one of {}",
+                path.display(),
+                config.language,
+                LANGUAGES.join(", ")
+            )
+        })?;
+        if config.unit_types.is_empty() {
+            config.unit_types = Self::default().unit_types;
+        }
+        if config.columns.is_empty() {
+            config.columns = Self::default().columns;
+        }
+        for probe in &config.health.probes {
+            probe.check().map_err(|e| format!("{}: {}", path.display(), e))?;
+        }
+        for hook in &config.hooks {
+            hook.check().map_err(|e| format!("{}: {}", path.display(), e))?;
+        }
+        // Probes show up next to the state, unless the columns place them elsewhere.
+        if !config.health.probes.is_empty() && !config.columns.contains(&Column::Health) {
+            let position = config.columns.iter().position(|column| *column == Column::Active).map_or(0, |index| index + 1);
+            config.columns.insert(position, Column::Health);
+        }
+
+        Ok(config)
+    }
+
+    /// Deserializes the config file, with the bindings of its keymap under the ones it sets itself.
+    fn parse(content: &str) -> Result<Self, Box<dyn Error>> {
+        let mut table: toml::Table = toml::from_str(content)?;
+        let keymap = match table.get("keymap") {
+            Some(keymap) => Keymap::deserialize(keymap.clone())?,
+            None => Keymap::Default,
+        };
+        let Some(preset) = keymap.preset() else {
+            return Ok(toml::from_str(content)?);
+        };
+
+        let preset: toml::Table = toml::from_str(preset)?;
+        for (section, bindings) in preset {
+            let toml::Value::Table(bindings) = bindings else {
+                continue;
+            };
+            let entry = table
+                .entry(section.clone())
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            let toml::Value::Table(user) = entry else {
+                return Err(format!("{} must be a table", section).into());
+            };
+            for (action, key) in bindings {
+                user.entry(action).or_insert(key);
+            }
+        }
+        Ok(table.try_into()?)
+    }
+
+    /// Reads every `.toml` file of the templates directory, named after the file, in name order.
+    fn load_templates(directory: &Path) -> Result<Vec<UnitTemplate>, Box<dyn Error>> {
+        let entries = match fs::read_dir(directory) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("{}: {}", directory.display(), e).into()),
+        };
+
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|extension| extension == "toml"))
+            .collect();
+        paths.sort();
+
+        paths
+            .into_iter()
+            .map(|path| {
+                let content = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+                let mut template: UnitTemplate =
+                    toml::from_str(&content).map_err(|e| format!("{}: {}", path.display(), e))?;
+                template.name = path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                template.check().map_err(|e| format!("{}: {}", path.display(), e))?;
+                Ok(template)
+            })
+            .collect()
+    }
+
+    /// Appends the profile to the config file, creating it if needed, and returns its path. The
+    /// rest of the file is left as it is, comments included.
+    pub fn append_profile(profile: &Profile) -> Result<PathBuf, Box<dyn Error>> {
+        #[derive(Serialize)]
+        struct Profiles<'a> {
+            profiles: [&'a Profile; 1],
+        }
+
+        let path = Self::path().ok_or("Neither XDG_CONFIG_HOME nor HOME is set, there is no config file")?;
+        let mut content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("{}: {}", path.display(), e).into()),
+        };
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        if !content.is_empty() {
+            content.push('\n');
+        }
+        content.push_str(&toml::to_string(&Profiles { profiles: [profile] })?);
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(|e| format!("{}: {}", directory.display(), e))?;
+        }
+        fs::write(&path, content).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(path)
+    }
+
+    /// Creates the config file from the answers of the first-run setup; an existing file is kept.
+    pub fn write_initial(settings: &InitialSettings) -> Result<PathBuf, Box<dyn Error>> {
+        let path = Self::path().ok_or("Neither XDG_CONFIG_HOME nor HOME is set, there is no config file")?;
+        if let Some(directory) = path.parent() {
+            fs::create_dir_all(directory).map_err(|e| format!("{}: {}", directory.display(), e))?;
+        }
+        let content = format!(
+            "# Written by the first-run setup; every other setting is described in the README.\n{}",
+            toml::to_string(settings)?
+        );
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        file.write_all(content.as_bytes()).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(path)
+    }
+
+    /// Whether the TUI runs for the first time here: there is a place for a config file but none yet.
+    pub fn is_first_run() -> bool {
+        Self::path().is_some_and(|path| !path.exists())
+    }
+
+    /// `$XDG_CONFIG_HOME/services-manager-tui/config.toml`, or `~/.config/...` when unset.
+    pub fn path() -> Option<PathBuf> {
+        let base = std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+        Some(base.join(CONFIG_DIR).join(CONFIG_FILE))
+    }
+}
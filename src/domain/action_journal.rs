@@ -0,0 +1,109 @@
+/// Actions kept for undoing, the oldest dropped past it.
+const MAX_ACTIONS: usize = 50;
+
+/// One change of the unit files, as EnableUnitFiles and DisableUnitFiles report it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnitFileChange {
+    /// "symlink" or "unlink".
+    pub kind: String,
+    /// The symlink created or removed, e.g. "/etc/systemd/system/multi-user.target.wants/nginx.service".
+    pub path: String,
+    /// What a created symlink points to, empty for removed ones.
+    pub source: String,
+}
+
+impl UnitFileChange {
+    pub fn is_symlink(&self) -> bool {
+        self.kind == "symlink"
+    }
+
+    /// What undoing it does, e.g. "remove /etc/.../nginx.service".
+    pub fn describe_undo(&self) -> String {
+        if self.is_symlink() { format!("remove {}", self.path) } else { format!("link {} again", self.path) }
+    }
+}
+
+/// A change of the session that can be undone.
+#[derive(Clone, Debug)]
+pub enum UndoableAction {
+    /// Enabling the unit, with the links it created.
+    Enabled { unit: String, changes: Vec<UnitFileChange> },
+    /// Disabling the unit, with the links it removed.
+    Disabled { unit: String, changes: Vec<UnitFileChange> },
+    /// A drop-in written for the unit, with the content it replaced, `None` for a new file.
+    DropIn {
+        unit: String,
+        path: String,
+        previous: Option<String>,
+    },
+}
+
+impl UndoableAction {
+    pub fn unit(&self) -> &str {
+        match self {
+            UndoableAction::Enabled { unit, .. }
+            | UndoableAction::Disabled { unit, .. }
+            | UndoableAction::DropIn { unit, .. } => unit,
+        }
+    }
+
+    /// e.g. "enable of nginx.service" or "override /etc/.../environment-PORT.conf".
+    pub fn description(&self) -> String {
+        match self {
+            UndoableAction::Enabled { unit, .. } => format!("enable of {}", unit),
+            UndoableAction::Disabled { unit, .. } => format!("disable of {}", unit),
+            UndoableAction::DropIn { path, .. } => format!("override {}", path),
+        }
+    }
+
+    /// What undoing it does, one step a line.
+    pub fn undo_steps(&self) -> Vec<String> {
+        match self {
+            UndoableAction::Enabled { unit, changes } | UndoableAction::Disabled { unit, changes }
+                if changes.is_empty() =>
+            {
+                let opposite = if matches!(self, UndoableAction::Enabled { .. }) { "disable" } else { "enable" };
+                vec![format!("{} {}", opposite, unit)]
+            }
+            UndoableAction::Enabled { changes, .. } | UndoableAction::Disabled { changes, .. } => {
+                changes.iter().map(UnitFileChange::describe_undo).collect()
+            }
+            UndoableAction::DropIn { path, previous: None, .. } => vec![format!("remove {}", path)],
+            UndoableAction::DropIn { path, previous: Some(_), .. } => {
+                vec![format!("write back the previous content of {}", path)]
+            }
+        }
+    }
+}
+
+/// The undoable actions of the session, newest last.
+#[derive(Default)]
+pub struct ActionJournal {
+    actions: Vec<UndoableAction>,
+}
+
+impl ActionJournal {
+    pub fn push(&mut self, action: UndoableAction) {
+        self.actions.push(action);
+        if self.actions.len() > MAX_ACTIONS {
+            self.actions.remove(0);
+        }
+    }
+
+    pub fn last(&self) -> Option<&UndoableAction> {
+        self.actions.last()
+    }
+
+    pub fn pop(&mut self) -> Option<UndoableAction> {
+        self.actions.pop()
+    }
+
+    /// Drops the enables and disables of the unit, e.g. once reverted another way.
+    pub fn forget_enablement(&mut self, unit: &str) {
+        self.actions.retain(|action| matches!(action, UndoableAction::DropIn { .. }) || action.unit() != unit);
+    }
+
+    pub fn clear(&mut self) {
+        self.actions.clear();
+    }
+}
@@ -0,0 +1,90 @@
+use super::action_policy::PolicyAction;
+
+/// Actions that change the state of a unit.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UnitAction {
+    Start,
+    Stop,
+    Restart,
+    Reload,
+    ReloadOrRestart,
+    Enable,
+    Disable,
+    Freeze,
+    Thaw,
+}
+
+impl UnitAction {
+    /// Stopping or restarting a unit carries over to the active units bound to it
+    /// (BindsTo=, PartOf= and Requires= on their side), as does reload-or-restart when it
+    /// falls back to a restart.
+    pub fn propagates(&self) -> bool {
+        matches!(self, UnitAction::Stop | UnitAction::Restart | UnitAction::ReloadOrRestart)
+    }
+
+    /// The action of the policy it is checked as, a reload-or-restart counting as a restart.
+    pub fn policy_action(&self) -> PolicyAction {
+        match self {
+            UnitAction::Start => PolicyAction::Start,
+            UnitAction::Stop => PolicyAction::Stop,
+            UnitAction::Restart | UnitAction::ReloadOrRestart => PolicyAction::Restart,
+            UnitAction::Reload => PolicyAction::Reload,
+            UnitAction::Enable => PolicyAction::Enable,
+            UnitAction::Disable => PolicyAction::Disable,
+            UnitAction::Freeze => PolicyAction::Freeze,
+            UnitAction::Thaw => PolicyAction::Thaw,
+        }
+    }
+
+    /// What a unit shows while the action waits in the queue or runs, e.g. "restarting…".
+    pub fn in_progress(&self) -> &'static str {
+        match self {
+            UnitAction::Start => "starting…",
+            UnitAction::Stop => "stopping…",
+            UnitAction::Restart => "restarting…",
+            UnitAction::Reload => "reloading…",
+            UnitAction::ReloadOrRestart => "reloading or restarting…",
+            UnitAction::Enable => "enabling…",
+            UnitAction::Disable => "disabling…",
+            UnitAction::Freeze => "freezing…",
+            UnitAction::Thaw => "thawing…",
+        }
+    }
+}
+
+/// What an action would do, without doing it: the D-Bus call and its known side effects.
+#[derive(Clone, Debug)]
+pub struct ActionPlan {
+    method: String,
+    arguments: String,
+    propagated_units: Vec<String>,
+    side_effects: Vec<String>,
+}
+
+impl ActionPlan {
+    pub fn new(method: String, arguments: String, propagated_units: Vec<String>, side_effects: Vec<String>) -> Self {
+        Self {
+            method,
+            arguments,
+            propagated_units,
+            side_effects,
+        }
+    }
+
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    pub fn arguments(&self) -> &str {
+        &self.arguments
+    }
+
+    /// Active units the action is carried over to, see `UnitAction::propagates`.
+    pub fn propagated_units(&self) -> &[String] {
+        &self.propagated_units
+    }
+
+    pub fn side_effects(&self) -> &[String] {
+        &self.side_effects
+    }
+}
@@ -0,0 +1,127 @@
+use serde::Deserialize;
+
+/// Actions a policy rule allows or denies on units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PolicyAction {
+    Start,
+    Stop,
+    /// Also reload-or-restart, which may fall back to a restart.
+    Restart,
+    Reload,
+    Enable,
+    Disable,
+    Freeze,
+    Thaw,
+    Kill,
+    ResetFailed,
+    /// Unit files and drop-ins: editing, cloning, environment and resource limits.
+    Edit,
+    /// Isolating a target or making it the default one.
+    Isolate,
+}
+
+impl PolicyAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PolicyAction::Start => "start",
+            PolicyAction::Stop => "stop",
+            PolicyAction::Restart => "restart",
+            PolicyAction::Reload => "reload",
+            PolicyAction::Enable => "enable",
+            PolicyAction::Disable => "disable",
+            PolicyAction::Freeze => "freeze",
+            PolicyAction::Thaw => "thaw",
+            PolicyAction::Kill => "kill",
+            PolicyAction::ResetFailed => "reset-failed",
+            PolicyAction::Edit => "edit",
+            PolicyAction::Isolate => "isolate",
+        }
+    }
+
+    /// Actions taking a running unit down, which critical units only allow in the maintenance
+    /// windows unless confirmed. Isolating a target stops the units it does not need, and
+    /// rebooting or powering off isolates reboot.target or poweroff.target.
+    pub fn is_disruptive(&self) -> bool {
+        matches!(
+            self,
+            PolicyAction::Stop | PolicyAction::Restart | PolicyAction::Disable | PolicyAction::Freeze | PolicyAction::Isolate
+        )
+    }
+}
+
+/// Actions allowed or denied on the units matching a glob, e.g. "myapp-*.service".
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PolicyRule {
+    /// Glob of unit names, `*` and `?` being wildcards; without a type, ".service" is implied.
+    pub units: String,
+    /// When not empty, the only actions allowed on the units.
+    pub allow: Vec<PolicyAction>,
+    /// Actions refused on the units, whatever other rules allow.
+    pub deny: Vec<PolicyAction>,
+}
+
+impl PolicyRule {
+    pub fn covers(&self, unit: &str) -> bool {
+        let pattern = if self.units.contains('.') {
+            self.units.clone()
+        } else {
+            format!("{}.service", self.units)
+        };
+        glob_matches(pattern.as_bytes(), unit.as_bytes())
+    }
+}
+
+/// Whether `name` matches `pattern`, `*` standing for any run of characters and `?` for one.
+pub fn glob_matches(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => (0..=name.len()).any(|skip| glob_matches(rest, &name[skip..])),
+        Some((b'?', rest)) => !name.is_empty() && glob_matches(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && glob_matches(rest, &name[1..]),
+    }
+}
+
+/// The rules of the configuration, checked before each action reaches the service manager.
+#[derive(Debug, Clone, Default)]
+pub struct ActionPolicy {
+    rules: Vec<PolicyRule>,
+}
+
+impl ActionPolicy {
+    pub fn new(rules: Vec<PolicyRule>) -> Self {
+        Self { rules }
+    }
+
+    /// Refuses the action when a rule covering the unit denies it, or when rules covering the
+    /// unit have allow lists and none of them allows it.
+    pub fn check(&self, action: PolicyAction, unit: &str) -> Result<(), String> {
+        let rules: Vec<&PolicyRule> = self.rules.iter().filter(|rule| rule.covers(unit)).collect();
+        if let Some(rule) = rules.iter().find(|rule| rule.deny.contains(&action)) {
+            return Err(format!(
+                "Blocked by policy: {} is denied on {} (rule \"{}\")",
+                action.label(),
+                unit,
+                rule.units
+            ));
+        }
+        let allow_lists: Vec<&Vec<PolicyAction>> =
+            rules.iter().map(|rule| &rule.allow).filter(|allow| !allow.is_empty()).collect();
+        if !allow_lists.is_empty() && !allow_lists.iter().any(|allow| allow.contains(&action)) {
+            let mut allowed: Vec<&str> = Vec::new();
+            for label in allow_lists.iter().flat_map(|allow| allow.iter().map(PolicyAction::label)) {
+                if !allowed.contains(&label) {
+                    allowed.push(label);
+                }
+            }
+            return Err(format!(
+                "Blocked by policy: {} is not allowed on {}, only {}",
+                action.label(),
+                unit,
+                allowed.join(", ")
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+/// Time a unit took to activate during the boot, as listed by `systemd-analyze blame`.
+#[derive(Debug, Clone)]
+pub struct UnitActivation {
+    unit: String,
+    activation_ms: u64,
+}
+
+impl UnitActivation {
+    pub fn new(unit: String, activation_ms: u64) -> Self {
+        Self { unit, activation_ms }
+    }
+
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    pub fn activation_ms(&self) -> u64 {
+        self.activation_ms
+    }
+}
+
+/// A unit of the critical chain, as printed by `systemd-analyze critical-chain`.
+#[derive(Debug, Clone)]
+pub struct ChainLink {
+    /// 0 for the unit the chain is of, then one more per unit it waited for.
+    pub depth: usize,
+    pub unit: String,
+    /// When the unit became active, since the start of the manager.
+    pub active_at_ms: Option<u64>,
+    /// How long the unit took to start.
+    pub took_ms: Option<u64>,
+}
+
+/// Milliseconds of a systemd time span, e.g. "1min 2.345s", "812ms" or "1h 3min".
+pub fn parse_timespan(text: &str) -> Option<u64> {
+    const UNITS: [(&str, f64); 8] = [
+        ("ms", 1.0),
+        ("us", 0.001),
+        ("µs", 0.001),
+        ("min", 60_000.0),
+        ("s", 1000.0),
+        ("h", 3_600_000.0),
+        ("d", 86_400_000.0),
+        ("w", 604_800_000.0),
+    ];
+    let mut total = 0.0;
+    let mut parsed = false;
+    for part in text.split_whitespace() {
+        let (number, factor) = UNITS
+            .iter()
+            .find_map(|(suffix, factor)| Some((part.strip_suffix(suffix)?, *factor)))?;
+        total += number.parse::<f64>().ok()? * factor;
+        parsed = true;
+    }
+    parsed.then_some(total.round() as u64)
+}
+
+/// e.g. "812ms", "2.345s" or "1min 2.345s", like systemd.
+pub fn format_timespan(ms: u64) -> String {
+    match ms {
+        ms if ms < 1000 => format!("{}ms", ms),
+        ms if ms < 60_000 => format!("{:.3}s", ms as f64 / 1000.0),
+        ms => format!("{}min {:.3}s", ms / 60_000, (ms % 60_000) as f64 / 1000.0),
+    }
+}
+
+/// Units of the output of `systemd-analyze blame`, slowest first.
+pub fn parse_blame(output: &str) -> Vec<UnitActivation> {
+    output
+        .lines()
+        .filter_map(|line| {
+            // The time span has spaces in it, unit names never do.
+            let (time, unit) = line.trim().rsplit_once(char::is_whitespace)?;
+            Some(UnitActivation::new(unit.to_string(), parse_timespan(time)?))
+        })
+        .collect()
+}
+
+/// Units of the output of `systemd-analyze critical-chain`, without its explanation header.
+pub fn parse_critical_chain(output: &str) -> Vec<ChainLink> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let start = line.find(|c: char| !matches!(c, ' ' | '│' | '├' | '└' | '─'))?;
+            let text = &line[start..];
+            let (unit, timing) = text.split_once(' ').unwrap_or((text, ""));
+            if !unit.contains('.') {
+                return None;
+            }
+            let (active_at, took) = match timing.split_once('+') {
+                Some((active_at, took)) => (active_at, Some(took)),
+                None => (timing, None),
+            };
+            Some(ChainLink {
+                depth: line[..start].chars().count() / 2,
+                unit: unit.to_string(),
+                active_at_ms: active_at.trim().strip_prefix('@').and_then(parse_timespan),
+                took_ms: took.and_then(parse_timespan),
+            })
+        })
+        .collect()
+}
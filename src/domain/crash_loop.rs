@@ -0,0 +1,153 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use super::humanize::format_span;
+use super::service::Service;
+
+/// Delays below this leave a failing dependency (network, database...) no time to come back.
+const SHORT_RESTART_USEC: u64 = 1_000_000;
+
+/// Restart settings of a service with the number of automatic restarts since it was started.
+#[derive(Debug, Clone)]
+pub struct RestartSettings {
+    restart: String,
+    restart_usec: u64,
+    n_restarts: u32,
+    start_limit_interval_usec: u64,
+    start_limit_burst: u32,
+}
+
+impl RestartSettings {
+    pub fn new(
+        restart: String,
+        restart_usec: u64,
+        n_restarts: u32,
+        start_limit_interval_usec: u64,
+        start_limit_burst: u32,
+    ) -> Self {
+        Self {
+            restart,
+            restart_usec,
+            n_restarts,
+            start_limit_interval_usec,
+            start_limit_burst,
+        }
+    }
+
+    pub fn n_restarts(&self) -> u32 {
+        self.n_restarts
+    }
+
+    /// The settings as written in the unit file, e.g. ("RestartSec", "100ms").
+    pub fn lines(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("Restart", self.restart.clone()),
+            ("RestartSec", format_span(self.restart_usec)),
+            ("StartLimitIntervalSec", format_span(self.start_limit_interval_usec)),
+            ("StartLimitBurst", self.start_limit_burst.to_string()),
+            ("NRestarts", self.n_restarts.to_string()),
+        ]
+    }
+
+    fn rate_limited(&self) -> bool {
+        self.start_limit_interval_usec > 0 && self.start_limit_burst > 0
+    }
+
+    /// What to look at or change, from the settings of the unit.
+    pub fn suggestions(&self) -> Vec<String> {
+        let mut suggestions = vec!["Read the logs for why the process exits: restarting does not fix the cause.".to_string()];
+        if self.restart == "always" {
+            suggestions.push(
+                "Restart=always also restarts after a clean exit; consider Restart=on-failure.".to_string(),
+            );
+        }
+        if self.restart_usec < SHORT_RESTART_USEC {
+            suggestions.push(format!(
+                "RestartSec is {}: consider RestartSec=5s, so that what the unit waits for has time to come back.",
+                format_span(self.restart_usec)
+            ));
+        }
+        if self.rate_limited() {
+            suggestions.push(format!(
+                "After {} starts within {} systemd stops restarting it and marks it failed; tune StartLimitIntervalSec and StartLimitBurst to give up sooner or later.",
+                self.start_limit_burst,
+                format_span(self.start_limit_interval_usec)
+            ));
+        } else {
+            suggestions.push(
+                "The start rate limit is off, so it restarts forever; consider StartLimitIntervalSec and StartLimitBurst.".to_string(),
+            );
+        }
+        suggestions
+    }
+}
+
+/// Counts the automatic restarts of units over a sliding window, from their NRestarts counter
+/// sampled at each refresh.
+pub struct CrashLoopDetector {
+    max_restarts: u32,
+    window: Duration,
+    /// Time and NRestarts of the samples of each unit, from the last one before the window.
+    samples: HashMap<String, VecDeque<(Instant, u32)>>,
+}
+
+impl CrashLoopDetector {
+    pub fn new(max_restarts: u32, window: Duration) -> Self {
+        Self {
+            max_restarts,
+            window,
+            samples: HashMap::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.max_restarts > 0
+    }
+
+    /// Whether the counter of the unit should be sampled: it is being restarted, has failed,
+    /// or restarted during the window.
+    pub fn should_sample(&self, service: &Service) -> bool {
+        let state = service.state();
+        self.samples.contains_key(service.name())
+            || state.active() == "failed"
+            || state.sub().starts_with("auto-restart")
+    }
+
+    /// Records a sample of the counter, returning the restarts within the window.
+    pub fn observe(&mut self, unit: &str, n_restarts: u32, now: Instant) -> u32 {
+        let samples = self.samples.entry(unit.to_string()).or_default();
+        // The counter starts over when the unit is started by hand.
+        if samples.back().is_some_and(|(_, previous)| *previous > n_restarts) {
+            samples.clear();
+        }
+        samples.push_back((now, n_restarts));
+        // Keeps the last sample from before the window as the count to compare with.
+        while samples
+            .get(1)
+            .is_some_and(|(time, _)| now.duration_since(*time) >= self.window)
+        {
+            samples.pop_front();
+        }
+
+        let (first_time, first) = samples[0];
+        let restarts = n_restarts - first;
+        if restarts == 0 && now.duration_since(first_time) >= self.window {
+            // Quiet for the whole window, no need to keep sampling it.
+            self.samples.remove(unit);
+        }
+        restarts
+    }
+
+    /// Whether that many restarts within the window make a crash loop.
+    pub fn is_crash_loop(&self, restarts: u32) -> bool {
+        self.is_enabled() && restarts > self.max_restarts
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+}
@@ -0,0 +1,47 @@
+/// Enable or disable performed during the session, with the unit file state it replaced.
+#[derive(Clone, Debug)]
+pub struct EnablementChange {
+    unit: String,
+    /// Unit file state before the first change of the session, e.g. "enabled" or "disabled".
+    previous: String,
+    /// Whether the last change enabled the unit.
+    enabled: bool,
+}
+
+impl EnablementChange {
+    pub fn new(unit: String, previous: String, enabled: bool) -> Self {
+        Self {
+            unit,
+            previous,
+            enabled,
+        }
+    }
+
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    pub fn previous(&self) -> &str {
+        &self.previous
+    }
+
+    /// Whether reverting enables the unit again, `None` when it was in a state an enable or
+    /// disable cannot restore, e.g. "static" or "masked".
+    pub fn revert_enables(&self) -> Option<bool> {
+        match self.previous.as_str() {
+            "enabled" => Some(true),
+            "disabled" => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Whether the changes of the session cancelled out.
+    pub fn is_noop(&self) -> bool {
+        self.revert_enables() == Some(self.enabled)
+    }
+
+    /// What the last change did, "enabled" or "disabled".
+    pub fn action(&self) -> &'static str {
+        if self.enabled { "enabled" } else { "disabled" }
+    }
+}
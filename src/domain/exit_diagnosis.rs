@@ -0,0 +1,177 @@
+use super::humanize::format_span;
+use super::process::format_bytes;
+use super::resource_limits::{ResourceLimits, UNLIMITED};
+use super::service_property::ServiceProperty;
+
+/// si_code of the main process, as in ExecMainCode.
+const CLD_EXITED: i32 = 1;
+const CLD_KILLED: i32 = 2;
+const CLD_DUMPED: i32 = 3;
+
+/// Linux signals a main process is commonly killed with, with what they usually mean.
+const SIGNALS: [(i32, &str, &str); 17] = [
+    (1, "SIGHUP", "hangup"),
+    (2, "SIGINT", "interrupted"),
+    (3, "SIGQUIT", "quit"),
+    (4, "SIGILL", "illegal instruction"),
+    (5, "SIGTRAP", "trace trap"),
+    (6, "SIGABRT", "aborted, e.g. by a failed assertion"),
+    (7, "SIGBUS", "bus error"),
+    (8, "SIGFPE", "arithmetic error"),
+    (9, "SIGKILL", "killed"),
+    (10, "SIGUSR1", "user signal 1"),
+    (11, "SIGSEGV", "segmentation fault"),
+    (12, "SIGUSR2", "user signal 2"),
+    (13, "SIGPIPE", "broken pipe"),
+    (14, "SIGALRM", "alarm"),
+    (15, "SIGTERM", "terminated"),
+    (24, "SIGXCPU", "CPU time limit exceeded"),
+    (25, "SIGXFSZ", "file size limit exceeded"),
+];
+
+/// Exit statuses of systemd's own setup of the process and of shells, e.g. 203 when ExecStart=
+/// could not be run.
+const EXIT_STATUSES: [(i32, &str); 14] = [
+    (1, "generic failure"),
+    (2, "invalid arguments"),
+    (126, "command not executable"),
+    (127, "command not found"),
+    (200, "CHDIR, WorkingDirectory= could not be entered"),
+    (203, "EXEC, the command could not be run"),
+    (204, "MEMORY, out of memory while setting up"),
+    (209, "STDOUT, standard output could not be set up"),
+    (210, "CHROOT, RootDirectory= could not be entered"),
+    (214, "SETSCHEDULER, the scheduling policy could not be set"),
+    (216, "GROUP, Group= could not be resolved"),
+    (217, "USER, User= could not be resolved"),
+    (226, "NAMESPACE, the sandbox could not be set up"),
+    (243, "CREDENTIALS, the credentials could not be loaded"),
+];
+
+/// e.g. "SIGSEGV (segmentation fault)" for 11.
+fn signal_meaning(signal: i32) -> String {
+    match SIGNALS.iter().find(|(number, _, _)| *number == signal) {
+        Some((_, name, meaning)) => format!("{} ({})", name, meaning),
+        None => format!("signal {}", signal),
+    }
+}
+
+/// Why the main process of a service ended abnormally, decoded from ExecMainCode, ExecMainStatus
+/// and Result, with the OOM killer messages of the journal and the settings worth looking at.
+#[derive(Debug, Clone)]
+pub struct ExitDiagnosis {
+    /// e.g. "killed by SIGKILL, possible OOM" or "exited with status 203 (EXEC, ...)".
+    cause: String,
+    /// Journal lines of the kernel and systemd about the OOM killer, newest last.
+    oom_lines: Vec<String>,
+    suggestions: Vec<String>,
+}
+
+impl ExitDiagnosis {
+    /// `None` when the service did not fail, stopping it with SIGTERM being a success.
+    pub fn diagnose(
+        name: &str,
+        property: &ServiceProperty,
+        limits: &ResourceLimits,
+        oom_lines: Vec<String>,
+    ) -> Option<Self> {
+        let result = property.result();
+        if result.is_empty() || result == "success" {
+            return None;
+        }
+        let (code, status) = (property.exec_main_code(), property.exec_main_status());
+        let killed = code == CLD_KILLED || code == CLD_DUMPED;
+        let oom = result == "oom-kill" || (killed && status == 9 && !oom_lines.is_empty());
+        let mut suggestions = Vec::new();
+
+        let cause = if result == "oom-kill" {
+            "killed by the OOM killer".to_string()
+        } else if killed && status == 9 {
+            if oom {
+                "killed by SIGKILL after running out of memory".to_string()
+            } else {
+                "killed by SIGKILL, possible OOM".to_string()
+            }
+        } else if code == CLD_DUMPED {
+            format!("dumped core after {}", signal_meaning(status))
+        } else if code == CLD_KILLED {
+            format!("killed by {}", signal_meaning(status))
+        } else if code == CLD_EXITED {
+            match EXIT_STATUSES.iter().find(|(number, _)| *number == status) {
+                Some((_, meaning)) => format!("exited with status {} ({})", status, meaning),
+                None => format!("exited with status {}", status),
+            }
+        } else {
+            format!("failed with result {}", result)
+        };
+
+        if oom {
+            suggestions.push(match limits.memory_max_bytes.filter(|bytes| *bytes != UNLIMITED) {
+                Some(bytes) => format!(
+                    "MemoryMax is {}: raise it, or find what makes the unit use more memory.",
+                    format_bytes(bytes)
+                ),
+                None => "Find what makes the unit use that much memory; consider MemoryMax to keep the OOM killer off the other units.".to_string(),
+            });
+        } else if killed && status == 9 {
+            suggestions.push(
+                "SIGKILL is also sent to processes still running after TimeoutStopSec, or by hand; read the logs around the exit."
+                    .to_string(),
+            );
+        }
+        if killed && status == 24 {
+            let limit = property.limit_cpu();
+            suggestions.push(if limit == UNLIMITED {
+                "The CPU time limit was reached: check LimitCPU and the ulimits of the command.".to_string()
+            } else {
+                format!("LimitCPU is {}s of CPU time: raise it if the unit needs more.", limit)
+            });
+        }
+        if code == CLD_DUMPED || (killed && [4, 6, 7, 8, 11].contains(&status)) {
+            suggestions.push(format!("The process crashed: look at the core dump with coredumpctl info {}.", name));
+        }
+        if code == CLD_EXITED {
+            match status {
+                203 | 126 | 127 => suggestions.push("Check the path of ExecStart and that it is executable.".to_string()),
+                200 => suggestions.push("Check that WorkingDirectory exists.".to_string()),
+                216 | 217 => suggestions.push("Check that User and Group exist.".to_string()),
+                226 => suggestions.push("Check the paths of ReadWritePaths and the other sandboxing settings.".to_string()),
+                _ => {}
+            }
+        }
+        match result {
+            "timeout" => suggestions.push(
+                "It did not start or stop in time: find what it waits for, or raise TimeoutStartSec and TimeoutStopSec."
+                    .to_string(),
+            ),
+            "watchdog" => suggestions.push(format!(
+                "It stopped sending keep-alives within WatchdogSec ({}): raise it if the service is only slow.",
+                format_span(property.watchdog_usec())
+            )),
+            "start-limit-hit" => suggestions.push(
+                "It was started too often within StartLimitIntervalSec: fix the failure or raise StartLimitBurst, then reset it."
+                    .to_string(),
+            ),
+            "resources" => suggestions.push("Setting up the unit failed: check the files, sockets and directories it needs.".to_string()),
+            _ => {}
+        }
+
+        Some(Self {
+            cause,
+            oom_lines,
+            suggestions,
+        })
+    }
+
+    pub fn cause(&self) -> &str {
+        &self.cause
+    }
+
+    pub fn oom_lines(&self) -> &[String] {
+        &self.oom_lines
+    }
+
+    pub fn suggestions(&self) -> &[String] {
+        &self.suggestions
+    }
+}
@@ -0,0 +1,37 @@
+use super::service::Service;
+use super::unit_property::UnitProperty;
+
+/// A failed unit with what is needed to tell why it failed.
+#[derive(Clone)]
+pub struct FailedUnit {
+    service: Service,
+    /// Last journal lines of the unit, oldest first.
+    log_tail: Vec<String>,
+}
+
+impl FailedUnit {
+    pub fn new(service: Service, log_tail: Vec<String>) -> Self {
+        Self { service, log_tail }
+    }
+
+    pub fn service(&self) -> &Service {
+        &self.service
+    }
+
+    pub fn log_tail(&self) -> &[String] {
+        &self.log_tail
+    }
+
+    /// "exit-code", "signal", "timeout"..., empty when the properties could not be read.
+    pub fn result(&self) -> &str {
+        self.service.properties().map_or("", UnitProperty::result)
+    }
+
+    /// Exit code or signal number of the main process, for services.
+    pub fn exit_status(&self) -> Option<i32> {
+        match self.service.properties() {
+            Some(UnitProperty::Service(property)) => Some(property.exec_main_status()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use serde::Deserialize;
+
+/// How a unit answers its probe, beyond the state systemd reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Health {
+    Ok,
+    /// Answering, but with an error: an HTTP status of 400 or above, or exit status 1.
+    Degraded,
+    /// Not answering at all.
+    Down,
+}
+
+impl Health {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Health::Ok => "OK",
+            Health::Degraded => "degraded",
+            Health::Down => "down",
+        }
+    }
+
+    /// Health of an HTTP status code.
+    pub fn from_http_status(status: u16) -> Self {
+        if status < 400 { Health::Ok } else { Health::Degraded }
+    }
+
+    /// Health of the exit status of a check command, as monitoring plugins report it: 0 is OK, 1
+    /// a warning and anything else critical.
+    pub fn from_exit_code(code: Option<i32>) -> Self {
+        match code {
+            Some(0) => Health::Ok,
+            Some(1) => Health::Degraded,
+            _ => Health::Down,
+        }
+    }
+}
+
+/// Check of whether a unit actually serves, as set in the config, e.g.
+/// `{ unit = "nginx", http = "http://localhost/health" }`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HealthProbe {
+    /// The unit, ".service" implied without a type.
+    pub unit: String,
+    /// URL answering a status below 400 when healthy.
+    pub http: Option<String>,
+    /// Address accepting connections when healthy, e.g. "127.0.0.1:6379".
+    pub tcp: Option<String>,
+    /// Shell command exiting with 0 when healthy and 1 when degraded.
+    pub command: Option<String>,
+}
+
+impl HealthProbe {
+    pub fn unit_name(&self) -> String {
+        if self.unit.contains('.') { self.unit.clone() } else { format!("{}.service", self.unit) }
+    }
+
+    /// Refuses probes without a unit or without exactly one of http, tcp and command.
+    pub fn check(&self) -> Result<(), String> {
+        if self.unit.is_empty() {
+            return Err("health probe without a unit".to_string());
+        }
+        let kinds = [&self.http, &self.tcp, &self.command].iter().filter(|kind| kind.is_some()).count();
+        if kinds != 1 {
+            return Err(format!("health probe of {} needs one of http, tcp or command", self.unit));
+        }
+        if let Some(url) = &self.http
+            && !url.starts_with("http://")
+            && !url.starts_with("https://")
+        {
+            return Err(format!("health probe of {}: \"{}\" is not an http:// or https:// URL", self.unit, url));
+        }
+        Ok(())
+    }
+}
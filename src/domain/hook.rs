@@ -0,0 +1,134 @@
+use chrono::Local;
+use serde::Deserialize;
+use serde_json::json;
+
+use super::action_policy::{glob_matches, PolicyAction};
+use super::unit_transition::UnitTransition;
+
+/// Active states a unit settles in, hooked when a watched unit enters them.
+const STATE_EVENTS: [&str; 3] = ["failed", "active", "inactive"];
+
+/// Actions hooked once they succeeded, named as in the policy.
+const ACTION_EVENTS: [PolicyAction; 10] = [
+    PolicyAction::Start,
+    PolicyAction::Stop,
+    PolicyAction::Restart,
+    PolicyAction::Reload,
+    PolicyAction::Enable,
+    PolicyAction::Disable,
+    PolicyAction::Freeze,
+    PolicyAction::Thaw,
+    PolicyAction::Kill,
+    PolicyAction::ResetFailed,
+];
+
+/// Command or webhook run when units enter a state or are acted upon, as set in the config, e.g.
+/// `{ on = ["failed"], url = "https://hooks.slack.com/services/..." }`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Hook {
+    /// Events running the hook: "failed", "active" or "inactive" when a unit enters the state,
+    /// or an action such as "restart" once it succeeded.
+    pub on: Vec<String>,
+    /// Glob of unit names, `*` and `?` being wildcards; without a type, ".service" is implied.
+    pub units: String,
+    /// Shell command, run with the event in `$HOOK_EVENT`, the unit in `$UNIT` and the
+    /// description in `$HOOK_TEXT`.
+    pub command: Option<String>,
+    /// URL receiving the event as a JSON POST, with a `text` field for Slack-like webhooks.
+    pub url: Option<String>,
+}
+
+impl Default for Hook {
+    fn default() -> Self {
+        Self {
+            on: Vec::new(),
+            units: "*".to_string(),
+            command: None,
+            url: None,
+        }
+    }
+}
+
+impl Hook {
+    /// Refuses hooks without events, with unknown ones, or without exactly one of command and url.
+    pub fn check(&self) -> Result<(), String> {
+        if self.on.is_empty() {
+            return Err(format!("hook of \"{}\" without events", self.units));
+        }
+        for event in &self.on {
+            let known = STATE_EVENTS.contains(&event.as_str())
+                || ACTION_EVENTS.iter().any(|action| action.label() == event);
+            if !known {
+                let actions: Vec<&str> = ACTION_EVENTS.iter().map(PolicyAction::label).collect();
+                return Err(format!(
+                    "unknown hook event \"{}\", expected one of {}, {}",
+                    event,
+                    STATE_EVENTS.join(", "),
+                    actions.join(", ")
+                ));
+            }
+        }
+        if self.command.is_some() == self.url.is_some() {
+            return Err(format!("hook of \"{}\" needs one of command or url", self.units));
+        }
+        Ok(())
+    }
+
+    pub fn matches(&self, event: &HookEvent) -> bool {
+        let pattern = if self.units.contains('.') {
+            self.units.clone()
+        } else {
+            format!("{}.service", self.units)
+        };
+        self.on.contains(&event.name) && glob_matches(pattern.as_bytes(), event.unit.as_bytes())
+    }
+}
+
+/// Something that happened to a unit, handed to the hooks.
+#[derive(Debug, Clone)]
+pub struct HookEvent {
+    /// "failed", "restart"...
+    pub name: String,
+    pub unit: String,
+    /// e.g. "nginx.service failed (active → failed (exit-code))".
+    pub text: String,
+}
+
+impl HookEvent {
+    pub fn entered(transition: &UnitTransition) -> Self {
+        Self {
+            name: transition.to().to_string(),
+            unit: transition.unit().to_string(),
+            text: format!("{} {} ({})", transition.unit(), transition.to(), transition.describe()),
+        }
+    }
+
+    /// A unit found failed while it was not watched, its previous state unknown.
+    pub fn failed(unit: &str) -> Self {
+        Self {
+            name: "failed".to_string(),
+            unit: unit.to_string(),
+            text: format!("{} failed", unit),
+        }
+    }
+
+    pub fn performed(action: PolicyAction, unit: &str) -> Self {
+        Self {
+            name: action.label().to_string(),
+            unit: unit.to_string(),
+            text: format!("{} of {} done", action.label(), unit),
+        }
+    }
+
+    /// Body of the webhook requests.
+    pub fn json(&self) -> String {
+        json!({
+            "event": self.name,
+            "unit": self.unit,
+            "text": self.text,
+            "time": Local::now().to_rfc3339(),
+        })
+        .to_string()
+    }
+}
@@ -0,0 +1,66 @@
+use chrono::{DateTime, Local, TimeZone};
+
+/// Realtime clock in microseconds, what systemd timestamps are compared to.
+pub fn now_usec() -> u64 {
+    Local::now().timestamp_micros().max(0) as u64
+}
+
+/// e.g. "45s", "5m", "2h 13m" or "3d 4h".
+pub fn format_duration(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86_400, secs % 86_400 / 3600, secs % 3600 / 60);
+    match (days, hours, minutes) {
+        (0, 0, 0) => format!("{}s", secs),
+        (0, 0, _) => format!("{}m", minutes),
+        (0, _, _) => format!("{}h {}m", hours, minutes),
+        _ => format!("{}d {}h", days, hours),
+    }
+}
+
+/// A span in microseconds, as systemd writes it in unit files, e.g. "100ms", "5s" or "10min".
+pub fn format_span(usec: u64) -> String {
+    match usec {
+        u64::MAX => "infinity".to_string(),
+        0 => "0".to_string(),
+        usec if usec < 1_000_000 => format!("{}ms", usec / 1000),
+        usec if usec % 60_000_000 == 0 => format!("{}min", usec / 60_000_000),
+        usec => format!("{}s", usec / 1_000_000),
+    }
+}
+
+/// Realtime timestamp in microseconds, as local time; "n/a" for 0, systemd's "never".
+pub fn format_timestamp(usec: u64) -> String {
+    match DateTime::from_timestamp_micros(usec as i64) {
+        Some(time) if usec > 0 => time.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S").to_string(),
+        _ => "n/a".to_string(),
+    }
+}
+
+/// How far a timestamp is from now, e.g. "2h 13m ago" or "in 5m" for the next run of a timer;
+/// "n/a" for 0.
+pub fn format_ago(usec: u64, now_usec: u64) -> String {
+    if usec == 0 {
+        return "n/a".to_string();
+    }
+    if usec > now_usec {
+        format!("in {}", format_duration((usec - now_usec) / 1_000_000))
+    } else {
+        format!("{} ago", format_duration((now_usec - usec) / 1_000_000))
+    }
+}
+
+/// Both, like `systemctl status`, e.g. "2026-10-17 09:12:03 (2h 13m ago)".
+pub fn format_timestamp_ago(usec: u64, now_usec: u64) -> String {
+    if usec == 0 {
+        return "n/a".to_string();
+    }
+    format!("{} ({})", format_timestamp(usec), format_ago(usec, now_usec))
+}
+
+/// Time of a journal entry as `journalctl -o short` prints it, e.g. "Oct 17 09:12:03".
+pub fn format_journal_time(usec: u64) -> String {
+    Local
+        .timestamp_micros(usec as i64)
+        .single()
+        .map(|time| time.format("%b %d %H:%M:%S").to_string())
+        .unwrap_or_default()
+}
@@ -0,0 +1,22 @@
+/// Outcome of a job queued by an action, as reported by systemd's JobRemoved signal.
+#[derive(Clone, Debug)]
+pub struct JobResult {
+    job: String,
+    result: String,
+}
+
+impl JobResult {
+    pub fn new(job: String, result: String) -> Self {
+        Self { job, result }
+    }
+
+    /// Object path of the job, as returned by StartUnit, StopUnit...
+    pub fn job(&self) -> &str {
+        &self.job
+    }
+
+    /// "done", "canceled", "timeout", "failed", "dependency" or "skipped".
+    pub fn result(&self) -> &str {
+        &self.result
+    }
+}
@@ -0,0 +1,55 @@
+/// A boot of the journal, by its offset from the current one as in `journalctl -b -1`, and its
+/// boot ID, which the entries are matched on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Boot {
+    pub offset: i32,
+    pub id: u128,
+}
+
+impl Boot {
+    /// The boot ID as `_BOOT_ID` and `journalctl -b` take it, 32 hexadecimal digits.
+    pub fn id_hex(&self) -> String {
+        format!("{:032x}", self.id)
+    }
+
+    /// e.g. "current boot" or "boot -1".
+    pub fn describe(&self) -> String {
+        if self.offset == 0 { "current boot".to_string() } else { format!("boot {}", self.offset) }
+    }
+}
+
+/// A boot as `journalctl --list-boots` lists it, with when its first and last entries were logged.
+#[derive(Debug, Clone)]
+pub struct ListedBoot {
+    pub boot: Boot,
+    /// e.g. "Fri 2026-10-16 08:00:01 CEST — Fri 2026-10-16 23:59:59 CEST".
+    pub entries: String,
+}
+
+/// The boots of `journalctl --list-boots`, newest first. Older versions separate the first and
+/// last entry with "—", newer ones print them as columns after a header.
+pub fn parse_boots(output: &str) -> Vec<ListedBoot> {
+    let mut boots: Vec<ListedBoot> = output
+        .lines()
+        .filter_map(|line| {
+            let mut words = line.split_whitespace();
+            let offset = words.next()?.parse::<i32>().ok()?;
+            let id = u128::from_str_radix(words.next()?, 16).ok()?;
+            let rest = words.collect::<Vec<_>>().join(" ");
+            let entries = match rest.split_once('—') {
+                Some((first, last)) => format!("{} — {}", first.trim(), last.trim()),
+                None => {
+                    let words: Vec<&str> = rest.split(' ').collect();
+                    let (first, last) = words.split_at(words.len() / 2);
+                    format!("{} — {}", first.join(" "), last.join(" "))
+                }
+            };
+            Some(ListedBoot {
+                boot: Boot { offset, id },
+                entries,
+            })
+        })
+        .collect();
+    boots.sort_by_key(|listed| std::cmp::Reverse(listed.boot.offset));
+    boots
+}
@@ -0,0 +1,225 @@
+use chrono::Local;
+use serde_json::Value;
+
+use super::humanize::format_journal_time;
+use super::journal_boot::Boot;
+
+/// Syslog priorities, from the most to the least severe, as journalctl names them.
+const PRIORITY_NAMES: [&str; 8] = ["emerg", "alert", "crit", "err", "warning", "notice", "info", "debug"];
+pub const PRIORITY_ERR: u8 = 3;
+pub const PRIORITY_WARNING: u8 = 4;
+pub const PRIORITY_DEBUG: u8 = 7;
+
+/// Minimum priorities the log view steps through, from every entry to errors only.
+const MIN_PRIORITY_STEPS: [u8; 5] = [PRIORITY_DEBUG, 6, 5, PRIORITY_WARNING, PRIORITY_ERR];
+/// Values of `journalctl --since` the log view steps through, none meaning the whole journal.
+const SINCE_STEPS: [Option<&str>; 5] = [None, Some("-15min"), Some("-1h"), Some("today"), Some("-7d")];
+
+pub fn priority_name(priority: u8) -> &'static str {
+    PRIORITY_NAMES.get(priority as usize).copied().unwrap_or("unknown")
+}
+
+/// Unix time of a `journalctl --since` value of the log view, e.g. "-15min" or "today".
+pub fn since_unix_time(since: &str) -> Option<i64> {
+    let now = Local::now();
+    if since == "today" {
+        let midnight = now.date_naive().and_hms_opt(0, 0, 0)?;
+        return midnight.and_local_timezone(Local).earliest().map(|midnight| midnight.timestamp());
+    }
+    let relative = since.strip_prefix('-')?;
+    let (amount, unit) = relative.split_at(relative.find(|c: char| !c.is_ascii_digit())?);
+    let seconds = match unit {
+        "s" => 1,
+        "min" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        _ => return None,
+    };
+    Some(now.timestamp() - amount.parse::<i64>().ok()? * seconds)
+}
+
+/// One journal entry, from a line of `journalctl -o json` or read from the journal files.
+/// Logs that are not read from the journal only have a message.
+#[derive(Debug, Clone, Default)]
+pub struct JournalEntry {
+    /// Realtime timestamp, in microseconds.
+    timestamp: Option<u64>,
+    /// Position of the entry in the journal, to read the entries before it.
+    cursor: Option<String>,
+    priority: Option<u8>,
+    pid: Option<u32>,
+    identifier: Option<String>,
+    message: String,
+    /// Unit the entry was read for, in logs merging several units.
+    unit: Option<String>,
+}
+
+/// Fields of `journalctl -o json` are strings; MESSAGE is an array of bytes when not UTF-8.
+fn field_string(entry: &Value, field: &str) -> Option<String> {
+    match entry.get(field)? {
+        Value::String(value) => Some(value.clone()),
+        Value::Array(bytes) => {
+            let bytes: Vec<u8> = bytes.iter().filter_map(|byte| byte.as_u64()).map(|byte| byte as u8).collect();
+            Some(String::from_utf8_lossy(&bytes).into_owned())
+        }
+        _ => None,
+    }
+}
+
+impl JournalEntry {
+    pub fn from_json(line: &str) -> Option<Self> {
+        let entry: Value = serde_json::from_str(line).ok()?;
+        Some(Self::from_fields(|field| field_string(&entry, field)))
+    }
+
+    /// An entry from its journal fields, with the `__REALTIME_TIMESTAMP` and `__CURSOR` ones
+    /// of the export formats.
+    pub fn from_fields(field: impl Fn(&str) -> Option<String>) -> Self {
+        Self {
+            timestamp: field("__REALTIME_TIMESTAMP").and_then(|value| value.parse().ok()),
+            cursor: field("__CURSOR"),
+            priority: field("PRIORITY").and_then(|value| value.parse().ok()),
+            pid: field("_PID").and_then(|value| value.parse().ok()),
+            identifier: field("SYSLOG_IDENTIFIER"),
+            message: field("MESSAGE").unwrap_or_default(),
+            unit: None,
+        }
+    }
+
+    /// The entry tagged with the unit it was read for, shown in logs merging several units.
+    pub fn tagged(self, unit: &str) -> Self {
+        Self {
+            unit: Some(unit.to_string()),
+            ..self
+        }
+    }
+
+    /// A line of a plain text log, kept as it is.
+    pub fn raw(line: &str) -> Self {
+        Self {
+            message: line.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// A line of a log with timestamps but no priorities, e.g. of a container.
+    pub fn timed(timestamp: u64, identifier: &str, message: &str) -> Self {
+        Self {
+            timestamp: Some(timestamp),
+            identifier: Some(identifier.to_string()),
+            message: message.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Realtime timestamp, in microseconds.
+    pub fn timestamp(&self) -> Option<u64> {
+        self.timestamp
+    }
+
+    pub fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref()
+    }
+
+    pub fn priority(&self) -> Option<u8> {
+        self.priority
+    }
+
+    pub fn identifier(&self) -> Option<&str> {
+        self.identifier.as_deref()
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn unit(&self) -> Option<&str> {
+        self.unit.as_deref()
+    }
+
+    /// Bytes of the timestamp and the space after it at the start of `line`, 0 without one.
+    pub fn time_width(&self) -> usize {
+        self.timestamp.map_or(0, |timestamp| format_journal_time(timestamp).len() + 1)
+    }
+
+    /// The entry as `journalctl -o short` prints it, e.g. "Oct 17 09:12:03 nginx[812]: message",
+    /// the unit being tagged after the time when set, e.g. "Oct 17 09:12:03 [nginx.service] nginx[812]: message".
+    pub fn line(&self) -> String {
+        let Some(timestamp) = self.timestamp else {
+            return self.message.clone();
+        };
+        let time = format_journal_time(timestamp);
+        let source = match (&self.identifier, self.pid) {
+            (Some(identifier), Some(pid)) => format!("{}[{}]", identifier, pid),
+            (Some(identifier), None) => identifier.clone(),
+            (None, Some(pid)) => format!("[{}]", pid),
+            (None, None) => String::new(),
+        };
+        match &self.unit {
+            Some(unit) => format!("{} [{}] {}: {}", time, unit, source, self.message),
+            None => format!("{} {}: {}", time, source, self.message),
+        }
+    }
+}
+
+/// Which entries the log view asks the journal for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogFilter {
+    /// Least severe priority shown, debug showing every entry.
+    pub min_priority: u8,
+    /// Passed to `journalctl --since`.
+    pub since: Option<&'static str>,
+    /// The boot the entries were logged during, none for every boot.
+    pub boot: Option<Boot>,
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self {
+            min_priority: PRIORITY_DEBUG,
+            since: None,
+            boot: None,
+        }
+    }
+}
+
+impl LogFilter {
+    /// Realtime timestamp (microseconds) of the start of the time range, none for the whole journal.
+    pub fn since_usec(&self) -> Result<Option<u64>, String> {
+        match self.since {
+            Some(since) => since_unix_time(since)
+                .map(|time| Some(time.max(0) as u64 * 1_000_000))
+                .ok_or_else(|| format!("Unknown time range {}", since)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        *self != Self::default()
+    }
+
+    pub fn next_min_priority(&mut self) {
+        let index = MIN_PRIORITY_STEPS.iter().position(|step| *step == self.min_priority).unwrap_or(0);
+        self.min_priority = MIN_PRIORITY_STEPS[(index + 1) % MIN_PRIORITY_STEPS.len()];
+    }
+
+    pub fn next_since(&mut self) {
+        let index = SINCE_STEPS.iter().position(|step| *step == self.since).unwrap_or(0);
+        self.since = SINCE_STEPS[(index + 1) % SINCE_STEPS.len()];
+    }
+
+    /// e.g. "warning and above, since -1h, boot -1".
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if self.min_priority < PRIORITY_DEBUG {
+            parts.push(format!("{} and above", priority_name(self.min_priority)));
+        }
+        if let Some(since) = self.since {
+            parts.push(format!("since {}", since));
+        }
+        if let Some(boot) = self.boot {
+            parts.push(boot.describe());
+        }
+        parts.join(", ")
+    }
+}
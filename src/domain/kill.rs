@@ -0,0 +1,24 @@
+/// Processes of a unit that receive a signal, as understood by systemd's KillUnit.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KillTarget {
+    /// Only the main process.
+    Main,
+    /// Every process of the unit's control group.
+    All,
+}
+
+impl KillTarget {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KillTarget::Main => "main",
+            KillTarget::All => "all",
+        }
+    }
+
+    pub fn toggle(&self) -> Self {
+        match self {
+            KillTarget::Main => KillTarget::All,
+            KillTarget::All => KillTarget::Main,
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+use std::time::Duration;
+
+/// Time taken by the bulk listing call and by the state calls made for each listed unit.
+pub struct ListTimings {
+    list_call: Duration,
+    unit_calls: Vec<Duration>,
+}
+
+impl ListTimings {
+    pub fn new(list_call: Duration, unit_calls: Vec<Duration>) -> Self {
+        Self { list_call, unit_calls }
+    }
+
+    pub fn list_call(&self) -> Duration {
+        self.list_call
+    }
+
+    pub fn unit_calls(&self) -> &[Duration] {
+        &self.unit_calls
+    }
+}
+
+/// Minimum, average and maximum of a series of latency samples.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Latency {
+    samples: usize,
+    min: Duration,
+    avg: Duration,
+    max: Duration,
+    total: Duration,
+}
+
+impl Latency {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        let total: Duration = samples.iter().sum();
+        Self {
+            samples: samples.len(),
+            min: samples.iter().min().copied().unwrap_or_default(),
+            avg: total / samples.len() as u32,
+            max: samples.iter().max().copied().unwrap_or_default(),
+            total,
+        }
+    }
+
+    pub fn min(&self) -> Duration {
+        self.min
+    }
+
+    pub fn avg(&self) -> Duration {
+        self.avg
+    }
+
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+impl fmt::Display for Latency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:>9.3} ms  avg {:>9.3} ms  max {:>9.3} ms",
+            millis(self.min),
+            millis(self.avg),
+            millis(self.max)
+        )
+    }
+}
@@ -0,0 +1,43 @@
+/// A grep-like filter of log lines such as "error !healthcheck": a line is kept when it contains
+/// every term and none of the terms starting with "!", ignoring case.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LogExpression {
+    text: String,
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl LogExpression {
+    pub fn parse(text: &str) -> Self {
+        let mut expression = Self {
+            text: text.split_whitespace().collect::<Vec<_>>().join(" "),
+            ..Self::default()
+        };
+        for term in text.split_whitespace() {
+            match term.strip_prefix('!') {
+                Some("") => {}
+                Some(excluded) => expression.exclude.push(excluded.to_lowercase()),
+                None => expression.include.push(term.to_lowercase()),
+            }
+        }
+        expression
+    }
+
+    /// The expression as typed, without extra spaces.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    pub fn matches(&self, line: &str) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        let line = line.to_lowercase();
+        self.include.iter().all(|term| line.contains(term.as_str()))
+            && !self.exclude.iter().any(|term| line.contains(term.as_str()))
+    }
+}
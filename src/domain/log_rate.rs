@@ -0,0 +1,46 @@
+const MICROS_PER_MINUTE: u64 = 60_000_000;
+const MAX_MINUTES: u64 = 24 * 60;
+
+/// Number of journal entries written per minute, oldest minute first.
+#[derive(Clone, Debug, Default)]
+pub struct LogRate {
+    per_minute: Vec<u64>,
+}
+
+impl LogRate {
+    /// Buckets realtime timestamps (in microseconds) by minute, from the oldest entry up to
+    /// `now_usec`. At most the last 24 hours are kept.
+    pub fn from_timestamps(timestamps: &[u64], now_usec: u64) -> Self {
+        let Some(&oldest) = timestamps.iter().min() else {
+            return Self::default();
+        };
+
+        let last_minute = now_usec.max(oldest) / MICROS_PER_MINUTE;
+        let first_minute = (oldest / MICROS_PER_MINUTE).max(last_minute.saturating_sub(MAX_MINUTES - 1));
+
+        let mut per_minute = vec![0; (last_minute - first_minute + 1) as usize];
+        for timestamp in timestamps {
+            let minute = timestamp / MICROS_PER_MINUTE;
+            if (first_minute..=last_minute).contains(&minute) {
+                per_minute[(minute - first_minute) as usize] += 1;
+            }
+        }
+
+        Self { per_minute }
+    }
+
+    pub fn per_minute(&self) -> &[u64] {
+        &self.per_minute
+    }
+
+    pub fn peak(&self) -> u64 {
+        self.per_minute.iter().copied().max().unwrap_or(0)
+    }
+
+    pub fn average(&self) -> f64 {
+        if self.per_minute.is_empty() {
+            return 0.0;
+        }
+        self.per_minute.iter().sum::<u64>() as f64 / self.per_minute.len() as f64
+    }
+}
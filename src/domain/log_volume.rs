@@ -0,0 +1,37 @@
+use std::collections::HashMap;
+
+/// Number of journal entries a unit wrote over a period of time.
+#[derive(Clone, Debug)]
+pub struct LogVolume {
+    unit: String,
+    entries: u64,
+}
+
+impl LogVolume {
+    pub fn new(unit: String, entries: u64) -> Self {
+        Self { unit, entries }
+    }
+
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    pub fn entries(&self) -> u64 {
+        self.entries
+    }
+
+    /// Counts entries per unit and ranks the units from the noisiest to the quietest.
+    pub fn rank<'a>(units: impl Iterator<Item = &'a str>) -> Vec<LogVolume> {
+        let mut counts: HashMap<&str, u64> = HashMap::new();
+        for unit in units {
+            *counts.entry(unit).or_default() += 1;
+        }
+
+        let mut volumes: Vec<LogVolume> = counts
+            .into_iter()
+            .map(|(unit, entries)| LogVolume::new(unit.to_string(), entries))
+            .collect();
+        volumes.sort_by(|a, b| b.entries.cmp(&a.entries).then_with(|| a.unit.cmp(&b.unit)));
+        volumes
+    }
+}
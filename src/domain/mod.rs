@@ -1,4 +1,55 @@
+pub mod action_journal;
+pub mod action_plan;
+pub mod action_policy;
+pub mod boot_timing;
+pub mod crash_loop;
+pub mod enablement_change;
+pub mod exit_diagnosis;
+pub mod failed_unit;
+pub mod health;
+pub mod hook;
+pub mod humanize;
+pub mod job;
+pub mod journal_boot;
+pub mod journal_entry;
+pub mod kill;
+pub mod latency;
+pub mod log_expression;
+pub mod log_rate;
+pub mod log_volume;
+pub mod power_action;
+pub mod process;
+pub mod resource_limits;
+pub mod resource_usage;
+pub mod runtime_stats;
+pub mod scheduled_action;
+pub mod security_audit;
 pub mod service;
 pub mod service_property;
+pub mod service_query;
+pub mod service_group;
 pub mod service_repository;
+pub mod service_security;
 pub mod service_state;
+pub mod session_recording;
+pub mod slice;
+pub mod snapshot;
+pub mod system_summary;
+pub mod transient_unit;
+pub mod unit_alias;
+pub mod unit_capabilities;
+pub mod unit_clone;
+pub mod unit_conditions;
+pub mod unit_dependencies;
+pub mod unit_documentation;
+pub mod unit_environment;
+pub mod unit_file;
+pub mod unit_file_drift;
+pub mod unit_history;
+pub mod unit_path;
+pub mod unit_property;
+pub mod unit_template;
+pub mod unit_transition;
+pub mod unit_triggers;
+pub mod unit_verification;
+pub mod uptime;
@@ -0,0 +1,23 @@
+/// Shutting the machine down, like `systemctl reboot` and `systemctl poweroff`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PowerAction {
+    Reboot,
+    PowerOff,
+}
+
+impl PowerAction {
+    /// The target started for the action, which stops every unit first.
+    pub fn target(&self) -> &'static str {
+        match self {
+            PowerAction::Reboot => "reboot.target",
+            PowerAction::PowerOff => "poweroff.target",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PowerAction::Reboot => "Reboot",
+            PowerAction::PowerOff => "Power off",
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::{HashMap, HashSet};
+
+/// A process of the control group of a unit.
+#[derive(Debug, Clone)]
+pub struct UnitProcess {
+    pid: u32,
+    ppid: u32,
+    command: String,
+    /// CPU time used since the process started, user and system, in microseconds.
+    cpu_usec: u64,
+    rss_bytes: u64,
+}
+
+/// e.g. "512K", "23.4M" or "1.2G", like `ps` and `top`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["K", "M", "G", "T"];
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{:.0}{}", value, UNITS[unit])
+    } else {
+        format!("{:.1}{}", value, UNITS[unit])
+    }
+}
+
+impl UnitProcess {
+    pub fn new(pid: u32, ppid: u32, command: String, cpu_usec: u64, rss_bytes: u64) -> Self {
+        Self {
+            pid,
+            ppid,
+            command,
+            cpu_usec,
+            rss_bytes,
+        }
+    }
+
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    pub fn cpu_usec(&self) -> u64 {
+        self.cpu_usec
+    }
+
+    pub fn rss_bytes(&self) -> u64 {
+        self.rss_bytes
+    }
+}
+
+/// The processes in tree order, children under their parent by PID, with their depth.
+/// Processes whose parent is outside of the unit are roots.
+pub fn process_tree(processes: Vec<UnitProcess>) -> Vec<(usize, UnitProcess)> {
+    let pids: HashSet<u32> = processes.iter().map(UnitProcess::pid).collect();
+    let mut children: HashMap<u32, Vec<UnitProcess>> = HashMap::new();
+    let mut roots = Vec::new();
+    for process in processes {
+        if pids.contains(&process.ppid) && process.ppid != process.pid {
+            children.entry(process.ppid).or_default().push(process);
+        } else {
+            roots.push(process);
+        }
+    }
+
+    let mut tree = Vec::new();
+    // Reversed, so that popping visits the lowest PID first.
+    roots.sort_by_key(|process| std::cmp::Reverse(process.pid));
+    let mut stack: Vec<(usize, UnitProcess)> = roots.into_iter().map(|process| (0, process)).collect();
+    while let Some((depth, process)) = stack.pop() {
+        if let Some(mut descendants) = children.remove(&process.pid) {
+            descendants.sort_by_key(|child| std::cmp::Reverse(child.pid));
+            stack.extend(descendants.into_iter().map(|child| (depth + 1, child)));
+        }
+        tree.push((depth, process));
+    }
+    tree
+}
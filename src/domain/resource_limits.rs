@@ -0,0 +1,110 @@
+use super::process::format_bytes;
+
+/// Value of a limit without any, like systemd's "infinity".
+pub const UNLIMITED: u64 = u64::MAX;
+
+/// cgroup limits of a unit, `None` for the ones left as they are.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResourceLimits {
+    /// CPUQuota=, in percent of one CPU, e.g. 150 for one and a half.
+    pub cpu_quota_percent: Option<u64>,
+    /// MemoryMax=, in bytes.
+    pub memory_max_bytes: Option<u64>,
+    /// TasksMax=, in number of processes and threads.
+    pub tasks_max: Option<u64>,
+}
+
+impl ResourceLimits {
+    /// Parses the limits as typed in a unit file, e.g. "50%", "512M" and "100"; empty text
+    /// leaves a limit as it is and "infinity" removes it.
+    pub fn parse(cpu_quota: &str, memory_max: &str, tasks_max: &str) -> Result<Self, String> {
+        Ok(Self {
+            cpu_quota_percent: parse_limit(cpu_quota, parse_percent).map_err(|e| format!("CPUQuota: {}", e))?,
+            memory_max_bytes: parse_limit(memory_max, parse_bytes).map_err(|e| format!("MemoryMax: {}", e))?,
+            tasks_max: parse_limit(tasks_max, |text| {
+                text.parse().map_err(|_| format!("\"{}\" is not a number", text))
+            })
+            .map_err(|e| format!("TasksMax: {}", e))?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// (setting, value) of the limits set, e.g. ("MemoryMax", "512.0M").
+    pub fn summary(&self) -> Vec<(&'static str, String)> {
+        let mut summary = Vec::new();
+        if let Some(percent) = self.cpu_quota_percent {
+            summary.push(("CPUQuota", format_limit(percent, |percent| format!("{}%", percent))));
+        }
+        if let Some(bytes) = self.memory_max_bytes {
+            summary.push(("MemoryMax", format_limit(bytes, format_bytes)));
+        }
+        if let Some(tasks) = self.tasks_max {
+            summary.push(("TasksMax", format_limit(tasks, |tasks| tasks.to_string())));
+        }
+        summary
+    }
+
+    /// e.g. "CPUQuota 50%, TasksMax 100".
+    pub fn describe(&self) -> String {
+        self.summary()
+            .into_iter()
+            .map(|(setting, value)| format!("{} {}", setting, value))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Section of the unit file holding the cgroup settings of the unit, e.g. "Service" for
+/// nginx.service, `None` for unit types without a control group.
+pub fn cgroup_section(unit: &str) -> Option<&'static str> {
+    match unit.rsplit_once('.')?.1 {
+        "service" => Some("Service"),
+        "socket" => Some("Socket"),
+        "mount" => Some("Mount"),
+        "swap" => Some("Swap"),
+        "slice" => Some("Slice"),
+        "scope" => Some("Scope"),
+        _ => None,
+    }
+}
+
+fn parse_limit(text: &str, parse: impl Fn(&str) -> Result<u64, String>) -> Result<Option<u64>, String> {
+    match text.trim() {
+        "" => Ok(None),
+        "infinity" => Ok(Some(UNLIMITED)),
+        text => parse(text).map(Some),
+    }
+}
+
+/// "infinity" for `UNLIMITED`, otherwise the formatted value.
+fn format_limit(value: u64, format: impl Fn(u64) -> String) -> String {
+    if value == UNLIMITED { "infinity".to_string() } else { format(value) }
+}
+
+/// Percent of one CPU, e.g. "50%" or "200%".
+fn parse_percent(text: &str) -> Result<u64, String> {
+    let number = text.strip_suffix('%').unwrap_or(text);
+    match number.parse::<u64>() {
+        Ok(percent) if percent > 0 => Ok(percent),
+        _ => Err(format!("\"{}\" is not a percentage, e.g. 50%", text)),
+    }
+}
+
+/// Bytes of a size with an optional K, M, G or T suffix, in powers of 1024 like systemd.
+fn parse_bytes(text: &str) -> Result<u64, String> {
+    let (number, factor) = match text.char_indices().last() {
+        Some((index, suffix @ ('K' | 'M' | 'G' | 'T'))) => {
+            let power = "KMGT".find(suffix).unwrap_or(0) as u32 + 1;
+            (&text[..index], 1024u64.pow(power))
+        }
+        _ => (text, 1),
+    };
+    number
+        .parse::<u64>()
+        .ok()
+        .and_then(|number| number.checked_mul(factor))
+        .ok_or_else(|| format!("\"{}\" is not a size, e.g. 512M", text))
+}
@@ -0,0 +1,84 @@
+use std::collections::VecDeque;
+
+use super::process::format_bytes;
+
+/// CPU time and memory of a unit, from CPUUsageNSec and MemoryCurrent.
+#[derive(Debug, Clone, Copy)]
+pub struct ResourceUsage {
+    /// CPU time used since the unit started, None when it is not accounted.
+    pub cpu_nsec: Option<u64>,
+    /// Memory of the control group, None when it is not accounted.
+    pub memory_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct UsageSample {
+    /// Time of the sample in microseconds, from any fixed origin.
+    at_usec: u64,
+    usage: ResourceUsage,
+}
+
+/// Samples of a unit over the last minutes, oldest first, for the graphs of the details.
+#[derive(Debug, Clone)]
+pub struct UsageHistory {
+    samples: VecDeque<UsageSample>,
+    capacity: usize,
+}
+
+impl UsageHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(2),
+        }
+    }
+
+    pub fn push(&mut self, at_usec: u64, usage: ResourceUsage) {
+        let sample = UsageSample { at_usec, usage };
+        // The CPU counter starts over with the unit, the history of the previous run is dropped.
+        if let (Some(last), Some(cpu)) = (self.samples.back().and_then(|last| last.usage.cpu_nsec), usage.cpu_nsec)
+            && cpu < last
+        {
+            self.samples.clear();
+        }
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// Percent of one CPU used between each two samples, 0 where a sample is not accounted.
+    pub fn cpu_percents(&self) -> Vec<u64> {
+        self.samples
+            .iter()
+            .zip(self.samples.iter().skip(1))
+            .map(|(previous, sample)| match (previous.usage.cpu_nsec, sample.usage.cpu_nsec) {
+                (Some(before), Some(after)) if sample.at_usec > previous.at_usec => {
+                    let elapsed_nsec = (sample.at_usec - previous.at_usec) * 1000;
+                    after.saturating_sub(before) * 100 / elapsed_nsec
+                }
+                _ => 0,
+            })
+            .collect()
+    }
+
+    /// Memory of each sample, 0 where it is not accounted.
+    pub fn memory(&self) -> Vec<u64> {
+        self.samples.iter().map(|sample| sample.usage.memory_bytes.unwrap_or(0)).collect()
+    }
+
+    /// e.g. "12%, peak 85%", None before two samples.
+    pub fn cpu_summary(&self) -> Option<String> {
+        let percents = self.cpu_percents();
+        let current = percents.last()?;
+        let peak = percents.iter().max()?;
+        Some(format!("{}%, peak {}%", current, peak))
+    }
+
+    /// e.g. "23.4M, peak 40.1M", None when the memory is not accounted.
+    pub fn memory_summary(&self) -> Option<String> {
+        let current = self.samples.back()?.usage.memory_bytes?;
+        let peak = self.samples.iter().filter_map(|sample| sample.usage.memory_bytes).max()?;
+        Some(format!("{}, peak {}", format_bytes(current), format_bytes(peak)))
+    }
+}
@@ -0,0 +1,45 @@
+use super::process::format_bytes;
+use super::humanize::format_duration;
+
+/// Restart counter, start time of the main process and memory of a service, for the list columns.
+#[derive(Debug, Clone, Copy)]
+pub struct RuntimeStats {
+    n_restarts: u32,
+    /// Realtime start of the main process in microseconds, 0 when it is not running.
+    main_start_usec: u64,
+    /// Memory of the control group, None when it is not accounted.
+    memory_bytes: Option<u64>,
+}
+
+impl RuntimeStats {
+    pub fn new(n_restarts: u32, main_start_usec: u64, memory_bytes: Option<u64>) -> Self {
+        Self {
+            n_restarts,
+            main_start_usec,
+            memory_bytes,
+        }
+    }
+
+    pub fn memory_bytes(&self) -> Option<u64> {
+        self.memory_bytes
+    }
+
+    /// e.g. "23.4M", "-" when the memory is not accounted.
+    pub fn formatted_memory(&self) -> String {
+        self.memory_bytes.map_or("-".to_string(), format_bytes)
+    }
+
+    pub fn n_restarts(&self) -> u32 {
+        self.n_restarts
+    }
+
+    /// Seconds the main process has been running, None when it is not.
+    pub fn uptime_secs(&self, now_usec: u64) -> Option<u64> {
+        (self.main_start_usec > 0).then(|| now_usec.saturating_sub(self.main_start_usec) / 1_000_000)
+    }
+
+    /// e.g. "3d 4h", "-" when the main process is not running.
+    pub fn formatted_uptime(&self, now_usec: u64) -> String {
+        self.uptime_secs(now_usec).map_or("-".to_string(), format_duration)
+    }
+}
@@ -0,0 +1,50 @@
+use super::action_policy::PolicyAction;
+
+/// Start of the names of the timers running scheduled actions, telling them from other timers.
+pub const TIMER_PREFIX: &str = "scheduled-";
+
+/// Actions that can be scheduled, in the order the schedule view offers them.
+pub const SCHEDULABLE: [PolicyAction; 4] = [
+    PolicyAction::Start,
+    PolicyAction::Stop,
+    PolicyAction::Restart,
+    PolicyAction::Reload,
+];
+
+/// An action run on a unit by a timer, e.g. restarting myapp.service every night at 02:00 like
+/// `systemd-run --on-calendar=02:00 systemctl restart myapp.service`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledAction {
+    /// The timer, e.g. "scheduled-restart-1739312400.timer".
+    pub timer: String,
+    pub action: PolicyAction,
+    pub unit: String,
+    /// OnCalendar expression, e.g. "02:00" or "Mon *-*-* 03:00".
+    pub calendar: String,
+    /// Next run in microseconds since the epoch, 0 when it will not run again.
+    pub next_elapse: u64,
+}
+
+impl ScheduledAction {
+    /// Name of a new timer for the action, unique by the time it is created.
+    pub fn timer_name(action: PolicyAction, created_sec: u64) -> String {
+        format!("{}{}-{}.timer", TIMER_PREFIX, action.label(), created_sec)
+    }
+
+    /// Description of the timer and of its service, read back by `from_description`.
+    pub fn description(action: PolicyAction, unit: &str) -> String {
+        format!("{} {}", action.label(), unit)
+    }
+
+    /// The action and unit of a timer created to schedule one, from its description.
+    pub fn from_description(description: &str) -> Option<(PolicyAction, String)> {
+        let (label, unit) = description.split_once(' ')?;
+        let action = SCHEDULABLE.into_iter().find(|action| action.label() == label)?;
+        Some((action, unit.to_string()))
+    }
+
+    /// e.g. "restart of nginx.service at 02:00".
+    pub fn describe(&self) -> String {
+        format!("{} of {} at {}", self.action.label(), self.unit, self.calendar)
+    }
+}
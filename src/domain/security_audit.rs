@@ -0,0 +1,107 @@
+/// One check of `systemd-analyze security`, e.g. "PrivateTmp=" and whether the unit passes it.
+#[derive(Debug, Clone)]
+pub struct SecurityFinding {
+    pub passed: bool,
+    pub setting: String,
+    pub description: String,
+    /// What the check adds to the overall exposure, none when it passed.
+    pub exposure: Option<f64>,
+}
+
+/// The findings of `systemd-analyze security` for a unit, with its overall exposure.
+#[derive(Debug, Clone)]
+pub struct SecurityAudit {
+    pub findings: Vec<SecurityFinding>,
+    /// 0.0 for a locked down unit to 10.0 for an unrestricted one.
+    pub exposure: f64,
+    /// "SAFE", "OK", "MEDIUM", "EXPOSED" or "UNSAFE".
+    pub level: String,
+}
+
+/// The overall exposure of a unit, as listed by `systemd-analyze security` without units.
+#[derive(Debug, Clone)]
+pub struct SecurityScore {
+    pub unit: String,
+    pub exposure: f64,
+    pub level: String,
+}
+
+impl SecurityAudit {
+    /// The findings most worth fixing first: failed checks by the exposure they add, then
+    /// the passed ones.
+    pub fn sorted_findings(&self) -> Vec<&SecurityFinding> {
+        let mut findings: Vec<_> = self.findings.iter().collect();
+        findings.sort_by(|a, b| {
+            a.passed
+                .cmp(&b.passed)
+                .then(b.exposure.unwrap_or(0.0).total_cmp(&a.exposure.unwrap_or(0.0)))
+        });
+        findings
+    }
+}
+
+/// Columns of a line of `systemd-analyze security`, which are padded with at least two spaces.
+fn columns(line: &str) -> Vec<&str> {
+    line.split("  ").map(str::trim).filter(|column| !column.is_empty()).collect()
+}
+
+/// The findings of `systemd-analyze security <unit>`, `None` when the output has no overall
+/// exposure line, e.g. for a unit type it does not analyze.
+pub fn parse_security_audit(output: &str) -> Option<SecurityAudit> {
+    let mut findings = Vec::new();
+    let mut overall = None;
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix('→') {
+            // "→ Overall exposure level for cron.service: 9.6 UNSAFE 😨"
+            let (_, score) = rest.rsplit_once(": ")?;
+            let mut words = score.split_whitespace();
+            let exposure = words.next()?.parse::<f64>().ok()?;
+            overall = Some((exposure, words.next().unwrap_or_default().to_string()));
+            continue;
+        }
+        let (passed, rest) = match line.chars().next() {
+            Some('✓') => (true, &line['✓'.len_utf8()..]),
+            Some('✗') => (false, &line['✗'.len_utf8()..]),
+            _ => continue,
+        };
+        let columns = columns(rest);
+        let Some((setting, rest)) = columns.split_first() else {
+            continue;
+        };
+        let (exposure, description) = match rest.split_last() {
+            Some((last, description)) if last.parse::<f64>().is_ok() => (last.parse().ok(), description),
+            _ => (None, rest),
+        };
+        findings.push(SecurityFinding {
+            passed,
+            setting: setting.to_string(),
+            description: description.join(" "),
+            exposure,
+        });
+    }
+    let (exposure, level) = overall?;
+    Some(SecurityAudit {
+        findings,
+        exposure,
+        level,
+    })
+}
+
+/// Units of `systemd-analyze security`, without its header.
+pub fn parse_security_scores(output: &str) -> Vec<SecurityScore> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut words = line.split_whitespace();
+            let unit = words.next()?;
+            let exposure = words.next()?.parse::<f64>().ok()?;
+            let level = words.next()?;
+            Some(SecurityScore {
+                unit: unit.to_string(),
+                exposure,
+                level: level.to_string(),
+            })
+        })
+        .collect()
+}
@@ -1,12 +1,39 @@
-use super::service_property::ServiceProperty;
+use serde::Serialize;
+
+use super::unit_property::UnitProperty;
 use super::service_state::ServiceState;
 
-#[derive(Clone)]
+/// Separates the source from the unit in the names of a merged view, e.g. "containers/web";
+/// neither unit nor container names contain it.
+pub const SOURCE_SEPARATOR: char = '/';
+
+/// The name of a unit of `source` in a merged view.
+pub fn qualified_name(source: &str, name: &str) -> String {
+    format!("{}{}{}", source, SOURCE_SEPARATOR, name)
+}
+
+/// The source part of a name, with its separator, and the unit, e.g. ("session/", "getty@tty1.service").
+fn split_source(name: &str) -> (&str, &str) {
+    match name.rfind(SOURCE_SEPARATOR) {
+        Some(index) => name.split_at(index + 1),
+        None => ("", name),
+    }
+}
+
+#[derive(Clone, Serialize)]
 pub struct Service {
     name: String,
     description: String,
     state: ServiceState,
-    properties: Option<ServiceProperty>,
+    /// The D-Bus object of the unit, e.g. "/org/freedesktop/systemd1/unit/nginx_2eservice", for
+    /// the managers that have one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    object_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    properties: Option<UnitProperty>,
+    /// Other names of the unit whose rows were folded into this one, e.g. "sshd.service".
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    aliases: Vec<String>,
 }
 
 impl Service {
@@ -15,16 +42,88 @@ impl Service {
             name,
             description,
             state,
+            object_path: None,
             properties: None,
+            aliases: Vec::new(),
         }
     }
 
+    /// The same unit with the D-Bus object it is reached through.
+    pub fn with_object_path(mut self, object_path: String) -> Self {
+        self.object_path = Some(object_path);
+        self
+    }
+
+    /// The same unit, also known under the alias names.
+    pub fn with_aliases(mut self, aliases: Vec<String>) -> Self {
+        self.aliases = aliases;
+        self
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
 
+    pub fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    /// The aliases without the ".service" suffix, e.g. "sshd", to match the filter against.
+    pub fn formatted_aliases(&self) -> impl Iterator<Item = &str> {
+        self.aliases.iter().map(|alias| alias.strip_suffix(".service").unwrap_or(alias))
+    }
+
+    /// The name without its source nor the ".service" suffix, e.g. "nginx".
     pub fn formatted_name(&self) -> &str {
-        self.name.strip_suffix(".service").unwrap_or(&self.name)
+        let name = self.name.split_once(SOURCE_SEPARATOR).map_or(self.name.as_str(), |(_, name)| name);
+        name.strip_suffix(".service").unwrap_or(name)
+    }
+
+    /// Where the unit comes from in a merged view, e.g. "session" or "admin@web-1".
+    pub fn source(&self) -> Option<&str> {
+        self.name.split_once(SOURCE_SEPARATOR).map(|(source, _)| source)
+    }
+
+    /// The same unit under its name in a merged view.
+    pub fn qualified(mut self, source: &str) -> Self {
+        self.name = qualified_name(source, &self.name);
+        self
+    }
+
+    /// Whether the unit is a template, e.g. "getty@.service", which only runs as instances.
+    pub fn is_template(&self) -> bool {
+        split_source(&self.name).1.contains("@.")
+    }
+
+    /// The template the unit is an instance of, e.g. "getty@.service" for "getty@tty1.service",
+    /// in the same source.
+    pub fn template(&self) -> Option<String> {
+        let (source, unit) = split_source(&self.name);
+        let (prefix, rest) = unit.split_once('@')?;
+        let (instance, unit_type) = rest.rsplit_once('.')?;
+        (!instance.is_empty()).then(|| format!("{}{}@.{}", source, prefix, unit_type))
+    }
+
+    /// The instance named `instance` of the template, or of the template of an instance, e.g.
+    /// "getty@tty3.service" for "tty3".
+    pub fn instance_name(&self, instance: &str) -> Result<String, String> {
+        let template = if self.is_template() {
+            self.name.clone()
+        } else {
+            self.template().ok_or_else(|| format!("{} is not a template", self.name))?
+        };
+        let instance = instance.trim();
+        if instance.is_empty() {
+            return Err("The instance needs a name".to_string());
+        }
+        if let Some(c) = instance
+            .chars()
+            .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, ':' | '-' | '_' | '.' | '\\')))
+        {
+            return Err(format!("Instance names cannot contain \"{}\", escape it with systemd-escape", c));
+        }
+        let (prefix, unit_type) = template.rsplit_once("@.").unwrap_or((&template, "service"));
+        Ok(format!("{}@{}.{}", prefix, instance, unit_type))
     }
 
     pub fn description(&self) -> &str {
@@ -35,12 +134,15 @@ impl Service {
         &self.state
     }
 
-    #[allow(dead_code)]
-    pub fn properties(&self) -> Option<&ServiceProperty> {
+    pub fn state_mut(&mut self) -> &mut ServiceState {
+        &mut self.state
+    }
+
+    pub fn properties(&self) -> Option<&UnitProperty> {
         self.properties.as_ref()
     }
 
-    pub fn update_properties(&mut self, properties: ServiceProperty) {
+    pub fn update_properties(&mut self, properties: UnitProperty) {
         self.properties = Some(properties);
     }
 }
@@ -0,0 +1,63 @@
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+
+/// Units managed together, e.g. a "web stack" of nginx, php-fpm and redis, as the config names
+/// them.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServiceGroup {
+    pub name: String,
+    /// Units in the order they are started, stopped in reverse; without a type, ".service" is implied.
+    pub units: Vec<String>,
+}
+
+/// A group with its units resolved to unit names, configured or derived from a name prefix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnitGroup {
+    pub name: String,
+    pub units: Vec<String>,
+    /// Made from units sharing the start of their names, e.g. "systemd*".
+    pub derived: bool,
+}
+
+fn unit_name(unit: &str) -> String {
+    if unit.contains('.') { unit.to_string() } else { format!("{}.service", unit) }
+}
+
+/// The start of a unit name shared by related units: "php8.2-fpm.service" gives "php8.2",
+/// "getty@tty1.service" gives "getty". `None` for names without a separator.
+fn name_prefix(unit: &str) -> Option<&str> {
+    let stem = unit.rsplit_once('.').map_or(unit, |(stem, _)| stem);
+    let end = stem.find(['-', '@'])?;
+    (end > 0).then(|| &stem[..end])
+}
+
+/// The configured groups, followed by a group per name prefix shared by two listed units or more
+/// that are in no configured group, by prefix.
+pub fn unit_groups(configured: &[ServiceGroup], listed: &[String]) -> Vec<UnitGroup> {
+    let mut groups: Vec<UnitGroup> = configured
+        .iter()
+        .map(|group| UnitGroup {
+            name: group.name.clone(),
+            units: group.units.iter().map(|unit| unit_name(unit)).collect(),
+            derived: false,
+        })
+        .collect();
+
+    let grouped: HashSet<&str> = groups.iter().flat_map(|group| group.units.iter().map(String::as_str)).collect();
+    let mut by_prefix: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for unit in listed.iter().filter(|unit| !grouped.contains(unit.as_str())) {
+        if let Some(prefix) = name_prefix(unit) {
+            by_prefix.entry(prefix).or_default().push(unit.clone());
+        }
+    }
+    groups.extend(by_prefix.into_iter().filter(|(_, units)| units.len() > 1).map(|(prefix, mut units)| {
+        units.sort();
+        UnitGroup {
+            name: format!("{}*", prefix),
+            units,
+            derived: true,
+        }
+    }));
+    groups
+}
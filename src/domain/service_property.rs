@@ -1,5 +1,15 @@
 #![allow(dead_code)]
-use chrono::DateTime;
+use serde::Serialize;
+
+use super::humanize::{format_duration, format_span, format_timestamp_ago, now_usec};
+use super::process::format_bytes;
+use super::service_security::ServiceSecurity;
+use super::unit_property::PropertySection;
+
+/// A resource limit, u64::MAX standing for no limit.
+fn format_limit(value: u64, format: impl Fn(u64) -> String) -> String {
+    if value == u64::MAX { "infinity".to_string() } else { format(value) }
+}
 
 /// Represents a systemd exec command specification as returned by D-Bus properties
 /// like ExecStart, ExecStop, etc. Each tuple element corresponds to:
@@ -17,7 +27,7 @@ use chrono::DateTime;
 #[allow(clippy::upper_case_acronyms)]
 pub type SASBTTUII = (String, Vec<String>, bool, u64, u64, u64, u64, u32, i32, i32);
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ServiceProperty {
     exec_start: Vec<SASBTTUII>,
     exec_start_pre: Vec<SASBTTUII>,
@@ -36,12 +46,15 @@ pub struct ServiceProperty {
 
     restart: String,
     restart_usec: u64,
+    n_restarts: u32,
+    watchdog_usec: u64,
 
     status_text: String,
     result: String,
 
     user: String,
     group: String,
+    working_directory: String,
 
     limit_cpu: u64,
     limit_nofile: u64,
@@ -49,6 +62,8 @@ pub struct ServiceProperty {
     limit_memlock: u64,
     memory_limit: u64,
     cpu_shares: u64,
+
+    security: ServiceSecurity,
 }
 
 impl ServiceProperty {
@@ -71,12 +86,15 @@ impl ServiceProperty {
 
         restart: String,
         restart_usec: u64,
+        n_restarts: u32,
+        watchdog_usec: u64,
 
         status_text: String,
         result: String,
 
         user: String,
         group: String,
+        working_directory: String,
 
         limit_cpu: u64,
         limit_nofile: u64,
@@ -84,6 +102,8 @@ impl ServiceProperty {
         limit_memlock: u64,
         memory_limit: u64,
         cpu_shares: u64,
+
+        security: ServiceSecurity,
     ) -> Self {
         Self {
             exec_start,
@@ -103,12 +123,15 @@ impl ServiceProperty {
 
             restart,
             restart_usec,
+            n_restarts,
+            watchdog_usec,
 
             status_text,
             result,
 
             user,
             group,
+            working_directory,
 
             limit_cpu,
             limit_nofile,
@@ -116,6 +139,8 @@ impl ServiceProperty {
             limit_memlock,
             memory_limit,
             cpu_shares,
+
+            security,
         }
     }
 
@@ -147,13 +172,122 @@ impl ServiceProperty {
         self.format_exec_field(&self.exec_stop_post)
     }
 
-    pub fn format_timestamp(&self, timestamp: u64) -> String {
-        let naive_datetime = DateTime::from_timestamp(timestamp as i64, 0);
-        match naive_datetime {
-            Some(dt) => dt.format("%Y-%m-%d %H:%M:%S").to_string(),
-            None => "".to_string(),
+    /// The most useful properties grouped by what they are about, humanized: dates, durations
+    /// and sizes instead of microseconds and bytes.
+    pub fn sections(&self) -> Vec<PropertySection> {
+        let mut sections = Vec::new();
+
+        let commands = [
+            ("ExecStartPre", &self.exec_start_pre),
+            ("ExecStart", &self.exec_start),
+            ("ExecStartPost", &self.exec_start_post),
+            ("ExecStop", &self.exec_stop),
+            ("ExecStopPost", &self.exec_stop_post),
+        ];
+        let mut exec: Vec<(&'static str, String)> = commands
+            .into_iter()
+            .flat_map(|(label, field)| {
+                self.format_exec_field(field)
+                    .lines()
+                    .map(|line| (label, line.to_string()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        if let Some(binary) = self.binary_path() {
+            exec.push(("Binary", binary.to_string()));
+        }
+        exec.push((
+            "WorkingDirectory",
+            self.working_directory().unwrap_or("default").to_string(),
+        ));
+        sections.push(("Exec", exec));
+
+        let mut process = vec![(
+            "Main PID",
+            if self.main_pid == 0 { "n/a".to_string() } else { self.main_pid.to_string() },
+        )];
+        if self.control_pid != 0 {
+            process.push(("Control PID", self.control_pid.to_string()));
+        }
+        if self.main_pid != 0 {
+            process.push(("Started", format_timestamp_ago(self.exec_main_start_timestamp, now_usec())));
+        } else if self.exec_main_exit_timestamp > 0 {
+            process.push(("Exited", format_timestamp_ago(self.exec_main_exit_timestamp, now_usec())));
+        }
+        if let Some(exit) = self.formatted_exit() {
+            process.push(("Exit", exit));
+        }
+        if !self.status_text.is_empty() {
+            process.push(("Status", self.status_text.clone()));
+        }
+        process.push(("Result", self.result.clone()));
+        sections.push(("Process", process));
+
+        let mut restart = vec![
+            ("Restart", self.restart.clone()),
+            ("RestartSec", format_span(self.restart_usec)),
+            ("Restarts", self.n_restarts.to_string()),
+        ];
+        if self.watchdog_usec > 0 {
+            restart.push(("Watchdog", format_span(self.watchdog_usec)));
+        }
+        sections.push(("Restart", restart));
+
+        let mut limits = vec![
+            ("MemoryLimit", format_limit(self.memory_limit, format_bytes)),
+            ("LimitCPU", format_limit(self.limit_cpu, format_duration)),
+            ("LimitNOFILE", format_limit(self.limit_nofile, |files| files.to_string())),
+            ("LimitNPROC", format_limit(self.limit_nproc, |processes| processes.to_string())),
+            ("LimitMEMLOCK", format_limit(self.limit_memlock, format_bytes)),
+        ];
+        // u64::MAX when CPUShares= is not set.
+        if self.cpu_shares != u64::MAX {
+            limits.push(("CPUShares", self.cpu_shares.to_string()));
+        }
+        sections.push(("Limits", limits));
+
+        let or_root = |value: &str| if value.is_empty() { "root".to_string() } else { value.to_string() };
+        sections.push(("Identity", vec![("User", or_root(&self.user)), ("Group", or_root(&self.group))]));
+        sections.push(("Security", self.security.summary(&self.user)));
+        sections
+    }
+
+    /// Executable of the first ExecStart= command, `None` for units without one.
+    pub fn binary_path(&self) -> Option<&str> {
+        self.exec_start.first().map(|(path, ..)| path.as_str()).filter(|path| !path.is_empty())
+    }
+
+    /// WorkingDirectory= without the "-" that makes a missing directory no error, `None` when
+    /// unset: the commands then run in "/", or the home of the user for the user manager.
+    pub fn working_directory(&self) -> Option<&str> {
+        let directory = self.working_directory.strip_prefix('-').unwrap_or(&self.working_directory);
+        (!directory.is_empty()).then_some(directory)
+    }
+
+    /// Where a shell debugging the unit starts: its working directory, "~" being the home of its
+    /// user as "~user", and "/" when unset.
+    pub fn shell_directory(&self) -> String {
+        match self.working_directory() {
+            None => "/".to_string(),
+            Some("~") if !self.user.is_empty() => format!("~{}", self.user),
+            Some(directory) => directory.to_string(),
+        }
+    }
+
+    /// How the main process ended, e.g. "exited, status=1" or "killed, signal=9", from its
+    /// CLD_* code; `None` while it has not.
+    fn formatted_exit(&self) -> Option<String> {
+        if self.exec_main_exit_timestamp == 0 {
+            return None;
         }
+        Some(match self.exec_main_code {
+            1 => format!("exited, status={}", self.exec_main_status),
+            2 => format!("killed, signal={}", self.exec_main_status),
+            3 => format!("dumped core, signal={}", self.exec_main_status),
+            code => format!("code={}, status={}", code, self.exec_main_status),
+        })
     }
+
     #[allow(dead_code)]
     pub fn exec_start(&self) -> &Vec<SASBTTUII> {
         &self.exec_start
@@ -204,6 +338,12 @@ impl ServiceProperty {
     pub fn restart_usec(&self) -> u64 {
         self.restart_usec
     }
+    pub fn n_restarts(&self) -> u32 {
+        self.n_restarts
+    }
+    pub fn watchdog_usec(&self) -> u64 {
+        self.watchdog_usec
+    }
 
     pub fn status_text(&self) -> &str {
         &self.status_text
@@ -237,4 +377,8 @@ impl ServiceProperty {
     pub fn cpu_shares(&self) -> u64 {
         self.cpu_shares
     }
+
+    pub fn security(&self) -> &ServiceSecurity {
+        &self.security
+    }
 }
@@ -0,0 +1,107 @@
+use super::service::Service;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Field {
+    /// Active state or sub state, e.g. "failed" or "running".
+    State,
+    Load,
+    /// Unit file state, e.g. "enabled", "static" or "masked".
+    File,
+    Enabled,
+    Frozen,
+}
+
+impl Field {
+    fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "state" => Some(Field::State),
+            "load" => Some(Field::Load),
+            "file" => Some(Field::File),
+            "enabled" => Some(Field::Enabled),
+            "frozen" => Some(Field::Frozen),
+            _ => None,
+        }
+    }
+}
+
+/// `key:value`, `key:a,b` for any of several values, `key:!value` for none of them.
+#[derive(PartialEq)]
+struct Condition {
+    field: Field,
+    values: Vec<String>,
+    negated: bool,
+}
+
+impl Condition {
+    fn matches(&self, service: &Service) -> bool {
+        // Still being typed, e.g. "state:".
+        if self.values.is_empty() {
+            return true;
+        }
+        let state = service.state();
+        let yes_no = |flag: bool| if flag { "yes" } else { "no" };
+        let enabled = matches!(state.file(), "enabled" | "enabled-runtime" | "alias");
+        let found = self.values.iter().any(|value| match self.field {
+            Field::State => state.active() == value || state.sub() == value,
+            Field::Load => state.load() == value,
+            Field::File => state.file() == value,
+            Field::Enabled => yes_no(enabled) == value,
+            Field::Frozen => yes_no(state.is_frozen()) == value,
+        });
+        found != self.negated
+    }
+}
+
+/// A filter such as `nginx state:failed enabled:no`. Words starting with `state:`, `load:`,
+/// `file:`, `enabled:` or `frozen:` match the state of the unit, the rest its name, `|`
+/// separating names of which any may match.
+pub struct ServiceQuery {
+    name: String,
+    conditions: Vec<Condition>,
+}
+
+impl ServiceQuery {
+    pub fn parse(text: &str) -> Self {
+        let mut name = Vec::new();
+        let mut conditions = Vec::new();
+        for word in text.split_whitespace() {
+            let condition = word.split_once(':').and_then(|(key, value)| {
+                let field = Field::from_key(&key.to_lowercase())?;
+                let value = value.to_lowercase();
+                let (negated, value) = match value.strip_prefix('!') {
+                    Some(value) => (true, value.to_string()),
+                    None => (false, value),
+                };
+                Some(Condition {
+                    field,
+                    values: value.split(',').filter(|v| !v.is_empty()).map(str::to_string).collect(),
+                    negated,
+                })
+            });
+            match condition {
+                Some(condition) => conditions.push(condition),
+                None => name.push(word),
+            }
+        }
+        Self {
+            name: name.join(" "),
+            conditions,
+        }
+    }
+
+    /// The names of the query, `nginx|php|redis` matching any of the three.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.name.split('|').map(str::trim)
+    }
+
+    /// Whether every unit matching the query also matches `previous`, the name being typed
+    /// further with the same conditions, so that only the matches of `previous` are checked.
+    pub fn narrows(&self, previous: &ServiceQuery) -> bool {
+        self.conditions == previous.conditions && !self.name.contains('|') && self.name.starts_with(&previous.name)
+    }
+
+    /// Whether the unit state satisfies every `key:value` condition of the query.
+    pub fn matches_state(&self, service: &Service) -> bool {
+        self.conditions.iter().all(|condition| condition.matches(service))
+    }
+}
@@ -1,18 +1,205 @@
+use crate::errors::ServiceError;
+use crate::infrastructure::call_limits::CallLimits;
 use crate::infrastructure::systemd_service_adapter::ConnectionType;
 
-use super::{service::Service, service_property::ServiceProperty};
-use std::error::Error;
+use super::{
+    action_journal::UnitFileChange,
+    action_plan::{ActionPlan, UnitAction},
+    action_policy::PolicyAction,
+    boot_timing::{ChainLink, UnitActivation},
+    crash_loop::RestartSettings,
+    process::UnitProcess,
+    resource_limits::ResourceLimits,
+    resource_usage::ResourceUsage,
+    runtime_stats::RuntimeStats,
+    scheduled_action::ScheduledAction,
+    security_audit::{SecurityAudit, SecurityScore},
+    system_summary::SystemSummary,
+    job::JobResult,
+    journal_boot::ListedBoot,
+    journal_entry::{JournalEntry, LogFilter},
+    kill::KillTarget,
+    power_action::PowerAction,
+    latency::ListTimings,
+    unit_alias::UnitLinks,
+    unit_capabilities::UnitCapabilities,
+    unit_conditions::UnitConditions,
+    unit_history::UnitHistory,
+    service::Service, slice::Slice, unit_dependencies::UnitDependencies, unit_environment::UnitEnvironment, unit_file::{UnitFileMatch, UnitFilePaths}, unit_path::UnitPathReference,
+    unit_property::{SocketProperty, TimerProperty, UnitProperty},
+    unit_transition::UnitTransition,
+    unit_triggers::UnitTriggers,
+    unit_verification::VerifyFinding,
+};
+use std::collections::HashMap;
+use std::process::Command;
+use std::sync::mpsc::Sender;
 
-pub trait ServiceRepository {
-    fn list_services(&self) -> Result<Vec<Service>, Box<dyn Error>>;
-    fn get_service_log(&self, name: &str) -> Result<String, Box<dyn Error>>;
-    fn start_service(&self, name: &str) -> Result<(), Box<dyn Error>>;
-    fn stop_service(&self, name: &str) -> Result<(), Box<dyn Error>>;
-    fn restart_service(&self, name: &str) -> Result<(), Box<dyn Error>>;
-    fn enable_service(&self, name: &str) -> Result<(), Box<dyn Error>>;
-    fn disable_service(&self, name: &str) -> Result<(), Box<dyn Error>>;
-    fn reload_daemon(&self) -> Result<(), Box<dyn std::error::Error>>;
-    fn get_service_property(&self, name: &str) -> Result<ServiceProperty, Box<dyn std::error::Error>>;
-    fn change_connection(&mut self, connection_type: ConnectionType) -> Result<(), zbus::Error>;
-    fn systemctl_cat(&self, name: &str) -> Result<String, Box<dyn Error>>;
+pub trait ServiceRepository: Send {
+    fn list_services(&self) -> Result<Vec<Service>, ServiceError>;
+    /// Times what `list_services` does, separating the listing call from the per-unit state calls.
+    fn time_listing(&self) -> Result<ListTimings, ServiceError>;
+    fn get_service_log(&self, name: &str, lines: u32) -> Result<String, ServiceError>;
+    /// The last `lines` entries of the unit matching `filter`, oldest first.
+    fn get_journal_entries(&self, name: &str, lines: u32, filter: &LogFilter) -> Result<Vec<JournalEntry>, ServiceError>;
+    /// The boots the journal has entries of, newest first, like `journalctl --list-boots`.
+    fn get_journal_boots(&self) -> Result<Vec<ListedBoot>, ServiceError>;
+    /// Up to `lines` entries logged before the one at `cursor`, oldest first, to page back
+    /// through the log view.
+    fn get_older_journal_entries(&self, name: &str, cursor: &str, lines: u32, filter: &LogFilter) -> Result<Vec<JournalEntry>, ServiceError>;
+    /// Realtime timestamps (microseconds) of the entries shown by `get_service_log`.
+    fn get_log_timestamps(&self, name: &str) -> Result<Vec<u64>, ServiceError>;
+    /// Unit name of every journal entry written during the last `minutes` minutes.
+    fn get_journal_units_since(&self, minutes: u64) -> Result<Vec<String>, ServiceError>;
+    /// Starts the unit, returning the object path of the queued job, `None` when there is no job
+    /// to wait for. Same for `stop_service` and `restart_service`.
+    fn start_service(&self, name: &str) -> Result<Option<String>, ServiceError>;
+    /// Runs `argv` as a new transient service named `name`, with the limits set, like
+    /// `systemd-run`. Returns the job starting it.
+    fn start_transient_unit(&self, name: &str, argv: &[String], limits: &ResourceLimits) -> Result<Option<String>, ServiceError>;
+    fn stop_service(&self, name: &str) -> Result<Option<String>, ServiceError>;
+    fn restart_service(&self, name: &str) -> Result<Option<String>, ServiceError>;
+    /// Asks the unit to reload its configuration without stopping, like `systemctl reload`.
+    fn reload_service(&self, name: &str) -> Result<Option<String>, ServiceError>;
+    /// Reloads the unit when it can, restarts it otherwise, like `systemctl reload-or-restart`.
+    fn reload_or_restart_service(&self, name: &str) -> Result<Option<String>, ServiceError>;
+    /// Which of start, stop, reload and isolate the manager accepts for the unit, e.g. no
+    /// reload for services without ExecReload=.
+    fn get_unit_capabilities(&self, name: &str) -> Result<UnitCapabilities, ServiceError>;
+    /// Sends the result of every job finishing from now on, until the repository is dropped.
+    fn watch_jobs(&mut self, sender: Sender<JobResult>) -> Result<(), ServiceError>;
+    /// Sends the active state changes of `units` from now on, replacing the units watched
+    /// before; none stops watching.
+    fn watch_units(&mut self, units: &[String], sender: Sender<UnitTransition>) -> Result<(), ServiceError>;
+    /// Enables the unit, returning the links created, empty when the manager has no such links.
+    fn enable_service(&self, name: &str) -> Result<Vec<UnitFileChange>, ServiceError>;
+    /// Disables the unit, returning the links removed, empty when the manager has no such links.
+    fn disable_service(&self, name: &str) -> Result<Vec<UnitFileChange>, ServiceError>;
+    /// Undoes the changes of an enable or disable of the unit: the links created are removed,
+    /// those removed point to the unit file again.
+    fn revert_unit_file_changes(&self, name: &str, changes: &[UnitFileChange]) -> Result<(), ServiceError>;
+    /// Pauses every process of the unit with the cgroup freezer, without stopping it.
+    fn freeze_service(&self, name: &str) -> Result<(), ServiceError>;
+    fn thaw_service(&self, name: &str) -> Result<(), ServiceError>;
+    /// Clears the failed state of the unit, like `systemctl reset-failed <unit>`.
+    fn reset_failed_unit(&self, name: &str) -> Result<(), ServiceError>;
+    /// Clears the failed state of every unit.
+    fn reset_failed(&self) -> Result<(), ServiceError>;
+    fn kill_service(&self, name: &str, target: KillTarget, signal: i32) -> Result<(), ServiceError>;
+    fn reload_daemon(&self) -> Result<(), ServiceError>;
+    /// Properties of the unit's type interface (Service, Timer, Socket or Mount).
+    fn get_unit_property(&self, name: &str) -> Result<UnitProperty, ServiceError>;
+    /// Restart settings of a service, with its NRestarts counter.
+    fn get_restart_settings(&self, name: &str) -> Result<RestartSettings, ServiceError>;
+    /// Restart counter and main process start of a service.
+    fn get_runtime_stats(&self, name: &str) -> Result<RuntimeStats, ServiceError>;
+    /// CPU time and memory of the control group of a unit, sampled for the usage graphs.
+    fn get_resource_usage(&self, name: &str) -> Result<ResourceUsage, ServiceError>;
+    /// Processes of the control group of the unit, like `systemctl status` shows them.
+    fn get_unit_processes(&self, name: &str) -> Result<Vec<UnitProcess>, ServiceError>;
+    /// When the unit entered its current active state, in microseconds, 0 when it never did.
+    fn get_state_change_timestamp(&self, name: &str) -> Result<u64, ServiceError>;
+    /// Whether the unit file or a drop-in changed on disk since the unit was loaded, read again
+    /// rather than taken from the list, which may not ask every unit for it.
+    fn get_need_daemon_reload(&self, name: &str) -> Result<bool, ServiceError>;
+    /// Freezer state of a unit, e.g. "running" or "frozen", read again rather than taken from
+    /// the list, which may not ask every unit for it.
+    fn get_freezer_state(&self, name: &str) -> Result<String, ServiceError>;
+    /// Active slices with the resources of their control group and the units placed in them.
+    fn get_slices(&self) -> Result<Vec<Slice>, ServiceError>;
+    /// How long the last boot took, like `systemd-analyze time`.
+    fn get_boot_time(&self) -> Result<String, ServiceError>;
+    /// Units loaded, failed and jobs queued by the manager, with its state, the virtualization
+    /// it runs under and when the machine booted.
+    fn get_system_summary(&self) -> Result<SystemSummary, ServiceError>;
+    /// Units by the time they took to activate during the boot, slowest first.
+    fn get_boot_blame(&self) -> Result<Vec<UnitActivation>, ServiceError>;
+    /// Units the activation of `unit`, or of the default target, waited for.
+    fn get_critical_chain(&self, unit: Option<&str>) -> Result<Vec<ChainLink>, ServiceError>;
+    /// Overall exposure of every service, like `systemd-analyze security`.
+    fn get_security_scores(&self) -> Result<Vec<SecurityScore>, ServiceError>;
+    /// Checks of the sandboxing of a service, like `systemd-analyze security <unit>`.
+    fn get_security_audit(&self, name: &str) -> Result<SecurityAudit, ServiceError>;
+    fn change_connection(&mut self, connection_type: ConnectionType) -> Result<(), ServiceError>;
+    /// Whether the connection to the service manager was closed, every call failing until `reconnect`.
+    fn connection_lost(&self) -> bool;
+    fn reconnect(&mut self) -> Result<(), ServiceError>;
+    /// Another handle on the same manager, for the thread running the queued actions; it shares
+    /// the connection where there is one.
+    fn worker(&self) -> Result<Box<dyn ServiceRepository>, ServiceError>;
+    /// Bounds the slow calls, listing the units or reading the logs, see `CallLimits`.
+    fn set_call_limits(&mut self, limits: CallLimits);
+    /// Opaque value that changes whenever the unit is (re)started, changes state or is reloaded,
+    /// so results fetched for an older generation can be reused. `None` disables caching.
+    fn unit_generation(&self, name: &str) -> Option<String>;
+    fn systemctl_cat(&self, name: &str) -> Result<String, ServiceError>;
+    /// Paths of the unit file and drop-ins `systemctl_cat` prints, in the order they apply.
+    fn get_unit_file_paths(&self, name: &str) -> Result<UnitFilePaths, ServiceError>;
+    /// Alias names of the unit and the symlinks to its unit file, enabling it or naming it.
+    fn get_unit_links(&self, name: &str) -> Result<UnitLinks, ServiceError>;
+    /// Values of the given settings in the loaded unit, as `systemctl show -p` prints them.
+    fn get_loaded_settings(&self, name: &str, settings: &[String]) -> Result<HashMap<String, Vec<String>>, ServiceError>;
+    /// Problems of the unit file and drop-ins on disk, like `systemd-analyze verify`, none when
+    /// the unit would load and start cleanly.
+    fn verify_unit(&self, name: &str) -> Result<Vec<VerifyFinding>, ServiceError>;
+    /// Writes a new unit file where the manager looks for administrator units, returning its path.
+    /// Existing units are never replaced.
+    fn create_unit(&self, name: &str, content: &str) -> Result<String, ServiceError>;
+    /// Writes, or replaces, a drop-in of the unit where the manager looks for administrator
+    /// units, returning its path.
+    fn write_drop_in(&self, name: &str, file_name: &str, content: &str) -> Result<String, ServiceError>;
+    /// Command opening the whole unit file in the editor of the user, like `systemctl edit --full`,
+    /// reloading the manager once saved. It needs the terminal.
+    fn edit_unit_command(&self, name: &str) -> Result<Command, ServiceError>;
+    /// Command starting the shell of the user in a directory of the host of the unit, e.g. its
+    /// working directory, where "~" and "~user" are homes. It needs the terminal.
+    fn shell_command(&self, name: &str, directory: &str) -> Result<Command, ServiceError>;
+    /// Removes a drop-in `write_drop_in` wrote for the unit, with its directory once empty.
+    fn remove_drop_in(&self, name: &str, path: &str) -> Result<(), ServiceError>;
+    /// Variables and environment files the commands of the unit run with.
+    fn get_unit_environment(&self, name: &str) -> Result<UnitEnvironment, ServiceError>;
+    /// CPUQuota, MemoryMax and TasksMax of the unit, empty for unit types without a cgroup.
+    fn get_resource_limits(&self, name: &str) -> Result<ResourceLimits, ServiceError>;
+    /// Sets the limits of the unit at once, until the next reboot when `runtime` is set, or
+    /// in a persistent drop-in of the manager otherwise, like `systemctl set-property`.
+    fn set_resource_limits(&self, name: &str, limits: &ResourceLimits, runtime: bool) -> Result<(), ServiceError>;
+    fn list_unit_paths(&self) -> Result<Vec<UnitPathReference>, ServiceError>;
+    /// Lines of the unit files and drop-ins of the listed unit types containing `query`,
+    /// ignoring case.
+    fn search_unit_files(&self, query: &str) -> Result<Vec<UnitFileMatch>, ServiceError>;
+    fn get_unit_dependencies(&self, name: &str) -> Result<UnitDependencies, ServiceError>;
+    /// Timers triggering the unit, by name, with their properties.
+    fn get_unit_timers(&self, name: &str) -> Result<Vec<(String, TimerProperty)>, ServiceError>;
+    /// Sockets triggering the unit, by name, with their properties.
+    fn get_unit_sockets(&self, name: &str) -> Result<Vec<(String, SocketProperty)>, ServiceError>;
+    /// Units the unit activates and the timers, sockets and paths activating it, by name.
+    fn get_unit_triggers(&self, name: &str) -> Result<UnitTriggers, ServiceError>;
+    /// The Condition*= settings of the unit and whether they held when it was last started.
+    fn get_unit_conditions(&self, name: &str) -> Result<UnitConditions, ServiceError>;
+    /// Starts, stops and failures of the unit over the last `HISTORY_DAYS` days, from the journal.
+    fn get_unit_history(&self, name: &str) -> Result<UnitHistory, ServiceError>;
+    /// What systemd and the kernel logged about the OOM killer and the unit over the last
+    /// `HISTORY_DAYS` days, oldest first.
+    fn get_oom_entries(&self, name: &str) -> Result<Vec<JournalEntry>, ServiceError>;
+    /// The references of the Documentation= setting of the unit, e.g. "man:sshd(8)".
+    fn get_unit_documentation(&self, name: &str) -> Result<Vec<String>, ServiceError>;
+    /// Target units, loaded or only installed (e.g. rescue.target), like `systemctl list-units
+    /// --type=target --all`.
+    fn list_targets(&self) -> Result<Vec<Service>, ServiceError>;
+    /// The target the machine boots into, like `systemctl get-default`.
+    fn get_default_target(&self) -> Result<String, ServiceError>;
+    fn set_default_target(&self, name: &str) -> Result<(), ServiceError>;
+    /// Starts the target and stops every unit it does not need, like `systemctl isolate`.
+    /// Returns the job starting it.
+    fn isolate_target(&self, name: &str) -> Result<Option<String>, ServiceError>;
+    /// Reboots or powers off the machine, stopping its units first.
+    fn power(&self, action: PowerAction) -> Result<(), ServiceError>;
+    /// Describes what `action` would do on the unit without performing it.
+    fn plan_action(&self, action: UnitAction, name: &str) -> Result<ActionPlan, ServiceError>;
+    /// Runs the action on the unit whenever the OnCalendar expression elapses, through a transient
+    /// timer. Returns the timer.
+    fn schedule_action(&self, action: PolicyAction, name: &str, calendar: &str) -> Result<String, ServiceError>;
+    fn list_scheduled_actions(&self) -> Result<Vec<ScheduledAction>, ServiceError>;
+    /// Stops the timer of a scheduled action, which unloads it.
+    fn cancel_scheduled_action(&self, timer: &str) -> Result<(), ServiceError>;
 }
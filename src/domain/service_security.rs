@@ -0,0 +1,142 @@
+use serde::Serialize;
+
+/// Capability names by bit, as in linux/capability.h.
+const CAPABILITIES: [&str; 41] = [
+    "CAP_CHOWN", "CAP_DAC_OVERRIDE", "CAP_DAC_READ_SEARCH", "CAP_FOWNER", "CAP_FSETID", "CAP_KILL",
+    "CAP_SETGID", "CAP_SETUID", "CAP_SETPCAP", "CAP_LINUX_IMMUTABLE", "CAP_NET_BIND_SERVICE",
+    "CAP_NET_BROADCAST", "CAP_NET_ADMIN", "CAP_NET_RAW", "CAP_IPC_LOCK", "CAP_IPC_OWNER", "CAP_SYS_MODULE",
+    "CAP_SYS_RAWIO", "CAP_SYS_CHROOT", "CAP_SYS_PTRACE", "CAP_SYS_PACCT", "CAP_SYS_ADMIN", "CAP_SYS_BOOT",
+    "CAP_SYS_NICE", "CAP_SYS_RESOURCE", "CAP_SYS_TIME", "CAP_SYS_TTY_CONFIG", "CAP_MKNOD", "CAP_LEASE",
+    "CAP_AUDIT_WRITE", "CAP_AUDIT_CONTROL", "CAP_SETFCAP", "CAP_MAC_OVERRIDE", "CAP_MAC_ADMIN", "CAP_SYSLOG",
+    "CAP_WAKE_ALARM", "CAP_BLOCK_SUSPEND", "CAP_AUDIT_READ", "CAP_PERFMON", "CAP_BPF", "CAP_CHECKPOINT_RESTORE",
+];
+/// Every capability the kernel knows of, the bounding set of a unit that does not restrict it.
+const ALL_CAPABILITIES: u64 = (1 << CAPABILITIES.len()) - 1;
+/// Capabilities that amount to root on their own.
+const CAP_SYS_ADMIN: u64 = 1 << 21;
+const CAP_SYS_MODULE: u64 = 1 << 16;
+const CAP_SYS_PTRACE: u64 = 1 << 19;
+
+/// The control group and sandboxing settings of a service, from its D-Bus properties.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ServiceSecurity {
+    control_group: String,
+    slice: String,
+    delegate: bool,
+    dynamic_user: bool,
+    /// "no", "yes", "full" or "strict".
+    protect_system: String,
+    /// "no", "yes", "read-only" or "tmpfs".
+    protect_home: String,
+    no_new_privileges: bool,
+    /// Bit mask of the capabilities the processes may ever get.
+    capability_bounding_set: u64,
+}
+
+impl ServiceSecurity {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        control_group: String,
+        slice: String,
+        delegate: bool,
+        dynamic_user: bool,
+        protect_system: String,
+        protect_home: String,
+        no_new_privileges: bool,
+        capability_bounding_set: u64,
+    ) -> Self {
+        Self {
+            control_group,
+            slice,
+            delegate,
+            dynamic_user,
+            protect_system,
+            protect_home,
+            no_new_privileges,
+            capability_bounding_set,
+        }
+    }
+
+    /// Rough exposure from 0.0 (locked down) to 10.0 (unrestricted root), weighing the settings
+    /// read here like `systemd-analyze security` weighs all of its checks. `user` is the User=
+    /// of the service, empty for root.
+    pub fn exposure(&self, user: &str) -> f64 {
+        let root = !self.dynamic_user && matches!(user, "" | "root" | "0");
+        let mut exposure = if root { 2.5 } else { 0.0 };
+        if !self.no_new_privileges {
+            exposure += 1.0;
+        }
+        exposure += match self.protect_system.as_str() {
+            "strict" => 0.0,
+            "full" => 0.5,
+            "yes" => 1.0,
+            _ => 2.0,
+        };
+        exposure += match self.protect_home.as_str() {
+            "yes" => 0.0,
+            "read-only" | "tmpfs" => 0.5,
+            _ => 1.5,
+        };
+        let capabilities = self.capability_bounding_set & ALL_CAPABILITIES;
+        exposure += if capabilities == ALL_CAPABILITIES {
+            2.5
+        } else if capabilities & (CAP_SYS_ADMIN | CAP_SYS_MODULE | CAP_SYS_PTRACE) != 0 {
+            1.5
+        } else if capabilities != 0 {
+            0.5
+        } else {
+            0.0
+        };
+        if self.delegate {
+            exposure += 0.5;
+        }
+        exposure
+    }
+
+    /// The level `systemd-analyze security` would print for the exposure.
+    pub fn exposure_level(exposure: f64) -> &'static str {
+        match exposure {
+            e if e < 1.0 => "SAFE",
+            e if e < 4.0 => "OK",
+            e if e < 7.0 => "MEDIUM",
+            e if e < 9.0 => "EXPOSED",
+            _ => "UNSAFE",
+        }
+    }
+
+    /// The capabilities of the bounding set, "all" when it is not restricted.
+    pub fn formatted_capabilities(&self) -> String {
+        let capabilities = self.capability_bounding_set & ALL_CAPABILITIES;
+        if capabilities == ALL_CAPABILITIES {
+            return "all".to_string();
+        }
+        if capabilities == 0 {
+            return "none".to_string();
+        }
+        CAPABILITIES
+            .iter()
+            .enumerate()
+            .filter(|(bit, _)| capabilities & (1 << bit) != 0)
+            .map(|(_, name)| *name)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The lines of the "Security" section of the properties tab, the exposure first.
+    pub fn summary(&self, user: &str) -> Vec<(&'static str, String)> {
+        let exposure = self.exposure(user);
+        let or_no = |value: &str| if value.is_empty() { "no".to_string() } else { value.to_string() };
+        let yes_no = |value: bool| if value { "yes" } else { "no" }.to_string();
+        vec![
+            ("Exposure", format!("{:.1} {}", exposure, Self::exposure_level(exposure))),
+            ("ControlGroup", self.control_group.clone()),
+            ("Slice", self.slice.clone()),
+            ("Delegate", yes_no(self.delegate)),
+            ("DynamicUser", yes_no(self.dynamic_user)),
+            ("ProtectSystem", or_no(&self.protect_system)),
+            ("ProtectHome", or_no(&self.protect_home)),
+            ("NoNewPrivileges", yes_no(self.no_new_privileges)),
+            ("Capabilities", self.formatted_capabilities()),
+        ]
+    }
+}
@@ -1,21 +1,45 @@
-#[derive(Clone)]
+use serde::Serialize;
+
+#[derive(Clone, Serialize)]
 pub struct ServiceState {
     load: String,
     active: String,
     sub: String,
     file: String,
+    preset: String,
+    /// cgroup freezer state: "running", "freezing", "frozen" or "thawing", empty when unknown.
+    freezer: String,
+    /// Whether the unit file or a drop-in changed on disk since systemd loaded the unit.
+    need_daemon_reload: bool,
 }
 
 impl ServiceState {
-    pub fn new(load: String, active: String, sub: String, file: String) -> Self {
+    pub fn new(load: String, active: String, sub: String, file: String, preset: String, freezer: String) -> Self {
         ServiceState {
             load,
             active,
             sub,
             file,
+            preset,
+            freezer,
+            need_daemon_reload: false,
         }
     }
 
+    /// The same state, the unit files having changed on disk since the unit was loaded or not.
+    pub fn with_need_daemon_reload(mut self, need_daemon_reload: bool) -> Self {
+        self.need_daemon_reload = need_daemon_reload;
+        self
+    }
+
+    pub fn set_need_daemon_reload(&mut self, need_daemon_reload: bool) {
+        self.need_daemon_reload = need_daemon_reload;
+    }
+
+    pub fn set_freezer(&mut self, freezer: String) {
+        self.freezer = freezer;
+    }
+
     pub fn load(&self) -> &str {
         &self.load
     }
@@ -31,4 +55,27 @@ impl ServiceState {
     pub fn file(&self) -> &str {
         &self.file
     }
+
+    pub fn preset(&self) -> &str {
+        &self.preset
+    }
+
+    pub fn freezer(&self) -> &str {
+        &self.freezer
+    }
+
+    pub fn need_daemon_reload(&self) -> bool {
+        self.need_daemon_reload
+    }
+
+    /// Whether the processes of the unit are paused, or being paused, by the cgroup freezer.
+    pub fn is_frozen(&self) -> bool {
+        matches!(self.freezer.as_str(), "frozen" | "freezing")
+    }
+
+    /// Whether the unit was manually enabled/disabled against its vendor preset.
+    pub fn deviates_from_preset(&self) -> bool {
+        let toggleable = |state: &str| matches!(state, "enabled" | "disabled");
+        toggleable(&self.file) && toggleable(&self.preset) && self.file != self.preset
+    }
 }
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Version of the recording format, written in the header.
+pub const RECORDING_VERSION: u32 = 1;
+
+/// First line of a recording: the size of the terminal and when the session started, in
+/// seconds since the epoch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingHeader {
+    pub version: u32,
+    pub width: u16,
+    pub height: u16,
+    pub timestamp: i64,
+}
+
+/// What happened `time` seconds into a session: a key pressed, written like the keys of
+/// `--screenshot`, or a screen drawn, as text without colors.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum RecordedEvent {
+    Key { time: f64, key: String },
+    Screen { time: f64, screen: String },
+}
+
+impl RecordedEvent {
+    pub fn time(&self) -> f64 {
+        match self {
+            RecordedEvent::Key { time, .. } | RecordedEvent::Screen { time, .. } => *time,
+        }
+    }
+}
+
+/// A session of the TUI as `--record` writes it, one JSON value per line: the header, then
+/// the keys and screens in the order they happened.
+#[derive(Debug, Clone)]
+pub struct Recording {
+    pub header: RecordingHeader,
+    pub events: Vec<RecordedEvent>,
+}
+
+impl Recording {
+    /// Reads a recording; a last line cut short, e.g. by a crash, is left out.
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+        let header: RecordingHeader = lines
+            .next()
+            .ok_or("The recording is empty")
+            .and_then(|line| serde_json::from_str(line).map_err(|_| "The first line is not the header of a recording"))?;
+        if header.version != RECORDING_VERSION {
+            return Err(format!("Recordings of version {} cannot be read", header.version));
+        }
+        let lines: Vec<&str> = lines.collect();
+        let mut events = Vec::new();
+        for (index, line) in lines.iter().enumerate() {
+            match serde_json::from_str(line) {
+                Ok(event) => events.push(event),
+                Err(_) if index + 1 == lines.len() => break,
+                Err(e) => return Err(format!("Line {}: {}", index + 2, e)),
+            }
+        }
+        Ok(Self { header, events })
+    }
+
+    /// The keys pressed, separated by spaces, to press again with `--screenshot`.
+    pub fn key_script(&self) -> String {
+        self.events
+            .iter()
+            .filter_map(|event| match event {
+                RecordedEvent::Key { key, .. } => Some(key.as_str()),
+                RecordedEvent::Screen { .. } => None,
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The screens as an asciinema cast (version 2), each one clearing the previous; the
+    /// keys go in as markers, the chapters of the player.
+    pub fn to_cast(&self) -> String {
+        let header = json!({
+            "version": 2,
+            "width": self.header.width,
+            "height": self.header.height,
+            "timestamp": self.header.timestamp,
+        });
+        let mut lines = vec![header.to_string()];
+        lines.extend(self.events.iter().map(|event| match event {
+            RecordedEvent::Key { time, key } => json!([time, "m", key]).to_string(),
+            RecordedEvent::Screen { time, screen } => {
+                json!([time, "o", format!("\u{1b}[H\u{1b}[2J{}", screen.replace('\n', "\r\n"))]).to_string()
+            }
+        }));
+        lines.join("\n") + "\n"
+    }
+}
@@ -0,0 +1,53 @@
+use super::resource_usage::ResourceUsage;
+
+/// A slice unit, with the resources of its control group, which include the slices beneath it.
+#[derive(Debug, Clone)]
+pub struct Slice {
+    pub name: String,
+    pub usage: ResourceUsage,
+    /// Units placed directly in the slice, by name, without the slices beneath it.
+    pub units: Vec<String>,
+}
+
+/// The slice `name` is beneath, from its name: "system-getty.slice" is in "system.slice" and the
+/// top ones in the root slice "-.slice", which is in none.
+pub fn parent_slice(name: &str) -> Option<String> {
+    let stem = name.strip_suffix(".slice")?;
+    if stem == "-" {
+        return None;
+    }
+    Some(match stem.rsplit_once('-') {
+        Some((parent, _)) => format!("{}.slice", parent),
+        None => "-.slice".to_string(),
+    })
+}
+
+/// The slices depth first with their depth, each followed by the ones beneath it by name. A
+/// slice whose parent is not listed, e.g. not active, is at the top.
+pub fn slice_tree(slices: &[Slice]) -> Vec<(usize, &Slice)> {
+    fn push<'a>(slices: &'a [Slice], slice: &'a Slice, depth: usize, tree: &mut Vec<(usize, &'a Slice)>) {
+        tree.push((depth, slice));
+        let mut children: Vec<&Slice> = slices
+            .iter()
+            .filter(|child| parent_slice(&child.name).as_deref() == Some(slice.name.as_str()))
+            .collect();
+        children.sort_by(|a, b| a.name.cmp(&b.name));
+        for child in children {
+            push(slices, child, depth + 1, tree);
+        }
+    }
+
+    let mut roots: Vec<&Slice> = slices
+        .iter()
+        .filter(|slice| match parent_slice(&slice.name) {
+            Some(parent) => !slices.iter().any(|other| other.name == parent),
+            None => true,
+        })
+        .collect();
+    roots.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut tree = Vec::new();
+    for root in roots {
+        push(slices, root, 0, &mut tree);
+    }
+    tree
+}
@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::service::Service;
+
+/// States of a unit recorded in a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnitSnapshot {
+    pub active: String,
+    pub sub: String,
+    pub file: String,
+}
+
+impl UnitSnapshot {
+    fn is_failed(&self) -> bool {
+        self.active == "failed"
+    }
+
+    fn is_running(&self) -> bool {
+        matches!(self.active.as_str(), "active" | "activating" | "reloading")
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.file.starts_with("enabled")
+    }
+
+    /// e.g. "active (running), enabled".
+    pub fn describe(&self) -> String {
+        format!("{} ({}), {}", self.active, self.sub, self.file)
+    }
+}
+
+/// The states of every unit at a point in time, to compare with later, e.g. across an upgrade.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// RFC 3339 time the snapshot was taken at.
+    pub taken_at: String,
+    pub units: BTreeMap<String, UnitSnapshot>,
+}
+
+/// How a unit differs from the snapshot, most worrying first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChangeKind {
+    NewlyFailed,
+    Removed,
+    Stopped,
+    NewlyDisabled,
+    NewlyEnabled,
+    Started,
+    Recovered,
+    Added,
+    Changed,
+}
+
+impl ChangeKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChangeKind::NewlyFailed => "newly failed",
+            ChangeKind::Removed => "removed",
+            ChangeKind::Stopped => "stopped",
+            ChangeKind::NewlyDisabled => "newly disabled",
+            ChangeKind::NewlyEnabled => "newly enabled",
+            ChangeKind::Started => "started",
+            ChangeKind::Recovered => "recovered",
+            ChangeKind::Added => "added",
+            ChangeKind::Changed => "changed",
+        }
+    }
+
+    /// Whether the change may need attention rather than being expected.
+    pub fn is_regression(&self) -> bool {
+        matches!(self, ChangeKind::NewlyFailed | ChangeKind::Removed | ChangeKind::Stopped | ChangeKind::NewlyDisabled)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UnitChange {
+    pub unit: String,
+    pub kind: ChangeKind,
+    pub before: Option<UnitSnapshot>,
+    pub after: Option<UnitSnapshot>,
+}
+
+impl UnitChange {
+    /// e.g. "active (running), enabled -> failed (failed), enabled".
+    pub fn describe(&self) -> String {
+        let describe = |unit: &Option<UnitSnapshot>| unit.as_ref().map_or("absent".to_string(), UnitSnapshot::describe);
+        format!("{} -> {}", describe(&self.before), describe(&self.after))
+    }
+}
+
+fn change_kind(before: &UnitSnapshot, after: &UnitSnapshot) -> Option<ChangeKind> {
+    if before == after {
+        return None;
+    }
+    let kind = if after.is_failed() && !before.is_failed() {
+        ChangeKind::NewlyFailed
+    } else if before.is_failed() && !after.is_failed() {
+        ChangeKind::Recovered
+    } else if before.is_enabled() && !after.is_enabled() {
+        ChangeKind::NewlyDisabled
+    } else if after.is_enabled() && !before.is_enabled() {
+        ChangeKind::NewlyEnabled
+    } else if before.is_running() && !after.is_running() {
+        ChangeKind::Stopped
+    } else if after.is_running() && !before.is_running() {
+        ChangeKind::Started
+    } else {
+        ChangeKind::Changed
+    };
+    Some(kind)
+}
+
+impl Snapshot {
+    pub fn new(services: &[Service], taken_at: String) -> Self {
+        let units = services
+            .iter()
+            .map(|service| {
+                let state = service.state();
+                (
+                    service.name().to_string(),
+                    UnitSnapshot {
+                        active: state.active().to_string(),
+                        sub: state.sub().to_string(),
+                        file: state.file().to_string(),
+                    },
+                )
+            })
+            .collect();
+        Self { taken_at, units }
+    }
+
+    /// Units whose state differs in `current`, most worrying first, then by name.
+    pub fn diff(&self, current: &Snapshot) -> Vec<UnitChange> {
+        let mut changes: Vec<UnitChange> = self
+            .units
+            .iter()
+            .filter_map(|(unit, before)| {
+                let kind = match current.units.get(unit) {
+                    Some(after) => change_kind(before, after)?,
+                    None => ChangeKind::Removed,
+                };
+                Some(UnitChange {
+                    unit: unit.clone(),
+                    kind,
+                    before: Some(before.clone()),
+                    after: current.units.get(unit).cloned(),
+                })
+            })
+            .collect();
+        changes.extend(
+            current
+                .units
+                .iter()
+                .filter(|(unit, _)| !self.units.contains_key(*unit))
+                .map(|(unit, after)| UnitChange {
+                    unit: unit.clone(),
+                    kind: if after.is_failed() { ChangeKind::NewlyFailed } else { ChangeKind::Added },
+                    before: None,
+                    after: Some(after.clone()),
+                }),
+        );
+        changes.sort_by(|a, b| a.kind.cmp(&b.kind).then_with(|| a.unit.cmp(&b.unit)));
+        changes
+    }
+}
@@ -0,0 +1,65 @@
+use super::humanize::{format_ago, format_timestamp};
+
+/// Overall health of the manager, as its Manager properties tell it: how many units it has
+/// loaded, failed and jobs queued, its state and the machine it runs on.
+#[derive(Debug, Clone)]
+pub struct SystemSummary {
+    n_names: u32,
+    n_failed_units: u32,
+    n_jobs: u32,
+    /// e.g. "running", "degraded" or "starting".
+    system_state: String,
+    /// e.g. "kvm" or "docker", empty on bare metal.
+    virtualization: String,
+    /// Realtime boot in microseconds, 0 when unknown.
+    boot_usec: u64,
+}
+
+impl SystemSummary {
+    pub fn new(
+        n_names: u32,
+        n_failed_units: u32,
+        n_jobs: u32,
+        system_state: String,
+        virtualization: String,
+        boot_usec: u64,
+    ) -> Self {
+        Self {
+            n_names,
+            n_failed_units,
+            n_jobs,
+            system_state,
+            virtualization,
+            boot_usec,
+        }
+    }
+
+    pub fn n_names(&self) -> u32 {
+        self.n_names
+    }
+
+    pub fn n_failed_units(&self) -> u32 {
+        self.n_failed_units
+    }
+
+    pub fn n_jobs(&self) -> u32 {
+        self.n_jobs
+    }
+
+    pub fn system_state(&self) -> &str {
+        &self.system_state
+    }
+
+    /// e.g. "kvm", "bare metal" when the manager detected no virtualization.
+    pub fn virtualization(&self) -> &str {
+        if self.virtualization.is_empty() { "bare metal" } else { &self.virtualization }
+    }
+
+    /// e.g. "2026-10-17 09:12:03 (3d 4h ago)", "n/a" when unknown.
+    pub fn formatted_boot(&self, now_usec: u64) -> String {
+        if self.boot_usec == 0 {
+            return "n/a".to_string();
+        }
+        format!("{} ({})", format_timestamp(self.boot_usec), format_ago(self.boot_usec, now_usec))
+    }
+}
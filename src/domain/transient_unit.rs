@@ -0,0 +1,110 @@
+use chrono::Local;
+
+use super::resource_limits::ResourceLimits;
+
+/// A command to run as a transient service, like `systemd-run`.
+#[derive(Debug, Clone)]
+pub struct TransientUnit {
+    pub command: String,
+    /// Name typed for the unit, `default_name` when empty.
+    pub name: String,
+    pub cpu_quota: String,
+    pub memory_max: String,
+    pub tasks_max: String,
+    default_name: String,
+}
+
+impl TransientUnit {
+    /// An empty command, named after the current time unless another name is typed.
+    pub fn new() -> Self {
+        Self {
+            command: String::new(),
+            name: String::new(),
+            cpu_quota: String::new(),
+            memory_max: String::new(),
+            tasks_max: String::new(),
+            default_name: format!("run-tui-{}", Local::now().format("%Y%m%d-%H%M%S")),
+        }
+    }
+
+    pub fn default_name(&self) -> &str {
+        &self.default_name
+    }
+
+    /// The name of the service, ".service" being added when missing.
+    pub fn unit_name(&self) -> String {
+        let name = self.name.trim();
+        let name = if name.is_empty() { self.default_name.as_str() } else { name };
+        if name.ends_with(".service") { name.to_string() } else { format!("{}.service", name) }
+    }
+
+    /// The program and its arguments, split like a shell would, without expanding anything.
+    pub fn argv(&self) -> Result<Vec<String>, String> {
+        let argv = split_command(&self.command)?;
+        if argv.is_empty() {
+            return Err("Type the command to run".to_string());
+        }
+        Ok(argv)
+    }
+
+    pub fn limits(&self) -> Result<ResourceLimits, String> {
+        ResourceLimits::parse(&self.cpu_quota, &self.memory_max, &self.tasks_max)
+    }
+
+    /// e.g. "nightly-sync.service" or "nightly-sync.service with MemoryMax 512.0M".
+    pub fn describe(&self) -> String {
+        match self.limits().map(|limits| limits.describe()) {
+            Ok(limits) if !limits.is_empty() => format!("{} with {}", self.unit_name(), limits),
+            _ => self.unit_name(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        self.argv()?;
+        self.limits()?;
+        if let Some(c) = self
+            .unit_name()
+            .chars()
+            .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, ':' | '-' | '_' | '.' | '\\')))
+        {
+            return Err(format!("Unit names cannot contain \"{}\"", c));
+        }
+        Ok(())
+    }
+}
+
+impl Default for TransientUnit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Splits at whitespace, keeping quoted text together and honoring backslash escapes.
+fn split_command(command: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quote: Option<char> = None;
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"') | None, '\\') => {
+                if let Some(escaped) = chars.next() {
+                    word.get_or_insert_with(String::new).push(escaped);
+                }
+            }
+            (Some(_), c) => word.get_or_insert_with(String::new).push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                word.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => words.extend(word.take()),
+            (None, c) => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err("The command has an unclosed quote".to_string());
+    }
+    words.extend(word);
+    Ok(words)
+}
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use super::service::Service;
+
+/// The other names of a unit and the symlinks to its unit file, e.g. sshd.service for
+/// ssh.service through /etc/systemd/system/sshd.service, next to the links enabling it.
+#[derive(Debug, Clone, Default)]
+pub struct UnitLinks {
+    /// Names the unit is also loaded under, from Alias= or a symlink to its file.
+    pub aliases: Vec<String>,
+    /// Symlinks to the unit file, e.g. "/etc/systemd/system/multi-user.target.wants/nginx.service".
+    pub links: Vec<String>,
+    /// The unit file the links point to, empty when the unit has none.
+    pub target: String,
+}
+
+impl UnitLinks {
+    /// e.g. "/etc/systemd/system/sshd.service → /usr/lib/systemd/system/ssh.service".
+    pub fn describe_link(&self, link: &str) -> String {
+        if self.target.is_empty() {
+            link.to_string()
+        } else {
+            format!("{} → {}", link, self.target)
+        }
+    }
+}
+
+/// Folds the rows of alias unit files into the row of the unit they name, which lists them as
+/// its aliases. `targets` maps each alias to its unit, as systemd resolves it; aliases whose unit
+/// is not listed keep their row.
+pub fn fold_aliases(services: Vec<Service>, targets: &HashMap<String, String>) -> Vec<Service> {
+    let listed: Vec<String> = services.iter().map(|service| service.name().to_string()).collect();
+    let mut aliases: HashMap<&str, Vec<String>> = HashMap::new();
+    for (alias, target) in targets {
+        if alias != target && listed.contains(target) {
+            aliases.entry(target.as_str()).or_default().push(alias.clone());
+        }
+    }
+    services
+        .into_iter()
+        .filter(|service| !targets.get(service.name()).is_some_and(|target| aliases.contains_key(target.as_str())))
+        .map(|service| match aliases.get(service.name()) {
+            Some(names) => {
+                let mut names = names.clone();
+                names.sort();
+                service.with_aliases(names)
+            }
+            None => service,
+        })
+        .collect()
+}
@@ -0,0 +1,61 @@
+use super::action_plan::UnitAction;
+
+/// What the manager lets be done to a unit by hand, from its CanStart, CanStop, CanReload and
+/// CanIsolate properties, e.g. no start for units with RefuseManualStart=.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnitCapabilities {
+    pub can_start: bool,
+    pub can_stop: bool,
+    pub can_reload: bool,
+    pub can_isolate: bool,
+}
+
+impl UnitCapabilities {
+    /// Every action allowed, for units whose properties cannot be read.
+    pub const UNRESTRICTED: Self = Self {
+        can_start: true,
+        can_stop: true,
+        can_reload: true,
+        can_isolate: true,
+    };
+
+    /// Whether the manager accepts the action, restarting taking both a stop and a start.
+    pub fn allows(&self, action: UnitAction) -> bool {
+        match action {
+            UnitAction::Start => self.can_start,
+            UnitAction::Stop => self.can_stop,
+            UnitAction::Restart => self.can_start && self.can_stop,
+            UnitAction::Reload => self.can_reload,
+            UnitAction::ReloadOrRestart => self.can_reload || (self.can_start && self.can_stop),
+            UnitAction::Enable | UnitAction::Disable | UnitAction::Freeze | UnitAction::Thaw => true,
+        }
+    }
+
+    /// Why the action is refused, to follow the unit name, e.g. "cannot reload, it has no
+    /// reload command". `None` when it is allowed.
+    pub fn refusal(&self, action: UnitAction) -> Option<&'static str> {
+        if self.allows(action) {
+            return None;
+        }
+        Some(match action {
+            UnitAction::Start => "cannot be started by hand (CanStart=no)",
+            UnitAction::Stop => "cannot be stopped by hand (CanStop=no)",
+            UnitAction::Reload => "cannot reload, it has no reload command (CanReload=no)",
+            _ => "cannot be restarted by hand (CanStart=no or CanStop=no)",
+        })
+    }
+
+    /// The actions allowed, e.g. "start, stop, isolate", "none" without any.
+    pub fn summary(&self) -> String {
+        let allowed: Vec<&str> = [
+            (self.can_start, "start"),
+            (self.can_stop, "stop"),
+            (self.can_reload, "reload"),
+            (self.can_isolate, "isolate"),
+        ]
+        .into_iter()
+        .filter_map(|(allowed, action)| allowed.then_some(action))
+        .collect();
+        if allowed.is_empty() { "none".to_string() } else { allowed.join(", ") }
+    }
+}
@@ -0,0 +1,59 @@
+/// What cloning a unit under a new name makes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnitClone {
+    /// A copy of the unit file, e.g. "nginx-staging.service" from "nginx.service".
+    Copy(String),
+    /// An instance of the template the unit is or belongs to, e.g. "getty@tty3.service" from
+    /// "getty@.service", needing no file of its own.
+    Instance(String),
+}
+
+/// The part before "@" of templates and their instances, e.g. "getty" for "getty@tty1.service".
+fn template_prefix(stem: &str) -> Option<&str> {
+    stem.split_once('@').map(|(prefix, _)| prefix)
+}
+
+impl UnitClone {
+    /// The clone of `source` named `input`. For templates and their instances, a name without
+    /// "@" is the instance ("tty3" makes "getty@tty3.service"); the type of the source is added
+    /// when missing.
+    pub fn parse(source: &str, input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err("The clone needs a name".to_string());
+        }
+        if let Some(c) = input
+            .chars()
+            .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, ':' | '-' | '_' | '.' | '\\' | '@')))
+        {
+            return Err(format!("Unit names cannot contain \"{}\"", c));
+        }
+        let Some((stem, unit_type)) = source.rsplit_once('.') else {
+            return Err(format!("{} has no unit type to clone", source));
+        };
+        let suffix = format!(".{}", unit_type);
+        let base = input.strip_suffix(&suffix).unwrap_or(input);
+        let prefix = template_prefix(stem);
+
+        let name = match prefix {
+            Some(prefix) if !base.contains('@') => format!("{}@{}{}", prefix, base, suffix),
+            _ => format!("{}{}", base, suffix),
+        };
+        if name == source {
+            return Err(format!("{} already exists", source));
+        }
+        let instance = prefix.is_some_and(|prefix| {
+            name.strip_prefix(&format!("{}@", prefix))
+                .is_some_and(|rest| rest != suffix)
+        });
+        Ok(if instance { UnitClone::Instance(name) } else { UnitClone::Copy(name) })
+    }
+
+    /// e.g. "copy nginx.service to nginx-staging.service".
+    pub fn describe(&self, source: &str) -> String {
+        match self {
+            UnitClone::Copy(name) => format!("copy {} to {}", source, name),
+            UnitClone::Instance(name) => format!("create the instance {} of the template of {}", name, source),
+        }
+    }
+}
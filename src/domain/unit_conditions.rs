@@ -0,0 +1,100 @@
+use super::humanize::{format_timestamp_ago, now_usec};
+
+/// One of the Condition*= settings of a unit, from its Conditions property.
+#[derive(Debug, Clone)]
+pub struct UnitCondition {
+    /// e.g. "ConditionPathExists".
+    kind: String,
+    /// Triggering conditions (`|`) only need one of them to be met.
+    trigger: bool,
+    negate: bool,
+    parameter: String,
+    /// Positive when met, negative when not, 0 when not checked yet.
+    state: i32,
+}
+
+impl UnitCondition {
+    pub fn new(kind: String, trigger: bool, negate: bool, parameter: String, state: i32) -> Self {
+        Self {
+            kind,
+            trigger,
+            negate,
+            parameter,
+            state,
+        }
+    }
+
+    pub fn is_failed(&self) -> bool {
+        self.state < 0
+    }
+
+    /// As written in the unit file, e.g. "ConditionPathExists=|!/etc/foo".
+    pub fn describe(&self) -> String {
+        format!(
+            "{}={}{}{}",
+            self.kind,
+            if self.trigger { "|" } else { "" },
+            if self.negate { "!" } else { "" },
+            self.parameter
+        )
+    }
+
+    pub fn state_label(&self) -> &'static str {
+        match self.state {
+            state if state > 0 => "met",
+            state if state < 0 => "not met",
+            _ => "not checked",
+        }
+    }
+}
+
+/// Whether the conditions of a unit held when it was last started, the unit being skipped,
+/// and looking inactive, when they did not.
+#[derive(Debug, Clone, Default)]
+pub struct UnitConditions {
+    result: bool,
+    /// When they were last checked, in microseconds, 0 when never.
+    timestamp: u64,
+    conditions: Vec<UnitCondition>,
+}
+
+impl UnitConditions {
+    pub fn new(result: bool, timestamp: u64, conditions: Vec<UnitCondition>) -> Self {
+        Self {
+            result,
+            timestamp,
+            conditions,
+        }
+    }
+
+    pub fn conditions(&self) -> &[UnitCondition] {
+        &self.conditions
+    }
+
+    /// The conditions were checked and kept the unit from starting.
+    pub fn skipped(&self) -> bool {
+        self.timestamp > 0 && !self.result
+    }
+
+    /// Like the Condition line of `systemctl status`, e.g. "not met at 2026-10-17 09:12:03
+    /// (5m ago), the unit was skipped: ConditionPathExists=/etc/foo". `None` for units without conditions.
+    pub fn summary(&self) -> Option<String> {
+        if self.conditions.is_empty() {
+            return None;
+        }
+        if self.timestamp == 0 {
+            return Some("not checked yet".to_string());
+        }
+        let time = format_timestamp_ago(self.timestamp, now_usec());
+        if self.result {
+            return Some(format!("met at {}", time));
+        }
+        let failed: Vec<String> = self
+            .conditions
+            .iter()
+            .filter(|condition| condition.is_failed())
+            .map(UnitCondition::describe)
+            .collect();
+        Some(format!("not met at {}, the unit was skipped: {}", time, failed.join(", ")))
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::{HashMap, HashSet};
+
+use super::service::qualified_name;
+
+/// Dependency properties of a unit, as exposed by the org.freedesktop.systemd1.Unit interface.
+#[derive(Clone, Debug, Default)]
+pub struct UnitDependencies {
+    requires: Vec<String>,
+    wants: Vec<String>,
+    binds_to: Vec<String>,
+    after: Vec<String>,
+    before: Vec<String>,
+}
+
+impl UnitDependencies {
+    pub fn new(
+        requires: Vec<String>,
+        wants: Vec<String>,
+        binds_to: Vec<String>,
+        after: Vec<String>,
+        before: Vec<String>,
+    ) -> Self {
+        Self {
+            requires,
+            wants,
+            binds_to,
+            after,
+            before,
+        }
+    }
+
+    /// The same dependencies under their names in a merged view, all of them being units of
+    /// `source`.
+    pub fn qualified(self, source: &str) -> Self {
+        let qualify = |units: Vec<String>| units.iter().map(|unit| qualified_name(source, unit)).collect();
+        Self {
+            requires: qualify(self.requires),
+            wants: qualify(self.wants),
+            binds_to: qualify(self.binds_to),
+            after: qualify(self.after),
+            before: qualify(self.before),
+        }
+    }
+
+    /// Every dependency paired with the name of the property it comes from.
+    pub fn by_relation(&self) -> Vec<(&'static str, &[String])> {
+        vec![
+            ("Requires", &self.requires),
+            ("Wants", &self.wants),
+            ("BindsTo", &self.binds_to),
+            ("After", &self.after),
+            ("Before", &self.before),
+        ]
+    }
+
+    /// The units of `units` this one starts after: the ones it requires, is bound to or is
+    /// ordered after.
+    fn starts_after<'a>(&'a self, units: &'a [String]) -> impl Iterator<Item = &'a String> {
+        self.requires
+            .iter()
+            .chain(&self.binds_to)
+            .chain(&self.after)
+            .filter(move |unit| units.contains(unit))
+    }
+
+    /// The units this one pulls in, and therefore depends on.
+    pub fn pulled_in(&self) -> impl Iterator<Item = &String> {
+        self.requires.iter().chain(&self.wants).chain(&self.binds_to)
+    }
+}
+
+/// Number of units depending on each unit, directly or through other units, given the units
+/// each one pulls in. Units pulled in but absent from `pulled_in` are counted too.
+pub fn impact_scores(pulled_in: &HashMap<String, Vec<String>>) -> HashMap<String, usize> {
+    let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (unit, dependencies) in pulled_in {
+        for dependency in dependencies {
+            dependents.entry(dependency).or_default().push(unit);
+        }
+    }
+
+    dependents
+        .keys()
+        .map(|&unit| {
+            let mut seen: HashSet<&str> = HashSet::new();
+            let mut pending = vec![unit];
+            while let Some(current) = pending.pop() {
+                for &dependent in dependents.get(current).into_iter().flatten() {
+                    if dependent != unit && seen.insert(dependent) {
+                        pending.push(dependent);
+                    }
+                }
+            }
+            (unit.to_string(), seen.len())
+        })
+        .collect()
+}
+
+/// The units in the order they start: each after the units of the list it requires, is bound
+/// to or is ordered after, and before the ones listed in its Before. Units left unordered keep
+/// their order in `units`, and so do those in an ordering cycle, placed last.
+pub fn start_order(units: &[String], dependencies: &HashMap<String, UnitDependencies>) -> Vec<String> {
+    let mut preceding: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for unit in units {
+        let Some(own) = dependencies.get(unit) else {
+            continue;
+        };
+        for earlier in own.starts_after(units).filter(|earlier| *earlier != unit) {
+            preceding.entry(unit).or_default().insert(earlier);
+        }
+        for later in own.before.iter().filter(|later| units.contains(later) && *later != unit) {
+            preceding.entry(later).or_default().insert(unit);
+        }
+    }
+
+    let mut ordered: Vec<String> = Vec::with_capacity(units.len());
+    let mut pending: Vec<&String> = units.iter().collect();
+    loop {
+        let ready = pending.iter().position(|unit| {
+            preceding
+                .get(unit.as_str())
+                .is_none_or(|earlier| earlier.iter().all(|earlier| ordered.iter().any(|done| done == earlier)))
+        });
+        match ready {
+            Some(index) => ordered.push(pending.remove(index).clone()),
+            None => break,
+        }
+    }
+    ordered.extend(pending.into_iter().cloned());
+    ordered
+}
@@ -0,0 +1,55 @@
+/// One reference of the Documentation= setting of a unit.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DocumentationLink {
+    /// "man:systemd.service(5)", the section being optional.
+    ManPage { page: String, section: Option<String> },
+    /// "file:/usr/share/doc/foo/README".
+    File(String),
+    /// "https://..." and "http://...".
+    Url(String),
+    /// Anything else, e.g. "info:", kept as it is.
+    Other(String),
+}
+
+impl DocumentationLink {
+    pub fn parse(reference: &str) -> Self {
+        if let Some(man) = reference.strip_prefix("man:") {
+            return match man.strip_suffix(')').and_then(|man| man.split_once('(')) {
+                Some((page, section)) => DocumentationLink::ManPage {
+                    page: page.to_string(),
+                    section: Some(section.to_string()),
+                },
+                None => DocumentationLink::ManPage {
+                    page: man.to_string(),
+                    section: None,
+                },
+            };
+        }
+        if let Some(path) = reference.strip_prefix("file:") {
+            return DocumentationLink::File(path.to_string());
+        }
+        if reference.starts_with("https://") || reference.starts_with("http://") {
+            return DocumentationLink::Url(reference.to_string());
+        }
+        DocumentationLink::Other(reference.to_string())
+    }
+
+    /// As written in the unit file, e.g. "man:sshd(8)".
+    pub fn reference(&self) -> String {
+        match self {
+            DocumentationLink::ManPage { page, section: Some(section) } => format!("man:{}({})", page, section),
+            DocumentationLink::ManPage { page, section: None } => format!("man:{}", page),
+            DocumentationLink::File(path) => format!("file:{}", path),
+            DocumentationLink::Url(url) | DocumentationLink::Other(url) => url.clone(),
+        }
+    }
+
+    /// What Enter does with it, e.g. "read" or "open in the browser".
+    pub fn action(&self) -> &'static str {
+        match self {
+            DocumentationLink::ManPage { .. } | DocumentationLink::File(_) => "read",
+            DocumentationLink::Url(_) => "open in the browser",
+            DocumentationLink::Other(_) => "copy only",
+        }
+    }
+}
@@ -0,0 +1,65 @@
+/// The environment of a unit, from its D-Bus Environment and EnvironmentFiles properties.
+#[derive(Debug, Clone, Default)]
+pub struct UnitEnvironment {
+    /// "KEY=VALUE" assignments, in the order they apply.
+    pub variables: Vec<String>,
+    /// Files read when the unit starts, with whether a missing file is ignored ("-" prefix).
+    pub files: Vec<(String, bool)>,
+}
+
+impl UnitEnvironment {
+    /// (label, value) pairs shown in the properties of the unit.
+    pub fn summary(&self) -> Vec<(&'static str, String)> {
+        let variables = self.variables.iter().map(|variable| ("Environment", variable.clone()));
+        let files = self.files.iter().map(|(path, optional)| {
+            let path = if *optional { format!("{} (optional)", path) } else { path.clone() };
+            ("Env file", path)
+        });
+        variables.chain(files).collect()
+    }
+}
+
+/// Section holding the Environment= setting for the type of the unit, e.g. "Service" for
+/// nginx.service, `None` for unit types not running commands.
+pub fn exec_section(unit: &str) -> Option<&'static str> {
+    match unit.rsplit_once('.')?.1 {
+        "service" => Some("Service"),
+        "socket" => Some("Socket"),
+        "mount" => Some("Mount"),
+        "swap" => Some("Swap"),
+        _ => None,
+    }
+}
+
+/// Quotes the assignment for Environment=, escaping what systemd would otherwise interpret.
+fn quote(assignment: &str) -> String {
+    let escaped = assignment.replace('\\', "\\\\").replace('"', "\\\"").replace('%', "%%");
+    format!("\"{}\"", escaped)
+}
+
+/// Validates a "KEY=VALUE" assignment, returning the variable name.
+pub fn parse_assignment(assignment: &str) -> Result<&str, String> {
+    let Some((key, _)) = assignment.split_once('=') else {
+        return Err("Type the variable as KEY=VALUE".to_string());
+    };
+    let valid = key.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(format!("\"{}\" is not a valid variable name", key));
+    }
+    if assignment.contains('\n') {
+        return Err("Values must fit on one line".to_string());
+    }
+    Ok(key)
+}
+
+/// (file name, content) of the drop-in setting the variable of the assignment for the unit.
+/// Each variable has a drop-in of its own, so setting it again replaces the previous value.
+pub fn environment_drop_in(unit: &str, assignment: &str) -> Result<(String, String), String> {
+    let key = parse_assignment(assignment)?;
+    let section = exec_section(unit).ok_or_else(|| format!("{} runs no command to set variables for", unit))?;
+    Ok((
+        format!("environment-{}.conf", key),
+        format!("[{}]\nEnvironment={}\n", section, quote(assignment)),
+    ))
+}
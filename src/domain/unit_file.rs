@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+
+/// Settings whose assignments add up instead of replacing each other; only an empty assignment
+/// resets them.
+pub const LIST_SETTINGS: [&str; 31] = [
+    "After", "Before", "Wants", "Requires", "Requisite", "BindsTo", "PartOf", "Upholds", "Conflicts",
+    "OnFailure", "OnSuccess", "Documentation", "WantedBy", "RequiredBy", "UpheldBy", "Also",
+    "Environment", "EnvironmentFile", "ExecCondition", "ExecStartPre", "ExecStart", "ExecStartPost",
+    "ExecReload", "ExecStop", "ExecStopPost", "ReadWritePaths", "ReadOnlyPaths", "InaccessiblePaths",
+    "ListenStream", "ListenDatagram", "OnCalendar",
+];
+
+/// The unit file of a unit and its drop-ins, in the order they apply, from the D-Bus
+/// FragmentPath and DropInPaths properties.
+#[derive(Debug, Clone, Default)]
+pub struct UnitFilePaths {
+    /// Empty for units without a unit file, e.g. some generated or transient ones.
+    pub fragment: String,
+    pub drop_ins: Vec<String>,
+}
+
+/// How a setting relates to the assignments of the same setting in the other files.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettingNote {
+    /// Replaced by the assignment of a later file.
+    OverriddenBy(String),
+    /// Replaces the assignment of an earlier file.
+    Overrides(String),
+    /// Added to by the assignment of a later file, for list settings like `After=`.
+    ExtendedBy(String),
+    /// Adds to the assignment of an earlier file.
+    Extends(String),
+}
+
+impl SettingNote {
+    /// e.g. "overridden by override.conf".
+    pub fn describe(&self) -> String {
+        match self {
+            SettingNote::OverriddenBy(file) => format!("overridden by {}", file),
+            SettingNote::Overrides(file) => format!("overrides {}", file),
+            SettingNote::ExtendedBy(file) => format!("extended by {}", file),
+            SettingNote::Extends(file) => format!("extends {}", file),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UnitFileLine {
+    pub text: String,
+    pub note: Option<SettingNote>,
+}
+
+/// One of the files making up the configuration of a unit, as printed by `systemctl cat`.
+#[derive(Debug, Clone)]
+pub struct UnitFile {
+    /// Empty for content printed before any known file, e.g. for transient units.
+    pub path: String,
+    pub drop_in: bool,
+    pub lines: Vec<UnitFileLine>,
+}
+
+fn file_name(path: &str) -> String {
+    path.rsplit('/').next().unwrap_or(path).to_string()
+}
+
+/// (setting, value) of an assignment line.
+pub fn assignment(line: &str) -> Option<(&str, &str)> {
+    if line.starts_with('#') || line.starts_with(';') {
+        return None;
+    }
+    let (key, value) = line.split_once('=')?;
+    Some((key.trim(), value.trim()))
+}
+
+impl UnitFile {
+    /// Splits the output of `systemctl cat` at the "# <path>" header of each file, then notes
+    /// which settings a later file overrides or extends.
+    pub fn split(content: &str, paths: &UnitFilePaths) -> Vec<UnitFile> {
+        let mut files: Vec<UnitFile> = Vec::new();
+        for line in content.lines() {
+            let header = line.strip_prefix("# ").filter(|path| {
+                *path == paths.fragment || paths.drop_ins.iter().any(|drop_in| drop_in == path)
+            });
+            if let Some(path) = header {
+                files.push(UnitFile {
+                    path: path.to_string(),
+                    drop_in: path != paths.fragment,
+                    lines: Vec::new(),
+                });
+                continue;
+            }
+            if files.is_empty() {
+                files.push(UnitFile { path: String::new(), drop_in: false, lines: Vec::new() });
+            }
+            if let Some(file) = files.last_mut() {
+                file.lines.push(UnitFileLine { text: line.to_string(), note: None });
+            }
+        }
+        for file in &mut files {
+            while file.lines.last().is_some_and(|line| line.text.trim().is_empty()) {
+                file.lines.pop();
+            }
+        }
+        annotate(&mut files);
+        files
+    }
+
+    /// "unit file" or "drop-in".
+    pub fn kind(&self) -> &'static str {
+        if self.drop_in { "drop-in" } else { "unit file" }
+    }
+}
+
+/// Notes the assignments replaced or added to by a later file, like systemd applies them.
+fn annotate(files: &mut [UnitFile]) {
+    // Assignments in effect for each (section, setting), as (file, line) indexes.
+    let mut in_effect: HashMap<(String, String), Vec<(usize, usize)>> = HashMap::new();
+    // Files whose assignments an empty assignment reset, e.g. `ExecStart=` before a new one.
+    let mut reset_from: HashMap<(String, String), String> = HashMap::new();
+    let mut notes: Vec<((usize, usize), SettingNote)> = Vec::new();
+    for (file_index, file) in files.iter().enumerate() {
+        let mut section = String::new();
+        for (line_index, line) in file.lines.iter().enumerate() {
+            let text = line.text.trim();
+            if text.starts_with('[') && text.ends_with(']') {
+                section = text.to_string();
+                continue;
+            }
+            let Some((key, value)) = assignment(text) else {
+                continue;
+            };
+            let setting = (section.clone(), key.to_string());
+            let earlier = in_effect.entry(setting.clone()).or_default();
+            let from_other_files: Vec<(usize, usize)> =
+                earlier.iter().copied().filter(|(file, _)| *file != file_index).collect();
+            let appends = LIST_SETTINGS.contains(&key) && !value.is_empty();
+            if let Some((first_file, _)) = from_other_files.first() {
+                let name = file_name(&files[*first_file].path);
+                let this_name = file_name(&file.path);
+                for position in &from_other_files {
+                    let note = if appends {
+                        SettingNote::ExtendedBy(this_name.clone())
+                    } else {
+                        SettingNote::OverriddenBy(this_name.clone())
+                    };
+                    notes.push((*position, note));
+                }
+                if value.is_empty() {
+                    reset_from.insert(setting, name.clone());
+                }
+                let note = if appends { SettingNote::Extends(name) } else { SettingNote::Overrides(name) };
+                notes.push(((file_index, line_index), note));
+            } else if let Some(name) = reset_from.remove(&setting) {
+                notes.push(((file_index, line_index), SettingNote::Overrides(name)));
+            }
+            if !appends {
+                earlier.clear();
+            }
+            if !value.is_empty() {
+                earlier.push((file_index, line_index));
+            }
+        }
+    }
+    for ((file, line), note) in notes {
+        files[file].lines[line].note = Some(note);
+    }
+}
+
+/// A line of a unit file or drop-in containing the searched text.
+#[derive(Debug, Clone)]
+pub struct UnitFileMatch {
+    pub unit: String,
+    pub path: String,
+    pub line_number: usize,
+    pub line: String,
+}
+
+/// (path, line number, line) of the output of `grep -HnZ`, whose file names end with a NUL.
+pub fn parse_grep_matches(output: &str) -> Vec<(String, usize, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (path, rest) = line.split_once('\0')?;
+            let (line_number, text) = rest.split_once(':')?;
+            Some((path.to_string(), line_number.parse().ok()?, text.trim().to_string()))
+        })
+        .collect()
+}
+
+/// (file name, content) of the drop-in replacing the Description= of a unit. "%" starts a
+/// specifier in unit files, it is doubled to be kept as is.
+pub fn description_drop_in(description: &str) -> Result<(String, String), String> {
+    let description = description.trim();
+    if description.is_empty() {
+        return Err("The description cannot be empty".to_string());
+    }
+    if description.contains(['\n', '\r']) {
+        return Err("The description must fit on one line".to_string());
+    }
+    Ok((
+        "description.conf".to_string(),
+        format!("[Unit]\nDescription={}\n", description.replace('%', "%%")),
+    ))
+}
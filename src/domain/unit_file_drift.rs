@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use super::unit_file::{assignment, UnitFile, LIST_SETTINGS};
+
+/// A setting of the unit file and its drop-ins next to the value systemd loaded, which differ
+/// when the files were edited without a daemon reload (NeedDaemonReload=yes).
+#[derive(Debug, Clone)]
+pub struct SettingDrift {
+    /// e.g. "[Service]".
+    pub section: String,
+    pub setting: String,
+    /// Values in effect in the files on disk, several for list settings like `After=`.
+    pub on_disk: Vec<String>,
+    /// Values of `systemctl show`, `None` when systemd reports no property of that name, e.g.
+    /// for `TimeoutStartSec=` which it shows as TimeoutStartUSec.
+    pub loaded: Option<Vec<String>>,
+}
+
+impl SettingDrift {
+    fn is_command(&self) -> bool {
+        self.setting.starts_with("Exec")
+    }
+
+    /// Whether the loaded unit does not have the value of the files. Settings systemd does not
+    /// report never differ, and list settings only when a value of the files is missing, the
+    /// loaded unit also having the dependencies systemd adds, e.g. After=basic.target.
+    pub fn differs(&self) -> bool {
+        let Some(loaded) = &self.loaded else {
+            return false;
+        };
+        if self.is_command() {
+            let on_disk: Vec<&str> = self.on_disk.iter().map(|command| command_line(command)).collect();
+            let loaded: Vec<&str> = loaded.iter().map(|command| loaded_command_line(command)).collect();
+            return on_disk != loaded;
+        }
+        if LIST_SETTINGS.contains(&self.setting.as_str()) {
+            let loaded: Vec<&str> = loaded.iter().flat_map(|value| value.split_whitespace()).collect();
+            return self
+                .on_disk
+                .iter()
+                .flat_map(|value| value.split_whitespace())
+                .any(|value| !loaded.contains(&value.trim_matches('"')));
+        }
+        let on_disk = self.on_disk.last().map(String::as_str).unwrap_or_default();
+        !same_value(on_disk, &loaded.join(" "))
+    }
+
+    pub fn on_disk_text(&self) -> String {
+        let separator = if self.is_command() { "; " } else { " " };
+        self.on_disk.join(separator)
+    }
+
+    /// e.g. "/usr/sbin/nginx -g daemon off;" for an ExecStart= shown as "{ path=... ; argv[]=... }".
+    pub fn loaded_text(&self) -> String {
+        match &self.loaded {
+            None => "not reported".to_string(),
+            Some(loaded) if self.is_command() => {
+                loaded.iter().map(|command| loaded_command_line(command)).collect::<Vec<_>>().join("; ")
+            }
+            Some(loaded) => loaded.join(" "),
+        }
+    }
+}
+
+/// The command of an Exec setting without its prefixes, e.g. "-" to ignore failures.
+fn command_line(command: &str) -> &str {
+    command.trim_start_matches(['-', '@', ':', '+', '!', '|']).trim()
+}
+
+/// The argv of a command as `systemctl show` prints it, e.g.
+/// "{ path=/usr/sbin/sshd ; argv[]=/usr/sbin/sshd -D ; ignore_errors=no ; ... }".
+fn loaded_command_line(command: &str) -> &str {
+    let Some(start) = command.find("argv[]=") else {
+        return command;
+    };
+    let argv = &command[start + "argv[]=".len()..];
+    argv.find(" ; ignore_errors=").map_or(argv, |end| &argv[..end]).trim()
+}
+
+/// Equal once booleans are spelled alike and seconds have their unit, as systemd shows them.
+fn same_value(on_disk: &str, loaded: &str) -> bool {
+    let boolean = |value: &str| match value.to_lowercase().as_str() {
+        "yes" | "true" | "on" | "1" => Some(true),
+        "no" | "false" | "off" | "0" => Some(false),
+        _ => None,
+    };
+    if on_disk == loaded {
+        return true;
+    }
+    if let (Some(on_disk), Some(loaded)) = (boolean(on_disk), boolean(loaded)) {
+        return on_disk == loaded;
+    }
+    !on_disk.is_empty() && on_disk.chars().all(|c| c.is_ascii_digit()) && loaded == format!("{}s", on_disk)
+}
+
+/// The settings in effect in the files, in the order they first appear: a later assignment
+/// replaces an earlier one, except for list settings which add up until an empty assignment.
+/// The [Install] section is left out, systemd only reads it to enable the unit.
+pub fn settings_on_disk(files: &[UnitFile]) -> Vec<SettingDrift> {
+    let mut settings: Vec<SettingDrift> = Vec::new();
+    for file in files {
+        let mut section = String::new();
+        for line in &file.lines {
+            let text = line.text.trim();
+            if text.starts_with('[') && text.ends_with(']') {
+                section = text.to_string();
+                continue;
+            }
+            if section == "[Install]" {
+                continue;
+            }
+            let Some((key, value)) = assignment(text) else {
+                continue;
+            };
+            let index = match settings.iter().position(|setting| setting.section == section && setting.setting == key) {
+                Some(index) => index,
+                None => {
+                    settings.push(SettingDrift {
+                        section: section.clone(),
+                        setting: key.to_string(),
+                        on_disk: Vec::new(),
+                        loaded: None,
+                    });
+                    settings.len() - 1
+                }
+            };
+            let values = &mut settings[index].on_disk;
+            if value.is_empty() || !LIST_SETTINGS.contains(&key) {
+                values.clear();
+            }
+            if !value.is_empty() {
+                values.push(value.to_string());
+            }
+        }
+    }
+    settings.retain(|setting| !setting.on_disk.is_empty());
+    settings
+}
+
+/// Setting names of the files, to ask systemd for their loaded values.
+pub fn setting_names(settings: &[SettingDrift]) -> Vec<String> {
+    let mut names: Vec<String> = settings.iter().map(|setting| setting.setting.clone()).collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Fills in the loaded values of the settings.
+pub fn with_loaded(mut settings: Vec<SettingDrift>, loaded: &HashMap<String, Vec<String>>) -> Vec<SettingDrift> {
+    for setting in &mut settings {
+        setting.loaded = loaded.get(&setting.setting).cloned();
+    }
+    settings
+}
+
+/// Values by property of `systemctl show -p ...`, a property listed once per value for
+/// commands, e.g. two ExecStartPre= lines.
+pub fn parse_show(output: &str) -> HashMap<String, Vec<String>> {
+    let mut values: HashMap<String, Vec<String>> = HashMap::new();
+    for line in output.lines() {
+        if let Some((key, value)) = line.split_once('=') {
+            values.entry(key.to_string()).or_default().push(value.to_string());
+        }
+    }
+    values
+}
@@ -0,0 +1,133 @@
+use super::journal_entry::JournalEntry;
+
+/// Days of journal the history tab covers.
+pub const HISTORY_DAYS: u64 = 7;
+
+/// What the manager logged about a unit, from the messages of systemd itself.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HistoryEventKind {
+    Started,
+    /// Stopped, or finished for oneshot services.
+    Stopped,
+    Failed,
+    /// An automatic restart, following Restart=.
+    Restarted,
+    /// Failed with result 'oom-kill'.
+    OomKilled,
+}
+
+impl HistoryEventKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            HistoryEventKind::Started => "started",
+            HistoryEventKind::Stopped => "stopped",
+            HistoryEventKind::Failed => "failed",
+            HistoryEventKind::Restarted => "restarted",
+            HistoryEventKind::OomKilled => "OOM killed",
+        }
+    }
+
+    /// Kinds worse than others are kept when several fall in the same column of the timeline.
+    pub fn severity(&self) -> u8 {
+        match self {
+            HistoryEventKind::Stopped => 0,
+            HistoryEventKind::Started => 1,
+            HistoryEventKind::Restarted => 2,
+            HistoryEventKind::Failed => 3,
+            HistoryEventKind::OomKilled => 4,
+        }
+    }
+
+    /// From a message of the manager, e.g. "Started nginx.service - A web server." or
+    /// "nginx.service: Failed with result 'exit-code'.". `None` for the other messages, e.g.
+    /// "Starting ..." or "Failed to start ...", which repeat the result logged next to them.
+    fn from_message(message: &str) -> Option<Self> {
+        let detail = message.split_once(": ").map_or(message, |(_, detail)| detail);
+        if detail.starts_with("Failed with result 'oom-kill'") {
+            Some(HistoryEventKind::OomKilled)
+        } else if detail.starts_with("Failed with result") {
+            Some(HistoryEventKind::Failed)
+        } else if detail.starts_with("Scheduled restart job") {
+            Some(HistoryEventKind::Restarted)
+        } else if message.starts_with("Started ") {
+            Some(HistoryEventKind::Started)
+        } else if message.starts_with("Stopped ") || message.starts_with("Finished ") {
+            Some(HistoryEventKind::Stopped)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HistoryEvent {
+    /// Realtime timestamp, in microseconds.
+    timestamp: u64,
+    kind: HistoryEventKind,
+    message: String,
+}
+
+impl HistoryEvent {
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    pub fn kind(&self) -> HistoryEventKind {
+        self.kind
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// Starts, stops, failures and OOM kills of a unit over the last `HISTORY_DAYS` days, oldest first.
+#[derive(Debug, Clone, Default)]
+pub struct UnitHistory {
+    events: Vec<HistoryEvent>,
+}
+
+impl UnitHistory {
+    /// Keeps the entries logged by the manager (SYSLOG_IDENTIFIER=systemd) that tell a state
+    /// change, leaving out what the unit logged itself.
+    pub fn from_entries(entries: &[JournalEntry]) -> Self {
+        let events = entries
+            .iter()
+            .filter(|entry| entry.identifier() == Some("systemd"))
+            .filter_map(|entry| {
+                Some(HistoryEvent {
+                    timestamp: entry.timestamp()?,
+                    kind: HistoryEventKind::from_message(entry.message())?,
+                    message: entry.message().to_string(),
+                })
+            })
+            .collect();
+        Self { events }
+    }
+
+    pub fn events(&self) -> &[HistoryEvent] {
+        &self.events
+    }
+
+    pub fn count(&self, kind: HistoryEventKind) -> usize {
+        self.events.iter().filter(|event| event.kind == kind).count()
+    }
+
+    /// The events of `[start, end)` spread over `columns` equal slots, keeping the worst event
+    /// of each slot.
+    pub fn buckets(&self, start: u64, end: u64, columns: usize) -> Vec<Option<HistoryEventKind>> {
+        let mut buckets: Vec<Option<HistoryEventKind>> = vec![None; columns];
+        if columns == 0 || end <= start {
+            return buckets;
+        }
+        let span = end - start;
+        for event in self.events.iter().filter(|event| (start..end).contains(&event.timestamp)) {
+            let column = ((event.timestamp - start) as u128 * columns as u128 / span as u128) as usize;
+            let bucket = &mut buckets[column.min(columns - 1)];
+            if bucket.is_none_or(|kind| kind.severity() < event.kind.severity()) {
+                *bucket = Some(event.kind);
+            }
+        }
+        buckets
+    }
+}
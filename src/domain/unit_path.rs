@@ -0,0 +1,63 @@
+use std::fmt;
+use std::path::Path;
+
+/// How a unit relates to a filesystem path.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PathRelation {
+    Mount,
+    Automount,
+    /// A path unit watch, with its type (e.g. "PathChanged", "DirectoryNotEmpty").
+    Watch(String),
+    Exec,
+}
+
+impl fmt::Display for PathRelation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PathRelation::Mount => write!(f, "mounted at"),
+            PathRelation::Automount => write!(f, "automounted at"),
+            PathRelation::Watch(kind) => write!(f, "watches ({})", kind),
+            PathRelation::Exec => write!(f, "executes"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct UnitPathReference {
+    unit: String,
+    relation: PathRelation,
+    path: String,
+}
+
+impl UnitPathReference {
+    pub fn new(unit: String, relation: PathRelation, path: String) -> Self {
+        Self {
+            unit,
+            relation,
+            path,
+        }
+    }
+
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    pub fn relation(&self) -> &PathRelation {
+        &self.relation
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Whether this reference manages the queried path: mounts cover everything below them,
+    /// watches match in both directions and executables match when they live under the query.
+    pub fn covers(&self, query: &Path) -> bool {
+        let path = Path::new(&self.path);
+        match self.relation {
+            PathRelation::Mount | PathRelation::Automount => query.starts_with(path),
+            PathRelation::Watch(_) => query.starts_with(path) || path.starts_with(query),
+            PathRelation::Exec => path.starts_with(query),
+        }
+    }
+}
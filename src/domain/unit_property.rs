@@ -0,0 +1,193 @@
+use serde::Serialize;
+
+use super::humanize::{format_timestamp_ago, now_usec};
+use super::service_property::ServiceProperty;
+
+/// A titled group of (label, value) lines of the properties tab, e.g. "Exec" or "Limits".
+pub type PropertySection = (&'static str, Vec<(&'static str, String)>);
+
+/// Properties of the org.freedesktop.systemd1.Timer interface.
+#[derive(Debug, Clone, Serialize)]
+pub struct TimerProperty {
+    unit: String,
+    /// (base, e.g. "OnCalendar", calendar expression, next elapse).
+    timers_calendar: Vec<(String, String, u64)>,
+    /// (base, e.g. "OnBootUSec", offset in microseconds, next elapse).
+    timers_monotonic: Vec<(String, u64, u64)>,
+    next_elapse_realtime: u64,
+    last_trigger: u64,
+    persistent: bool,
+    result: String,
+}
+
+impl TimerProperty {
+    pub fn new(
+        unit: String,
+        timers_calendar: Vec<(String, String, u64)>,
+        timers_monotonic: Vec<(String, u64, u64)>,
+        next_elapse_realtime: u64,
+        last_trigger: u64,
+        persistent: bool,
+        result: String,
+    ) -> Self {
+        Self {
+            unit,
+            timers_calendar,
+            timers_monotonic,
+            next_elapse_realtime,
+            last_trigger,
+            persistent,
+            result,
+        }
+    }
+
+    /// The unit started when the timer elapses.
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    /// The calendar and monotonic expressions of the timer, with its next and last runs.
+    pub fn schedule(&self) -> Vec<(&'static str, String)> {
+        let mut lines: Vec<(&'static str, String)> = self
+            .timers_calendar
+            .iter()
+            .map(|(base, expression, _)| ("Schedule", format!("{}={}", base, expression)))
+            .collect();
+        lines.extend(self.timers_monotonic.iter().map(|(base, offset, _)| {
+            ("Schedule", format!("{}={}s", base.trim_end_matches("USec"), offset / 1_000_000))
+        }));
+        lines.push(("Next elapse", format_timestamp_ago(self.next_elapse_realtime, now_usec())));
+        lines.push(("Last trigger", format_timestamp_ago(self.last_trigger, now_usec())));
+        lines
+    }
+
+    fn summary(&self) -> Vec<(&'static str, String)> {
+        let mut lines = vec![("Activates", self.unit.clone())];
+        lines.extend(self.schedule());
+        lines.push(("Persistent", if self.persistent { "yes" } else { "no" }.to_string()));
+        lines.push(("Result", self.result.clone()));
+        lines
+    }
+}
+
+/// Properties of the org.freedesktop.systemd1.Socket interface.
+#[derive(Debug, Clone, Serialize)]
+pub struct SocketProperty {
+    /// (type, e.g. "Stream", address).
+    listen: Vec<(String, String)>,
+    /// e.g. "listening", "running" while a connection is handled, "dead" once stopped.
+    sub_state: String,
+    accept: bool,
+    connections: u32,
+    accepted: u32,
+    refused: u32,
+    result: String,
+}
+
+impl SocketProperty {
+    pub fn new(
+        listen: Vec<(String, String)>,
+        sub_state: String,
+        accept: bool,
+        connections: u32,
+        accepted: u32,
+        refused: u32,
+        result: String,
+    ) -> Self {
+        Self {
+            listen,
+            sub_state,
+            accept,
+            connections,
+            accepted,
+            refused,
+            result,
+        }
+    }
+
+    /// The addresses the socket listens on, its state and connection counters.
+    pub fn activity(&self) -> Vec<(&'static str, String)> {
+        let mut lines: Vec<(&'static str, String)> = self
+            .listen
+            .iter()
+            .map(|(kind, address)| ("Listen", format!("{} ({})", address, kind)))
+            .collect();
+        lines.push(("State", self.sub_state.clone()));
+        lines.push(("Connections", self.connections.to_string()));
+        lines.push(("Accepted", self.accepted.to_string()));
+        lines.push(("Refused", self.refused.to_string()));
+        lines
+    }
+
+    fn summary(&self) -> Vec<(&'static str, String)> {
+        let mut lines = self.activity();
+        lines.push(("Accept", if self.accept { "yes" } else { "no" }.to_string()));
+        lines.push(("Result", self.result.clone()));
+        lines
+    }
+}
+
+/// Properties of the org.freedesktop.systemd1.Mount interface.
+#[derive(Debug, Clone, Serialize)]
+pub struct MountProperty {
+    what: String,
+    r#where: String,
+    fs_type: String,
+    options: String,
+    result: String,
+}
+
+impl MountProperty {
+    pub fn new(what: String, r#where: String, fs_type: String, options: String, result: String) -> Self {
+        Self {
+            what,
+            r#where,
+            fs_type,
+            options,
+            result,
+        }
+    }
+
+    fn summary(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("What", self.what.clone()),
+            ("Where", self.r#where.clone()),
+            ("Type", self.fs_type.clone()),
+            ("Options", self.options.clone()),
+            ("Result", self.result.clone()),
+        ]
+    }
+}
+
+/// Properties of a unit, read from the D-Bus interface of its type.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum UnitProperty {
+    Service(Box<ServiceProperty>),
+    Timer(TimerProperty),
+    Socket(SocketProperty),
+    Mount(MountProperty),
+}
+
+impl UnitProperty {
+    /// How the last run ended: "success", "exit-code", "timeout", "signal"...
+    pub fn result(&self) -> &str {
+        match self {
+            UnitProperty::Service(property) => property.result(),
+            UnitProperty::Timer(property) => &property.result,
+            UnitProperty::Socket(property) => &property.result,
+            UnitProperty::Mount(property) => &property.result,
+        }
+    }
+
+    /// The most useful properties of the unit type, grouped in sections; one section named
+    /// after the type for the types with few of them.
+    pub fn sections(&self) -> Vec<PropertySection> {
+        match self {
+            UnitProperty::Service(property) => property.sections(),
+            UnitProperty::Timer(property) => vec![("Timer", property.summary())],
+            UnitProperty::Socket(property) => vec![("Socket", property.summary())],
+            UnitProperty::Mount(property) => vec![("Mount", property.summary())],
+        }
+    }
+}
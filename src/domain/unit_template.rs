@@ -0,0 +1,415 @@
+use serde::Deserialize;
+
+/// Values of `Restart=`, offered by the templates running a long-lived process.
+pub const RESTART_POLICIES: [&str; 7] = [
+    "no",
+    "on-failure",
+    "always",
+    "on-success",
+    "on-abnormal",
+    "on-abort",
+    "on-watchdog",
+];
+
+/// Placeholders every template can use besides its parameters: the unit name without its type
+/// suffix, and the install target of the manager ("multi-user.target" or "default.target").
+const RESERVED_KEYS: [&str; 2] = ["name", "default_target"];
+
+/// A value asked by the new unit form, substituted for `{{key}}` in the unit files.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateParameter {
+    pub key: String,
+    /// Shown in the form, the key when empty.
+    #[serde(default)]
+    pub label: String,
+    /// Initial value, which may use `{{default_target}}`.
+    #[serde(default)]
+    pub default: String,
+    /// Values cycled through instead of typed, e.g. the restart policies.
+    #[serde(default)]
+    pub choices: Vec<String>,
+    #[serde(default)]
+    pub required: bool,
+}
+
+impl TemplateParameter {
+    fn new(key: &str, label: &str, default: &str) -> Self {
+        Self {
+            key: key.to_string(),
+            label: label.to_string(),
+            default: default.to_string(),
+            choices: Vec::new(),
+            required: false,
+        }
+    }
+
+    fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    fn choices(mut self, choices: &[&str]) -> Self {
+        self.choices = choices.iter().map(|choice| choice.to_string()).collect();
+        self
+    }
+
+    pub fn label(&self) -> &str {
+        if self.label.is_empty() { &self.key } else { &self.label }
+    }
+}
+
+/// A unit file written by a template, named after the new unit with its type as suffix.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateFile {
+    /// Unit type, e.g. "service" or "timer".
+    #[serde(rename = "type")]
+    pub unit_type: String,
+    /// Content of the unit file. A `Key={{parameter}}` line is left out when the parameter is
+    /// empty, and so is a section left without any line.
+    pub content: String,
+}
+
+impl TemplateFile {
+    fn new(unit_type: &str, content: &str) -> Self {
+        Self {
+            unit_type: unit_type.to_string(),
+            content: content.to_string(),
+        }
+    }
+}
+
+/// A parameterized set of unit files offered by the new unit form, built in or read from the
+/// templates directory next to the config file.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UnitTemplate {
+    /// Built-in name, or file name without extension for the templates directory.
+    #[serde(skip)]
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub parameters: Vec<TemplateParameter>,
+    pub files: Vec<TemplateFile>,
+    /// Type of the unit enabled and started once created, the one of the first file when unset.
+    #[serde(default)]
+    pub start: Option<String>,
+}
+
+/// The `{{key}}` placeholders of a unit file.
+fn placeholders(content: &str) -> Vec<&str> {
+    let mut keys = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find("{{") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find("}}") else {
+            break;
+        };
+        keys.push(&rest[..end]);
+        rest = &rest[end + 2..];
+    }
+    keys
+}
+
+impl UnitTemplate {
+    fn builtin(name: &str, description: &str, parameters: Vec<TemplateParameter>, files: Vec<TemplateFile>) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            parameters,
+            files,
+            start: None,
+        }
+    }
+
+    /// The templates shipped with the program, before the ones of the templates directory.
+    pub fn library() -> Vec<Self> {
+        let description = || TemplateParameter::new("description", "Description", "");
+        let exec_start = || TemplateParameter::new("exec_start", "ExecStart", "").required();
+        let user = || TemplateParameter::new("user", "User", "");
+        let wanted_by = || TemplateParameter::new("wanted_by", "WantedBy", "{{default_target}}");
+
+        let daemon = Self::builtin(
+            "simple-daemon",
+            "Long-running command, restarted when it fails",
+            vec![
+                description(),
+                exec_start(),
+                user(),
+                TemplateParameter::new("restart", "Restart", "on-failure").choices(&RESTART_POLICIES),
+                wanted_by(),
+            ],
+            vec![TemplateFile::new(
+                "service",
+                "[Unit]\n\
+                 Description={{description}}\n\
+                 \n\
+                 [Service]\n\
+                 ExecStart={{exec_start}}\n\
+                 User={{user}}\n\
+                 Restart={{restart}}\n\
+                 \n\
+                 [Install]\n\
+                 WantedBy={{wanted_by}}\n",
+            )],
+        );
+
+        let mut scheduled = Self::builtin(
+            "oneshot-timer",
+            "Command run to completion on a schedule, by a timer",
+            vec![
+                description(),
+                exec_start(),
+                user(),
+                TemplateParameter::new("on_calendar", "OnCalendar", "daily").required(),
+                TemplateParameter::new("persistent", "Persistent", "true").choices(&["true", "false"]),
+            ],
+            vec![
+                TemplateFile::new(
+                    "service",
+                    "[Unit]\n\
+                     Description={{description}}\n\
+                     \n\
+                     [Service]\n\
+                     Type=oneshot\n\
+                     ExecStart={{exec_start}}\n\
+                     User={{user}}\n",
+                ),
+                TemplateFile::new(
+                    "timer",
+                    "[Unit]\n\
+                     Description=Runs {{name}}.service on a schedule\n\
+                     \n\
+                     [Timer]\n\
+                     OnCalendar={{on_calendar}}\n\
+                     Persistent={{persistent}}\n\
+                     \n\
+                     [Install]\n\
+                     WantedBy=timers.target\n",
+                ),
+            ],
+        );
+        scheduled.start = Some("timer".to_string());
+
+        // What podman's quadlet generator writes for a .container file, without needing quadlet.
+        let container = Self::builtin(
+            "podman-container",
+            "Container run by podman, like a quadlet .container unit",
+            vec![
+                description(),
+                TemplateParameter::new("image", "Image", "").required(),
+                TemplateParameter::new("podman_args", "Podman options", ""),
+                TemplateParameter::new("restart", "Restart", "always").choices(&RESTART_POLICIES),
+                wanted_by(),
+            ],
+            vec![TemplateFile::new(
+                "service",
+                "[Unit]\n\
+                 Description={{description}}\n\
+                 Wants=network-online.target\n\
+                 After=network-online.target\n\
+                 \n\
+                 [Service]\n\
+                 Environment=PODMAN_SYSTEMD_UNIT=%n\n\
+                 Type=notify\n\
+                 NotifyAccess=all\n\
+                 KillMode=mixed\n\
+                 Delegate=yes\n\
+                 ExecStart=/usr/bin/podman run --name {{name}} --cidfile=%t/%N.cid --replace --rm --cgroups=split --sdnotify=conmon -d {{podman_args}} {{image}}\n\
+                 ExecStop=/usr/bin/podman rm -v -f -i --cidfile=%t/%N.cid\n\
+                 ExecStopPost=-/usr/bin/podman rm -v -f -i --cidfile=%t/%N.cid\n\
+                 Restart={{restart}}\n\
+                 \n\
+                 [Install]\n\
+                 WantedBy={{wanted_by}}\n",
+            )],
+        );
+
+        vec![daemon, scheduled, container]
+    }
+
+    /// Type of the unit enabled and started once created.
+    pub fn start_type(&self) -> &str {
+        self.start
+            .as_deref()
+            .or_else(|| self.files.first().map(|file| file.unit_type.as_str()))
+            .unwrap_or("service")
+    }
+
+    /// Checks a template read from the templates directory.
+    pub fn check(&self) -> Result<(), String> {
+        if self.files.is_empty() {
+            return Err("a template needs at least one file".to_string());
+        }
+        for (index, parameter) in self.parameters.iter().enumerate() {
+            if RESERVED_KEYS.contains(&parameter.key.as_str()) {
+                return Err(format!("\"{}\" is reserved, it cannot be a parameter", parameter.key));
+            }
+            if self.parameters[..index].iter().any(|other| other.key == parameter.key) {
+                return Err(format!("parameter \"{}\" is declared twice", parameter.key));
+            }
+            if !parameter.choices.is_empty() && !parameter.choices.contains(&parameter.default) {
+                return Err(format!("the default of \"{}\" is not one of its choices", parameter.key));
+            }
+        }
+        if !self.files.iter().any(|file| file.unit_type == self.start_type()) {
+            return Err(format!("no file has the type \"{}\" to start", self.start_type()));
+        }
+        for file in &self.files {
+            if let Some(key) = placeholders(&file.content)
+                .into_iter()
+                .find(|key| !RESERVED_KEYS.contains(key) && !self.parameters.iter().any(|p| p.key == *key))
+            {
+                return Err(format!("{{{{{}}}}} is not a parameter", key));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A template filled in by the new unit form.
+#[derive(Clone, Debug)]
+pub struct NewUnit {
+    template: UnitTemplate,
+    /// Name of the units, with or without a type suffix.
+    pub name: String,
+    /// Value of each parameter of the template, in order.
+    pub values: Vec<String>,
+    default_target: String,
+}
+
+impl NewUnit {
+    /// The template with its default values, installing session units in default.target.
+    pub fn new(template: UnitTemplate, session: bool) -> Self {
+        let default_target = if session { "default.target" } else { "multi-user.target" }.to_string();
+        let values = template
+            .parameters
+            .iter()
+            .map(|parameter| parameter.default.replace("{{default_target}}", &default_target))
+            .collect();
+        Self {
+            template,
+            name: String::new(),
+            values,
+            default_target,
+        }
+    }
+
+    pub fn template(&self) -> &UnitTemplate {
+        &self.template
+    }
+
+    /// The name without the suffix of one of the template's unit types.
+    fn base_name(&self) -> &str {
+        let name = self.name.trim();
+        self.template
+            .files
+            .iter()
+            .find_map(|file| name.strip_suffix(&format!(".{}", file.unit_type)))
+            .unwrap_or(name)
+    }
+
+    fn unit_name(&self, unit_type: &str) -> String {
+        format!("{}.{}", self.base_name(), unit_type)
+    }
+
+    /// Names of the units written, in the order of the template's files.
+    pub fn unit_names(&self) -> Vec<String> {
+        self.template.files.iter().map(|file| self.unit_name(&file.unit_type)).collect()
+    }
+
+    /// The unit enabled and started once created, e.g. the timer of a scheduled command.
+    pub fn start_unit(&self) -> String {
+        self.unit_name(self.template.start_type())
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let name = self.base_name();
+        if name.is_empty() {
+            return Err("The unit needs a name".to_string());
+        }
+        if let Some(c) = name
+            .chars()
+            .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, ':' | '-' | '_' | '.' | '\\' | '@')))
+        {
+            return Err(format!("Unit names cannot contain \"{}\"", c));
+        }
+        for (parameter, value) in self.template.parameters.iter().zip(&self.values) {
+            if parameter.required && value.trim().is_empty() {
+                return Err(format!("{} is required", parameter.label()));
+            }
+            if !parameter.choices.is_empty() && !parameter.choices.contains(value) {
+                return Err(format!("Unknown {} \"{}\"", parameter.label(), value));
+            }
+            if value.contains('\n') {
+                return Err("Values must fit on one line".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    fn value(&self, key: &str) -> Option<&str> {
+        match key {
+            "name" => Some(self.base_name()),
+            "default_target" => Some(&self.default_target),
+            _ => self
+                .template
+                .parameters
+                .iter()
+                .position(|parameter| parameter.key == key)
+                .map(|index| self.values[index].trim()),
+        }
+    }
+
+    fn render_file(&self, content: &str) -> String {
+        let mut lines: Vec<String> = Vec::new();
+        for line in content.lines() {
+            // A setting made only of an empty parameter is left to its default.
+            let only_key = line
+                .split_once('=')
+                .and_then(|(_, value)| value.trim().strip_prefix("{{")?.strip_suffix("}}"))
+                .filter(|key| !key.contains("{{") && !key.contains("}}"));
+            if only_key.is_some_and(|key| self.value(key).is_some_and(str::is_empty)) {
+                continue;
+            }
+            let mut rendered = line.to_string();
+            for key in placeholders(line) {
+                rendered = rendered.replace(&format!("{{{{{}}}}}", key), self.value(key).unwrap_or_default());
+            }
+            lines.push(rendered);
+        }
+
+        // Sections whose settings were all left out.
+        let mut kept: Vec<String> = Vec::new();
+        let mut skipping = false;
+        for (index, line) in lines.iter().enumerate() {
+            if line.starts_with('[') {
+                skipping = lines[index + 1..]
+                    .iter()
+                    .take_while(|next| !next.starts_with('['))
+                    .all(|next| next.trim().is_empty());
+            }
+            let leading_blank = kept.is_empty() && line.trim().is_empty();
+            if !skipping && !leading_blank {
+                kept.push(line.clone());
+            }
+        }
+        while kept.last().is_some_and(|line| line.trim().is_empty()) {
+            kept.pop();
+        }
+        let mut rendered = kept.join("\n");
+        rendered.push('\n');
+        rendered
+    }
+
+    /// Name and content of each unit file.
+    pub fn render(&self) -> Vec<(String, String)> {
+        self.template
+            .files
+            .iter()
+            .map(|file| (self.unit_name(&file.unit_type), self.render_file(&file.content)))
+            .collect()
+    }
+}
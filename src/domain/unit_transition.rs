@@ -0,0 +1,69 @@
+use chrono::{DateTime, Local};
+
+use super::service::qualified_name;
+
+/// A watched unit settling in another active state, as reported by its PropertiesChanged signal.
+#[derive(Clone, Debug)]
+pub struct UnitTransition {
+    unit: String,
+    /// Active state before the change, e.g. "active".
+    from: String,
+    /// Active state after the change, e.g. "failed".
+    to: String,
+    sub_state: String,
+    at: DateTime<Local>,
+}
+
+impl UnitTransition {
+    pub fn new(unit: String, from: String, to: String, sub_state: String) -> Self {
+        Self {
+            unit,
+            from,
+            to,
+            sub_state,
+            at: Local::now(),
+        }
+    }
+
+    /// The same change, reported under the unit's name in a merged view.
+    pub fn qualified(mut self, source: &str) -> Self {
+        self.unit = qualified_name(source, &self.unit);
+        self
+    }
+
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    pub fn from(&self) -> &str {
+        &self.from
+    }
+
+    pub fn to(&self) -> &str {
+        &self.to
+    }
+
+    pub fn sub_state(&self) -> &str {
+        &self.sub_state
+    }
+
+    pub fn at(&self) -> DateTime<Local> {
+        self.at
+    }
+
+    /// The unit went down, by failing or by being stopped.
+    pub fn is_alarming(&self) -> bool {
+        matches!(self.to.as_str(), "failed" | "inactive")
+    }
+
+    /// e.g. "active → failed (exit-code)".
+    pub fn describe(&self) -> String {
+        format!("{} → {} ({})", self.from, self.to, self.sub_state)
+    }
+}
+
+/// Active states a unit settles in, the ones between them (activating, deactivating,
+/// reloading...) are not reported.
+pub fn is_settled(active_state: &str) -> bool {
+    matches!(active_state, "active" | "inactive" | "failed")
+}
@@ -0,0 +1,37 @@
+use super::service::qualified_name;
+
+/// The units a unit activates and the ones activating it, as the Triggers and TriggeredBy
+/// properties of org.freedesktop.systemd1.Unit list them: a timer, socket or path unit and
+/// the service it starts.
+#[derive(Clone, Debug, Default)]
+pub struct UnitTriggers {
+    /// e.g. backup.service for backup.timer.
+    pub triggers: Vec<String>,
+    /// e.g. cups.socket and cups.path for cups.service.
+    pub triggered_by: Vec<String>,
+}
+
+impl UnitTriggers {
+    pub fn new(triggers: Vec<String>, triggered_by: Vec<String>) -> Self {
+        Self { triggers, triggered_by }
+    }
+
+    /// The same units under their names in a merged view, all of them being units of `source`.
+    pub fn qualified(self, source: &str) -> Self {
+        let qualify = |units: Vec<String>| units.iter().map(|unit| qualified_name(source, unit)).collect();
+        Self {
+            triggers: qualify(self.triggers),
+            triggered_by: qualify(self.triggered_by),
+        }
+    }
+
+    /// Every related unit paired with the label of the status tab, the units activating this
+    /// one first.
+    pub fn by_relation(&self) -> Vec<(&'static str, &str)> {
+        self.triggered_by
+            .iter()
+            .map(|unit| ("Triggered by", unit.as_str()))
+            .chain(self.triggers.iter().map(|unit| ("Triggers", unit.as_str())))
+            .collect()
+    }
+}
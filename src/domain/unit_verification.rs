@@ -0,0 +1,80 @@
+/// How bad a finding of `systemd-analyze verify` is. systemd says "ignoring" of what it skips,
+/// a typo in a setting name for instance, and loads the unit without it; the rest, e.g. a
+/// command that is not executable, keeps the unit from starting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl Severity {
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// A message of `systemd-analyze verify`, e.g.
+/// "/etc/systemd/system/web.service:12: Unknown key name 'ExecStrat' in section 'Service', ignoring."
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifyFinding {
+    /// The unit file, or the unit name for messages about the loaded unit, `None` when the
+    /// message names neither.
+    pub path: Option<String>,
+    /// Line of the file the message is about.
+    pub line: Option<usize>,
+    pub message: String,
+    pub severity: Severity,
+}
+
+impl VerifyFinding {
+    /// e.g. "/etc/systemd/system/web.service:12", empty without a path.
+    pub fn location(&self) -> String {
+        match (&self.path, self.line) {
+            (Some(path), Some(line)) => format!("{}:{}", path, line),
+            (Some(path), None) => path.clone(),
+            (None, _) => String::new(),
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+/// Whether the head of a message, before its first ": ", names a unit file or a unit.
+fn is_location(head: &str) -> bool {
+    !head.contains(char::is_whitespace) && (head.starts_with('/') || head.contains('.'))
+}
+
+fn parse_finding(line: &str) -> VerifyFinding {
+    let (path, line_number, message) = match line.split_once(": ") {
+        Some((head, message)) if is_location(head) => match head.rsplit_once(':') {
+            Some((path, number)) if number.parse::<usize>().is_ok() => {
+                (Some(path.to_string()), number.parse().ok(), message)
+            }
+            _ => (Some(head.to_string()), None, message),
+        },
+        _ => (None, None, line),
+    };
+    let severity = if message.contains("ignoring") { Severity::Warning } else { Severity::Error };
+    VerifyFinding {
+        path,
+        line: line_number,
+        message: message.to_string(),
+        severity,
+    }
+}
+
+/// The findings of the output of `systemd-analyze verify`, one per line, in its order. A
+/// unit without problems prints nothing.
+pub fn parse_verify(output: &str) -> Vec<VerifyFinding> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(parse_finding)
+        .collect()
+}
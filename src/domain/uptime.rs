@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+use super::humanize::format_duration;
+
+/// Observations further apart than this, e.g. while the program was closed or the list not
+/// refreshed, say nothing about the time in between and are not counted.
+const MAX_GAP_SECS: i64 = 600;
+
+/// Time a unit was seen up or down, accumulated between consecutive observations.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct UptimeRecord {
+    up_secs: u64,
+    down_secs: u64,
+    /// State and unix time of the last observation.
+    last_up: bool,
+    last_seen: i64,
+}
+
+/// Share of the observed time a unit was up.
+pub struct Availability {
+    up_secs: u64,
+    observed_secs: u64,
+}
+
+impl Availability {
+    pub fn percent(&self) -> f64 {
+        self.up_secs as f64 * 100.0 / self.observed_secs as f64
+    }
+}
+
+impl fmt::Display for Availability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2}% over {} observed", self.percent(), format_duration(self.observed_secs))
+    }
+}
+
+/// Observed uptime of the watched units, by "host/unit", kept across sessions.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct UptimeLog {
+    records: HashMap<String, UptimeRecord>,
+}
+
+impl UptimeLog {
+    /// Records the state of the unit at `now` (unix seconds), crediting the time since the
+    /// previous observation to the state seen then.
+    pub fn observe(&mut self, key: &str, up: bool, now: i64) {
+        let record = self.records.entry(key.to_string()).or_default();
+        let elapsed = now - record.last_seen;
+        if record.last_seen > 0 && (0..=MAX_GAP_SECS).contains(&elapsed) {
+            if record.last_up {
+                record.up_secs += elapsed as u64;
+            } else {
+                record.down_secs += elapsed as u64;
+            }
+        }
+        record.last_up = up;
+        record.last_seen = now;
+    }
+
+    /// `None` until some time was observed.
+    pub fn availability(&self, key: &str) -> Option<Availability> {
+        let record = self.records.get(key)?;
+        let observed_secs = record.up_secs + record.down_secs;
+        (observed_secs > 0).then_some(Availability {
+            up_secs: record.up_secs,
+            observed_secs,
+        })
+    }
+}
@@ -0,0 +1,310 @@
+use std::error::Error;
+use std::io;
+
+use thiserror::Error;
+use zbus::DBusError;
+
+/// Why a call to the service manager failed, in the same kinds for every backend, so that
+/// callers branch on the kind rather than on the message.
+#[derive(Debug, Clone, Error)]
+pub enum ServiceError {
+    /// The unit, container or file asked for does not exist.
+    #[error("{0}")]
+    NotFound(String),
+    /// Refused by the manager, polkit or the policy of the config, or read-only mode.
+    #[error("{0}")]
+    PermissionDenied(String),
+    /// The manager cannot be reached: no D-Bus, no engine socket, the ssh connection failing...
+    #[error("{0}")]
+    Unavailable(String),
+    #[error("{0}")]
+    Timeout(String),
+    /// Given up by the user while waiting for it, e.g. a long journal read.
+    #[error("{0}")]
+    Cancelled(String),
+    /// An answer or an output that could not be read.
+    #[error("{0}")]
+    Parse(String),
+    /// Not offered by the backend, e.g. boot analysis with OpenRC.
+    #[error("{0}")]
+    Unsupported(String),
+    /// A D-Bus call rejected for another reason, e.g. "org.freedesktop.systemd1.UnitMasked".
+    #[error("{name}: {message}")]
+    DBus { name: String, message: String },
+    /// Anything else, e.g. a command that failed.
+    #[error("{0}")]
+    Other(String),
+}
+
+impl ServiceError {
+    /// Short name of the kind, e.g. as the title of a toast.
+    pub fn label(&self) -> &str {
+        match self {
+            ServiceError::NotFound(_) => "Not found",
+            ServiceError::PermissionDenied(_) => "Permission denied",
+            ServiceError::Unavailable(_) => "Unavailable",
+            ServiceError::Timeout(_) => "Timeout",
+            ServiceError::Cancelled(_) => "Cancelled",
+            ServiceError::Parse(_) => "Unreadable answer",
+            ServiceError::Unsupported(_) => "Unsupported",
+            ServiceError::DBus { name, .. } => name,
+            ServiceError::Other(_) => "Error",
+        }
+    }
+
+    /// The error as reported, without the D-Bus error name.
+    pub fn message(&self) -> &str {
+        match self {
+            ServiceError::NotFound(message)
+            | ServiceError::PermissionDenied(message)
+            | ServiceError::Unavailable(message)
+            | ServiceError::Timeout(message)
+            | ServiceError::Cancelled(message)
+            | ServiceError::Parse(message)
+            | ServiceError::Unsupported(message)
+            | ServiceError::DBus { message, .. }
+            | ServiceError::Other(message) => message,
+        }
+    }
+
+    /// The standard error of a failed command (journalctl, rc-service, ssh...), classified by
+    /// the usual phrasing of these tools.
+    pub fn from_stderr(stderr: String) -> Self {
+        let lower = stderr.to_lowercase();
+        if lower.contains("not found") || lower.contains("does not exist") || lower.contains("no such") {
+            ServiceError::NotFound(stderr)
+        } else if lower.contains("permission denied")
+            || lower.contains("access denied")
+            || lower.contains("authentication required")
+            || lower.contains("superuser")
+        {
+            ServiceError::PermissionDenied(stderr)
+        } else if lower.contains("timed out") {
+            ServiceError::Timeout(stderr)
+        } else if lower.contains("connection refused")
+            || lower.contains("could not resolve hostname")
+            || lower.contains("failed to connect")
+        {
+            ServiceError::Unavailable(stderr)
+        } else {
+            ServiceError::Other(stderr)
+        }
+    }
+
+    /// The kind a D-Bus error name stands for.
+    fn from_dbus(name: &str, message: String) -> Self {
+        match name {
+            "org.freedesktop.systemd1.NoSuchUnit" | "org.freedesktop.DBus.Error.FileNotFound" => {
+                ServiceError::NotFound(message)
+            }
+            "org.freedesktop.DBus.Error.InteractiveAuthorizationRequired" => {
+                ServiceError::PermissionDenied(format!("{} Try running the program with sudo.", message))
+            }
+            "org.freedesktop.DBus.Error.AccessDenied" | "org.freedesktop.DBus.Error.AuthFailed" => {
+                ServiceError::PermissionDenied(message)
+            }
+            "org.freedesktop.DBus.Error.ServiceUnknown"
+            | "org.freedesktop.DBus.Error.NameHasNoOwner"
+            | "org.freedesktop.DBus.Error.Disconnected" => ServiceError::Unavailable(message),
+            "org.freedesktop.DBus.Error.NoReply" | "org.freedesktop.DBus.Error.Timeout" => ServiceError::Timeout(message),
+            _ => ServiceError::DBus {
+                name: name.to_string(),
+                message,
+            },
+        }
+    }
+}
+
+impl From<zbus::Error> for ServiceError {
+    fn from(error: zbus::Error) -> Self {
+        match error {
+            zbus::Error::MethodError(name, detail, _) => Self::from_dbus(&name, detail.unwrap_or_default()),
+            zbus::Error::FDO(error) => Self::from_dbus(
+                error.name().as_str(),
+                DBusError::description(error.as_ref()).unwrap_or_default().to_string(),
+            ),
+            zbus::Error::InputOutput(error) => io::Error::new(error.kind(), error.to_string()).into(),
+            zbus::Error::Address(_) | zbus::Error::Handshake(_) => ServiceError::Unavailable(error.to_string()),
+            zbus::Error::Variant(_) | zbus::Error::InvalidReply | zbus::Error::InvalidField => {
+                ServiceError::Parse(error.to_string())
+            }
+            error => ServiceError::Other(error.to_string()),
+        }
+    }
+}
+
+impl From<zbus::fdo::Error> for ServiceError {
+    fn from(error: zbus::fdo::Error) -> Self {
+        zbus::Error::FDO(Box::new(error)).into()
+    }
+}
+
+impl From<zbus::zvariant::Error> for ServiceError {
+    fn from(error: zbus::zvariant::Error) -> Self {
+        ServiceError::Parse(error.to_string())
+    }
+}
+
+impl From<io::Error> for ServiceError {
+    fn from(error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::NotFound => ServiceError::NotFound(error.to_string()),
+            io::ErrorKind::PermissionDenied => ServiceError::PermissionDenied(error.to_string()),
+            io::ErrorKind::TimedOut => ServiceError::Timeout(error.to_string()),
+            io::ErrorKind::ConnectionRefused | io::ErrorKind::NotConnected | io::ErrorKind::BrokenPipe => {
+                ServiceError::Unavailable(error.to_string())
+            }
+            _ => ServiceError::Other(error.to_string()),
+        }
+    }
+}
+
+impl From<serde_json::Error> for ServiceError {
+    fn from(error: serde_json::Error) -> Self {
+        ServiceError::Parse(error.to_string())
+    }
+}
+
+impl From<std::num::ParseIntError> for ServiceError {
+    fn from(error: std::num::ParseIntError) -> Self {
+        ServiceError::Parse(error.to_string())
+    }
+}
+
+impl From<std::string::FromUtf8Error> for ServiceError {
+    fn from(error: std::string::FromUtf8Error) -> Self {
+        ServiceError::Parse(error.to_string())
+    }
+}
+
+impl From<String> for ServiceError {
+    fn from(message: String) -> Self {
+        ServiceError::Other(message)
+    }
+}
+
+impl From<&str> for ServiceError {
+    fn from(message: &str) -> Self {
+        ServiceError::Other(message.to_string())
+    }
+}
+
+/// Errors of helpers still returning any error keep their kind when they are one of the above.
+impl From<Box<dyn Error>> for ServiceError {
+    fn from(error: Box<dyn Error>) -> Self {
+        let error = match error.downcast::<ServiceError>() {
+            Ok(error) => return *error,
+            Err(error) => error,
+        };
+        let error = match error.downcast::<zbus::Error>() {
+            Ok(error) => return (*error).into(),
+            Err(error) => error,
+        };
+        match error.downcast::<io::Error>() {
+            Ok(error) => (*error).into(),
+            Err(error) => ServiceError::Other(error.to_string()),
+        }
+    }
+}
+
+/// An error shown to the user, keeping the D-Bus error name of failed method calls.
+#[derive(Debug, Clone, Error)]
+pub enum AppError {
+    /// A D-Bus call was rejected, e.g. "org.freedesktop.systemd1.NoSuchUnit".
+    #[error("{name}: {message}")]
+    DBus { name: String, message: String },
+    /// A call to the service manager failing for a reason every backend reports alike.
+    #[error("{0}")]
+    Service(ServiceError),
+    /// Anything else: a command that failed, a missing file...
+    #[error("{0}")]
+    Other(String),
+}
+
+impl AppError {
+    pub fn dbus_name(&self) -> Option<&str> {
+        match self {
+            AppError::DBus { name, .. } => Some(name),
+            AppError::Service(_) | AppError::Other(_) => None,
+        }
+    }
+
+    /// e.g. the D-Bus error name, or the kind of a service manager error.
+    pub fn title(&self) -> &str {
+        match self {
+            AppError::DBus { name, .. } => name,
+            AppError::Service(error) => error.label(),
+            AppError::Other(_) => "Error",
+        }
+    }
+
+    /// The error as reported, without the D-Bus error name.
+    pub fn message(&self) -> &str {
+        match self {
+            AppError::DBus { message, .. } => message,
+            AppError::Service(error) => error.message(),
+            AppError::Other(message) => message,
+        }
+    }
+
+    /// A human-readable explanation of well-known errors, the message itself otherwise.
+    pub fn explanation(&self) -> &str {
+        if let AppError::Service(ServiceError::Timeout(_)) = self {
+            return "The service did not respond in time. It might be busy or not functioning properly.";
+        }
+        match self.dbus_name() {
+            Some("org.freedesktop.DBus.Error.InteractiveAuthorizationRequired") => {
+                "You do not have the permission to do that. Try running the program with sudo."
+            }
+            Some("org.freedesktop.DBus.Error.ServiceUnknown") => {
+                "The requested service is not available or not running."
+            }
+            Some("org.freedesktop.DBus.Error.NoReply") => {
+                "The service did not respond in time. It might be busy or not functioning properly."
+            }
+            Some("org.freedesktop.DBus.Error.AccessDenied") => {
+                "Access denied. You don't have sufficient permissions for this operation."
+            }
+            Some("org.freedesktop.DBus.Error.UnknownMethod") => {
+                "The service manager does not support this operation, it may be too old."
+            }
+            Some("org.freedesktop.systemd1.NoSuchUnit") => "The requested service unit doesn't exist.",
+            Some("org.freedesktop.systemd1.UnitMasked") => "The unit is masked, unmask it first.",
+            _ => self.message(),
+        }
+    }
+}
+
+impl From<ServiceError> for AppError {
+    fn from(error: ServiceError) -> Self {
+        match error {
+            ServiceError::DBus { name, message } => AppError::DBus { name, message },
+            error => AppError::Service(error),
+        }
+    }
+}
+
+impl From<Box<dyn Error>> for AppError {
+    fn from(error: Box<dyn Error>) -> Self {
+        if let Some(error) = error.downcast_ref::<ServiceError>() {
+            return error.clone().into();
+        }
+        match error.downcast_ref::<zbus::Error>() {
+            Some(zbus::Error::MethodError(name, detail, _)) => AppError::DBus {
+                name: name.to_string(),
+                message: detail.clone().unwrap_or_default(),
+            },
+            Some(zbus::Error::FDO(error)) => AppError::DBus {
+                name: error.name().to_string(),
+                message: DBusError::description(error.as_ref()).unwrap_or_default().to_string(),
+            },
+            _ => AppError::Other(error.to_string()),
+        }
+    }
+}
+
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        AppError::Other(message)
+    }
+}
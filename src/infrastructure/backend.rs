@@ -0,0 +1,86 @@
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::path::Path;
+
+use crate::domain::service_repository::ServiceRepository;
+use crate::errors::ServiceError;
+use crate::infrastructure::container_service_adapter::ContainerServiceAdapter;
+use crate::infrastructure::demo_service_adapter::{DemoServiceAdapter, Fixture};
+use crate::infrastructure::openrc_service_adapter::OpenRcServiceAdapter;
+use crate::infrastructure::systemd_service_adapter::{ConnectionType, SystemdServiceAdapter};
+
+/// Init system, or container engine, whose services are managed.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    /// Detect the running init system, falling back to a container engine.
+    Auto,
+    Systemd,
+    Openrc,
+    /// Docker or Podman containers, through the API socket of the engine.
+    Containers,
+    /// Example units kept in memory, to explore the interface without a service manager.
+    Demo,
+}
+
+impl Backend {
+    /// The init system of this machine, following `sd_booted()` and OpenRC's own check.
+    fn detect() -> Option<Self> {
+        if Path::new("/run/systemd/system").exists() {
+            Some(Backend::Systemd)
+        } else if Path::new("/run/openrc").exists() {
+            Some(Backend::Openrc)
+        } else {
+            None
+        }
+    }
+
+    pub fn create_repository(
+        self,
+        connection_type: ConnectionType,
+        unit_types: Vec<String>,
+    ) -> Result<Box<dyn ServiceRepository>, ServiceError> {
+        let repository: Box<dyn ServiceRepository> = match self {
+            Backend::Auto => return Self::fallback(connection_type, unit_types),
+            Backend::Systemd => Box::new(SystemdServiceAdapter::new(connection_type, unit_types).map_err(|e| {
+                ServiceError::Unavailable(format!(
+                    "Could not reach systemd over D-Bus ({}). Is this machine running systemd? \
+                     --backend openrc, containers or demo manage something else.",
+                    e
+                ))
+            })?),
+            Backend::Openrc => Box::new(OpenRcServiceAdapter::new(connection_type)),
+            Backend::Containers => Box::new(ContainerServiceAdapter::new(connection_type)?),
+            Backend::Demo => Box::new(DemoServiceAdapter::new(Fixture::examples(), unit_types)),
+        };
+        Ok(repository)
+    }
+
+    /// The detected init system, or without one whatever answers: systemd may still be
+    /// reachable over D-Bus, e.g. from a container sharing the bus of its host, and then a
+    /// container engine. Explains what is missing when nothing does.
+    fn fallback(
+        connection_type: ConnectionType,
+        unit_types: Vec<String>,
+    ) -> Result<Box<dyn ServiceRepository>, ServiceError> {
+        if let Some(backend) = Self::detect() {
+            return backend.create_repository(connection_type, unit_types);
+        }
+        let systemd_error = match SystemdServiceAdapter::new(connection_type, unit_types) {
+            Ok(repository) => return Ok(Box::new(repository)),
+            Err(e) => e,
+        };
+        let container_error = match ContainerServiceAdapter::new(connection_type) {
+            Ok(repository) => return Ok(Box::new(repository)),
+            Err(e) => e,
+        };
+        Err(ServiceError::Unavailable(format!(
+            "No service manager found on this machine:\n  \
+             systemd is not running (no /run/systemd/system, and D-Bus answered: {})\n  \
+             OpenRC is not running (no /run/openrc)\n  \
+             no Docker or Podman engine answers ({})\n\
+             Run with --backend demo to explore the interface with example units.",
+            systemd_error, container_error
+        )))
+    }
+}
@@ -0,0 +1,120 @@
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::errors::ServiceError;
+
+/// How often a call waited for is checked for a timeout or a cancellation.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Bounds the slow calls to the service manager, listing the units or reading the journal:
+/// they fail after `timeout`, or as soon as `cancel` is called from another thread, e.g. the
+/// one reading the keys while the main thread waits.
+#[derive(Clone, Default)]
+pub struct CallLimits {
+    timeout: Option<Duration>,
+    cancelled: Arc<AtomicBool>,
+    /// Calls in progress, only those can be cancelled.
+    pending: Arc<AtomicUsize>,
+}
+
+/// A call in progress, counted until dropped.
+pub struct PendingCall {
+    limits: CallLimits,
+    what: String,
+    started: Instant,
+}
+
+impl CallLimits {
+    /// `None` waits as long as the call takes.
+    pub fn new(timeout: Option<Duration>) -> Self {
+        Self {
+            timeout,
+            ..Self::default()
+        }
+    }
+
+    /// Cancels the calls in progress, false when there is none.
+    pub fn cancel(&self) -> bool {
+        if self.pending.load(Ordering::Relaxed) == 0 {
+            return false;
+        }
+        self.cancelled.store(true, Ordering::Relaxed);
+        true
+    }
+
+    /// Starts counting a call described as e.g. "Listing the units", clearing the cancellation
+    /// of a previous one.
+    pub fn begin(&self, what: &str) -> PendingCall {
+        if self.pending.fetch_add(1, Ordering::Relaxed) == 0 {
+            self.cancelled.store(false, Ordering::Relaxed);
+        }
+        PendingCall {
+            limits: self.clone(),
+            what: what.to_string(),
+            started: Instant::now(),
+        }
+    }
+
+    /// Runs `call` on its own thread, waiting for it until the timeout or a cancellation. The
+    /// call is abandoned then, its result dropped whenever it comes.
+    pub fn run<T, F>(&self, what: &str, call: F) -> Result<T, ServiceError>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> Result<T, ServiceError> + Send + 'static,
+    {
+        let pending = self.begin(what);
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = sender.send(call());
+        });
+        loop {
+            match receiver.recv_timeout(POLL_INTERVAL) {
+                Ok(result) => return result,
+                Err(RecvTimeoutError::Timeout) => pending.check()?,
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(ServiceError::Other(format!("{} stopped unexpectedly", what)));
+                }
+            }
+        }
+    }
+
+    /// Like `Command::output`, killing the command when it times out or is cancelled.
+    pub fn output(&self, what: &str, command: &mut Command) -> Result<Output, ServiceError> {
+        let child = command.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+        let pid = child.id();
+        let output = self.run(what, move || Ok(child.wait_with_output()?));
+        if let Err(ServiceError::Timeout(_) | ServiceError::Cancelled(_)) = output {
+            unsafe {
+                libc::kill(pid as libc::pid_t, libc::SIGKILL);
+            }
+        }
+        output
+    }
+}
+
+impl PendingCall {
+    /// Fails once the call was cancelled or took longer than the timeout.
+    pub fn check(&self) -> Result<(), ServiceError> {
+        if self.limits.cancelled.load(Ordering::Relaxed) {
+            return Err(ServiceError::Cancelled(format!("{} was cancelled", self.what)));
+        }
+        match self.limits.timeout {
+            Some(timeout) if self.started.elapsed() > timeout => Err(ServiceError::Timeout(format!(
+                "{} took longer than {}s, see call_timeout",
+                self.what,
+                timeout.as_secs()
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Drop for PendingCall {
+    fn drop(&mut self) {
+        self.limits.pending.fetch_sub(1, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,10 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use std::io::{self, Write};
+
+/// Sets the system clipboard through the terminal with an OSC 52 escape sequence, which also
+/// works over SSH. Terminals that do not support it ignore the sequence.
+pub fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", STANDARD.encode(text))?;
+    stdout.flush()
+}
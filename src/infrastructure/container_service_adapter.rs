@@ -0,0 +1,738 @@
+use std::collections::HashMap;
+use std::env;
+use std::io::{Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant};
+
+use chrono::DateTime;
+use serde_json::{json, Value};
+
+use crate::errors::ServiceError;
+use crate::infrastructure::call_limits::CallLimits;
+use crate::domain::action_journal::UnitFileChange;
+use crate::domain::action_plan::{ActionPlan, UnitAction};
+use crate::domain::action_policy::PolicyAction;
+use crate::domain::boot_timing::{ChainLink, UnitActivation};
+use crate::domain::security_audit::{SecurityAudit, SecurityScore};
+use crate::domain::slice::Slice;
+use crate::domain::crash_loop::RestartSettings;
+use crate::domain::job::JobResult;
+use crate::domain::journal_boot::ListedBoot;
+use crate::domain::journal_entry::{since_unix_time, JournalEntry, LogFilter};
+use crate::domain::kill::KillTarget;
+use crate::domain::latency::ListTimings;
+use crate::domain::power_action::PowerAction;
+use crate::domain::process::UnitProcess;
+use crate::domain::resource_limits::{ResourceLimits, UNLIMITED};
+use crate::domain::resource_usage::ResourceUsage;
+use crate::domain::runtime_stats::RuntimeStats;
+use crate::domain::system_summary::SystemSummary;
+use crate::domain::scheduled_action::ScheduledAction;
+use crate::domain::service::Service;
+use crate::domain::service_repository::ServiceRepository;
+use crate::domain::service_state::ServiceState;
+use crate::domain::unit_alias::UnitLinks;
+use crate::domain::unit_capabilities::UnitCapabilities;
+use crate::domain::unit_conditions::UnitConditions;
+use crate::domain::unit_history::UnitHistory;
+use crate::domain::unit_dependencies::UnitDependencies;
+use crate::domain::unit_environment::UnitEnvironment;
+use crate::domain::unit_file::{UnitFileMatch, UnitFilePaths};
+use crate::domain::unit_path::UnitPathReference;
+use crate::domain::unit_property::{SocketProperty, TimerProperty, UnitProperty};
+use crate::domain::unit_transition::UnitTransition;
+use crate::domain::unit_verification::VerifyFinding;
+use crate::domain::unit_triggers::UnitTriggers;
+use crate::infrastructure::systemd_service_adapter::ConnectionType;
+
+/// Sockets of rootful Docker and Podman, tried in this order.
+const SYSTEM_SOCKETS: [&str; 2] = ["/var/run/docker.sock", "/run/podman/podman.sock"];
+/// Sockets of rootless Docker and Podman, under `$XDG_RUNTIME_DIR`.
+const SESSION_SOCKETS: [&str; 2] = ["docker.sock", "podman/podman.sock"];
+/// Long enough for a stop, which waits 10 seconds before killing the container.
+const TIMEOUT: Duration = Duration::from_secs(60);
+/// Restart policies under which the engine starts the container with itself.
+const ENABLED_POLICY: &str = "unless-stopped";
+const DISABLED_POLICY: &str = "no";
+
+/// Manages Docker or Podman containers as services, through the Docker Engine API both serve
+/// on their unix socket.
+pub struct ContainerServiceAdapter {
+    socket: PathBuf,
+}
+
+fn unsupported(what: &str) -> ServiceError {
+    ServiceError::Unsupported(format!("{} is not available with containers", what))
+}
+
+/// The API socket for the connection type: `$DOCKER_HOST` when it names a unix socket, then
+/// the rootful (system) or rootless (session) sockets of Docker and Podman.
+fn find_socket(connection_type: ConnectionType) -> Result<PathBuf, ServiceError> {
+    if let Ok(host) = env::var("DOCKER_HOST")
+        && let Some(path) = host.strip_prefix("unix://")
+    {
+        return Ok(PathBuf::from(path));
+    }
+    let candidates: Vec<PathBuf> = match connection_type {
+        ConnectionType::System => SYSTEM_SOCKETS.iter().map(PathBuf::from).collect(),
+        ConnectionType::Session => {
+            let runtime_dir = env::var("XDG_RUNTIME_DIR").map_err(|_| "XDG_RUNTIME_DIR is not set")?;
+            SESSION_SOCKETS.iter().map(|socket| Path::new(&runtime_dir).join(socket)).collect()
+        }
+    };
+    candidates.iter().find(|path| path.exists()).cloned().ok_or_else(|| {
+        let tried: Vec<String> = candidates.iter().map(|path| path.display().to_string()).collect();
+        format!("No Docker or Podman socket found, tried {}", tried.join(", ")).into()
+    })
+}
+
+/// Status code and body of an HTTP/1.1 response, decoding a chunked body.
+fn parse_response(response: &[u8]) -> Result<(u16, Vec<u8>), ServiceError> {
+    let header_end = response
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or("Incomplete response from the container engine")?;
+    let head = String::from_utf8_lossy(&response[..header_end]);
+    let mut lines = head.lines();
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse().ok())
+        .ok_or("Malformed response from the container engine")?;
+    let chunked = lines.any(|line| {
+        let line = line.to_ascii_lowercase();
+        line.starts_with("transfer-encoding:") && line.contains("chunked")
+    });
+    let body = &response[header_end + 4..];
+    Ok((status, if chunked { dechunk(body)? } else { body.to_vec() }))
+}
+
+/// Joins the chunks of a `Transfer-Encoding: chunked` body, each one its hexadecimal size
+/// on a line, then its data.
+fn dechunk(mut body: &[u8]) -> Result<Vec<u8>, ServiceError> {
+    let mut decoded = Vec::new();
+    loop {
+        let line_end = body
+            .windows(2)
+            .position(|window| window == b"\r\n")
+            .ok_or("Truncated response from the container engine")?;
+        let size = String::from_utf8_lossy(&body[..line_end]);
+        let size = usize::from_str_radix(size.split(';').next().unwrap_or_default().trim(), 16)?;
+        if size == 0 {
+            return Ok(decoded);
+        }
+        let start = line_end + 2;
+        let chunk = body.get(start..start + size).ok_or("Truncated response from the container engine")?;
+        decoded.extend_from_slice(chunk);
+        body = body.get(start + size + 2..).unwrap_or_default();
+    }
+}
+
+/// Joins the frames of the log stream of a container without a TTY, each one an 8-byte
+/// header (the stream, then the big-endian size of the payload) followed by its payload.
+fn demultiplex(mut stream: &[u8]) -> Vec<u8> {
+    let mut output = Vec::new();
+    while stream.len() >= 8 {
+        let size = u32::from_be_bytes([stream[4], stream[5], stream[6], stream[7]]) as usize;
+        let end = (8 + size).min(stream.len());
+        output.extend_from_slice(&stream[8..end]);
+        stream = &stream[end..];
+    }
+    output
+}
+
+fn text<'a>(value: &'a Value, pointer: &str) -> &'a str {
+    value.pointer(pointer).and_then(Value::as_str).unwrap_or_default()
+}
+
+fn number(value: &Value, pointer: &str) -> i64 {
+    value.pointer(pointer).and_then(Value::as_i64).unwrap_or_default()
+}
+
+/// The container as a service: its image as description, its restart policy as enablement
+/// and its engine state as sub state, e.g. "exited".
+fn container_service(name: &str, inspect: &Value) -> Service {
+    let status = text(inspect, "/State/Status");
+    let active = match status {
+        "running" | "paused" => "active",
+        "restarting" => "activating",
+        "removing" => "deactivating",
+        "dead" => "failed",
+        "exited" if number(inspect, "/State/ExitCode") != 0 => "failed",
+        _ => "inactive",
+    };
+    let enabled = matches!(text(inspect, "/HostConfig/RestartPolicy/Name"), "always" | "unless-stopped");
+    let state = ServiceState::new(
+        "loaded".to_string(),
+        active.to_string(),
+        status.to_string(),
+        if enabled { "enabled" } else { "disabled" }.to_string(),
+        String::new(),
+        if status == "paused" { "frozen" } else { "running" }.to_string(),
+    );
+    Service::new(name.to_string(), text(inspect, "/Config/Image").to_string(), state)
+}
+
+/// A container limit, 0 (or -1 for the number of processes) meaning none.
+fn limit(value: i64) -> u64 {
+    if value <= 0 { UNLIMITED } else { value as u64 }
+}
+
+impl ContainerServiceAdapter {
+    pub fn new(connection_type: ConnectionType) -> Result<Self, ServiceError> {
+        Ok(Self {
+            socket: find_socket(connection_type)?,
+        })
+    }
+
+    /// Sends a request to the API, returning the body of the response. Requests for a state
+    /// the container is already in (304) succeed.
+    fn request(&self, method: &str, path: &str, body: Option<Value>) -> Result<Vec<u8>, ServiceError> {
+        let mut stream = UnixStream::connect(&self.socket).map_err(|e| format!("{}: {}", self.socket.display(), e))?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+        let body = body.map(|body| body.to_string()).unwrap_or_default();
+        let request = format!(
+            "{} {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            body.len(),
+            body
+        );
+        stream.write_all(request.as_bytes())?;
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response)?;
+
+        let (status, body) = parse_response(&response)?;
+        if (200..300).contains(&status) || status == 304 {
+            return Ok(body);
+        }
+        let message = serde_json::from_slice::<Value>(&body)
+            .ok()
+            .and_then(|error| error.get("message").and_then(Value::as_str).map(str::to_string))
+            .unwrap_or_else(|| format!("The container engine answered {}", status));
+        Err(match status {
+            404 => ServiceError::NotFound(message),
+            401 | 403 => ServiceError::PermissionDenied(message),
+            _ => ServiceError::Other(message),
+        })
+    }
+
+    fn get(&self, path: &str) -> Result<Value, ServiceError> {
+        Ok(serde_json::from_slice(&self.request("GET", path, None)?)?)
+    }
+
+    fn post(&self, path: &str, body: Option<Value>) -> Result<(), ServiceError> {
+        self.request("POST", path, body).map(|_| ())
+    }
+
+    /// Names of every container, running or not, without the leading "/".
+    fn container_names(&self) -> Result<Vec<String>, ServiceError> {
+        let containers = self.get("/containers/json?all=true")?;
+        Ok(containers
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|container| container.pointer("/Names/0")?.as_str())
+            .map(|name| name.trim_start_matches('/').to_string())
+            .collect())
+    }
+
+    fn inspect(&self, name: &str) -> Result<Value, ServiceError> {
+        self.get(&format!("/containers/{}/json", name))
+    }
+
+    /// The last `lines` lines of the output of the container, as entries named after it.
+    fn log_entries(&self, name: &str, lines: u32, since: Option<i64>) -> Result<Vec<JournalEntry>, ServiceError> {
+        // Without a TTY, stdout and stderr come multiplexed in frames.
+        let tty = self.inspect(name)?.pointer("/Config/Tty").and_then(Value::as_bool).unwrap_or(false);
+        let mut path = format!("/containers/{}/logs?stdout=true&stderr=true&timestamps=true&tail={}", name, lines);
+        if let Some(since) = since {
+            path.push_str(&format!("&since={}", since));
+        }
+        let body = self.request("GET", &path, None)?;
+        let output = if tty { body } else { demultiplex(&body) };
+        Ok(String::from_utf8_lossy(&output)
+            .lines()
+            .map(|line| {
+                let line = line.trim_end_matches('\r');
+                let timed = line.split_once(' ').and_then(|(time, message)| {
+                    let time = DateTime::parse_from_rfc3339(time).ok()?;
+                    Some(JournalEntry::timed(time.timestamp_micros() as u64, name, message))
+                });
+                timed.unwrap_or_else(|| JournalEntry::raw(line))
+            })
+            .collect())
+    }
+
+    fn set_restart_policy(&self, name: &str, policy: &str) -> Result<(), ServiceError> {
+        self.post(
+            &format!("/containers/{}/update", name),
+            Some(json!({ "RestartPolicy": { "Name": policy } })),
+        )
+    }
+}
+
+impl ServiceRepository for ContainerServiceAdapter {
+    /// Switches between the rootful (system) and rootless (session) engine.
+    fn change_connection(&mut self, connection_type: ConnectionType) -> Result<(), ServiceError> {
+        self.socket = find_socket(connection_type)?;
+        Ok(())
+    }
+
+    /// Every request opens its own connection to the socket.
+    fn connection_lost(&self) -> bool {
+        false
+    }
+
+    fn reconnect(&mut self) -> Result<(), ServiceError> {
+        Ok(())
+    }
+
+    fn worker(&self) -> Result<Box<dyn ServiceRepository>, ServiceError> {
+        Ok(Box::new(Self { socket: self.socket.clone() }))
+    }
+
+    /// Requests already give up after `TIMEOUT`, which a stop needs.
+    fn set_call_limits(&mut self, _limits: CallLimits) {}
+
+    fn list_services(&self) -> Result<Vec<Service>, ServiceError> {
+        // Containers removed since they were listed are left out.
+        Ok(self
+            .container_names()?
+            .iter()
+            .filter_map(|name| Some(container_service(name, &self.inspect(name).ok()?)))
+            .collect())
+    }
+
+    /// The listing call lists the containers, the per-unit calls inspecting each one.
+    fn time_listing(&self) -> Result<ListTimings, ServiceError> {
+        let started = Instant::now();
+        let names = self.container_names()?;
+        let list_call = started.elapsed();
+
+        let unit_calls = names
+            .iter()
+            .map(|name| {
+                let started = Instant::now();
+                let _ = self.inspect(name);
+                started.elapsed()
+            })
+            .collect();
+
+        Ok(ListTimings::new(list_call, unit_calls))
+    }
+
+    fn get_service_log(&self, name: &str, lines: u32) -> Result<String, ServiceError> {
+        let entries = self.get_journal_entries(name, lines, &LogFilter::default())?;
+        Ok(entries.iter().map(JournalEntry::line).collect::<Vec<_>>().join("\n"))
+    }
+
+    // Container output has no priorities, every line is shown whatever the minimum priority.
+    fn get_journal_entries(&self, name: &str, lines: u32, filter: &LogFilter) -> Result<Vec<JournalEntry>, ServiceError> {
+        let since = match filter.since {
+            Some(since) => Some(since_unix_time(since).ok_or_else(|| format!("Unknown time range {}", since))?),
+            None => None,
+        };
+        self.log_entries(name, lines, since)
+    }
+
+    fn get_journal_boots(&self) -> Result<Vec<ListedBoot>, ServiceError> {
+        Err(unsupported("Reading the logs of previous boots"))
+    }
+
+    fn get_older_journal_entries(
+        &self,
+        _name: &str,
+        _cursor: &str,
+        _lines: u32,
+        _filter: &LogFilter,
+    ) -> Result<Vec<JournalEntry>, ServiceError> {
+        Err(unsupported("Paging back through the log"))
+    }
+
+    fn get_log_timestamps(&self, name: &str) -> Result<Vec<u64>, ServiceError> {
+        // Same lines as the log view, keeping only the timestamp of each one.
+        Ok(self.log_entries(name, 1000, None)?.iter().filter_map(JournalEntry::timestamp).collect())
+    }
+
+    fn get_journal_units_since(&self, _minutes: u64) -> Result<Vec<String>, ServiceError> {
+        Err(unsupported("Ranking services by log volume"))
+    }
+
+    // The engine answers once the container is started or stopped, there is no job to track.
+    fn start_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        self.post(&format!("/containers/{}/start", name), None).map(|_| None)
+    }
+
+    fn start_transient_unit(&self, _name: &str, _argv: &[String], _limits: &ResourceLimits) -> Result<Option<String>, ServiceError> {
+        Err(unsupported("Running transient services"))
+    }
+
+    fn stop_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        self.post(&format!("/containers/{}/stop", name), None).map(|_| None)
+    }
+
+    fn restart_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        self.post(&format!("/containers/{}/restart", name), None).map(|_| None)
+    }
+
+    fn reload_service(&self, _name: &str) -> Result<Option<String>, ServiceError> {
+        Err(unsupported("Reloading"))
+    }
+
+    fn reload_or_restart_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        self.restart_service(name)
+    }
+
+    fn get_unit_capabilities(&self, _name: &str) -> Result<UnitCapabilities, ServiceError> {
+        Ok(UnitCapabilities {
+            can_start: true,
+            can_stop: true,
+            can_reload: false,
+            can_isolate: false,
+        })
+    }
+
+    fn list_targets(&self) -> Result<Vec<Service>, ServiceError> {
+        Err(unsupported("Targets"))
+    }
+
+    fn get_default_target(&self) -> Result<String, ServiceError> {
+        Err(unsupported("Targets"))
+    }
+
+    fn set_default_target(&self, _name: &str) -> Result<(), ServiceError> {
+        Err(unsupported("Targets"))
+    }
+
+    fn isolate_target(&self, _name: &str) -> Result<Option<String>, ServiceError> {
+        Err(unsupported("Targets"))
+    }
+
+    fn power(&self, _action: PowerAction) -> Result<(), ServiceError> {
+        Err(unsupported("Shutting the machine down"))
+    }
+
+    fn watch_jobs(&mut self, _sender: Sender<JobResult>) -> Result<(), ServiceError> {
+        Ok(())
+    }
+
+    fn watch_units(&mut self, units: &[String], _sender: Sender<UnitTransition>) -> Result<(), ServiceError> {
+        if units.is_empty() {
+            return Ok(());
+        }
+        Err(unsupported("Watching units"))
+    }
+
+    /// Containers restarting "unless-stopped" are started with the engine, like enabled units
+    /// are with the machine.
+    fn enable_service(&self, name: &str) -> Result<Vec<UnitFileChange>, ServiceError> {
+        self.set_restart_policy(name, ENABLED_POLICY).map(|_| Vec::new())
+    }
+
+    fn disable_service(&self, name: &str) -> Result<Vec<UnitFileChange>, ServiceError> {
+        self.set_restart_policy(name, DISABLED_POLICY).map(|_| Vec::new())
+    }
+
+    fn revert_unit_file_changes(&self, _name: &str, _changes: &[UnitFileChange]) -> Result<(), ServiceError> {
+        Err(unsupported("Reverting unit file links"))
+    }
+
+    fn freeze_service(&self, name: &str) -> Result<(), ServiceError> {
+        self.post(&format!("/containers/{}/pause", name), None)
+    }
+
+    fn thaw_service(&self, name: &str) -> Result<(), ServiceError> {
+        self.post(&format!("/containers/{}/unpause", name), None)
+    }
+
+    // An exited container keeps its exit code until it starts again.
+    fn reset_failed_unit(&self, _name: &str) -> Result<(), ServiceError> {
+        Err(unsupported("Resetting the failed state"))
+    }
+
+    fn reset_failed(&self) -> Result<(), ServiceError> {
+        Err(unsupported("Resetting the failed state"))
+    }
+
+    /// The engine signals the first process of the container, which is all of it for most
+    /// images.
+    fn kill_service(&self, name: &str, target: KillTarget, signal: i32) -> Result<(), ServiceError> {
+        if target == KillTarget::All {
+            return Err(unsupported("Signaling every process"));
+        }
+        self.post(&format!("/containers/{}/kill?signal={}", name, signal), None)
+    }
+
+    fn reload_daemon(&self) -> Result<(), ServiceError> {
+        Ok(())
+    }
+
+    fn get_unit_property(&self, _name: &str) -> Result<UnitProperty, ServiceError> {
+        Err(unsupported("Reading service properties"))
+    }
+
+    fn get_restart_settings(&self, _name: &str) -> Result<RestartSettings, ServiceError> {
+        Err(unsupported("Reading restart settings"))
+    }
+
+    fn get_runtime_stats(&self, name: &str) -> Result<RuntimeStats, ServiceError> {
+        let inspect = self.inspect(name)?;
+        let running = inspect.pointer("/State/Running").and_then(Value::as_bool).unwrap_or(false);
+        let started = DateTime::parse_from_rfc3339(text(&inspect, "/State/StartedAt"));
+        let main_start_usec = match started {
+            Ok(started) if running => started.timestamp_micros() as u64,
+            _ => 0,
+        };
+        // The memory of a container takes a stats call of its own, streamed by default.
+        Ok(RuntimeStats::new(number(&inspect, "/RestartCount") as u32, main_start_usec, None))
+    }
+
+    fn get_resource_usage(&self, _name: &str) -> Result<ResourceUsage, ServiceError> {
+        Err(unsupported("Sampling the resource usage"))
+    }
+
+    /// The processes `ps` reports for the container, from the engine's `top`.
+    fn get_unit_processes(&self, name: &str) -> Result<Vec<UnitProcess>, ServiceError> {
+        let top = self.get(&format!("/containers/{}/top?ps_args=-o%20pid,ppid,cputimes,rss,args", name))?;
+        let rows = top.get("Processes").and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default();
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                let field = |index: usize| row.get(index).and_then(Value::as_str);
+                Some(UnitProcess::new(
+                    field(0)?.parse().ok()?,
+                    field(1)?.parse().ok()?,
+                    field(4)?.to_string(),
+                    field(2)?.parse::<u64>().ok()? * 1_000_000,
+                    field(3)?.parse::<u64>().ok()? * 1024,
+                ))
+            })
+            .collect())
+    }
+
+    fn get_state_change_timestamp(&self, name: &str) -> Result<u64, ServiceError> {
+        let inspect = self.inspect(name)?;
+        let running = inspect.pointer("/State/Running").and_then(Value::as_bool).unwrap_or(false);
+        let pointer = if running { "/State/StartedAt" } else { "/State/FinishedAt" };
+        // Engines report "0001-01-01T00:00:00Z" for a container that never stopped.
+        Ok(DateTime::parse_from_rfc3339(text(&inspect, pointer))
+            .map(|time| time.timestamp_micros().max(0) as u64)
+            .unwrap_or(0))
+    }
+
+    // A container has no unit file to reload.
+    fn get_need_daemon_reload(&self, _name: &str) -> Result<bool, ServiceError> {
+        Ok(false)
+    }
+
+    fn get_freezer_state(&self, name: &str) -> Result<String, ServiceError> {
+        let inspect = self.inspect(name)?;
+        let paused = inspect.pointer("/State/Paused").and_then(Value::as_bool).unwrap_or(false);
+        Ok(if paused { "frozen" } else { "running" }.to_string())
+    }
+
+    fn get_slices(&self) -> Result<Vec<Slice>, ServiceError> {
+        Err(unsupported("Browsing the slices"))
+    }
+
+    fn get_boot_time(&self) -> Result<String, ServiceError> {
+        Err(unsupported("Analyzing the boot"))
+    }
+
+    fn get_system_summary(&self) -> Result<SystemSummary, ServiceError> {
+        Err(unsupported("Summarizing the manager"))
+    }
+
+    fn get_boot_blame(&self) -> Result<Vec<UnitActivation>, ServiceError> {
+        Err(unsupported("Analyzing the boot"))
+    }
+
+    fn get_critical_chain(&self, _unit: Option<&str>) -> Result<Vec<ChainLink>, ServiceError> {
+        Err(unsupported("Analyzing the boot"))
+    }
+
+    fn get_security_scores(&self) -> Result<Vec<SecurityScore>, ServiceError> {
+        Err(unsupported("Analyzing the security of services"))
+    }
+
+    fn get_security_audit(&self, _name: &str) -> Result<SecurityAudit, ServiceError> {
+        Err(unsupported("Analyzing the security of services"))
+    }
+
+    /// Inspecting a container is a single cheap call, not worth caching.
+    fn unit_generation(&self, _name: &str) -> Option<String> {
+        None
+    }
+
+    /// The configuration of the container, like `docker inspect`.
+    fn systemctl_cat(&self, name: &str) -> Result<String, ServiceError> {
+        Ok(format!("# docker inspect {}\n{}", name, serde_json::to_string_pretty(&self.inspect(name)?)?))
+    }
+
+    /// The inspected configuration stands for the unit file, under the header `systemctl_cat`
+    /// gives it.
+    fn get_unit_file_paths(&self, name: &str) -> Result<UnitFilePaths, ServiceError> {
+        Ok(UnitFilePaths {
+            fragment: format!("docker inspect {}", name),
+            drop_ins: Vec::new(),
+        })
+    }
+
+    fn get_unit_links(&self, _name: &str) -> Result<UnitLinks, ServiceError> {
+        Err(unsupported("Aliases"))
+    }
+
+    fn get_loaded_settings(&self, _name: &str, _settings: &[String]) -> Result<HashMap<String, Vec<String>>, ServiceError> {
+        Err(unsupported("Comparing unit files with the loaded units"))
+    }
+
+    fn verify_unit(&self, _name: &str) -> Result<Vec<VerifyFinding>, ServiceError> {
+        Err(unsupported("Checking unit files"))
+    }
+
+    fn create_unit(&self, _name: &str, _content: &str) -> Result<String, ServiceError> {
+        Err(unsupported("Creating services"))
+    }
+
+    fn write_drop_in(&self, _name: &str, _file_name: &str, _content: &str) -> Result<String, ServiceError> {
+        Err(unsupported("Editing services"))
+    }
+
+    fn edit_unit_command(&self, _name: &str) -> Result<Command, ServiceError> {
+        Err(unsupported("Editing services"))
+    }
+
+    fn shell_command(&self, _name: &str, _directory: &str) -> Result<Command, ServiceError> {
+        Err(unsupported("Opening a shell for services"))
+    }
+
+    fn remove_drop_in(&self, _name: &str, _path: &str) -> Result<(), ServiceError> {
+        Err(unsupported("Editing services"))
+    }
+
+    fn get_unit_environment(&self, name: &str) -> Result<UnitEnvironment, ServiceError> {
+        let inspect = self.inspect(name)?;
+        let variables = inspect.pointer("/Config/Env").and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default();
+        Ok(UnitEnvironment {
+            variables: variables.iter().filter_map(Value::as_str).map(str::to_string).collect(),
+            files: Vec::new(),
+        })
+    }
+
+    fn get_resource_limits(&self, name: &str) -> Result<ResourceLimits, ServiceError> {
+        let inspect = self.inspect(name)?;
+        let nano_cpus = limit(number(&inspect, "/HostConfig/NanoCpus"));
+        Ok(ResourceLimits {
+            // A billion nano CPUs is one CPU, 100%.
+            cpu_quota_percent: Some(if nano_cpus == UNLIMITED { UNLIMITED } else { nano_cpus / 10_000_000 }),
+            memory_max_bytes: Some(limit(number(&inspect, "/HostConfig/Memory"))),
+            tasks_max: Some(limit(number(&inspect, "/HostConfig/PidsLimit"))),
+        })
+    }
+
+    /// `docker update` changes the container for good, the limits outlive a reboot either way.
+    fn set_resource_limits(&self, name: &str, limits: &ResourceLimits, _runtime: bool) -> Result<(), ServiceError> {
+        let mut update = serde_json::Map::new();
+        if let Some(percent) = limits.cpu_quota_percent {
+            let nano_cpus = if percent == UNLIMITED { 0 } else { percent * 10_000_000 };
+            update.insert("NanoCpus".to_string(), json!(nano_cpus));
+        }
+        if let Some(bytes) = limits.memory_max_bytes {
+            update.insert("Memory".to_string(), json!(if bytes == UNLIMITED { 0 } else { bytes }));
+            // Otherwise a memory limit above the previous swap limit is refused.
+            update.insert("MemorySwap".to_string(), json!(-1));
+        }
+        if let Some(tasks) = limits.tasks_max {
+            update.insert("PidsLimit".to_string(), json!(if tasks == UNLIMITED { -1 } else { tasks as i64 }));
+        }
+        self.post(&format!("/containers/{}/update", name), Some(Value::Object(update)))
+    }
+
+    fn list_unit_paths(&self) -> Result<Vec<UnitPathReference>, ServiceError> {
+        Err(unsupported("Looking up the services managing a path"))
+    }
+
+    fn search_unit_files(&self, _query: &str) -> Result<Vec<UnitFileMatch>, ServiceError> {
+        Err(unsupported("Searching unit files"))
+    }
+
+    // Containers are started on their own, whatever network or volume they share.
+    fn get_unit_dependencies(&self, _name: &str) -> Result<UnitDependencies, ServiceError> {
+        Ok(UnitDependencies::default())
+    }
+
+    fn get_unit_timers(&self, _name: &str) -> Result<Vec<(String, TimerProperty)>, ServiceError> {
+        Ok(Vec::new())
+    }
+
+    fn get_unit_sockets(&self, _name: &str) -> Result<Vec<(String, SocketProperty)>, ServiceError> {
+        Ok(Vec::new())
+    }
+
+    fn get_unit_triggers(&self, _name: &str) -> Result<UnitTriggers, ServiceError> {
+        Ok(UnitTriggers::default())
+    }
+
+    fn get_unit_conditions(&self, _name: &str) -> Result<UnitConditions, ServiceError> {
+        Ok(UnitConditions::default())
+    }
+
+    fn get_unit_history(&self, _name: &str) -> Result<UnitHistory, ServiceError> {
+        Err(unsupported("The start and stop history"))
+    }
+
+    fn get_oom_entries(&self, _name: &str) -> Result<Vec<JournalEntry>, ServiceError> {
+        Err(unsupported("The OOM killer messages"))
+    }
+
+    fn get_unit_documentation(&self, _name: &str) -> Result<Vec<String>, ServiceError> {
+        Ok(Vec::new())
+    }
+
+    fn plan_action(&self, action: UnitAction, name: &str) -> Result<ActionPlan, ServiceError> {
+        let (path, body) = match action {
+            UnitAction::Start => ("start", String::new()),
+            UnitAction::Stop => ("stop", String::new()),
+            // The engine has no reload, reload-or-restart is planned as the restart it stands for.
+            UnitAction::Restart | UnitAction::ReloadOrRestart => ("restart", String::new()),
+            UnitAction::Reload => return Err(unsupported("Reloading")),
+            UnitAction::Enable => ("update", format!(" {{\"RestartPolicy\": {{\"Name\": \"{}\"}}}}", ENABLED_POLICY)),
+            UnitAction::Disable => ("update", format!(" {{\"RestartPolicy\": {{\"Name\": \"{}\"}}}}", DISABLED_POLICY)),
+            UnitAction::Freeze => ("pause", String::new()),
+            UnitAction::Thaw => ("unpause", String::new()),
+        };
+
+        let side_effects = match action {
+            UnitAction::Stop | UnitAction::Restart | UnitAction::ReloadOrRestart => {
+                vec!["kills the container if it has not stopped after 10 seconds".to_string()]
+            }
+            UnitAction::Enable => vec!["starts the container with the engine, unless stopped by hand".to_string()],
+            UnitAction::Disable => vec!["no longer restarts the container, nor starts it with the engine".to_string()],
+            _ => Vec::new(),
+        };
+
+        Ok(ActionPlan::new(
+            format!("POST /containers/{}/{}", name, path),
+            body,
+            Vec::new(),
+            side_effects,
+        ))
+    }
+
+    fn schedule_action(&self, _action: PolicyAction, _name: &str, _calendar: &str) -> Result<String, ServiceError> {
+        Err(unsupported("Scheduling actions"))
+    }
+
+    fn list_scheduled_actions(&self) -> Result<Vec<ScheduledAction>, ServiceError> {
+        Err(unsupported("Scheduling actions"))
+    }
+
+    fn cancel_scheduled_action(&self, _timer: &str) -> Result<(), ServiceError> {
+        Err(unsupported("Scheduling actions"))
+    }
+}
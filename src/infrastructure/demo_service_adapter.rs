@@ -0,0 +1,1191 @@
+use chrono::{Duration, Local, NaiveTime, TimeZone};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Instant;
+
+use crate::errors::ServiceError;
+use crate::infrastructure::call_limits::CallLimits;
+use crate::domain::action_journal::UnitFileChange;
+use crate::domain::action_plan::{ActionPlan, UnitAction};
+use crate::domain::action_policy::PolicyAction;
+use crate::domain::boot_timing::{ChainLink, UnitActivation};
+use crate::domain::security_audit::{SecurityAudit, SecurityScore};
+use crate::domain::slice::Slice;
+use crate::domain::crash_loop::RestartSettings;
+use crate::domain::humanize::{format_timestamp, now_usec};
+use crate::domain::job::JobResult;
+use crate::domain::journal_boot::{Boot, ListedBoot};
+use crate::domain::journal_entry::{JournalEntry, LogFilter};
+use crate::domain::kill::KillTarget;
+use crate::domain::latency::ListTimings;
+use crate::domain::power_action::PowerAction;
+use crate::domain::process::UnitProcess;
+use crate::domain::resource_limits::ResourceLimits;
+use crate::domain::resource_usage::ResourceUsage;
+use crate::domain::runtime_stats::RuntimeStats;
+use crate::domain::system_summary::SystemSummary;
+use crate::domain::scheduled_action::ScheduledAction;
+use crate::domain::service::Service;
+use crate::domain::unit_property::{SocketProperty, TimerProperty, UnitProperty};
+use crate::domain::service_repository::ServiceRepository;
+use crate::domain::service_state::ServiceState;
+use crate::domain::unit_alias::UnitLinks;
+use crate::domain::unit_capabilities::UnitCapabilities;
+use crate::domain::unit_conditions::UnitConditions;
+use crate::domain::unit_history::UnitHistory;
+use crate::domain::unit_dependencies::UnitDependencies;
+use crate::domain::unit_environment::UnitEnvironment;
+use crate::domain::unit_file::{UnitFile, UnitFileMatch, UnitFilePaths};
+use crate::domain::unit_file_drift::settings_on_disk;
+use crate::domain::unit_path::UnitPathReference;
+use crate::domain::unit_transition::UnitTransition;
+use crate::domain::unit_verification::{parse_verify, VerifyFinding};
+use crate::domain::unit_triggers::UnitTriggers;
+use crate::infrastructure::systemd_service_adapter::ConnectionType;
+
+const UNIT_DIR: &str = "/usr/lib/systemd/system";
+const WANTS_DIR: &str = "/etc/systemd/system/multi-user.target.wants";
+/// How long the example units have been running when the demo starts.
+const UPTIME_USEC: u64 = 3 * 3600 * 1_000_000;
+
+/// A unit of a fixture file, e.g.
+/// `{ name = "api.service", state = "failed", file = "enabled", requires = ["db.service"] }`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FixtureUnit {
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    command: String,
+    /// "active", "inactive" or "failed".
+    #[serde(default = "default_state")]
+    state: String,
+    /// Unit file state, e.g. "enabled", "disabled" or "static".
+    #[serde(default = "default_file")]
+    file: String,
+    /// Whether the unit has ExecReload=.
+    #[serde(default)]
+    reloads: bool,
+    #[serde(default)]
+    requires: Vec<String>,
+    /// ExecStart= of the unit file on disk, when it was edited since the unit was loaded.
+    #[serde(default)]
+    edited_command: Option<String>,
+    /// Alias= names of the unit, e.g. ["sshd.service"].
+    #[serde(default)]
+    aliases: Vec<String>,
+}
+
+fn default_state() -> String {
+    "inactive".to_string()
+}
+
+fn default_file() -> String {
+    "disabled".to_string()
+}
+
+/// An action failing every time it is run on a unit, with the message systemd would give,
+/// e.g. `{ action = "start", unit = "api.service", message = "Job for api.service failed." }`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScriptedFailure {
+    /// "start", "stop", "restart", "reload", "enable", "disable", "freeze", "thaw" or "kill".
+    action: String,
+    unit: String,
+    message: String,
+}
+
+/// Units and failures of the demo, read from a TOML file with `[[units]]` and `[[failures]]`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Fixture {
+    units: Vec<FixtureUnit>,
+    failures: Vec<ScriptedFailure>,
+}
+
+/// name, description, command, state, unit file state, whether it reloads, units it requires.
+type ExampleUnit = (&'static str, &'static str, &'static str, &'static str, &'static str, bool, &'static [&'static str]);
+
+const EXAMPLE_UNITS: &[ExampleUnit] = &[
+    ("nginx.service", "A high performance web server and a reverse proxy server", "/usr/sbin/nginx -g daemon off;", "active", "enabled", true, &[]),
+    ("postgresql.service", "PostgreSQL database server", "/usr/lib/postgresql/16/bin/postgres -D /var/lib/postgresql/16/main", "active", "enabled", true, &[]),
+    ("redis-server.service", "Advanced key-value store", "/usr/bin/redis-server 127.0.0.1:6379", "active", "enabled", false, &[]),
+    ("ssh.service", "OpenBSD Secure Shell server", "/usr/sbin/sshd -D", "active", "enabled", true, &[]),
+    ("cron.service", "Regular background program processing daemon", "/usr/sbin/cron -f", "active", "enabled", false, &[]),
+    ("webapp.service", "Example web application", "/opt/webapp/bin/server --port 8080", "failed", "enabled", false, &["postgresql.service"]),
+    ("backup.service", "Nightly backup of /srv", "/usr/local/bin/backup /srv", "inactive", "static", false, &[]),
+    ("bluetooth.service", "Bluetooth service", "/usr/libexec/bluetooth/bluetoothd", "inactive", "disabled", false, &[]),
+    ("cups.service", "CUPS Scheduler", "/usr/sbin/cupsd -l", "inactive", "disabled", false, &[]),
+    ("backup.timer", "Run the nightly backup", "", "active", "enabled", false, &[]),
+];
+
+/// An example unit whose file was edited without a daemon reload, and its new command.
+const EDITED_EXAMPLE: (&str, &str) = ("redis-server.service", "/usr/bin/redis-server /etc/redis/redis.conf");
+
+/// An example unit with an alias, as Debian installs OpenSSH.
+const ALIASED_EXAMPLE: (&str, &str) = ("ssh.service", "sshd.service");
+
+impl Fixture {
+    /// The example units shown without a fixture file.
+    pub fn examples() -> Self {
+        let units = EXAMPLE_UNITS
+            .iter()
+            .map(|(name, description, command, state, file, reloads, requires)| FixtureUnit {
+                name: name.to_string(),
+                description: description.to_string(),
+                command: command.to_string(),
+                state: state.to_string(),
+                file: file.to_string(),
+                reloads: *reloads,
+                requires: requires.iter().map(|required| required.to_string()).collect(),
+                edited_command: (*name == EDITED_EXAMPLE.0).then(|| EDITED_EXAMPLE.1.to_string()),
+                aliases: (*name == ALIASED_EXAMPLE.0).then(|| ALIASED_EXAMPLE.1.to_string()).into_iter().collect(),
+            })
+            .collect();
+        Self { units, failures: Vec::new() }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ServiceError> {
+        let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let fixture: Self = toml::from_str(&content).map_err(|e| format!("{}: {}", path.display(), e))?;
+        for unit in &fixture.units {
+            if !matches!(unit.state.as_str(), "active" | "inactive" | "failed") {
+                return Err(format!("{}: unknown state \"{}\" of {}", path.display(), unit.state, unit.name).into());
+            }
+        }
+        Ok(fixture)
+    }
+}
+
+struct DemoUnit {
+    name: String,
+    description: String,
+    command: String,
+    /// "active", "inactive" or "failed".
+    active: &'static str,
+    file: String,
+    /// Unit file state the unit came with, which its preset stands for.
+    preset: String,
+    reloads: bool,
+    requires: Vec<String>,
+    frozen: bool,
+    pid: u32,
+    /// Realtime start of the main process in microseconds, 0 when it is not running.
+    started_usec: u64,
+    restarts: u32,
+    /// ExecStart= on disk when it differs from the loaded `command`, until a daemon reload.
+    edited_command: Option<String>,
+    aliases: Vec<String>,
+}
+
+impl DemoUnit {
+    fn is_named(&self, name: &str) -> bool {
+        self.name == name || self.aliases.iter().any(|alias| alias == name)
+    }
+
+    fn is_active(&self) -> bool {
+        self.active == "active"
+    }
+
+    fn is_timer(&self) -> bool {
+        self.name.ends_with(".timer")
+    }
+
+    fn sub(&self) -> &'static str {
+        match self.active {
+            "active" if self.is_timer() => "waiting",
+            "active" => "running",
+            "failed" => "failed",
+            _ => "dead",
+        }
+    }
+
+    /// The unit file on disk.
+    fn unit_file(&self) -> String {
+        self.unit_file_with(self.edited_command.as_deref().unwrap_or(&self.command))
+    }
+
+    fn unit_file_with(&self, command: &str) -> String {
+        let mut content = format!("[Unit]\nDescription={}\n", self.description);
+        for required in &self.requires {
+            content.push_str(&format!("Requires={}\nAfter={}\n", required, required));
+        }
+        if self.is_timer() {
+            content.push_str("\n[Timer]\nOnCalendar=daily\nPersistent=true\n\n[Install]\nWantedBy=timers.target\n");
+        } else {
+            content.push_str(&format!("\n[Service]\nExecStart={}\n", command));
+            if self.reloads {
+                content.push_str("ExecReload=/bin/kill -HUP $MAINPID\n");
+            }
+            if self.file != "static" {
+                content.push_str("\n[Install]\nWantedBy=multi-user.target\n");
+                for alias in &self.aliases {
+                    content.push_str(&format!("Alias={}\n", alias));
+                }
+            }
+        }
+        content
+    }
+}
+
+struct DemoState {
+    units: Vec<DemoUnit>,
+    /// Entries of every unit, oldest first.
+    log: Vec<(String, JournalEntry)>,
+    default_target: String,
+    /// Realtime boot of the example machine in microseconds.
+    boot_usec: u64,
+    next_pid: u32,
+    failures: Vec<ScriptedFailure>,
+    schedules: Vec<ScheduledAction>,
+}
+
+/// Example units kept in memory, to explore the interface on machines without a supported
+/// service manager. Actions change the example units and log what systemd would, nothing on
+/// the machine is touched.
+pub struct DemoServiceAdapter {
+    /// Shared with the worker of the action queue, which acts on the same units.
+    state: Arc<Mutex<DemoState>>,
+    unit_types: Vec<String>,
+}
+
+fn unsupported(what: &str) -> ServiceError {
+    ServiceError::Unsupported(format!("{} is not available in the demo", what))
+}
+
+fn not_found(name: &str) -> ServiceError {
+    ServiceError::NotFound(format!("Unit {} not found.", name))
+}
+
+/// An entry as the journal would store it.
+fn entry(timestamp: u64, priority: u8, identifier: &str, pid: u32, message: &str) -> JournalEntry {
+    JournalEntry::from_fields(|field| match field {
+        "__REALTIME_TIMESTAMP" => Some(timestamp.to_string()),
+        "PRIORITY" => Some(priority.to_string()),
+        "SYSLOG_IDENTIFIER" => Some(identifier.to_string()),
+        "_PID" => Some(pid.to_string()),
+        "MESSAGE" => Some(message.to_string()),
+        _ => None,
+    })
+}
+
+/// What a running example service logs by itself, with its priority.
+fn example_messages(name: &str) -> &'static [(u8, &'static str)] {
+    match name {
+        "nginx.service" => &[(6, "GET / HTTP/1.1 200 612"), (6, "GET /favicon.ico HTTP/1.1 404 153"), (4, "upstream response is buffered to a temporary file")],
+        "postgresql.service" => &[(6, "checkpoint starting: time"), (6, "checkpoint complete: wrote 42 buffers (0.3%)"), (6, "automatic vacuum of table \"app.public.sessions\"")],
+        "redis-server.service" => &[(6, "100 changes in 300 seconds. Saving..."), (6, "Background saving terminated with success")],
+        "ssh.service" => &[(6, "Accepted publickey for admin from 192.168.1.20 port 51234 ssh2"), (6, "pam_unix(sshd:session): session closed for user admin")],
+        "cron.service" => &[(6, "(root) CMD (command -v debian-sa1 > /dev/null && debian-sa1 1 1)")],
+        _ => &[],
+    }
+}
+
+/// What a failed example service logged before exiting.
+fn failure_message(name: &str) -> &'static str {
+    match name {
+        "webapp.service" => "could not connect to the database: connection refused",
+        _ => "exiting with status 1",
+    }
+}
+
+impl DemoState {
+    /// The scripted failure of `action` on `name`, checked before the action changes anything.
+    fn check_failure(&self, action: &str, name: &str) -> Result<(), ServiceError> {
+        match self.failures.iter().find(|failure| failure.action == action && failure.unit == name) {
+            Some(failure) => Err(failure.message.clone().into()),
+            None => Ok(()),
+        }
+    }
+
+    /// The unit of a name or of one of its aliases, which systemd loads as the same unit.
+    fn unit(&self, name: &str) -> Result<&DemoUnit, ServiceError> {
+        self.units.iter().find(|unit| unit.is_named(name)).ok_or_else(|| not_found(name))
+    }
+
+    fn unit_mut(&mut self, name: &str) -> Result<&mut DemoUnit, ServiceError> {
+        self.units.iter_mut().find(|unit| unit.is_named(name)).ok_or_else(|| not_found(name))
+    }
+
+    /// Logs a message of the manager about `name`, which the start and stop history is read from.
+    fn log_manager(&mut self, name: &str, priority: u8, message: String) {
+        self.log.push((name.to_string(), entry(now_usec(), priority, "systemd", 1, &message)));
+    }
+
+    fn start(&mut self, name: &str) -> Result<(), ServiceError> {
+        let requires = self.unit(name)?.requires.clone();
+        for required in requires {
+            if !self.unit(&required)?.is_active() {
+                self.start(&required)?;
+            }
+        }
+        let pid = self.next_pid;
+        let unit = self.unit_mut(name)?;
+        if unit.is_active() {
+            return Ok(());
+        }
+        unit.active = "active";
+        unit.pid = pid;
+        unit.started_usec = now_usec();
+        let description = unit.description.clone();
+        self.next_pid += 1;
+        self.log_manager(name, 6, format!("Starting {}...", description));
+        self.log_manager(name, 6, format!("Started {} - {}.", name, description));
+        Ok(())
+    }
+
+    fn stop(&mut self, name: &str) -> Result<(), ServiceError> {
+        let dependents: Vec<String> = self
+            .units
+            .iter()
+            .filter(|unit| unit.is_active() && unit.requires.iter().any(|required| required == name))
+            .map(|unit| unit.name.clone())
+            .collect();
+        for dependent in dependents {
+            self.stop(&dependent)?;
+        }
+        let unit = self.unit_mut(name)?;
+        if !unit.is_active() {
+            return Ok(());
+        }
+        unit.active = "inactive";
+        unit.frozen = false;
+        unit.pid = 0;
+        unit.started_usec = 0;
+        let description = unit.description.clone();
+        self.log_manager(name, 6, format!("Stopping {}...", description));
+        self.log_manager(name, 6, format!("{}: Deactivated successfully.", name));
+        self.log_manager(name, 6, format!("Stopped {} - {}.", name, description));
+        Ok(())
+    }
+
+    fn reload(&mut self, name: &str) -> Result<(), ServiceError> {
+        let unit = self.unit(name)?;
+        if !unit.is_active() {
+            return Err(format!("Unit {} cannot be reloaded because it is inactive.", name).into());
+        }
+        if !unit.reloads {
+            return Err(format!("Job type reload is not applicable for unit {}.", name).into());
+        }
+        let description = unit.description.clone();
+        self.log_manager(name, 6, format!("Reloading {}...", description));
+        self.log_manager(name, 6, format!("Reloaded {} - {}.", name, description));
+        Ok(())
+    }
+}
+
+impl DemoServiceAdapter {
+    /// The units of the fixture, running since a few hours ago when active, with the log their
+    /// start and a few messages would have left.
+    pub fn new(fixture: Fixture, unit_types: Vec<String>) -> Self {
+        let now = now_usec();
+        let boot = now.saturating_sub(UPTIME_USEC);
+        let mut next_pid = 400;
+        let mut log = Vec::new();
+        let units = fixture
+            .units
+            .into_iter()
+            .map(|unit| {
+                let FixtureUnit { name, description, command, state, file, reloads, requires, edited_command, aliases } = unit;
+                let active = match state.as_str() {
+                    "active" => "active",
+                    "failed" => "failed",
+                    _ => "inactive",
+                };
+                let pid = if active == "active" && !command.is_empty() { next_pid } else { 0 };
+                next_pid += 37;
+                let identifier = command.split(' ').next().unwrap_or_default().rsplit('/').next().unwrap_or_default().to_string();
+                let manager = |timestamp: u64, priority: u8, message: String| {
+                    (name.clone(), entry(timestamp, priority, "systemd", 1, &message))
+                };
+                log.push(manager(boot, 6, format!("Starting {}...", description)));
+                match active {
+                    "active" => log.push(manager(boot + 1_000, 6, format!("Started {} - {}.", name, description))),
+                    "failed" => {
+                        log.push((name.clone(), entry(boot + 2_000_000, 3, &identifier, next_pid, failure_message(&name))));
+                        log.push(manager(boot + 2_100_000, 4, format!("{}: Main process exited, code=exited, status=1/FAILURE", name)));
+                        log.push(manager(boot + 2_100_000, 4, format!("{}: Failed with result 'exit-code'.", name)));
+                        log.push(manager(boot + 2_100_000, 3, format!("Failed to start {} - {}.", name, description)));
+                    }
+                    _ => log.push(manager(boot + 500_000, 6, format!("Finished {} - {}.", name, description))),
+                }
+                if pid != 0 {
+                    let messages = example_messages(&name);
+                    // One message every ten minutes since the boot.
+                    for (index, timestamp) in (boot + 60_000_000..now).step_by(600_000_000).enumerate() {
+                        if let Some((priority, message)) = messages.get(index % messages.len().max(1)) {
+                            log.push((name.clone(), entry(timestamp, *priority, &identifier, pid, message)));
+                        }
+                    }
+                }
+                DemoUnit {
+                    name,
+                    description,
+                    command,
+                    active,
+                    preset: file.clone(),
+                    file,
+                    reloads,
+                    requires,
+                    frozen: false,
+                    pid,
+                    started_usec: if pid != 0 { boot } else { 0 },
+                    restarts: 0,
+                    edited_command,
+                    aliases,
+                }
+            })
+            .collect();
+        log.sort_by_key(|(_, entry)| entry.timestamp());
+
+        Self {
+            state: Arc::new(Mutex::new(DemoState {
+                units,
+                log,
+                default_target: "multi-user.target".to_string(),
+                boot_usec: boot,
+                next_pid,
+                failures: fixture.failures,
+                schedules: Vec::new(),
+            })),
+            unit_types,
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, DemoState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn is_listed_unit(&self, name: &str) -> bool {
+        self.unit_types
+            .iter()
+            .any(|unit_type| name.rsplit_once('.').is_some_and(|(_, suffix)| suffix == unit_type))
+    }
+
+    /// Entries of `name` matching the filter, oldest first.
+    fn entries(&self, name: &str, filter: &LogFilter) -> Result<Vec<JournalEntry>, ServiceError> {
+        // The demo log is of the current boot only.
+        if filter.boot.is_some_and(|boot| boot.offset != 0) {
+            return Ok(Vec::new());
+        }
+        let since = filter.since_usec()?.unwrap_or(0);
+        Ok(self
+            .state()
+            .log
+            .iter()
+            .filter(|(unit, entry)| {
+                unit == name
+                    && entry.priority().is_none_or(|priority| priority <= filter.min_priority)
+                    && entry.timestamp().is_some_and(|timestamp| timestamp >= since)
+            })
+            .map(|(_, entry)| entry.clone())
+            .collect())
+    }
+}
+
+impl ServiceRepository for DemoServiceAdapter {
+    fn change_connection(&mut self, _connection_type: ConnectionType) -> Result<(), ServiceError> {
+        Ok(())
+    }
+
+    fn connection_lost(&self) -> bool {
+        false
+    }
+
+    fn reconnect(&mut self) -> Result<(), ServiceError> {
+        Ok(())
+    }
+
+    fn worker(&self) -> Result<Box<dyn ServiceRepository>, ServiceError> {
+        Ok(Box::new(Self {
+            state: Arc::clone(&self.state),
+            unit_types: self.unit_types.clone(),
+        }))
+    }
+
+    fn set_call_limits(&mut self, _limits: CallLimits) {}
+
+    fn list_services(&self) -> Result<Vec<Service>, ServiceError> {
+        Ok(self
+            .state()
+            .units
+            .iter()
+            .filter(|unit| self.is_listed_unit(&unit.name))
+            .map(|unit| {
+                let state = ServiceState::new(
+                    "loaded".to_string(),
+                    unit.active.to_string(),
+                    unit.sub().to_string(),
+                    unit.file.clone(),
+                    unit.preset.clone(),
+                    if unit.frozen { "frozen" } else { "running" }.to_string(),
+                )
+                .with_need_daemon_reload(unit.edited_command.is_some());
+                Service::new(unit.name.clone(), unit.description.clone(), state).with_aliases(unit.aliases.clone())
+            })
+            .collect())
+    }
+
+    fn time_listing(&self) -> Result<ListTimings, ServiceError> {
+        let started = Instant::now();
+        let services = self.list_services()?;
+        Ok(ListTimings::new(started.elapsed(), services.iter().map(|_| started.elapsed() / services.len() as u32).collect()))
+    }
+
+    fn get_service_log(&self, name: &str, lines: u32) -> Result<String, ServiceError> {
+        let entries = self.get_journal_entries(name, lines, &LogFilter::default())?;
+        Ok(entries.iter().map(JournalEntry::line).collect::<Vec<_>>().join("\n"))
+    }
+
+    fn get_journal_entries(&self, name: &str, lines: u32, filter: &LogFilter) -> Result<Vec<JournalEntry>, ServiceError> {
+        let mut entries = self.entries(name, filter)?;
+        let start = entries.len().saturating_sub(lines as usize);
+        Ok(entries.split_off(start))
+    }
+
+    fn get_journal_boots(&self) -> Result<Vec<ListedBoot>, ServiceError> {
+        const DAY_USEC: u64 = 24 * 3_600_000_000;
+        let now = now_usec();
+        let boot = now.saturating_sub(UPTIME_USEC);
+        let boots = [
+            (0, boot, now),
+            (-1, boot.saturating_sub(DAY_USEC), boot.saturating_sub(60_000_000)),
+            (-2, boot.saturating_sub(8 * DAY_USEC), boot.saturating_sub(7 * DAY_USEC)),
+        ];
+        Ok(boots
+            .into_iter()
+            .enumerate()
+            .map(|(index, (offset, first, last))| ListedBoot {
+                boot: Boot { offset, id: 0x5eed_0000_0000_0000_0000_0000_0000_0000 + index as u128 },
+                entries: format!("{} — {}", format_timestamp(first), format_timestamp(last)),
+            })
+            .collect())
+    }
+
+    fn get_older_journal_entries(
+        &self,
+        _name: &str,
+        _cursor: &str,
+        _lines: u32,
+        _filter: &LogFilter,
+    ) -> Result<Vec<JournalEntry>, ServiceError> {
+        Err(unsupported("Paging back through the log"))
+    }
+
+    fn get_log_timestamps(&self, name: &str) -> Result<Vec<u64>, ServiceError> {
+        Ok(self.entries(name, &LogFilter::default())?.iter().filter_map(JournalEntry::timestamp).collect())
+    }
+
+    fn get_journal_units_since(&self, minutes: u64) -> Result<Vec<String>, ServiceError> {
+        let since = now_usec().saturating_sub(minutes * 60_000_000);
+        Ok(self
+            .state()
+            .log
+            .iter()
+            .filter(|(_, entry)| entry.timestamp().is_some_and(|timestamp| timestamp >= since))
+            .map(|(unit, _)| unit.clone())
+            .collect())
+    }
+
+    fn start_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        let mut state = self.state();
+        state.check_failure("start", name)?;
+        state.start(name).map(|_| None)
+    }
+
+    fn start_transient_unit(&self, name: &str, argv: &[String], _limits: &ResourceLimits) -> Result<Option<String>, ServiceError> {
+        let mut state = self.state();
+        if state.unit(name).is_ok() {
+            return Err(format!("Unit {} was already loaded or has a fragment file.", name).into());
+        }
+        state.units.push(DemoUnit {
+            name: name.to_string(),
+            description: argv.join(" "),
+            command: argv.join(" "),
+            active: "inactive",
+            file: "transient".to_string(),
+            preset: String::new(),
+            reloads: false,
+            requires: Vec::new(),
+            frozen: false,
+            pid: 0,
+            started_usec: 0,
+            restarts: 0,
+            edited_command: None,
+            aliases: Vec::new(),
+        });
+        state.start(name).map(|_| None)
+    }
+
+    fn stop_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        let mut state = self.state();
+        state.check_failure("stop", name)?;
+        state.stop(name).map(|_| None)
+    }
+
+    fn restart_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        let mut state = self.state();
+        state.check_failure("restart", name)?;
+        state.stop(name)?;
+        state.start(name).map(|_| None)
+    }
+
+    fn reload_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        let mut state = self.state();
+        state.check_failure("reload", name)?;
+        state.reload(name).map(|_| None)
+    }
+
+    fn reload_or_restart_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        let mut state = self.state();
+        let unit = state.unit(name)?;
+        if unit.is_active() && unit.reloads {
+            state.check_failure("reload", name)?;
+            return state.reload(name).map(|_| None);
+        }
+        state.check_failure("restart", name)?;
+        state.stop(name)?;
+        state.start(name).map(|_| None)
+    }
+
+    fn get_unit_capabilities(&self, name: &str) -> Result<UnitCapabilities, ServiceError> {
+        Ok(UnitCapabilities {
+            can_start: true,
+            can_stop: true,
+            can_reload: self.state().unit(name)?.reloads,
+            can_isolate: false,
+        })
+    }
+
+    fn list_targets(&self) -> Result<Vec<Service>, ServiceError> {
+        let default_target = self.get_default_target()?;
+        Ok(["multi-user.target", "graphical.target", "rescue.target"]
+            .into_iter()
+            .map(|target| {
+                let active = target == "multi-user.target";
+                let state = ServiceState::new(
+                    "loaded".to_string(),
+                    if active { "active" } else { "inactive" }.to_string(),
+                    if active { "active" } else { "dead" }.to_string(),
+                    if target == default_target { "indirect" } else { "static" }.to_string(),
+                    String::new(),
+                    String::new(),
+                );
+                Service::new(target.to_string(), String::new(), state)
+            })
+            .collect())
+    }
+
+    fn get_default_target(&self) -> Result<String, ServiceError> {
+        Ok(self.state().default_target.clone())
+    }
+
+    fn set_default_target(&self, name: &str) -> Result<(), ServiceError> {
+        self.state().default_target = name.to_string();
+        Ok(())
+    }
+
+    fn isolate_target(&self, _name: &str) -> Result<Option<String>, ServiceError> {
+        Err(unsupported("Switching targets"))
+    }
+
+    fn power(&self, _action: PowerAction) -> Result<(), ServiceError> {
+        Err(unsupported("Rebooting or powering off"))
+    }
+
+    fn watch_jobs(&mut self, _sender: Sender<JobResult>) -> Result<(), ServiceError> {
+        Ok(())
+    }
+
+    fn watch_units(&mut self, units: &[String], _sender: Sender<UnitTransition>) -> Result<(), ServiceError> {
+        if units.is_empty() {
+            return Ok(());
+        }
+        Err(unsupported("Watching units"))
+    }
+
+    fn enable_service(&self, name: &str) -> Result<Vec<UnitFileChange>, ServiceError> {
+        let mut state = self.state();
+        state.check_failure("enable", name)?;
+        let unit = state.unit_mut(name)?;
+        if unit.file != "disabled" {
+            return Ok(Vec::new());
+        }
+        unit.file = "enabled".to_string();
+        Ok(vec![UnitFileChange {
+            kind: "symlink".to_string(),
+            path: format!("{}/{}", WANTS_DIR, name),
+            source: format!("{}/{}", UNIT_DIR, name),
+        }])
+    }
+
+    fn disable_service(&self, name: &str) -> Result<Vec<UnitFileChange>, ServiceError> {
+        let mut state = self.state();
+        state.check_failure("disable", name)?;
+        let unit = state.unit_mut(name)?;
+        if unit.file != "enabled" {
+            return Ok(Vec::new());
+        }
+        unit.file = "disabled".to_string();
+        Ok(vec![UnitFileChange {
+            kind: "unlink".to_string(),
+            path: format!("{}/{}", WANTS_DIR, name),
+            source: String::new(),
+        }])
+    }
+
+    fn revert_unit_file_changes(&self, name: &str, changes: &[UnitFileChange]) -> Result<(), ServiceError> {
+        let mut state = self.state();
+        let unit = state.unit_mut(name)?;
+        for change in changes {
+            unit.file = if change.is_symlink() { "disabled" } else { "enabled" }.to_string();
+        }
+        Ok(())
+    }
+
+    fn freeze_service(&self, name: &str) -> Result<(), ServiceError> {
+        let mut state = self.state();
+        state.check_failure("freeze", name)?;
+        let unit = state.unit_mut(name)?;
+        if !unit.is_active() {
+            return Err(format!("Unit {} is not active.", name).into());
+        }
+        unit.frozen = true;
+        Ok(())
+    }
+
+    fn thaw_service(&self, name: &str) -> Result<(), ServiceError> {
+        let mut state = self.state();
+        state.check_failure("thaw", name)?;
+        state.unit_mut(name)?.frozen = false;
+        Ok(())
+    }
+
+    fn reset_failed_unit(&self, name: &str) -> Result<(), ServiceError> {
+        let mut state = self.state();
+        let unit = state.unit_mut(name)?;
+        if unit.active == "failed" {
+            unit.active = "inactive";
+        }
+        Ok(())
+    }
+
+    fn reset_failed(&self) -> Result<(), ServiceError> {
+        for unit in self.state().units.iter_mut().filter(|unit| unit.active == "failed") {
+            unit.active = "inactive";
+        }
+        Ok(())
+    }
+
+    /// The example processes never catch signals, so any signal kills the service.
+    fn kill_service(&self, name: &str, _target: KillTarget, signal: i32) -> Result<(), ServiceError> {
+        let mut state = self.state();
+        state.check_failure("kill", name)?;
+        let unit = state.unit_mut(name)?;
+        if unit.pid == 0 {
+            return Err(format!("No main process to kill for {}.", name).into());
+        }
+        unit.active = "failed";
+        unit.frozen = false;
+        unit.pid = 0;
+        unit.started_usec = 0;
+        state.log_manager(name, 4, format!("{}: Main process exited, code=killed, status={}", name, signal));
+        state.log_manager(name, 4, format!("{}: Failed with result 'signal'.", name));
+        Ok(())
+    }
+
+    fn reload_daemon(&self) -> Result<(), ServiceError> {
+        for unit in &mut self.state().units {
+            if let Some(command) = unit.edited_command.take() {
+                unit.command = command;
+            }
+        }
+        Ok(())
+    }
+
+    fn get_unit_property(&self, _name: &str) -> Result<UnitProperty, ServiceError> {
+        Err(unsupported("Reading unit properties"))
+    }
+
+    fn get_restart_settings(&self, _name: &str) -> Result<RestartSettings, ServiceError> {
+        Err(unsupported("Reading restart settings"))
+    }
+
+    fn get_runtime_stats(&self, name: &str) -> Result<RuntimeStats, ServiceError> {
+        let state = self.state();
+        let unit = state.unit(name)?;
+        let memory = (unit.pid != 0).then(|| u64::from(unit.pid) * 64 * 1024);
+        Ok(RuntimeStats::new(unit.restarts, unit.started_usec, memory))
+    }
+
+    /// Made up from the time the service has been running, so that the graphs move.
+    fn get_resource_usage(&self, name: &str) -> Result<ResourceUsage, ServiceError> {
+        let state = self.state();
+        let unit = state.unit(name)?;
+        if unit.pid == 0 {
+            return Ok(ResourceUsage { cpu_nsec: None, memory_bytes: None });
+        }
+        let running_usec = now_usec().saturating_sub(unit.started_usec);
+        // A few percent of a CPU, varying from one second to the next.
+        let load = 2 + (running_usec / 1_000_000 * u64::from(unit.pid)) % 7;
+        Ok(ResourceUsage {
+            cpu_nsec: Some(running_usec * 10 * load + running_usec % 1_000_000 * 10 * load),
+            memory_bytes: Some(u64::from(unit.pid) * 64 * 1024 + running_usec / 1_000_000 % 300 * 4096),
+        })
+    }
+
+    /// The example services all run in system.slice, which accounts for their usage.
+    fn get_slices(&self) -> Result<Vec<Slice>, ServiceError> {
+        let services: Vec<String> = self
+            .state()
+            .units
+            .iter()
+            .filter(|unit| unit.name.ends_with(".service"))
+            .map(|unit| unit.name.clone())
+            .collect();
+        let mut cpu_nsec = 0;
+        let mut memory_bytes = 0;
+        for service in &services {
+            let usage = self.get_resource_usage(service)?;
+            cpu_nsec += usage.cpu_nsec.unwrap_or(0);
+            memory_bytes += usage.memory_bytes.unwrap_or(0);
+        }
+        let slice = |name: &str, units: Vec<String>, share: u64| Slice {
+            name: name.to_string(),
+            usage: ResourceUsage {
+                cpu_nsec: Some(cpu_nsec * share / 100),
+                memory_bytes: Some(memory_bytes * share / 100),
+            },
+            units,
+        };
+        Ok(vec![
+            slice("-.slice", Vec::new(), 112),
+            slice("system.slice", services, 100),
+            slice("user.slice", Vec::new(), 12),
+        ])
+    }
+
+    fn get_unit_processes(&self, name: &str) -> Result<Vec<UnitProcess>, ServiceError> {
+        let state = self.state();
+        let unit = state.unit(name)?;
+        if unit.pid == 0 {
+            return Ok(Vec::new());
+        }
+        let cpu_usec = now_usec().saturating_sub(unit.started_usec) / 50;
+        Ok(vec![UnitProcess::new(unit.pid, 1, unit.command.clone(), cpu_usec, u64::from(unit.pid) * 64 * 1024)])
+    }
+
+    fn get_state_change_timestamp(&self, name: &str) -> Result<u64, ServiceError> {
+        Ok(self.state().unit(name)?.started_usec)
+    }
+
+    fn get_need_daemon_reload(&self, name: &str) -> Result<bool, ServiceError> {
+        Ok(self.state().unit(name)?.edited_command.is_some())
+    }
+
+    fn get_freezer_state(&self, name: &str) -> Result<String, ServiceError> {
+        Ok(if self.state().unit(name)?.frozen { "frozen" } else { "running" }.to_string())
+    }
+
+    fn get_boot_time(&self) -> Result<String, ServiceError> {
+        Err(unsupported("Analyzing the boot"))
+    }
+
+    fn get_system_summary(&self) -> Result<SystemSummary, ServiceError> {
+        let state = self.state();
+        let failed = state.units.iter().filter(|unit| unit.active == "failed").count() as u32;
+        Ok(SystemSummary::new(
+            state.units.len() as u32,
+            failed,
+            0,
+            if failed > 0 { "degraded" } else { "running" }.to_string(),
+            "kvm".to_string(),
+            state.boot_usec,
+        ))
+    }
+
+    fn get_boot_blame(&self) -> Result<Vec<UnitActivation>, ServiceError> {
+        Err(unsupported("Analyzing the boot"))
+    }
+
+    fn get_critical_chain(&self, _unit: Option<&str>) -> Result<Vec<ChainLink>, ServiceError> {
+        Err(unsupported("Analyzing the boot"))
+    }
+
+    fn get_security_scores(&self) -> Result<Vec<SecurityScore>, ServiceError> {
+        Err(unsupported("Analyzing the security of services"))
+    }
+
+    fn get_security_audit(&self, _name: &str) -> Result<SecurityAudit, ServiceError> {
+        Err(unsupported("Analyzing the security of services"))
+    }
+
+    fn unit_generation(&self, _name: &str) -> Option<String> {
+        None
+    }
+
+    fn systemctl_cat(&self, name: &str) -> Result<String, ServiceError> {
+        let state = self.state();
+        let unit = state.unit(name)?;
+        Ok(format!("# {}/{}\n{}", UNIT_DIR, name, unit.unit_file()))
+    }
+
+    fn get_unit_file_paths(&self, name: &str) -> Result<UnitFilePaths, ServiceError> {
+        self.state().unit(name)?;
+        Ok(UnitFilePaths {
+            fragment: format!("{}/{}", UNIT_DIR, name),
+            drop_ins: Vec::new(),
+        })
+    }
+
+    fn get_unit_links(&self, name: &str) -> Result<UnitLinks, ServiceError> {
+        let state = self.state();
+        let unit = state.unit(name)?;
+        let mut links: Vec<String> = unit.aliases.iter().map(|alias| format!("/etc/systemd/system/{}", alias)).collect();
+        if unit.file == "enabled" && !unit.is_timer() {
+            links.push(format!("{}/{}", WANTS_DIR, unit.name));
+        }
+        Ok(UnitLinks {
+            aliases: unit.aliases.clone(),
+            links,
+            target: format!("{}/{}", UNIT_DIR, unit.name),
+        })
+    }
+
+    fn get_loaded_settings(&self, name: &str, settings: &[String]) -> Result<HashMap<String, Vec<String>>, ServiceError> {
+        let state = self.state();
+        let unit = state.unit(name)?;
+        let paths = UnitFilePaths { fragment: String::new(), drop_ins: Vec::new() };
+        let loaded = settings_on_disk(&UnitFile::split(&unit.unit_file_with(&unit.command), &paths));
+        Ok(loaded
+            .into_iter()
+            .filter(|setting| settings.contains(&setting.setting))
+            .map(|setting| (setting.setting, setting.on_disk))
+            .collect())
+    }
+
+    /// A failed unit runs a program that is gone, and the example edited on disk gained a typo,
+    /// both reported as `systemd-analyze verify` words them.
+    fn verify_unit(&self, name: &str) -> Result<Vec<VerifyFinding>, ServiceError> {
+        let state = self.state();
+        let unit = state.unit(name)?;
+        let mut output = String::new();
+        if unit.edited_command.is_some() {
+            let line = unit.unit_file().lines().position(|line| line.starts_with("ExecStart=")).unwrap_or(0) + 2;
+            output.push_str(&format!(
+                "{}/{}:{}: Unknown key name 'RestartSecs' in section 'Service', ignoring.\n",
+                UNIT_DIR, unit.name, line
+            ));
+        }
+        if unit.active == "failed" {
+            let program = unit.command.split_whitespace().next().unwrap_or_default();
+            output.push_str(&format!("{}: Command {} is not executable: No such file or directory.\n", unit.name, program));
+        }
+        Ok(parse_verify(&output))
+    }
+
+    fn create_unit(&self, _name: &str, _content: &str) -> Result<String, ServiceError> {
+        Err(unsupported("Creating units"))
+    }
+
+    fn write_drop_in(&self, _name: &str, _file_name: &str, _content: &str) -> Result<String, ServiceError> {
+        Err(unsupported("Editing units"))
+    }
+
+    fn edit_unit_command(&self, _name: &str) -> Result<Command, ServiceError> {
+        Err(unsupported("Editing units"))
+    }
+
+    fn shell_command(&self, _name: &str, _directory: &str) -> Result<Command, ServiceError> {
+        Err(unsupported("Opening a shell for services"))
+    }
+
+    fn remove_drop_in(&self, _name: &str, _path: &str) -> Result<(), ServiceError> {
+        Err(unsupported("Editing units"))
+    }
+
+    fn get_unit_environment(&self, _name: &str) -> Result<UnitEnvironment, ServiceError> {
+        Err(unsupported("Reading the environment of units"))
+    }
+
+    fn get_resource_limits(&self, _name: &str) -> Result<ResourceLimits, ServiceError> {
+        Ok(ResourceLimits::default())
+    }
+
+    fn set_resource_limits(&self, _name: &str, _limits: &ResourceLimits, _runtime: bool) -> Result<(), ServiceError> {
+        Err(unsupported("Setting resource limits"))
+    }
+
+    fn search_unit_files(&self, query: &str) -> Result<Vec<UnitFileMatch>, ServiceError> {
+        let query = query.to_lowercase();
+        Ok(self
+            .state()
+            .units
+            .iter()
+            .flat_map(|unit| {
+                let path = format!("{}/{}", UNIT_DIR, unit.name);
+                unit.unit_file()
+                    .lines()
+                    .enumerate()
+                    .filter(|(_, line)| line.to_lowercase().contains(&query))
+                    .map(|(index, line)| UnitFileMatch {
+                        unit: unit.name.clone(),
+                        path: path.clone(),
+                        line_number: index + 1,
+                        line: line.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect())
+    }
+
+    fn list_unit_paths(&self) -> Result<Vec<UnitPathReference>, ServiceError> {
+        Ok(Vec::new())
+    }
+
+    fn get_unit_dependencies(&self, name: &str) -> Result<UnitDependencies, ServiceError> {
+        let state = self.state();
+        let unit = state.unit(name)?;
+        let before = state
+            .units
+            .iter()
+            .filter(|other| other.requires.iter().any(|required| required == name))
+            .map(|other| other.name.clone())
+            .collect();
+        let mut after = unit.requires.clone();
+        after.push("network.target".to_string());
+        Ok(UnitDependencies::new(unit.requires.clone(), Vec::new(), Vec::new(), after, before))
+    }
+
+    fn get_unit_timers(&self, _name: &str) -> Result<Vec<(String, TimerProperty)>, ServiceError> {
+        Ok(Vec::new())
+    }
+
+    fn get_unit_sockets(&self, _name: &str) -> Result<Vec<(String, SocketProperty)>, ServiceError> {
+        Ok(Vec::new())
+    }
+
+    /// A timer, socket or path unit triggers the service of the same name, as systemd defaults to.
+    fn get_unit_triggers(&self, name: &str) -> Result<UnitTriggers, ServiceError> {
+        let state = self.state();
+        let unit = state.unit(name)?;
+        let Some((stem, unit_type)) = unit.name.rsplit_once('.') else {
+            return Ok(UnitTriggers::default());
+        };
+        let existing = |names: Vec<String>| -> Vec<String> {
+            names.into_iter().filter(|name| state.unit(name).is_ok()).collect()
+        };
+        Ok(match unit_type {
+            "timer" | "socket" | "path" => UnitTriggers::new(existing(vec![format!("{}.service", stem)]), Vec::new()),
+            _ => UnitTriggers::new(
+                Vec::new(),
+                existing(["timer", "socket", "path"].iter().map(|kind| format!("{}.{}", stem, kind)).collect()),
+            ),
+        })
+    }
+
+    fn get_unit_conditions(&self, _name: &str) -> Result<UnitConditions, ServiceError> {
+        Ok(UnitConditions::default())
+    }
+
+    fn get_unit_history(&self, name: &str) -> Result<UnitHistory, ServiceError> {
+        Ok(UnitHistory::from_entries(&self.entries(name, &LogFilter::default())?))
+    }
+
+    fn get_oom_entries(&self, _name: &str) -> Result<Vec<JournalEntry>, ServiceError> {
+        Ok(Vec::new())
+    }
+
+    fn get_unit_documentation(&self, _name: &str) -> Result<Vec<String>, ServiceError> {
+        Ok(Vec::new())
+    }
+
+    /// Planned as systemd would run it, though nothing but the example units changes.
+    fn plan_action(&self, action: UnitAction, name: &str) -> Result<ActionPlan, ServiceError> {
+        let state = self.state();
+        let unit = state.unit(name)?;
+        let active: HashMap<&str, bool> = state.units.iter().map(|unit| (unit.name.as_str(), unit.is_active())).collect();
+
+        let takes_down = match action {
+            UnitAction::Stop | UnitAction::Restart => true,
+            UnitAction::ReloadOrRestart => !unit.reloads,
+            _ => false,
+        };
+        let (method, arguments) = match action {
+            UnitAction::Start => ("StartUnit", format!("(\"{}\", \"replace\")", name)),
+            UnitAction::Stop => ("StopUnit", format!("(\"{}\", \"replace\")", name)),
+            UnitAction::Restart => ("RestartUnit", format!("(\"{}\", \"replace\")", name)),
+            UnitAction::Reload => ("ReloadUnit", format!("(\"{}\", \"replace\")", name)),
+            UnitAction::ReloadOrRestart => ("ReloadOrRestartUnit", format!("(\"{}\", \"replace\")", name)),
+            UnitAction::Enable => ("EnableUnitFiles", format!("([\"{}\"], false, true)", name)),
+            UnitAction::Disable => ("DisableUnitFiles", format!("([\"{}\"], false)", name)),
+            UnitAction::Freeze => ("FreezeUnit", format!("(\"{}\")", name)),
+            UnitAction::Thaw => ("ThawUnit", format!("(\"{}\")", name)),
+        };
+
+        let mut side_effects = vec!["changes the example units only, nothing on this machine".to_string()];
+        if action == UnitAction::Start {
+            for required in &unit.requires {
+                if !active.get(required.as_str()).copied().unwrap_or(false) {
+                    side_effects.push(format!("will also start {}", required));
+                }
+            }
+        }
+        let propagated_units = if takes_down {
+            state
+                .units
+                .iter()
+                .filter(|other| other.is_active() && other.requires.iter().any(|required| required == name))
+                .map(|other| other.name.clone())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(ActionPlan::new(method.to_string(), arguments, propagated_units, side_effects))
+    }
+
+    fn schedule_action(&self, action: PolicyAction, name: &str, calendar: &str) -> Result<String, ServiceError> {
+        let mut state = self.state();
+        state.unit(name)?;
+        // Only daily times are understood here, systemd takes any OnCalendar expression.
+        let time = NaiveTime::parse_from_str(calendar, "%H:%M")
+            .map_err(|_| format!("\"{}\" is not a time of day like 02:00, the only schedule of the demo", calendar))?;
+        let now = Local::now();
+        let today = now.date_naive().and_time(time);
+        let next = if today > now.naive_local() { today } else { today + Duration::days(1) };
+        let next_elapse = Local.from_local_datetime(&next).earliest().map_or(0, |next| next.timestamp_micros() as u64);
+
+        let timer = ScheduledAction::timer_name(action, now_usec() / 1_000_000 + state.schedules.len() as u64);
+        state.schedules.push(ScheduledAction {
+            timer: timer.clone(),
+            action,
+            unit: name.to_string(),
+            calendar: calendar.to_string(),
+            next_elapse,
+        });
+        Ok(timer)
+    }
+
+    fn list_scheduled_actions(&self) -> Result<Vec<ScheduledAction>, ServiceError> {
+        let mut scheduled = self.state().schedules.clone();
+        scheduled.sort_by_key(|action| action.next_elapse);
+        Ok(scheduled)
+    }
+
+    fn cancel_scheduled_action(&self, timer: &str) -> Result<(), ServiceError> {
+        let mut state = self.state();
+        let before = state.schedules.len();
+        state.schedules.retain(|action| action.timer != timer);
+        if state.schedules.len() == before {
+            return Err(not_found(timer));
+        }
+        Ok(())
+    }
+}
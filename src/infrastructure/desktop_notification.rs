@@ -0,0 +1,26 @@
+use std::collections::HashMap;
+use zbus::blocking::Connection;
+use zbus::zvariant::Value;
+
+/// Urgency hint of the notification specification, kept on screen until dismissed.
+const CRITICAL: u8 = 2;
+
+/// Shows a desktop notification through the org.freedesktop.Notifications service of the
+/// session bus, like `notify-send`. Always on this machine, even for the units of a remote host.
+pub fn notify(summary: &str, body: &str, critical: bool) -> zbus::Result<()> {
+    let connection = Connection::session()?;
+    let mut hints: HashMap<&str, Value> = HashMap::new();
+    if critical {
+        hints.insert("urgency", Value::U8(CRITICAL));
+    }
+    let actions: Vec<&str> = Vec::new();
+    connection.call_method(
+        Some("org.freedesktop.Notifications"),
+        "/org/freedesktop/Notifications",
+        Some("org.freedesktop.Notifications"),
+        "Notify",
+        // app name, id replaced, icon, summary, body, actions, hints, timeout (server default)
+        &("services-manager-tui", 0u32, "", summary, body, actions, hints, -1i32),
+    )?;
+    Ok(())
+}
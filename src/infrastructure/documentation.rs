@@ -0,0 +1,55 @@
+use std::io;
+use std::process::{Command, Stdio};
+use std::thread;
+
+/// Removes the overstrikes ("x\x08x" for bold, "_\x08x" for underlined) and the SGR escape
+/// sequences man and groff format pages with.
+fn plain_text(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\x08' => {
+                plain.pop();
+            }
+            '\x1b' if chars.peek() == Some(&'[') => {
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            }
+            c => plain.push(c),
+        }
+    }
+    plain
+}
+
+/// A man page of this machine as plain text wrapped at `width` columns, like `man -P cat`.
+/// Always read locally, even for the units of a remote host.
+pub fn read_man_page(page: &str, section: Option<&str>, width: u16) -> io::Result<String> {
+    let mut command = Command::new("man");
+    command.env("MANWIDTH", width.to_string()).env("GROFF_NO_SGR", "1").args(["-P", "cat"]);
+    if let Some(section) = section {
+        command.arg(section);
+    }
+    let output = command.arg(page).stdin(Stdio::null()).output()?;
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(io::Error::other(if error.is_empty() { format!("No manual entry for {}", page) } else { error }));
+    }
+    Ok(plain_text(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Opens the URL in the browser of this machine with xdg-open, without waiting for it.
+pub fn open_url(url: &str) -> io::Result<()> {
+    let mut child = Command::new("xdg-open")
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    // Reaped once the browser is handed the URL.
+    thread::spawn(move || child.wait());
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use chrono::Local;
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::domain::service::Service;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Json,
+    Yaml,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Yaml => "yaml",
+        }
+    }
+}
+
+pub fn serialize_services(services: &[Service], format: ExportFormat) -> Result<String, Box<dyn Error>> {
+    let content = match format {
+        ExportFormat::Json => serde_json::to_string_pretty(services)? + "\n",
+        ExportFormat::Yaml => serde_yaml::to_string(services)?,
+    };
+    Ok(content)
+}
+
+/// Writes the services to a timestamped file in `directory`, returning its path.
+pub fn export_to_directory(
+    services: &[Service],
+    format: ExportFormat,
+    directory: &Path,
+) -> Result<PathBuf, Box<dyn Error>> {
+    let file_name = format!(
+        "services-{}.{}",
+        Local::now().format("%Y%m%d-%H%M%S"),
+        format.extension()
+    );
+    let path = directory.join(file_name);
+    fs::write(&path, serialize_services(services, format)?)
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+/// Writes log lines to `path`, one per line.
+pub fn write_log(path: &Path, lines: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut content = lines.join("\n");
+    content.push('\n');
+    fs::write(path, content).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(())
+}
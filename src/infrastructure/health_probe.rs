@@ -0,0 +1,99 @@
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::domain::health::{Health, HealthProbe};
+
+/// Interval between two checks of whether a probe command exited.
+const COMMAND_POLL: Duration = Duration::from_millis(50);
+
+/// Runs the probe from this machine, a probe taking longer than `timeout` counting as down.
+pub fn probe(probe: &HealthProbe, timeout: Duration) -> Health {
+    if let Some(url) = &probe.http {
+        probe_http(url, timeout)
+    } else if let Some(address) = &probe.tcp {
+        connect(address, timeout).map_or(Health::Down, |_| Health::Ok)
+    } else if let Some(command) = &probe.command {
+        probe_command(command, timeout)
+    } else {
+        Health::Down
+    }
+}
+
+fn connect(address: &str, timeout: Duration) -> Option<TcpStream> {
+    let address = address.to_socket_addrs().ok()?.next()?;
+    let stream = TcpStream::connect_timeout(&address, timeout).ok()?;
+    stream.set_read_timeout(Some(timeout)).ok()?;
+    stream.set_write_timeout(Some(timeout)).ok()?;
+    Some(stream)
+}
+
+/// Sends a GET and reads the status line; https goes through curl, which does the TLS.
+fn probe_http(url: &str, timeout: Duration) -> Health {
+    let status = match url.strip_prefix("http://") {
+        Some(rest) => http_status(rest, timeout),
+        None => curl_status(url, timeout),
+    };
+    status.map_or(Health::Down, Health::from_http_status)
+}
+
+/// Status of a plain HTTP request, `rest` being the URL without its scheme.
+fn http_status(rest: &str, timeout: Duration) -> Option<u16> {
+    let (authority, path) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, "/"),
+    };
+    let address = if authority.contains(':') { authority.to_string() } else { format!("{}:80", authority) };
+    let mut stream = connect(&address, timeout)?;
+    let request = format!(
+        "GET {} HTTP/1.0\r\nHost: {}\r\nUser-Agent: services-manager-tui\r\nConnection: close\r\n\r\n",
+        path, authority
+    );
+    stream.write_all(request.as_bytes()).ok()?;
+
+    // "HTTP/1.1 200 OK" fits in the first bytes, the rest of the response is not needed.
+    let mut head = [0u8; 64];
+    let read = stream.read(&mut head).ok()?;
+    let status_line = String::from_utf8_lossy(&head[..read]);
+    status_line.split_whitespace().nth(1)?.parse().ok()
+}
+
+fn curl_status(url: &str, timeout: Duration) -> Option<u16> {
+    let output = Command::new("curl")
+        .args(["--silent", "--output", "/dev/null", "--write-out", "%{http_code}", "--max-time"])
+        .arg(timeout.as_secs().max(1).to_string())
+        .arg(url)
+        .stdin(Stdio::null())
+        .output()
+        .ok()?;
+    // curl writes 000 when there was no response.
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok().filter(|status| *status > 0)
+}
+
+/// Runs the command with `sh -c`, killing it when it outlives the timeout.
+fn probe_command(command: &str, timeout: Duration) -> Health {
+    let Ok(mut child) = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return Health::Down;
+    };
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return Health::from_exit_code(status.code()),
+            Ok(None) if started.elapsed() < timeout => thread::sleep(COMMAND_POLL),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Health::Down;
+            }
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::thread;
+
+use crate::domain::hook::{Hook, HookEvent};
+
+/// Seconds a webhook request may take.
+const WEBHOOK_TIMEOUT: &str = "10";
+
+/// Runs the hook on its own thread, so that a slow command or endpoint does not hold up the
+/// action or the TUI. What the hook outputs, and whether it fails, is left to the hook.
+pub fn run(hook: &Hook, event: &HookEvent) {
+    let hook = hook.clone();
+    let event = event.clone();
+    thread::spawn(move || {
+        if let Some(command) = &hook.command {
+            let _ = Command::new("sh")
+                .arg("-c")
+                .arg(command)
+                .env("HOOK_EVENT", &event.name)
+                .env("UNIT", &event.unit)
+                .env("HOOK_TEXT", &event.text)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status();
+        } else if let Some(url) = &hook.url {
+            post(url, &event.json());
+        }
+    });
+}
+
+/// POSTs the JSON body with curl, which also speaks TLS.
+fn post(url: &str, body: &str) {
+    let child = Command::new("curl")
+        .args(["--silent", "--max-time", WEBHOOK_TIMEOUT, "--header", "Content-Type: application/json"])
+        .args(["--data-binary", "@-", url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    if let Ok(mut child) = child {
+        if let Some(mut stdin) = child.stdin.take() {
+            let _ = stdin.write_all(body.as_bytes());
+        }
+        let _ = child.wait();
+    }
+}
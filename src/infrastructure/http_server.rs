@@ -0,0 +1,130 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+
+/// How long a client may take to send its request.
+const TIMEOUT: Duration = Duration::from_secs(5);
+/// Largest request head read, the API taking no body.
+const MAX_HEAD: u64 = 16 * 1024;
+
+/// The parts of an HTTP/1.1 request the API looks at.
+pub struct HttpRequest {
+    pub method: String,
+    /// The path without the query, percent-decoded, e.g. "/units/nginx.service/logs".
+    pub path: String,
+    pub query: HashMap<String, String>,
+    /// The value of the Authorization header, if any.
+    pub authorization: Option<String>,
+}
+
+impl HttpRequest {
+    /// Reads the request line and headers; a body, which no endpoint takes, is ignored.
+    pub fn read(stream: &TcpStream) -> Result<Self, String> {
+        stream.set_read_timeout(Some(TIMEOUT)).map_err(|e| e.to_string())?;
+        let mut reader = BufReader::new(stream.take(MAX_HEAD));
+        let mut line = String::new();
+        reader.read_line(&mut line).map_err(|e| e.to_string())?;
+        let mut parts = line.split_whitespace();
+        let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+            return Err("Malformed request line".to_string());
+        };
+        let method = method.to_string();
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let path = percent_decode(path);
+        let query = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (percent_decode(key), percent_decode(&value.replace('+', " ")))
+            })
+            .collect();
+
+        let mut authorization = None;
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).map_err(|e| e.to_string())? == 0 {
+                return Err("Incomplete request".to_string());
+            }
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':')
+                && name.eq_ignore_ascii_case("authorization")
+            {
+                authorization = Some(value.trim().to_string());
+            }
+        }
+        Ok(Self { method, path, query, authorization })
+    }
+
+    /// Whether the request carries `Authorization: Bearer <token>`, compared in constant time.
+    pub fn is_authorized(&self, token: &str) -> bool {
+        let Some(given) = self.authorization.as_deref().and_then(|value| value.strip_prefix("Bearer ")) else {
+            return false;
+        };
+        given.len() == token.len() && given.bytes().zip(token.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+    }
+}
+
+/// Decodes the `%XX` escapes of a path or query part, leaving malformed ones as they are.
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = (bytes[index] == b'%')
+            .then(|| bytes.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+/// Answers with a JSON body and closes the connection; a client gone meanwhile is ignored.
+pub fn respond(mut stream: TcpStream, status: u16, body: &Value) {
+    let body = body.to_string();
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        501 => "Not Implemented",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    };
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes());
+}
+
+/// A token for a server started without one, 32 hexadecimal digits read from the kernel's
+/// random source.
+pub fn random_token() -> Result<String, String> {
+    let mut bytes = [0u8; 16];
+    File::open("/dev/urandom")
+        .and_then(|mut random| random.read_exact(&mut bytes))
+        .map_err(|e| format!("/dev/urandom: {}", e))?;
+    Ok(bytes.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
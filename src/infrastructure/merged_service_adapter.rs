@@ -0,0 +1,659 @@
+use std::collections::HashMap;
+use std::process::Command;
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+
+use crate::errors::ServiceError;
+use crate::infrastructure::call_limits::CallLimits;
+use crate::domain::action_journal::UnitFileChange;
+use crate::domain::action_plan::{ActionPlan, UnitAction};
+use crate::domain::action_policy::PolicyAction;
+use crate::domain::boot_timing::{ChainLink, UnitActivation};
+use crate::domain::security_audit::{SecurityAudit, SecurityScore};
+use crate::domain::slice::Slice;
+use crate::domain::crash_loop::RestartSettings;
+use crate::domain::job::JobResult;
+use crate::domain::journal_boot::ListedBoot;
+use crate::domain::journal_entry::{JournalEntry, LogFilter};
+use crate::domain::kill::KillTarget;
+use crate::domain::latency::ListTimings;
+use crate::domain::power_action::PowerAction;
+use crate::domain::process::UnitProcess;
+use crate::domain::resource_limits::ResourceLimits;
+use crate::domain::resource_usage::ResourceUsage;
+use crate::domain::runtime_stats::RuntimeStats;
+use crate::domain::system_summary::SystemSummary;
+use crate::domain::scheduled_action::ScheduledAction;
+use crate::domain::service::{qualified_name, Service, SOURCE_SEPARATOR};
+use crate::domain::service_repository::ServiceRepository;
+use crate::domain::unit_alias::UnitLinks;
+use crate::domain::unit_capabilities::UnitCapabilities;
+use crate::domain::unit_conditions::UnitConditions;
+use crate::domain::unit_history::UnitHistory;
+use crate::domain::unit_dependencies::UnitDependencies;
+use crate::domain::unit_environment::UnitEnvironment;
+use crate::domain::unit_file::{UnitFileMatch, UnitFilePaths};
+use crate::domain::unit_path::UnitPathReference;
+use crate::domain::unit_property::{SocketProperty, TimerProperty, UnitProperty};
+use crate::domain::unit_transition::UnitTransition;
+use crate::domain::unit_verification::VerifyFinding;
+use crate::domain::unit_triggers::UnitTriggers;
+use crate::infrastructure::backend::Backend;
+use crate::infrastructure::container_service_adapter::ContainerServiceAdapter;
+use crate::infrastructure::systemd_service_adapter::{ConnectionType, SystemdServiceAdapter};
+
+/// A repository of a merged view, named by the `sources` entry it comes from.
+struct Source {
+    label: String,
+    repository: Box<dyn ServiceRepository>,
+}
+
+/// Shows the units of several repositories in one table, e.g. the system and session managers
+/// and the containers. Units are named "source/unit", each call going to the repository of
+/// its unit; names without a source go to the first one.
+pub struct MergedServiceAdapter {
+    sources: Vec<Source>,
+}
+
+/// Runs `fetch` on every source, keeping the results of the ones it works with; an error
+/// only when it fails with all of them, e.g. searching unit files with a container source.
+fn gather<T, F>(sources: &[Source], fetch: F) -> Result<Vec<T>, ServiceError>
+where
+    F: Fn(&Source) -> Result<Vec<T>, ServiceError>,
+{
+    let mut gathered = Vec::new();
+    let mut last_error = None;
+    for source in sources {
+        match fetch(source) {
+            Ok(items) => gathered.extend(items),
+            Err(e) => last_error = Some(format!("{}: {}", source.label, e)),
+        }
+    }
+    match last_error {
+        Some(e) if gathered.is_empty() => Err(e.into()),
+        _ => Ok(gathered),
+    }
+}
+
+/// Runs `action` on every source, returning the first error once all of them ran.
+fn each<F>(sources: &[Source], action: F) -> Result<(), ServiceError>
+where
+    F: Fn(&Source) -> Result<(), ServiceError>,
+{
+    let mut first_error: Option<ServiceError> = None;
+    for source in sources {
+        if let Err(e) = action(source) {
+            first_error.get_or_insert_with(|| format!("{}: {}", source.label, e).into());
+        }
+    }
+    first_error.map_or(Ok(()), Err)
+}
+
+impl MergedServiceAdapter {
+    /// Connects to every source: "system" and "session" for the managers of the backend,
+    /// "containers" for the container engine, and ssh destinations for remote systemd hosts.
+    pub fn new(labels: &[String], backend: Backend, unit_types: Vec<String>) -> Result<Self, ServiceError> {
+        let mut sources = Vec::new();
+        for label in labels {
+            if label.contains(SOURCE_SEPARATOR) || sources.iter().any(|source: &Source| source.label == *label) {
+                return Err(format!("Invalid or repeated source \"{}\"", label).into());
+            }
+            let repository: Box<dyn ServiceRepository> = match label.as_str() {
+                "system" => backend.create_repository(ConnectionType::System, unit_types.clone())?,
+                "session" => backend.create_repository(ConnectionType::Session, unit_types.clone())?,
+                "containers" => Box::new(ContainerServiceAdapter::new(ConnectionType::System)?),
+                host => Box::new(SystemdServiceAdapter::remote(host.to_string(), ConnectionType::System, unit_types.clone())?),
+            };
+            sources.push(Source {
+                label: label.clone(),
+                repository,
+            });
+        }
+        if sources.is_empty() {
+            return Err("No source to merge".into());
+        }
+        Ok(Self { sources })
+    }
+
+    /// The source of the unit and its name there.
+    fn route<'a>(&self, name: &'a str) -> Result<(&Source, &'a str), ServiceError> {
+        let Some((label, unit)) = name.split_once(SOURCE_SEPARATOR) else {
+            return Ok((&self.sources[0], name));
+        };
+        let source = self
+            .sources
+            .iter()
+            .find(|source| source.label == label)
+            .ok_or_else(|| format!("Unknown source \"{}\"", label))?;
+        Ok((source, unit))
+    }
+
+    /// Jobs are named after their source too, job paths of two managers being alike.
+    fn qualified_job(source: &Source, job: Option<String>) -> Option<String> {
+        job.map(|job| qualified_name(&source.label, &job))
+    }
+}
+
+impl ServiceRepository for MergedServiceAdapter {
+    fn list_services(&self) -> Result<Vec<Service>, ServiceError> {
+        let mut services = Vec::new();
+        for source in &self.sources {
+            let listed = source.repository.list_services().map_err(|e| format!("{}: {}", source.label, e))?;
+            services.extend(listed.into_iter().map(|service| service.qualified(&source.label)));
+        }
+        Ok(services)
+    }
+
+    fn time_listing(&self) -> Result<ListTimings, ServiceError> {
+        let mut list_call = Default::default();
+        let mut unit_calls = Vec::new();
+        for source in &self.sources {
+            let timings = source.repository.time_listing()?;
+            list_call += timings.list_call();
+            unit_calls.extend_from_slice(timings.unit_calls());
+        }
+        Ok(ListTimings::new(list_call, unit_calls))
+    }
+
+    fn get_service_log(&self, name: &str, lines: u32) -> Result<String, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_service_log(unit, lines)
+    }
+
+    fn get_journal_entries(&self, name: &str, lines: u32, filter: &LogFilter) -> Result<Vec<JournalEntry>, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_journal_entries(unit, lines, filter)
+    }
+
+    fn get_journal_boots(&self) -> Result<Vec<ListedBoot>, ServiceError> {
+        self.sources[0].repository.get_journal_boots()
+    }
+
+    fn get_older_journal_entries(
+        &self,
+        name: &str,
+        cursor: &str,
+        lines: u32,
+        filter: &LogFilter,
+    ) -> Result<Vec<JournalEntry>, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_older_journal_entries(unit, cursor, lines, filter)
+    }
+
+    fn get_log_timestamps(&self, name: &str) -> Result<Vec<u64>, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_log_timestamps(unit)
+    }
+
+    fn get_journal_units_since(&self, minutes: u64) -> Result<Vec<String>, ServiceError> {
+        gather(&self.sources, |source| {
+            let units = source.repository.get_journal_units_since(minutes)?;
+            Ok(units.iter().map(|unit| qualified_name(&source.label, unit)).collect())
+        })
+    }
+
+    fn start_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        Ok(Self::qualified_job(source, source.repository.start_service(unit)?))
+    }
+
+    fn start_transient_unit(&self, name: &str, argv: &[String], limits: &ResourceLimits) -> Result<Option<String>, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        Ok(Self::qualified_job(source, source.repository.start_transient_unit(unit, argv, limits)?))
+    }
+
+    fn stop_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        Ok(Self::qualified_job(source, source.repository.stop_service(unit)?))
+    }
+
+    fn restart_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        Ok(Self::qualified_job(source, source.repository.restart_service(unit)?))
+    }
+
+    fn reload_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        Ok(Self::qualified_job(source, source.repository.reload_service(unit)?))
+    }
+
+    fn reload_or_restart_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        Ok(Self::qualified_job(source, source.repository.reload_or_restart_service(unit)?))
+    }
+
+    fn get_unit_capabilities(&self, name: &str) -> Result<UnitCapabilities, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_unit_capabilities(unit)
+    }
+
+    fn list_targets(&self) -> Result<Vec<Service>, ServiceError> {
+        gather(&self.sources, |source| {
+            let targets = source.repository.list_targets()?;
+            Ok(targets.into_iter().map(|target| target.qualified(&source.label)).collect())
+        })
+    }
+
+    // The machine is the one of the first source, like for the boot analysis.
+    fn get_default_target(&self) -> Result<String, ServiceError> {
+        let source = &self.sources[0];
+        Ok(qualified_name(&source.label, &source.repository.get_default_target()?))
+    }
+
+    fn set_default_target(&self, name: &str) -> Result<(), ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.set_default_target(unit)
+    }
+
+    fn isolate_target(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        Ok(Self::qualified_job(source, source.repository.isolate_target(unit)?))
+    }
+
+    fn power(&self, action: PowerAction) -> Result<(), ServiceError> {
+        self.sources[0].repository.power(action)
+    }
+
+    /// Forwards the jobs of every source, named after it.
+    fn watch_jobs(&mut self, sender: Sender<JobResult>) -> Result<(), ServiceError> {
+        for source in &mut self.sources {
+            let (source_sender, receiver) = mpsc::channel::<JobResult>();
+            source.repository.watch_jobs(source_sender)?;
+            let label = source.label.clone();
+            let sender = sender.clone();
+            // Ends once the source drops its sender, or the application its receiver.
+            thread::spawn(move || {
+                for job in receiver {
+                    let job = JobResult::new(qualified_name(&label, job.job()), job.result().to_string());
+                    if sender.send(job).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        Ok(())
+    }
+
+    fn watch_units(&mut self, units: &[String], sender: Sender<UnitTransition>) -> Result<(), ServiceError> {
+        let mut by_source: HashMap<String, Vec<String>> = HashMap::new();
+        for name in units {
+            let (source, unit) = self.route(name)?;
+            by_source.entry(source.label.clone()).or_default().push(unit.to_string());
+        }
+        // Every source is told, so the ones left without watched units stop watching.
+        for source in &mut self.sources {
+            let units = by_source.remove(&source.label).unwrap_or_default();
+            let (source_sender, receiver) = mpsc::channel::<UnitTransition>();
+            source.repository.watch_units(&units, source_sender)?;
+            let label = source.label.clone();
+            let sender = sender.clone();
+            thread::spawn(move || {
+                for transition in receiver {
+                    if sender.send(transition.qualified(&label)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        Ok(())
+    }
+
+    fn enable_service(&self, name: &str) -> Result<Vec<UnitFileChange>, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.enable_service(unit)
+    }
+
+    fn disable_service(&self, name: &str) -> Result<Vec<UnitFileChange>, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.disable_service(unit)
+    }
+
+    fn revert_unit_file_changes(&self, name: &str, changes: &[UnitFileChange]) -> Result<(), ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.revert_unit_file_changes(unit, changes)
+    }
+
+    fn freeze_service(&self, name: &str) -> Result<(), ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.freeze_service(unit)
+    }
+
+    fn thaw_service(&self, name: &str) -> Result<(), ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.thaw_service(unit)
+    }
+
+    fn reset_failed_unit(&self, name: &str) -> Result<(), ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.reset_failed_unit(unit)
+    }
+
+    fn reset_failed(&self) -> Result<(), ServiceError> {
+        each(&self.sources, |source| source.repository.reset_failed())
+    }
+
+    fn kill_service(&self, name: &str, target: KillTarget, signal: i32) -> Result<(), ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.kill_service(unit, target, signal)
+    }
+
+    fn reload_daemon(&self) -> Result<(), ServiceError> {
+        each(&self.sources, |source| source.repository.reload_daemon())
+    }
+
+    fn get_unit_property(&self, name: &str) -> Result<UnitProperty, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_unit_property(unit)
+    }
+
+    fn get_restart_settings(&self, name: &str) -> Result<RestartSettings, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_restart_settings(unit)
+    }
+
+    fn get_runtime_stats(&self, name: &str) -> Result<RuntimeStats, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_runtime_stats(unit)
+    }
+
+    fn get_resource_usage(&self, name: &str) -> Result<ResourceUsage, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_resource_usage(unit)
+    }
+
+    fn get_unit_processes(&self, name: &str) -> Result<Vec<UnitProcess>, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_unit_processes(unit)
+    }
+
+    fn get_state_change_timestamp(&self, name: &str) -> Result<u64, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_state_change_timestamp(unit)
+    }
+
+    fn get_need_daemon_reload(&self, name: &str) -> Result<bool, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_need_daemon_reload(unit)
+    }
+
+    fn get_freezer_state(&self, name: &str) -> Result<String, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_freezer_state(unit)
+    }
+
+    /// The slices of the first source, like its boot; the units stay qualified to be acted upon.
+    fn get_slices(&self) -> Result<Vec<Slice>, ServiceError> {
+        let source = &self.sources[0];
+        let mut slices = source.repository.get_slices()?;
+        for slice in &mut slices {
+            for unit in &mut slice.units {
+                *unit = qualified_name(&source.label, unit);
+            }
+        }
+        Ok(slices)
+    }
+
+    // The boot of the machine is the one of the first source.
+    fn get_boot_time(&self) -> Result<String, ServiceError> {
+        self.sources[0].repository.get_boot_time()
+    }
+
+    fn get_system_summary(&self) -> Result<SystemSummary, ServiceError> {
+        self.sources[0].repository.get_system_summary()
+    }
+
+    fn get_boot_blame(&self) -> Result<Vec<UnitActivation>, ServiceError> {
+        let source = &self.sources[0];
+        let blame = source.repository.get_boot_blame()?;
+        Ok(blame
+            .into_iter()
+            .map(|activation| UnitActivation::new(qualified_name(&source.label, activation.unit()), activation.activation_ms()))
+            .collect())
+    }
+
+    fn get_critical_chain(&self, unit: Option<&str>) -> Result<Vec<ChainLink>, ServiceError> {
+        let (source, unit) = match unit {
+            Some(name) => {
+                let (source, unit) = self.route(name)?;
+                (source, Some(unit))
+            }
+            None => (&self.sources[0], None),
+        };
+        let mut chain = source.repository.get_critical_chain(unit)?;
+        for link in &mut chain {
+            link.unit = qualified_name(&source.label, &link.unit);
+        }
+        Ok(chain)
+    }
+
+    /// Scores of the sources able to analyze their services, e.g. not the OpenRC ones.
+    fn get_security_scores(&self) -> Result<Vec<SecurityScore>, ServiceError> {
+        let mut scores = Vec::new();
+        for source in &self.sources {
+            match source.repository.get_security_scores() {
+                Ok(source_scores) => scores.extend(source_scores.into_iter().map(|score| SecurityScore {
+                    unit: qualified_name(&source.label, &score.unit),
+                    ..score
+                })),
+                Err(ServiceError::Unsupported(_)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(scores)
+    }
+
+    fn get_security_audit(&self, name: &str) -> Result<SecurityAudit, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_security_audit(unit)
+    }
+
+    fn change_connection(&mut self, _connection_type: ConnectionType) -> Result<(), ServiceError> {
+        Err(ServiceError::Unsupported("The merged view shows the managers set in the sources setting".to_string()))
+    }
+
+    fn connection_lost(&self) -> bool {
+        self.sources.iter().any(|source| source.repository.connection_lost())
+    }
+
+    /// Reconnects the sources that lost their connection, the others keep theirs.
+    fn reconnect(&mut self) -> Result<(), ServiceError> {
+        for source in self.sources.iter_mut().filter(|source| source.repository.connection_lost()) {
+            source.repository.reconnect()?;
+        }
+        Ok(())
+    }
+
+    fn worker(&self) -> Result<Box<dyn ServiceRepository>, ServiceError> {
+        let sources = self
+            .sources
+            .iter()
+            .map(|source| {
+                Ok(Source {
+                    label: source.label.clone(),
+                    repository: source.repository.worker()?,
+                })
+            })
+            .collect::<Result<_, ServiceError>>()?;
+        Ok(Box::new(Self { sources }))
+    }
+
+    fn set_call_limits(&mut self, limits: CallLimits) {
+        for source in &mut self.sources {
+            source.repository.set_call_limits(limits.clone());
+        }
+    }
+
+    fn unit_generation(&self, name: &str) -> Option<String> {
+        let (source, unit) = self.route(name).ok()?;
+        source.repository.unit_generation(unit)
+    }
+
+    fn systemctl_cat(&self, name: &str) -> Result<String, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.systemctl_cat(unit)
+    }
+
+    fn get_unit_file_paths(&self, name: &str) -> Result<UnitFilePaths, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_unit_file_paths(unit)
+    }
+
+    fn get_unit_links(&self, name: &str) -> Result<UnitLinks, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_unit_links(unit)
+    }
+
+    fn get_loaded_settings(&self, name: &str, settings: &[String]) -> Result<HashMap<String, Vec<String>>, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_loaded_settings(unit, settings)
+    }
+
+    fn verify_unit(&self, name: &str) -> Result<Vec<VerifyFinding>, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.verify_unit(unit)
+    }
+
+    fn create_unit(&self, name: &str, content: &str) -> Result<String, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.create_unit(unit, content)
+    }
+
+    fn write_drop_in(&self, name: &str, file_name: &str, content: &str) -> Result<String, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.write_drop_in(unit, file_name, content)
+    }
+
+    fn edit_unit_command(&self, name: &str) -> Result<Command, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.edit_unit_command(unit)
+    }
+
+    fn shell_command(&self, name: &str, directory: &str) -> Result<Command, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.shell_command(unit, directory)
+    }
+
+    fn remove_drop_in(&self, name: &str, path: &str) -> Result<(), ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.remove_drop_in(unit, path)
+    }
+
+    fn get_unit_environment(&self, name: &str) -> Result<UnitEnvironment, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_unit_environment(unit)
+    }
+
+    fn get_resource_limits(&self, name: &str) -> Result<ResourceLimits, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_resource_limits(unit)
+    }
+
+    fn set_resource_limits(&self, name: &str, limits: &ResourceLimits, runtime: bool) -> Result<(), ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.set_resource_limits(unit, limits, runtime)
+    }
+
+    fn list_unit_paths(&self) -> Result<Vec<UnitPathReference>, ServiceError> {
+        gather(&self.sources, |source| {
+            let references = source.repository.list_unit_paths()?;
+            Ok(references
+                .into_iter()
+                .map(|reference| {
+                    UnitPathReference::new(
+                        qualified_name(&source.label, reference.unit()),
+                        reference.relation().clone(),
+                        reference.path().to_string(),
+                    )
+                })
+                .collect())
+        })
+    }
+
+    fn search_unit_files(&self, query: &str) -> Result<Vec<UnitFileMatch>, ServiceError> {
+        gather(&self.sources, |source| {
+            let mut matches = source.repository.search_unit_files(query)?;
+            for found in &mut matches {
+                found.unit = qualified_name(&source.label, &found.unit);
+            }
+            Ok(matches)
+        })
+    }
+
+    fn get_unit_dependencies(&self, name: &str) -> Result<UnitDependencies, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        Ok(source.repository.get_unit_dependencies(unit)?.qualified(&source.label))
+    }
+
+    fn get_unit_timers(&self, name: &str) -> Result<Vec<(String, TimerProperty)>, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        let timers = source.repository.get_unit_timers(unit)?;
+        Ok(timers.into_iter().map(|(timer, property)| (qualified_name(&source.label, &timer), property)).collect())
+    }
+
+    fn get_unit_sockets(&self, name: &str) -> Result<Vec<(String, SocketProperty)>, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        let sockets = source.repository.get_unit_sockets(unit)?;
+        Ok(sockets.into_iter().map(|(socket, property)| (qualified_name(&source.label, &socket), property)).collect())
+    }
+
+    fn get_unit_triggers(&self, name: &str) -> Result<UnitTriggers, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        Ok(source.repository.get_unit_triggers(unit)?.qualified(&source.label))
+    }
+
+    fn get_unit_conditions(&self, name: &str) -> Result<UnitConditions, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_unit_conditions(unit)
+    }
+
+    fn get_unit_history(&self, name: &str) -> Result<UnitHistory, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_unit_history(unit)
+    }
+
+    fn get_oom_entries(&self, name: &str) -> Result<Vec<JournalEntry>, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_oom_entries(unit)
+    }
+
+    fn get_unit_documentation(&self, name: &str) -> Result<Vec<String>, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        source.repository.get_unit_documentation(unit)
+    }
+
+    fn plan_action(&self, action: UnitAction, name: &str) -> Result<ActionPlan, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        let plan = source.repository.plan_action(action, unit)?;
+        Ok(ActionPlan::new(
+            format!("{}: {}", source.label, plan.method()),
+            plan.arguments().to_string(),
+            plan.propagated_units().iter().map(|unit| qualified_name(&source.label, unit)).collect(),
+            plan.side_effects().to_vec(),
+        ))
+    }
+
+    fn schedule_action(&self, action: PolicyAction, name: &str, calendar: &str) -> Result<String, ServiceError> {
+        let (source, unit) = self.route(name)?;
+        let timer = source.repository.schedule_action(action, unit, calendar)?;
+        Ok(qualified_name(&source.label, &timer))
+    }
+
+    fn list_scheduled_actions(&self) -> Result<Vec<ScheduledAction>, ServiceError> {
+        gather(&self.sources, |source| {
+            let scheduled = source.repository.list_scheduled_actions()?;
+            Ok(scheduled
+                .into_iter()
+                .map(|action| ScheduledAction {
+                    timer: qualified_name(&source.label, &action.timer),
+                    unit: qualified_name(&source.label, &action.unit),
+                    ..action
+                })
+                .collect())
+        })
+    }
+
+    fn cancel_scheduled_action(&self, timer: &str) -> Result<(), ServiceError> {
+        let (source, timer) = self.route(timer)?;
+        source.repository.cancel_scheduled_action(timer)
+    }
+}
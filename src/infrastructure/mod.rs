@@ -1 +1,22 @@
+pub mod backend;
+pub mod call_limits;
+pub mod clipboard;
+pub mod container_service_adapter;
+pub mod demo_service_adapter;
+pub mod desktop_notification;
+pub mod documentation;
+pub mod export;
+pub mod health_probe;
+pub mod hook_runner;
+pub mod http_server;
+pub mod merged_service_adapter;
+pub mod openrc_service_adapter;
+pub mod pinned_store;
+pub mod session_recorder;
+pub mod session_store;
+pub mod profile_store;
+pub mod sd_journal;
+pub mod snapshot_store;
 pub mod systemd_service_adapter;
+pub mod trace_log;
+pub mod uptime_store;
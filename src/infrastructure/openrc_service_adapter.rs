@@ -0,0 +1,694 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use std::sync::mpsc::Sender;
+use std::time::Instant;
+
+use crate::errors::ServiceError;
+use crate::infrastructure::call_limits::CallLimits;
+use crate::domain::action_journal::UnitFileChange;
+use crate::domain::action_plan::{ActionPlan, UnitAction};
+use crate::domain::action_policy::PolicyAction;
+use crate::domain::boot_timing::{ChainLink, UnitActivation};
+use crate::domain::security_audit::{SecurityAudit, SecurityScore};
+use crate::domain::slice::Slice;
+use crate::domain::crash_loop::RestartSettings;
+use crate::domain::job::JobResult;
+use crate::domain::journal_boot::ListedBoot;
+use crate::domain::journal_entry::{JournalEntry, LogFilter};
+use crate::domain::kill::KillTarget;
+use crate::domain::latency::ListTimings;
+use crate::domain::power_action::PowerAction;
+use crate::domain::process::UnitProcess;
+use crate::domain::resource_limits::ResourceLimits;
+use crate::domain::resource_usage::ResourceUsage;
+use crate::domain::runtime_stats::RuntimeStats;
+use crate::domain::system_summary::SystemSummary;
+use crate::domain::scheduled_action::ScheduledAction;
+use crate::domain::service::Service;
+use crate::domain::unit_property::{SocketProperty, TimerProperty, UnitProperty};
+use crate::domain::service_repository::ServiceRepository;
+use crate::domain::service_state::ServiceState;
+use crate::domain::unit_alias::UnitLinks;
+use crate::domain::unit_capabilities::UnitCapabilities;
+use crate::domain::unit_conditions::UnitConditions;
+use crate::domain::unit_history::UnitHistory;
+use crate::domain::unit_dependencies::UnitDependencies;
+use crate::domain::unit_environment::UnitEnvironment;
+use crate::domain::unit_file::{parse_grep_matches, UnitFileMatch, UnitFilePaths};
+use crate::domain::unit_path::UnitPathReference;
+use crate::domain::unit_transition::UnitTransition;
+use crate::domain::unit_verification::VerifyFinding;
+use crate::domain::unit_triggers::UnitTriggers;
+use crate::infrastructure::systemd_service_adapter::ConnectionType;
+
+const INIT_DIR: &str = "/etc/init.d";
+const SYSTEM_LOG: &str = "/var/log/messages";
+
+/// Manages OpenRC services (Alpine, Gentoo, Artix...) through rc-service, rc-update and rc-status.
+pub struct OpenRcServiceAdapter {
+    /// Manage user services (`--user`, OpenRC 0.60 and later) instead of system ones.
+    user: bool,
+    /// Timeout and cancellation of listing the services.
+    limits: CallLimits,
+}
+
+fn unsupported(what: &str) -> ServiceError {
+    ServiceError::Unsupported(format!("{} is not available with OpenRC", what))
+}
+
+/// The standard output of an OpenRC tool, or its standard error when it failed.
+fn stdout(output: Output) -> Result<String, ServiceError> {
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        let err_msg = String::from_utf8_lossy(&output.stderr).to_string();
+        Err(ServiceError::from_stderr(err_msg))
+    }
+}
+
+impl OpenRcServiceAdapter {
+    pub fn new(connection_type: ConnectionType) -> Self {
+        Self {
+            user: connection_type == ConnectionType::Session,
+            limits: CallLimits::default(),
+        }
+    }
+
+    /// Runs an OpenRC tool, returning its standard output.
+    fn run(&self, program: &str, args: &[&str]) -> Result<String, ServiceError> {
+        let output = self.command(program, args).output()?;
+        stdout(output)
+    }
+
+    /// Like `run`, for the listings, bounded by the call limits.
+    fn list(&self, program: &str, args: &[&str]) -> Result<String, ServiceError> {
+        let output = self.limits.output("Listing the services", &mut self.command(program, args))?;
+        stdout(output)
+    }
+
+    fn command(&self, program: &str, args: &[&str]) -> Command {
+        let mut command = Command::new(program);
+        if self.user {
+            command.arg("--user");
+        }
+        command.arg("--nocolor").args(args);
+        command
+    }
+
+    /// Status of every service rc-status knows about, e.g. "started" or "crashed".
+    fn statuses(&self) -> Result<HashMap<String, String>, ServiceError> {
+        let output = self.list("rc-status", &["--all"])?;
+        Ok(output
+            .lines()
+            .filter_map(|line| {
+                let (name, rest) = line.split_once('[')?;
+                let status = rest.trim_end().strip_suffix(']')?.split_whitespace().next()?;
+                Some((name.trim().to_string(), status.to_string()))
+            })
+            .collect())
+    }
+
+    /// Runlevels of every service, empty for the ones not added to any.
+    fn runlevels(&self) -> Result<HashMap<String, Vec<String>>, ServiceError> {
+        let output = self.list("rc-update", &["--verbose", "show"])?;
+        Ok(output
+            .lines()
+            .filter_map(|line| {
+                let (name, runlevels) = line.split_once('|')?;
+                let runlevels = runlevels.split_whitespace().map(str::to_string).collect();
+                Some((name.trim().to_string(), runlevels))
+            })
+            .collect())
+    }
+
+    fn description(&self, name: &str) -> String {
+        if self.user {
+            return String::new();
+        }
+        fs::read_to_string(Path::new(INIT_DIR).join(name))
+            .ok()
+            .and_then(|script| {
+                script.lines().find_map(|line| {
+                    let value = line.trim().strip_prefix("description=")?;
+                    Some(value.trim_matches(|c| c == '"' || c == '\'').to_string())
+                })
+            })
+            .unwrap_or_default()
+    }
+
+    /// Services listed by one of the dependency commands of openrc-run, e.g. "ineed".
+    fn dependency(&self, name: &str, command: &str) -> Result<Vec<String>, ServiceError> {
+        let output = self.run("rc-service", &[name, command])?;
+        Ok(output.split_whitespace().map(str::to_string).collect())
+    }
+
+    /// Whether the init script declares a "reload" command, in extra_commands= or
+    /// extra_started_commands=.
+    fn has_reload(&self, name: &str) -> Result<bool, ServiceError> {
+        let script = fs::read_to_string(Path::new(INIT_DIR).join(name))?;
+        Ok(script.lines().any(|line| {
+            let line = line.trim();
+            (line.starts_with("extra_commands=") || line.starts_with("extra_started_commands="))
+                && line.split(['=', '"', '\'', ' ']).any(|word| word == "reload")
+        }))
+    }
+
+    fn service_command(&self, name: &str, command: &str) -> Result<(), ServiceError> {
+        self.run("rc-service", &[name, command]).map(|_| ())
+    }
+}
+
+impl ServiceRepository for OpenRcServiceAdapter {
+    fn change_connection(&mut self, connection_type: ConnectionType) -> Result<(), ServiceError> {
+        self.user = connection_type == ConnectionType::Session;
+        Ok(())
+    }
+
+    /// rc-service is run for every call, there is no connection to lose.
+    fn connection_lost(&self) -> bool {
+        false
+    }
+
+    fn reconnect(&mut self) -> Result<(), ServiceError> {
+        Ok(())
+    }
+
+    fn worker(&self) -> Result<Box<dyn ServiceRepository>, ServiceError> {
+        Ok(Box::new(Self {
+            user: self.user,
+            limits: CallLimits::default(),
+        }))
+    }
+
+    fn set_call_limits(&mut self, limits: CallLimits) {
+        self.limits = limits;
+    }
+
+    fn list_services(&self) -> Result<Vec<Service>, ServiceError> {
+        let statuses = self.statuses()?;
+        let runlevels = self.runlevels()?;
+        let names = self.run("rc-service", &["--list"])?;
+
+        let services = names
+            .lines()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(|name| {
+                let status = statuses.get(name).map_or("stopped", String::as_str);
+                let active = match status {
+                    "started" => "active",
+                    "starting" | "stopping" => "activating",
+                    "crashed" | "failed" => "failed",
+                    _ => "inactive",
+                };
+                let enabled = runlevels.get(name).is_some_and(|levels| !levels.is_empty());
+                let state = ServiceState::new(
+                    "loaded".to_string(),
+                    active.to_string(),
+                    status.to_string(),
+                    if enabled { "enabled" } else { "disabled" }.to_string(),
+                    String::new(),
+                    String::new(),
+                );
+                Service::new(name.to_string(), self.description(name), state)
+            })
+            .collect();
+
+        Ok(services)
+    }
+
+    /// The listing call covers rc-service, rc-status and rc-update, the per-unit calls reading
+    /// the description of each init script.
+    fn time_listing(&self) -> Result<ListTimings, ServiceError> {
+        let started = Instant::now();
+        self.statuses()?;
+        self.runlevels()?;
+        let names = self.run("rc-service", &["--list"])?;
+        let list_call = started.elapsed();
+
+        let unit_calls = names
+            .lines()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(|name| {
+                let started = Instant::now();
+                self.description(name);
+                started.elapsed()
+            })
+            .collect();
+
+        Ok(ListTimings::new(list_call, unit_calls))
+    }
+
+    /// OpenRC has no journal, so this shows the syslog lines mentioning the service.
+    fn get_service_log(&self, name: &str, lines: u32) -> Result<String, ServiceError> {
+        let log = fs::read_to_string(SYSTEM_LOG).map_err(|e| format!("{}: {}", SYSTEM_LOG, e))?;
+        let matching: Vec<&str> = log.lines().filter(|line| line.contains(name)).collect();
+        let start = matching.len().saturating_sub(lines as usize);
+        Ok(matching[start..].join("\n"))
+    }
+
+    // The system log has no priorities, and no timestamps journalctl would parse.
+    fn get_journal_entries(&self, name: &str, lines: u32, filter: &LogFilter) -> Result<Vec<JournalEntry>, ServiceError> {
+        if filter.since.is_some() {
+            return Err(unsupported("Filtering logs by time"));
+        }
+        let log = self.get_service_log(name, lines)?;
+        Ok(log.lines().map(JournalEntry::raw).collect())
+    }
+
+    fn get_journal_boots(&self) -> Result<Vec<ListedBoot>, ServiceError> {
+        Err(unsupported("Reading the logs of previous boots"))
+    }
+
+    fn get_older_journal_entries(
+        &self,
+        _name: &str,
+        _cursor: &str,
+        _lines: u32,
+        _filter: &LogFilter,
+    ) -> Result<Vec<JournalEntry>, ServiceError> {
+        Err(unsupported("Paging back through the log"))
+    }
+
+    fn get_log_timestamps(&self, _name: &str) -> Result<Vec<u64>, ServiceError> {
+        Err(unsupported("The log rate"))
+    }
+
+    fn get_journal_units_since(&self, _minutes: u64) -> Result<Vec<String>, ServiceError> {
+        Err(unsupported("Ranking services by log volume"))
+    }
+
+    // rc-service waits for the service, so there is never a job left to track.
+    fn start_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        self.service_command(name, "start").map(|_| None)
+    }
+
+    fn start_transient_unit(&self, _name: &str, _argv: &[String], _limits: &ResourceLimits) -> Result<Option<String>, ServiceError> {
+        Err(unsupported("Running transient services"))
+    }
+
+    fn stop_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        self.service_command(name, "stop").map(|_| None)
+    }
+
+    fn restart_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        self.service_command(name, "restart").map(|_| None)
+    }
+
+    fn reload_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        self.service_command(name, "reload").map(|_| None)
+    }
+
+    fn reload_or_restart_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        let command = if self.has_reload(name)? { "reload" } else { "restart" };
+        self.service_command(name, command).map(|_| None)
+    }
+
+    // Runlevels are switched with openrc itself, never by isolating a service.
+    fn get_unit_capabilities(&self, name: &str) -> Result<UnitCapabilities, ServiceError> {
+        Ok(UnitCapabilities {
+            can_start: true,
+            can_stop: true,
+            can_reload: self.has_reload(name)?,
+            can_isolate: false,
+        })
+    }
+
+    /// Runlevels stand for targets, the current one active.
+    fn list_targets(&self) -> Result<Vec<Service>, ServiceError> {
+        let current = self.run("rc-status", &["--runlevel"])?.trim().to_string();
+        let runlevels = self.run("rc-status", &["--list"])?;
+        Ok(runlevels
+            .lines()
+            .map(str::trim)
+            .filter(|runlevel| !runlevel.is_empty())
+            .map(|runlevel| {
+                let active = if runlevel == current { "active" } else { "inactive" };
+                let state = ServiceState::new(
+                    "loaded".to_string(),
+                    active.to_string(),
+                    if runlevel == current { "started" } else { "stopped" }.to_string(),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                );
+                Service::new(runlevel.to_string(), String::new(), state)
+            })
+            .collect())
+    }
+
+    fn get_default_target(&self) -> Result<String, ServiceError> {
+        Ok("default".to_string())
+    }
+
+    // The runlevel booted into is set on the kernel command line (softlevel=).
+    fn set_default_target(&self, _name: &str) -> Result<(), ServiceError> {
+        Err(unsupported("Changing the default runlevel"))
+    }
+
+    fn isolate_target(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        self.run("openrc", &[name]).map(|_| None)
+    }
+
+    fn power(&self, action: PowerAction) -> Result<(), ServiceError> {
+        let program = match action {
+            PowerAction::Reboot => "reboot",
+            PowerAction::PowerOff => "poweroff",
+        };
+        let output = Command::new(program).output()?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(ServiceError::from_stderr(String::from_utf8_lossy(&output.stderr).to_string()))
+        }
+    }
+
+    fn watch_jobs(&mut self, _sender: Sender<JobResult>) -> Result<(), ServiceError> {
+        Ok(())
+    }
+
+    fn watch_units(&mut self, units: &[String], _sender: Sender<UnitTransition>) -> Result<(), ServiceError> {
+        if units.is_empty() {
+            return Ok(());
+        }
+        Err(unsupported("Watching units"))
+    }
+
+    // Runlevels are undone with rc-update itself, no links are reported.
+    fn enable_service(&self, name: &str) -> Result<Vec<UnitFileChange>, ServiceError> {
+        self.run("rc-update", &["add", name, "default"]).map(|_| Vec::new())
+    }
+
+    fn disable_service(&self, name: &str) -> Result<Vec<UnitFileChange>, ServiceError> {
+        self.run("rc-update", &["--all", "del", name]).map(|_| Vec::new())
+    }
+
+    fn revert_unit_file_changes(&self, _name: &str, _changes: &[UnitFileChange]) -> Result<(), ServiceError> {
+        Err(unsupported("Reverting unit file links"))
+    }
+
+    fn freeze_service(&self, _name: &str) -> Result<(), ServiceError> {
+        Err(unsupported("Freezing a service"))
+    }
+
+    fn thaw_service(&self, _name: &str) -> Result<(), ServiceError> {
+        Err(unsupported("Thawing a service"))
+    }
+
+    /// `zap` marks a crashed service as stopped, the closest thing to a failed state reset.
+    fn reset_failed_unit(&self, name: &str) -> Result<(), ServiceError> {
+        self.service_command(name, "zap").map(|_| ())
+    }
+
+    fn reset_failed(&self) -> Result<(), ServiceError> {
+        for service in self.list_services()? {
+            if service.state().active() == "failed" {
+                self.reset_failed_unit(service.name())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn kill_service(&self, _name: &str, _target: KillTarget, _signal: i32) -> Result<(), ServiceError> {
+        Err(unsupported("Sending signals to a service"))
+    }
+
+    fn reload_daemon(&self) -> Result<(), ServiceError> {
+        Ok(())
+    }
+
+    fn get_unit_property(&self, _name: &str) -> Result<UnitProperty, ServiceError> {
+        Err(unsupported("Reading service properties"))
+    }
+
+    fn get_restart_settings(&self, _name: &str) -> Result<RestartSettings, ServiceError> {
+        Err(unsupported("Reading restart settings"))
+    }
+
+    fn get_runtime_stats(&self, _name: &str) -> Result<RuntimeStats, ServiceError> {
+        Err(unsupported("Counting restarts"))
+    }
+
+    fn get_resource_usage(&self, _name: &str) -> Result<ResourceUsage, ServiceError> {
+        Err(unsupported("Sampling the resource usage"))
+    }
+
+    fn get_unit_processes(&self, _name: &str) -> Result<Vec<UnitProcess>, ServiceError> {
+        Err(unsupported("Listing the processes of a service"))
+    }
+
+    fn get_state_change_timestamp(&self, _name: &str) -> Result<u64, ServiceError> {
+        Err(unsupported("Reading when a service changed state"))
+    }
+
+    // Init scripts are read each time they run.
+    fn get_need_daemon_reload(&self, _name: &str) -> Result<bool, ServiceError> {
+        Ok(false)
+    }
+
+    fn get_freezer_state(&self, _name: &str) -> Result<String, ServiceError> {
+        Err(unsupported("Freezing a service"))
+    }
+
+    fn get_slices(&self) -> Result<Vec<Slice>, ServiceError> {
+        Err(unsupported("Browsing the slices"))
+    }
+
+    fn get_boot_time(&self) -> Result<String, ServiceError> {
+        Err(unsupported("Analyzing the boot"))
+    }
+
+    fn get_system_summary(&self) -> Result<SystemSummary, ServiceError> {
+        Err(unsupported("Summarizing the manager"))
+    }
+
+    fn get_boot_blame(&self) -> Result<Vec<UnitActivation>, ServiceError> {
+        Err(unsupported("Analyzing the boot"))
+    }
+
+    fn get_critical_chain(&self, _unit: Option<&str>) -> Result<Vec<ChainLink>, ServiceError> {
+        Err(unsupported("Analyzing the boot"))
+    }
+
+    fn get_security_scores(&self) -> Result<Vec<SecurityScore>, ServiceError> {
+        Err(unsupported("Analyzing the security of services"))
+    }
+
+    fn get_security_audit(&self, _name: &str) -> Result<SecurityAudit, ServiceError> {
+        Err(unsupported("Analyzing the security of services"))
+    }
+
+    /// Init scripts are read straight from disk, which is cheap enough not to be cached.
+    fn unit_generation(&self, _name: &str) -> Option<String> {
+        None
+    }
+
+    /// The init script and its conf.d settings, the closest thing to `systemctl cat`.
+    fn systemctl_cat(&self, name: &str) -> Result<String, ServiceError> {
+        let script = Path::new(INIT_DIR).join(name);
+        let mut content = format!("# {}\n{}", script.display(), fs::read_to_string(&script)?);
+        let conf = Path::new("/etc/conf.d").join(name);
+        if let Ok(settings) = fs::read_to_string(&conf) {
+            content.push_str(&format!("\n# {}\n{}", conf.display(), settings));
+        }
+        Ok(content)
+    }
+
+    fn get_unit_file_paths(&self, name: &str) -> Result<UnitFilePaths, ServiceError> {
+        // The settings of /etc/conf.d apply over the variables of the init script.
+        let conf = Path::new("/etc/conf.d").join(name);
+        Ok(UnitFilePaths {
+            fragment: Path::new(INIT_DIR).join(name).display().to_string(),
+            drop_ins: conf.exists().then(|| conf.display().to_string()).into_iter().collect(),
+        })
+    }
+
+    fn get_unit_links(&self, _name: &str) -> Result<UnitLinks, ServiceError> {
+        Err(unsupported("Aliases"))
+    }
+
+    fn get_loaded_settings(&self, _name: &str, _settings: &[String]) -> Result<HashMap<String, Vec<String>>, ServiceError> {
+        Err(unsupported("Comparing unit files with the loaded units"))
+    }
+
+    fn verify_unit(&self, _name: &str) -> Result<Vec<VerifyFinding>, ServiceError> {
+        Err(unsupported("Checking unit files"))
+    }
+
+    fn create_unit(&self, _name: &str, _content: &str) -> Result<String, ServiceError> {
+        Err(unsupported("Creating services"))
+    }
+
+    fn write_drop_in(&self, _name: &str, _file_name: &str, _content: &str) -> Result<String, ServiceError> {
+        Err(unsupported("Editing services"))
+    }
+
+    fn edit_unit_command(&self, _name: &str) -> Result<Command, ServiceError> {
+        Err(unsupported("Editing services"))
+    }
+
+    fn shell_command(&self, _name: &str, _directory: &str) -> Result<Command, ServiceError> {
+        Err(unsupported("Opening a shell for services"))
+    }
+
+    fn remove_drop_in(&self, _name: &str, _path: &str) -> Result<(), ServiceError> {
+        Err(unsupported("Editing services"))
+    }
+
+    fn get_unit_environment(&self, _name: &str) -> Result<UnitEnvironment, ServiceError> {
+        Err(unsupported("Reading the environment of services"))
+    }
+
+    fn get_resource_limits(&self, _name: &str) -> Result<ResourceLimits, ServiceError> {
+        Err(unsupported("Reading resource limits"))
+    }
+
+    fn set_resource_limits(&self, _name: &str, _limits: &ResourceLimits, _runtime: bool) -> Result<(), ServiceError> {
+        Err(unsupported("Setting resource limits"))
+    }
+
+    fn search_unit_files(&self, query: &str) -> Result<Vec<UnitFileMatch>, ServiceError> {
+        let output = Command::new("grep")
+            .args(["-rHnFiZ", "-e", query, "--", INIT_DIR, "/etc/conf.d"])
+            .output()?;
+        // grep exits with 1 when nothing matches, and with 2 when some file could not be read.
+        if output.status.code() == Some(2) && output.stdout.is_empty() {
+            return Err(String::from_utf8_lossy(&output.stderr).to_string().into());
+        }
+
+        let mut matches: Vec<UnitFileMatch> = parse_grep_matches(&String::from_utf8_lossy(&output.stdout))
+            .into_iter()
+            .filter_map(|(path, line_number, line)| {
+                let unit = Path::new(&path).file_name()?.to_str()?.to_string();
+                Some(UnitFileMatch { unit, path, line_number, line })
+            })
+            .collect();
+        matches.sort_by(|a, b| a.unit.cmp(&b.unit).then(a.path.cmp(&b.path)).then(a.line_number.cmp(&b.line_number)));
+        Ok(matches)
+    }
+
+    fn list_unit_paths(&self) -> Result<Vec<UnitPathReference>, ServiceError> {
+        Err(unsupported("Looking up the services managing a path"))
+    }
+
+    fn get_unit_dependencies(&self, name: &str) -> Result<UnitDependencies, ServiceError> {
+        let mut wants = self.dependency(name, "iuse")?;
+        wants.extend(self.dependency(name, "iwant")?);
+        Ok(UnitDependencies::new(
+            self.dependency(name, "ineed")?,
+            wants,
+            Vec::new(),
+            self.dependency(name, "iafter")?,
+            self.dependency(name, "ibefore")?,
+        ))
+    }
+
+    fn get_unit_timers(&self, _name: &str) -> Result<Vec<(String, TimerProperty)>, ServiceError> {
+        // OpenRC has no timers, services are scheduled by cron.
+        Ok(Vec::new())
+    }
+
+    fn get_unit_sockets(&self, _name: &str) -> Result<Vec<(String, SocketProperty)>, ServiceError> {
+        // OpenRC has no socket activation, inetd-style daemons are services of their own.
+        Ok(Vec::new())
+    }
+
+    fn get_unit_triggers(&self, _name: &str) -> Result<UnitTriggers, ServiceError> {
+        Ok(UnitTriggers::default())
+    }
+
+    fn get_unit_conditions(&self, _name: &str) -> Result<UnitConditions, ServiceError> {
+        // Init scripts check what they need in start_pre, failing rather than being skipped.
+        Ok(UnitConditions::default())
+    }
+
+    fn get_unit_history(&self, _name: &str) -> Result<UnitHistory, ServiceError> {
+        Err(unsupported("The start and stop history"))
+    }
+
+    fn get_oom_entries(&self, _name: &str) -> Result<Vec<JournalEntry>, ServiceError> {
+        Err(unsupported("The OOM killer messages"))
+    }
+
+    fn get_unit_documentation(&self, _name: &str) -> Result<Vec<String>, ServiceError> {
+        // Init scripts have no Documentation=.
+        Ok(Vec::new())
+    }
+
+    fn plan_action(&self, action: UnitAction, name: &str) -> Result<ActionPlan, ServiceError> {
+        let started: HashSet<String> = self
+            .statuses()?
+            .into_iter()
+            .filter(|(_, status)| status == "started")
+            .map(|(service, _)| service)
+            .collect();
+
+        // rc-service has no reload-or-restart, it is planned as the command it stands for.
+        let takes_down = match action {
+            UnitAction::Stop | UnitAction::Restart => true,
+            UnitAction::ReloadOrRestart => !self.has_reload(name)?,
+            _ => false,
+        };
+
+        let (program, arguments) = match action {
+            UnitAction::Start => ("rc-service", format!("{} start", name)),
+            UnitAction::Stop => ("rc-service", format!("{} stop", name)),
+            UnitAction::Restart => ("rc-service", format!("{} restart", name)),
+            UnitAction::Reload => ("rc-service", format!("{} reload", name)),
+            UnitAction::ReloadOrRestart => ("rc-service", format!("{} {}", name, if takes_down { "restart" } else { "reload" })),
+            UnitAction::Enable => ("rc-update", format!("add {} default", name)),
+            UnitAction::Disable => ("rc-update", format!("--all del {}", name)),
+            UnitAction::Freeze => return Err(unsupported("Freezing a service")),
+            UnitAction::Thaw => return Err(unsupported("Thawing a service")),
+        };
+
+        let mut side_effects = Vec::new();
+        match action {
+            UnitAction::Start => {
+                for dependency in self.dependency(name, "ineed")? {
+                    if !started.contains(&dependency) {
+                        side_effects.push(format!("will also start {}", dependency));
+                    }
+                }
+            }
+            UnitAction::Enable => side_effects.push("adds the service to the default runlevel".to_string()),
+            UnitAction::Disable => side_effects.push("removes the service from every runlevel".to_string()),
+            UnitAction::Reload => {
+                if !self.has_reload(name)? {
+                    side_effects.push("fails, the init script has no reload command".to_string());
+                }
+            }
+            UnitAction::Stop | UnitAction::Restart | UnitAction::ReloadOrRestart | UnitAction::Freeze | UnitAction::Thaw => {}
+        }
+        let propagated_units = if takes_down {
+            self.dependency(name, "needsme")?
+                .into_iter()
+                .filter(|dependent| started.contains(dependent))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(ActionPlan::new(
+            program.to_string(),
+            format!(" {}", arguments),
+            propagated_units,
+            side_effects,
+        ))
+    }
+
+    fn schedule_action(&self, _action: PolicyAction, _name: &str, _calendar: &str) -> Result<String, ServiceError> {
+        Err(unsupported("Scheduling actions"))
+    }
+
+    fn list_scheduled_actions(&self) -> Result<Vec<ScheduledAction>, ServiceError> {
+        Err(unsupported("Scheduling actions"))
+    }
+
+    fn cancel_scheduled_action(&self, _timer: &str) -> Result<(), ServiceError> {
+        Err(unsupported("Scheduling actions"))
+    }
+}
@@ -0,0 +1,32 @@
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::infrastructure::uptime_store::state_dir;
+
+const PINNED_FILE: &str = "pinned.json";
+
+fn pinned_path() -> Result<PathBuf, Box<dyn Error>> {
+    Ok(state_dir()
+        .ok_or("Neither XDG_STATE_HOME nor HOME is set, there is nowhere to keep the pinned units")?
+        .join(PINNED_FILE))
+}
+
+/// The pinned units, in the order they were pinned, empty when none was pinned yet.
+pub fn load_pinned() -> Result<Vec<String>, Box<dyn Error>> {
+    let path = pinned_path()?;
+    match fs::read_to_string(&path) {
+        Ok(content) => Ok(serde_json::from_str(&content).map_err(|e| format!("{}: {}", path.display(), e))?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(format!("{}: {}", path.display(), e).into()),
+    }
+}
+
+pub fn save_pinned(units: &[String]) -> Result<(), Box<dyn Error>> {
+    let path = pinned_path()?;
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory).map_err(|e| format!("{}: {}", directory.display(), e))?;
+    }
+    fs::write(&path, serde_json::to_string(units)?).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(())
+}
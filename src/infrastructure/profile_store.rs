@@ -0,0 +1,37 @@
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::infrastructure::uptime_store::state_dir;
+
+const PROFILE_FILE: &str = "profile";
+
+fn profile_path() -> Option<PathBuf> {
+    Some(state_dir()?.join(PROFILE_FILE))
+}
+
+/// The profile used last, `None` when it was the default layout or nothing was saved yet.
+pub fn load_last_profile() -> Option<String> {
+    let name = fs::read_to_string(profile_path()?).ok()?;
+    Some(name.trim().to_string()).filter(|name| !name.is_empty())
+}
+
+/// Remembers the profile for the next run, forgetting it for the default layout.
+pub fn save_last_profile(name: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let path = profile_path().ok_or("Neither XDG_STATE_HOME nor HOME is set, there is nowhere to keep the profile")?;
+    match name {
+        Some(name) => {
+            if let Some(directory) = path.parent() {
+                fs::create_dir_all(directory).map_err(|e| format!("{}: {}", directory.display(), e))?;
+            }
+            fs::write(&path, format!("{}\n", name)).map_err(|e| format!("{}: {}", path.display(), e))?;
+        }
+        None => match fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                return Err(format!("{}: {}", path.display(), e).into());
+            }
+            _ => {}
+        },
+    }
+    Ok(())
+}
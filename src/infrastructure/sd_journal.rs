@@ -0,0 +1,257 @@
+use libc::{c_char, c_int, c_void};
+use std::error::Error;
+use std::ffi::{CStr, CString};
+use std::io;
+use std::sync::OnceLock;
+
+use crate::domain::journal_entry::JournalEntry;
+use crate::infrastructure::call_limits::PendingCall;
+
+/// Only the journal files of this machine, as journalctl reads them without --merge.
+const SD_JOURNAL_LOCAL_ONLY: c_int = 1;
+
+type Open = unsafe extern "C" fn(*mut *mut c_void, c_int) -> c_int;
+type Close = unsafe extern "C" fn(*mut c_void);
+type AddMatch = unsafe extern "C" fn(*mut c_void, *const c_void, usize) -> c_int;
+type Combine = unsafe extern "C" fn(*mut c_void) -> c_int;
+type Seek = unsafe extern "C" fn(*mut c_void) -> c_int;
+type SeekRealtime = unsafe extern "C" fn(*mut c_void, u64) -> c_int;
+type SeekCursor = unsafe extern "C" fn(*mut c_void, *const c_char) -> c_int;
+type Step = unsafe extern "C" fn(*mut c_void) -> c_int;
+type GetData = unsafe extern "C" fn(*mut c_void, *const c_char, *mut *const c_void, *mut usize) -> c_int;
+type GetRealtime = unsafe extern "C" fn(*mut c_void, *mut u64) -> c_int;
+type GetCursor = unsafe extern "C" fn(*mut c_void, *mut *mut c_char) -> c_int;
+
+/// The sd-journal functions of libsystemd, loaded when the journal is first read so that the
+/// binary still starts where libsystemd is missing, e.g. on OpenRC.
+struct SdJournalApi {
+    open: Open,
+    close: Close,
+    add_match: AddMatch,
+    add_disjunction: Combine,
+    add_conjunction: Combine,
+    seek_tail: Seek,
+    seek_realtime_usec: SeekRealtime,
+    seek_cursor: SeekCursor,
+    next: Step,
+    previous: Step,
+    get_data: GetData,
+    get_realtime_usec: GetRealtime,
+    get_cursor: GetCursor,
+}
+
+fn api() -> Option<&'static SdJournalApi> {
+    static API: OnceLock<Option<SdJournalApi>> = OnceLock::new();
+    API.get_or_init(|| unsafe { load() }).as_ref()
+}
+
+/// # Safety
+/// Each symbol is cast to the signature sd-journal(3) documents for it.
+unsafe fn load() -> Option<SdJournalApi> {
+    // The library is never closed, the functions are used until the end of the process.
+    let library = unsafe { libc::dlopen(c"libsystemd.so.0".as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+    if library.is_null() {
+        return None;
+    }
+    let symbol = |name: &CStr| {
+        let symbol = unsafe { libc::dlsym(library, name.as_ptr()) };
+        (!symbol.is_null()).then_some(symbol)
+    };
+    unsafe {
+        Some(SdJournalApi {
+            open: std::mem::transmute::<*mut c_void, Open>(symbol(c"sd_journal_open")?),
+            close: std::mem::transmute::<*mut c_void, Close>(symbol(c"sd_journal_close")?),
+            add_match: std::mem::transmute::<*mut c_void, AddMatch>(symbol(c"sd_journal_add_match")?),
+            add_disjunction: std::mem::transmute::<*mut c_void, Combine>(symbol(c"sd_journal_add_disjunction")?),
+            add_conjunction: std::mem::transmute::<*mut c_void, Combine>(symbol(c"sd_journal_add_conjunction")?),
+            seek_tail: std::mem::transmute::<*mut c_void, Seek>(symbol(c"sd_journal_seek_tail")?),
+            seek_realtime_usec: std::mem::transmute::<*mut c_void, SeekRealtime>(symbol(c"sd_journal_seek_realtime_usec")?),
+            seek_cursor: std::mem::transmute::<*mut c_void, SeekCursor>(symbol(c"sd_journal_seek_cursor")?),
+            next: std::mem::transmute::<*mut c_void, Step>(symbol(c"sd_journal_next")?),
+            previous: std::mem::transmute::<*mut c_void, Step>(symbol(c"sd_journal_previous")?),
+            get_data: std::mem::transmute::<*mut c_void, GetData>(symbol(c"sd_journal_get_data")?),
+            get_realtime_usec: std::mem::transmute::<*mut c_void, GetRealtime>(symbol(c"sd_journal_get_realtime_usec")?),
+            get_cursor: std::mem::transmute::<*mut c_void, GetCursor>(symbol(c"sd_journal_get_cursor")?),
+        })
+    }
+}
+
+/// sd-journal returns negative errno values.
+fn check(result: c_int) -> Result<c_int, Box<dyn Error>> {
+    if result < 0 {
+        return Err(Box::new(io::Error::from_raw_os_error(-result)));
+    }
+    Ok(result)
+}
+
+/// The journal of this machine read in place through sd-journal, instead of through the output
+/// of journalctl.
+pub struct Journal {
+    api: &'static SdJournalApi,
+    handle: *mut c_void,
+    /// The read in progress, stepping through the entries failing once it times out or is cancelled.
+    pending: Option<PendingCall>,
+}
+
+impl Journal {
+    /// Fails when libsystemd cannot be loaded, the journal then being read with journalctl.
+    pub fn open() -> Result<Self, Box<dyn Error>> {
+        let api = api().ok_or("libsystemd.so.0 could not be loaded")?;
+        let mut handle = std::ptr::null_mut();
+        check(unsafe { (api.open)(&mut handle, SD_JOURNAL_LOCAL_ONLY) })?;
+        Ok(Self { api, handle, pending: None })
+    }
+
+    fn add_match(&mut self, field: &str, value: &str) -> Result<(), Box<dyn Error>> {
+        let term = format!("{}={}", field, value);
+        check(unsafe { (self.api.add_match)(self.handle, term.as_ptr().cast(), term.len()) })?;
+        Ok(())
+    }
+
+    /// What the unit logged and what the manager and systemd logged about it, like `journalctl -u`.
+    pub fn match_unit(&mut self, unit: &str) -> Result<(), Box<dyn Error>> {
+        self.add_match("_SYSTEMD_UNIT", unit)?;
+        check(unsafe { (self.api.add_disjunction)(self.handle) })?;
+        self.add_match("_PID", "1")?;
+        self.add_match("UNIT", unit)?;
+        check(unsafe { (self.api.add_disjunction)(self.handle) })?;
+        self.add_match("_UID", "0")?;
+        self.add_match("OBJECT_SYSTEMD_UNIT", unit)?;
+        check(unsafe { (self.api.add_conjunction)(self.handle) })?;
+        Ok(())
+    }
+
+    /// Entries logged during the boot of `boot_id`, like `journalctl -b`.
+    pub fn match_boot(&mut self, boot_id: &str) -> Result<(), Box<dyn Error>> {
+        self.add_match("_BOOT_ID", boot_id)?;
+        check(unsafe { (self.api.add_conjunction)(self.handle) })?;
+        Ok(())
+    }
+
+    /// The kernel messages, like `journalctl -k`.
+    pub fn match_kernel(&mut self) -> Result<(), Box<dyn Error>> {
+        self.add_match("_TRANSPORT", "kernel")?;
+        check(unsafe { (self.api.add_conjunction)(self.handle) })?;
+        Ok(())
+    }
+
+    /// Entries of `priority` or more severe, like `journalctl -p`.
+    pub fn match_priority(&mut self, priority: u8) -> Result<(), Box<dyn Error>> {
+        // Matches on the same field are alternatives.
+        for level in 0..=priority {
+            self.add_match("PRIORITY", &level.to_string())?;
+        }
+        check(unsafe { (self.api.add_conjunction)(self.handle) })?;
+        Ok(())
+    }
+
+    pub fn seek_tail(&mut self) -> Result<(), Box<dyn Error>> {
+        check(unsafe { (self.api.seek_tail)(self.handle) })?;
+        Ok(())
+    }
+
+    pub fn seek_realtime(&mut self, usec: u64) -> Result<(), Box<dyn Error>> {
+        check(unsafe { (self.api.seek_realtime_usec)(self.handle, usec) })?;
+        Ok(())
+    }
+
+    /// Positions the journal on the entry of `cursor`, read by the next step either way.
+    pub fn seek_cursor(&mut self, cursor: &str) -> Result<(), Box<dyn Error>> {
+        let cursor = CString::new(cursor)?;
+        check(unsafe { (self.api.seek_cursor)(self.handle, cursor.as_ptr()) })?;
+        Ok(())
+    }
+
+    /// Bounds the reading of the entries by the timeout and cancellation of `pending`.
+    pub fn limit(&mut self, pending: PendingCall) {
+        self.pending = Some(pending);
+    }
+
+    fn check_pending(&self) -> Result<(), Box<dyn Error>> {
+        if let Some(pending) = &self.pending {
+            pending.check()?;
+        }
+        Ok(())
+    }
+
+    /// Moves to the next entry, false at the end of the journal.
+    pub fn next(&mut self) -> Result<bool, Box<dyn Error>> {
+        self.check_pending()?;
+        Ok(check(unsafe { (self.api.next)(self.handle) })? > 0)
+    }
+
+    /// Moves to the previous entry, false at the start of the journal.
+    pub fn previous(&mut self) -> Result<bool, Box<dyn Error>> {
+        self.check_pending()?;
+        Ok(check(unsafe { (self.api.previous)(self.handle) })? > 0)
+    }
+
+    pub fn realtime_usec(&self) -> Option<u64> {
+        let mut usec = 0;
+        check(unsafe { (self.api.get_realtime_usec)(self.handle, &mut usec) }).ok()?;
+        Some(usec)
+    }
+
+    pub fn cursor(&self) -> Option<String> {
+        let mut cursor = std::ptr::null_mut();
+        check(unsafe { (self.api.get_cursor)(self.handle, &mut cursor) }).ok()?;
+        let value = unsafe { CStr::from_ptr(cursor) }.to_string_lossy().into_owned();
+        unsafe { libc::free(cursor.cast()) };
+        Some(value)
+    }
+
+    /// A field of the current entry, with `__REALTIME_TIMESTAMP` and `__CURSOR` as in the export
+    /// formats. Values that are not UTF-8 are read lossily.
+    pub fn field(&self, name: &str) -> Option<String> {
+        match name {
+            "__REALTIME_TIMESTAMP" => return self.realtime_usec().map(|usec| usec.to_string()),
+            "__CURSOR" => return self.cursor(),
+            _ => {}
+        }
+        let field = CString::new(name).ok()?;
+        let mut data = std::ptr::null();
+        let mut length = 0;
+        check(unsafe { (self.api.get_data)(self.handle, field.as_ptr(), &mut data, &mut length) }).ok()?;
+        // The data is "FIELD=value", valid until the journal moves.
+        let bytes = unsafe { std::slice::from_raw_parts(data.cast::<u8>(), length) };
+        let value = bytes.get(name.len() + 1..)?;
+        Some(String::from_utf8_lossy(value).into_owned())
+    }
+
+    pub fn entry(&self) -> JournalEntry {
+        JournalEntry::from_fields(|field| self.field(field))
+    }
+
+    /// Up to `count` entries going back from the current position, not older than `since_usec`,
+    /// oldest first as journalctl prints them.
+    pub fn entries_back(&mut self, count: u32, since_usec: Option<u64>) -> Result<Vec<JournalEntry>, Box<dyn Error>> {
+        self.entries_back_where(count, since_usec, |_| true)
+    }
+
+    /// Like `entries_back`, keeping only the entries `keep` accepts, like `journalctl --grep`.
+    pub fn entries_back_where(
+        &mut self,
+        count: u32,
+        since_usec: Option<u64>,
+        keep: impl Fn(&JournalEntry) -> bool,
+    ) -> Result<Vec<JournalEntry>, Box<dyn Error>> {
+        let mut entries = Vec::new();
+        while entries.len() < count as usize && self.previous()? {
+            if since_usec.is_some_and(|since| self.realtime_usec().is_some_and(|usec| usec < since)) {
+                break;
+            }
+            let entry = self.entry();
+            if keep(&entry) {
+                entries.push(entry);
+            }
+        }
+        entries.reverse();
+        Ok(entries)
+    }
+}
+
+impl Drop for Journal {
+    fn drop(&mut self) {
+        unsafe { (self.api.close)(self.handle) };
+    }
+}
@@ -0,0 +1,62 @@
+use chrono::Local;
+use std::error::Error;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::time::Instant;
+
+use crate::domain::session_recording::{RecordedEvent, RecordingHeader, RECORDING_VERSION};
+
+/// File a session of the TUI is recorded to with `--record`, see `Recording`. Each line is
+/// written as it happens, so that a crash keeps the keys and screens that led to it.
+pub struct SessionRecorder {
+    file: File,
+    started: Instant,
+    /// The last screen written, the same one drawn again being left out.
+    last_screen: String,
+}
+
+impl SessionRecorder {
+    /// Creates the file, replacing an older recording, and writes the header.
+    pub fn create(path: &Path, (width, height): (u16, u16)) -> Result<Self, Box<dyn Error>> {
+        let mut file = File::create(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let header = RecordingHeader {
+            version: RECORDING_VERSION,
+            width,
+            height,
+            timestamp: Local::now().timestamp(),
+        };
+        writeln!(file, "{}", serde_json::to_string(&header)?).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(Self {
+            file,
+            started: Instant::now(),
+            last_screen: String::new(),
+        })
+    }
+
+    pub fn key(&mut self, key: String) {
+        let time = self.elapsed();
+        self.write(&RecordedEvent::Key { time, key });
+    }
+
+    pub fn screen(&mut self, screen: String) {
+        if screen == self.last_screen {
+            return;
+        }
+        let time = self.elapsed();
+        self.write(&RecordedEvent::Screen { time, screen: screen.clone() });
+        self.last_screen = screen;
+    }
+
+    fn elapsed(&self) -> f64 {
+        // Milliseconds are enough to replay, and keep the lines short.
+        (self.started.elapsed().as_secs_f64() * 1000.0).round() / 1000.0
+    }
+
+    /// Failing to write loses the event only, like a line of the trace.
+    fn write(&mut self, event: &RecordedEvent) {
+        if let Ok(line) = serde_json::to_string(event) {
+            let _ = writeln!(self.file, "{}", line);
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::Column;
+use crate::infrastructure::uptime_store::state_dir;
+
+const SESSION_FILE: &str = "session.json";
+
+/// Where the TUI was when it was left, to start there again.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+    /// On the session services tab rather than the system one.
+    pub session_tab: bool,
+    pub filter: String,
+    pub sort: Option<Column>,
+    pub columns: Vec<Column>,
+    /// Name of the selected unit.
+    pub selected: Option<String>,
+    /// First row shown of the list.
+    pub offset: usize,
+    /// Title of the unit tab shown, e.g. "Logs", `None` for the list.
+    pub view: Option<String>,
+    /// Lines scrolled past in the details tab shown.
+    pub scroll: u16,
+}
+
+fn session_path() -> Option<PathBuf> {
+    Some(state_dir()?.join(SESSION_FILE))
+}
+
+/// The state saved when the TUI was last left, `None` when there is none or it cannot be read.
+pub fn load_session() -> Option<SessionState> {
+    let content = fs::read_to_string(session_path()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Writes the state through a temporary file, so that a crash never leaves half of it.
+pub fn save_session(state: &SessionState) -> Result<(), Box<dyn Error>> {
+    let path = session_path().ok_or("Neither XDG_STATE_HOME nor HOME is set, there is nowhere to keep the session")?;
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory).map_err(|e| format!("{}: {}", directory.display(), e))?;
+    }
+    let temporary = path.with_extension("json.tmp");
+    fs::write(&temporary, serde_json::to_string(state)?).map_err(|e| format!("{}: {}", temporary.display(), e))?;
+    fs::rename(&temporary, &path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(())
+}
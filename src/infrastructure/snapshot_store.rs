@@ -0,0 +1,70 @@
+use chrono::Local;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::domain::snapshot::Snapshot;
+use crate::infrastructure::uptime_store::state_dir;
+
+const SNAPSHOTS_DIR: &str = "snapshots";
+
+/// `$XDG_STATE_HOME/services-manager-tui/snapshots`, or `~/.local/state/...` when unset.
+pub fn snapshots_dir() -> Result<PathBuf, Box<dyn Error>> {
+    Ok(state_dir()
+        .ok_or("Neither XDG_STATE_HOME nor HOME is set, there is nowhere to keep snapshots")?
+        .join(SNAPSHOTS_DIR))
+}
+
+/// Start of the file names of the snapshots of a service manager: the system or user one of
+/// this machine ("local") or of an ssh destination.
+fn file_prefix(host: Option<&str>, user: bool) -> String {
+    let host: String = host
+        .unwrap_or("local")
+        .chars()
+        .map(|c| if c == '/' { '_' } else { c })
+        .collect();
+    format!("snapshot-{}{}-", host, if user { "-user" } else { "" })
+}
+
+/// Writes the snapshot to `directory` as snapshot-<host>[-user]-<timestamp>.json, returning its path.
+pub fn save_snapshot(
+    directory: &Path,
+    host: Option<&str>,
+    user: bool,
+    snapshot: &Snapshot,
+) -> Result<PathBuf, Box<dyn Error>> {
+    fs::create_dir_all(directory).map_err(|e| format!("{}: {}", directory.display(), e))?;
+    let path = directory.join(format!(
+        "{}{}.json",
+        file_prefix(host, user),
+        Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    fs::write(&path, serde_json::to_string_pretty(snapshot)? + "\n")
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+pub fn load_snapshot(path: &Path) -> Result<Snapshot, Box<dyn Error>> {
+    let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(serde_json::from_str(&content).map_err(|e| format!("{}: {}", path.display(), e))?)
+}
+
+/// The last snapshot of the service manager in `directory`, None when none was taken yet.
+pub fn latest_snapshot(directory: &Path, host: Option<&str>, user: bool) -> Result<Option<PathBuf>, Box<dyn Error>> {
+    let entries = match fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("{}: {}", directory.display(), e).into()),
+    };
+    let prefix = file_prefix(host, user);
+    // Timestamps in the names sort in the order the snapshots were taken.
+    Ok(entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".json"))
+        })
+        .max())
+}
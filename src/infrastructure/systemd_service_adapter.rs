@@ -1,12 +1,59 @@
-use zbus::blocking::{Connection, Proxy};
-use zbus::zvariant::OwnedObjectPath;
+use zbus::address::transport::{Transport, Unixexec};
+use zbus::blocking::{connection, proxy::Builder, Connection, MessageIterator, Proxy};
+use zbus::proxy::CacheProperties;
+use zbus::{message, Address, MatchRule};
+use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
 use zbus::Error;
-use std::process::Command;
-use std::io::{self, ErrorKind};
+use std::collections::{HashMap, HashSet};
+use std::process::{Command, Output, Stdio};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Instant;
+use crate::errors::ServiceError;
+use crate::domain::action_journal::UnitFileChange;
+use crate::domain::action_plan::{ActionPlan, UnitAction};
+use crate::domain::action_policy::PolicyAction;
+use crate::domain::boot_timing::{parse_blame, parse_critical_chain, ChainLink, UnitActivation};
+use crate::domain::security_audit::{parse_security_audit, parse_security_scores, SecurityAudit, SecurityScore};
+use crate::domain::slice::Slice;
+use crate::domain::crash_loop::RestartSettings;
+use crate::domain::job::JobResult;
+use crate::domain::journal_boot::{parse_boots, ListedBoot};
+use crate::domain::journal_entry::{JournalEntry, LogFilter, PRIORITY_DEBUG};
+use crate::domain::kill::KillTarget;
+use crate::domain::latency::ListTimings;
+use crate::domain::power_action::PowerAction;
+use crate::domain::process::UnitProcess;
+use crate::domain::resource_limits::{cgroup_section, ResourceLimits, UNLIMITED};
+use crate::domain::resource_usage::ResourceUsage;
+use crate::domain::runtime_stats::RuntimeStats;
+use crate::domain::system_summary::SystemSummary;
+use crate::domain::scheduled_action::{ScheduledAction, TIMER_PREFIX};
 use crate::domain::service::Service;
 use crate::domain::service_property::{ServiceProperty, SASBTTUII};
+use crate::domain::service_security::ServiceSecurity;
 use crate::domain::service_repository::ServiceRepository;
 use crate::domain::service_state::ServiceState;
+use crate::domain::unit_alias::{fold_aliases, UnitLinks};
+use crate::domain::unit_verification::{parse_verify, VerifyFinding};
+use crate::domain::unit_capabilities::UnitCapabilities;
+use crate::domain::unit_conditions::{UnitCondition, UnitConditions};
+use crate::domain::unit_dependencies::UnitDependencies;
+use crate::domain::unit_environment::{exec_section, UnitEnvironment};
+use crate::domain::unit_file::{parse_grep_matches, UnitFileMatch, UnitFilePaths};
+use crate::domain::unit_file_drift::parse_show;
+use crate::domain::unit_history::{UnitHistory, HISTORY_DAYS};
+use crate::domain::humanize::now_usec;
+use crate::infrastructure::call_limits::CallLimits;
+use crate::infrastructure::sd_journal::Journal;
+use crate::domain::unit_path::{PathRelation, UnitPathReference};
+use crate::domain::unit_transition::{is_settled, UnitTransition};
+use crate::domain::unit_property::{MountProperty, SocketProperty, TimerProperty, UnitProperty};
+use crate::domain::unit_triggers::UnitTriggers;
 
 /// Represents a systemd unit as returned by the D-Bus ListUnits method.
 /// Each tuple element corresponds to a specific property of the unit:
@@ -34,29 +81,717 @@ type SystemdUnit = (
     OwnedObjectPath,
 );
 
+/// The object path systemd gives the unit `name`, its name escaped like sd_bus_path_encode does:
+/// every byte but ASCII letters and digits, and a leading digit, as "_" and two hex digits.
+fn unit_object_path(name: &str) -> String {
+    let mut path = String::from("/org/freedesktop/systemd1/unit/");
+    if name.is_empty() {
+        path.push('_');
+    }
+    for (index, byte) in name.bytes().enumerate() {
+        if byte.is_ascii_alphabetic() || (byte.is_ascii_digit() && index > 0) {
+            path.push(byte as char);
+        } else {
+            path.push_str(&format!("_{:02x}", byte));
+        }
+    }
+    path
+}
+
+/// Writes stdin to the unit file "$2" in the directory "$1", refusing to replace an existing unit.
+const WRITE_UNIT_SCRIPT: &str =
+    r#"if [ -e "$2" ]; then echo "$2 already exists" >&2; exit 1; fi; mkdir -p "$1" && cat > "$2""#;
+/// Same, replacing the file, for drop-ins.
+const WRITE_DROP_IN_SCRIPT: &str = r#"mkdir -p "$1" && cat > "$2""#;
+/// Removes the drop-in "$1", then its directory if nothing else is left in it.
+const REMOVE_DROP_IN_SCRIPT: &str = r#"rm -f -- "$1" && { rmdir -- "$(dirname -- "$1")" 2>/dev/null || true; }"#;
+/// Undoes "symlink:<path>" and "unlink:<path>" changes, links pointing to the unit file "$1".
+const REVERT_LINKS_SCRIPT: &str = r#"target="$1"; shift
+for change in "$@"; do
+    path="${change#*:}"
+    case "$change" in
+        symlink:*) rm -f -- "$path" || exit 1 ;;
+        unlink:*) mkdir -p -- "$(dirname -- "$path")" && ln -sf -- "$target" "$path" || exit 1 ;;
+    esac
+done"#;
+
+/// Load state of the installed units ListUnits does not return, as systemd has not loaded them.
+const NOT_LOADED: &str = "not-loaded";
+
+/// Unit file states and vendor presets, which only change along with the unit files on disk.
+#[derive(Default)]
+struct UnitFileCache {
+    /// Unit file state by unit name, from a single ListUnitFiles call, `None` until the next one.
+    states: Option<HashMap<String, String>>,
+    /// Vendor preset by unit name, fetched the first time the unit is listed.
+    presets: HashMap<String, String>,
+    /// NeedDaemonReload by unit name, read for the rows shown with `get_need_daemon_reload`
+    /// rather than for every unit listed. Also cleared when the manager reloads.
+    need_daemon_reload: HashMap<String, bool>,
+}
+
+/// D-Bus interface of the properties of a unit file section, e.g. "Service".
+fn section_interface(section: &str) -> Option<&'static str> {
+    match section {
+        "Service" => Some("org.freedesktop.systemd1.Service"),
+        "Socket" => Some("org.freedesktop.systemd1.Socket"),
+        "Mount" => Some("org.freedesktop.systemd1.Mount"),
+        "Swap" => Some("org.freedesktop.systemd1.Swap"),
+        "Slice" => Some("org.freedesktop.systemd1.Slice"),
+        "Scope" => Some("org.freedesktop.systemd1.Scope"),
+        _ => None,
+    }
+}
+
+/// The (type, file name, destination) changes of EnableUnitFiles and DisableUnitFiles.
+fn unit_file_changes(changes: Vec<(String, String, String)>) -> Vec<UnitFileChange> {
+    changes
+        .into_iter()
+        .map(|(kind, path, source)| UnitFileChange { kind, path, source })
+        .collect()
+}
+
+/// The D-Bus properties setting the limits, for StartTransientUnit and SetUnitProperties.
+fn limit_properties(limits: &ResourceLimits) -> Vec<(&'static str, Value<'static>)> {
+    let mut properties = Vec::new();
+    if let Some(percent) = limits.cpu_quota_percent {
+        // Of CPU time per second, infinity staying infinity.
+        properties.push(("CPUQuotaPerSecUSec", Value::from(percent.saturating_mul(10_000))));
+    }
+    if let Some(bytes) = limits.memory_max_bytes {
+        properties.push(("MemoryMax", Value::from(bytes)));
+    }
+    if let Some(tasks) = limits.tasks_max {
+        properties.push(("TasksMax", Value::from(tasks)));
+    }
+    properties
+}
+
+/// Calls Subscribe, which systemd refuses for clients that already subscribed.
+fn subscribe(proxy: &Proxy) -> Result<(), Error> {
+    match proxy.call::<_, _, ()>("Subscribe", &()) {
+        Err(Error::MethodError(name, ..)) if name.as_str() == "org.freedesktop.systemd1.AlreadySubscribed" => Ok(()),
+        result => result,
+    }
+}
+
+/// Fields of /proc/<pid>/stat used by the process tree.
+struct ProcStat {
+    pid: u32,
+    ppid: u32,
+    /// utime + stime, in clock ticks.
+    cpu_ticks: u64,
+    rss_pages: u64,
+}
+
+/// Parses a /proc/<pid>/stat line, "pid (comm) state ppid ...": the command name may contain
+/// spaces and parentheses, so fields are counted from the last ')'.
+fn parse_proc_stat(line: &str) -> Option<ProcStat> {
+    let (pid, rest) = line.split_once(" (")?;
+    let (_, fields) = rest.rsplit_once(") ")?;
+    // Fields from the state on, i.e. field 3 of proc(5) is fields[0].
+    let fields: Vec<&str> = fields.split_whitespace().collect();
+    let field = |number: usize| -> Option<u64> { fields.get(number - 3)?.parse().ok() };
+    Some(ProcStat {
+        pid: pid.trim().parse().ok()?,
+        ppid: field(4)? as u32,
+        cpu_ticks: field(14)? + field(15)?,
+        rss_pages: field(24)?,
+    })
+}
+
+/// The properties of one interface of a unit, read at once with GetAll instead of one
+/// round trip each.
+struct Properties(HashMap<String, OwnedValue>);
+
+impl Properties {
+    fn take<T>(&mut self, name: &str) -> Result<T, ServiceError>
+    where
+        T: TryFrom<OwnedValue>,
+        T::Error: Into<Error>,
+    {
+        let value = self
+            .0
+            .remove(name)
+            .ok_or_else(|| ServiceError::Parse(format!("The unit has no {} property", name)))?;
+        T::try_from(value).map_err(|e| ServiceError::from(e.into()))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
 pub enum ConnectionType {
     Session,
     System
 }
 
 pub struct SystemdServiceAdapter {
-    connection: Connection
+    connection: Connection,
+    connection_type: ConnectionType,
+    unit_types: Vec<String>,
+    /// ssh destination of a remote host, `None` for the local machine.
+    host: Option<String>,
+    /// Where finished jobs are reported, see `watch_jobs`.
+    job_sender: Option<Sender<JobResult>>,
+    /// Cleared to stop the thread forwarding the JobRemoved signals of the current connection.
+    job_watch: Arc<AtomicBool>,
+    /// Where the state changes of `watched_units` are reported, see `watch_units`.
+    transition_sender: Option<Sender<UnitTransition>>,
+    watched_units: Vec<String>,
+    /// Cleared to stop the thread forwarding the PropertiesChanged signals of the watched units.
+    unit_watch: Arc<AtomicBool>,
+    unit_files: Arc<Mutex<UnitFileCache>>,
+    /// Set while the UnitFilesChanged signals of the current connection clear `unit_files`,
+    /// without which the unit files are listed again on every refresh.
+    unit_files_watch: Arc<AtomicBool>,
+    /// Freezer states other than "running" last set or read with `get_freezer_state`, which the
+    /// listing returns instead of asking every running unit for its own; the list reads it again
+    /// for the rows it shows.
+    freezer_states: Arc<Mutex<HashMap<String, String>>>,
+    /// Set when the bus closed the current connection, e.g. after a restart of dbus, until
+    /// `reconnect` opens another one.
+    connection_lost: Arc<AtomicBool>,
+    /// Timeout and cancellation of listing the units and reading the journal.
+    limits: CallLimits,
 }
 
 impl SystemdServiceAdapter {
-    pub fn new(connection_type: ConnectionType) -> Result<Self, Error> {
-        let connection = 
-            match connection_type {
-                ConnectionType::Session => Connection::session()?,
-                ConnectionType::System => Connection::system()?
+    pub fn new(connection_type: ConnectionType, unit_types: Vec<String>) -> Result<Self, Error> {
+        let connection = Self::connect(None, connection_type)?;
+        let mut adapter = Self::with_connection(connection, connection_type, unit_types, None);
+        adapter.spawn_unit_files_watch();
+        Ok(adapter)
+    }
+
+    /// Manages the units of another machine through `ssh <host> systemd-stdio-bridge`, like `systemctl -H`.
+    pub fn remote(host: String, connection_type: ConnectionType, unit_types: Vec<String>) -> Result<Self, Error> {
+        let connection = Self::connect(Some(&host), connection_type)?;
+        let mut adapter = Self::with_connection(connection, connection_type, unit_types, Some(host));
+        adapter.spawn_unit_files_watch();
+        Ok(adapter)
+    }
+
+    fn with_connection(
+        connection: Connection,
+        connection_type: ConnectionType,
+        unit_types: Vec<String>,
+        host: Option<String>,
+    ) -> Self {
+        Self {
+            connection,
+            connection_type,
+            unit_types,
+            host,
+            job_sender: None,
+            job_watch: Arc::new(AtomicBool::new(false)),
+            transition_sender: None,
+            watched_units: Vec::new(),
+            unit_watch: Arc::new(AtomicBool::new(false)),
+            unit_files: Arc::new(Mutex::new(UnitFileCache::default())),
+            unit_files_watch: Arc::new(AtomicBool::new(false)),
+            freezer_states: Arc::new(Mutex::new(HashMap::new())),
+            connection_lost: Arc::new(AtomicBool::new(false)),
+            limits: CallLimits::default(),
+        }
+    }
+
+    /// Clears the cached unit file states and presets whenever systemd reports a change of the
+    /// unit files (enable, disable, daemon reload...), replacing the previous watcher.
+    fn spawn_unit_files_watch(&mut self) {
+        self.unit_files_watch.store(false, Ordering::Relaxed);
+        self.invalidate_unit_files();
+        let running = Arc::new(AtomicBool::new(false));
+        self.unit_files_watch = running.clone();
+
+        let Ok(proxy) = self.manager_proxy() else {
+            return;
+        };
+        let Ok(signals) = proxy.receive_signal("UnitFilesChanged") else {
+            return;
+        };
+        let Ok(reloads) = proxy.receive_signal("Reloading") else {
+            return;
+        };
+        if subscribe(&proxy).is_err() {
+            return;
+        }
+        running.store(true, Ordering::Relaxed);
+        let unit_files = self.unit_files.clone();
+        let connection_lost = self.connection_lost.clone();
+        // A reload, whoever asked for it, loads the unit files changed on disk.
+        {
+            let running = running.clone();
+            let unit_files = unit_files.clone();
+            thread::spawn(move || {
+                for _message in reloads {
+                    if !running.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    unit_files.lock().unwrap().need_daemon_reload.clear();
+                }
+            });
+        }
+        thread::spawn(move || {
+            for _message in signals {
+                if !running.load(Ordering::Relaxed) {
+                    return;
+                }
+                *unit_files.lock().unwrap() = UnitFileCache::default();
+            }
+            // The connection is gone, the states can no longer be trusted and every call fails
+            // until another connection is opened.
+            if running.swap(false, Ordering::Relaxed) {
+                connection_lost.store(true, Ordering::Relaxed);
+            }
+        });
+    }
+
+    fn invalidate_unit_files(&self) {
+        *self.unit_files.lock().unwrap() = UnitFileCache::default();
+    }
+
+    /// Unit file state of every unit with a unit file, by unit name.
+    fn list_unit_files(&self, proxy: &Proxy<'static>) -> Result<HashMap<String, String>, ServiceError> {
+        let proxy = proxy.clone();
+        let files: Vec<(String, String)> =
+            self.limits.run("Listing the unit files", move || Ok(proxy.call("ListUnitFiles", &())?))?;
+        Ok(files
+            .into_iter()
+            .filter_map(|(path, state)| {
+                let name = Path::new(&path).file_name()?.to_str()?.to_string();
+                Some((name, state))
+            })
+            .collect())
+    }
+
+    /// Forwards the JobRemoved signals of the current connection, replacing the previous watcher.
+    fn spawn_job_watch(&mut self) -> Result<(), ServiceError> {
+        let Some(sender) = self.job_sender.clone() else {
+            return Ok(());
+        };
+        self.job_watch.store(false, Ordering::Relaxed);
+        let running = Arc::new(AtomicBool::new(true));
+        self.job_watch = running.clone();
+
+        let proxy = self.manager_proxy()?;
+        let signals = proxy.receive_signal("JobRemoved")?;
+        // systemd only emits JobRemoved to clients that subscribed.
+        subscribe(&proxy)?;
+        thread::spawn(move || {
+            for message in signals {
+                if !running.load(Ordering::Relaxed) {
+                    break;
+                }
+                let Ok((_id, job, _unit, result)) =
+                    message.body().deserialize::<(u32, OwnedObjectPath, String, String)>()
+                else {
+                    continue;
+                };
+                if sender.send(JobResult::new(job.to_string(), result)).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(())
+    }
+
+    /// Forwards the active state changes of the watched units, replacing the previous watcher.
+    /// Only the states a unit settles in are reported, from the last one seen.
+    fn spawn_unit_watch(&mut self) -> Result<(), ServiceError> {
+        self.unit_watch.store(false, Ordering::Relaxed);
+        let Some(sender) = self.transition_sender.clone() else {
+            return Ok(());
+        };
+        if self.watched_units.is_empty() {
+            return Ok(());
+        }
+        let running = Arc::new(AtomicBool::new(true));
+        self.unit_watch = running.clone();
+
+        let proxy = self.manager_proxy()?;
+        // Object path of each watched unit, with its name and last active state.
+        let mut units: HashMap<String, (String, String)> = HashMap::new();
+        for name in &self.watched_units {
+            // LoadUnit resolves aliases to the path of the unit, the one its signals come from.
+            let unit_path: OwnedObjectPath = proxy.call("LoadUnit", &(name.as_str()))?;
+            let unit = self.unit_proxy(&unit_path, "org.freedesktop.systemd1.Unit")?;
+            let state: String = unit.get_property("ActiveState")?;
+            units.insert(unit_path.to_string(), (name.clone(), state));
+        }
+        let rule = MatchRule::builder()
+            .msg_type(message::Type::Signal)
+            .sender("org.freedesktop.systemd1")?
+            .interface("org.freedesktop.DBus.Properties")?
+            .member("PropertiesChanged")?
+            .path_namespace("/org/freedesktop/systemd1/unit")?
+            .build();
+        let messages = MessageIterator::for_match_rule(rule, &self.connection, None)?;
+        // systemd only emits the PropertiesChanged of units to clients that subscribed.
+        subscribe(&proxy)?;
+        thread::spawn(move || {
+            for message in messages {
+                if !running.load(Ordering::Relaxed) {
+                    break;
+                }
+                let Ok(message) = message else {
+                    continue;
+                };
+                let header = message.header();
+                let Some((name, state)) = header.path().and_then(|path| units.get_mut(path.as_str())) else {
+                    continue;
+                };
+                let Ok((interface, changed, _invalidated)) =
+                    message.body().deserialize::<(String, HashMap<String, OwnedValue>, Vec<String>)>()
+                else {
+                    continue;
+                };
+                let text = |property: &str| changed.get(property).and_then(|value| <&str>::try_from(&**value).ok());
+                let Some(active) = text("ActiveState").filter(|_| interface == "org.freedesktop.systemd1.Unit") else {
+                    continue;
+                };
+                if active == state.as_str() || !is_settled(active) {
+                    continue;
+                }
+                let from = std::mem::replace(state, active.to_string());
+                let sub_state = text("SubState").unwrap_or_default().to_string();
+                if sender.send(UnitTransition::new(name.clone(), from, active.to_string(), sub_state)).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(())
+    }
+
+    fn connect(host: Option<&str>, connection_type: ConnectionType) -> Result<Connection, Error> {
+        let Some(host) = host else {
+            return match connection_type {
+                ConnectionType::Session => Connection::session(),
+                ConnectionType::System => Connection::system()
             };
+        };
+
+        let mut args = vec!["-xT".into(), host.into(), "--".into(), "systemd-stdio-bridge".into()];
+        if connection_type == ConnectionType::Session {
+            args.push("--user".into());
+        }
+        let transport = Transport::Unixexec(Unixexec::new("ssh".into(), None, args));
+        connection::Builder::address(Address::from(transport))?.build()
+    }
+
+    /// `program` with its arguments, run through ssh when the adapter manages a remote host.
+    fn command(&self, program: &str, args: &[&str]) -> Command {
+        match &self.host {
+            None => {
+                let mut command = Command::new(program);
+                command.args(args);
+                command
+            }
+            Some(host) => {
+                // ssh hands the command line to the remote shell, so every argument is quoted.
+                let remote_command = std::iter::once(program)
+                    .chain(args.iter().copied())
+                    .map(|arg| format!("'{}'", arg.replace('\'', "'\\''")))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let mut command = Command::new("ssh");
+                command.args(["-xT", host, "--", &remote_command]);
+                command
+            }
+        }
+    }
+
+    /// The journal read in place through sd-journal on this machine, `None` on remote hosts or
+    /// without libsystemd, journalctl being run then.
+    fn local_journal(&self) -> Option<Journal> {
+        if self.host.is_some() {
+            return None;
+        }
+        let mut journal = Journal::open().ok()?;
+        journal.limit(self.limits.begin("Reading the journal"));
+        Some(journal)
+    }
+
+    /// Output of journalctl, killed when it takes longer than the call timeout or is cancelled.
+    fn journalctl(&self, args: &[&str]) -> Result<Output, ServiceError> {
+        self.limits.output("Reading the journal", &mut self.command("journalctl", args))
+    }
+
+    /// The local journal narrowed to the entries of the log view of the unit.
+    fn filtered_journal(&self, name: &str, filter: &LogFilter) -> Result<Option<Journal>, ServiceError> {
+        let Some(mut journal) = self.local_journal() else {
+            return Ok(None);
+        };
+        journal.match_unit(name)?;
+        if filter.min_priority < PRIORITY_DEBUG {
+            journal.match_priority(filter.min_priority)?;
+        }
+        if let Some(boot) = filter.boot {
+            journal.match_boot(&boot.id_hex())?;
+        }
+        Ok(Some(journal))
+    }
+
+    /// The entries of the log view from `journalctl -o json`, the last ones or those before
+    /// `cursor`, oldest first.
+    fn journalctl_entries(
+        &self,
+        name: &str,
+        lines: u32,
+        filter: &LogFilter,
+        cursor: Option<&str>,
+    ) -> Result<Vec<JournalEntry>, ServiceError> {
+        let lines = lines.to_string();
+        let priority = filter.min_priority.to_string();
+        let after_cursor = cursor.map(|cursor| format!("--after-cursor={}", cursor));
+        let mut args = vec![
+            "-u", name, "-n", &lines, "-o", "json", "--no-pager",
+            "--output-fields=PRIORITY,_PID,SYSLOG_IDENTIFIER,MESSAGE",
+        ];
+        if filter.min_priority < PRIORITY_DEBUG {
+            args.extend(["-p", &priority]);
+        }
+        if let Some(since) = filter.since {
+            args.extend(["--since", since]);
+        }
+        let boot = filter.boot.map(|boot| boot.id_hex());
+        if let Some(boot) = &boot {
+            args.extend(["-b", boot]);
+        }
+        // Reversed, --after-cursor goes back from the entry before the cursor.
+        if let Some(after_cursor) = &after_cursor {
+            args.extend(["-r", after_cursor]);
+        }
+        let output = self.journalctl(&args)?;
+
+        if !output.status.success() {
+            let err_msg = String::from_utf8_lossy(&output.stderr).to_string();
+            return Err(ServiceError::from_stderr(err_msg));
+        }
+
+        let mut entries: Vec<JournalEntry> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(JournalEntry::from_json)
+            .collect();
+        if cursor.is_some() {
+            entries.reverse();
+        }
+        Ok(entries)
+    }
+
+    /// `get_oom_entries` from the local journal, matching the messages like the patterns given
+    /// to `journalctl --grep`.
+    fn local_oom_entries(&self, name: &str) -> Result<Vec<JournalEntry>, ServiceError> {
+        let since = now_usec().saturating_sub(HISTORY_DAYS * 86_400_000_000);
+        let mut journal = Journal::open()?;
+        journal.match_unit(name)?;
+        journal.seek_tail()?;
+        let mut entries = journal.entries_back_where(20, Some(since), |entry| {
+            let message = entry.message().to_lowercase();
+            message.contains("oom") || message.contains("out of memory")
+        })?;
+
+        let mut journal = Journal::open()?;
+        journal.match_kernel()?;
+        journal.seek_tail()?;
+        let control_group = format!("/{}", name.to_lowercase());
+        entries.extend(journal.entries_back_where(20, Some(since), |entry| {
+            let message = entry.message().to_lowercase();
+            message
+                .find("oom")
+                .is_some_and(|start| message[start..].contains(&control_group))
+        })?);
+        entries.sort_by_key(|entry| entry.timestamp());
+        Ok(entries)
+    }
+
+    /// Like `command`, with a terminal for the program on remote hosts too, e.g. for an editor.
+    fn interactive_command(&self, program: &str, args: &[&str]) -> Command {
+        let command = self.command(program, args);
+        if self.host.is_none() {
+            return command;
+        }
+        // Same ssh command line, with -t in place of the -xT `command` starts with.
+        let mut interactive = Command::new("ssh");
+        interactive.arg("-xt").args(command.get_args().skip(1));
+        interactive
+    }
+
+    /// Output of `systemd-analyze` for the manager of the connection.
+    fn systemd_analyze(&self, args: &[&str]) -> Result<String, ServiceError> {
+        let mut args = args.to_vec();
+        if self.connection_type == ConnectionType::Session {
+            args.push("--user");
+        }
+        args.push("--no-pager");
+        let output = self.command("systemd-analyze", &args).output()?;
+
+        if output.status.success() {
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        } else {
+            let err_msg = String::from_utf8_lossy(&output.stderr).to_string();
+            Err(ServiceError::from_stderr(err_msg))
+        }
+    }
+
+    fn is_listed_unit(&self, name: &str) -> bool {
+        self.unit_types.iter().any(|unit_type| {
+            name.rsplit_once('.')
+                .is_some_and(|(_, suffix)| suffix == unit_type)
+        })
+    }
+
+
+
+    /// Where the manager looks for administrator units, relative to the home directory over ssh.
+    fn unit_directory(&self) -> Result<String, ServiceError> {
+        Ok(match (self.connection_type, &self.host) {
+            (ConnectionType::System, _) => "/etc/systemd/system".to_string(),
+            // ssh starts the remote command in the home directory.
+            (ConnectionType::Session, Some(_)) => ".config/systemd/user".to_string(),
+            (ConnectionType::Session, None) => std::env::var_os("XDG_CONFIG_HOME")
+                .filter(|dir| !dir.is_empty())
+                .map(PathBuf::from)
+                .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+                .ok_or("Cannot find the user unit directory, HOME is not set")?
+                .join("systemd/user")
+                .display()
+                .to_string(),
+        })
+    }
+
+    /// Runs one of the write scripts, creating `directory` and writing `content` to `path`.
+    /// Runs `sh` with the arguments, failing with its error output.
+    fn run_script(&self, args: &[&str]) -> Result<(), ServiceError> {
+        let output = self.command("sh", args).stdin(Stdio::null()).output()?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(ServiceError::from_stderr(String::from_utf8_lossy(&output.stderr).trim().to_string()))
+        }
+    }
+
+    fn write_file(&self, script: &str, directory: &str, path: &str, content: &str) -> Result<(), ServiceError> {
+        let mut child = self
+            .command("sh", &["-c", script, "sh", directory, path])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(content.as_bytes())?;
+        }
+        let output = child.wait_with_output()?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(ServiceError::from_stderr(String::from_utf8_lossy(&output.stderr).trim().to_string()))
+        }
+    }
+
+    fn unit_proxy(&self, path: &OwnedObjectPath, interface: &'static str) -> Result<Proxy<'static>, Error> {
+        Builder::new(&self.connection)
+            .destination("org.freedesktop.systemd1")?
+            .path(path.clone())?
+            .interface(interface)?
+            .cache_properties(CacheProperties::No)
+            .build()
+    }
+
+    /// The object path of a unit, without asking the manager: systemd loads a unit that is not
+    /// loaded yet, e.g. inactive, when its path is used, which GetUnit would refuse.
+    fn unit_path(&self, name: &str) -> Result<OwnedObjectPath, ServiceError> {
+        Ok(OwnedObjectPath::try_from(unit_object_path(name)).map_err(Error::from)?)
+    }
 
-        Ok(Self {connection})
+    /// The given units that are currently active, or in the middle of a state change.
+    fn active_units(&self, names: Vec<String>) -> Result<Vec<String>, ServiceError> {
+        let mut active = Vec::new();
+        for name in names {
+            let unit_path = self.unit_path(&name)?;
+            let unit = self.unit_proxy(&unit_path, "org.freedesktop.systemd1.Unit")?;
+            let state: String = unit.get_property("ActiveState")?;
+            if matches!(state.as_str(), "active" | "activating" | "reloading") {
+                active.push(name);
+            }
+        }
+        Ok(active)
+    }
+
+    /// Unit file states of the listed units, from the cache while the unit files are watched.
+    fn unit_file_states(&self, proxy: &Proxy<'static>) -> Result<HashMap<String, String>, ServiceError> {
+        let watched = self.unit_files_watch.load(Ordering::Relaxed);
+        if watched && let Some(states) = &self.unit_files.lock().unwrap().states {
+            return Ok(states.clone());
+        }
+        let states = self.list_unit_files(proxy)?;
+        if watched {
+            self.unit_files.lock().unwrap().states = Some(states.clone());
+        }
+        Ok(states)
     }
 
+    /// Unit file state ("enabled", "static"...), vendor preset, freezer state and whether the unit
+    /// files changed since the unit was loaded, of a listed unit. Only units missing from `states`
+    /// (e.g. instances of a template) cost a GetUnitFileState call, and the freezer state of
+    /// units with running processes is the one last known, see `freezer_states`, like whether
+    /// their unit files changed on disk.
+    fn unit_file_state(
+        &self,
+        proxy: &Proxy,
+        states: &HashMap<String, String>,
+        name: &str,
+        active_state: &str,
+        object_path: &OwnedObjectPath,
+    ) -> (String, String, String, bool) {
+        let state = states.get(name).cloned().unwrap_or_else(|| {
+            proxy
+                .call("GetUnitFileState", &name)
+                .unwrap_or_else(|_| "unknown".into())
+        });
 
+        let unit = self.unit_proxy(object_path, "org.freedesktop.systemd1.Unit");
+        let property = |property: &str| -> String {
+            unit.as_ref()
+                .ok()
+                .and_then(|unit| unit.get_property(property).ok())
+                .unwrap_or_default()
+        };
 
-    fn manager_proxy(&self) -> Result<Proxy<'static>, Box<dyn std::error::Error>> {
+        let cached_preset = self.unit_files.lock().unwrap().presets.get(name).cloned();
+        let preset = cached_preset.unwrap_or_else(|| {
+            let preset = property("UnitFilePreset");
+            if self.unit_files_watch.load(Ordering::Relaxed) {
+                self.unit_files.lock().unwrap().presets.insert(name.to_string(), preset.clone());
+            }
+            preset
+        });
+
+        // Units without processes have nothing to freeze, and start again thawed.
+        let freezer = if matches!(active_state, "inactive" | "failed") {
+            self.freezer_states.lock().unwrap().remove(name);
+            String::new()
+        } else {
+            let known = self.freezer_states.lock().unwrap().get(name).cloned();
+            known.unwrap_or_else(|| "running".to_string())
+        };
+        let need_daemon_reload = self.unit_files.lock().unwrap().need_daemon_reload.get(name).copied().unwrap_or(false);
+
+        (state, preset, freezer, need_daemon_reload)
+    }
+
+    /// Like systemd-run, programs without a path are looked up in the PATH of the machine.
+    fn program_path(&self, program: &str) -> Result<String, ServiceError> {
+        if program.contains('/') {
+            return Ok(program.to_string());
+        }
+        let output = self.command("sh", &["-c", r#"command -v "$1""#, "sh", program]).output()?;
+        let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if !output.status.success() || !path.starts_with('/') {
+            return Err(format!("{} is not in the PATH", program).into());
+        }
+        Ok(path)
+    }
+
+    fn manager_proxy(&self) -> Result<Proxy<'static>, ServiceError> {
         let proxy = Proxy::new(
             &self.connection,
             "org.freedesktop.systemd1",
@@ -65,25 +800,174 @@ impl SystemdServiceAdapter {
         )?;
         Ok(proxy)
     }
+
+    /// Every property of `interface` on the unit, in one call.
+    fn all_properties(&self, unit_path: &OwnedObjectPath, interface: &str) -> Result<Properties, ServiceError> {
+        let properties = self.unit_proxy(unit_path, "org.freedesktop.DBus.Properties")?;
+        Ok(Properties(properties.call("GetAll", &(interface))?))
+    }
+
+    fn timer_property(&self, unit_path: &OwnedObjectPath) -> Result<TimerProperty, ServiceError> {
+        let mut timer = self.all_properties(unit_path, "org.freedesktop.systemd1.Timer")?;
+        Ok(TimerProperty::new(
+            timer.take("Unit")?,
+            timer.take("TimersCalendar")?,
+            timer.take("TimersMonotonic")?,
+            timer.take("NextElapseUSecRealtime")?,
+            timer.take("LastTriggerUSec")?,
+            timer.take("Persistent")?,
+            timer.take("Result")?,
+        ))
+    }
+
+    fn socket_property(&self, unit_path: &OwnedObjectPath) -> Result<SocketProperty, ServiceError> {
+        let unit = self.unit_proxy(unit_path, "org.freedesktop.systemd1.Unit")?;
+        let mut socket = self.all_properties(unit_path, "org.freedesktop.systemd1.Socket")?;
+        Ok(SocketProperty::new(
+            socket.take("Listen")?,
+            unit.get_property("SubState")?,
+            socket.take("Accept")?,
+            socket.take("NConnections")?,
+            socket.take("NAccepted")?,
+            socket.take("NRefused")?,
+            socket.take("Result")?,
+        ))
+    }
+
+    fn service_property(&self, unit_path: &OwnedObjectPath) -> Result<ServiceProperty, ServiceError> {
+        let mut service = self.all_properties(unit_path, "org.freedesktop.systemd1.Service")?;
+
+        let exec_start: Vec<SASBTTUII> = service.take("ExecStart")?;
+        let exec_start_pre: Vec<SASBTTUII> = service.take("ExecStartPre")?;
+        let exec_start_post: Vec<SASBTTUII> = service.take("ExecStartPost")?;
+        let exec_stop: Vec<SASBTTUII> = service.take("ExecStop")?;
+        let exec_stop_post: Vec<SASBTTUII> = service.take("ExecStopPost")?;
+
+        let exec_main_pid: u32 = service.take("ExecMainPID")?;
+        let exec_main_start_timestamp: u64 = service.take("ExecMainStartTimestamp")?;
+        let exec_main_exit_timestamp: u64 = service.take("ExecMainExitTimestamp")?;
+        let exec_main_code: i32 = service.take("ExecMainCode")?;
+        let exec_main_status: i32 = service.take("ExecMainStatus")?;
+
+        let main_pid: u32 = service.take("MainPID")?;
+        let control_pid: u32 = service.take("ControlPID")?;
+
+        let restart: String = service.take("Restart")?;
+        let restart_usec: u64 = service.take("RestartUSec")?;
+        let n_restarts: u32 = service.take("NRestarts")?;
+        let watchdog_usec: u64 = service.take("WatchdogUSec")?;
+
+        let status_text: String = service.take("StatusText")?;
+        let result: String = service.take("Result")?;
+
+        let user: String = service.take("User")?;
+        let group: String = service.take("Group")?;
+        let working_directory: String = service.take("WorkingDirectory")?;
+
+        let limit_cpu: u64 = service.take("LimitCPU")?;
+        let limit_nofile: u64 = service.take("LimitNOFILE")?;
+        let limit_nproc: u64 = service.take("LimitNPROC")?;
+        let limit_memlock: u64 = service.take("LimitMEMLOCK")?;
+        let memory_limit: u64 = service.take("MemoryLimit")?;
+        let cpu_shares: u64 = service.take("CPUShares")?;
+
+        let security = ServiceSecurity::new(
+            service.take("ControlGroup")?,
+            service.take("Slice")?,
+            service.take("Delegate")?,
+            service.take("DynamicUser")?,
+            service.take("ProtectSystem")?,
+            service.take("ProtectHome")?,
+            service.take("NoNewPrivileges")?,
+            service.take("CapabilityBoundingSet")?,
+        );
+
+        Ok(ServiceProperty::new(
+            exec_start,
+            exec_start_pre,
+            exec_start_post,
+            exec_stop,
+            exec_stop_post,
+            exec_main_pid,
+            exec_main_start_timestamp,
+            exec_main_exit_timestamp,
+            exec_main_code,
+            exec_main_status,
+            main_pid,
+            control_pid,
+            restart,
+            restart_usec,
+            n_restarts,
+            watchdog_usec,
+            status_text,
+            result,
+            user,
+            group,
+            working_directory,
+            limit_cpu,
+            limit_nofile,
+            limit_nproc,
+            limit_memlock,
+            memory_limit,
+            cpu_shares,
+            security,
+        ))
+    }
+}
+
+impl Drop for SystemdServiceAdapter {
+    fn drop(&mut self) {
+        self.job_watch.store(false, Ordering::Relaxed);
+        self.unit_watch.store(false, Ordering::Relaxed);
+        self.unit_files_watch.store(false, Ordering::Relaxed);
+    }
 }
 
 impl ServiceRepository for SystemdServiceAdapter {
-    fn change_connection(&mut self, connection_type: ConnectionType) -> Result<(), Error> {
-        self.connection = match connection_type {
-            ConnectionType::Session => Connection::session()?,
-            ConnectionType::System => Connection::system()?
-        };
-        Ok(())
+    fn change_connection(&mut self, connection_type: ConnectionType) -> Result<(), ServiceError> {
+        self.connection = Self::connect(self.host.as_deref(), connection_type)?;
+        self.connection_type = connection_type;
+        self.connection_lost.store(false, Ordering::Relaxed);
+        self.spawn_unit_files_watch();
+        self.spawn_unit_watch()?;
+        self.spawn_job_watch()
+    }
+
+    fn connection_lost(&self) -> bool {
+        self.connection_lost.load(Ordering::Relaxed)
     }
 
-    fn list_services(&self) -> Result<Vec<Service>, Box<dyn std::error::Error>> {
+    fn set_call_limits(&mut self, limits: CallLimits) {
+        self.limits = limits;
+    }
+
+    /// Opens another connection of the same type, subscribing the watchers again.
+    fn reconnect(&mut self) -> Result<(), ServiceError> {
+        self.change_connection(self.connection_type)
+    }
+
+    /// The same connection, without the watchers: the worker only calls the manager.
+    fn worker(&self) -> Result<Box<dyn ServiceRepository>, ServiceError> {
+        Ok(Box::new(Self::with_connection(
+            self.connection.clone(),
+            self.connection_type,
+            self.unit_types.clone(),
+            self.host.clone(),
+        )))
+    }
+
+    fn list_services(&self) -> Result<Vec<Service>, ServiceError> {
         let proxy = self.manager_proxy()?;
 
-        let units: Vec<SystemdUnit> = proxy.call("ListUnits", &())?;
+        let units: Vec<SystemdUnit> = {
+            let proxy = proxy.clone();
+            self.limits.run("Listing the units", move || Ok(proxy.call("ListUnits", &())?))?
+        };
+        let states = self.unit_file_states(&proxy)?;
 
-        let services = units
+        let mut services: Vec<Service> = units
             .into_iter()
-            .filter(|(name, ..)| name.ends_with(".service"))
+            .filter(|(name, ..)| self.is_listed_unit(name))
             .map(
                 |(
                     name,
@@ -92,32 +976,109 @@ impl ServiceRepository for SystemdServiceAdapter {
                     active_state,
                     sub_state,
                     _followed,
-                    _object_path,
+                    object_path,
                     _job_id,
                     _job_type,
                     _job_object,
                 )| {
-                    let state: String = proxy
-                        .call("GetUnitFileState", &name)
-                        .unwrap_or_else(|_| "unknown".into());
+                    let (state, preset, freezer, need_daemon_reload) =
+                        self.unit_file_state(&proxy, &states, &name, &active_state, &object_path);
 
-                    let service_state =
-                        ServiceState::new(load_state, active_state, sub_state, state);
+                    let service_state = ServiceState::new(load_state, active_state, sub_state, state, preset, freezer)
+                        .with_need_daemon_reload(need_daemon_reload);
 
-                    Service::new(name, description, service_state)
+                    Service::new(name, description, service_state).with_object_path(object_path.to_string())
                 },
             )
             .collect();
 
-        Ok(services)
+        // Installed unit files ListUnits leaves out because nothing loaded them, e.g. a service
+        // installed but never started. Templates only run as instances, counted in their row.
+        let loaded: HashSet<String> = services.iter().map(|service| service.name().to_string()).collect();
+        let mut active_instances: HashMap<String, usize> = HashMap::new();
+        for service in &services {
+            if let Some(template) = service.template()
+                && service.state().active() == "active"
+            {
+                *active_instances.entry(template).or_default() += 1;
+            }
+        }
+        let mut unloaded: Vec<Service> = states
+            .iter()
+            .filter(|(name, _)| !loaded.contains(*name) && self.is_listed_unit(name))
+            .map(|(name, file_state)| {
+                let preset = self.unit_files.lock().unwrap().presets.get(name).cloned().unwrap_or_default();
+                let template = name.contains("@.");
+                let service_state = ServiceState::new(
+                    NOT_LOADED.to_string(),
+                    "inactive".to_string(),
+                    if template { "template" } else { "dead" }.to_string(),
+                    file_state.clone(),
+                    preset,
+                    String::new(),
+                );
+                let description = match active_instances.get(name) {
+                    Some(1) => "Template, 1 active instance".to_string(),
+                    Some(count) => format!("Template, {} active instances", count),
+                    None if template => "Template".to_string(),
+                    None => String::new(),
+                };
+                let service = Service::new(name.clone(), description, service_state);
+                if template { service } else { service.with_object_path(unit_object_path(name)) }
+            })
+            .collect();
+        unloaded.sort_by(|a, b| a.name().cmp(b.name()));
+        services.extend(unloaded);
+
+        // Symlinks naming another unit, e.g. sshd.service for ssh.service, are listed by
+        // ListUnitFiles as "alias" files of their own; systemd loads them as the unit they name.
+        let aliases: HashMap<String, String> = states
+            .iter()
+            .filter(|(name, state)| state.as_str() == "alias" && !loaded.contains(*name) && self.is_listed_unit(name))
+            .filter_map(|(name, _)| {
+                let unit = self.unit_proxy(&self.unit_path(name).ok()?, "org.freedesktop.systemd1.Unit").ok()?;
+                let id: String = unit.get_property("Id").ok()?;
+                Some((name.clone(), id))
+            })
+            .collect();
+
+        Ok(fold_aliases(services, &aliases))
+    }
+
+    fn time_listing(&self) -> Result<ListTimings, ServiceError> {
+        let proxy = self.manager_proxy()?;
+
+        let started = Instant::now();
+        let units: Vec<SystemdUnit> = proxy.call("ListUnits", &())?;
+        // Timed without the cache, as on the first refresh.
+        let states = self.list_unit_files(&proxy)?;
+        let list_call = started.elapsed();
+
+        let unit_calls = units
+            .iter()
+            .filter(|(name, ..)| self.is_listed_unit(name))
+            .map(|(name, _, _, active_state, _, _, object_path, ..)| {
+                let started = Instant::now();
+                self.unit_file_state(&proxy, &states, name, active_state, object_path);
+                started.elapsed()
+            })
+            .collect();
+
+        Ok(ListTimings::new(list_call, unit_calls))
     }
 
-    fn get_service_log(&self, name: &str) -> Result<String, Box<dyn std::error::Error>> {
-        let output = std::process::Command::new("journalctl")
-            .arg("-eu")
-            .arg(name)
-            .arg("--no-pager")
-            .output()?;
+    fn get_service_log(&self, name: &str, lines: u32) -> Result<String, ServiceError> {
+        if let Some(mut journal) = self.local_journal() {
+            journal.match_unit(name)?;
+            journal.seek_tail()?;
+            let entries = journal.entries_back(lines, None)?;
+            if entries.is_empty() {
+                return Ok("-- No entries --".to_string());
+            }
+            return Ok(entries.iter().map(JournalEntry::line).collect::<Vec<_>>().join("\n"));
+        }
+        let lines = lines.to_string();
+        let output = self.journalctl(&["-u", name, "-n", &lines, "--no-pager"])?;
 
         let log = if output.status.success() {
             String::from_utf8_lossy(&output.stdout).to_string()
@@ -128,132 +1089,1035 @@ impl ServiceRepository for SystemdServiceAdapter {
         Ok(log)
     }
 
-    
-    fn systemctl_cat(&self, name: &str) -> Result<String, Box<dyn std::error::Error>> {
-        let output = Command::new("systemctl")
-            .arg("cat")
-            .arg("--no-pager")
-            .arg(name)
-            .output()?;
+    fn get_journal_entries(
+        &self,
+        name: &str,
+        lines: u32,
+        filter: &LogFilter,
+    ) -> Result<Vec<JournalEntry>, ServiceError> {
+        if let Some(mut journal) = self.filtered_journal(name, filter)? {
+            journal.seek_tail()?;
+            return Ok(journal.entries_back(lines, filter.since_usec()?)?);
+        }
+        self.journalctl_entries(name, lines, filter, None)
+    }
+
+    fn get_journal_boots(&self) -> Result<Vec<ListedBoot>, ServiceError> {
+        let output = self.journalctl(&["--list-boots", "--no-pager"])?;
+        if !output.status.success() {
+            return Err(ServiceError::from_stderr(String::from_utf8_lossy(&output.stderr).to_string()));
+        }
+        Ok(parse_boots(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    fn get_older_journal_entries(
+        &self,
+        name: &str,
+        cursor: &str,
+        lines: u32,
+        filter: &LogFilter,
+    ) -> Result<Vec<JournalEntry>, ServiceError> {
+        if let Some(mut journal) = self.filtered_journal(name, filter)? {
+            journal.seek_cursor(cursor)?;
+            // Onto the entry of the cursor, the ones before it being read from there.
+            journal.previous()?;
+            return Ok(journal.entries_back(lines, filter.since_usec()?)?);
+        }
+        self.journalctl_entries(name, lines, filter, Some(cursor))
+    }
+
+    fn get_log_timestamps(&self, name: &str) -> Result<Vec<u64>, ServiceError> {
+        if let Some(mut journal) = self.local_journal() {
+            journal.match_unit(name)?;
+            journal.seek_tail()?;
+            let entries = journal.entries_back(1000, None)?;
+            return Ok(entries.iter().filter_map(JournalEntry::timestamp).collect());
+        }
+        // Same entries as the log view, keeping only the timestamp of each one.
+        let output = self.journalctl(&[
+            "-u", name, "-n", "1000", "-o", "json", "--output-fields=__REALTIME_TIMESTAMP", "--no-pager",
+        ])?;
+
+        if !output.status.success() {
+            let err_msg = String::from_utf8_lossy(&output.stderr).to_string();
+            return Err(ServiceError::from_stderr(err_msg));
+        }
+
+        const FIELD: &str = "\"__REALTIME_TIMESTAMP\":\"";
+        let timestamps = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let start = line.find(FIELD)? + FIELD.len();
+                let end = start + line[start..].find('"')?;
+                line[start..end].parse().ok()
+            })
+            .collect();
+
+        Ok(timestamps)
+    }
+
+    fn get_journal_units_since(&self, minutes: u64) -> Result<Vec<String>, ServiceError> {
+        if let Some(mut journal) = self.local_journal() {
+            journal.seek_realtime(now_usec().saturating_sub(minutes * 60_000_000))?;
+            let mut units = Vec::new();
+            while journal.next()? {
+                units.extend(journal.field("_SYSTEMD_UNIT"));
+            }
+            return Ok(units);
+        }
+        let since = format!("-{}min", minutes);
+        let output =
+            self.journalctl(&["--since", &since, "-o", "json", "--output-fields=_SYSTEMD_UNIT", "--no-pager"])?;
+
+        if !output.status.success() {
+            let err_msg = String::from_utf8_lossy(&output.stderr).to_string();
+            return Err(ServiceError::from_stderr(err_msg));
+        }
+
+        const FIELD: &str = "\"_SYSTEMD_UNIT\":\"";
+        let units = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let start = line.find(FIELD)? + FIELD.len();
+                let end = start + line[start..].find('"')?;
+                Some(line[start..end].to_string())
+            })
+            .collect();
+
+        Ok(units)
+    }
+
+    /// Built from the last daemon reload (UnitsLoadTimestamp), the unit's InvocationID, its last
+    /// state change and whether its unit file changed on disk since it was loaded.
+    fn unit_generation(&self, name: &str) -> Option<String> {
+        // A non-caching proxy, the default one would fetch every Manager property first.
+        let units_load: u64 = OwnedObjectPath::try_from("/org/freedesktop/systemd1")
+            .ok()
+            .and_then(|path| self.unit_proxy(&path, "org.freedesktop.systemd1.Manager").ok())
+            .and_then(|manager| manager.get_property("UnitsLoadTimestamp").ok())
+            .unwrap_or_default();
+        let unit_path = self.unit_path(name).ok()?;
+        let unit = self.unit_proxy(&unit_path, "org.freedesktop.systemd1.Unit").ok()?;
+
+        let invocation: Vec<u8> = unit.get_property("InvocationID").ok()?;
+        let state_change: u64 = unit.get_property("StateChangeTimestamp").ok()?;
+        let need_reload: bool = unit.get_property("NeedDaemonReload").ok()?;
+        let invocation: String = invocation.iter().map(|byte| format!("{:02x}", byte)).collect();
+        Some(format!("{}:{}:{}:{}", units_load, invocation, state_change, need_reload))
+    }
+
+    fn systemctl_cat(&self, name: &str) -> Result<String, ServiceError> {
+        let output = self.command("systemctl", &["cat", "--no-pager", name]).output()?;
 
         if output.status.success() {
             Ok(String::from_utf8_lossy(&output.stdout).to_string())
         } else {
             let err_msg = String::from_utf8_lossy(&output.stderr).to_string();
-            Err(Box::new(io::Error::new(ErrorKind::Other, err_msg)))
+            Err(ServiceError::from_stderr(err_msg))
+        }
+    }
+
+    fn get_unit_file_paths(&self, name: &str) -> Result<UnitFilePaths, ServiceError> {
+        let unit_path = self.unit_path(name)?;
+        let unit = self.unit_proxy(&unit_path, "org.freedesktop.systemd1.Unit")?;
+        Ok(UnitFilePaths {
+            fragment: unit.get_property("FragmentPath")?,
+            drop_ins: unit.get_property("DropInPaths")?,
+        })
+    }
+
+    fn get_unit_links(&self, name: &str) -> Result<UnitLinks, ServiceError> {
+        let unit_path = self.unit_path(name)?;
+        let unit = self.unit_proxy(&unit_path, "org.freedesktop.systemd1.Unit")?;
+        let id: String = unit.get_property("Id")?;
+        let names: Vec<String> = unit.get_property("Names")?;
+        let target: String = unit.get_property("FragmentPath")?;
+        let mut links = Vec::new();
+        if !target.is_empty() {
+            let proxy = self.manager_proxy()?;
+            // Links of /etc, then of /run. Managers older than systemd 243 have no
+            // GetUnitFileLinks, their links are left out.
+            for runtime in [false, true] {
+                if let Ok(found) = proxy.call::<_, _, Vec<String>>("GetUnitFileLinks", &(id.as_str(), runtime)) {
+                    links.extend(found);
+                }
+            }
+        }
+        Ok(UnitLinks {
+            aliases: names.into_iter().filter(|alias| *alias != id).collect(),
+            links,
+            target,
+        })
+    }
+
+    fn get_loaded_settings(&self, name: &str, settings: &[String]) -> Result<HashMap<String, Vec<String>>, ServiceError> {
+        let properties = settings.join(",");
+        let mut args = vec!["show", "--no-pager", "-p", &properties, name];
+        if self.connection_type == ConnectionType::Session {
+            args.push("--user");
+        }
+        let output = self.command("systemctl", &args).output()?;
+        if !output.status.success() {
+            return Err(ServiceError::from_stderr(String::from_utf8_lossy(&output.stderr).to_string()));
+        }
+        Ok(parse_show(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    fn verify_unit(&self, name: &str) -> Result<Vec<VerifyFinding>, ServiceError> {
+        let mut args = vec!["verify", "--no-pager", name];
+        if self.connection_type == ConnectionType::Session {
+            args.push("--user");
+        }
+        let output = self.command("systemd-analyze", &args).output()?;
+        // The findings are on stderr, and fail the command when one is an error.
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let findings = parse_verify(&format!("{}{}", String::from_utf8_lossy(&output.stdout), stderr));
+        if !output.status.success() && findings.is_empty() {
+            return Err(ServiceError::from_stderr(stderr));
+        }
+        Ok(findings)
+    }
+
+    fn create_unit(&self, name: &str, content: &str) -> Result<String, ServiceError> {
+        let directory = self.unit_directory()?;
+        let path = format!("{}/{}", directory, name);
+        self.write_file(WRITE_UNIT_SCRIPT, &directory, &path, content)?;
+        Ok(path)
+    }
+
+    fn write_drop_in(&self, name: &str, file_name: &str, content: &str) -> Result<String, ServiceError> {
+        let directory = format!("{}/{}.d", self.unit_directory()?, name);
+        let path = format!("{}/{}", directory, file_name);
+        self.write_file(WRITE_DROP_IN_SCRIPT, &directory, &path, content)?;
+        Ok(path)
+    }
+
+    fn remove_drop_in(&self, _name: &str, path: &str) -> Result<(), ServiceError> {
+        self.run_script(&["-c", REMOVE_DROP_IN_SCRIPT, "sh", path])
+    }
+
+    fn edit_unit_command(&self, name: &str) -> Result<Command, ServiceError> {
+        let mut args = vec!["edit", "--full", name];
+        if self.connection_type == ConnectionType::Session {
+            args.push("--user");
+        }
+        Ok(self.interactive_command("systemctl", &args))
+    }
+
+    fn shell_command(&self, _name: &str, directory: &str) -> Result<Command, ServiceError> {
+        // "~user" is only expanded unquoted, so it goes through eval once known to be a user name.
+        if let Some(user) = directory.strip_prefix('~')
+            && !user.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        {
+            return Err(ServiceError::Parse(format!("Invalid home directory {}", directory)));
+        }
+        let script = r#"case "$1" in "~"*) eval "cd $1" ;; *) cd -- "$1" ;; esac && exec "${SHELL:-/bin/sh}" -i"#;
+        Ok(self.interactive_command("sh", &["-c", script, "sh", directory]))
+    }
+
+    fn get_unit_environment(&self, name: &str) -> Result<UnitEnvironment, ServiceError> {
+        // Other unit types run no command.
+        let Some(interface) = exec_section(name).and_then(section_interface) else {
+            return Ok(UnitEnvironment::default());
+        };
+        let unit_path = self.unit_path(name)?;
+        let unit = self.unit_proxy(&unit_path, interface)?;
+        Ok(UnitEnvironment {
+            variables: unit.get_property("Environment")?,
+            files: unit.get_property("EnvironmentFiles")?,
+        })
+    }
+
+    fn get_resource_limits(&self, name: &str) -> Result<ResourceLimits, ServiceError> {
+        let Some(interface) = cgroup_section(name).and_then(section_interface) else {
+            return Ok(ResourceLimits::default());
+        };
+        let unit_path = self.unit_path(name)?;
+        let unit = self.unit_proxy(&unit_path, interface)?;
+        let cpu_quota_usec: u64 = unit.get_property("CPUQuotaPerSecUSec")?;
+        Ok(ResourceLimits {
+            cpu_quota_percent: Some(if cpu_quota_usec == UNLIMITED { UNLIMITED } else { cpu_quota_usec / 10_000 }),
+            memory_max_bytes: Some(unit.get_property("MemoryMax")?),
+            tasks_max: Some(unit.get_property("TasksMax")?),
+        })
+    }
+
+    fn set_resource_limits(&self, name: &str, limits: &ResourceLimits, runtime: bool) -> Result<(), ServiceError> {
+        let proxy = self.manager_proxy()?;
+        proxy.call::<_, _, ()>("SetUnitProperties", &(name, runtime, limit_properties(limits)))?;
+        Ok(())
+    }
+
+    fn search_unit_files(&self, query: &str) -> Result<Vec<UnitFileMatch>, ServiceError> {
+        let proxy = self.manager_proxy()?;
+        // Units of each file: the installed unit files, then the unit files and drop-ins of the
+        // loaded units, e.g. of template instances.
+        let mut units_of: HashMap<String, Vec<String>> = HashMap::new();
+        let files: Vec<(String, String)> = proxy.call("ListUnitFiles", &())?;
+        for (path, _) in files {
+            let Some(name) = Path::new(&path).file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if self.is_listed_unit(name) {
+                units_of.entry(path.clone()).or_default().push(name.to_string());
+            }
+        }
+        let units: Vec<SystemdUnit> = proxy.call("ListUnits", &())?;
+        for (name, _, _, _, _, _, object_path, ..) in units {
+            if !self.is_listed_unit(&name) {
+                continue;
+            }
+            let Ok(unit) = self.unit_proxy(&object_path, "org.freedesktop.systemd1.Unit") else {
+                continue;
+            };
+            let fragment: String = unit.get_property("FragmentPath").unwrap_or_default();
+            let drop_ins: Vec<String> = unit.get_property("DropInPaths").unwrap_or_default();
+            for path in std::iter::once(fragment).chain(drop_ins).filter(|path| !path.is_empty()) {
+                let units = units_of.entry(path).or_default();
+                if !units.contains(&name) {
+                    units.push(name.clone());
+                }
+            }
+        }
+        if units_of.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut args = vec!["-HnFiZ", "-e", query, "--"];
+        args.extend(units_of.keys().map(String::as_str));
+        let output = self.command("grep", &args).output()?;
+        // grep exits with 1 when nothing matches, and with 2 when some file could not be read.
+        if output.status.code() == Some(2) && output.stdout.is_empty() {
+            let err_msg = String::from_utf8_lossy(&output.stderr).to_string();
+            return Err(ServiceError::from_stderr(err_msg));
+        }
+
+        let mut matches: Vec<UnitFileMatch> = parse_grep_matches(&String::from_utf8_lossy(&output.stdout))
+            .into_iter()
+            .flat_map(|(path, line_number, line)| {
+                units_of.get(&path).cloned().unwrap_or_default().into_iter().map(move |unit| UnitFileMatch {
+                    unit,
+                    path: path.clone(),
+                    line_number,
+                    line: line.clone(),
+                })
+            })
+            .collect();
+        matches.sort_by(|a, b| a.unit.cmp(&b.unit).then(a.path.cmp(&b.path)).then(a.line_number.cmp(&b.line_number)));
+        Ok(matches)
+    }
+
+    fn list_unit_paths(&self) -> Result<Vec<UnitPathReference>, ServiceError> {
+        let proxy = self.manager_proxy()?;
+        let units: Vec<SystemdUnit> = proxy.call("ListUnits", &())?;
+
+        let mut references = Vec::new();
+        for (name, _, _, _, _, _, object_path, ..) in units {
+            let Some((_, unit_type)) = name.rsplit_once('.') else {
+                continue;
+            };
+
+            match unit_type {
+                "mount" | "automount" => {
+                    let (interface, relation) = if unit_type == "mount" {
+                        ("org.freedesktop.systemd1.Mount", PathRelation::Mount)
+                    } else {
+                        ("org.freedesktop.systemd1.Automount", PathRelation::Automount)
+                    };
+                    if let Ok(mount_point) = self
+                        .unit_proxy(&object_path, interface)
+                        .and_then(|unit| unit.get_property::<String>("Where"))
+                    {
+                        references.push(UnitPathReference::new(name, relation, mount_point));
+                    }
+                }
+                "path" => {
+                    let paths: Vec<(String, String)> = self
+                        .unit_proxy(&object_path, "org.freedesktop.systemd1.Path")
+                        .and_then(|unit| unit.get_property("Paths"))
+                        .unwrap_or_default();
+                    for (kind, path) in paths {
+                        references.push(UnitPathReference::new(
+                            name.clone(),
+                            PathRelation::Watch(kind),
+                            path,
+                        ));
+                    }
+                }
+                "service" => {
+                    let exec_start: Vec<SASBTTUII> = self
+                        .unit_proxy(&object_path, "org.freedesktop.systemd1.Service")
+                        .and_then(|unit| unit.get_property("ExecStart"))
+                        .unwrap_or_default();
+                    for (path, ..) in exec_start {
+                        references.push(UnitPathReference::new(
+                            name.clone(),
+                            PathRelation::Exec,
+                            path,
+                        ));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(references)
+    }
+
+    fn get_unit_dependencies(&self, name: &str) -> Result<UnitDependencies, ServiceError> {
+        let unit_path = self.unit_path(name)?;
+        let unit = self.unit_proxy(&unit_path, "org.freedesktop.systemd1.Unit")?;
+
+        Ok(UnitDependencies::new(
+            unit.get_property("Requires")?,
+            unit.get_property("Wants")?,
+            unit.get_property("BindsTo")?,
+            unit.get_property("After")?,
+            unit.get_property("Before")?,
+        ))
+    }
+
+    fn get_unit_timers(&self, name: &str) -> Result<Vec<(String, TimerProperty)>, ServiceError> {
+        let unit_path = self.unit_path(name)?;
+        let unit = self.unit_proxy(&unit_path, "org.freedesktop.systemd1.Unit")?;
+        let triggered_by: Vec<String> = unit.get_property("TriggeredBy")?;
+
+        let mut timers = Vec::new();
+        for timer in triggered_by.into_iter().filter(|trigger| trigger.ends_with(".timer")) {
+            let timer_path = self.unit_path(&timer)?;
+            let property = self.timer_property(&timer_path)?;
+            timers.push((timer, property));
+        }
+        Ok(timers)
+    }
+
+    fn get_unit_sockets(&self, name: &str) -> Result<Vec<(String, SocketProperty)>, ServiceError> {
+        let unit_path = self.unit_path(name)?;
+        let unit = self.unit_proxy(&unit_path, "org.freedesktop.systemd1.Unit")?;
+        let triggered_by: Vec<String> = unit.get_property("TriggeredBy")?;
+
+        let mut sockets = Vec::new();
+        for socket in triggered_by.into_iter().filter(|trigger| trigger.ends_with(".socket")) {
+            let socket_path = self.unit_path(&socket)?;
+            let property = self.socket_property(&socket_path)?;
+            sockets.push((socket, property));
+        }
+        Ok(sockets)
+    }
+
+    fn get_unit_triggers(&self, name: &str) -> Result<UnitTriggers, ServiceError> {
+        let unit_path = self.unit_path(name)?;
+        let unit = self.unit_proxy(&unit_path, "org.freedesktop.systemd1.Unit")?;
+        Ok(UnitTriggers::new(unit.get_property("Triggers")?, unit.get_property("TriggeredBy")?))
+    }
+
+    fn get_unit_conditions(&self, name: &str) -> Result<UnitConditions, ServiceError> {
+        let unit_path = self.unit_path(name)?;
+        let unit = self.unit_proxy(&unit_path, "org.freedesktop.systemd1.Unit")?;
+        // (type, trigger, negate, parameter, state), like ("ConditionPathExists", false, false, "/etc/foo", -1).
+        let conditions: Vec<(String, bool, bool, String, i32)> = unit.get_property("Conditions")?;
+        Ok(UnitConditions::new(
+            unit.get_property("ConditionResult")?,
+            unit.get_property("ConditionTimestamp")?,
+            conditions
+                .into_iter()
+                .map(|(kind, trigger, negate, parameter, state)| UnitCondition::new(kind, trigger, negate, parameter, state))
+                .collect(),
+        ))
+    }
+
+    fn get_unit_history(&self, name: &str) -> Result<UnitHistory, ServiceError> {
+        // -u also matches what the manager logs about the unit, which the history is made of.
+        if let Some(mut journal) = self.local_journal() {
+            journal.match_unit(name)?;
+            journal.seek_tail()?;
+            let since = now_usec().saturating_sub(HISTORY_DAYS * 86_400_000_000);
+            return Ok(UnitHistory::from_entries(&journal.entries_back(20000, Some(since))?));
+        }
+        let since = format!("-{}d", HISTORY_DAYS);
+        let output = self.journalctl(&[
+            "-u", name, "--since", &since, "-n", "20000", "-o", "json", "--no-pager",
+            "--output-fields=SYSLOG_IDENTIFIER,MESSAGE",
+        ])?;
+
+        if !output.status.success() {
+            let err_msg = String::from_utf8_lossy(&output.stderr).to_string();
+            return Err(ServiceError::from_stderr(err_msg));
+        }
+
+        let entries: Vec<JournalEntry> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(JournalEntry::from_json)
+            .collect();
+        Ok(UnitHistory::from_entries(&entries))
+    }
+
+    fn get_oom_entries(&self, name: &str) -> Result<Vec<JournalEntry>, ServiceError> {
+        if self.host.is_none() && Journal::open().is_ok() {
+            return self.local_oom_entries(name);
+        }
+        let since = format!("-{}d", HISTORY_DAYS);
+        // systemd's own messages about the unit, then the kernel's, which name its control group.
+        let escaped = name.replace('\\', "\\\\").replace('.', "\\.");
+        let kernel_pattern = format!("oom.*/{}", escaped);
+        let queries: [Vec<&str>; 2] = [
+            vec!["-u", name, "--grep", "oom|out of memory"],
+            vec!["-k", "--grep", &kernel_pattern],
+        ];
+        let mut entries = Vec::new();
+        for query in queries {
+            let mut args = query;
+            args.extend(["--since", &since, "-n", "20", "-o", "json", "--no-pager"]);
+            let output = self.journalctl(&args)?;
+            // journalctl exits with 1 when --grep matches nothing.
+            if !output.status.success() && !output.stderr.is_empty() {
+                let err_msg = String::from_utf8_lossy(&output.stderr).to_string();
+                return Err(ServiceError::from_stderr(err_msg));
+            }
+            entries.extend(String::from_utf8_lossy(&output.stdout).lines().filter_map(JournalEntry::from_json));
+        }
+        entries.sort_by_key(|entry| entry.timestamp());
+        Ok(entries)
+    }
+
+    fn get_unit_documentation(&self, name: &str) -> Result<Vec<String>, ServiceError> {
+        let unit_path = self.unit_path(name)?;
+        let unit = self.unit_proxy(&unit_path, "org.freedesktop.systemd1.Unit")?;
+        Ok(unit.get_property("Documentation")?)
+    }
+
+    fn plan_action(&self, action: UnitAction, name: &str) -> Result<ActionPlan, ServiceError> {
+        let unit_path = self.unit_path(name)?;
+        let unit = self.unit_proxy(&unit_path, "org.freedesktop.systemd1.Unit")?;
+        let list = |property: &'static str| -> Result<Vec<String>, Error> { unit.get_property(property) };
+
+        let (method, arguments) = match action {
+            UnitAction::Start => ("StartUnit", format!("(\"{}\", \"replace\")", name)),
+            UnitAction::Stop => ("StopUnit", format!("(\"{}\", \"replace\")", name)),
+            UnitAction::Restart => ("RestartUnit", format!("(\"{}\", \"replace\")", name)),
+            UnitAction::Reload => ("ReloadUnit", format!("(\"{}\", \"replace\")", name)),
+            UnitAction::ReloadOrRestart => ("ReloadOrRestartUnit", format!("(\"{}\", \"replace\")", name)),
+            UnitAction::Enable => ("EnableUnitFiles", format!("([\"{}\"], false, true)", name)),
+            UnitAction::Disable => ("DisableUnitFiles", format!("([\"{}\"], false)", name)),
+            UnitAction::Freeze => ("FreezeUnit", format!("(\"{}\")", name)),
+            UnitAction::Thaw => ("ThawUnit", format!("(\"{}\")", name)),
+        };
+
+        let can_reload: bool = unit.get_property("CanReload")?;
+        // ReloadOrRestartUnit restarts the units that cannot reload.
+        let effective_action = match action {
+            UnitAction::ReloadOrRestart if !can_reload => UnitAction::Restart,
+            action => action,
+        };
+
+        let mut propagated_units = Vec::new();
+        let mut side_effects = Vec::new();
+        match effective_action {
+            UnitAction::Reload | UnitAction::ReloadOrRestart => {
+                if !can_reload {
+                    side_effects.push("fails, the unit cannot reload".to_string());
+                }
+                for target in self.active_units(list("PropagatesReloadTo")?)? {
+                    side_effects.push(format!("will also reload {}", target));
+                }
+            }
+            UnitAction::Start => {
+                let mut pulled_in = list("Requires")?;
+                pulled_in.extend(list("Wants")?);
+                pulled_in.extend(list("BindsTo")?);
+                let active = self.active_units(pulled_in.clone())?;
+                for dependency in pulled_in.into_iter().filter(|unit| !active.contains(unit)) {
+                    side_effects.push(format!("will also start {}", dependency));
+                }
+                for conflict in self.active_units(list("Conflicts")?)? {
+                    side_effects.push(format!("will stop {} (conflicts)", conflict));
+                }
+            }
+            UnitAction::Stop | UnitAction::Restart => {
+                if action == UnitAction::ReloadOrRestart {
+                    side_effects.push("restarts the unit, which cannot reload".to_string());
+                }
+                let mut dependents = list("RequiredBy")?;
+                dependents.extend(list("BoundBy")?);
+                dependents.extend(list("ConsistsOf")?);
+                dependents.sort();
+                dependents.dedup();
+                propagated_units = self.active_units(dependents)?;
+                if action == UnitAction::Stop {
+                    for trigger in self.active_units(list("TriggeredBy")?)? {
+                        side_effects.push(format!("can be started again by {}", trigger));
+                    }
+                }
+            }
+            UnitAction::Enable | UnitAction::Disable => {
+                side_effects.push("changes unit file symlinks only, no unit is started or stopped".to_string());
+            }
+            UnitAction::Freeze => {
+                side_effects.push("pauses every process of the unit, which keep their memory and connections".to_string());
+            }
+            UnitAction::Thaw => side_effects.push("resumes the paused processes of the unit".to_string()),
+        }
+
+        Ok(ActionPlan::new(
+            format!("org.freedesktop.systemd1.Manager.{}", method),
+            arguments,
+            propagated_units,
+            side_effects,
+        ))
+    }
+
+    fn start_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        let proxy = self.manager_proxy()?;
+        let job: OwnedObjectPath = proxy.call("StartUnit", &(name, "replace"))?;
+        Ok(Some(job.to_string()))
+    }
+
+    fn start_transient_unit(
+        &self,
+        name: &str,
+        argv: &[String],
+        limits: &ResourceLimits,
+    ) -> Result<Option<String>, ServiceError> {
+        let path = self.program_path(&argv[0])?;
+        let mut properties: Vec<(&str, Value)> = vec![
+            ("Description", Value::from(argv.join(" "))),
+            ("ExecStart", Value::from(vec![(path, argv.to_vec(), false)])),
+        ];
+        properties.extend(limit_properties(limits));
+        let aux: Vec<(&str, Vec<(&str, Value)>)> = Vec::new();
+        let proxy = self.manager_proxy()?;
+        let job: OwnedObjectPath = proxy.call("StartTransientUnit", &(name, "fail", properties, aux))?;
+        Ok(Some(job.to_string()))
+    }
+
+    fn schedule_action(&self, action: PolicyAction, name: &str, calendar: &str) -> Result<String, ServiceError> {
+        let created = now_usec() / 1_000_000;
+        let timer = ScheduledAction::timer_name(action, created);
+        let service = format!("{}.service", timer.trim_end_matches(".timer"));
+        let description = ScheduledAction::description(action, name);
+
+        let mut argv = vec!["systemctl".to_string()];
+        if self.connection_type == ConnectionType::Session {
+            argv.push("--user".to_string());
+        }
+        argv.extend([action.label().to_string(), name.to_string()]);
+        let path = self.program_path("systemctl")?;
+
+        // Like systemd-run --on-calendar: a transient timer with the service it starts.
+        let properties: Vec<(&str, Value)> = vec![
+            ("Description", Value::from(description.clone())),
+            ("OnCalendar", Value::from(calendar)),
+        ];
+        let aux: Vec<(&str, Vec<(&str, Value)>)> = vec![(
+            service.as_str(),
+            vec![
+                ("Description", Value::from(description)),
+                ("ExecStart", Value::from(vec![(path, argv, false)])),
+            ],
+        )];
+        let proxy = self.manager_proxy()?;
+        let _job: OwnedObjectPath = proxy.call("StartTransientUnit", &(timer.as_str(), "fail", properties, aux))?;
+        Ok(timer)
+    }
+
+    fn list_scheduled_actions(&self) -> Result<Vec<ScheduledAction>, ServiceError> {
+        let proxy = self.manager_proxy()?;
+        let no_states: Vec<&str> = Vec::new();
+        let pattern = format!("{}*.timer", TIMER_PREFIX);
+        let units: Vec<SystemdUnit> = proxy.call("ListUnitsByPatterns", &(no_states, vec![pattern.as_str()]))?;
+
+        let mut scheduled = Vec::new();
+        for (timer, description, _, _, _, _, unit_path, ..) in units {
+            let Some((action, unit)) = ScheduledAction::from_description(&description) else {
+                continue;
+            };
+            let mut properties = self.all_properties(&unit_path, "org.freedesktop.systemd1.Timer")?;
+            let calendars: Vec<(String, String, u64)> = properties.take("TimersCalendar")?;
+            let calendar = calendars.into_iter().map(|(_, expression, _)| expression).collect::<Vec<_>>().join(", ");
+            scheduled.push(ScheduledAction {
+                timer,
+                action,
+                unit,
+                calendar,
+                next_elapse: properties.take("NextElapseUSecRealtime")?,
+            });
         }
+        scheduled.sort_by_key(|action| action.next_elapse);
+        Ok(scheduled)
     }
 
-    fn start_service(&self, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    fn cancel_scheduled_action(&self, timer: &str) -> Result<(), ServiceError> {
+        if !timer.starts_with(TIMER_PREFIX) || !timer.ends_with(".timer") {
+            return Err(format!("{} is not the timer of a scheduled action", timer).into());
+        }
         let proxy = self.manager_proxy()?;
-        let _job: OwnedObjectPath = proxy.call("StartUnit", &(name, "replace"))?;
+        let _job: OwnedObjectPath = proxy.call("StopUnit", &(timer, "replace"))?;
         Ok(())
     }
 
-    fn stop_service(&self, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    fn stop_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        let proxy = self.manager_proxy()?;
+        let job: OwnedObjectPath = proxy.call("StopUnit", &(name, "replace"))?;
+        Ok(Some(job.to_string()))
+    }
+
+    fn restart_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        let proxy = self.manager_proxy()?;
+        let job: OwnedObjectPath = proxy.call("RestartUnit", &(name, "replace"))?;
+        Ok(Some(job.to_string()))
+    }
+
+    fn reload_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        let proxy = self.manager_proxy()?;
+        let job: OwnedObjectPath = proxy.call("ReloadUnit", &(name, "replace"))?;
+        Ok(Some(job.to_string()))
+    }
+
+    fn reload_or_restart_service(&self, name: &str) -> Result<Option<String>, ServiceError> {
         let proxy = self.manager_proxy()?;
-        let _job: OwnedObjectPath = proxy.call("StopUnit", &(name, "replace"))?;
+        let job: OwnedObjectPath = proxy.call("ReloadOrRestartUnit", &(name, "replace"))?;
+        Ok(Some(job.to_string()))
+    }
+
+    fn get_unit_capabilities(&self, name: &str) -> Result<UnitCapabilities, ServiceError> {
+        let unit_path = self.unit_path(name)?;
+        let unit = self.unit_proxy(&unit_path, "org.freedesktop.systemd1.Unit")?;
+        Ok(UnitCapabilities {
+            can_start: unit.get_property("CanStart")?,
+            can_stop: unit.get_property("CanStop")?,
+            can_reload: unit.get_property("CanReload")?,
+            can_isolate: unit.get_property("CanIsolate")?,
+        })
+    }
+
+    fn list_targets(&self) -> Result<Vec<Service>, ServiceError> {
+        let proxy = self.manager_proxy()?;
+        let no_states: Vec<&str> = Vec::new();
+        let units: Vec<SystemdUnit> = proxy.call("ListUnitsByPatterns", &(no_states, vec!["*.target"]))?;
+        let states = self.unit_file_states(&proxy)?;
+
+        let mut targets: Vec<Service> = units
+            .into_iter()
+            .map(|(name, description, load_state, active_state, sub_state, ..)| {
+                let file_state = states.get(&name).cloned().unwrap_or_default();
+                let state = ServiceState::new(load_state, active_state, sub_state, file_state, String::new(), String::new());
+                Service::new(name, description, state)
+            })
+            .collect();
+        let loaded: HashSet<String> = targets.iter().map(|target| target.name().to_string()).collect();
+        targets.extend(
+            states
+                .iter()
+                .filter(|(name, _)| name.ends_with(".target") && !loaded.contains(*name) && !name.contains("@."))
+                .map(|(name, file_state)| {
+                    let state = ServiceState::new(
+                        NOT_LOADED.to_string(),
+                        "inactive".to_string(),
+                        "dead".to_string(),
+                        file_state.clone(),
+                        String::new(),
+                        String::new(),
+                    );
+                    Service::new(name.clone(), String::new(), state)
+                }),
+        );
+        targets.sort_by(|a, b| a.name().cmp(b.name()));
+        Ok(targets)
+    }
+
+    fn get_default_target(&self) -> Result<String, ServiceError> {
+        let proxy = self.manager_proxy()?;
+        Ok(proxy.call("GetDefaultTarget", &())?)
+    }
+
+    fn set_default_target(&self, name: &str) -> Result<(), ServiceError> {
+        let proxy = self.manager_proxy()?;
+        // Replaces the default.target symlink, as `systemctl set-default` does.
+        let _changes: Vec<(String, String, String)> = proxy.call("SetDefaultTarget", &(name, true))?;
         Ok(())
     }
 
-    fn restart_service(&self, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    fn isolate_target(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        let proxy = self.manager_proxy()?;
+        let job: OwnedObjectPath = proxy.call("StartUnit", &(name, "isolate"))?;
+        Ok(Some(job.to_string()))
+    }
+
+    fn power(&self, action: PowerAction) -> Result<(), ServiceError> {
         let proxy = self.manager_proxy()?;
-        let _job: OwnedObjectPath = proxy.call("RestartUnit", &(name, "replace"))?;
+        // Like systemctl, through the target rather than Reboot()/PowerOff(), which skip stopping units.
+        let _job: OwnedObjectPath = proxy.call("StartUnit", &(action.target(), "replace-irreversibly"))?;
         Ok(())
     }
 
-    fn enable_service(&self, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    fn watch_jobs(&mut self, sender: Sender<JobResult>) -> Result<(), ServiceError> {
+        self.job_sender = Some(sender);
+        self.spawn_job_watch()
+    }
+
+    fn watch_units(&mut self, units: &[String], sender: Sender<UnitTransition>) -> Result<(), ServiceError> {
+        self.transition_sender = Some(sender);
+        self.watched_units = units.to_vec();
+        self.spawn_unit_watch()
+    }
+
+    fn enable_service(&self, name: &str) -> Result<Vec<UnitFileChange>, ServiceError> {
         let proxy = self.manager_proxy()?;
-        let (_carries_install_info, _changes): (bool, Vec<(String, String, String)>) =
+        let (_carries_install_info, changes): (bool, Vec<(String, String, String)>) =
             proxy.call("EnableUnitFiles", &(vec![name], false, true))?;
+        self.invalidate_unit_files();
+        Ok(unit_file_changes(changes))
+    }
+
+    fn disable_service(&self, name: &str) -> Result<Vec<UnitFileChange>, ServiceError> {
+        let proxy = self.manager_proxy()?;
+        let changes: Vec<(String, String, String)> = proxy.call("DisableUnitFiles", &(vec![name], false))?;
+        self.invalidate_unit_files();
+        Ok(unit_file_changes(changes))
+    }
+
+    fn revert_unit_file_changes(&self, name: &str, changes: &[UnitFileChange]) -> Result<(), ServiceError> {
+        // Removed links pointed to the unit file, as enabling would link them again.
+        let target = self.get_unit_file_paths(name)?.fragment;
+        let arguments: Vec<String> = changes.iter().map(|change| format!("{}:{}", change.kind, change.path)).collect();
+        let mut args = vec!["-c", REVERT_LINKS_SCRIPT, "sh", &target];
+        args.extend(arguments.iter().map(String::as_str));
+        self.run_script(&args)?;
+        self.invalidate_unit_files();
         Ok(())
     }
 
-    fn disable_service(&self, name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    fn freeze_service(&self, name: &str) -> Result<(), ServiceError> {
         let proxy = self.manager_proxy()?;
-        let _changes: Vec<(String, String, String)> =
-            proxy.call("DisableUnitFiles", &(vec![name], false))?;
+        proxy.call::<_, _, ()>("FreezeUnit", &(name))?;
+        self.freezer_states.lock().unwrap().insert(name.to_string(), "frozen".to_string());
         Ok(())
     }
 
-    fn reload_daemon(&self) -> Result<(), Box<dyn std::error::Error>> {
+    fn thaw_service(&self, name: &str) -> Result<(), ServiceError> {
         let proxy = self.manager_proxy()?;
-        proxy.call::<&str, (), ()>("Reload", &())?;
+        proxy.call::<_, _, ()>("ThawUnit", &(name))?;
+        self.freezer_states.lock().unwrap().remove(name);
         Ok(())
     }
 
-    fn get_service_property(
-        &self,
-        name: &str,
-    ) -> Result<ServiceProperty, Box<dyn std::error::Error>> {
+    fn reset_failed_unit(&self, name: &str) -> Result<(), ServiceError> {
         let proxy = self.manager_proxy()?;
+        proxy.call::<_, _, ()>("ResetFailedUnit", &(name))?;
+        Ok(())
+    }
 
-        let unit_path: OwnedObjectPath = proxy.call("GetUnit", &(name))?;
+    fn reset_failed(&self) -> Result<(), ServiceError> {
+        let proxy = self.manager_proxy()?;
+        proxy.call::<_, _, ()>("ResetFailed", &())?;
+        Ok(())
+    }
 
-        let service_proxy = Proxy::new(
-            &self.connection,
-            "org.freedesktop.systemd1",
-            unit_path.as_str(),
-            "org.freedesktop.systemd1.Service",
-        )?;
+    fn kill_service(&self, name: &str, target: KillTarget, signal: i32) -> Result<(), ServiceError> {
+        let proxy = self.manager_proxy()?;
+        proxy.call::<_, _, ()>("KillUnit", &(name, target.as_str(), signal))?;
+        Ok(())
+    }
+
+    fn reload_daemon(&self) -> Result<(), ServiceError> {
+        let proxy = self.manager_proxy()?;
+        proxy.call::<&str, (), ()>("Reload", &())?;
+        self.invalidate_unit_files();
+        Ok(())
+    }
+
+    fn get_restart_settings(&self, name: &str) -> Result<RestartSettings, ServiceError> {
+        let unit_path = self.unit_path(name)?;
+        let service = self.unit_proxy(&unit_path, "org.freedesktop.systemd1.Service")?;
+        let unit = self.unit_proxy(&unit_path, "org.freedesktop.systemd1.Unit")?;
+        Ok(RestartSettings::new(
+            service.get_property("Restart")?,
+            service.get_property("RestartUSec")?,
+            service.get_property("NRestarts")?,
+            unit.get_property("StartLimitIntervalUSec")?,
+            unit.get_property("StartLimitBurst")?,
+        ))
+    }
 
-        let exec_start: Vec<SASBTTUII> = service_proxy.get_property("ExecStart")?;
-        let exec_start_pre: Vec<SASBTTUII> = service_proxy.get_property("ExecStartPre")?;
-        let exec_start_post: Vec<SASBTTUII> = service_proxy.get_property("ExecStartPost")?;
-        let exec_stop: Vec<SASBTTUII> = service_proxy.get_property("ExecStop")?;
-        let exec_stop_post: Vec<SASBTTUII> = service_proxy.get_property("ExecStopPost")?;
+    fn get_runtime_stats(&self, name: &str) -> Result<RuntimeStats, ServiceError> {
+        let unit_path = self.unit_path(name)?;
+        let service = self.unit_proxy(&unit_path, "org.freedesktop.systemd1.Service")?;
+        // The start timestamp outlives the process, the main PID does not.
+        let main_pid: u32 = service.get_property("MainPID")?;
+        let main_start_usec = if main_pid == 0 { 0 } else { service.get_property("ExecMainStartTimestamp")? };
+        // u64::MAX when the memory of the unit is not accounted.
+        let memory: u64 = service.get_property("MemoryCurrent")?;
+        Ok(RuntimeStats::new(
+            service.get_property("NRestarts")?,
+            main_start_usec,
+            (memory != u64::MAX).then_some(memory),
+        ))
+    }
 
-        let exec_main_pid: u32 = service_proxy.get_property("ExecMainPID")?;
-        let exec_main_start_timestamp: u64 =
-        service_proxy.get_property("ExecMainStartTimestamp")?;
-        let exec_main_exit_timestamp: u64 = service_proxy.get_property("ExecMainExitTimestamp")?;
-        let exec_main_code: i32 = service_proxy.get_property("ExecMainCode")?;
-        let exec_main_status: i32 = service_proxy.get_property("ExecMainStatus")?;
+    fn get_resource_usage(&self, name: &str) -> Result<ResourceUsage, ServiceError> {
+        let unit_type = name.rsplit('.').next().unwrap_or_default();
+        let mut section = unit_type.to_string();
+        if let Some(first) = section.get_mut(0..1) {
+            first.make_ascii_uppercase();
+        }
+        let interface = section_interface(&section).ok_or_else(|| format!("{} units have no control group", unit_type))?;
+        let unit_path = self.unit_path(name)?;
+        let unit = self.unit_proxy(&unit_path, interface)?;
+        // u64::MAX when the accounting is off.
+        let cpu: u64 = unit.get_property("CPUUsageNSec")?;
+        let memory: u64 = unit.get_property("MemoryCurrent")?;
+        Ok(ResourceUsage {
+            cpu_nsec: (cpu != u64::MAX).then_some(cpu),
+            memory_bytes: (memory != u64::MAX).then_some(memory),
+        })
+    }
 
-        let main_pid: u32 = service_proxy.get_property("MainPID")?;
-        let control_pid: u32 = service_proxy.get_property("ControlPID")?;
+    fn get_slices(&self) -> Result<Vec<Slice>, ServiceError> {
+        let proxy = self.manager_proxy()?;
+        let units: Vec<SystemdUnit> = proxy.call("ListUnitsByPatterns", &(vec!["active"], vec!["*.slice"]))?;
+        units
+            .into_iter()
+            .map(|(name, _, _, _, _, _, unit_path, ..)| {
+                let mut slice = self.all_properties(&unit_path, "org.freedesktop.systemd1.Slice")?;
+                // u64::MAX when the accounting is off.
+                let cpu: u64 = slice.take("CPUUsageNSec")?;
+                let memory: u64 = slice.take("MemoryCurrent")?;
+                // Units in a slice require it, so do the slices beneath it.
+                let required_by: Vec<String> = self.unit_proxy(&unit_path, "org.freedesktop.systemd1.Unit")?.get_property("RequiredBy")?;
+                Ok(Slice {
+                    name,
+                    usage: ResourceUsage {
+                        cpu_nsec: (cpu != u64::MAX).then_some(cpu),
+                        memory_bytes: (memory != u64::MAX).then_some(memory),
+                    },
+                    units: required_by.into_iter().filter(|unit| !unit.ends_with(".slice")).collect(),
+                })
+            })
+            .collect()
+    }
 
-        let restart: String = service_proxy.get_property("Restart")?;
-        let restart_usec: u64 = service_proxy.get_property("RestartUSec")?;
+    fn get_unit_processes(&self, name: &str) -> Result<Vec<UnitProcess>, ServiceError> {
+        let proxy = self.manager_proxy()?;
+        // (control group, PID, command line) of every process of the unit.
+        let processes: Vec<(String, u32, String)> = proxy.call("GetUnitProcesses", &(name))?;
+        if processes.is_empty() {
+            return Ok(Vec::new());
+        }
 
-        let status_text: String = service_proxy.get_property("StatusText")?;
-        let result: String = service_proxy.get_property("Result")?;
+        // Read through `cat`, so that it also works over ssh; processes may exit meanwhile.
+        let stat_paths: Vec<String> = processes.iter().map(|(_, pid, _)| format!("/proc/{}/stat", pid)).collect();
+        let stat_args: Vec<&str> = stat_paths.iter().map(String::as_str).collect();
+        let output = self.command("cat", &stat_args).stderr(Stdio::null()).output()?;
+        let stats: HashMap<u32, ProcStat> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(parse_proc_stat)
+            .map(|stat| (stat.pid, stat))
+            .collect();
 
-        let user: String = service_proxy.get_property("User")?;
-        let group: String = service_proxy.get_property("Group")?;
+        // SAFETY: sysconf only reads system configuration values.
+        let (clock_ticks, page_size) = unsafe { (libc::sysconf(libc::_SC_CLK_TCK), libc::sysconf(libc::_SC_PAGESIZE)) };
+        let (clock_ticks, page_size) = (clock_ticks.max(1) as u64, page_size.max(1) as u64);
+        Ok(processes
+            .into_iter()
+            .filter_map(|(_, pid, command)| {
+                let stat = stats.get(&pid)?;
+                Some(UnitProcess::new(
+                    pid,
+                    stat.ppid,
+                    command,
+                    stat.cpu_ticks * 1_000_000 / clock_ticks,
+                    stat.rss_pages * page_size,
+                ))
+            })
+            .collect())
+    }
 
-        let limit_cpu: u64 = service_proxy.get_property("LimitCPU")?;
-        let limit_nofile: u64 = service_proxy.get_property("LimitNOFILE")?;
-        let limit_nproc: u64 = service_proxy.get_property("LimitNPROC")?;
-        let limit_memlock: u64 = service_proxy.get_property("LimitMEMLOCK")?;
-        let memory_limit: u64 = service_proxy.get_property("MemoryLimit")?;
-        let cpu_shares: u64 = service_proxy.get_property("CPUShares")?;
+    fn get_state_change_timestamp(&self, name: &str) -> Result<u64, ServiceError> {
+        let unit_path = self.unit_path(name)?;
+        let unit = self.unit_proxy(&unit_path, "org.freedesktop.systemd1.Unit")?;
+        Ok(unit.get_property("StateChangeTimestamp")?)
+    }
 
-        Ok(ServiceProperty::new(
-            exec_start,
-            exec_start_pre,
-            exec_start_post,
-            exec_stop,
-            exec_stop_post,
-            exec_main_pid,
-            exec_main_start_timestamp,
-            exec_main_exit_timestamp,
-            exec_main_code,
-            exec_main_status,
-            main_pid,
-            control_pid,
-            restart,
-            restart_usec,
-            status_text,
-            result,
-            user,
-            group,
-            limit_cpu,
-            limit_nofile,
-            limit_nproc,
-            limit_memlock,
-            memory_limit,
-            cpu_shares,
+    fn get_need_daemon_reload(&self, name: &str) -> Result<bool, ServiceError> {
+        let unit_path = self.unit_path(name)?;
+        let unit = self.unit_proxy(&unit_path, "org.freedesktop.systemd1.Unit")?;
+        let need_daemon_reload: bool = unit.get_property("NeedDaemonReload")?;
+        if self.unit_files_watch.load(Ordering::Relaxed) {
+            self.unit_files.lock().unwrap().need_daemon_reload.insert(name.to_string(), need_daemon_reload);
+        }
+        Ok(need_daemon_reload)
+    }
+
+    fn get_freezer_state(&self, name: &str) -> Result<String, ServiceError> {
+        let unit_path = self.unit_path(name)?;
+        let unit = self.unit_proxy(&unit_path, "org.freedesktop.systemd1.Unit")?;
+        let state: String = unit.get_property("FreezerState")?;
+        let mut states = self.freezer_states.lock().unwrap();
+        if state == "running" {
+            states.remove(name);
+        } else {
+            states.insert(name.to_string(), state.clone());
+        }
+        Ok(state)
+    }
+
+    fn get_boot_time(&self) -> Result<String, ServiceError> {
+        Ok(self.systemd_analyze(&["time"])?.trim().to_string())
+    }
+
+    fn get_system_summary(&self) -> Result<SystemSummary, ServiceError> {
+        let manager = self.manager_proxy()?;
+        // The kernel started with the machine; a user manager has no kernel timestamp, its
+        // userspace one being when it started.
+        let kernel: u64 = manager.get_property("KernelTimestamp")?;
+        let userspace: u64 = manager.get_property("UserspaceTimestamp")?;
+        Ok(SystemSummary::new(
+            manager.get_property("NNames")?,
+            manager.get_property("NFailedUnits")?,
+            manager.get_property("NJobs")?,
+            manager.get_property("SystemState")?,
+            manager.get_property("Virtualization")?,
+            if kernel > 0 { kernel } else { userspace },
         ))
     }
+
+    fn get_boot_blame(&self) -> Result<Vec<UnitActivation>, ServiceError> {
+        Ok(parse_blame(&self.systemd_analyze(&["blame"])?))
+    }
+
+    fn get_critical_chain(&self, unit: Option<&str>) -> Result<Vec<ChainLink>, ServiceError> {
+        let mut args = vec!["critical-chain"];
+        args.extend(unit);
+        Ok(parse_critical_chain(&self.systemd_analyze(&args)?))
+    }
+
+    fn get_security_scores(&self) -> Result<Vec<SecurityScore>, ServiceError> {
+        Ok(parse_security_scores(&self.systemd_analyze(&["security"])?))
+    }
+
+    fn get_security_audit(&self, name: &str) -> Result<SecurityAudit, ServiceError> {
+        parse_security_audit(&self.systemd_analyze(&["security", name])?)
+            .ok_or_else(|| ServiceError::Parse(format!("systemd-analyze printed no exposure for {}", name)))
+    }
+
+    fn get_unit_property(&self, name: &str) -> Result<UnitProperty, ServiceError> {
+        let unit_path = self.unit_path(name)?;
+
+        match name.rsplit_once('.').map_or("service", |(_, unit_type)| unit_type) {
+            "service" => Ok(UnitProperty::Service(Box::new(self.service_property(&unit_path)?))),
+            "timer" => Ok(UnitProperty::Timer(self.timer_property(&unit_path)?)),
+            "socket" => Ok(UnitProperty::Socket(self.socket_property(&unit_path)?)),
+            "mount" => {
+                let mut mount = self.all_properties(&unit_path, "org.freedesktop.systemd1.Mount")?;
+                Ok(UnitProperty::Mount(MountProperty::new(
+                    mount.take("What")?,
+                    mount.take("Where")?,
+                    mount.take("Type")?,
+                    mount.take("Options")?,
+                    mount.take("Result")?,
+                )))
+            }
+            unit_type => Err(format!("{} units have no type-specific properties", unit_type).into()),
+        }
+    }
 }
@@ -0,0 +1,34 @@
+use chrono::Local;
+use std::error::Error;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::time::Duration;
+
+/// File the duration of each step of the refresh pipeline is appended to, one line per step:
+/// "2026-10-17T09:12:44.118+02:00 list units 12.345 ms".
+pub struct TraceLog {
+    file: File,
+}
+
+impl TraceLog {
+    pub fn open(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(Self { file })
+    }
+
+    /// Failing to write loses the line only, the trace is a diagnostic aid.
+    pub fn write(&mut self, step: &str, duration: Duration) {
+        let _ = writeln!(
+            self.file,
+            "{} {} {:.3} ms",
+            Local::now().format("%Y-%m-%dT%H:%M:%S%.3f%:z"),
+            step,
+            duration.as_secs_f64() * 1000.0
+        );
+    }
+}
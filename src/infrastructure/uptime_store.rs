@@ -0,0 +1,42 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::domain::uptime::UptimeLog;
+
+const STATE_DIR: &str = "services-manager-tui";
+const UPTIME_FILE: &str = "uptime.json";
+
+/// `$XDG_STATE_HOME/services-manager-tui`, or `~/.local/state/...` when unset.
+pub fn state_dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_STATE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/state")))?;
+
+    Some(base.join(STATE_DIR))
+}
+
+pub fn uptime_path() -> Option<PathBuf> {
+    Some(state_dir()?.join(UPTIME_FILE))
+}
+
+/// The saved uptime, empty when nothing was saved yet.
+pub fn load_uptime(path: &Path) -> Result<UptimeLog, Box<dyn Error>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(serde_json::from_str(&content).map_err(|e| format!("{}: {}", path.display(), e))?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(UptimeLog::default()),
+        Err(e) => Err(format!("{}: {}", path.display(), e).into()),
+    }
+}
+
+/// Writes the uptime through a temporary file, so that a crash never leaves half of it.
+pub fn save_uptime(path: &Path, log: &UptimeLog) -> Result<(), Box<dyn Error>> {
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory).map_err(|e| format!("{}: {}", directory.display(), e))?;
+    }
+    let temporary = path.with_extension("json.tmp");
+    fs::write(&temporary, serde_json::to_string(log)?).map_err(|e| format!("{}: {}", temporary.display(), e))?;
+    fs::rename(&temporary, path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(())
+}
@@ -1,45 +1,185 @@
+mod cli;
+mod config;
 mod domain;
+mod errors;
 mod infrastructure;
 mod terminal;
 mod usecases;
+use clap::{CommandFactory, Parser};
+use clap_complete::CompleteEnv;
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+use cli::Cli;
+use config::{Column, Config};
+use domain::action_policy::ActionPolicy;
+use domain::service_repository::ServiceRepository;
 use terminal::app::App;
-use infrastructure::systemd_service_adapter::{SystemdServiceAdapter, ConnectionType};
+use terminal::app_state::AppState;
+use terminal::headless::{parse_keys, screenshot};
+use terminal::terminal_guard::{install_panic_hook, TerminalGuard};
+use infrastructure::backend::Backend;
+use infrastructure::demo_service_adapter::{DemoServiceAdapter, Fixture};
+use infrastructure::merged_service_adapter::MergedServiceAdapter;
+use infrastructure::session_recorder::SessionRecorder;
+use infrastructure::trace_log::TraceLog;
+use infrastructure::systemd_service_adapter::ConnectionType;
 use usecases::services_manager::ServicesManager;
 
+use std::io::{stdout, IsTerminal};
 use std::sync::mpsc;
 use std::cell::RefCell;
 use std::rc::Rc;
 
 use terminal::app::AppEvent;
 
-use terminal::components::details::ServiceDetails;
-use terminal::components::filter::Filter;
-use terminal::components::list::TableServices;
-use terminal::components::log::ServiceLog;
+use terminal::components::setup_wizard;
 
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
-    let terminal = ratatui::init();
+    install_panic_hook();
+    // Answers the shell when it asks for completions, `COMPLETE=bash systemd-manager-tui` printing
+    // the script to register them.
+    CompleteEnv::with_factory(Cli::command).complete();
+    let cli = Cli::parse();
+    if Config::is_first_run()
+        && cli.command.is_none()
+        && !cli.watch
+        && cli.serve.is_none()
+        && cli.screenshot.is_none()
+        && !cli.demo
+        && cli.backend != Some(Backend::Demo)
+        && stdout().is_terminal()
+    {
+        first_run_setup(cli.accessible)?;
+    }
+    let mut config = Config::load().map_err(|e| color_eyre::eyre::eyre!("Invalid configuration file {}", e))?;
+    config.accessible |= cli.accessible;
+    config.no_color |= cli.no_color || std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    config.ascii |= cli.ascii;
+    if let Some(trace) = &cli.trace {
+        config.trace = Some(trace.clone());
+    }
+    config.read_only |= cli.read_only;
+    if let Some(backend) = cli.backend {
+        config.backend = backend;
+    }
+    if cli.demo {
+        config.backend = Backend::Demo;
+    }
+    if !cli.sources.is_empty() {
+        config.sources = cli.sources.clone();
+    }
+    // A merged view tells its units apart by their source.
+    if !config.sources.is_empty() && !config.columns.contains(&Column::Source) {
+        config.columns.insert(0, Column::Source);
+    }
+    let config = Rc::new(config);
+    let connection_type = cli.connection_type();
+    if cli.watch {
+        return cli::watch(
+            cli.watch_filter,
+            cli.watch_output,
+            cli.host,
+            connection_type,
+            &config,
+        )
+        .map_err(|e| color_eyre::eyre::eyre!("{}", e));
+    }
+    if let Some(address) = cli.serve {
+        return cli::serve(address, cli.host, connection_type, &config).map_err(|e| color_eyre::eyre::eyre!("{}", e));
+    }
+    if let Some(command) = cli.command {
+        return cli::run(command, cli.host, connection_type, &cli.confirm, &config)
+            .map_err(|e| color_eyre::eyre::eyre!("{}", e));
+    }
+
+    if let Some(name) = &cli.profile
+        && config.profile(name).is_none()
+    {
+        return Err(color_eyre::eyre::eyre!("No profile named {} in the config", name));
+    }
+    let keys = match &cli.screenshot {
+        Some(script) => Some(parse_keys(script).map_err(|e| color_eyre::eyre::eyre!("{}", e))?),
+        None => None,
+    };
+
+    // Connected before the terminal is taken over, so that failing prints a readable message.
+    let repository: Box<dyn ServiceRepository> = if let Some(fixture) = &cli.fixture {
+        Fixture::load(fixture)
+            .map(|fixture| Box::new(DemoServiceAdapter::new(fixture, config.unit_types.clone())) as Box<dyn ServiceRepository>)
+    } else if config.sources.is_empty() {
+        config.backend.create_repository(ConnectionType::System, config.unit_types.clone())
+    } else {
+        MergedServiceAdapter::new(&config.sources, config.backend, config.unit_types.clone())
+            .map(|repository| Box::new(repository) as Box<dyn ServiceRepository>)
+    }
+    .map_err(|e| color_eyre::eyre::eyre!("{}", e))?;
 
     let (event_tx, event_rx) = mpsc::channel::<AppEvent>();
+    if config.backend == Backend::Demo && keys.is_none() {
+        event_tx
+            .send(AppEvent::Notice("Demo: example units, nothing on this machine is changed".to_string()))
+            .unwrap();
+    }
+
+    let mut usecase = ServicesManager::new(repository);
+    usecase.set_read_only(config.read_only);
+    usecase.set_policy(ActionPolicy::new(config.policy.clone()));
+    usecase.set_maintenance(config.maintenance.clone());
+    usecase.set_hooks(config.hooks.clone());
+    usecase.set_call_limits(config.call_limits());
+    if let Some(path) = &config.trace {
+        usecase.set_trace(TraceLog::open(path).map_err(|e| color_eyre::eyre::eyre!("{}", e))?);
+    }
+    let usecase = Rc::new(RefCell::new(usecase));
+    let state = AppState::new(event_tx.clone(), usecase.clone(), config.clone());
 
-    let usecase = Rc::new(RefCell::new(ServicesManager::new(Box::new(SystemdServiceAdapter::new(ConnectionType::System)?))));
-    let table_services = TableServices::new(event_tx.clone(), usecase.clone());
-    let filter = Filter::new(event_tx.clone());
-    let service_log = ServiceLog::new(event_tx.clone(), usecase.clone());
-    let details = ServiceDetails::new(event_tx.clone(), usecase.clone());
-
-    let mut app = App::new(
-        event_tx,
-        event_rx,
-        Rc::new(RefCell::new(table_services)),
-        Rc::new(RefCell::new(filter)),
-        Rc::new(RefCell::new(service_log)),
-        Rc::new(RefCell::new(details)),
-        usecase
-    );
+    let mut app = App::new(event_tx, event_rx, state, usecase, config);
+    if let Some(keys) = keys {
+        if let Some(name) = &cli.profile {
+            app.open_profile(name);
+        }
+        if cli.failed {
+            app.open_failed_units();
+        }
+        if let Some(path) = &cli.record {
+            app.record(SessionRecorder::create(path, cli.size).map_err(|e| color_eyre::eyre::eyre!("{}", e))?);
+        }
+        println!("{}", screenshot(app, keys, cli.size)?);
+        return Ok(());
+    }
+
+    // Restores the terminal on every way out, errors and panics included, before they are printed.
+    let _guard = TerminalGuard::enter()?;
+    let mut terminal = Terminal::new(app.backend(CrosstermBackend::new(stdout())))?;
+    if let Some(path) = &cli.record {
+        let size = terminal.size()?;
+        app.record(SessionRecorder::create(path, (size.width, size.height)).map_err(|e| color_eyre::eyre::eyre!("{}", e))?);
+    }
     app.init();
-    let result = app.run(terminal);
-    ratatui::restore();
-    result
+    match cli.profile {
+        Some(name) => app.open_profile(&name),
+        None => app.restore_session(),
+    }
+    if cli.failed {
+        app.open_failed_units();
+    }
+    app.run(&mut terminal)
+}
+
+/// Asks for the theme, keymap, tab and confirmations on the first launch and writes them as the
+/// config file; skipping it starts with the defaults and asks again next time.
+fn first_run_setup(accessible: bool) -> color_eyre::Result<()> {
+    let Some(path) = Config::path() else {
+        return Ok(());
+    };
+    let settings = {
+        let _guard = TerminalGuard::enter()?;
+        let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+        setup_wizard::run(&mut terminal, path, accessible)?
+    };
+    if let Some(settings) = settings {
+        Config::write_initial(&settings).map_err(|e| color_eyre::eyre::eyre!("Could not write the configuration file {}", e))?;
+    }
+    Ok(())
 }
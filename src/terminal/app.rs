@@ -1,31 +1,90 @@
 use color_eyre::Result;
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
-use ratatui::layout::{Alignment, Constraint, Layout, Rect};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Clear, Paragraph, Tabs};
-use ratatui::DefaultTerminal;
+use ratatui::widgets::{Paragraph, Tabs};
+use ratatui::backend::Backend;
+use ratatui::buffer::Buffer;
+use ratatui::Terminal;
 use ratatui::Frame;
-use std::sync::mpsc::{Receiver, Sender};
+use crossterm::cursor::Show;
+use crossterm::event::{DisableBracketedPaste, DisableFocusChange, EnableBracketedPaste, EnableFocusChange};
+use crossterm::execute;
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use std::io::stdout;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use crate::config::{names_unit, Config, Profile};
+use crate::domain::health::Health;
+use crate::domain::job::JobResult;
+use crate::domain::journal_entry::JournalEntry;
+use crate::domain::service::Service;
+use crate::domain::unit_transition::UnitTransition;
+use crate::errors::AppError;
+use crate::infrastructure::call_limits::CallLimits;
+use crate::infrastructure::profile_store::{load_last_profile, save_last_profile};
+use crate::infrastructure::session_store::{load_session, save_session, SessionState};
+use crate::infrastructure::session_recorder::SessionRecorder;
+use crate::infrastructure::health_probe;
 use crate::infrastructure::systemd_service_adapter::ConnectionType;
+use crate::usecases::action_queue::FinishedAction;
 use crate::usecases::services_manager::ServicesManager;
+use crate::usecases::timings::Stage;
+use crate::usecases::uptime_tracker::UptimeTracker;
+use crate::usecases::usage_sampler::UsageSampler;
 
+use super::headless::{key_name, screen_text};
+use super::components::command_line::Command;
+use super::components::unit_verify::UnitVerify;
+use super::components::dependencies::ServiceDependencies;
+use super::components::processes::ServiceProcesses;
 use super::components::details::ServiceDetails;
+use super::components::failure_alerts::{notify_desktop, ring_bell, FailureAlerts};
 use super::components::filter::Filter;
-use super::components::list::TableServices;
+use super::components::hosts::{HostSwitch, HostSwitcher};
+use super::components::list::{ServiceAction, TableServices};
+use super::components::list_refresh::ListRefresh;
+use super::components::system_summary::SystemSummaryBar;
+use super::components::lock::ScreenLock;
+use super::components::notifications::Notifications;
+use super::components::status_bar::{ActionOutcome, StatusBar};
+use super::components::timings::TimingsOverlay;
+use super::components::toasts::Toasts;
+use super::components::unit_tabs::UnitTab;
+use super::app_state::{AppState, Overlays};
+use super::plain_backend::PlainBackend;
+use super::theme::Theme;
 use super::components::log::ServiceLog;
 
+/// How often the toasts are checked for expiry while shown.
+const TOAST_TICK: Duration = Duration::from_millis(250);
+/// The panes are only taken while an event is handled, which does not reenter the app.
+const STATE_LENT: &str = "the panes are lent twice";
+/// How often the connection to the service manager is checked, and reopened once lost.
+const CONNECTION_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+/// Columns each pane of the split view needs, the list being shown alone on narrower screens.
+const PANE_MIN_WIDTH: u16 = 60;
+/// Bounds of the percent of the width the list takes in the split view.
+const SPLIT_RATIO_RANGE: (u16, u16) = (20, 80);
+/// Rows below which the list collapses the filter to a line and leaves the crash-loop banner out.
+const COMPACT_HEIGHT: u16 = 20;
+
 #[derive(PartialEq)]
 enum Status {
     List,
     Log,
     Details,
+    Dependencies,
+    Processes,
 }
 
 pub enum Actions {
@@ -35,44 +94,152 @@ pub enum Actions {
     ResetList,
     GoLog,
     GoDetails,
-    Updatelog((String, String)),
+    /// Shows the status, properties, unit file or history tab of the details.
+    GoDetailsTab(UnitTab),
+    GoDependencies,
+    GoProcesses,
+    /// Tick of the auto-refresh of the processes tab, ignored once it is left.
+    RefreshProcesses,
+    JumpToService(String),
+    /// Shows another unit in the details, e.g. the service a timer triggers.
+    ShowInDetails(String),
+    Updatelog((String, Vec<JournalEntry>)),
     #[allow(dead_code)]
     UpdateDetails,
     Filter(String),
     UpdateIgnoreListKeys(bool),
+    Lock,
+    CycleTheme,
+    RunBatchStep,
+    /// Runs the action on the units one after the other, in the given order.
+    RunOnUnits(ServiceAction, Vec<String>),
+    OpenPathLookup,
+    OpenTopTalkers,
+    OpenErrorHistory,
+    OpenFailedUnits,
+    OpenTargets,
+    OpenNewUnit,
+    /// Hands the terminal to the editor for the unit file, then enables and starts the unit when set.
+    EditUnit(String, bool),
+    /// Hands the terminal to a shell in a directory of the host of the unit.
+    OpenShell(String, String),
+    OpenSessionChanges,
+    OpenNotifications,
+    /// Fetches the services again, keeping the filter and selection.
+    ReloadList,
+    /// Tick of the auto-refresh of the list, ignored while it is paused or hidden.
+    RefreshList,
+    /// The list fetched the services again.
+    ServicesFetched,
+    /// The list shows other rows, whose state the listing left out is read again.
+    CheckShownRows,
+    /// An action was performed on a unit, described as e.g. "restart of nginx.service".
+    ActionPerformed(String, ActionOutcome),
+    RefreshTopTalkers,
+    /// Tick of the CPU and memory sampling of the usage graphs.
+    SampleUsage,
+    /// Tick of the refresh of the properties tab, ignored while it is not shown.
+    RefreshProperties,
+    CheckFailures,
+    NextHost,
+    /// Tick of the check of the connection to the service manager.
+    CheckConnection,
+    /// Opens another connection once the status bar tells it is being done.
+    Reconnect,
+    /// A line of the command line was run.
+    RunCommand(Command),
+    /// Quits at once, the changes of the session already reverted or kept.
+    Quit,
 }
 
 pub enum AppEvent {
     Key(KeyEvent),
+    /// Text pasted at once (bracketed paste), instead of one key event per character.
+    Paste(String),
     Action(Actions),
-    Error(String),
+    Error(AppError),
+    Notice(String),
+    JobRemoved(JobResult),
+    /// A queued start, stop, restart or reload returned.
+    ActionFinished(FinishedAction),
+    /// A watched unit settled in another active state.
+    UnitTransition(UnitTransition),
+    /// A health probe of a unit answered.
+    Health(String, Health),
+    /// The terminal gained (true) or lost (false) the focus.
+    Focus(bool),
+    /// The terminal was resized, the next draw picks up the new size.
+    Resize,
 }
 
-fn get_user_friendly_error(error: &str) -> &str {
-    if error.contains("org.freedesktop.DBus.Error.InteractiveAuthorizationRequired") {
-        "You do not have the permission to do that. Try running the program with sudo."
-    } else if error.contains("org.freedesktop.DBus.Error.ServiceUnknown") {
-        "The requested service is not available or not running."
-    } else if error.contains("org.freedesktop.DBus.Error.NoReply") {
-        "The service did not respond in time. It might be busy or not functioning properly."
-    } else if error.contains("org.freedesktop.DBus.Error.AccessDenied") {
-        "Access denied. You don't have sufficient permissions for this operation."
-    } else if error.contains("org.freedesktop.systemd1.NoSuchUnit") {
-        "The requested service unit doesn't exist."
-    } else {
-        error
-    }
+/// Plain text of the shortcut lines, for the accessible mode.
+fn linear_shortcuts(shortcuts: Vec<Line<'_>>) -> Vec<String> {
+    shortcuts
+        .iter()
+        .map(|line| line.spans.iter().map(|span| span.content.as_ref()).collect::<String>())
+        .filter(|line| !line.is_empty())
+        .collect()
 }
-fn spawn_key_event_listener(event_tx: Sender<AppEvent>) {
+
+fn is_quit_key(key: &KeyEvent) -> bool {
+    key.modifiers == KeyModifiers::CONTROL
+        && matches!(key.code, KeyCode::Char('c') | KeyCode::Char('C'))
+}
+
+/// Reads the terminal events until the receiver is gone, leaving the terminal alone while
+/// `paused` is set, e.g. to an editor. Esc cancels the slow call the main thread is waiting
+/// for, if any, instead of reaching it.
+fn spawn_key_event_listener(
+    event_tx: Sender<AppEvent>,
+    idle_timeout: Option<Duration>,
+    paused: Arc<AtomicBool>,
+    call_limits: CallLimits,
+) {
     thread::spawn(move || {
+        let mut last_key_press = Instant::now();
         loop {
+            if paused.load(Ordering::Relaxed) {
+                // Nor is the screen locked while the terminal is away.
+                last_key_press = Instant::now();
+                thread::sleep(Duration::from_millis(50));
+                continue;
+            }
             if event::poll(Duration::from_millis(100)).unwrap_or(false) {
-                if let Ok(Event::Key(key_event)) = event::read() {
-                    if key_event.kind == KeyEventKind::Press
-                        && event_tx.send(AppEvent::Key(key_event)).is_err()
+                let app_event = match event::read() {
+                    Ok(Event::Key(key_event))
+                        if key_event.kind == KeyEventKind::Press
+                            && key_event.code == KeyCode::Esc
+                            && call_limits.cancel() =>
                     {
-                        break;
+                        last_key_press = Instant::now();
+                        None
+                    }
+                    Ok(Event::Key(key_event)) if key_event.kind == KeyEventKind::Press => {
+                        last_key_press = Instant::now();
+                        Some(AppEvent::Key(key_event))
                     }
+                    Ok(Event::Paste(text)) => {
+                        last_key_press = Instant::now();
+                        Some(AppEvent::Paste(text))
+                    }
+                    Ok(Event::Resize(..)) => Some(AppEvent::Resize),
+                    Ok(Event::FocusGained) => Some(AppEvent::Focus(true)),
+                    Ok(Event::FocusLost) => Some(AppEvent::Focus(false)),
+                    _ => None,
+                };
+                if let Some(app_event) = app_event
+                    && event_tx.send(app_event).is_err()
+                {
+                    break;
+                }
+            }
+
+            if let Some(timeout) = idle_timeout
+                && last_key_press.elapsed() >= timeout
+            {
+                last_key_press = Instant::now();
+                if event_tx.send(AppEvent::Action(Actions::Lock)).is_err() {
+                    break;
                 }
             }
         }
@@ -81,79 +248,888 @@ fn spawn_key_event_listener(event_tx: Sender<AppEvent>) {
 pub struct App {
     running: bool,
     status: Status,
-    table_service: Rc<RefCell<TableServices>>,
-    filter: Rc<RefCell<Filter>>,
-    service_log: Rc<RefCell<ServiceLog>>,
-    details: Rc<RefCell<ServiceDetails>>,
+    /// The panes, `None` while lent to the handling of an event.
+    state: Option<AppState>,
     usecases: Rc<RefCell<ServicesManager>>,
     event_rx: Receiver<AppEvent>,
     event_tx: Sender<AppEvent>,
     selected_tab_index: usize,
+    screen_lock: ScreenLock,
+    failure_alerts: FailureAlerts,
+    list_refresh: ListRefresh,
+    system_summary: SystemSummaryBar,
+    uptime: UptimeTracker,
+    usage: UsageSampler,
+    toasts: Toasts,
+    status_bar: StatusBar,
+    timings: TimingsOverlay,
+    /// The selection in plain words, above the status bar, with the `announce` setting.
+    announcement: Option<String>,
+    hosts: HostSwitcher,
+    theme: Theme,
+    input_paused: Arc<AtomicBool>,
+    /// Whether the list shows the details of the selected unit beside it, on wide terminals.
+    split_view: bool,
+    /// The unit the right pane of the split view shows, `None` while the pane is not shown.
+    split_unit: Option<String>,
+    /// Percent of the width the list takes in the split view.
+    split_ratio: u16,
+    /// The profiles of the config, then the layouts saved since the start.
+    profiles: Vec<Profile>,
+    /// Index of the profile shown in `profiles`, `None` for the default layout.
+    profile: Option<usize>,
+    /// Keys pressed instead of reading the terminal, for the headless runs.
+    script: Option<VecDeque<KeyEvent>>,
+    /// Where the keys and screens go with `--record`.
+    recorder: Option<SessionRecorder>,
+    config: Rc<Config>,
 }
 
 impl App {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         event_tx: Sender<AppEvent>, 
         event_rx: Receiver<AppEvent>, 
-        table_service: Rc<RefCell<TableServices>>,
-        filter: Rc<RefCell<Filter>>,
-        service_log: Rc<RefCell<ServiceLog>>,
-        details: Rc<RefCell<ServiceDetails>>,
-        usecases: Rc<RefCell<ServicesManager>>
+        state: AppState,
+        usecases: Rc<RefCell<ServicesManager>>,
+        config: Rc<Config>,
     ) -> Self {
+        let hosts = HostSwitcher::new(event_tx.clone(), usecases.clone(), config.clone());
+        let mut system_summary = SystemSummaryBar::new(usecases.clone(), config.clone());
+        system_summary.refresh();
+        let (uptime, uptime_error) = UptimeTracker::new(config.uptime.units.clone());
+        if let Some(e) = uptime_error {
+            event_tx.send(AppEvent::Error(e.into())).unwrap();
+        }
         Self {
             running: true,
             status: Status::List,
-            table_service,
-            filter,
-            service_log,
-            details,
+            state: Some(state),
             usecases,
             event_rx,
             event_tx,
-            selected_tab_index: 0
+            selected_tab_index: 0,
+            screen_lock: ScreenLock::new(config.clone()),
+            failure_alerts: FailureAlerts::new(config.clone()),
+            list_refresh: ListRefresh::new(config.clone()),
+            system_summary,
+            uptime,
+            usage: UsageSampler::new(&config.usage),
+            toasts: Toasts::new(Duration::from_secs(config.toast_duration), config.locale),
+            status_bar: StatusBar::default(),
+            timings: TimingsOverlay::default(),
+            announcement: None,
+            hosts,
+            theme: Theme::with_overrides(&config.theme, &config.colors),
+            input_paused: Arc::new(AtomicBool::new(false)),
+            split_view: config.split_view,
+            split_unit: None,
+            split_ratio: config.split_ratio.clamp(SPLIT_RATIO_RANGE.0, SPLIT_RATIO_RANGE.1),
+            profiles: config.profiles.clone(),
+            profile: None,
+            script: None,
+            recorder: None,
+            config,
         }
     }
 
     pub fn init(&mut self) {
-        spawn_key_event_listener(self.event_tx.clone());
+        spawn_key_event_listener(
+            self.event_tx.clone(),
+            self.config.lock.idle_timeout(),
+            self.input_paused.clone(),
+            self.usecases.borrow().call_limits(),
+        );
+        let state = self.state.as_ref().expect(STATE_LENT);
+        state.overlays.top_talkers.spawn_background_check(self.event_tx.clone());
+        self.failure_alerts.spawn_background_check(self.event_tx.clone());
+        self.list_refresh.spawn_timer(self.event_tx.clone());
+        // Values changing every second would be read again and again by screen readers.
+        if !self.config.accessible {
+            self.spawn_usage_sampler();
+            self.spawn_properties_refresh();
+        }
+        self.spawn_connection_check();
+        self.spawn_health_probes();
+        self.watch_jobs();
+        self.watch_actions();
+        self.watch_transitions();
+    }
+
+    /// Presses `keys` one after the other instead of reading the terminal, each once the events
+    /// caused by the previous one are handled, and stops after the last. Nothing is saved on
+    /// stopping. Of the background threads, only the one running the queued actions is started.
+    pub fn script(&mut self, keys: Vec<KeyEvent>) {
+        self.script = Some(keys.into());
+        self.watch_actions();
+    }
+
+    /// Writes every key pressed and every screen drawn from now on to `recorder`.
+    pub fn record(&mut self, recorder: SessionRecorder) {
+        self.recorder = Some(recorder);
+    }
+
+    fn record_screen(&mut self, buffer: &Buffer) {
+        if let Some(recorder) = &mut self.recorder {
+            recorder.screen(screen_text(buffer));
+        }
+    }
+
+    /// Starts on the failed units view, for `--failed`.
+    pub fn open_failed_units(&mut self) {
+        self.state.as_mut().expect(STATE_LENT).overlays.failed_units.open();
+    }
+
+    /// Lends the panes to `update`, for the calls changing both them and the app.
+    fn with_state<T>(&mut self, update: impl FnOnce(&mut Self, &mut AppState) -> T) -> T {
+        let mut state = self.state.take().expect(STATE_LENT);
+        let result = update(self, &mut state);
+        self.state = Some(state);
+        result
+    }
+
+    /// Starts on a profile of the config, for `--profile` or the one used last.
+    pub fn open_profile(&mut self, name: &str) {
+        let Some(index) = self.profiles.iter().position(|profile| profile.name == name) else {
+            return;
+        };
+        self.with_state(|app, state| {
+            app.apply_profile(Some(index), &mut state.table_service, &mut state.filter, &mut state.details)
+        });
+    }
+
+    /// Puts the TUI back where it was left: tab, filter, sort, columns, selected unit, scroll and
+    /// unit tab. Units gone since are left unselected.
+    pub fn restore_session(&mut self) {
+        let last_profile = load_last_profile();
+        let Some(session) = load_session() else {
+            if let Some(name) = last_profile {
+                self.open_profile(&name);
+            }
+            if self.config.session && self.config.sources.is_empty() {
+                self.selected_tab_index = 1;
+                self.update_connection_and_reset();
+            }
+            return;
+        };
+        // The layout of the profile is part of the state, only which one it was is kept.
+        self.profile = last_profile.and_then(|name| self.profiles.iter().position(|profile| profile.name == name));
+        self.with_state(|app, state| app.restore_state(state, session));
+    }
+
+    fn restore_state(&mut self, state: &mut AppState, session: SessionState) {
+        let AppState { table_service, filter, details, .. } = state;
+        self.apply_panes(self.profile, details);
+
+        if session.session_tab
+            && self.config.sources.is_empty()
+            && self.usecases.borrow_mut().change_repository_connection(ConnectionType::Session).is_ok()
+        {
+            self.selected_tab_index = 1;
+            table_service.set_usecase(self.usecases.clone());
+        }
+        let columns = if session.columns.is_empty() { self.config.columns.clone() } else { session.columns };
+        table_service.set_layout(columns, session.sort);
+        filter.restore_input(&session.filter);
+        table_service.refresh(session.filter);
+        let Some(selected) = session.selected else {
+            return;
+        };
+        if !table_service.select_by_name(&selected) {
+            return;
+        }
+        table_service.set_offset(session.offset);
+
+        if let Some(tab) = session.view.as_deref().and_then(UnitTab::from_title) {
+            if tab.is_details() {
+                details.set_tab(tab);
+                details.set_scroll(session.scroll);
+            }
+            self.event_tx.send(AppEvent::Action(tab.action())).unwrap();
+        }
+    }
+
+    /// Where the TUI is, to restore it on the next start.
+    fn session_state(&self, table_service: &TableServices, filter: &Filter, details: &ServiceDetails) -> SessionState {
+        let view = match self.status {
+            Status::List => None,
+            Status::Log => Some(UnitTab::Logs),
+            Status::Details => Some(details.tab()),
+            Status::Dependencies => Some(UnitTab::Dependencies),
+            Status::Processes => Some(UnitTab::Processes),
+        };
+        SessionState {
+            session_tab: self.selected_tab_index != 0,
+            filter: filter.input.clone(),
+            sort: table_service.sort_column(),
+            columns: table_service.columns().to_vec(),
+            selected: table_service.get_selected_service().map(|service| service.name().to_string()),
+            offset: table_service.offset(),
+            view: view.map(|tab| tab.title().to_string()),
+            scroll: if self.status == Status::Details { details.scroll() } else { 0 },
+        }
+    }
+
+    /// Switches to the next profile, the default layout coming after the last one.
+    fn next_profile(&mut self, table_service: &mut TableServices, filter: &mut Filter, details: &mut ServiceDetails) {
+        if self.profiles.is_empty() {
+            self.event_tx
+                .send(AppEvent::Notice(self.config.locale.tr("No profile in the config, see [[profiles]]").to_string()))
+                .unwrap();
+            return;
+        }
+        let next = match self.profile {
+            None => Some(0),
+            Some(index) if index + 1 < self.profiles.len() => Some(index + 1),
+            Some(_) => None,
+        };
+        self.apply_profile(next, table_service, filter, details);
+        let name = next.map_or(self.config.locale.tr("default"), |index| self.profiles[index].name.as_str());
+        self.event_tx.send(AppEvent::Notice(self.config.locale.format("Profile: {}", &[name]))).unwrap();
+        if let Err(e) = save_last_profile(next.map(|index| self.profiles[index].name.as_str())) {
+            self.event_tx.send(AppEvent::Error(e.into())).unwrap();
+        }
+    }
+
+    /// Applies the filter, sort, columns and panes of the profile, or of the config for `None`.
+    fn apply_profile(
+        &mut self,
+        index: Option<usize>,
+        table_service: &mut TableServices,
+        filter: &mut Filter,
+        details: &mut ServiceDetails,
+    ) {
+        self.profile = index;
+        self.apply_panes(index, details);
+        let config = self.config.clone();
+        match index.map(|index| &self.profiles[index]) {
+            Some(profile) => {
+                let columns = if profile.columns.is_empty() { config.columns.clone() } else { profile.columns.clone() };
+                table_service.set_layout(columns, profile.sort);
+                filter.set_input(&profile.filter);
+            }
+            None => {
+                table_service.set_layout(config.columns.clone(), None);
+                filter.set_input(&config.default_filter);
+            }
+        }
+    }
+
+    /// Applies the split view and the height of the graphs of the profile, the settings of the
+    /// config standing for what it leaves out.
+    fn apply_panes(&mut self, index: Option<usize>, details: &mut ServiceDetails) {
+        let profile = index.map(|index| &self.profiles[index]);
+        self.split_view = profile.and_then(|profile| profile.split_view).unwrap_or(self.config.split_view);
+        self.split_ratio = profile
+            .and_then(|profile| profile.split_ratio)
+            .unwrap_or(self.config.split_ratio)
+            .clamp(SPLIT_RATIO_RANGE.0, SPLIT_RATIO_RANGE.1);
+        details.set_graphs_height(profile.and_then(|profile| profile.graphs_height).unwrap_or(self.config.graphs_height));
+    }
+
+    /// Widens or narrows the list of the split view, or resizes the usage graphs, by a step of
+    /// the key. Returns whether the key was one of those.
+    fn resize_panes(&mut self, key: &KeyEvent, details: &mut ServiceDetails) -> bool {
+        let keys = &self.config.keybindings;
+        let (low, high) = SPLIT_RATIO_RANGE;
+        if keys.grow_list.matches(key) {
+            self.split_ratio = (self.split_ratio + 5).min(high);
+        } else if keys.shrink_list.matches(key) {
+            self.split_ratio = self.split_ratio.saturating_sub(5).max(low);
+        } else if keys.grow_graphs.matches(key) {
+            details.set_graphs_height(details.graphs_height() + 1);
+        } else if keys.shrink_graphs.matches(key) {
+            details.set_graphs_height(details.graphs_height().saturating_sub(1));
+        } else {
+            return false;
+        }
+        true
+    }
+
+    /// Appends the filter, sort, columns and panes shown to the config as a new profile, named
+    /// after the first "layout-N" not taken, and switches to it.
+    fn save_layout(&mut self, table_service: &TableServices, filter: &Filter, details: &ServiceDetails) {
+        if self.script.is_some() {
+            let error = self.config.locale.tr("Nothing is saved in a headless run").to_string();
+            self.event_tx.send(AppEvent::Error(error.into())).unwrap();
+            return;
+        }
+        let name = (1..)
+            .map(|number| format!("layout-{}", number))
+            .find(|name| self.profiles.iter().all(|profile| profile.name != *name))
+            .unwrap_or_default();
+        let profile = Profile {
+            name: name.clone(),
+            filter: filter.input.clone(),
+            sort: table_service.sort_column(),
+            columns: table_service.columns().to_vec(),
+            split_view: Some(self.split_view),
+            split_ratio: Some(self.split_ratio),
+            graphs_height: Some(details.graphs_height()),
+        };
+        match Config::append_profile(&profile) {
+            Ok(path) => {
+                self.profiles.push(profile);
+                self.profile = Some(self.profiles.len() - 1);
+                let notice = self.config.locale.format(
+                    "Saved the layout as the profile \"{}\" in {}",
+                    &[&name, &path.display().to_string()],
+                );
+                self.event_tx.send(AppEvent::Notice(notice)).unwrap();
+                if let Err(e) = save_last_profile(Some(&name)) {
+                    self.event_tx.send(AppEvent::Error(e.into())).unwrap();
+                }
+            }
+            Err(e) => self.event_tx.send(AppEvent::Error(e.into())).unwrap(),
+        }
+    }
+
+    /// Forwards the results of finished jobs to the status bar.
+    fn watch_jobs(&self) {
+        let (job_tx, job_rx) = mpsc::channel::<JobResult>();
+        let event_tx = self.event_tx.clone();
+        thread::spawn(move || {
+            for job in job_rx {
+                if event_tx.send(AppEvent::JobRemoved(job)).is_err() {
+                    break;
+                }
+            }
+        });
+        if let Err(e) = self.usecases.borrow_mut().watch_jobs(job_tx) {
+            self.event_tx.send(AppEvent::Error(e.into())).unwrap();
+        }
+    }
+
+    /// Forwards the queued actions that returned to the list.
+    fn watch_actions(&self) {
+        let (finished_tx, finished_rx) = mpsc::channel::<FinishedAction>();
+        let event_tx = self.event_tx.clone();
+        thread::spawn(move || {
+            for finished in finished_rx {
+                if event_tx.send(AppEvent::ActionFinished(finished)).is_err() {
+                    break;
+                }
+            }
+        });
+        if let Err(e) = self.usecases.borrow_mut().watch_actions(finished_tx) {
+            self.event_tx.send(AppEvent::Error(e.into())).unwrap();
+        }
+    }
+
+    /// Ticks the sampling of the usage graphs, the samples being read on the main thread.
+    fn spawn_usage_sampler(&self) {
+        let sender = self.event_tx.clone();
+        let interval = UsageSampler::interval(&self.config.usage);
+        thread::spawn(move || {
+            while sender.send(AppEvent::Action(Actions::SampleUsage)).is_ok() {
+                thread::sleep(interval);
+            }
+        });
+    }
+
+    /// Ticks the refresh of the properties tab every `refresh_interval`, like the log.
+    fn spawn_properties_refresh(&self) {
+        let sender = self.event_tx.clone();
+        let interval = Duration::from_millis(self.config.refresh_interval.max(100));
+        thread::spawn(move || {
+            while sender.send(AppEvent::Action(Actions::RefreshProperties)).is_ok() {
+                thread::sleep(interval);
+            }
+        });
+    }
+
+    /// Runs the health probes of the config one after the other every `interval`, off the main
+    /// thread since a probe may wait for its timeout.
+    fn spawn_health_probes(&self) {
+        let settings = &self.config.health;
+        if settings.probes.is_empty() {
+            return;
+        }
+        let sender = self.event_tx.clone();
+        let probes = settings.probes.clone();
+        let interval = Duration::from_secs(settings.interval.max(1));
+        let timeout = Duration::from_secs(settings.timeout.max(1));
+        thread::spawn(move || {
+            loop {
+                for probe in &probes {
+                    let health = health_probe::probe(probe, timeout);
+                    if sender.send(AppEvent::Health(probe.unit_name(), health)).is_err() {
+                        return;
+                    }
+                }
+                thread::sleep(interval);
+            }
+        });
+    }
+
+    /// Ticks the check of the connection, lost e.g. when dbus restarts.
+    fn spawn_connection_check(&self) {
+        let sender = self.event_tx.clone();
+        thread::spawn(move || {
+            while sender.send(AppEvent::Action(Actions::CheckConnection)).is_ok() {
+                thread::sleep(CONNECTION_CHECK_INTERVAL);
+            }
+        });
+    }
+
+    /// Forwards the state changes of the watched units to the notifications.
+    fn watch_transitions(&self) {
+        let (transition_tx, transition_rx) = mpsc::channel::<UnitTransition>();
+        let event_tx = self.event_tx.clone();
+        thread::spawn(move || {
+            for transition in transition_rx {
+                if event_tx.send(AppEvent::UnitTransition(transition)).is_err() {
+                    break;
+                }
+            }
+        });
+        self.usecases.borrow_mut().watch_transitions(transition_tx);
+    }
+
+    /// Records the state change in the notifications, alerting when the unit went down.
+    fn unit_transition(&mut self, transition: UnitTransition, notifications: &mut Notifications) {
+        self.usecases.borrow().unit_changed(&transition);
+        if transition.is_alarming() {
+            let settings = &self.config.alerts;
+            if settings.bell {
+                ring_bell();
+            }
+            if settings.desktop {
+                notify_desktop(format!("{} is {}", transition.unit(), transition.to()), transition.describe());
+            }
+            self.toasts.push_notice(format!("{} is {} ({})", transition.unit(), transition.to(), transition.sub_state()));
+        }
+        notifications.push(transition);
+    }
+
+    /// Leaves the screen to the editor of the unit file until it exits, then verifies the unit
+    /// and enables and starts it when asked and it has no errors.
+    fn edit_unit<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        name: &str,
+        enable_and_start: bool,
+        unit_verify: &mut UnitVerify,
+    ) -> Result<()> {
+        let command = self.usecases.borrow().edit_unit_command(name);
+        let mut command = match command {
+            Ok(command) => command,
+            Err(e) => {
+                self.event_tx.send(AppEvent::Error(e.into()))?;
+                return Ok(());
+            }
+        };
+
+        self.input_paused.store(true, Ordering::Relaxed);
+        // Lets the key listener finish its poll, so the editor gets every key.
+        thread::sleep(Duration::from_millis(150));
+        execute!(stdout(), DisableBracketedPaste, DisableFocusChange, LeaveAlternateScreen, Show)?;
+        disable_raw_mode()?;
+        let status = command.status();
+        enable_raw_mode()?;
+        execute!(stdout(), EnterAlternateScreen, EnableBracketedPaste, EnableFocusChange)?;
+        self.input_paused.store(false, Ordering::Relaxed);
+        terminal.clear()?;
+
+        let locale = self.config.locale;
+        let event = match status {
+            Ok(status) if !status.success() => {
+                AppEvent::Error(locale.format("Editing {} failed ({})", &[name, &status.to_string()]).into())
+            }
+            Err(e) => AppEvent::Error(locale.format("Could not edit {}: {}", &[name, &e.to_string()]).into()),
+            // Verified before it is started, a typo shows with its line instead of failing the start.
+            Ok(_) => match (unit_verify.check(name), enable_and_start) {
+                (true, true) => {
+                    AppEvent::Error(locale.format("{} has errors, it was neither enabled nor started", &[name]).into())
+                }
+                (_, false) => AppEvent::Notice(locale.format("Saved {}", &[name])),
+                (false, true) => match self.usecases.borrow().enable_and_start(name) {
+                    Ok(()) => AppEvent::Notice(locale.format("Saved, enabled and started {}", &[name])),
+                    Err(e) => AppEvent::Error(e.into()),
+                },
+            },
+        };
+        self.event_tx.send(event)?;
+        Ok(())
     }
 
-    pub fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
+    fn open_shell<B: Backend>(&mut self, terminal: &mut Terminal<B>, name: &str, directory: &str) -> Result<()> {
+        let command = self.usecases.borrow().shell_command(name, directory);
+        let mut command = match command {
+            Ok(command) => command,
+            Err(e) => {
+                self.event_tx.send(AppEvent::Error(e.into()))?;
+                return Ok(());
+            }
+        };
+
+        self.input_paused.store(true, Ordering::Relaxed);
+        thread::sleep(Duration::from_millis(150));
+        execute!(stdout(), DisableBracketedPaste, DisableFocusChange, LeaveAlternateScreen, Show)?;
+        disable_raw_mode()?;
+        println!("{}", self.config.locale.format("Shell in {} for {}, exit to go back", &[directory, name]));
+        let status = command.status();
+        enable_raw_mode()?;
+        execute!(stdout(), EnterAlternateScreen, EnableBracketedPaste, EnableFocusChange)?;
+        self.input_paused.store(false, Ordering::Relaxed);
+        terminal.clear()?;
+
+        if let Err(e) = status {
+            let error = self.config.locale.format("Could not open a shell in {}: {}", &[directory, &e.to_string()]);
+            self.event_tx.send(AppEvent::Error(error.into()))?;
+        }
+        Ok(())
+    }
+
+    pub fn run<B: Backend>(mut self, terminal: &mut Terminal<B>) -> Result<()> {
         self.running = true;
 
-        let binding_table_service = self.table_service.clone();
-        let mut table_service = binding_table_service.borrow_mut();
-        let binding_filter = self.filter.clone();
-        let mut filter = binding_filter.borrow_mut();
-        let binding_log = self.service_log.clone();
-        let mut log = binding_log.borrow_mut();
-        let bindind_details = self.details.clone();
-        let mut details = bindind_details.borrow_mut();
+        let mut state = self.state.take().expect(STATE_LENT);
+        let AppState { table_service, filter, log, details, dependencies, processes, overlays } = &mut state;
 
         while self.running {
-            match self.status {
-                Status::Log => self.draw_log_status(&mut terminal, &mut log)?,
-                Status::List => self.draw_list_status(&mut terminal, &mut filter, &mut table_service)?,
-                Status::Details => self.draw_details_status(&mut terminal, &mut details)?,
+            self.toasts.expire();
+            table_service.expire_chord();
+            if self.config.announce {
+                self.announcement = self.announce(table_service, details);
+            }
+            if !self.config.accessible {
+                self.sync_split_view(terminal.size()?.width, table_service, details);
+            }
+            let started = Instant::now();
+            if self.config.accessible {
+                self.draw_linear_status(
+                    terminal,
+                    filter,
+                    table_service,
+                    log,
+                    details,
+                    dependencies,
+                    processes,
+                    overlays,
+                )?;
+            } else {
+                match self.status {
+                    Status::Log => self.draw_log_status(terminal, log, overlays)?,
+                    Status::List => self.draw_list_status(terminal, filter, table_service, details, overlays)?,
+                    Status::Details => self.draw_details_status(terminal, details, overlays)?,
+                    Status::Dependencies => self.draw_dependencies_status(terminal, dependencies, overlays)?,
+                    Status::Processes => self.draw_processes_status(terminal, processes, overlays)?,
+                }
             }
+            self.usecases.borrow().record_timing(Stage::Render, started.elapsed());
 
-            match self.event_rx.recv()? {
+            // While toasts are shown or a chord is pending, wake up to let them expire.
+            let timeout = [
+                (!self.toasts.is_empty()).then_some(TOAST_TICK),
+                table_service.chord_timeout(),
+                table_service.spinner_timeout(),
+            ]
+            .into_iter()
+            .flatten()
+            .min();
+            let event = if let Some(script) = &mut self.script {
+                match self.event_rx.try_recv() {
+                    Ok(event) => event,
+                    // The next key waits for the queued actions the previous ones asked for.
+                    Err(_) if self.usecases.borrow().has_pending_actions() => self.event_rx.recv()?,
+                    Err(_) => match script.pop_front() {
+                        Some(key) => AppEvent::Key(key),
+                        None => break,
+                    },
+                }
+            } else if let Some(timeout) = timeout {
+                match self.event_rx.recv_timeout(timeout) {
+                    Ok(event) => event,
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            } else {
+                self.event_rx.recv()?
+            };
+
+            if let (Some(recorder), AppEvent::Key(key)) = (&mut self.recorder, &event)
+                && let Some(name) = key_name(key)
+            {
+                recorder.key(name);
+            }
+
+            match event {
+                AppEvent::Key(key) if self.screen_lock.is_locked() => {
+                    if is_quit_key(&key) {
+                        self.quit(overlays);
+                    } else {
+                        self.screen_lock.on_key_event(key);
+                    }
+                }
+                AppEvent::Key(key) if overlays.is_open() => {
+                    if is_quit_key(&key) {
+                        self.quit(overlays);
+                    } else {
+                        overlays.on_key_event(key);
+                    }
+                }
+                // Nothing behind the lock takes a paste.
+                AppEvent::Paste(_) if self.screen_lock.is_locked() => {}
+                AppEvent::Paste(text) if overlays.is_open() => overlays.on_paste(&text),
+                AppEvent::Paste(text) => {
+                    if self.status == Status::List {
+                        if table_service.has_popup() {
+                            table_service.on_paste(&text);
+                        } else {
+                            filter.on_paste(&text);
+                        }
+                    }
+                }
+                // Text being typed gets the key when it is a character.
+                AppEvent::Key(key)
+                    if self.config.keybindings.toggle_timings.matches(&key)
+                        && !(self.status == Status::List
+                            && (table_service.ignore_key_events || table_service.has_popup()))
+                        && !(self.status == Status::Log && log.is_typing())
+                        && !(self.status == Status::Details && details.is_typing()) =>
+                {
+                    self.timings.toggle();
+                }
+                AppEvent::Key(key)
+                    if self.config.keybindings.help.matches(&key)
+                        && !self.overlay_open(overlays)
+                        && !(self.status == Status::List
+                            && (table_service.ignore_key_events || table_service.has_popup()))
+                        && !(self.status == Status::Log && log.is_typing())
+                        && !(self.status == Status::Details && details.is_typing()) =>
+                {
+                    let theme = self.theme.clone();
+                    let shortcuts = match self.status {
+                        Status::List => linear_shortcuts(table_service.shortcuts(&theme)),
+                        Status::Log => linear_shortcuts(log.shortcuts(&theme)),
+                        Status::Details => linear_shortcuts(details.shortcuts(&theme)),
+                        Status::Dependencies => linear_shortcuts(dependencies.shortcuts(&theme)),
+                        Status::Processes => linear_shortcuts(processes.shortcuts(&theme)),
+                    };
+                    overlays.help.open(shortcuts);
+                }
                 AppEvent::Key(key) => match self.status {
                     Status::Log => {
-                        self.on_key_event(key);
+                        self.on_key_event(key, overlays);
                         log.on_key_event(key)
                     }
+                    Status::List if table_service.has_popup() => {
+                        table_service.on_key_event(key);
+                    }
                     Status::List => {
-                        self.on_key_event(key);
+                        // Not while typing in the filter, where e.g. the h/l tab keys of the vim keymap are text.
+                        if table_service.ignore_key_events {
+                            if is_quit_key(&key) {
+                                self.quit(overlays);
+                            }
+                        } else {
+                            if self.config.keybindings.command.matches(&key) {
+                                let units = table_service.services.iter().map(|service| service.name().to_string()).collect();
+                                let hosts = self.hosts.names().iter().map(|host| host.to_string()).collect();
+                                overlays.command_line.open(units, hosts);
+                                continue;
+                            }
+                            if self.config.keybindings.crash_loops.matches(&key) {
+                                overlays.crash_loops.open();
+                                continue;
+                            }
+                            if self.config.keybindings.snapshots.matches(&key) {
+                                overlays.snapshot_diff.open(
+                                    self.hosts.current_name(),
+                                    self.selected_tab_index != 0,
+                                    table_service.services.clone(),
+                                );
+                                continue;
+                            }
+                            if self.config.keybindings.boot_performance.matches(&key) {
+                                overlays.boot_performance.open();
+                                continue;
+                            }
+                            if self.config.keybindings.security_overview.matches(&key) {
+                                overlays.security_overview.open();
+                                continue;
+                            }
+                            if self.config.keybindings.slices.matches(&key) {
+                                overlays.slice_browser.open();
+                                continue;
+                            }
+                            if self.config.keybindings.groups.matches(&key) {
+                                overlays.group_browser.open();
+                                continue;
+                            }
+                            if self.config.keybindings.schedules.matches(&key) {
+                                overlays.schedules.open(table_service.get_selected_service().cloned());
+                                continue;
+                            }
+                            if self.config.keybindings.reload_diff.matches(&key) {
+                                if let Some(service) = table_service.get_selected_service() {
+                                    overlays.reload_diff.open(service.clone());
+                                }
+                                continue;
+                            }
+                            if self.config.keybindings.verify.matches(&key) {
+                                if let Some(service) = table_service.get_selected_service() {
+                                    overlays.unit_verify.open(service.name());
+                                }
+                                continue;
+                            }
+                            if self.config.keybindings.search_unit_files.matches(&key) {
+                                overlays.unit_file_search.open();
+                                continue;
+                            }
+                            if self.config.keybindings.split_view.matches(&key) {
+                                self.split_view = !self.split_view;
+                                continue;
+                            }
+                            if self.config.keybindings.save_layout.matches(&key) {
+                                self.save_layout(table_service, filter, details);
+                                continue;
+                            }
+                            if self.resize_panes(&key, details) {
+                                continue;
+                            }
+                            if self.config.keybindings.next_profile.matches(&key) {
+                                self.next_profile(table_service, filter, details);
+                                continue;
+                            }
+                            self.on_key_event(key, overlays);
+                            if self.list_refresh.on_key_event(key) {
+                                continue;
+                            }
+                        }
                         table_service.on_key_event(key);
                         filter.on_key_event(key);
                     }
                     Status::Details => {
-                        self.on_key_event(key);
+                        if self.resize_panes(&key, details) {
+                            continue;
+                        }
+                        self.on_key_event(key, overlays);
                         details.on_key_event(key);
                     }
+                    Status::Dependencies => {
+                        self.on_key_event(key, overlays);
+                        dependencies.on_key_event(key);
+                    }
+                    Status::Processes => {
+                        self.on_key_event(key, overlays);
+                        processes.on_key_event(key);
+                    }
                 },
+                AppEvent::Action(Actions::Lock) => self.screen_lock.lock(),
+                AppEvent::Action(Actions::RunCommand(command)) => {
+                    self.run_command(command, table_service, filter, overlays)?;
+                }
+                AppEvent::Action(Actions::Quit) => self.running = false,
+                AppEvent::Action(Actions::RunBatchStep) => table_service.run_batch_step(),
+                AppEvent::Action(Actions::RunOnUnits(action, units)) => table_service.run_on_units(action, units),
+                AppEvent::Action(Actions::OpenPathLookup) => overlays.path_lookup.open(),
+                AppEvent::Action(Actions::OpenErrorHistory) => overlays.error_history.open(),
+                AppEvent::Action(Actions::OpenFailedUnits) => overlays.failed_units.open(),
+                AppEvent::Action(Actions::OpenTargets) => overlays.targets.open(),
+                AppEvent::Action(Actions::OpenSessionChanges) => overlays.session_changes.open(),
+                AppEvent::Action(Actions::OpenNotifications) => overlays.notifications.open(),
+                AppEvent::Action(Actions::OpenNewUnit) => table_service.open_new_unit(self.selected_tab_index != 0),
+                AppEvent::Action(Actions::EditUnit(..)) if self.script.is_some() => {
+                    self.event_tx
+                        .send(AppEvent::Error(self.config.locale.tr("No editor can be opened in a headless run").to_string().into()))?;
+                }
+                AppEvent::Action(Actions::OpenShell(..)) if self.script.is_some() => {
+                    self.event_tx
+                        .send(AppEvent::Error(self.config.locale.tr("No shell can be opened in a headless run").to_string().into()))?;
+                }
+                AppEvent::Action(Actions::OpenShell(name, directory)) => self.open_shell(terminal, &name, &directory)?,
+                AppEvent::Action(Actions::EditUnit(name, enable_and_start)) => {
+                    self.edit_unit(terminal, &name, enable_and_start, &mut overlays.unit_verify)?;
+                    table_service.reload();
+                    table_service.select_by_name(&name);
+                }
+                AppEvent::Action(Actions::ReloadList) => table_service.reload(),
+                AppEvent::Action(Actions::CheckShownRows) => table_service.check_shown_rows(),
+                AppEvent::Action(Actions::ServicesFetched) => {
+                    let host = self.hosts.current_name().to_string();
+                    if let Err(e) = self.uptime.observe(&host, &table_service.services) {
+                        self.event_tx.send(AppEvent::Error(e.into()))?;
+                    }
+                    overlays.crash_loops.check(&table_service.services);
+                    self.system_summary.refresh();
+                }
+                AppEvent::Action(Actions::RefreshList) => {
+                    if self.status == Status::List
+                        && !self.list_refresh.is_paused()
+                        && !self.overlay_open(overlays)
+                        && !table_service.has_popup()
+                    {
+                        table_service.reload();
+                    }
+                }
+                AppEvent::Action(Actions::OpenTopTalkers) => {
+                    overlays.top_talkers.open();
+                    table_service.set_noisy_units(overlays.top_talkers.noisy_units());
+                }
+                AppEvent::Action(Actions::RefreshTopTalkers) => {
+                    overlays.top_talkers.refresh();
+                    table_service.set_noisy_units(overlays.top_talkers.noisy_units());
+                }
+                AppEvent::Action(Actions::SampleUsage) => {
+                    let host = self.hosts.current_name().to_string();
+                    let shown = match self.status {
+                        Status::Details => table_service.get_selected_service().map(|service| service.name().to_string()),
+                        _ => self.split_unit.clone(),
+                    };
+                    self.usage
+                        .sample(&self.usecases.borrow(), &host, &table_service.services, shown.as_deref());
+                    if let Some(name) = shown {
+                        details.set_usage(self.usage.history(&host, &name).cloned());
+                    }
+                }
+                AppEvent::Action(Actions::RefreshProperties) => {
+                    let shown = self.status == Status::Details || self.split_unit.is_some();
+                    if shown && !self.overlay_open(overlays) {
+                        details.refresh_properties();
+                        details.refresh_status(&table_service.services);
+                    }
+                }
+                AppEvent::Action(Actions::CheckFailures) => {
+                    // Errors are left to the next refresh of the list, not reported every interval.
+                    let services = self.usecases.borrow().list_services();
+                    if let Ok(services) = services {
+                        for unit in self.failure_alerts.check(&services) {
+                            // Watched units run their hooks on the transition already.
+                            if !self.usecases.borrow().is_watched(&unit) {
+                                self.usecases.borrow().unit_failed(&unit);
+                            }
+                            self.toasts.push_notice(self.config.locale.format("{} failed", &[&unit]));
+                        }
+                    }
+                }
+                AppEvent::Action(Actions::CheckConnection) => {
+                    // Drawn before reconnecting, which blocks until the bus answers.
+                    if self.usecases.borrow().connection_lost() {
+                        self.status_bar.set_reconnecting(true);
+                        self.event_tx.send(AppEvent::Action(Actions::Reconnect))?;
+                    }
+                }
+                AppEvent::Action(Actions::Reconnect) => {
+                    // Failures are retried on the next check, the indicator staying until then.
+                    let reconnected = self.usecases.borrow_mut().reconnect();
+                    if reconnected.is_ok() {
+                        self.status_bar.set_reconnecting(false);
+                        self.toasts.push_notice(self.config.locale.tr("Reconnected to the service manager").to_string());
+                        table_service.reload();
+                    }
+                }
+                AppEvent::Focus(focused) => self.failure_alerts.set_focused(focused),
+                AppEvent::Action(Actions::NextHost) => {
+                    let switch = self
+                        .hosts
+                        .next_host(self.selected_tab_index, table_service.services.clone());
+                    self.apply_host_switch(switch, table_service, overlays);
+                }
+                AppEvent::Action(Actions::CycleTheme) => {
+                    self.theme = Theme::with_overrides(self.theme.next_name(), &self.config.colors);
+                }
                 AppEvent::Action(Actions::UpdateIgnoreListKeys(bool)) => {
                     table_service.set_ignore_key_events(bool);
                 }
@@ -165,13 +1141,9 @@ impl App {
                     log.update(data.0, data.1);
                 }
                 AppEvent::Action(Actions::RefreshLog) => {
-                    if self.status == Status::Log {
-                        if let Some(service) =
-                            table_service.get_selected_service()
-                        {
-                            log
-                                .fetch_log_and_dispatch(service.clone());
-                        }
+                    let services = table_service.log_targets();
+                    if self.status == Status::Log && !services.is_empty() {
+                        log.fetch_log_and_dispatch(services);
                     }
                 }
                 AppEvent::Action(Actions::GoLog) => {
@@ -181,6 +1153,7 @@ impl App {
                 }
                 AppEvent::Action(Actions::GoList) => self.status = Status::List,
                 AppEvent::Action(Actions::ResetList) => {
+                    overlays.crash_loops.clear();
                     table_service.set_usecase(self.usecases.clone());
                 },
                 AppEvent::Action(Actions::UpdateDetails) => {}
@@ -191,183 +1164,578 @@ impl App {
                 }
                 AppEvent::Action(Actions::GoDetails) => {
                     if let Some(service) = table_service.get_selected_service() {
-                        details.update(service.clone());
+                        self.show_details(service, details);
                     }
                     self.event_tx
                         .send(AppEvent::Action(Actions::RefreshDetails))?;
                     self.status = Status::Details;
                 }
-                AppEvent::Error(error_msg) => {
-                    self.error_popup(&mut terminal, error_msg)?;    
+                AppEvent::Action(Actions::GoDetailsTab(tab)) => {
+                    details.set_tab(tab);
+                    if self.status != Status::Details {
+                        self.event_tx.send(AppEvent::Action(Actions::GoDetails))?;
+                    }
+                }
+                AppEvent::Action(Actions::ShowInDetails(name)) => {
+                    // Units that are not listed, e.g. an inactive socket, are looked up by name.
+                    let service = if table_service.select_by_name(&name) {
+                        table_service.get_selected_service().cloned()
+                    } else {
+                        self.usecases.borrow().find_service(&name).ok()
+                    };
+                    match service {
+                        Some(service) => {
+                            self.show_details(&service, details);
+                            details.fetch_unit_file();
+                        }
+                        None => self.event_tx.send(AppEvent::Error(AppError::from(format!("{} was not found.", name))))?,
+                    }
+                }
+                AppEvent::Action(Actions::GoDependencies) => {
+                    if let Some(service) = table_service.get_selected_service() {
+                        dependencies.update(service.clone());
+                    }
+                    dependencies.fetch_dependencies();
+                    self.status = Status::Dependencies;
+                }
+                AppEvent::Action(Actions::GoProcesses) => {
+                    if let Some(service) = table_service.get_selected_service() {
+                        processes.update(service.clone());
+                    }
+                    processes.fetch_processes();
+                    processes.start_auto_refresh();
+                    self.status = Status::Processes;
+                }
+                AppEvent::Action(Actions::RefreshProcesses) => {
+                    if self.status == Status::Processes {
+                        processes.fetch_processes();
+                    }
                 }
+                AppEvent::Action(Actions::JumpToService(name)) => {
+                    self.status = Status::List;
+                    if !table_service.select_by_name(&name) {
+                        filter.clear();
+                        table_service.refresh(String::new());
+                        if !table_service.select_by_name(&name) {
+                            self.event_tx.send(AppEvent::Error(AppError::from(format!(
+                                "{} is not listed in this tab.",
+                                name
+                            ))))?;
+                        }
+                    }
+                }
+                AppEvent::Error(error) => {
+                    self.toasts.push_error(&error);
+                    overlays.error_history.push(error);
+                }
+                AppEvent::Notice(message) => self.toasts.push_notice(message),
+                AppEvent::Action(Actions::ActionPerformed(description, outcome)) => {
+                    self.status_bar.action_performed(description, outcome);
+                }
+                AppEvent::JobRemoved(job) => self.status_bar.job_removed(&job),
+                AppEvent::ActionFinished(finished) => {
+                    self.usecases.borrow_mut().action_finished(&finished);
+                    table_service.action_finished(finished);
+                }
+                AppEvent::UnitTransition(transition) => self.unit_transition(transition, &mut overlays.notifications),
+                AppEvent::Health(unit, health) => table_service.set_health(unit, health),
+                AppEvent::Resize => terminal.autoresize()?,
             }
         }
 
+        if self.script.is_some() {
+            return Ok(());
+        }
+        // Nowhere left to report a failure once quitting.
+        let _ = self.uptime.save();
+        let _ = save_session(&self.session_state(table_service, filter, details));
         Ok(())
     }
 
-    fn error_popup(&self, terminal: &mut DefaultTerminal, error_msg: String) -> Result<()> {
-        let user_friendly_message = get_user_friendly_error(&error_msg);
+    /// Draws the current screen, or the open overlay, as plain lines without any box or layout.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_linear_status<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        filter: &Filter,
+        table: &mut TableServices,
+        log: &mut ServiceLog,
+        details: &mut ServiceDetails,
+        dependencies: &mut ServiceDependencies,
+        processes: &mut ServiceProcesses,
+        overlays: &Overlays,
+    ) -> Result<()> {
+        let theme = self.hosts.themed(self.theme.clone());
+        let locale = self.config.locale;
+        let selected_name = table
+            .get_selected_service()
+            .map(|service| service.name().to_string())
+            .unwrap_or_default();
+
+        let (lines, shortcuts) = if self.screen_lock.is_locked() {
+            (self.screen_lock.linear_text(), Vec::new())
+        } else if let Some(lines) = overlays.linear_text() {
+            (lines, Vec::new())
+        } else {
+            match self.status {
+                Status::List => {
+                    let tab = if self.selected_tab_index == 0 { "system services" } else { "session services" };
+                    let mut lines = vec![locale.format("Screen: service list, {}", &[locale.tr(tab)])];
+                    lines.extend(self.system_summary.linear_text());
+                    if self.hosts.is_enabled() {
+                        lines.extend(self.hosts.linear_text());
+                    }
+                    lines.extend(self.list_refresh.linear_text());
+                    lines.extend(overlays.crash_loops.linear_banner());
+                    lines.extend(filter.linear_text());
+                    lines.extend(table.linear_text());
+                    (lines, linear_shortcuts(table.shortcuts(&theme)))
+                }
+                Status::Log => {
+                    let mut lines = vec![locale.format("Screen: logs of {}, newest first", &[&selected_name]), UnitTab::Logs.linear_text(locale)];
+                    lines.extend(log.linear_text());
+                    (lines, linear_shortcuts(log.shortcuts(&theme)))
+                }
+                Status::Details => {
+                    let mut lines = vec![locale.format("Screen: details of {}", &[&selected_name]), details.tab().linear_text(locale)];
+                    lines.extend(details.linear_text());
+                    (lines, linear_shortcuts(details.shortcuts(&theme)))
+                }
+                Status::Dependencies => {
+                    let mut lines = vec![locale.format("Screen: dependencies of {}", &[&selected_name]), UnitTab::Dependencies.linear_text(locale)];
+                    lines.extend(dependencies.linear_text());
+                    (lines, linear_shortcuts(dependencies.shortcuts(&theme)))
+                }
+                Status::Processes => {
+                    let mut lines = vec![locale.format("Screen: processes of {}", &[&selected_name]), UnitTab::Processes.linear_text(locale)];
+                    lines.extend(processes.linear_text());
+                    (lines, linear_shortcuts(processes.shortcuts(&theme)))
+                }
+            }
+        };
+
+        let lines: Vec<String> = self
+            .toasts
+            .linear_text()
+            .into_iter()
+            .chain(self.announcement.clone())
+            .chain(self.timings.linear_text(&self.usecases.borrow().timings(), self.config.locale))
+            .chain(lines)
+            .chain(self.status_bar.linear_text(self.config.locale))
+            .collect();
 
-        terminal.draw(|frame| {
+        let locale = self.config.locale;
+        let completed = terminal.draw(|frame| {
             let area = frame.area();
+            let mut footer: Vec<String> = shortcuts
+                .into_iter()
+                .map(|line| format!("{}: {}", locale.tr("Shortcuts"), line))
+                .collect();
+            if self.config.read_only {
+                footer.insert(0, locale.tr("Read-only mode: actions changing units are refused").to_string());
+            }
+            footer.push(format!("{}: Ctrl + c", locale.tr("Exit")));
 
-            let popup_width = std::cmp::min(70, area.width.saturating_sub(4));
-            let popup_height = std::cmp::min(10, area.height.saturating_sub(4));
-
-            let popup_x = (area.width.saturating_sub(popup_width)) / 2;
-            let popup_y = (area.height.saturating_sub(popup_height)) / 2;
-
-            let popup_area = Rect::new(
-                area.x + popup_x,
-                area.y + popup_y,
-                popup_width,
-                popup_height,
-            );
-
-            frame.render_widget(Clear, popup_area);
-
-            let text = vec![
-                Line::from(vec![Span::styled(
-                    "ERROR",
-                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-                )]),
-                Line::from(""),
-                Line::from(user_friendly_message),
-                Line::from(""),
-                Line::from(vec![Span::styled(
-                    "Press any key to dismiss",
-                    Style::default().fg(Color::Gray),
-                )]),
-            ];
-
-            let error_block = Paragraph::new(text)
-                .block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .border_style(Style::default().fg(Color::Red))
-                        .title("Error"),
-                )
-                .alignment(Alignment::Center)
-                .wrap(ratatui::widgets::Wrap { trim: true });
-
-            frame.render_widget(error_block, popup_area);
+            let width = area.width.max(1) as usize;
+            let footer_height: usize = footer
+                .iter()
+                .map(|line| line.chars().count().div_ceil(width).max(1))
+                .sum();
+            let [content_area, footer_area] = Layout::vertical([
+                Constraint::Min(0),
+                Constraint::Length(u16::try_from(footer_height).unwrap_or(u16::MAX)),
+            ])
+            .areas(area);
+
+            let wrap = ratatui::widgets::Wrap { trim: false };
+            let style = Style::default().fg(theme.text);
+            let content: Vec<Line> = lines.into_iter().map(Line::from).collect();
+            frame.render_widget(Paragraph::new(content).style(style).wrap(wrap), content_area);
+            let footer: Vec<Line> = footer.into_iter().map(Line::from).collect();
+            frame.render_widget(Paragraph::new(footer).style(style).wrap(wrap), footer_area);
         })?;
+        self.record_screen(completed.buffer);
 
-        if let Ok(Event::Key(_)) = event::read() {
-            // Continue after key press
-        };
         Ok(())
     }
 
-    fn draw_details_status(
+    fn draw_details_status<B: Backend>(
         &mut self,
-        terminal: &mut DefaultTerminal,
+        terminal: &mut Terminal<B>,
         service_details: &mut ServiceDetails,
+        overlays: &mut Overlays,
     ) -> Result<()> {
-        terminal.draw(|frame| {
-            let area = frame.area();
+        let theme = self.hosts.themed(self.theme.clone());
+        let completed = terminal.draw(|frame| {
+            let (area, status_area) = self.split_status_bar(frame.area());
+            self.draw_status(frame, status_area, &theme);
 
-            let [list_box, help_area_box] =
-                Layout::vertical([Constraint::Min(0), Constraint::Max(7)]).areas(area);
+            let [tabs_box, list_box, footer_box] = Layout::vertical([
+                Constraint::Length(1),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .areas(area);
 
-            service_details.render(frame, list_box);
-            self.draw_shortcuts(frame, help_area_box, service_details.shortcuts());
+            service_details.tab().render(frame, tabs_box, &theme, self.config.locale);
+            service_details.render(frame, list_box, &theme);
+            self.draw_footer(frame, footer_box, None);
+            overlays.render(frame, area, &theme);
+            self.toasts.render(frame, area, &theme);
+            self.timings.render(frame, area, &theme, &self.usecases.borrow().timings(), self.config.locale);
+            self.screen_lock.render(frame, area, &theme);
         })?;
+        self.record_screen(completed.buffer);
 
         Ok(())
     }
 
-    fn draw_log_status(
+    fn draw_dependencies_status<B: Backend>(
         &mut self,
-        terminal: &mut DefaultTerminal,
+        terminal: &mut Terminal<B>,
+        dependencies: &mut ServiceDependencies,
+        overlays: &mut Overlays,
+    ) -> Result<()> {
+        let theme = self.hosts.themed(self.theme.clone());
+        let completed = terminal.draw(|frame| {
+            let (area, status_area) = self.split_status_bar(frame.area());
+            self.draw_status(frame, status_area, &theme);
+
+            let [tabs_box, list_box, footer_box] = Layout::vertical([
+                Constraint::Length(1),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .areas(area);
+
+            UnitTab::Dependencies.render(frame, tabs_box, &theme, self.config.locale);
+            dependencies.render(frame, list_box, &theme);
+            self.draw_footer(frame, footer_box, None);
+            overlays.render(frame, area, &theme);
+            self.toasts.render(frame, area, &theme);
+            self.timings.render(frame, area, &theme, &self.usecases.borrow().timings(), self.config.locale);
+            self.screen_lock.render(frame, area, &theme);
+        })?;
+        self.record_screen(completed.buffer);
+
+        Ok(())
+    }
+
+    fn draw_processes_status<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        processes: &mut ServiceProcesses,
+        overlays: &mut Overlays,
+    ) -> Result<()> {
+        let theme = self.hosts.themed(self.theme.clone());
+        let completed = terminal.draw(|frame| {
+            let (area, status_area) = self.split_status_bar(frame.area());
+            self.draw_status(frame, status_area, &theme);
+
+            let [tabs_box, list_box, footer_box] = Layout::vertical([
+                Constraint::Length(1),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .areas(area);
+
+            UnitTab::Processes.render(frame, tabs_box, &theme, self.config.locale);
+            processes.render(frame, list_box, &theme);
+            self.draw_footer(frame, footer_box, None);
+            overlays.render(frame, area, &theme);
+            self.toasts.render(frame, area, &theme);
+            self.timings.render(frame, area, &theme, &self.usecases.borrow().timings(), self.config.locale);
+            self.screen_lock.render(frame, area, &theme);
+        })?;
+        self.record_screen(completed.buffer);
+
+        Ok(())
+    }
+
+    fn draw_log_status<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
         service_log: &mut ServiceLog,
+        overlays: &mut Overlays,
     ) -> Result<()> {
-        terminal.draw(|frame| {
-            let area = frame.area();
+        let theme = self.hosts.themed(self.theme.clone());
+        let completed = terminal.draw(|frame| {
+            let (area, status_area) = self.split_status_bar(frame.area());
+            self.draw_status(frame, status_area, &theme);
 
-            let [list_box, help_area_box] =
-                Layout::vertical([Constraint::Min(0), Constraint::Max(7)]).areas(area);
+            let [tabs_box, list_box, footer_box] = Layout::vertical([
+                Constraint::Length(1),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .areas(area);
 
-            service_log.render(frame, list_box);
-            self.draw_shortcuts(frame, help_area_box, service_log.shortcuts());
+            UnitTab::Logs.render(frame, tabs_box, &theme, self.config.locale);
+            service_log.render(frame, list_box, &theme);
+            self.draw_footer(frame, footer_box, None);
+            overlays.render(frame, area, &theme);
+            self.toasts.render(frame, area, &theme);
+            self.timings.render(frame, area, &theme, &self.usecases.borrow().timings(), self.config.locale);
+            self.screen_lock.render(frame, area, &theme);
         })?;
+        self.record_screen(completed.buffer);
 
         Ok(())
     }
 
-    fn draw_list_status(
+    fn draw_list_status<B: Backend>(
         &mut self,
-        terminal: &mut DefaultTerminal,
+        terminal: &mut Terminal<B>,
         filter: &mut Filter,
         table: &mut TableServices,
+        details: &mut ServiceDetails,
+        overlays: &mut Overlays,
     ) -> Result<()> {
-        terminal.draw(|frame| {
-            let area = frame.area();
+        let theme = self.hosts.themed(self.theme.clone());
+        let completed = terminal.draw(|frame| {
+            let split = self.split_shown(frame.area().width) && self.split_unit.is_some();
+            let (area, status_area) = self.split_status_bar(frame.area());
+            self.draw_status(frame, status_area, &theme);
 
-            let [filter_box, tabs_box, list_box, help_area_box] = Layout::vertical([
-                Constraint::Length(4),
+            // Short screens keep the rows for the units: the filter on one line, no summary or banner.
+            let compact = area.height < COMPACT_HEIGHT;
+            let [summary_box, filter_box, banner_box, tabs_box, list_box, footer_box] = Layout::vertical([
+                Constraint::Length(if compact { 0 } else { self.system_summary.height() }),
+                Constraint::Length(if compact { 1 } else { 4 }),
+                Constraint::Length(if compact { 0 } else { overlays.crash_loops.banner_height() }),
+                Constraint::Length(1),
+                Constraint::Min(0),
                 Constraint::Length(1),
-                Constraint::Min(10),
-                Constraint::Max(7),
             ])
             .areas(area);
 
-            let tabs = Tabs::new(vec!["System services","Session services"])
+            let locale = self.config.locale;
+            let titles = [locale.tr("System services"), locale.tr("Session services")];
+            // Each title is padded by a space on both sides and the two are split by a bar.
+            let tabs_width = titles.iter().map(|title| title.chars().count() as u16 + 2).sum::<u16>() + 1;
+            let tabs = Tabs::new(titles.to_vec())
                 .select(self.selected_tab_index)
-                .highlight_style(Style::default().fg(Color::Yellow));
+                .style(Style::default().fg(theme.text))
+                .highlight_style(Style::default().fg(theme.warning));
 
             frame.render_widget(tabs, tabs_box);
-            filter.draw(frame, filter_box);
-            table.render(frame, list_box);
-            self.draw_shortcuts(frame, help_area_box, table.shortcuts());
+            let refresh_box = Rect {
+                x: tabs_box.x + tabs_width.saturating_add(3).min(tabs_box.width),
+                width: tabs_box.width.saturating_sub(tabs_width.saturating_add(3)),
+                ..tabs_box
+            };
+            self.list_refresh.render(frame, refresh_box, &theme);
+            if self.hosts.is_enabled() {
+                self.hosts.render(frame, tabs_box, &theme);
+            }
+            if compact {
+                filter.draw_compact(frame, filter_box, &theme);
+            } else {
+                self.system_summary.render(frame, summary_box, &theme);
+                filter.draw(frame, filter_box, &theme);
+                overlays.crash_loops.render_banner(frame, banner_box, &theme);
+            }
+            if split {
+                let (list_width, _) = self.split_widths(list_box.width);
+                let [list_box, pane_box] =
+                    Layout::horizontal([Constraint::Length(list_width), Constraint::Min(0)]).areas(list_box);
+                let [unit_tabs_box, details_box] =
+                    Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(pane_box);
+                table.render(frame, list_box, &theme);
+                details.tab().render(frame, unit_tabs_box, &theme, self.config.locale);
+                details.render(frame, details_box, &theme);
+            } else {
+                table.render(frame, list_box, &theme);
+            }
+            self.draw_footer(frame, footer_box, table.footer_hint(&theme));
+            overlays.render(frame, area, &theme);
+            self.toasts.render(frame, area, &theme);
+            self.timings.render(frame, area, &theme, &self.usecases.borrow().timings(), self.config.locale);
+            self.screen_lock.render(frame, area, &theme);
         })?;
+        self.record_screen(completed.buffer);
 
         Ok(())
     }
 
-    fn draw_shortcuts(&mut self, frame: &mut Frame, help_area: Rect, shortcuts: Vec<Line<'_>>) {
-        let mut help_text: Vec<Line<'_>> = Vec::new();
-        let shortcuts_lens = shortcuts.len();
+    /// Gives the details the unit, with its availability and the samples of its usage graphs.
+    fn show_details(&self, service: &Service, details: &mut ServiceDetails) {
+        let availability = self
+            .uptime
+            .availability(self.hosts.current_name(), service.name())
+            .map(|availability| availability.to_string());
+        details.update(service.clone(), availability);
+        details.set_usage(self.usage.history(self.hosts.current_name(), service.name()).cloned());
+    }
+
+    /// Whether the list is drawn with the details beside it, on a screen `width` columns wide.
+    fn split_shown(&self, width: u16) -> bool {
+        let (list, details) = self.split_widths(width);
+        self.split_view && self.status == Status::List && list >= PANE_MIN_WIDTH && details >= PANE_MIN_WIDTH
+    }
 
-        help_text.extend(shortcuts);
+    /// Columns of the list and of the details in the split view.
+    fn split_widths(&self, width: u16) -> (u16, u16) {
+        let list = (u32::from(width) * u32::from(self.split_ratio) / 100) as u16;
+        (list, width - list)
+    }
 
-        if shortcuts_lens > 0 {
-            help_text.push(Line::raw(""));
-            if help_area.width > 140 {
-                help_text.push(Line::raw(""));
+    /// Reads the details of the selected unit for the right pane of the split view, when the
+    /// selection moved since they were read.
+    fn sync_split_view(&mut self, width: u16, table_service: &TableServices, details: &mut ServiceDetails) {
+        let selected = table_service.get_selected_service().filter(|_| self.split_shown(width));
+        let Some(service) = selected else {
+            self.split_unit = None;
+            return;
+        };
+        if self.split_unit.as_deref() == Some(service.name()) {
+            return;
+        }
+        self.split_unit = Some(service.name().to_string());
+        self.show_details(service, details);
+        details.fetch_unit_file();
+    }
+
+    /// Whether a view covers the screen, which then gets the keys and pastes.
+    fn overlay_open(&self, overlays: &Overlays) -> bool {
+        self.screen_lock.is_locked() || overlays.is_open()
+    }
+
+    fn apply_host_switch(
+        &mut self,
+        switch: Option<HostSwitch>,
+        table_service: &mut TableServices,
+        overlays: &mut Overlays,
+    ) {
+        if let Some(switch) = switch {
+            self.selected_tab_index = switch.tab_index;
+            match switch.services {
+                Some(services) => table_service.restore_services(services),
+                None => table_service.set_usecase(self.usecases.clone()),
             }
+            overlays.top_talkers.clear();
+            table_service.set_noisy_units(overlays.top_talkers.noisy_units());
+            self.failure_alerts.clear();
+            overlays.crash_loops.clear();
         }
+    }
 
-        help_text.push(Line::from(vec![
-            Span::styled(
-                "Exit",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-            ),
-            Span::raw(": Ctrl + c"),
-        ]));
+    fn run_command(
+        &mut self,
+        command: Command,
+        table_service: &mut TableServices,
+        filter: &mut Filter,
+        overlays: &mut Overlays,
+    ) -> Result<()> {
+        match command {
+            Command::Act(action, name) => {
+                let unit = table_service
+                    .services
+                    .iter()
+                    .find(|service| names_unit(&name, service.name()))
+                    .map(|service| service.name().to_string());
+                let Some(unit) = unit else {
+                    self.event_tx.send(AppEvent::Error(AppError::from(format!(
+                        "{} is not listed in this tab.",
+                        name
+                    ))))?;
+                    return Ok(());
+                };
+                // Like a jump, a unit hidden by the filter clears it.
+                if !table_service.select_by_name(&unit) {
+                    filter.clear();
+                    table_service.refresh(String::new());
+                    table_service.select_by_name(&unit);
+                }
+                table_service.act_on_selected_only(action);
+            }
+            Command::Filter(query) => filter.set_input(&query),
+            Command::Sort(column) => table_service.sort_by(column),
+            Command::Host(name) => {
+                let switch = self
+                    .hosts
+                    .switch_to(&name, self.selected_tab_index, table_service.services.clone());
+                self.apply_host_switch(switch, table_service, overlays);
+            }
+            Command::Quit => self.quit(overlays),
+        }
+        Ok(())
+    }
+
+    /// Keeps the last line of the screen for the status bar, once an action was performed.
+    fn split_status_bar(&self, area: Rect) -> (Rect, Rect) {
+        let height = u16::from(!self.status_bar.is_empty()) + u16::from(self.announcement.is_some());
+        let [main, status] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(height)]).areas(area);
+        (main, status)
+    }
+
+    /// The announcement, if any, then the status bar, in the area of `split_status_bar`.
+    fn draw_status(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let [announcement_area, status_area] =
+            Layout::vertical([Constraint::Length(u16::from(self.announcement.is_some())), Constraint::Min(0)]).areas(area);
+        if let Some(announcement) = &self.announcement {
+            frame.render_widget(Paragraph::new(announcement.as_str()).style(Style::default().fg(theme.text)), announcement_area);
+        }
+        self.status_bar.render(frame, status_area, theme, self.config.locale);
+    }
 
-        let help_block = Paragraph::new(help_text)
-            .block(Block::default().title("Shortcuts").borders(Borders::ALL))
-            .wrap(ratatui::widgets::Wrap { trim: true });
+    /// What the screen shows in plain words: the selected unit on the list, the view and the
+    /// unit elsewhere.
+    fn announce(&self, table_service: &TableServices, details: &ServiceDetails) -> Option<String> {
+        let name = table_service.get_selected_service()?.name().to_string();
+        let locale = self.config.locale;
+        Some(match self.status {
+            Status::List => locale.format("Selected {}", &[&table_service.announcement()?]),
+            Status::Log => locale.format("Logs of {}", &[&name]),
+            Status::Details => locale.format("{} of {}", &[locale.tr(details.tab().title()), &name]),
+            Status::Dependencies => locale.format("Dependencies of {}", &[&name]),
+            Status::Processes => locale.format("Processes of {}", &[&name]),
+        })
+    }
 
-        frame.render_widget(help_block, help_area);
+    /// Wraps the backend the app draws to, to leave out colors or non-ASCII characters when
+    /// the config asks to.
+    pub fn backend<B: Backend>(&self, inner: B) -> PlainBackend<B> {
+        PlainBackend::new(inner, self.config.no_color, self.config.ascii)
     }
 
-    fn on_key_event(&mut self, key: KeyEvent) {
+    /// One line under the screen: the hint of the screen, if any, then the help and exit keys.
+    fn draw_footer(&self, frame: &mut Frame, footer_area: Rect, hint: Option<Line<'_>>) {
+        let theme = &self.hosts.themed(self.theme.clone());
+        let mut spans: Vec<Span<'_>> = Vec::new();
+        if self.config.read_only {
+            spans.push(Span::styled(
+                self.config.locale.tr("READ-ONLY"),
+                Style::default().fg(theme.warning).add_modifier(Modifier::BOLD),
+            ));
+            spans.push(Span::raw(" | "));
+        }
+        if let Some(hint) = hint {
+            spans.extend(hint.spans);
+            spans.push(Span::raw(" | "));
+        }
+        let locale = self.config.locale;
+        spans.push(Span::raw(format!("{}: {} | ", locale.tr("Help"), self.config.keybindings.help)));
+        spans.push(Span::styled(
+            locale.tr("Exit"),
+            Style::default().fg(theme.error).add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw(": Ctrl + c"));
+
+        frame.render_widget(
+            Paragraph::new(Line::from(spans)).style(Style::default().fg(theme.text)),
+            footer_area,
+        );
+    }
+
+    fn on_key_event(&mut self, key: KeyEvent, overlays: &mut Overlays) {
+        let config = self.config.clone();
+        let keys = &config.keybindings;
         match key {
-            KeyEvent {
-                modifiers: KeyModifiers::CONTROL,
-                code: KeyCode::Char('c') | KeyCode::Char('C'),
-                ..
-            } => {
-                self.quit();
-            }
-
-            KeyEvent {
-                code: KeyCode::Left,
-                ..
-            } => {
+            k if is_quit_key(&k) => self.quit(overlays),
+
+            k if keys.previous_tab.matches(&k) => {
                 if matches!(self.status, Status::List) {
                     self.selected_tab_index = if self.selected_tab_index == 0 {
                         1 
@@ -379,10 +1747,7 @@ impl App {
                 }
             }
 
-            KeyEvent {
-                code: KeyCode::Right,
-                ..
-            } => {
+            k if keys.next_tab.matches(&k) => {
                 if matches!(self.status, Status::List) {
                     self.selected_tab_index = (self.selected_tab_index + 1) % 2;
 
@@ -404,7 +1769,7 @@ impl App {
             .borrow_mut()
             .change_repository_connection(conn_type)
         {
-            self.event_tx.send(AppEvent::Error("Failed to change connection type with D-Bus, try run without sudo".to_string())).expect("Failed to change connection type");
+            self.event_tx.send(AppEvent::Error(self.config.locale.tr("Failed to change connection type with D-Bus, try run without sudo").to_string().into())).expect("Failed to change connection type");
             self.selected_tab_index = 0;
             return
         }
@@ -414,7 +1779,11 @@ impl App {
             .expect("Failed to send ResetList event");
     }
 
-    fn quit(&mut self) {
+    /// Quits, unless the session changed units, in which case their revert is offered first.
+    fn quit(&mut self, overlays: &mut Overlays) {
+        if overlays.session_changes.open_on_quit() {
+            return;
+        }
         self.running = false;
     }
 }
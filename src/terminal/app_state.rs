@@ -0,0 +1,192 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+
+use crate::config::Config;
+use crate::usecases::services_manager::ServicesManager;
+
+use crossterm::event::KeyEvent;
+use ratatui::{layout::Rect, Frame};
+
+use super::app::AppEvent;
+use super::components::boot_performance::BootPerformance;
+use super::components::command_line::CommandLine;
+use super::components::crash_loops::CrashLoops;
+use super::components::dependencies::ServiceDependencies;
+use super::components::details::ServiceDetails;
+use super::components::error_history::ErrorHistory;
+use super::components::failed_units::FailedUnits;
+use super::components::filter::Filter;
+use super::components::group_browser::GroupBrowser;
+use super::components::help::Help;
+use super::components::list::TableServices;
+use super::components::log::ServiceLog;
+use super::components::notifications::Notifications;
+use super::components::overlay::Overlay;
+use super::components::path_lookup::PathLookup;
+use super::components::processes::ServiceProcesses;
+use super::components::reload_diff::ReloadDiff;
+use super::components::schedules::Schedules;
+use super::components::security_overview::SecurityOverview;
+use super::components::session_changes::SessionChanges;
+use super::components::slice_browser::SliceBrowser;
+use super::components::snapshot_diff::SnapshotDiff;
+use super::components::targets::Targets;
+use super::components::top_talkers::TopTalkers;
+use super::components::unit_file_search::UnitFileSearch;
+use super::components::unit_verify::UnitVerify;
+use super::theme::Theme;
+
+/// The panes of the TUI and the views over them, owned in one place. The app lends them to the
+/// handler of each event and to the drawing; a pane never holds another, it sends an `AppEvent`
+/// that the app turns into a call on the pane concerned. The panes still call the use cases
+/// themselves.
+pub struct AppState {
+    pub table_service: TableServices,
+    pub filter: Filter,
+    pub log: ServiceLog,
+    pub details: ServiceDetails,
+    pub dependencies: ServiceDependencies,
+    pub processes: ServiceProcesses,
+    pub overlays: Overlays,
+}
+
+impl AppState {
+    pub fn new(sender: Sender<AppEvent>, usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
+        Self {
+            table_service: TableServices::new(sender.clone(), usecase.clone(), config.clone()),
+            filter: Filter::new(sender.clone(), config.clone()),
+            log: ServiceLog::new(sender.clone(), usecase.clone(), config.clone()),
+            details: ServiceDetails::new(sender.clone(), usecase.clone(), config.clone()),
+            dependencies: ServiceDependencies::new(sender.clone(), usecase.clone(), config.clone()),
+            processes: ServiceProcesses::new(sender.clone(), usecase.clone(), config.clone()),
+            overlays: Overlays::new(sender, usecase, config),
+        }
+    }
+}
+
+/// The views drawn over the panes. The topmost open one gets the keys and pastes, and the
+/// accessible mode reads it instead of the screen under it.
+pub struct Overlays {
+    pub path_lookup: PathLookup,
+    pub unit_file_search: UnitFileSearch,
+    pub top_talkers: TopTalkers,
+    pub error_history: ErrorHistory,
+    pub notifications: Notifications,
+    pub failed_units: FailedUnits,
+    pub targets: Targets,
+    pub crash_loops: CrashLoops,
+    pub snapshot_diff: SnapshotDiff,
+    pub boot_performance: BootPerformance,
+    pub security_overview: SecurityOverview,
+    pub slice_browser: SliceBrowser,
+    pub group_browser: GroupBrowser,
+    pub schedules: Schedules,
+    pub reload_diff: ReloadDiff,
+    pub unit_verify: UnitVerify,
+    pub command_line: CommandLine,
+    pub help: Help,
+    pub session_changes: SessionChanges,
+}
+
+impl Overlays {
+    pub fn new(sender: Sender<AppEvent>, usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
+        Self {
+            path_lookup: PathLookup::new(usecase.clone(), config.clone()),
+            unit_file_search: UnitFileSearch::new(sender.clone(), usecase.clone(), config.clone()),
+            top_talkers: TopTalkers::new(sender.clone(), usecase.clone(), config.clone()),
+            error_history: ErrorHistory::new(config.clone()),
+            notifications: Notifications::new(sender.clone(), config.clone()),
+            failed_units: FailedUnits::new(sender.clone(), usecase.clone(), config.clone()),
+            targets: Targets::new(sender.clone(), usecase.clone(), config.clone()),
+            crash_loops: CrashLoops::new(sender.clone(), usecase.clone(), config.clone()),
+            snapshot_diff: SnapshotDiff::new(sender.clone(), config.clone()),
+            boot_performance: BootPerformance::new(sender.clone(), usecase.clone(), config.clone()),
+            security_overview: SecurityOverview::new(sender.clone(), usecase.clone(), config.clone()),
+            slice_browser: SliceBrowser::new(sender.clone(), usecase.clone(), config.clone()),
+            group_browser: GroupBrowser::new(sender.clone(), usecase.clone(), config.clone()),
+            schedules: Schedules::new(sender.clone(), usecase.clone(), config.clone()),
+            reload_diff: ReloadDiff::new(sender.clone(), usecase.clone(), config.clone()),
+            unit_verify: UnitVerify::new(sender.clone(), usecase.clone(), config.clone()),
+            command_line: CommandLine::new(sender.clone(), config.clone()),
+            help: Help::new(config.clone()),
+            session_changes: SessionChanges::new(sender, usecase, config),
+        }
+    }
+
+    /// Every view, in the order they are drawn: the last one is on top.
+    fn stack(&self) -> [&dyn Overlay; 19] {
+        [
+            &self.path_lookup,
+            &self.unit_file_search,
+            &self.top_talkers,
+            &self.error_history,
+            &self.notifications,
+            &self.failed_units,
+            &self.targets,
+            &self.crash_loops,
+            &self.snapshot_diff,
+            &self.boot_performance,
+            &self.security_overview,
+            &self.slice_browser,
+            &self.group_browser,
+            &self.schedules,
+            &self.reload_diff,
+            &self.unit_verify,
+            &self.command_line,
+            &self.help,
+            &self.session_changes,
+        ]
+    }
+
+    fn stack_mut(&mut self) -> [&mut dyn Overlay; 19] {
+        [
+            &mut self.path_lookup,
+            &mut self.unit_file_search,
+            &mut self.top_talkers,
+            &mut self.error_history,
+            &mut self.notifications,
+            &mut self.failed_units,
+            &mut self.targets,
+            &mut self.crash_loops,
+            &mut self.snapshot_diff,
+            &mut self.boot_performance,
+            &mut self.security_overview,
+            &mut self.slice_browser,
+            &mut self.group_browser,
+            &mut self.schedules,
+            &mut self.reload_diff,
+            &mut self.unit_verify,
+            &mut self.command_line,
+            &mut self.help,
+            &mut self.session_changes,
+        ]
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.stack().iter().any(|overlay| overlay.is_open())
+    }
+
+    pub fn on_key_event(&mut self, key: KeyEvent) {
+        if let Some(overlay) = self.stack_mut().into_iter().rev().find(|overlay| overlay.is_open()) {
+            overlay.on_key_event(key);
+        }
+    }
+
+    pub fn on_paste(&mut self, text: &str) {
+        if let Some(overlay) = self.stack_mut().into_iter().rev().find(|overlay| overlay.is_open()) {
+            overlay.on_paste(text);
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        for overlay in self.stack_mut().into_iter().filter(|overlay| overlay.is_open()) {
+            overlay.render(frame, area, theme);
+        }
+    }
+
+    /// The lines of the topmost open view, `None` when none is.
+    pub fn linear_text(&self) -> Option<Vec<String>> {
+        self.stack().into_iter().rev().find(|overlay| overlay.is_open()).map(|overlay| overlay.linear_text())
+    }
+}
@@ -0,0 +1,201 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Gauge, Paragraph},
+    Frame,
+};
+use std::collections::VecDeque;
+
+use crate::domain::service::Service;
+use crate::terminal::components::list::ServiceAction;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+/// An action applied to several services one at a time, so the UI can redraw between them.
+pub struct BatchOperation {
+    action: ServiceAction,
+    /// Steps left, each an action on a service.
+    pending: VecDeque<(ServiceAction, Service)>,
+    results: Vec<(String, Result<(), String>)>,
+    total: usize,
+    /// Steps are in dependency order: the first failure skips the rest, and results name the
+    /// action of each step.
+    ordered: bool,
+    skipped: usize,
+    locale: Locale,
+}
+
+impl BatchOperation {
+    pub fn new(action: ServiceAction, services: Vec<Service>, locale: Locale) -> Self {
+        Self {
+            action,
+            total: services.len(),
+            pending: services.into_iter().map(|service| (action, service)).collect(),
+            results: Vec::new(),
+            ordered: false,
+            skipped: 0,
+            locale,
+        }
+    }
+
+    /// The action on services in the order they start, stopping them in reverse; a restart stops
+    /// them all in reverse, then starts them in order.
+    pub fn ordered(action: ServiceAction, services: Vec<Service>, locale: Locale) -> Self {
+        let reversed = || services.iter().rev().map(|service| (ServiceAction::Stop, service.clone()));
+        let pending: VecDeque<(ServiceAction, Service)> = match action {
+            ServiceAction::Stop => reversed().collect(),
+            ServiceAction::Restart => reversed()
+                .chain(services.iter().map(|service| (ServiceAction::Start, service.clone())))
+                .collect(),
+            _ => services.iter().map(|service| (action, service.clone())).collect(),
+        };
+        Self {
+            action,
+            total: pending.len(),
+            pending,
+            results: Vec::new(),
+            ordered: true,
+            skipped: 0,
+            locale,
+        }
+    }
+
+    pub fn next_step(&mut self) -> Option<(ServiceAction, Service)> {
+        self.pending.pop_front()
+    }
+
+    pub fn record(&mut self, action: ServiceAction, service: &Service, result: Result<(), String>) {
+        let name = if self.ordered {
+            format!("{} {}", self.locale.tr(action.label()), service.name())
+        } else {
+            service.name().to_string()
+        };
+        if self.ordered && result.is_err() {
+            self.skipped = self.pending.len();
+            self.pending.clear();
+        }
+        self.results.push((name, result));
+    }
+
+    fn unit(&self) -> &str {
+        self.locale.tr(if self.ordered { "steps in dependency order" } else { "services" })
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        let locale = self.locale;
+        let mut lines = vec![locale.format(
+            "{}: {} of {} {} done",
+            &[
+                locale.tr(self.action.label()),
+                &self.results.len().to_string(),
+                &self.total.to_string(),
+                self.unit(),
+            ],
+        )];
+        lines.extend(self.results.iter().map(|(name, result)| match result {
+            Ok(()) => locale.format("Succeeded: {}", &[name]),
+            Err(e) => locale.format("Failed: {}: {}", &[name, e]),
+        }));
+        if self.skipped > 0 {
+            lines.push(locale.format("Aborted: {} steps skipped", &[&self.skipped.to_string()]));
+        }
+        if self.is_finished() {
+            lines.push(locale.tr("Press any key to dismiss").to_string());
+        }
+        lines
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_width = std::cmp::min(70, area.width.saturating_sub(4));
+        let popup_height = std::cmp::min(self.total as u16 + 6, area.height.saturating_sub(2));
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let locale = self.locale;
+        let action = locale.tr(self.action.label());
+        let failed = self.results.iter().filter(|(_, r)| r.is_err()).count();
+        let title = if self.is_finished() {
+            let skipped = if self.skipped > 0 {
+                locale.format(", {} skipped", &[&self.skipped.to_string()])
+            } else {
+                String::new()
+            };
+            locale.format(
+                "{}: {} succeeded, {} failed{}",
+                &[action, &(self.results.len() - failed).to_string(), &failed.to_string(), &skipped],
+            )
+        } else {
+            format!("{} {} {}...", action, self.total, self.unit())
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(if failed > 0 { theme.error } else { theme.border }))
+            .title(format!(" {} ", title))
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup_area);
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [gauge_area, _, results_area, help_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+
+        let ratio = if self.total == 0 {
+            1.0
+        } else {
+            self.results.len() as f64 / self.total as f64
+        };
+        let gauge = Gauge::default()
+            .gauge_style(Style::default().fg(theme.accent))
+            .label(format!("{}/{}", self.results.len(), self.total))
+            .ratio(ratio);
+        frame.render_widget(gauge, gauge_area);
+
+        let mut lines: Vec<Line> = self
+            .results
+            .iter()
+            .map(|(name, result)| match result {
+                Ok(()) => Line::from(vec![
+                    Span::styled("✔ ", Style::default().fg(theme.active)),
+                    Span::styled(name.clone(), Style::default().fg(theme.text)),
+                ]),
+                Err(e) => Line::from(vec![
+                    Span::styled("✘ ", Style::default().fg(theme.failed)),
+                    Span::styled(name.clone(), Style::default().fg(theme.text)),
+                    Span::styled(format!(": {}", e), Style::default().fg(theme.muted)),
+                ]),
+            })
+            .collect();
+        if self.skipped > 0 {
+            lines.push(Line::from(Span::styled(
+                locale.format("Aborted, {} steps skipped", &[&self.skipped.to_string()]),
+                Style::default().fg(theme.warning),
+            )));
+        }
+        frame.render_widget(Paragraph::new(lines), results_area);
+
+        if self.is_finished() {
+            let help = Paragraph::new(Span::styled(
+                locale.tr("Press any key to dismiss"),
+                Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC),
+            ))
+            .alignment(Alignment::Center);
+            frame.render_widget(help, help_area);
+        }
+    }
+}
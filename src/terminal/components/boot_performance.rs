@@ -0,0 +1,397 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+
+use crate::config::Config;
+use crate::domain::boot_timing::{format_timespan, ChainLink, UnitActivation};
+use crate::errors::{AppError, ServiceError};
+use crate::terminal::app::{Actions, AppEvent};
+use crate::terminal::components::search::SearchBar;
+use crate::terminal::components::overlay::Overlay;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+use crate::usecases::services_manager::ServicesManager;
+
+#[derive(PartialEq)]
+enum View {
+    Blame,
+    CriticalChain,
+}
+
+/// What slowed the boot down, like `systemd-analyze blame` and `critical-chain`.
+pub struct BootPerformance {
+    open: bool,
+    view: View,
+    summary: Result<String, String>,
+    blame: Result<Vec<UnitActivation>, String>,
+    sort_by_name: bool,
+    /// Unit the critical chain is of, the default target when None.
+    chain_unit: Option<String>,
+    chain: Result<Vec<ChainLink>, String>,
+    search: SearchBar,
+    table_state: TableState,
+    sender: Sender<AppEvent>,
+    usecase: Rc<RefCell<ServicesManager>>,
+    config: Rc<Config>,
+}
+
+fn explanation(locale: Locale, e: ServiceError) -> String {
+    locale.tr(AppError::from(e).explanation()).trim().to_string()
+}
+
+impl BootPerformance {
+    pub fn new(sender: Sender<AppEvent>, usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
+        Self {
+            open: false,
+            view: View::Blame,
+            summary: Ok(String::new()),
+            blame: Ok(Vec::new()),
+            sort_by_name: false,
+            chain_unit: None,
+            chain: Ok(Vec::new()),
+            search: SearchBar::default(),
+            table_state: TableState::default(),
+            sender,
+            usecase,
+            config,
+        }
+    }
+
+    /// Analyzes the boot of the manager shown, which may have changed since the last time.
+    pub fn open(&mut self) {
+        self.open = true;
+        self.view = View::Blame;
+        self.chain_unit = None;
+        self.search.clear();
+        self.refresh();
+    }
+
+    fn refresh(&mut self) {
+        let usecase = self.usecase.borrow();
+        self.summary = usecase.get_boot_time().map_err(|e| explanation(self.config.locale, e));
+        self.blame = usecase.get_boot_blame().map_err(|e| explanation(self.config.locale, e));
+        self.chain = usecase.get_critical_chain(self.chain_unit.as_deref()).map_err(|e| explanation(self.config.locale, e));
+        drop(usecase);
+        self.sort();
+        self.table_state.select(Some(0));
+    }
+
+    fn sort(&mut self) {
+        if let Ok(blame) = &mut self.blame {
+            if self.sort_by_name {
+                blame.sort_by(|a, b| a.unit().cmp(b.unit()));
+            } else {
+                blame.sort_by(|a, b| b.activation_ms().cmp(&a.activation_ms()).then_with(|| a.unit().cmp(b.unit())));
+            }
+        }
+        self.update_search_matches();
+    }
+
+    fn show_chain_of(&mut self, unit: Option<String>) {
+        self.chain_unit = unit;
+        self.chain = self
+            .usecase
+            .borrow()
+            .get_critical_chain(self.chain_unit.as_deref())
+            .map_err(|e| explanation(self.config.locale, e));
+        self.view = View::CriticalChain;
+        self.search.clear();
+        self.table_state.select(Some(0));
+    }
+
+    fn toggle_view(&mut self) {
+        self.view = match self.view {
+            View::Blame => View::CriticalChain,
+            View::CriticalChain => View::Blame,
+        };
+        self.update_search_matches();
+        self.table_state.select(Some(0));
+    }
+
+    /// Unit of each row of the view shown.
+    fn units(&self) -> Vec<&str> {
+        match self.view {
+            View::Blame => self.blame.iter().flatten().map(UnitActivation::unit).collect(),
+            View::CriticalChain => self.chain.iter().flatten().map(|link| link.unit.as_str()).collect(),
+        }
+    }
+
+    fn error(&self) -> Option<&str> {
+        match self.view {
+            View::Blame => self.blame.as_ref().err(),
+            View::CriticalChain => self.chain.as_ref().err(),
+        }
+        .map(String::as_str)
+    }
+
+    fn update_search_matches(&mut self) {
+        let units: Vec<String> = self.units().into_iter().map(str::to_string).collect();
+        self.search.update_matches(units.iter().map(String::as_str));
+    }
+
+    fn select_row(&mut self, row: Option<usize>) {
+        if row.is_some() {
+            self.table_state.select(row);
+        }
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        let len = self.units().len();
+        if len == 0 {
+            return;
+        }
+        let selected = self.table_state.selected().unwrap_or(0) as isize;
+        let next = (selected + delta).clamp(0, len as isize - 1);
+        self.table_state.select(Some(next as usize));
+    }
+
+    fn selected_unit(&self) -> Option<String> {
+        let selected = self.table_state.selected()?;
+        self.units().get(selected).map(|unit| unit.to_string())
+    }
+
+    fn title(&self) -> String {
+        let locale = self.config.locale;
+        let title = match (&self.view, &self.chain_unit) {
+            (View::Blame, _) => locale.format(
+                "Boot: activation time per unit, by {}",
+                &[locale.tr(if self.sort_by_name { "name" } else { "time" })],
+            ),
+            (View::CriticalChain, None) => locale.tr("Boot: critical chain of the default target").to_string(),
+            (View::CriticalChain, Some(unit)) => locale.format("Boot: critical chain of {}", &[unit]),
+        };
+        format!(" {} ", title)
+    }
+
+    fn summary(&self) -> String {
+        match &self.summary {
+            Ok(summary) => summary.clone(),
+            Err(e) => self.config.locale.format("Could not analyze the boot: {}", &[e]),
+        }
+    }
+
+    fn help(&self) -> String {
+        let keys = &self.config.keybindings;
+        let enter = match self.view {
+            View::Blame => "Critical chain of unit",
+            View::CriticalChain => "Default target",
+        };
+        let mut shortcuts = vec![
+            ("Blame/critical chain", "Tab".to_string()),
+            (enter, "Enter".to_string()),
+        ];
+        if self.view == View::Blame {
+            shortcuts.push(("Sort", keys.sort.to_string()));
+        }
+        shortcuts.extend([
+            ("Search", keys.search.to_string()),
+            ("Jump to unit", keys.jump.to_string()),
+            ("Close", keys.back.to_string()),
+        ]);
+        self.config.locale.shortcuts(&shortcuts)
+    }
+
+    /// (unit, time, depth) of each row of the view shown.
+    fn rows(&self) -> Vec<(&str, String, usize)> {
+        match self.view {
+            View::Blame => self
+                .blame
+                .iter()
+                .flatten()
+                .map(|activation| (activation.unit(), format_timespan(activation.activation_ms()), 0))
+                .collect(),
+            View::CriticalChain => self
+                .chain
+                .iter()
+                .flatten()
+                .map(|link| {
+                    let time = match (link.active_at_ms, link.took_ms) {
+                        (Some(at), Some(took)) => format!("@{} +{}", format_timespan(at), format_timespan(took)),
+                        (Some(at), None) => format!("@{}", format_timespan(at)),
+                        _ => String::new(),
+                    };
+                    (link.unit.as_str(), time, link.depth)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Overlay for BootPerformance {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn on_key_event(&mut self, key: KeyEvent) {
+        if self.search.is_editing() {
+            if self.search.on_key_event(key) {
+                self.update_search_matches();
+                self.select_row(self.search.current_line());
+            }
+            return;
+        }
+
+        let config = self.config.clone();
+        let keys = &config.keybindings;
+        match key {
+            k if k.code == KeyCode::Esc || keys.back.matches(&k) || keys.boot_performance.matches(&k) => {
+                self.open = false
+            }
+            k if k.code == KeyCode::Tab || keys.next_tab.matches(&k) || keys.previous_tab.matches(&k) => {
+                self.toggle_view()
+            }
+            k if k.code == KeyCode::Enter => match self.view {
+                View::Blame => self.show_chain_of(self.selected_unit()),
+                View::CriticalChain => self.show_chain_of(None),
+            },
+            k if keys.up.matches(&k) => self.scroll(-1),
+            k if keys.down.matches(&k) => self.scroll(1),
+            k if keys.page_up.matches(&k) => self.scroll(-10),
+            k if keys.page_down.matches(&k) => self.scroll(10),
+            k if keys.sort.matches(&k) && self.view == View::Blame => {
+                self.sort_by_name = !self.sort_by_name;
+                self.sort();
+                self.table_state.select(Some(0));
+            }
+            k if keys.search.matches(&k) => self.search.start(self.config.locale),
+            k if keys.next_match.matches(&k) => {
+                let row = self.search.next_match();
+                self.select_row(row);
+            }
+            k if keys.previous_match.matches(&k) => {
+                let row = self.search.previous_match();
+                self.select_row(row);
+            }
+            k if keys.refresh.matches(&k) => self.refresh(),
+            k if keys.jump.matches(&k) => {
+                if let Some(unit) = self.selected_unit() {
+                    self.sender.send(AppEvent::Action(Actions::JumpToService(unit))).unwrap();
+                    self.open = false;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open {
+            return;
+        }
+
+        let popup_width = std::cmp::min(120, area.width.saturating_sub(4));
+        let popup_height = area.height.saturating_sub(2);
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border))
+            .title(format!("{}{}", self.title(), self.search.match_counter()))
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let summary_height = self.summary().lines().count().max(1) as u16 + 1;
+        let search_height = u16::from(self.search.is_visible());
+        let [summary_area, table_area, search_area, help_area] = Layout::vertical([
+            Constraint::Length(summary_height),
+            Constraint::Min(0),
+            Constraint::Length(search_height),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+
+        let summary_color = if self.summary.is_ok() { theme.text } else { theme.error };
+        frame.render_widget(
+            Paragraph::new(self.summary()).style(Style::default().fg(summary_color)),
+            summary_area,
+        );
+
+        if let Some(e) = self.error() {
+            frame.render_widget(
+                Paragraph::new(format!("{}: {}", self.config.locale.tr("Error"), e)).style(Style::default().fg(theme.error)),
+                table_area,
+            );
+        } else {
+            let rows: Vec<Row> = self
+                .rows()
+                .into_iter()
+                .enumerate()
+                .map(|(index, (unit, time, depth))| {
+                    let branch = if depth == 0 { String::new() } else { format!("{}└─", "  ".repeat(depth - 1)) };
+                    let mut line = self.search.highlight(index, unit, theme);
+                    line.spans.insert(0, Span::styled(branch, Style::default().fg(theme.muted)));
+                    Row::new(vec![Cell::from(time), Cell::from(line)])
+                })
+                .collect();
+            let time_width = if self.view == View::Blame { 16 } else { 24 };
+            let table = Table::new(rows, [Constraint::Length(time_width), Constraint::Min(0)])
+                .header(
+                    Row::new(["Time", "Unit"].map(|header| self.config.locale.tr(header)))
+                        .style(Style::default().fg(theme.header).add_modifier(Modifier::BOLD)),
+                )
+                .style(Style::default().fg(theme.text))
+                .row_highlight_style(
+                    Style::default()
+                        .bg(theme.selection_bg)
+                        .fg(theme.selection_fg),
+                );
+            frame.render_stateful_widget(table, table_area, &mut self.table_state);
+        }
+
+        if self.search.is_visible() {
+            self.search.render(frame, search_area, theme);
+        }
+        frame.render_widget(
+            Paragraph::new(self.help())
+                .style(Style::default().fg(theme.muted))
+                .alignment(Alignment::Center),
+            help_area,
+        );
+    }
+
+    fn linear_text(&self) -> Vec<String> {
+        let mut lines = vec![self.title().trim().to_string(), self.summary()];
+        if let Some(e) = self.error() {
+            lines.push(format!("{}: {}", self.config.locale.tr("Error"), e));
+        }
+        if self.search.is_visible() {
+            lines.push(self.search.linear_text());
+        }
+        let locale = self.config.locale;
+        let rows = self.rows();
+        let selected = self.table_state.selected().unwrap_or(0);
+        lines.extend(rows.iter().enumerate().skip(selected).map(|(index, (unit, time, depth))| {
+            locale.format(
+                "Unit {} of {}{}: {}, {}{}",
+                &[
+                    &(index + 1).to_string(),
+                    &rows.len().to_string(),
+                    if index == selected { locale.tr(" (selected)") } else { "" },
+                    unit,
+                    time,
+                    &if self.view == View::CriticalChain {
+                        locale.format(", level {}", &[&(depth + 1).to_string()])
+                    } else {
+                        String::new()
+                    },
+                ],
+            )
+        }));
+        lines.push(self.help());
+        lines
+    }
+}
@@ -0,0 +1,120 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::domain::journal_boot::{Boot, ListedBoot};
+use crate::terminal::components::confirm::Confirmation;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+/// Picker of the boot the log view shows the entries of, every boot first.
+pub struct BootPicker {
+    boots: Vec<ListedBoot>,
+    /// 0 for every boot, then the position in `boots` plus one.
+    selected: usize,
+    locale: Locale,
+}
+
+impl BootPicker {
+    pub fn new(boots: Vec<ListedBoot>, current: Option<Boot>, locale: Locale) -> Self {
+        let selected = current
+            .and_then(|current| boots.iter().position(|listed| listed.boot.id == current.id))
+            .map_or(0, |index| index + 1);
+        Self { boots, selected, locale }
+    }
+
+    /// The boot chosen, `None` for every boot.
+    pub fn choice(&self) -> Option<Boot> {
+        self.selected.checked_sub(1).map(|index| self.boots[index].boot)
+    }
+
+    pub fn on_key_event(&mut self, key: KeyEvent) -> Confirmation {
+        let choices = self.boots.len() + 1;
+        match key.code {
+            KeyCode::Esc => return Confirmation::Cancelled,
+            KeyCode::Enter => return Confirmation::Confirmed,
+            KeyCode::Up => self.selected = self.selected.checked_sub(1).unwrap_or(choices - 1),
+            KeyCode::Down => self.selected = (self.selected + 1) % choices,
+            _ => {}
+        }
+        Confirmation::Pending
+    }
+
+    fn labels(&self) -> Vec<String> {
+        std::iter::once(self.locale.tr("All boots").to_string())
+            .chain(self.boots.iter().map(|listed| format!("{:>4}  {}", listed.boot.offset, listed.entries)))
+            .collect()
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        let locale = self.locale;
+        let mut lines = vec![locale.tr("Show the log of").to_string()];
+        lines.extend(self.labels().into_iter().enumerate().map(|(index, label)| {
+            if index == self.selected {
+                locale.format("Boot (selected): {}", &[&label])
+            } else {
+                locale.format("Boot: {}", &[&label])
+            }
+        }));
+        lines.push(locale.shortcuts(&[
+            ("Choose", "Up/Down".to_string()),
+            ("Show", "Enter".to_string()),
+            ("Cancel", "Esc".to_string()),
+        ]));
+        lines
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let labels = self.labels();
+        let popup_width = std::cmp::min(72, area.width.saturating_sub(4));
+        let popup_height = std::cmp::min(labels.len() as u16 + 3, area.height.saturating_sub(4));
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        // Keeps the selected boot in view when there are more than fit.
+        let visible = popup_height.saturating_sub(3) as usize;
+        let first = self.selected.saturating_sub(visible.saturating_sub(1));
+        let mut text: Vec<Line> = labels
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(visible)
+            .map(|(index, label)| {
+                if index == self.selected {
+                    Line::from(Span::styled(
+                        format!(">> {}", label),
+                        Style::default()
+                            .fg(theme.selection_fg)
+                            .bg(theme.selection_bg)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                } else {
+                    Line::from(format!("   {}", label))
+                }
+            })
+            .collect();
+        text.push(Line::from(Span::styled(
+            self.locale.tr("Enter: show | Esc: cancel"),
+            Style::default().fg(theme.muted),
+        )));
+
+        let picker = Paragraph::new(text).style(Style::default().fg(theme.text)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.highlight_border))
+                .title(format!(" {} ", self.locale.tr("Boot"))),
+        );
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(picker, popup_area);
+    }
+}
@@ -0,0 +1,193 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::domain::unit_clone::UnitClone;
+use crate::terminal::components::confirm::Confirmation;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+const FIELDS: [&str; 2] = ["Name", "Enable and start"];
+const LABEL_WIDTH: usize = 18;
+
+/// Form cloning the selected unit: a copy of its unit file under a new name, opened in the
+/// editor, or a new instance of its template.
+pub struct CloneUnitForm {
+    source: String,
+    name: String,
+    enable_and_start: bool,
+    selected: usize,
+    error: Option<String>,
+    locale: Locale,
+}
+
+impl CloneUnitForm {
+    pub fn new(source: &str, locale: Locale) -> Self {
+        Self {
+            source: source.to_string(),
+            name: String::new(),
+            enable_and_start: false,
+            selected: 0,
+            error: None,
+            locale,
+        }
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn enable_and_start(&self) -> bool {
+        self.enable_and_start
+    }
+
+    /// The clone of the name typed, valid once the form is confirmed.
+    pub fn clone_target(&self) -> Result<UnitClone, String> {
+        UnitClone::parse(&self.source, &self.name)
+    }
+
+    fn is_template(&self) -> bool {
+        self.source.contains('@')
+    }
+
+    fn placeholder(&self) -> &str {
+        self.locale
+            .tr(if self.is_template() { "instance, e.g. tty3, or a new unit name" } else { "e.g. the name with -2" })
+    }
+
+    /// Inserts pasted text in the name, e.g. an instance name.
+    pub fn on_paste(&mut self, text: &str) {
+        if self.selected == 0 {
+            self.name.extend(text.trim().chars().filter(|c| !c.is_control()));
+        }
+    }
+
+    pub fn on_key_event(&mut self, key: KeyEvent) -> Confirmation {
+        self.error = None;
+        match key.code {
+            KeyCode::Esc => return Confirmation::Cancelled,
+            KeyCode::Enter => match self.clone_target() {
+                Ok(_) => return Confirmation::Confirmed,
+                Err(e) => self.error = Some(e),
+            },
+            KeyCode::Tab | KeyCode::Down | KeyCode::BackTab | KeyCode::Up => self.selected = 1 - self.selected,
+            KeyCode::Left | KeyCode::Right | KeyCode::Char(' ') if self.selected == 1 => {
+                self.enable_and_start = !self.enable_and_start
+            }
+            KeyCode::Char(c) if self.selected == 0 => self.name.push(c),
+            KeyCode::Backspace if self.selected == 0 => {
+                self.name.pop();
+            }
+            _ => {}
+        }
+        Confirmation::Pending
+    }
+
+    fn value(&self, index: usize) -> String {
+        match index {
+            0 => self.name.clone(),
+            _ => if self.enable_and_start { "[x]" } else { "[ ]" }.to_string(),
+        }
+    }
+
+    /// What confirming does, e.g. "Will copy nginx.service to nginx-2.service, then open it in the editor".
+    fn outcome(&self) -> String {
+        let locale = self.locale;
+        match self.clone_target() {
+            Ok(clone @ UnitClone::Copy(_)) => {
+                locale.format("Will {}, then open it in the editor", &[&clone.describe(&self.source)])
+            }
+            Ok(clone @ UnitClone::Instance(_)) => locale.format("Will {}", &[&clone.describe(&self.source)]),
+            Err(_) => locale.format("Type the name of the clone of {}", &[&self.source]),
+        }
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        let locale = self.locale;
+        let mut lines = vec![locale.format("Clone {}", &[&self.source])];
+        lines.extend(FIELDS.iter().enumerate().map(|(index, label)| {
+            let selected = if index == self.selected { locale.tr(" (selected)") } else { "" };
+            format!("{}{}: {}", locale.tr(label), selected, self.value(index))
+        }));
+        lines.push(match &self.error {
+            Some(error) => format!("{}: {}", locale.tr("Error"), error),
+            None => self.outcome(),
+        });
+        lines.push(locale.shortcuts(&[
+            ("Next field", "Tab".to_string()),
+            ("Toggle", "Space".to_string()),
+            ("Clone", "Enter".to_string()),
+            ("Cancel", "Esc".to_string()),
+        ]));
+        lines
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_width = std::cmp::min(80, area.width.saturating_sub(4));
+        let popup_height = std::cmp::min(FIELDS.len() as u16 + 9, area.height.saturating_sub(2));
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let locale = self.locale;
+        let description = locale.tr(if self.is_template() {
+            "A name without @ makes a new instance of the template; others copy the unit file."
+        } else {
+            "Copies the unit file, without its drop-ins, under the new name."
+        });
+        let mut text: Vec<Line> = vec![
+            Line::from(Span::styled(description, Style::default().fg(theme.muted))),
+            Line::from(""),
+        ];
+        text.extend(FIELDS.iter().enumerate().map(|(index, label)| {
+            let label_style = if index == self.selected {
+                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.muted)
+            };
+            let value = if index == 0 && self.name.is_empty() {
+                Span::styled(self.placeholder(), Style::default().fg(theme.muted))
+            } else {
+                Span::raw(self.value(index))
+            };
+            Line::from(vec![Span::styled(format!("{:<LABEL_WIDTH$}", locale.tr(label)), label_style), value])
+        }));
+        text.push(Line::from(""));
+        text.push(match &self.error {
+            Some(error) => Line::from(Span::styled(error.clone(), Style::default().fg(theme.error))),
+            None => Line::from(Span::styled(self.outcome(), Style::default().fg(theme.muted))),
+        });
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            locale.tr("Tab: next | Space: toggle | Enter: clone | Esc: cancel"),
+            Style::default().fg(theme.muted),
+        )));
+
+        let form = Paragraph::new(text).style(Style::default().fg(theme.text)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.highlight_border))
+                .title(format!(" {} ", locale.format("Clone {}", &[&self.source]))),
+        );
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(form, popup_area);
+
+        if self.selected == 0 {
+            let column = LABEL_WIDTH + self.name.chars().count();
+            let x = popup_area.x + 1 + u16::try_from(column).unwrap_or(u16::MAX);
+            // Below the description and its blank line.
+            let y = popup_area.y + 3;
+            if x < popup_area.right().saturating_sub(1) {
+                frame.set_cursor_position(Position::new(x, y));
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::config::Column;
+use crate::terminal::components::confirm::Confirmation;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+/// Popup choosing the columns of the service list and their order, for the session.
+pub struct ColumnPicker {
+    /// Every column, the shown ones first in their order, with whether it is shown.
+    entries: Vec<(Column, bool)>,
+    selected: usize,
+    locale: Locale,
+}
+
+impl ColumnPicker {
+    pub fn new(shown: &[Column], locale: Locale) -> Self {
+        let mut entries: Vec<(Column, bool)> = shown.iter().map(|column| (*column, true)).collect();
+        entries.extend(
+            Column::ALL
+                .iter()
+                .filter(|column| !shown.contains(column))
+                .map(|column| (*column, false)),
+        );
+        Self { entries, selected: 0, locale }
+    }
+
+    /// The columns to show, in order.
+    pub fn columns(&self) -> Vec<Column> {
+        self.entries.iter().filter(|(_, shown)| *shown).map(|(column, _)| *column).collect()
+    }
+
+    fn move_selected(&mut self, down: bool) {
+        let target = if down { self.selected + 1 } else { self.selected.wrapping_sub(1) };
+        if target < self.entries.len() {
+            self.entries.swap(self.selected, target);
+            self.selected = target;
+        }
+    }
+
+    pub fn on_key_event(&mut self, key: KeyEvent) -> Confirmation {
+        let shifted = key.modifiers.contains(KeyModifiers::SHIFT);
+        match key.code {
+            KeyCode::Esc => return Confirmation::Cancelled,
+            // At least one column stays shown.
+            KeyCode::Enter if !self.columns().is_empty() => return Confirmation::Confirmed,
+            KeyCode::Up if shifted => self.move_selected(false),
+            KeyCode::Down if shifted => self.move_selected(true),
+            KeyCode::Char('K') => self.move_selected(false),
+            KeyCode::Char('J') => self.move_selected(true),
+            KeyCode::Up => self.selected = self.selected.checked_sub(1).unwrap_or(self.entries.len() - 1),
+            KeyCode::Down => self.selected = (self.selected + 1) % self.entries.len(),
+            KeyCode::Char(' ') => self.entries[self.selected].1 = !self.entries[self.selected].1,
+            _ => {}
+        }
+        Confirmation::Pending
+    }
+
+    fn option(&self, index: usize) -> String {
+        let (column, shown) = self.entries[index];
+        format!("[{}] {}", if shown { "x" } else { " " }, self.locale.tr(column.title()))
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        let locale = self.locale;
+        let mut lines = vec![locale.tr("Columns of the service list").to_string()];
+        lines.extend((0..self.entries.len()).map(|index| {
+            let (column, shown) = self.entries[index];
+            locale.format(
+                "Column{}: {}, {}",
+                &[
+                    if index == self.selected { locale.tr(" (selected)") } else { "" },
+                    locale.tr(column.title()),
+                    locale.tr(if shown { "shown" } else { "hidden" }),
+                ],
+            )
+        }));
+        lines.push(locale.shortcuts(&[
+            ("Choose", "Up/Down".to_string()),
+            ("Show or hide", "Space".to_string()),
+            ("Move", "Shift+Up/Down or K/J".to_string()),
+            ("Apply", "Enter".to_string()),
+            ("Cancel", "Esc".to_string()),
+        ]));
+        lines
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_width = std::cmp::min(50, area.width.saturating_sub(4));
+        let popup_height = std::cmp::min(self.entries.len() as u16 + 4, area.height.saturating_sub(4));
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let mut text: Vec<Line> = (0..self.entries.len())
+            .map(|index| {
+                if index == self.selected {
+                    Line::from(Span::styled(
+                        format!(">> {}", self.option(index)),
+                        Style::default()
+                            .fg(theme.selection_fg)
+                            .bg(theme.selection_bg)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                } else if self.entries[index].1 {
+                    Line::from(format!("   {}", self.option(index)))
+                } else {
+                    Line::from(Span::styled(format!("   {}", self.option(index)), Style::default().fg(theme.muted)))
+                }
+            })
+            .collect();
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            self.locale.tr("Space: show/hide | Shift+Up/Down: move | Enter: apply | Esc: cancel"),
+            Style::default().fg(theme.muted),
+        )));
+
+        let picker = Paragraph::new(text).style(Style::default().fg(theme.text)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border))
+                .title(format!(" {} ", self.locale.tr("Columns"))),
+        );
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(picker, popup_area);
+    }
+}
@@ -0,0 +1,356 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Position, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+
+use crate::config::{Column, Config};
+use crate::terminal::app::{Actions, AppEvent};
+use crate::terminal::components::list::ServiceAction;
+use crate::terminal::components::overlay::Overlay;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+/// Commands with their usage, in the order they are completed.
+const COMMANDS: [(&str, &str); 13] = [
+    ("start", "start <unit>"),
+    ("stop", "stop <unit>"),
+    ("restart", "restart <unit>"),
+    ("reload", "reload <unit>"),
+    ("reload-or-restart", "reload-or-restart <unit>"),
+    ("enable", "enable <unit>"),
+    ("disable", "disable <unit>"),
+    ("freeze", "freeze <unit>"),
+    ("thaw", "thaw <unit>"),
+    ("filter", "filter <query>"),
+    ("sort", "sort <column|none>"),
+    ("host", "host <name>"),
+    ("quit", "quit"),
+];
+
+const ACTIONS: [(&str, ServiceAction); 9] = [
+    ("start", ServiceAction::Start),
+    ("stop", ServiceAction::Stop),
+    ("restart", ServiceAction::Restart),
+    ("reload", ServiceAction::Reload),
+    ("reload-or-restart", ServiceAction::ReloadOrRestart),
+    ("enable", ServiceAction::Enable),
+    ("disable", ServiceAction::Disable),
+    ("freeze", ServiceAction::Freeze),
+    ("thaw", ServiceAction::Thaw),
+];
+
+/// Words of `:filter` standing for a `state:` condition, e.g. "failed" for "state:failed".
+const STATES: [&str; 8] = [
+    "active",
+    "inactive",
+    "failed",
+    "activating",
+    "deactivating",
+    "running",
+    "exited",
+    "dead",
+];
+
+const HELP: &str = "Run: Enter | Complete: Tab | History: ↑/↓ | Close: Esc";
+
+pub enum Command {
+    /// Acts on the unit of the current tab with the given name, "nginx" standing for "nginx.service".
+    Act(ServiceAction, String),
+    Filter(String),
+    /// Sorts by the column, or in the order of the service manager for None.
+    Sort(Option<Column>),
+    Host(String),
+    Quit,
+}
+
+fn usage(command: &str, locale: Locale) -> String {
+    let usage = COMMANDS
+        .iter()
+        .find(|(name, _)| *name == command)
+        .map_or(command, |(_, usage)| usage);
+    locale.format("Usage: :{}", &[usage])
+}
+
+fn single_argument<'a>(command: &str, arguments: &[&'a str], locale: Locale) -> Result<&'a str, String> {
+    match arguments {
+        [argument] => Ok(argument),
+        _ => Err(usage(command, locale)),
+    }
+}
+
+fn column_name(column: &Column) -> String {
+    column.title().to_lowercase()
+}
+
+/// Parses a command line, None when it is empty.
+fn parse(input: &str, columns: &[Column], locale: Locale) -> Result<Option<Command>, String> {
+    let mut words = input.split_whitespace();
+    let Some(command) = words.next() else {
+        return Ok(None);
+    };
+    let arguments: Vec<&str> = words.collect();
+
+    let command = match command {
+        "filter" => Command::Filter(
+            arguments
+                .iter()
+                .map(|word| {
+                    if STATES.contains(word) {
+                        format!("state:{}", word)
+                    } else {
+                        word.to_string()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+        ),
+        "sort" => {
+            let name = single_argument(command, &arguments, locale)?;
+            if name == "none" {
+                Command::Sort(None)
+            } else {
+                let column = columns
+                    .iter()
+                    .find(|column| column_name(column) == name)
+                    .ok_or_else(|| {
+                        let names: Vec<String> = columns.iter().map(column_name).collect();
+                        locale.format("Unknown column \"{}\", expected one of {}, none", &[name, &names.join(", ")])
+                    })?;
+                Command::Sort(Some(*column))
+            }
+        }
+        "host" => Command::Host(single_argument(command, &arguments, locale)?.to_string()),
+        "quit" | "q" if arguments.is_empty() => Command::Quit,
+        "quit" | "q" => return Err(usage("quit", locale)),
+        other => match ACTIONS.iter().find(|(name, _)| *name == other) {
+            Some((_, action)) => Command::Act(*action, single_argument(command, &arguments, locale)?.to_string()),
+            None => return Err(locale.format("Unknown command \"{}\"", &[other])),
+        },
+    };
+    Ok(Some(command))
+}
+
+/// Candidates replacing the last word of the input, from the byte `start`.
+struct Completion {
+    start: usize,
+    candidates: Vec<String>,
+    index: usize,
+}
+
+/// Vim-like `:` command line of the service list, e.g. `:restart nginx` or `:sort active`.
+pub struct CommandLine {
+    open: bool,
+    input: String,
+    completion: Option<Completion>,
+    history: Vec<String>,
+    /// Position in the history while going through it with Up/Down.
+    history_index: Option<usize>,
+    /// Units of the list and hosts the arguments are completed with, as they were on opening.
+    units: Vec<String>,
+    hosts: Vec<String>,
+    sender: Sender<AppEvent>,
+    config: Rc<Config>,
+}
+
+impl CommandLine {
+    pub fn new(sender: Sender<AppEvent>, config: Rc<Config>) -> Self {
+        Self {
+            open: false,
+            input: String::new(),
+            completion: None,
+            history: Vec::new(),
+            history_index: None,
+            units: Vec::new(),
+            hosts: Vec::new(),
+            sender,
+            config,
+        }
+    }
+
+    pub fn open(&mut self, units: Vec<String>, hosts: Vec<String>) {
+        self.open = true;
+        self.units = units;
+        self.hosts = hosts;
+        self.input.clear();
+        self.completion = None;
+        self.history_index = None;
+    }
+
+    /// Words the last word of the input can be completed with: commands first, then the
+    /// argument of the command.
+    fn candidates(&self, start: usize) -> Vec<String> {
+        let word = &self.input[start..];
+        let mut previous = self.input[..start].split_whitespace();
+        let command = previous.next();
+        let arguments = previous.count();
+
+        let mut candidates: Vec<String> = match (command, arguments) {
+            (None, _) => COMMANDS.iter().map(|(name, _)| name.to_string()).collect(),
+            (Some("filter"), _) => STATES.iter().map(|state| state.to_string()).collect(),
+            (Some("sort"), 0) => self
+                .config
+                .columns
+                .iter()
+                .map(column_name)
+                .chain(std::iter::once("none".to_string()))
+                .collect(),
+            (Some("host"), 0) => self.hosts.clone(),
+            (Some(command), 0) if ACTIONS.iter().any(|(name, _)| *name == command) => {
+                let mut units = self.units.clone();
+                units.sort();
+                units
+            }
+            _ => Vec::new(),
+        };
+        candidates.retain(|candidate| candidate.starts_with(word));
+        candidates.dedup();
+        candidates
+    }
+
+    /// Completes the last word, or goes to the next (or previous) candidate on the next Tab.
+    fn complete(&mut self, forward: bool) {
+        let completion = match self.completion.take() {
+            Some(mut completion) => {
+                let len = completion.candidates.len();
+                completion.index = if forward {
+                    (completion.index + 1) % len
+                } else {
+                    (completion.index + len - 1) % len
+                };
+                completion
+            }
+            None => {
+                let start = self.input.rfind(' ').map_or(0, |index| index + 1);
+                let candidates = self.candidates(start);
+                if candidates.is_empty() {
+                    return;
+                }
+                let index = if forward { 0 } else { candidates.len() - 1 };
+                Completion {
+                    start,
+                    candidates,
+                    index,
+                }
+            }
+        };
+        self.input.truncate(completion.start);
+        self.input.push_str(&completion.candidates[completion.index]);
+        self.completion = Some(completion);
+    }
+
+    fn browse_history(&mut self, older: bool) {
+        if self.history.is_empty() {
+            return;
+        }
+        let index = match (self.history_index, older) {
+            (None, true) => Some(self.history.len() - 1),
+            (None, false) => None,
+            (Some(index), true) => Some(index.saturating_sub(1)),
+            (Some(index), false) => (index + 1 < self.history.len()).then_some(index + 1),
+        };
+        self.history_index = index;
+        self.input = index.map(|index| self.history[index].clone()).unwrap_or_default();
+    }
+
+    /// The candidates of the completion in progress, when there is more than one.
+    fn completions(&self) -> Option<&[String]> {
+        self.completion
+            .as_ref()
+            .map(|completion| completion.candidates.as_slice())
+            .filter(|candidates| candidates.len() > 1)
+    }
+}
+
+impl Overlay for CommandLine {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Sends the command once the line is run; mistakes are reported as errors.
+    fn on_key_event(&mut self, key: KeyEvent) {
+        if !matches!(key.code, KeyCode::Tab | KeyCode::BackTab) {
+            self.completion = None;
+        }
+        match key.code {
+            KeyCode::Esc => self.open = false,
+            KeyCode::Enter => {
+                self.open = false;
+                let input = self.input.trim().to_string();
+                if !input.is_empty() && self.history.last() != Some(&input) {
+                    self.history.push(input.clone());
+                }
+                match parse(&input, &self.config.columns, self.config.locale) {
+                    Ok(Some(command)) => self.sender.send(AppEvent::Action(Actions::RunCommand(command))).unwrap(),
+                    Ok(None) => {}
+                    Err(e) => self.sender.send(AppEvent::Error(e.into())).unwrap(),
+                }
+            }
+            KeyCode::Tab => self.complete(true),
+            KeyCode::BackTab => self.complete(false),
+            KeyCode::Up => self.browse_history(true),
+            KeyCode::Down => self.browse_history(false),
+            // Like vim, erasing past the start of the line leaves it.
+            KeyCode::Backspace if self.input.is_empty() => self.open = false,
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Char(c) => self.input.push(c),
+            _ => {}
+        }
+    }
+
+    fn on_paste(&mut self, text: &str) {
+        self.completion = None;
+        self.input.extend(text.trim().chars().filter(|c| !c.is_control()));
+    }
+
+    /// Draws the command line over the bottom of the area.
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open || area.height < 3 {
+            return;
+        }
+
+        let line_area = Rect::new(area.x, area.bottom() - 3, area.width, 3);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.highlight_border))
+            .title(format!(" {} ", self.config.locale.tr("Command")))
+            .title_bottom(Line::from(format!(" {} ", self.config.locale.tr(HELP))).right_aligned());
+        let inner = block.inner(line_area);
+        frame.render_widget(Clear, line_area);
+        frame.render_widget(block, line_area);
+
+        let mut spans = vec![
+            Span::styled(":", Style::default().fg(theme.accent)),
+            Span::styled(self.input.as_str(), Style::default().fg(theme.text)),
+        ];
+        if let Some(candidates) = self.completions() {
+            spans.push(Span::styled(
+                format!("   {}", candidates.join("  ")),
+                Style::default().fg(theme.muted),
+            ));
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), inner);
+        #[allow(clippy::cast_possible_truncation)]
+        frame.set_cursor_position(Position::new(
+            (inner.x + 1 + self.input.chars().count() as u16).min(inner.right().saturating_sub(1)),
+            inner.y,
+        ));
+    }
+
+    fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        let mut lines = vec![locale.format("Command: :{}", &[&self.input])];
+        if let Some(candidates) = self.completions() {
+            lines.push(locale.format("Completions: {}", &[&candidates.join(", ")]));
+        }
+        lines.push(locale.tr(HELP).to_string());
+        lines
+    }
+}
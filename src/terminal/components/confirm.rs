@@ -0,0 +1,129 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::terminal::components::list::ServiceAction;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+/// "Type the unit name to proceed" prompt guarding actions on critical units.
+pub struct ConfirmPrompt {
+    action: ServiceAction,
+    units: Vec<String>,
+    expected: String,
+    input: String,
+    locale: Locale,
+}
+
+pub enum Confirmation {
+    Pending,
+    Confirmed,
+    Cancelled,
+}
+
+impl ConfirmPrompt {
+    pub fn new(action: ServiceAction, critical_units: Vec<String>, locale: Locale) -> Self {
+        Self {
+            action,
+            expected: critical_units.join(" "),
+            units: critical_units,
+            input: String::new(),
+            locale,
+        }
+    }
+
+    fn verb(&self) -> String {
+        self.locale.tr(self.action.label()).to_lowercase()
+    }
+
+    pub fn action(&self) -> ServiceAction {
+        self.action
+    }
+
+    /// The critical units whose names are typed.
+    pub fn units(&self) -> &[String] {
+        &self.units
+    }
+
+    pub fn on_key_event(&mut self, key: KeyEvent) -> Confirmation {
+        match key.code {
+            KeyCode::Esc => return Confirmation::Cancelled,
+            KeyCode::Enter if self.input == self.expected => return Confirmation::Confirmed,
+            KeyCode::Char(c) => self.input.push(c),
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            _ => {}
+        }
+        Confirmation::Pending
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        let locale = self.locale;
+        vec![
+            locale.tr("Outside of the maintenance windows").to_string(),
+            locale.format("Type {} to {}", &[&self.expected, &self.verb()]),
+            locale.format("Typed: {}", &[&self.input]),
+            locale.shortcuts(&[("Confirm", "Enter".to_string()), ("Cancel", "Esc".to_string())]),
+        ]
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_width = std::cmp::min(70, area.width.saturating_sub(4));
+        let popup_height = std::cmp::min(10, area.height.saturating_sub(4));
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let input_style = if self.expected.starts_with(&self.input) {
+            Style::default().fg(theme.text)
+        } else {
+            Style::default().fg(theme.error)
+        };
+        let locale = self.locale;
+        // The units to type are highlighted within the translated sentence.
+        let question = locale.format("Type {} to {}", &["\0", &self.verb()]);
+        let (before, after) = question.split_once('\0').unwrap_or((&question, ""));
+        let text = vec![
+            Line::from(Span::styled(
+                locale.tr("Outside of the maintenance windows"),
+                Style::default().fg(theme.warning).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+            Line::from(vec![
+                Span::raw(before.to_string()),
+                Span::styled(self.expected.clone(), Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+                Span::raw(after.to_string()),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled(format!("> {}", self.input), input_style)),
+            Line::from(""),
+            Line::from(Span::styled(
+                locale.shortcuts(&[("Confirm", "Enter".to_string()), ("Cancel", "Esc".to_string())]),
+                Style::default().fg(theme.muted),
+            )),
+        ];
+
+        let prompt = Paragraph::new(text)
+            .style(Style::default().fg(theme.text))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.warning))
+                    .title(format!(" {} ", locale.format("{} critical unit", &[locale.tr(self.action.label())]))),
+            )
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(prompt, popup_area);
+    }
+}
@@ -0,0 +1,133 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::terminal::components::confirm::Confirmation;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+#[derive(Clone, Copy)]
+pub enum CopyChoice {
+    UnitName,
+    ExecStart,
+    BinaryPath,
+    WorkingDirectory,
+    UnitFile,
+    StatusSummary,
+}
+
+const CHOICES: [CopyChoice; 6] = [
+    CopyChoice::UnitName,
+    CopyChoice::ExecStart,
+    CopyChoice::BinaryPath,
+    CopyChoice::WorkingDirectory,
+    CopyChoice::UnitFile,
+    CopyChoice::StatusSummary,
+];
+
+impl CopyChoice {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CopyChoice::UnitName => "Unit name",
+            CopyChoice::ExecStart => "ExecStart",
+            CopyChoice::BinaryPath => "Binary path",
+            CopyChoice::WorkingDirectory => "Working directory",
+            CopyChoice::UnitFile => "Unit file",
+            CopyChoice::StatusSummary => "Status summary",
+        }
+    }
+}
+
+/// Menu of what of the unit of the details view to copy to the clipboard.
+pub struct CopyMenu {
+    unit: String,
+    selected: usize,
+    locale: Locale,
+}
+
+impl CopyMenu {
+    pub fn new(unit: String, locale: Locale) -> Self {
+        Self { unit, selected: 0, locale }
+    }
+
+    pub fn choice(&self) -> CopyChoice {
+        CHOICES[self.selected]
+    }
+
+    pub fn on_key_event(&mut self, key: KeyEvent) -> Confirmation {
+        match key.code {
+            KeyCode::Esc => return Confirmation::Cancelled,
+            KeyCode::Enter => return Confirmation::Confirmed,
+            KeyCode::Up => self.selected = self.selected.checked_sub(1).unwrap_or(CHOICES.len() - 1),
+            KeyCode::Down => self.selected = (self.selected + 1) % CHOICES.len(),
+            _ => {}
+        }
+        Confirmation::Pending
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        let locale = self.locale;
+        let mut lines = vec![locale.format("Copy from {}", &[&self.unit])];
+        lines.extend(CHOICES.iter().enumerate().map(|(index, choice)| {
+            if index == self.selected {
+                locale.format("Copy (selected): {}", &[locale.tr(choice.label())])
+            } else {
+                locale.format("Copy: {}", &[locale.tr(choice.label())])
+            }
+        }));
+        lines.push(locale.shortcuts(&[
+            ("Choose", "Up/Down".to_string()),
+            ("Copy", "Enter".to_string()),
+            ("Cancel", "Esc".to_string()),
+        ]));
+        lines
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_width = std::cmp::min(40, area.width.saturating_sub(4));
+        let popup_height = std::cmp::min(CHOICES.len() as u16 + 3, area.height.saturating_sub(4));
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let mut text: Vec<Line> = CHOICES
+            .iter()
+            .enumerate()
+            .map(|(index, choice)| {
+                if index == self.selected {
+                    Line::from(Span::styled(
+                        format!(">> {}", self.locale.tr(choice.label())),
+                        Style::default()
+                            .fg(theme.selection_fg)
+                            .bg(theme.selection_bg)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                } else {
+                    Line::from(format!("   {}", self.locale.tr(choice.label())))
+                }
+            })
+            .collect();
+        text.push(Line::from(Span::styled(
+            self.locale.tr("Enter: copy | Esc: cancel"),
+            Style::default().fg(theme.muted),
+        )));
+
+        let menu = Paragraph::new(text).style(Style::default().fg(theme.text)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.highlight_border))
+                .title(format!(" {} ", self.locale.format("Copy from {}", &[&self.unit]))),
+        );
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(menu, popup_area);
+    }
+}
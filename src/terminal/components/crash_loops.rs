@@ -0,0 +1,352 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Wrap},
+    Frame,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant};
+
+use crate::config::Config;
+use crate::domain::crash_loop::{CrashLoopDetector, RestartSettings};
+use crate::domain::service::Service;
+use crate::terminal::app::{Actions, AppEvent};
+use crate::terminal::components::overlay::Overlay;
+use crate::terminal::theme::Theme;
+use crate::usecases::services_manager::ServicesManager;
+
+/// A unit restarted more often than the crash-loop threshold allows.
+struct CrashLoop {
+    unit: String,
+    restarts: u32,
+    settings: RestartSettings,
+}
+
+/// Banner over the service list while units crash-loop, and a panel with their restart
+/// settings, suggestions and one-key access to their logs and unit file.
+pub struct CrashLoops {
+    detector: CrashLoopDetector,
+    loops: Vec<CrashLoop>,
+    open: bool,
+    table_state: TableState,
+    sender: Sender<AppEvent>,
+    usecase: Rc<RefCell<ServicesManager>>,
+    config: Rc<Config>,
+}
+
+impl CrashLoops {
+    pub fn new(sender: Sender<AppEvent>, usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
+        let settings = &config.crash_loop;
+        Self {
+            detector: CrashLoopDetector::new(settings.restarts, Duration::from_secs(settings.minutes * 60)),
+            loops: Vec::new(),
+            open: false,
+            table_state: TableState::default(),
+            sender,
+            usecase,
+            config,
+        }
+    }
+
+    /// Samples the restart counter of the units that may be crash-looping, after each fetch.
+    pub fn check(&mut self, services: &[Service]) {
+        if !self.detector.is_enabled() {
+            return;
+        }
+        let now = Instant::now();
+        let mut loops = Vec::new();
+        for service in services {
+            if !self.detector.should_sample(service) {
+                continue;
+            }
+            // Errors are left to the views reading the unit, not reported every refresh.
+            let Ok(settings) = self.usecase.borrow().get_restart_settings(service.name()) else {
+                continue;
+            };
+            let restarts = self.detector.observe(service.name(), settings.n_restarts(), now);
+            if self.detector.is_crash_loop(restarts) {
+                loops.push(CrashLoop {
+                    unit: service.name().to_string(),
+                    restarts,
+                    settings,
+                });
+            }
+        }
+        loops.sort_by_key(|crash_loop| std::cmp::Reverse(crash_loop.restarts));
+        self.loops = loops;
+
+        if self.loops.is_empty() {
+            self.open = false;
+        } else if self.table_state.selected().is_none_or(|selected| selected >= self.loops.len()) {
+            self.table_state.select(Some(0));
+        }
+    }
+
+    /// Forgets the samples, e.g. after switching to another tab or host.
+    pub fn clear(&mut self) {
+        self.detector.clear();
+        self.loops.clear();
+        self.open = false;
+    }
+
+    pub fn open(&mut self) {
+        if !self.loops.is_empty() {
+            self.open = true;
+            self.table_state.select(Some(0));
+        }
+    }
+
+    fn selected(&self) -> Option<&CrashLoop> {
+        self.table_state.selected().and_then(|selected| self.loops.get(selected))
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        if self.loops.is_empty() {
+            return;
+        }
+        let selected = self.table_state.selected().unwrap_or(0) as isize;
+        let next = (selected + delta).clamp(0, self.loops.len() as isize - 1);
+        self.table_state.select(Some(next as usize));
+    }
+
+    /// Selects the unit in the list, then shows one of its views.
+    fn go_to(&mut self, view: Actions) {
+        let Some(crash_loop) = self.selected() else {
+            return;
+        };
+        self.sender
+            .send(AppEvent::Action(Actions::JumpToService(crash_loop.unit.clone())))
+            .unwrap();
+        self.sender.send(AppEvent::Action(view)).unwrap();
+        self.open = false;
+    }
+
+    fn summary(&self) -> Option<String> {
+        let first = self.loops.first()?;
+        let locale = self.config.locale;
+        let mut summary = locale.format(
+            "{} restarted {} times in the last {} min",
+            &[
+                &first.unit,
+                &first.restarts.to_string(),
+                &(self.detector.window().as_secs() / 60).to_string(),
+            ],
+        );
+        if self.loops.len() > 1 {
+            summary.push_str(&locale.format(" (+{} more crash-looping)", &[&(self.loops.len() - 1).to_string()]));
+        }
+        Some(summary)
+    }
+
+    fn help(&self) -> String {
+        let keys = &self.config.keybindings;
+        self.config.locale.shortcuts(&[
+            ("Select", format!("{}/{}", keys.up, keys.down)),
+            ("Logs", keys.view_logs.to_string()),
+            ("Unit file and properties", keys.properties.to_string()),
+            ("Close", keys.back.to_string()),
+        ])
+    }
+
+    /// Height of the banner, 0 when no unit crash-loops.
+    pub fn banner_height(&self) -> u16 {
+        if self.loops.is_empty() { 0 } else { 1 }
+    }
+
+    pub fn render_banner(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let Some(summary) = self.summary() else {
+            return;
+        };
+        let banner = Line::from(vec![
+            Span::styled(
+                format!(" {} ", self.config.locale.format("Crash loop: {}", &[&summary])),
+                Style::default().fg(theme.error).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                self.config
+                    .locale
+                    .format("Details: {}", &[&self.config.keybindings.crash_loops.to_string()]),
+                Style::default().fg(theme.muted),
+            ),
+        ]);
+        frame.render_widget(Paragraph::new(banner), area);
+    }
+
+    pub fn linear_banner(&self) -> Vec<String> {
+        self.summary()
+            .map(|summary| {
+                self.config.locale.format(
+                    "Crash loop: {}; details: {}",
+                    &[&summary, &self.config.keybindings.crash_loops.to_string()],
+                )
+            })
+            .into_iter()
+            .collect()
+    }
+}
+
+impl Overlay for CrashLoops {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn on_key_event(&mut self, key: KeyEvent) {
+        let keys = &self.config.keybindings;
+        match key {
+            k if k.code == KeyCode::Esc || keys.back.matches(&k) => self.open = false,
+            k if keys.up.matches(&k) => self.scroll(-1),
+            k if keys.down.matches(&k) => self.scroll(1),
+            k if keys.view_logs.matches(&k) => self.go_to(Actions::GoLog),
+            k if keys.properties.matches(&k) => self.go_to(Actions::GoDetails),
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open {
+            return;
+        }
+
+        let popup_width = std::cmp::min(110, area.width.saturating_sub(4));
+        let popup_height = std::cmp::min(26, area.height.saturating_sub(2));
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.error))
+            .title(format!(
+                " {} ({}) ",
+                self.config.locale.tr("Crash-looping units"),
+                self.loops.len()
+            ))
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [units_area, settings_area, suggestions_area, help_area] = Layout::vertical([
+            Constraint::Min(3),
+            Constraint::Length(6),
+            Constraint::Length(8),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+
+        let locale = self.config.locale;
+        let window = (self.detector.window().as_secs() / 60).to_string();
+        let rows = self.loops.iter().map(|crash_loop| {
+            Row::new(vec![
+                Cell::from(crash_loop.unit.clone()),
+                Cell::from(locale.format("{} in {} min", &[&crash_loop.restarts.to_string(), &window])),
+            ])
+            .style(Style::default().fg(theme.failed))
+        });
+        let table = Table::new(rows, [Constraint::Min(0), Constraint::Length(16)])
+            .header(
+                Row::new(["Unit", "Restarts"].map(|header| locale.tr(header)))
+                    .style(Style::default().fg(theme.header).add_modifier(Modifier::BOLD)),
+            )
+            .row_highlight_style(
+                Style::default()
+                    .bg(theme.selection_bg)
+                    .fg(theme.selection_fg),
+            );
+        frame.render_stateful_widget(table, units_area, &mut self.table_state);
+
+        let (settings, suggestions): (Vec<Line>, Vec<Line>) = match self.selected() {
+            Some(crash_loop) => (
+                crash_loop
+                    .settings
+                    .lines()
+                    .into_iter()
+                    .map(|(key, value)| {
+                        Line::from(vec![
+                            Span::styled(format!("{}=", key), Style::default().fg(theme.accent)),
+                            Span::raw(value),
+                        ])
+                    })
+                    .collect(),
+                crash_loop
+                    .settings
+                    .suggestions()
+                    .into_iter()
+                    .map(|suggestion| Line::from(format!("• {}", locale.tr(&suggestion))))
+                    .collect(),
+            ),
+            None => (Vec::new(), Vec::new()),
+        };
+        frame.render_widget(
+            Paragraph::new(settings).style(Style::default().fg(theme.text)).block(
+                Block::default()
+                    .borders(Borders::TOP)
+                    .border_style(Style::default().fg(theme.border))
+                    .title(format!(" {} ", locale.tr("Restart settings"))),
+            ),
+            settings_area,
+        );
+        frame.render_widget(
+            Paragraph::new(suggestions)
+                .style(Style::default().fg(theme.text))
+                .wrap(Wrap { trim: false })
+                .block(
+                    Block::default()
+                        .borders(Borders::TOP)
+                        .border_style(Style::default().fg(theme.border))
+                        .title(format!(" {} ", locale.tr("Suggestions"))),
+                ),
+            suggestions_area,
+        );
+
+        frame.render_widget(
+            Paragraph::new(self.help())
+                .style(Style::default().fg(theme.muted))
+                .alignment(Alignment::Center),
+            help_area,
+        );
+    }
+
+    fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        let mut lines = vec![locale.format("Crash-looping units, {} units", &[&self.loops.len().to_string()])];
+        let selected = self.table_state.selected().unwrap_or(0);
+        for (index, crash_loop) in self.loops.iter().enumerate() {
+            lines.push(locale.format(
+                "Unit {} of {}{}: {}; restarts: {}",
+                &[
+                    &(index + 1).to_string(),
+                    &self.loops.len().to_string(),
+                    if index == selected { locale.tr(" (selected)") } else { "" },
+                    &crash_loop.unit,
+                    &crash_loop.restarts.to_string(),
+                ],
+            ));
+        }
+        if let Some(crash_loop) = self.selected() {
+            lines.extend(
+                crash_loop
+                    .settings
+                    .lines()
+                    .into_iter()
+                    .map(|(key, value)| format!("{}: {}", key, value)),
+            );
+            lines.extend(
+                crash_loop
+                    .settings
+                    .suggestions()
+                    .into_iter()
+                    .map(|suggestion| locale.format("Suggestion: {}", &[locale.tr(&suggestion)])),
+            );
+        }
+        lines.push(self.help());
+        lines
+    }
+}
@@ -0,0 +1,242 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState},
+    Frame,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+
+use crate::config::Config;
+use crate::domain::service::Service;
+use crate::errors::AppError;
+use crate::terminal::app::{Actions, AppEvent};
+use crate::terminal::components::unit_tabs::UnitTab;
+use crate::terminal::theme::Theme;
+use crate::usecases::services_manager::ServicesManager;
+
+/// A row of the flattened dependency tree.
+struct DependencyItem {
+    unit: String,
+    relation: &'static str,
+    depth: usize,
+    expanded: bool,
+}
+
+pub struct ServiceDependencies {
+    service_name: String,
+    items: Vec<DependencyItem>,
+    list_state: ListState,
+    sender: Sender<AppEvent>,
+    usecase: Rc<RefCell<ServicesManager>>,
+    config: Rc<Config>,
+}
+
+impl ServiceDependencies {
+    pub fn new(sender: Sender<AppEvent>, usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
+        Self {
+            service_name: String::new(),
+            items: Vec::new(),
+            list_state: ListState::default(),
+            sender,
+            usecase,
+            config,
+        }
+    }
+
+    pub fn update(&mut self, service: Service) {
+        self.service_name = service.name().to_string();
+        self.items.clear();
+        self.list_state.select(Some(0));
+    }
+
+    pub fn fetch_dependencies(&mut self) {
+        match self.load_children(&self.service_name.clone(), 0) {
+            Ok(items) => self.items = items,
+            Err(e) => self.sender.send(AppEvent::Error(e)).unwrap(),
+        }
+    }
+
+    fn load_children(&self, unit: &str, depth: usize) -> Result<Vec<DependencyItem>, AppError> {
+        let dependencies = self
+            .usecase
+            .borrow()
+            .get_unit_dependencies(unit)?;
+
+        Ok(dependencies
+            .by_relation()
+            .into_iter()
+            .flat_map(|(relation, units)| {
+                units.iter().map(move |unit| DependencyItem {
+                    unit: unit.clone(),
+                    relation,
+                    depth,
+                    expanded: false,
+                })
+            })
+            .collect())
+    }
+
+    fn toggle_selected(&mut self) {
+        let Some(index) = self.list_state.selected() else {
+            return;
+        };
+        let Some(item) = self.items.get(index) else {
+            return;
+        };
+
+        if item.expanded {
+            let depth = item.depth;
+            let end = self.items[index + 1..]
+                .iter()
+                .position(|child| child.depth <= depth)
+                .map_or(self.items.len(), |offset| index + 1 + offset);
+            self.items.drain(index + 1..end);
+            self.items[index].expanded = false;
+        } else {
+            match self.load_children(&item.unit.clone(), item.depth + 1) {
+                Ok(children) => {
+                    self.items.splice(index + 1..index + 1, children);
+                    self.items[index].expanded = true;
+                }
+                Err(e) => self.sender.send(AppEvent::Error(e)).unwrap(),
+            }
+        }
+    }
+
+    fn select(&mut self, delta: isize) {
+        if self.items.is_empty() {
+            return;
+        }
+        let selected = self.list_state.selected().unwrap_or(0) as isize;
+        let next = (selected + delta).clamp(0, self.items.len() as isize - 1);
+        self.list_state.select(Some(next as usize));
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let items: Vec<ListItem> = self
+            .items
+            .iter()
+            .map(|item| {
+                let marker = if item.expanded { "▾ " } else { "▸ " };
+                ListItem::new(Line::from(vec![
+                    Span::raw("  ".repeat(item.depth)),
+                    Span::styled(marker, Style::default().fg(theme.muted)),
+                    Span::styled(item.unit.clone(), Style::default().fg(theme.accent)),
+                    Span::styled(format!("  {}", item.relation), Style::default().fg(theme.muted)),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .style(Style::default().fg(theme.text))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.border))
+                    .title(self.config.locale.format(" {} dependencies ", &[&self.service_name]))
+                    .title_alignment(Alignment::Center),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(theme.selection_bg)
+                    .fg(theme.selection_fg)
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    /// Dependencies from the selected one onwards, with their depth in the tree.
+    pub fn linear_text(&self) -> Vec<String> {
+        if self.items.is_empty() {
+            return vec![self.config.locale.tr("No dependencies").to_string()];
+        }
+        let selected = self.list_state.selected().unwrap_or(0);
+        self.items
+            .iter()
+            .enumerate()
+            .skip(selected)
+            .map(|(index, item)| {
+                format!(
+                    "{} {} of {}{}: {}, level {}, {}",
+                    item.relation,
+                    index + 1,
+                    self.items.len(),
+                    if index == selected { " (selected)" } else { "" },
+                    item.unit,
+                    item.depth + 1,
+                    if item.expanded { "expanded" } else { "collapsed" }
+                )
+            })
+            .collect()
+    }
+
+    pub fn on_key_event(&mut self, key: KeyEvent) {
+        if let Some(tab) = UnitTab::from_key(&key).filter(|tab| *tab != UnitTab::Dependencies) {
+            self.reset();
+            self.sender.send(AppEvent::Action(tab.action())).unwrap();
+            return;
+        }
+
+        let config = self.config.clone();
+        let keys = &config.keybindings;
+        match key {
+            k if keys.next_tab.matches(&k) => {
+                self.reset();
+                self.sender.send(AppEvent::Action(UnitTab::Dependencies.next().action())).unwrap();
+            }
+            k if keys.previous_tab.matches(&k) => {
+                self.reset();
+                self.sender.send(AppEvent::Action(UnitTab::Dependencies.previous().action())).unwrap();
+            }
+            k if keys.up.matches(&k) => self.select(-1),
+            k if keys.down.matches(&k) => self.select(1),
+            k if keys.page_up.matches(&k) => self.select(-10),
+            k if keys.page_down.matches(&k) => self.select(10),
+            k if k.code == KeyCode::Enter => self.toggle_selected(),
+            k if keys.jump.matches(&k) => {
+                if let Some(item) = self.list_state.selected().and_then(|i| self.items.get(i)) {
+                    let unit = item.unit.clone();
+                    self.reset();
+                    self.sender
+                        .send(AppEvent::Action(Actions::JumpToService(unit)))
+                        .unwrap();
+                }
+            }
+            k if keys.back.matches(&k) => {
+                self.reset();
+                self.sender.send(AppEvent::Action(Actions::GoList)).unwrap();
+            }
+            _ => {}
+        }
+    }
+
+    pub fn shortcuts(&mut self, theme: &Theme) -> Vec<Line<'_>> {
+        let locale = self.config.locale;
+        let keys = &self.config.keybindings;
+        vec![
+            Line::from(vec![Span::styled(
+                locale.tr("Actions"),
+                Style::default()
+                    .fg(theme.title)
+                    .add_modifier(Modifier::BOLD),
+            )]),
+            Line::from(locale.shortcuts(&[
+                ("Navigate", format!("{}/{}", keys.up, keys.down)),
+                ("Switch tabs", format!("{}/{} {} 1-8", keys.previous_tab, keys.next_tab, locale.tr("or"))),
+                ("Expand/collapse", "Enter".to_string()),
+                ("Jump to unit", keys.jump.to_string()),
+                ("Go back", keys.back.to_string()),
+            ])),
+        ]
+    }
+
+    pub fn reset(&mut self) {
+        self.items.clear();
+        self.list_state.select(Some(0));
+    }
+}
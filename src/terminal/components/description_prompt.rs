@@ -0,0 +1,104 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Position, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+const HELP: &str = "Set: Enter | Cancel: Esc";
+
+/// Prompt replacing the Description= of the unit of the details view, starting from the
+/// current one.
+#[derive(Default)]
+pub struct DescriptionPrompt {
+    open: bool,
+    unit: String,
+    input: String,
+    error: Option<String>,
+    locale: Locale,
+}
+
+impl DescriptionPrompt {
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn open(&mut self, unit: &str, description: &str, locale: Locale) {
+        self.open = true;
+        self.locale = locale;
+        self.unit = unit.to_string();
+        self.input = description.to_string();
+        self.error = None;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    /// Keeps the prompt open with the reason the description could not be set.
+    pub fn failed(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    /// The description to set, once Enter is pressed.
+    pub fn on_key_event(&mut self, key: KeyEvent) -> Option<String> {
+        match key.code {
+            KeyCode::Esc => self.open = false,
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Char(c) => self.input.push(c),
+            KeyCode::Enter if !self.input.trim().is_empty() => return Some(self.input.trim().to_string()),
+            _ => {}
+        }
+        None
+    }
+
+    fn title(&self) -> String {
+        self.locale.format("Description of {}", &[&self.unit])
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        let locale = self.locale;
+        let mut lines = vec![self.title(), locale.format("Description: {}", &[&self.input])];
+        lines.extend(self.error.iter().map(|error| format!("{}: {}", locale.tr("Error"), error)));
+        lines.push(locale.tr(HELP).to_string());
+        lines
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open || area.height < 3 {
+            return;
+        }
+
+        let prompt_area = Rect::new(area.x, area.bottom() - 3, area.width, 3);
+        let border = if self.error.is_some() { theme.error } else { theme.highlight_border };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border))
+            .title(format!(" {} ", self.title()))
+            .title_bottom(Line::from(format!(" {} ", self.locale.tr(HELP))).right_aligned());
+        let inner = block.inner(prompt_area);
+        frame.render_widget(Clear, prompt_area);
+        frame.render_widget(block, prompt_area);
+
+        let line = match &self.error {
+            Some(error) => Line::from(vec![
+                Span::styled(self.input.as_str(), Style::default().fg(theme.text)),
+                Span::styled(format!("  {}", error), Style::default().fg(theme.error)),
+            ]),
+            None => Line::from(Span::styled(self.input.as_str(), Style::default().fg(theme.text))),
+        };
+        frame.render_widget(Paragraph::new(line), inner);
+        #[allow(clippy::cast_possible_truncation)]
+        frame.set_cursor_position(Position::new(
+            (inner.x + self.input.chars().count() as u16).min(inner.right().saturating_sub(1)),
+            inner.y,
+        ));
+    }
+}
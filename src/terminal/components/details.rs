@@ -1,126 +1,1478 @@
 use ratatui::{
-    layout::{Alignment, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Sparkline},
     Frame,
 };
 use std::sync::mpsc::Sender;
 use std::sync::{Arc, Mutex};
 use std::rc::Rc;
 use std::cell::RefCell;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 use crossterm::event::{KeyCode, KeyEvent};
 
+use crate::infrastructure::clipboard::copy_to_clipboard;
+use crate::config::Config;
+use crate::domain::exit_diagnosis::ExitDiagnosis;
+use crate::domain::resource_limits::{cgroup_section, ResourceLimits};
+use crate::domain::resource_usage::UsageHistory;
+use crate::domain::action_plan::UnitAction;
 use crate::domain::service::Service;
+use crate::domain::service_property::ServiceProperty;
+use crate::domain::unit_alias::UnitLinks;
+use crate::domain::unit_capabilities::UnitCapabilities;
+use crate::domain::unit_conditions::UnitConditions;
+use crate::domain::unit_environment::exec_section;
+use crate::domain::humanize::{format_timestamp_ago, now_usec};
+use crate::domain::journal_entry::JournalEntry;
+use crate::domain::process::UnitProcess;
+use crate::domain::unit_file::{SettingNote, UnitFile};
+use crate::domain::unit_history::UnitHistory;
+use crate::domain::security_audit::SecurityAudit;
+use crate::domain::unit_property::{PropertySection, UnitProperty};
+use crate::domain::unit_triggers::UnitTriggers;
+use crate::errors::{AppError, ServiceError};
 use crate::terminal::app::{Actions, AppEvent};
+use crate::terminal::components::confirm::Confirmation;
+use crate::terminal::components::documentation::DocumentationViewer;
+use crate::terminal::components::copy_menu::{CopyChoice, CopyMenu};
+use crate::terminal::components::description_prompt::DescriptionPrompt;
+use crate::terminal::components::environment_prompt::{EnvironmentPrompt, EnvironmentRequest};
+use crate::terminal::components::limits_form::ResourceLimitsForm;
+use crate::terminal::components::search::SearchBar;
+use crate::terminal::components::status_bar::ActionOutcome;
+use crate::terminal::components::timeline::{timeline_lines, timeline_text};
+use crate::terminal::components::unit_tabs::UnitTab;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
 use crate::usecases::services_manager::ServicesManager;
 
+/// How long a property that changed stays highlighted.
+const CHANGE_HIGHLIGHT: Duration = Duration::from_secs(5);
+/// Log lines under the status, as many as `systemctl status` prints.
+const STATUS_LOG_LINES: u32 = 10;
+/// Units of the trail shown in the title before the current one, older ones standing as "…".
+const TRAIL_SHOWN: usize = 3;
+/// Bounds of the height of the usage graphs, in rows.
+const GRAPHS_MIN_HEIGHT: u16 = 5;
+const GRAPHS_MAX_HEIGHT: u16 = 15;
+
 pub struct ServiceDetails {
     service: Option<Arc<Mutex<Service>>>,
-    unit_file: String,
+    /// The unit file and its drop-ins, as `systemctl cat` prints them.
+    unit_files: Vec<UnitFile>,
+    /// Search in the unit file tab.
+    search: SearchBar,
+    /// Type-specific properties of the properties tab grouped in sections, empty when the unit
+    /// type has none.
+    properties: Vec<PropertySection>,
+    /// Previous value and time of change of the properties that changed between two reads,
+    /// by section and label.
+    changes: HashMap<(&'static str, &'static str), (String, Instant)>,
+    /// The timers and sockets activating the unit, shown in the status tab.
+    activation: Vec<(&'static str, String)>,
+    /// Rows of the usage graphs of the status tab.
+    graphs_height: u16,
+    /// Alias names of the unit and the symlinks to its unit file, shown in the status tab.
+    links: Option<UnitLinks>,
+    /// The units the unit triggers and the ones triggering it, links of the status tab.
+    triggers: UnitTriggers,
+    /// Index in `triggers.by_relation()` of the link Enter follows.
+    trigger_link: usize,
+    /// Units shown before following links, the last one being where back returns.
+    trail: Vec<String>,
+    /// Whether the unit is a timer or is triggered by one, making run_now available.
+    timed: bool,
+    /// The unit itself when it is a socket, or the sockets activating it, started and stopped
+    /// with start and stop.
+    sockets: Vec<String>,
+    /// What the manager accepts for the unit, reload being greyed out without CanReload.
+    capabilities: Option<UnitCapabilities>,
+    /// The Condition*= settings, telling why an inactive unit was skipped.
+    conditions: Option<UnitConditions>,
+    /// Why the main process of a failed service ended, with what to look at.
+    diagnosis: Option<ExitDiagnosis>,
+    /// Observed availability, for the units whose uptime is tracked.
+    availability: Option<String>,
+    /// CPU and memory samples of the last minutes, graphed in the status tab.
+    usage: Option<UsageHistory>,
+    /// Processes of the unit, for its main PID and task count in the status tab.
+    processes: Vec<UnitProcess>,
+    /// When the unit entered its active state, 0 when unknown.
+    state_change: u64,
+    /// Read with the status, the list not asking every unit for it.
+    freezer: String,
+    /// The last lines of its log, under the status like `systemctl status` prints them.
+    recent_log: Vec<String>,
+    /// Starts, stops and failures from the journal, read when the history tab is shown.
+    history: Option<Result<UnitHistory, String>>,
+    /// Findings of `systemd-analyze security`, run when the security tab is shown.
+    security: Option<Result<SecurityAudit, String>>,
+    environment: EnvironmentPrompt,
+    description: DescriptionPrompt,
+    copy_menu: Option<CopyMenu>,
+    limits: Option<ResourceLimitsForm>,
+    documentation: Option<DocumentationViewer>,
     sender: Sender<AppEvent>,
-    scroll: u16,
+    /// One of the status, properties, unit file, history and security tabs.
+    tab: UnitTab,
+    /// Scroll of the status, properties, unit file, history and security tabs, kept when switching between them.
+    scrolls: [u16; 5],
     usecase: Rc<RefCell<ServicesManager>>,
+    config: Rc<Config>,
 }
 
 impl ServiceDetails {
-    pub fn new(sender: Sender<AppEvent>,  usecase: Rc<RefCell<ServicesManager>>) -> Self {
+    pub fn new(sender: Sender<AppEvent>,  usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
         Self {
             service: None,
             sender,
-            unit_file: String::new(),
-            scroll: 0,
-            usecase
+            unit_files: Vec::new(),
+            search: SearchBar::default(),
+            properties: Vec::new(),
+            changes: HashMap::new(),
+            activation: Vec::new(),
+            graphs_height: config.graphs_height.clamp(GRAPHS_MIN_HEIGHT, GRAPHS_MAX_HEIGHT),
+            links: None,
+            triggers: UnitTriggers::default(),
+            trigger_link: 0,
+            trail: Vec::new(),
+            timed: false,
+            sockets: Vec::new(),
+            capabilities: None,
+            conditions: None,
+            diagnosis: None,
+            availability: None,
+            usage: None,
+            processes: Vec::new(),
+            state_change: 0,
+            freezer: String::new(),
+            recent_log: Vec::new(),
+            history: None,
+            security: None,
+            environment: EnvironmentPrompt::default(),
+            description: DescriptionPrompt::default(),
+            copy_menu: None,
+            limits: None,
+            documentation: None,
+            tab: UnitTab::Status,
+            scrolls: [0; 5],
+            usecase,
+            config,
+        }
+    }
+
+    pub fn tab(&self) -> UnitTab {
+        self.tab
+    }
+
+    /// Shows one of the status, properties, unit file, history and security tabs.
+    pub fn set_tab(&mut self, tab: UnitTab) {
+        if tab.is_details() {
+            self.tab = tab;
+            if self.history.is_none() {
+                self.fetch_history();
+            }
+            if self.security.is_none() {
+                self.fetch_security();
+            }
+        }
+    }
+
+    /// Reads the history of the unit when the history tab is shown, the journal of a week
+    /// taking a while to go through.
+    fn fetch_history(&mut self) {
+        if self.tab != UnitTab::History {
+            return;
+        }
+        let Some(service_arc) = &self.service else {
+            return;
+        };
+        let service = service_arc.lock().unwrap().clone();
+        self.history = Some(
+            self.usecase
+                .borrow()
+                .get_unit_history(&service)
+                .map_err(|e| self.config.locale.tr(AppError::from(e).explanation()).to_string()),
+        );
+    }
+
+    /// Analyzes the unit when the security tab is shown, `systemd-analyze` taking a moment.
+    fn fetch_security(&mut self) {
+        if self.tab != UnitTab::Security {
+            return;
+        }
+        let Some(service_arc) = &self.service else {
+            return;
+        };
+        let name = service_arc.lock().unwrap().name().to_string();
+        self.security = Some(
+            self.usecase
+                .borrow()
+                .get_security_audit(&name)
+                .map_err(|e| self.config.locale.tr(AppError::from(e).explanation()).to_string()),
+        );
+    }
+
+    pub fn scroll(&self) -> u16 {
+        match self.tab {
+            UnitTab::Properties => self.scrolls[1],
+            UnitTab::UnitFile => self.scrolls[2],
+            UnitTab::History => self.scrolls[3],
+            UnitTab::Security => self.scrolls[4],
+            _ => self.scrolls[0],
+        }
+    }
+
+    /// Scrolls the tab shown, e.g. back to where it was in the previous session.
+    pub fn set_scroll(&mut self, scroll: u16) {
+        *self.scroll_mut() = scroll;
+    }
+
+    fn scroll_mut(&mut self) -> &mut u16 {
+        match self.tab {
+            UnitTab::Properties => &mut self.scrolls[1],
+            UnitTab::UnitFile => &mut self.scrolls[2],
+            UnitTab::History => &mut self.scrolls[3],
+            UnitTab::Security => &mut self.scrolls[4],
+            _ => &mut self.scrolls[0],
+        }
+    }
+
+    /// (label, value) of the status tab, like the header of `systemctl status`.
+    fn status(&self, service: &Service) -> Vec<(&'static str, String)> {
+        let state = service.state();
+        let fragment = self.unit_files.iter().find(|file| !file.drop_in && !file.path.is_empty());
+        let loaded = match fragment {
+            Some(file) => format!("{} ({}; {}; preset: {})", state.load(), file.path, state.file(), state.preset()),
+            None => format!("{} ({}; preset: {})", state.load(), state.file(), state.preset()),
+        };
+        let mut active = format!("{} ({})", state.active(), state.sub());
+        if self.state_change > 0 {
+            active.push_str(&self.config.locale.format(" since {}", &[&format_timestamp_ago(self.state_change, now_usec())]));
+        }
+        let mut status = vec![
+            ("Unit", service.name().to_string()),
+            ("Description", service.description().to_string()),
+            ("Loaded", loaded),
+        ];
+        let drop_ins: Vec<&str> = self.unit_files.iter().filter(|file| file.drop_in).map(|file| file.path.as_str()).collect();
+        if !drop_ins.is_empty() {
+            status.push(("Drop-In", drop_ins.join(", ")));
+        }
+        if let Some(links) = &self.links {
+            if !links.aliases.is_empty() {
+                status.push(("Alias", links.aliases.join(", ")));
+            }
+            status.extend(links.links.iter().map(|link| ("Link", links.describe_link(link))));
+        }
+        status.push(("Active", active));
+        if let Some(property) = Self::service_property(service)
+            && property.main_pid() != 0
+        {
+            let pid = property.main_pid();
+            match self.processes.iter().find(|process| process.pid() == pid) {
+                Some(process) => status.push(("Main PID", format!("{} ({})", pid, process.command()))),
+                None => status.push(("Main PID", pid.to_string())),
+            }
+        }
+        if !self.processes.is_empty() {
+            status.push(("Tasks", self.processes.len().to_string()));
+        }
+        if matches!(self.freezer.as_str(), "frozen" | "freezing") {
+            status.push(("Freezer", self.freezer.clone()));
+        }
+        if let Some(availability) = &self.availability {
+            status.push(("Availability", availability.clone()));
+        }
+        if let Some(usage) = &self.usage {
+            status.extend(usage.cpu_summary().map(|summary| ("CPU", summary)));
+            status.extend(usage.memory_summary().map(|summary| ("Memory", summary)));
+        }
+        if let Some(capabilities) = &self.capabilities {
+            status.push(("Allows", capabilities.summary()));
+        }
+        if let Some(summary) = self.conditions.as_ref().and_then(UnitConditions::summary) {
+            status.push(("Condition", summary));
+        }
+        if let Some(diagnosis) = &self.diagnosis {
+            status.push(("Exit status", diagnosis.cause().to_string()));
+        }
+        status.extend(self.activation.iter().cloned());
+        status
+    }
+
+    /// Each condition with whether it was met, the ones that were not highlighted when they
+    /// kept the unit from starting.
+    fn condition_lines(&self, theme: &Theme) -> Vec<Line<'static>> {
+        let Some(conditions) = &self.conditions else {
+            return Vec::new();
+        };
+        conditions
+            .conditions()
+            .iter()
+            .map(|condition| {
+                let style = if condition.is_failed() && conditions.skipped() {
+                    Style::default().fg(theme.failed).add_modifier(Modifier::BOLD)
+                } else if condition.is_failed() {
+                    Style::default().fg(theme.warning)
+                } else {
+                    Style::default().fg(theme.muted)
+                };
+                Line::from(vec![
+                    Span::raw(format!("{:<14}", "")),
+                    Span::styled(format!("{} ({})", condition.describe(), condition.state_label()), style),
+                ])
+            })
+            .collect()
+    }
+
+    /// The OOM killer messages of the journal and what to look at, under the exit of a failed
+    /// service.
+    fn diagnosis_lines(&self, theme: &Theme) -> Vec<Line<'static>> {
+        let Some(diagnosis) = &self.diagnosis else {
+            return Vec::new();
+        };
+        let mut lines: Vec<Line<'static>> = diagnosis
+            .oom_lines()
+            .iter()
+            .map(|line| {
+                Line::from(vec![
+                    Span::raw(format!("{:<14}", "")),
+                    Span::styled(line.clone(), Style::default().fg(theme.failed)),
+                ])
+            })
+            .collect();
+        lines.extend(diagnosis.suggestions().iter().map(|suggestion| {
+            Line::from(vec![
+                Span::raw(format!("{:<14}", "")),
+                Span::styled(format!("→ {}", suggestion), Style::default().fg(theme.warning)),
+            ])
+        }));
+        lines
+    }
+
+    /// The units the unit triggers and the ones triggering it, the link Enter follows selected.
+    fn trigger_lines(&self, theme: &Theme) -> Vec<Line<'static>> {
+        self.triggers
+            .by_relation()
+            .into_iter()
+            .enumerate()
+            .map(|(index, (label, unit))| {
+                let style = if index == self.trigger_link {
+                    Style::default().fg(theme.selection_fg).bg(theme.selection_bg)
+                } else {
+                    Style::default().add_modifier(Modifier::UNDERLINED)
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!("{:<14}", self.config.locale.tr(label)),
+                        Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(unit.to_string(), style),
+                ])
+            })
+            .collect()
+    }
+
+    /// e.g. "… › a.timer › b.service › c.socket › d.service", the units shown before following
+    /// links then the current one.
+    fn breadcrumb(&self, name: &str) -> String {
+        let shown = &self.trail[self.trail.len().saturating_sub(TRAIL_SHOWN)..];
+        let older = (self.trail.len() > TRAIL_SHOWN).then_some("…");
+        older
+            .into_iter()
+            .chain(shown.iter().map(String::as_str))
+            .chain(std::iter::once(name))
+            .collect::<Vec<_>>()
+            .join(" › ")
+    }
+
+    /// The text of each line of the unit file tab: a header per file, then its lines, files
+    /// separated by a blank line.
+    fn unit_file_texts(&self) -> Vec<String> {
+        let mut texts = Vec::new();
+        for (index, file) in self.unit_files.iter().enumerate() {
+            if index > 0 {
+                texts.push(String::new());
+            }
+            if !file.path.is_empty() {
+                texts.push(format!("# {} ({})", file.path, file.kind()));
+            }
+            texts.extend(file.lines.iter().map(|line| line.text.clone()));
+        }
+        texts
+    }
+
+    fn unit_file_lines(&self, theme: &Theme) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        for (index, file) in self.unit_files.iter().enumerate() {
+            if index > 0 {
+                lines.push(Line::from(""));
+            }
+            if !file.path.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    format!("# {} ({})", file.path, file.kind()),
+                    Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+                )));
+            }
+            for line in &file.lines {
+                let index = lines.len();
+                let mut shown = if self.search.is_match(index) {
+                    self.search.highlight(index, &line.text, theme)
+                } else {
+                    match &line.note {
+                        Some(SettingNote::OverriddenBy(_)) => Line::styled(
+                            line.text.clone(),
+                            Style::default().fg(theme.muted).add_modifier(Modifier::CROSSED_OUT),
+                        ),
+                        Some(SettingNote::Overrides(_)) => Line::styled(line.text.clone(), Style::default().fg(theme.warning)),
+                        _ => unit_file_syntax(&line.text, theme),
+                    }
+                };
+                if let Some(note) = &line.note {
+                    shown.spans.push(Span::styled(format!("  ← {}", note.describe()), Style::default().fg(theme.muted)));
+                }
+                lines.push(shown);
+            }
         }
+        lines
     }
 
-    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
         if let Some(service_arc) = &self.service {
             let service = service_arc.lock().unwrap();
+            let locale = self.config.locale;
 
-            let paragraph = Paragraph::new(self.unit_file.clone())
+            let labelled = |pairs: Vec<(&'static str, String)>| -> Vec<Line<'static>> {
+                pairs
+                    .into_iter()
+                    .map(|(label, value)| {
+                        Line::from(vec![
+                            Span::styled(
+                                format!("{:<14}", label),
+                                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+                            ),
+                            Span::raw(value),
+                        ])
+                    })
+                    .collect()
+            };
+            let lines = match self.tab {
+                UnitTab::Properties if self.properties.is_empty() => vec![Line::styled(
+                    locale.tr("This unit type has no properties to show."),
+                    Style::default().fg(theme.muted),
+                )],
+                UnitTab::Properties => {
+                    let mut lines = Vec::new();
+                    for (title, pairs) in self.properties.clone() {
+                        if !lines.is_empty() {
+                            lines.push(Line::from(""));
+                        }
+                        lines.push(Line::styled(
+                            title,
+                            Style::default().fg(theme.header).add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                        ));
+                        for (label, value) in pairs {
+                            let mut line = labelled(vec![(label, value)]).remove(0);
+                            if let Some(previous) = self.previous_value(title, label) {
+                                line.spans[1].style = Style::default().fg(theme.warning).add_modifier(Modifier::BOLD);
+                                line.spans.push(Span::styled(
+                                    format!("  ({})", locale.format("was {}", &[previous])),
+                                    Style::default().fg(theme.muted),
+                                ));
+                            }
+                            lines.push(line);
+                        }
+                    }
+                    lines
+                }
+                UnitTab::UnitFile => self.unit_file_lines(theme),
+                UnitTab::History => match &self.history {
+                    Some(Ok(history)) => {
+                        timeline_lines(history, now_usec(), area.width.saturating_sub(2) as usize, theme, self.config.locale)
+                    }
+                    Some(Err(e)) => vec![Line::styled(e.clone(), Style::default().fg(theme.error))],
+                    None => Vec::new(),
+                },
+                UnitTab::Security => match &self.security {
+                    Some(Ok(audit)) => security_lines(audit, theme, locale),
+                    Some(Err(e)) => vec![Line::styled(e.clone(), Style::default().fg(theme.error))],
+                    None => Vec::new(),
+                },
+                _ => {
+                    let status = self.status(&service).into_iter().map(|(label, value)| (locale.tr(label), value));
+                    let mut lines = labelled(status.collect());
+                    lines.extend(self.condition_lines(theme));
+                    lines.extend(self.diagnosis_lines(theme));
+                    lines.extend(self.trigger_lines(theme));
+                    if !self.recent_log.is_empty() {
+                        lines.push(Line::from(""));
+                        lines.extend(self.recent_log.iter().map(|line| Line::from(line.clone())));
+                    }
+                    lines
+                }
+            };
+
+            let (area, search_area) = if self.tab == UnitTab::UnitFile && self.search.is_visible() {
+                let [text_area, search_area] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+                (text_area, Some(search_area))
+            } else {
+                (area, None)
+            };
+            // The graphs are left out when they would not leave 10 rows to the status.
+            let (area, graphs_area) = match &self.usage {
+                Some(usage)
+                    if self.tab == UnitTab::Status
+                        && usage.cpu_summary().is_some()
+                        && area.height >= self.graphs_height + 10 =>
+                {
+                    let [text_area, graphs_area] =
+                        Layout::vertical([Constraint::Min(0), Constraint::Length(self.graphs_height)]).areas(area);
+                    (text_area, Some(graphs_area))
+                }
+                _ => (area, None),
+            };
+
+            let paragraph = Paragraph::new(lines)
+                .style(Style::default().fg(theme.text))
                 .block(
                     Block::default()
                         .borders(Borders::ALL)
-                        .title(format!(" {} properties ", service.name()))
+                        .border_style(Style::default().fg(theme.border))
+                        .title(format!(
+                            " {} {} {}",
+                            self.breadcrumb(service.name()),
+                            locale.tr(self.tab.title()).to_lowercase(),
+                            if self.tab == UnitTab::UnitFile { self.search.match_counter() } else { String::new() }
+                        ))
                         .title_alignment(Alignment::Center),
                 )
-                .scroll((self.scroll, 0));
+                .scroll((self.scroll(), 0));
 
             frame.render_widget(paragraph, area);
+            if let Some(search_area) = search_area {
+                self.search.render(frame, search_area, theme);
+            }
+            if let (Some(graphs_area), Some(usage)) = (graphs_area, &self.usage) {
+                render_usage(frame, graphs_area, usage, theme, locale);
+            }
+            self.environment.render(frame, area, theme);
+            self.description.render(frame, area, theme);
+            if let Some(copy_menu) = &self.copy_menu {
+                copy_menu.render(frame, area, theme);
+            }
+            if let Some(limits) = &self.limits {
+                limits.render(frame, area, theme);
+            }
+            if let Some(documentation) = &self.documentation {
+                documentation.render(frame, area, theme);
+            }
+        }
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        let lines: Vec<String> = match self.tab {
+            UnitTab::Properties => self
+                .properties
+                .iter()
+                .flat_map(|(title, pairs)| {
+                    std::iter::once(locale.format("Section {}", &[title])).chain(pairs.iter().map(|(label, value)| {
+                        match self.previous_value(title, label) {
+                            Some(previous) => format!("{}: {} ({})", label, value, locale.format("changed, was {}", &[previous])),
+                            None => format!("{}: {}", label, value),
+                        }
+                    }))
+                })
+                .collect(),
+            UnitTab::UnitFile => self
+                .unit_files
+                .iter()
+                .flat_map(|file| {
+                    let header = (!file.path.is_empty()).then(|| format!("{}: {}", file.kind(), file.path));
+                    header.into_iter().chain(file.lines.iter().map(|line| match &line.note {
+                        Some(note) => format!("{} ({})", line.text, note.describe()),
+                        None => line.text.clone(),
+                    }))
+                })
+                .collect(),
+            UnitTab::History => match &self.history {
+                Some(Ok(history)) => timeline_text(history, now_usec(), self.config.locale),
+                Some(Err(e)) => vec![format!("{}: {}", locale.tr("Error"), e)],
+                None => Vec::new(),
+            },
+            UnitTab::Security => match &self.security {
+                Some(Ok(audit)) => security_text(audit, locale),
+                Some(Err(e)) => vec![format!("{}: {}", locale.tr("Error"), e)],
+                None => Vec::new(),
+            },
+            _ => match &self.service {
+                Some(service) => self
+                    .status(&service.lock().unwrap())
+                    .into_iter()
+                    .map(|(label, value)| format!("{}: {}", locale.tr(label), value))
+                    .chain(self.conditions.iter().flat_map(|conditions| {
+                        conditions
+                            .conditions()
+                            .iter()
+                            .map(|condition| format!("{}: {}", condition.describe(), condition.state_label()))
+                    }))
+                    .chain(self.diagnosis.iter().flat_map(|diagnosis| {
+                        let oom = diagnosis.oom_lines().iter().map(|line| locale.format("OOM killer: {}", &[line]));
+                        oom.chain(diagnosis.suggestions().iter().map(|suggestion| locale.format("Suggestion: {}", &[suggestion])))
+                    }))
+                    .chain(self.triggers.by_relation().into_iter().enumerate().map(|(index, (label, unit))| {
+                        let selected = if index == self.trigger_link { locale.tr(" (selected)") } else { "" };
+                        format!("{}: {}{}", locale.tr(label), unit, selected)
+                    }))
+                    .chain(self.recent_log.iter().map(|line| locale.format("Log: {}", &[line])))
+                    .collect(),
+                None => Vec::new(),
+            },
+        };
+        let mut lines: Vec<String> = lines.into_iter().skip(self.scroll() as usize).collect();
+        if self.tab == UnitTab::UnitFile && self.search.is_visible() {
+            lines.push(self.search.linear_text());
+        }
+        if self.environment.is_open() {
+            lines.extend(self.environment.linear_text());
+        }
+        if self.description.is_open() {
+            lines.extend(self.description.linear_text());
+        }
+        if let Some(copy_menu) = &self.copy_menu {
+            lines.extend(copy_menu.linear_text());
+        }
+        if let Some(limits) = &self.limits {
+            lines.extend(limits.linear_text());
+        }
+        if let Some(documentation) = &self.documentation {
+            lines.extend(documentation.linear_text());
+        }
+        lines
+    }
+
+    /// Whether keys are text typed in the environment or description prompts, the limits form or
+    /// the search of the unit file.
+    pub fn is_typing(&self) -> bool {
+        self.environment.is_open() || self.description.is_open() || self.limits.is_some() || self.search.is_editing()
+    }
+
+    fn update_search_matches(&mut self) {
+        let texts = self.unit_file_texts();
+        self.search.update_matches(texts.iter().map(String::as_str));
+    }
+
+    /// Scrolls the unit file tab to the line.
+    fn jump_to(&mut self, line: Option<usize>) {
+        if let Some(line) = line {
+            self.scrolls[2] = line as u16;
+        }
+    }
+
+    /// The next section header of the unit files below the top line, or the previous one above.
+    fn section(&self, forward: bool) -> Option<usize> {
+        let top = self.scrolls[2] as usize;
+        let sections = self.unit_file_texts().into_iter().enumerate().filter(|(_, text)| {
+            let text = text.trim();
+            text.starts_with('[') && text.ends_with(']')
+        });
+        if forward {
+            sections.map(|(index, _)| index).find(|index| *index > top)
+        } else {
+            sections.map(|(index, _)| index).rfind(|index| *index < top)
         }
     }
 
     pub fn on_key_event(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Right => {
-                self.reset();
-                self.sender.send(AppEvent::Action(Actions::GoLog)).unwrap();
+        if self.environment.is_open() {
+            match self.environment.on_key_event(key) {
+                Some(EnvironmentRequest::Set(assignment)) => self.set_environment(&assignment),
+                Some(EnvironmentRequest::Restart) => self.restart(),
+                None => {}
             }
-            KeyCode::Left => {
-                self.reset();
-                self.sender.send(AppEvent::Action(Actions::GoLog)).unwrap();
+            return;
+        }
+        if self.description.is_open() {
+            if let Some(description) = self.description.on_key_event(key) {
+                self.set_description(&description);
             }
-            KeyCode::Up => {
-                self.scroll = self.scroll.saturating_sub(1);
+            return;
+        }
+        if let Some(copy_menu) = &mut self.copy_menu {
+            match copy_menu.on_key_event(key) {
+                Confirmation::Pending => {}
+                Confirmation::Confirmed => {
+                    if let Some(copy_menu) = self.copy_menu.take() {
+                        self.copy(copy_menu.choice());
+                    }
+                }
+                Confirmation::Cancelled => self.copy_menu = None,
             }
-            KeyCode::Down => {
-                self.scroll += 1;
+            return;
+        }
+        if let Some(limits) = &mut self.limits {
+            match limits.on_key_event(key) {
+                Confirmation::Pending => {}
+                Confirmation::Confirmed => {
+                    if let Some(limits) = self.limits.take() {
+                        self.set_limits(limits);
+                    }
+                }
+                Confirmation::Cancelled => self.limits = None,
             }
-            KeyCode::PageUp => {
-                self.scroll = self.scroll.saturating_sub(10);
+            return;
+        }
+        if let Some(documentation) = &mut self.documentation {
+            if !documentation.on_key_event(key) {
+                self.documentation = None;
             }
-            KeyCode::PageDown => {
-                self.scroll += 10;
+            return;
+        }
+        if self.search.is_editing() {
+            if self.search.on_key_event(key) {
+                self.update_search_matches();
+                self.jump_to(self.search.current_line());
+            }
+            return;
+        }
+        let config = self.config.clone();
+        let keys = &config.keybindings;
+        if let Some(tab) = UnitTab::from_key(&key) {
+            self.go_to(tab);
+            return;
+        }
+        match key {
+            k if keys.next_tab.matches(&k) => self.go_to(self.tab.next()),
+            k if keys.previous_tab.matches(&k) => self.go_to(self.tab.previous()),
+            k if keys.up.matches(&k) => {
+                let scroll = self.scroll_mut();
+                *scroll = scroll.saturating_sub(1);
+            }
+            k if keys.down.matches(&k) => {
+                *self.scroll_mut() += 1;
+            }
+            k if keys.page_up.matches(&k) => {
+                let scroll = self.scroll_mut();
+                *scroll = scroll.saturating_sub(10);
+            }
+            k if keys.page_down.matches(&k) => {
+                *self.scroll_mut() += 10;
             }
 
-            KeyCode::Char('q') => {
-                self.reset();
-                self.exit();
+            k if keys.search.matches(&k) && self.tab == UnitTab::UnitFile => self.search.start(self.config.locale),
+            k if keys.next_match.matches(&k) && self.tab == UnitTab::UnitFile => {
+                let line = self.search.next_match();
+                self.jump_to(line);
+            }
+            k if keys.previous_match.matches(&k) && self.tab == UnitTab::UnitFile => {
+                let line = self.search.previous_match();
+                self.jump_to(line);
             }
+            k if keys.next_section.matches(&k) && self.tab == UnitTab::UnitFile => self.jump_to(self.section(true)),
+            k if keys.previous_section.matches(&k) && self.tab == UnitTab::UnitFile => self.jump_to(self.section(false)),
+            k if keys.next_section.matches(&k) && self.has_links() => self.select_link(1),
+            k if keys.previous_section.matches(&k) && self.has_links() => self.select_link(-1),
+            k if k.code == KeyCode::Enter && self.has_links() => self.follow_link(),
+            k if keys.run_now.matches(&k) && self.timed => self.run_now(),
+            k if keys.edit_limits.matches(&k) && self.tab == UnitTab::Properties && self.has_cgroup() => {
+                self.open_limits()
+            }
+            k if keys.set_environment.matches(&k) && self.has_environment() => {
+                if let Some(service) = &self.service {
+                    let name = service.lock().unwrap().name().to_string();
+                    self.environment.open(&name, self.config.locale);
+                }
+            }
+            k if keys.describe.matches(&k) => {
+                if let Some(service) = &self.service {
+                    let service = service.lock().unwrap();
+                    self.description.open(service.name(), service.description(), self.config.locale);
+                }
+            }
+            k if keys.documentation.matches(&k) => self.open_documentation(),
+            k if keys.copy_lines.matches(&k) => {
+                if let Some(service) = &self.service {
+                    self.copy_menu = Some(CopyMenu::new(service.lock().unwrap().name().to_string(), self.config.locale));
+                }
+            }
+            k if keys.shell.matches(&k) => self.open_shell(),
+            k if keys.reload.matches(&k) => self.reload(),
+            k if keys.start.matches(&k) && !self.sockets.is_empty() => self.control_sockets(true),
+            k if keys.stop.matches(&k) && !self.sockets.is_empty() => self.control_sockets(false),
+            k if keys.back.matches(&k) => match self.trail.pop() {
+                Some(previous) => self.show(previous),
+                None => {
+                    self.reset();
+                    self.exit();
+                }
+            },
             _ => {}
         }
     }
 
-    pub fn shortcuts(&mut self) -> Vec<Line<'_>> {
-        let help_text = vec![
+    /// Whether the status tab lists units to go to.
+    fn has_links(&self) -> bool {
+        self.tab == UnitTab::Status && !self.triggers.by_relation().is_empty()
+    }
+
+    fn select_link(&mut self, delta: isize) {
+        let len = self.triggers.by_relation().len() as isize;
+        self.trigger_link = (self.trigger_link as isize + delta).rem_euclid(len) as usize;
+    }
+
+    /// Shows the selected related unit, back returning to this one.
+    fn follow_link(&mut self) {
+        let Some(unit) = self.triggers.by_relation().get(self.trigger_link).map(|(_, unit)| unit.to_string()) else {
+            return;
+        };
+        if let Some(service) = &self.service {
+            self.trail.push(service.lock().unwrap().name().to_string());
+        }
+        self.show(unit);
+    }
+
+    fn show(&mut self, unit: String) {
+        self.trigger_link = 0;
+        self.sender.send(AppEvent::Action(Actions::ShowInDetails(unit))).unwrap();
+    }
+
+    pub fn shortcuts(&mut self, theme: &Theme) -> Vec<Line<'_>> {
+        let locale = self.config.locale;
+        let keys = &self.config.keybindings;
+        let mut help_text = vec![
             Line::from(vec![Span::styled(
-                "Actions",
+                locale.tr("Actions"),
                 Style::default()
-                    .fg(Color::LightMagenta)
+                    .fg(theme.title)
                     .add_modifier(Modifier::BOLD),
             )]),
-            Line::from("Switch tabs: ←/→ | Go back: q"),
         ];
+        let mut entries = vec![
+            ("Scroll", format!("{}/{}", keys.up, keys.down)),
+            ("Switch tabs", format!("{}/{} {} 1-8", keys.previous_tab, keys.next_tab, locale.tr("or"))),
+            ("Go back", keys.back.to_string()),
+        ];
+        if self.timed {
+            entries.push(("Run now", keys.run_now.to_string()));
+        }
+        if self.has_links() {
+            entries.extend([
+                ("Follow link", "Enter".to_string()),
+                ("Next/previous link", format!("{}/{}", keys.next_section, keys.previous_section)),
+            ]);
+        }
+        if self.has_environment() {
+            entries.push(("Set variable", keys.set_environment.to_string()));
+        }
+        if self.tab == UnitTab::Properties && self.has_cgroup() {
+            entries.push(("Resource limits", keys.edit_limits.to_string()));
+        }
+        if self.tab == UnitTab::UnitFile {
+            entries.extend([
+                ("Search", keys.search.to_string()),
+                ("Next/previous match", format!("{}/{}", keys.next_match, keys.previous_match)),
+                ("Next/previous section", format!("{}/{}", keys.next_section, keys.previous_section)),
+            ]);
+        }
+        entries.extend([
+            ("Description", keys.describe.to_string()),
+            ("Documentation", keys.documentation.to_string()),
+            ("Copy", keys.copy_lines.to_string()),
+        ]);
+        if self.service.as_ref().is_some_and(|service| Self::service_property(&service.lock().unwrap()).is_some()) {
+            entries.push(("Shell", keys.shell.to_string()));
+        }
+        if !self.sockets.is_empty() {
+            entries.extend([
+                ("Start socket", keys.start.to_string()),
+                ("Stop socket", keys.stop.to_string()),
+            ]);
+        }
+        let shortcuts = locale.shortcuts(&entries);
+        let reload_style = if self.capabilities.is_none_or(|capabilities| capabilities.can_reload) {
+            Style::default()
+        } else {
+            Style::default().fg(theme.muted).add_modifier(Modifier::CROSSED_OUT)
+        };
+        help_text.push(Line::from(vec![
+            Span::raw(shortcuts),
+            Span::raw(" | "),
+            Span::styled(format!("{}: {}", locale.tr("Reload"), keys.reload), reload_style),
+        ]));
 
         help_text
     }
 
     pub fn reset(&mut self) {
         self.service = None;
-        self.scroll = 0;
+        self.properties.clear();
+        self.changes.clear();
+        self.activation.clear();
+        self.links = None;
+        self.triggers = UnitTriggers::default();
+        self.trigger_link = 0;
+        self.trail.clear();
+        self.timed = false;
+        self.sockets.clear();
+        self.capabilities = None;
+        self.conditions = None;
+        self.diagnosis = None;
+        self.availability = None;
+        self.usage = None;
+        self.processes.clear();
+        self.state_change = 0;
+        self.freezer.clear();
+        self.recent_log.clear();
+        self.history = None;
+        self.security = None;
+        self.environment.close();
+        self.description.close();
+        self.copy_menu = None;
+        self.limits = None;
+        self.documentation = None;
+        self.search.clear();
+        self.tab = UnitTab::Status;
+        self.scrolls = [0; 5];
+    }
+
+    /// Switches between the status, properties, unit file, history and security tabs, or leaves for another one.
+    fn go_to(&mut self, tab: UnitTab) {
+        if tab.is_details() {
+            self.set_tab(tab);
+            return;
+        }
+        self.reset();
+        self.sender.send(AppEvent::Action(tab.action())).unwrap();
     }
 
     fn exit(&self) {
         self.sender.send(AppEvent::Action(Actions::GoList)).unwrap();
     }
 
+    /// The sections of the properties tab, with the environment and the resource limits.
+    fn read_properties(&self, service: &mut Service) -> (Vec<PropertySection>, ResourceLimits) {
+        // Unit types without properties, e.g. targets, have an empty properties tab.
+        let mut properties = match self.usecase.borrow().update_properties(service) {
+            Ok(()) => service.properties().map(|properties| properties.sections()).unwrap_or_default(),
+            Err(_) => Vec::new(),
+        };
+        // Environment and EnvironmentFiles, for the unit types running commands.
+        if let Ok(environment) = self.usecase.borrow().get_environment(service) {
+            let environment = environment.summary();
+            if !environment.is_empty() {
+                properties.push(("Environment", environment));
+            }
+        }
+        // The cgroup limits along the rlimits of services, in a section of their own otherwise.
+        let resource_limits = self.usecase.borrow().get_resource_limits(service).unwrap_or_default();
+        let limits = resource_limits.summary();
+        match properties.iter_mut().find(|(title, _)| *title == "Limits") {
+            Some((_, pairs)) => pairs.extend(limits),
+            None if !limits.is_empty() => properties.push(("Limits", limits)),
+            None => {}
+        }
+        (properties, resource_limits)
+    }
+
+    /// Replaces the properties, remembering the values that changed since the last read.
+    fn set_properties(&mut self, properties: Vec<PropertySection>) {
+        let now = Instant::now();
+        self.changes.retain(|_, (_, at)| now.duration_since(*at) < CHANGE_HIGHLIGHT);
+        for (title, pairs) in &properties {
+            let Some((_, previous_pairs)) = self.properties.iter().find(|(previous, _)| previous == title) else {
+                continue;
+            };
+            for (label, value) in pairs {
+                if let Some((_, previous)) = previous_pairs.iter().find(|(previous, _)| previous == label)
+                    && previous != value
+                {
+                    self.changes.insert((title, label), (previous.clone(), now));
+                }
+            }
+        }
+        self.properties = properties;
+    }
+
+    /// The value the property had before it changed, while the change is highlighted.
+    fn previous_value(&self, title: &'static str, label: &'static str) -> Option<&str> {
+        self.changes
+            .get(&(title, label))
+            .filter(|(_, at)| at.elapsed() < CHANGE_HIGHLIGHT)
+            .map(|(previous, _)| previous.as_str())
+    }
+
+    /// The processes, the time of the last state change and the last log lines of the status tab.
+    fn fetch_status(&mut self, service: &Service) {
+        let usecase = self.usecase.borrow();
+        self.processes = usecase.get_unit_processes(service).unwrap_or_default();
+        self.state_change = usecase.get_state_change_timestamp(service).unwrap_or_default();
+        self.freezer = usecase
+            .get_freezer_state(service)
+            .unwrap_or_else(|_| service.state().freezer().to_string());
+        self.recent_log = usecase
+            .get_log_lines(service, STATUS_LOG_LINES)
+            .map(|log| log.lines().map(str::to_string).collect())
+            .unwrap_or_default();
+    }
+
+    /// Reads the status again while the status tab is shown, for the periodic refresh, with the
+    /// state of the unit from the last refresh of the list.
+    pub fn refresh_status(&mut self, services: &[Service]) {
+        if self.tab != UnitTab::Status {
+            return;
+        }
+        let Some(service_arc) = self.service.clone() else {
+            return;
+        };
+        let mut service = service_arc.lock().unwrap();
+        if let Some(listed) = services.iter().find(|listed| listed.name() == service.name()) {
+            let mut listed = listed.clone();
+            // The properties, e.g. the main PID, are read again below.
+            let (properties, _) = self.read_properties(&mut listed);
+            self.set_properties(properties);
+            *service = listed;
+        }
+        self.fetch_status(&service);
+    }
+
+    /// Reads the properties again while the properties tab is shown, for the periodic refresh.
+    pub fn refresh_properties(&mut self) {
+        if self.tab != UnitTab::Properties {
+            return;
+        }
+        let Some(service_arc) = self.service.clone() else {
+            return;
+        };
+        let (properties, _) = self.read_properties(&mut service_arc.lock().unwrap());
+        self.set_properties(properties);
+    }
+
     pub fn fetch_unit_file(&mut self) {
-        if let Some(service_arc) = &self.service {
-            let service = service_arc.lock().unwrap();
-            match self.usecase.borrow().systemctl_cat(&service) {
-                Ok(content) => {
-                    self.unit_file = content;
+        if let Some(service_arc) = self.service.clone() {
+            let mut service = service_arc.lock().unwrap();
+            let (properties, resource_limits) = self.read_properties(&mut service);
+            self.set_properties(properties);
+            self.capabilities = self.usecase.borrow().capabilities(&service).ok();
+            self.conditions = self.usecase.borrow().get_unit_conditions(&service).ok();
+            self.diagnosis = match service.properties() {
+                Some(UnitProperty::Service(property)) if !matches!(property.result(), "" | "success") => {
+                    let oom_lines = self
+                        .usecase
+                        .borrow()
+                        .get_oom_entries(&service)
+                        .map(|entries| entries.iter().map(JournalEntry::line).collect())
+                        .unwrap_or_default();
+                    ExitDiagnosis::diagnose(service.name(), property, &resource_limits, oom_lines)
+                }
+                _ => None,
+            };
+            self.activation.clear();
+            self.timed = matches!(service.properties(), Some(UnitProperty::Timer(_)));
+            // The schedules of the timers activating the unit, e.g. for a backup service.
+            if let Ok(timers) = self.usecase.borrow().get_unit_timers(&service) {
+                for (name, timer) in timers {
+                    self.timed = true;
+                    self.activation.push(("Timer", name));
+                    self.activation.extend(timer.schedule());
+                }
+            }
+            self.sockets.clear();
+            if matches!(service.properties(), Some(UnitProperty::Socket(_))) {
+                self.sockets.push(service.name().to_string());
+            }
+            // The sockets starting the unit on connections, e.g. for cups or sshd per connection.
+            if let Ok(sockets) = self.usecase.borrow().get_unit_sockets(&service) {
+                for (name, socket) in sockets {
+                    self.activation.push(("Socket", name.clone()));
+                    self.activation.extend(socket.activity());
+                    self.sockets.push(name);
+                }
+            }
+            let unit_files = self.usecase.borrow().unit_files(&service);
+            match unit_files {
+                Ok(files) => {
+                    self.unit_files = files;
+                    self.update_search_matches();
                 },
                 Err(e) => {
-                    self.sender.send(AppEvent::Error(e.to_string())).unwrap();
+                    self.sender.send(AppEvent::Error(e.into())).unwrap();
                 }
             }
+            // Not every manager has aliases, e.g. OpenRC, the status tab goes without them.
+            self.links = self.usecase.borrow().unit_links(&service).ok();
+            self.triggers = self.usecase.borrow().get_unit_triggers(&service).unwrap_or_default();
+            self.trigger_link = self.trigger_link.min(self.triggers.by_relation().len().saturating_sub(1));
+            self.fetch_status(&service);
+        }
+        self.fetch_history();
+        self.fetch_security();
+    }
+
+    /// Starts the timed service now, reporting the job in the status bar.
+    fn run_now(&mut self) {
+        let Some(service_arc) = &self.service else {
+            return;
+        };
+        let service = service_arc.lock().unwrap().clone();
+        match self.usecase.borrow().run_now(&service) {
+            Ok((unit, job)) => {
+                let outcome = job.map_or(ActionOutcome::Completed, ActionOutcome::Queued);
+                self.sender
+                    .send(AppEvent::Action(Actions::ActionPerformed(format!("start of {}", unit), outcome)))
+                    .unwrap();
+            }
+            Err(e) => self.sender.send(AppEvent::Error(e.into())).unwrap(),
+        }
+        self.sender.send(AppEvent::Action(Actions::ReloadList)).unwrap();
+    }
+
+    /// Whether the unit runs commands, whose environment can be set.
+    fn has_environment(&self) -> bool {
+        self.service
+            .as_ref()
+            .is_some_and(|service| exec_section(service.lock().unwrap().name()).is_some())
+    }
+
+    /// Whether the unit has a control group, whose resource limits can be set.
+    fn has_cgroup(&self) -> bool {
+        self.service
+            .as_ref()
+            .is_some_and(|service| cgroup_section(service.lock().unwrap().name()).is_some())
+    }
+
+    /// Lists the Documentation= links of the unit, unless it has none.
+    fn open_documentation(&mut self) {
+        let Some(service_arc) = &self.service else {
+            return;
+        };
+        let service = service_arc.lock().unwrap().clone();
+        match self.usecase.borrow().get_unit_documentation(&service) {
+            Ok(links) if links.is_empty() => self
+                .sender
+                .send(AppEvent::Notice(format!("{} has no Documentation=", service.name())))
+                .unwrap(),
+            Ok(links) => {
+                self.documentation =
+                    Some(DocumentationViewer::new(service.name(), links, self.sender.clone(), self.config.clone()))
+            }
+            Err(e) => self.sender.send(AppEvent::Error(e.into())).unwrap(),
+        }
+    }
+
+    /// Opens the limits form on the current limits of the unit.
+    fn open_limits(&mut self) {
+        let Some(service_arc) = &self.service else {
+            return;
+        };
+        let service = service_arc.lock().unwrap().clone();
+        match self.usecase.borrow().get_resource_limits(&service) {
+            Ok(current) => self.limits = Some(ResourceLimitsForm::new(service.name(), current, self.config.locale)),
+            Err(e) => self.sender.send(AppEvent::Error(e.into())).unwrap(),
         }
     }
 
-    pub fn update(&mut self, service: Service) {
+    /// Sets the limits of the form, then shows the new ones.
+    fn set_limits(&mut self, form: ResourceLimitsForm) {
+        let Some(service_arc) = &self.service else {
+            return;
+        };
+        let service = service_arc.lock().unwrap().clone();
+        let Ok(limits) = form.limits() else {
+            return;
+        };
+        match self.usecase.borrow().set_resource_limits(&service, &limits, form.runtime()) {
+            Ok(()) => {
+                let locale = self.config.locale;
+                let until = locale.tr(if form.runtime() { "until reboot" } else { "persistently" });
+                let notice = locale.format("Set {} of {} {}", &[&limits.describe(), service.name(), until]);
+                self.sender.send(AppEvent::Notice(notice)).unwrap();
+            }
+            Err(e) => self.sender.send(AppEvent::Error(e.into())).unwrap(),
+        }
+        self.sender.send(AppEvent::Action(Actions::RefreshDetails)).unwrap();
+    }
+
+    /// Writes the variable to a drop-in of the unit, then asks whether to restart it.
+    fn set_environment(&mut self, assignment: &str) {
+        let Some(service_arc) = &self.service else {
+            return;
+        };
+        let service = service_arc.lock().unwrap().clone();
+        match self.usecase.borrow().set_environment(&service, assignment) {
+            Ok(path) => {
+                self.environment.written(path);
+                self.sender.send(AppEvent::Action(Actions::RefreshDetails)).unwrap();
+            }
+            Err(e) => self.environment.failed(self.config.locale.tr(AppError::from(e).explanation()).to_string()),
+        }
+    }
+
+    /// Writes the description to a drop-in of the unit, which the list shows once reloaded.
+    fn set_description(&mut self, description: &str) {
+        let Some(service_arc) = &self.service else {
+            return;
+        };
+        let service = service_arc.lock().unwrap().clone();
+        match self.usecase.borrow().set_description(&service, description) {
+            Ok(path) => {
+                self.description.close();
+                self.sender.send(AppEvent::Notice(self.config.locale.format("Wrote {}", &[&path]))).unwrap();
+                self.sender.send(AppEvent::Action(Actions::ReloadList)).unwrap();
+                self.sender.send(AppEvent::Action(Actions::RefreshDetails)).unwrap();
+            }
+            Err(e) => self.description.failed(self.config.locale.tr(AppError::from(e).explanation()).to_string()),
+        }
+    }
+
+    /// Exec settings of a service, `None` for other unit types or before they were read.
+    fn service_property(service: &Service) -> Option<&ServiceProperty> {
+        match service.properties() {
+            Some(UnitProperty::Service(property)) => Some(property),
+            _ => None,
+        }
+    }
+
+    /// Hands the terminal to a shell in the working directory of the service, to look around
+    /// the environment it runs in.
+    fn open_shell(&self) {
+        let Some(service_arc) = &self.service else {
+            return;
+        };
+        let service = service_arc.lock().unwrap();
+        if let Some(property) = Self::service_property(&service) {
+            let action = Actions::OpenShell(service.name().to_string(), property.shell_directory());
+            self.sender.send(AppEvent::Action(action)).unwrap();
+        }
+    }
+
+    /// Copies the unit name, its ExecStart lines, its binary, its working directory, its unit
+    /// file with the drop-ins like `systemctl cat`, or the lines of the status tab.
+    fn copy(&mut self, choice: CopyChoice) {
+        let Some(service_arc) = &self.service else {
+            return;
+        };
+        let service = service_arc.lock().unwrap().clone();
+        let text = match choice {
+            CopyChoice::UnitName => service.name().to_string(),
+            CopyChoice::ExecStart => self
+                .properties
+                .iter()
+                .filter(|(title, _)| *title == "Exec")
+                .flat_map(|(_, pairs)| pairs.iter().filter(|(label, _)| *label == "ExecStart"))
+                .map(|(_, command)| command.as_str())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            CopyChoice::BinaryPath => Self::service_property(&service)
+                .and_then(|property| property.binary_path().map(str::to_string))
+                .unwrap_or_default(),
+            CopyChoice::WorkingDirectory => Self::service_property(&service)
+                .and_then(|property| property.working_directory().map(str::to_string))
+                .unwrap_or_default(),
+            CopyChoice::UnitFile => self
+                .unit_files
+                .iter()
+                .map(|file| {
+                    let lines = file.lines.iter().map(|line| line.text.as_str()).collect::<Vec<_>>().join("\n");
+                    if file.path.is_empty() { lines } else { format!("# {}\n{}", file.path, lines) }
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+            CopyChoice::StatusSummary => self
+                .status(&service)
+                .into_iter()
+                .map(|(label, value)| format!("{}: {}", label, value))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        };
+        let locale = self.config.locale;
+        let label = locale.tr(choice.label());
+        let event = if text.is_empty() {
+            AppEvent::Notice(locale.format("{} has no {} to copy", &[service.name(), label]))
+        } else {
+            match copy_to_clipboard(&text) {
+                Ok(()) => AppEvent::Notice(locale.format(
+                    "Copied the {} of {} to the clipboard",
+                    &[&label.to_lowercase(), service.name()],
+                )),
+                Err(e) => AppEvent::Error(locale.format("Could not copy to the clipboard: {}", &[&e.to_string()]).into()),
+            }
+        };
+        self.sender.send(event).unwrap();
+    }
+
+    /// Restarts the unit so that it sees the new environment.
+    fn restart(&mut self) {
+        self.perform("restart", ServicesManager::restart_service);
+    }
+
+    /// Reloads the configuration of the unit, which keeps running.
+    fn reload(&mut self) {
+        let refusal = self.capabilities.and_then(|capabilities| capabilities.refusal(UnitAction::Reload));
+        match (refusal, &self.service) {
+            (Some(refusal), Some(service_arc)) => {
+                let notice = format!("{} {}", service_arc.lock().unwrap().name(), refusal);
+                self.sender.send(AppEvent::Notice(notice)).unwrap();
+            }
+            _ => self.perform("reload", ServicesManager::reload_service),
+        }
+    }
+
+    /// Runs a job of the unit, reporting it in the status bar.
+    fn perform<F>(&mut self, label: &str, action: F)
+    where
+        F: FnOnce(&ServicesManager, &Service) -> Result<Option<String>, ServiceError>,
+    {
+        let Some(service_arc) = &self.service else {
+            return;
+        };
+        let service = service_arc.lock().unwrap().clone();
+        match action(&self.usecase.borrow(), &service) {
+            Ok(job) => {
+                let outcome = job.map_or(ActionOutcome::Completed, ActionOutcome::Queued);
+                self.sender
+                    .send(AppEvent::Action(Actions::ActionPerformed(format!("{} of {}", label, service.name()), outcome)))
+                    .unwrap();
+            }
+            Err(e) => self.sender.send(AppEvent::Error(e.into())).unwrap(),
+        }
+        self.sender.send(AppEvent::Action(Actions::ReloadList)).unwrap();
+    }
+
+    /// Starts or stops the sockets of the unit, then shows their new state.
+    fn control_sockets(&mut self, start: bool) {
+        for socket in &self.sockets {
+            let usecase = self.usecase.borrow();
+            let result = if start { usecase.start_socket(socket) } else { usecase.stop_socket(socket) };
+            match result {
+                Ok(job) => {
+                    let action = if start { "start" } else { "stop" };
+                    let outcome = job.map_or(ActionOutcome::Completed, ActionOutcome::Queued);
+                    self.sender
+                        .send(AppEvent::Action(Actions::ActionPerformed(format!("{} of {}", action, socket), outcome)))
+                        .unwrap();
+                }
+                Err(e) => self.sender.send(AppEvent::Error(e.into())).unwrap(),
+            }
+        }
+        self.sender.send(AppEvent::Action(Actions::ReloadList)).unwrap();
+        self.sender.send(AppEvent::Action(Actions::RefreshDetails)).unwrap();
+    }
+
+    pub fn update(&mut self, service: Service, availability: Option<String>) {
+        // The properties of another unit are not compared with the ones of this one.
+        if self.service.as_ref().is_some_and(|shown| shown.lock().unwrap().name() != service.name()) {
+            self.properties.clear();
+            self.changes.clear();
+        }
         self.service = Some(Arc::new(Mutex::new(service)));
+        self.availability = availability;
+    }
+
+    pub fn set_usage(&mut self, usage: Option<UsageHistory>) {
+        self.usage = usage;
+    }
+
+    pub fn graphs_height(&self) -> u16 {
+        self.graphs_height
+    }
+
+    /// Resizes the usage graphs of the status tab, within 5 to 15 rows.
+    pub fn set_graphs_height(&mut self, rows: u16) {
+        self.graphs_height = rows.clamp(GRAPHS_MIN_HEIGHT, GRAPHS_MAX_HEIGHT);
     }
 }
+
+/// CPU and memory graphs side by side, the latest samples on the right.
+fn render_usage(frame: &mut Frame, area: Rect, usage: &UsageHistory, theme: &Theme, locale: Locale) {
+    let [cpu_area, memory_area] = Layout::horizontal([Constraint::Percentage(50); 2]).areas(area);
+    let graphs = [
+        (cpu_area, "CPU", usage.cpu_percents(), usage.cpu_summary()),
+        (memory_area, "Memory", usage.memory(), usage.memory_summary()),
+    ];
+    for (area, label, data, summary) in graphs {
+        let width = area.width.saturating_sub(2) as usize;
+        let data = &data[data.len().saturating_sub(width)..];
+        let sparkline = Sparkline::default()
+            .data(data)
+            .style(Style::default().fg(theme.accent))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.border))
+                    .title(format!(" {} {} ", locale.tr(label), summary.unwrap_or_default())),
+            );
+        frame.render_widget(sparkline, area);
+    }
+}
+
+/// A line of a unit file with its section headers, setting names and comments colored.
+fn unit_file_syntax(text: &str, theme: &Theme) -> Line<'static> {
+    let trimmed = text.trim_start();
+    if trimmed.starts_with('#') || trimmed.starts_with(';') {
+        return Line::styled(text.to_string(), Style::default().fg(theme.muted).add_modifier(Modifier::ITALIC));
+    }
+    if trimmed.starts_with('[') && trimmed.trim_end().ends_with(']') {
+        return Line::styled(text.to_string(), Style::default().fg(theme.header).add_modifier(Modifier::BOLD));
+    }
+    match text.split_once('=') {
+        Some((key, value)) => Line::from(vec![
+            Span::styled(key.to_string(), Style::default().fg(theme.accent)),
+            Span::styled("=", Style::default().fg(theme.muted)),
+            Span::styled(value.to_string(), Style::default().fg(theme.text)),
+        ]),
+        None => Line::raw(text.to_string()),
+    }
+}
+
+/// The overall exposure, then the failed checks by the exposure they add and the passed ones.
+fn security_lines(audit: &SecurityAudit, theme: &Theme, locale: Locale) -> Vec<Line<'static>> {
+    let level_color = |level: &str| match level {
+        "SAFE" | "OK" => theme.active,
+        "MEDIUM" => theme.warning,
+        _ => theme.error,
+    };
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(format!("{:<14}", locale.tr("Exposure")), Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+            Span::styled(
+                format!("{:.1} {}", audit.exposure, audit.level),
+                Style::default().fg(level_color(&audit.level)).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(""),
+    ];
+    let width = audit.findings.iter().map(|finding| finding.setting.chars().count()).max().unwrap_or(0);
+    for finding in audit.sorted_findings() {
+        let (mark, color) = if finding.passed { ("✓", theme.active) } else { ("✗", theme.error) };
+        let exposure = finding.exposure.map(|exposure| format!("{:.1}", exposure)).unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} {:>4} ", mark, exposure), Style::default().fg(color)),
+            Span::styled(format!("{:<width$}  ", finding.setting), Style::default().fg(theme.accent)),
+            Span::raw(finding.description.clone()),
+        ]));
+    }
+    lines
+}
+
+fn security_text(audit: &SecurityAudit, locale: Locale) -> Vec<String> {
+    std::iter::once(format!("{}: {:.1} {}", locale.tr("Exposure"), audit.exposure, audit.level))
+        .chain(audit.sorted_findings().into_iter().map(|finding| {
+            let state = locale.tr(if finding.passed { "passed" } else { "failed" });
+            match finding.exposure {
+                Some(exposure) => format!(
+                    "{} {}, {}: {}",
+                    finding.setting,
+                    state,
+                    locale.format("adds {}", &[&format!("{:.1}", exposure)]),
+                    finding.description
+                ),
+                None => format!("{} {}: {}", finding.setting, state, finding.description),
+            }
+        }))
+        .collect()
+}
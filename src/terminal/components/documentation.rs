@@ -0,0 +1,236 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+
+use crate::config::Config;
+use crate::domain::unit_documentation::DocumentationLink;
+use crate::infrastructure::clipboard::copy_to_clipboard;
+use crate::infrastructure::documentation::{open_url, read_man_page};
+use crate::terminal::app::AppEvent;
+use crate::terminal::theme::Theme;
+
+/// A page read inside the viewer.
+struct Page {
+    /// e.g. "man:systemd.service(5)".
+    title: String,
+    lines: Vec<String>,
+    scroll: u16,
+}
+
+/// The Documentation= links of the unit of the details view: man pages and files are read in a
+/// scrollable pane, URLs opened in the browser or copied.
+pub struct DocumentationViewer {
+    unit: String,
+    links: Vec<DocumentationLink>,
+    selected: usize,
+    page: Option<Page>,
+    sender: Sender<AppEvent>,
+    config: Rc<Config>,
+}
+
+impl DocumentationViewer {
+    pub fn new(unit: &str, links: Vec<DocumentationLink>, sender: Sender<AppEvent>, config: Rc<Config>) -> Self {
+        Self {
+            unit: unit.to_string(),
+            links,
+            selected: 0,
+            page: None,
+            sender,
+            config,
+        }
+    }
+
+    /// Man pages are wrapped to the popup, a little narrower than the terminal.
+    fn page_width() -> u16 {
+        crossterm::terminal::size().map_or(80, |(width, _)| width.saturating_sub(8).max(40))
+    }
+
+    fn open_selected(&mut self) {
+        let Some(link) = self.links.get(self.selected).cloned() else {
+            return;
+        };
+        let text = match &link {
+            DocumentationLink::ManPage { page, section } => read_man_page(page, section.as_deref(), Self::page_width()),
+            DocumentationLink::File(path) => std::fs::read_to_string(path),
+            DocumentationLink::Url(url) => {
+                let locale = self.config.locale;
+                let event = match open_url(url) {
+                    Ok(()) => AppEvent::Notice(locale.format("Opened {} in the browser", &[url])),
+                    Err(e) => AppEvent::Error(locale.format("Could not open {}: {}", &[url, &e.to_string()]).into()),
+                };
+                self.sender.send(event).unwrap();
+                return;
+            }
+            DocumentationLink::Other(_) => {
+                self.copy_selected();
+                return;
+            }
+        };
+        match text {
+            Ok(text) => {
+                self.page = Some(Page {
+                    title: link.reference(),
+                    lines: text.lines().map(str::to_string).collect(),
+                    scroll: 0,
+                })
+            }
+            Err(e) => self
+                .sender
+                .send(AppEvent::Error(
+                    self.config.locale.format("Could not read {}: {}", &[&link.reference(), &e.to_string()]).into(),
+                ))
+                .unwrap(),
+        }
+    }
+
+    fn copy_selected(&self) {
+        let Some(link) = self.links.get(self.selected) else {
+            return;
+        };
+        let locale = self.config.locale;
+        let reference = link.reference();
+        let event = match copy_to_clipboard(&reference) {
+            Ok(()) => AppEvent::Notice(locale.format("Copied {} to the clipboard", &[&reference])),
+            Err(e) => AppEvent::Error(locale.format("Could not copy to the clipboard: {}", &[&e.to_string()]).into()),
+        };
+        self.sender.send(event).unwrap();
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        if let Some(page) = &mut self.page {
+            let last = page.lines.len().saturating_sub(1) as isize;
+            page.scroll = (page.scroll as isize + delta).clamp(0, last) as u16;
+        } else if !self.links.is_empty() {
+            self.selected = (self.selected as isize + delta).clamp(0, self.links.len() as isize - 1) as usize;
+        }
+    }
+
+    /// `false` once closed.
+    pub fn on_key_event(&mut self, key: KeyEvent) -> bool {
+        let config = self.config.clone();
+        let keys = &config.keybindings;
+        match key {
+            // Back to the links from a page, closed from the links.
+            k if k.code == KeyCode::Esc || keys.back.matches(&k) => return self.page.take().is_some(),
+            k if k.code == KeyCode::Enter && self.page.is_none() => self.open_selected(),
+            k if keys.copy_lines.matches(&k) && self.page.is_none() => self.copy_selected(),
+            k if keys.up.matches(&k) => self.scroll(-1),
+            k if keys.down.matches(&k) => self.scroll(1),
+            k if keys.page_up.matches(&k) => self.scroll(-20),
+            k if keys.page_down.matches(&k) => self.scroll(20),
+            _ => {}
+        }
+        true
+    }
+
+    fn help(&self) -> String {
+        let keys = &self.config.keybindings;
+        let shortcuts = match self.page {
+            Some(_) => vec![("Scroll", format!("{}/{}", keys.up, keys.down)), ("Back", "Esc".to_string())],
+            None => vec![
+                ("Choose", format!("{}/{}", keys.up, keys.down)),
+                ("Read or open", "Enter".to_string()),
+                ("Copy", keys.copy_lines.to_string()),
+                ("Close", "Esc".to_string()),
+            ],
+        };
+        self.config.locale.shortcuts(&shortcuts)
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        let mut lines = Vec::new();
+        match &self.page {
+            Some(page) => {
+                lines.push(locale.format(
+                    "{}, line {} of {}",
+                    &[&page.title, &(page.scroll + 1).to_string(), &page.lines.len().to_string()],
+                ));
+                lines.extend(page.lines.iter().skip(page.scroll as usize).cloned());
+            }
+            None => {
+                lines.push(locale.format("Documentation of {}", &[&self.unit]));
+                lines.extend(self.links.iter().enumerate().map(|(index, link)| {
+                    format!(
+                        "{}{}: {}",
+                        link.reference(),
+                        if index == self.selected { locale.tr(" (selected)") } else { "" },
+                        locale.tr(link.action())
+                    )
+                }));
+            }
+        }
+        lines.push(self.help());
+        lines
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_width = area.width.saturating_sub(4);
+        let popup_height = area.height.saturating_sub(2);
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let locale = self.config.locale;
+        let (title, text, scroll) = match &self.page {
+            Some(page) => (
+                format!(" {} ", page.title),
+                page.lines.iter().map(|line| Line::from(line.clone())).collect(),
+                page.scroll,
+            ),
+            None => {
+                let lines: Vec<Line> = self
+                    .links
+                    .iter()
+                    .enumerate()
+                    .map(|(index, link)| {
+                        let reference = format!("{:<60}", link.reference());
+                        if index == self.selected {
+                            Line::from(vec![
+                                Span::styled(
+                                    format!(">> {}", reference),
+                                    Style::default()
+                                        .fg(theme.selection_fg)
+                                        .bg(theme.selection_bg)
+                                        .add_modifier(Modifier::BOLD),
+                                ),
+                                Span::styled(format!(" {}", locale.tr(link.action())), Style::default().fg(theme.muted)),
+                            ])
+                        } else {
+                            Line::from(vec![
+                                Span::raw(format!("   {}", reference)),
+                                Span::styled(format!(" {}", locale.tr(link.action())), Style::default().fg(theme.muted)),
+                            ])
+                        }
+                    })
+                    .collect();
+                (format!(" {} ", locale.format("Documentation of {}", &[&self.unit])), lines, 0)
+            }
+        };
+
+        let viewer = Paragraph::new(text)
+            .style(Style::default().fg(theme.text))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.highlight_border))
+                    .title(title)
+                    .title_alignment(Alignment::Center)
+                    .title_bottom(Line::from(format!(" {} ", self.help())).right_aligned()),
+            )
+            .scroll((scroll, 0));
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(viewer, popup_area);
+    }
+}
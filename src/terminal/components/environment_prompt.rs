@@ -0,0 +1,145 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Position, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+const INPUT_HELP: &str = "Set: Enter | Cancel: Esc";
+const RESTART_HELP: &str = "Restart: Enter/y | Later: Esc/n";
+
+/// What the details view is asked to do.
+pub enum EnvironmentRequest {
+    /// Write the "KEY=VALUE" assignment to a drop-in.
+    Set(String),
+    Restart,
+}
+
+/// Prompt setting an environment variable of the unit of the details view, then offering to
+/// restart it so that it sees the variable.
+#[derive(Default)]
+pub struct EnvironmentPrompt {
+    open: bool,
+    unit: String,
+    input: String,
+    error: Option<String>,
+    /// The drop-in written, once asking whether to restart.
+    written: Option<String>,
+    locale: Locale,
+}
+
+impl EnvironmentPrompt {
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn open(&mut self, unit: &str, locale: Locale) {
+        self.open = true;
+        self.locale = locale;
+        self.unit = unit.to_string();
+        self.input.clear();
+        self.error = None;
+        self.written = None;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    /// Asks whether to restart the unit now that the drop-in is written.
+    pub fn written(&mut self, path: String) {
+        self.written = Some(path);
+        self.error = None;
+    }
+
+    /// Keeps the prompt open with the reason the variable could not be set.
+    pub fn failed(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    pub fn on_key_event(&mut self, key: KeyEvent) -> Option<EnvironmentRequest> {
+        if self.written.is_some() {
+            match key.code {
+                KeyCode::Enter | KeyCode::Char('y') => {
+                    self.open = false;
+                    return Some(EnvironmentRequest::Restart);
+                }
+                KeyCode::Esc | KeyCode::Char('n') => self.open = false,
+                _ => {}
+            }
+            return None;
+        }
+        match key.code {
+            KeyCode::Esc => self.open = false,
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Char(c) => self.input.push(c),
+            KeyCode::Enter if !self.input.trim().is_empty() => {
+                return Some(EnvironmentRequest::Set(self.input.trim().to_string()));
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn title(&self) -> String {
+        match &self.written {
+            Some(path) => self.locale.format("Wrote {}, restart {} now?", &[path, &self.unit]),
+            None => self.locale.format("Set a variable of {}, as KEY=VALUE", &[&self.unit]),
+        }
+    }
+
+    fn help(&self) -> &str {
+        self.locale.tr(if self.written.is_some() { RESTART_HELP } else { INPUT_HELP })
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        let mut lines = vec![self.title()];
+        if self.written.is_none() {
+            lines.push(self.locale.format("Variable: {}", &[&self.input]));
+        }
+        lines.extend(self.error.iter().map(|error| format!("{}: {}", self.locale.tr("Error"), error)));
+        lines.push(self.help().to_string());
+        lines
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open || area.height < 3 {
+            return;
+        }
+
+        let prompt_area = Rect::new(area.x, area.bottom() - 3, area.width, 3);
+        let border = if self.error.is_some() { theme.error } else { theme.highlight_border };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border))
+            .title(format!(" {} ", self.title()))
+            .title_bottom(Line::from(format!(" {} ", self.help())).right_aligned());
+        let inner = block.inner(prompt_area);
+        frame.render_widget(Clear, prompt_area);
+        frame.render_widget(block, prompt_area);
+
+        if self.written.is_some() {
+            return;
+        }
+        let line = match &self.error {
+            Some(error) => Line::from(vec![
+                Span::styled(self.input.as_str(), Style::default().fg(theme.text)),
+                Span::styled(format!("  {}", error), Style::default().fg(theme.error)),
+            ]),
+            None => Line::from(Span::styled(self.input.as_str(), Style::default().fg(theme.text))),
+        };
+        frame.render_widget(Paragraph::new(line), inner);
+        #[allow(clippy::cast_possible_truncation)]
+        frame.set_cursor_position(Position::new(
+            (inner.x + self.input.chars().count() as u16).min(inner.right().saturating_sub(1)),
+            inner.y,
+        ));
+    }
+}
@@ -0,0 +1,156 @@
+use chrono::{DateTime, Local};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use std::rc::Rc;
+
+use crate::config::Config;
+use crate::errors::AppError;
+use crate::terminal::components::overlay::Overlay;
+use crate::terminal::theme::Theme;
+
+/// Errors kept by the history, the oldest ones are dropped first.
+const MAX_ENTRIES: usize = 100;
+
+/// Every error reported during the session, newest first, with its D-Bus name and raw message.
+pub struct ErrorHistory {
+    open: bool,
+    entries: Vec<(DateTime<Local>, AppError)>,
+    scroll: u16,
+    config: Rc<Config>,
+}
+
+impl ErrorHistory {
+    pub fn new(config: Rc<Config>) -> Self {
+        Self {
+            open: false,
+            entries: Vec::new(),
+            scroll: 0,
+            config,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+        self.scroll = 0;
+    }
+
+    pub fn push(&mut self, error: AppError) {
+        self.entries.insert(0, (Local::now(), error));
+        self.entries.truncate(MAX_ENTRIES);
+    }
+
+    fn help(&self) -> String {
+        let keys = &self.config.keybindings;
+        self.config.locale.shortcuts(&[
+            ("Scroll", format!("{}/{}", keys.up, keys.down)),
+            ("Clear", keys.clear_marks.to_string()),
+            ("Close", keys.back.to_string()),
+        ])
+    }
+}
+
+impl Overlay for ErrorHistory {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn on_key_event(&mut self, key: KeyEvent) {
+        let keys = &self.config.keybindings;
+        match key {
+            k if k.code == KeyCode::Esc || keys.back.matches(&k) => self.open = false,
+            k if keys.up.matches(&k) => self.scroll = self.scroll.saturating_sub(1),
+            k if keys.down.matches(&k) => self.scroll = self.scroll.saturating_add(1),
+            k if keys.page_up.matches(&k) => self.scroll = self.scroll.saturating_sub(10),
+            k if keys.page_down.matches(&k) => self.scroll = self.scroll.saturating_add(10),
+            k if keys.clear_marks.matches(&k) => {
+                self.entries.clear();
+                self.scroll = 0;
+            }
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open {
+            return;
+        }
+
+        let popup_width = std::cmp::min(100, area.width.saturating_sub(4));
+        let popup_height = area.height.saturating_sub(4);
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let locale = self.config.locale;
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border))
+            .title(format!(" {} ({}) ", locale.tr("Error history"), self.entries.len()))
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [entries_area, help_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+
+        let mut lines = Vec::new();
+        if self.entries.is_empty() {
+            lines.push(Line::from(Span::styled(locale.tr("No errors so far"), Style::default().fg(theme.muted))));
+        }
+        for (time, error) in &self.entries {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{} ", time.format("%H:%M:%S")), Style::default().fg(theme.muted)),
+                Span::styled(
+                    locale.tr(error.explanation()).to_string(),
+                    Style::default().fg(theme.error).add_modifier(Modifier::BOLD),
+                ),
+            ]));
+            if let Some(name) = error.dbus_name() {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  {} ", locale.tr("D-Bus error")), Style::default().fg(theme.muted)),
+                    Span::raw(name.to_string()),
+                ]));
+                if error.message() != error.explanation() {
+                    lines.push(Line::from(format!("  {}", error.message())));
+                }
+            }
+            lines.push(Line::from(""));
+        }
+
+        frame.render_widget(
+            Paragraph::new(lines)
+                .style(Style::default().fg(theme.text))
+                .wrap(Wrap { trim: false })
+                .scroll((self.scroll, 0)),
+            entries_area,
+        );
+        frame.render_widget(
+            Paragraph::new(self.help()).style(Style::default().fg(theme.muted)),
+            help_area,
+        );
+    }
+
+    fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        let mut lines = vec![locale.tr("Error history, {} errors, newest first").replace("{}", &self.entries.len().to_string())];
+        lines.extend(self.entries.iter().skip(self.scroll as usize).map(|(time, error)| {
+            let mut line = format!("{}: {}", time.format("%H:%M:%S"), locale.tr(error.explanation()));
+            if let Some(name) = error.dbus_name() {
+                line.push_str(&format!("; {}: {}; {}: {}", locale.tr("D-Bus error"), name, locale.tr("message"), error.message()));
+            }
+            line
+        }));
+        lines.push(self.help());
+        lines
+    }
+}
@@ -0,0 +1,271 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Wrap},
+    Frame,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+
+use crate::config::Config;
+use crate::domain::failed_unit::FailedUnit;
+use crate::errors::AppError;
+use crate::terminal::app::{Actions, AppEvent};
+use crate::terminal::components::overlay::Overlay;
+use crate::terminal::theme::Theme;
+use crate::usecases::services_manager::ServicesManager;
+
+/// Failed units with why they failed, and their failed state reset from the TUI.
+pub struct FailedUnits {
+    open: bool,
+    units: Option<Result<Vec<FailedUnit>, String>>,
+    table_state: TableState,
+    sender: Sender<AppEvent>,
+    usecase: Rc<RefCell<ServicesManager>>,
+    config: Rc<Config>,
+}
+
+impl FailedUnits {
+    pub fn new(sender: Sender<AppEvent>, usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
+        Self {
+            open: false,
+            units: None,
+            table_state: TableState::default(),
+            sender,
+            usecase,
+            config,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+        self.table_state.select(Some(0));
+        self.refresh();
+    }
+
+    fn refresh(&mut self) {
+        self.units = Some(
+            self.usecase
+                .borrow()
+                .failed_units()
+                .map_err(|e| self.config.locale.tr(AppError::from(e).explanation()).to_string()),
+        );
+        let len = self.units().len();
+        if self.table_state.selected().is_some_and(|selected| selected >= len) {
+            self.table_state.select(Some(len.saturating_sub(1)));
+        }
+    }
+
+    fn units(&self) -> &[FailedUnit] {
+        match &self.units {
+            Some(Ok(units)) => units,
+            _ => &[],
+        }
+    }
+
+    fn selected(&self) -> Option<&FailedUnit> {
+        self.table_state.selected().and_then(|selected| self.units().get(selected))
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        let len = self.units().len();
+        if len == 0 {
+            return;
+        }
+        let selected = self.table_state.selected().unwrap_or(0) as isize;
+        let next = (selected + delta).clamp(0, len as isize - 1);
+        self.table_state.select(Some(next as usize));
+    }
+
+    /// Resets the selected unit, or every unit when `all` is set, then reloads both lists.
+    fn reset(&mut self, all: bool) {
+        let locale = self.config.locale;
+        let result = if all {
+            self.usecase.borrow().reset_failed().map(|denied| match denied.as_slice() {
+                [] => locale.tr("every failed unit").to_string(),
+                _ => locale.format("the failed units but {}, refused by the policy", &[&denied.join(", ")]),
+            })
+        } else {
+            let Some(unit) = self.selected() else {
+                return;
+            };
+            let service = unit.service().clone();
+            self.usecase
+                .borrow()
+                .reset_failed_unit(&service)
+                .map(|_| service.name().to_string())
+        };
+        match result {
+            Ok(target) => self
+                .sender
+                .send(AppEvent::Notice(locale.format("Reset the failed state of {}", &[&target])))
+                .unwrap(),
+            Err(e) => self.sender.send(AppEvent::Error(e.into())).unwrap(),
+        }
+        self.refresh();
+        self.sender.send(AppEvent::Action(Actions::ReloadList)).unwrap();
+    }
+
+    fn help(&self) -> String {
+        let keys = &self.config.keybindings;
+        self.config.locale.shortcuts(&[
+            ("Scroll", format!("{}/{}", keys.up, keys.down)),
+            ("Reset", keys.reset_failed.to_string()),
+            ("Reset all", keys.clear_marks.to_string()),
+            ("Refresh", keys.refresh.to_string()),
+            ("Close", keys.back.to_string()),
+        ])
+    }
+
+    fn exit_status(unit: &FailedUnit) -> String {
+        unit.exit_status().map(|status| status.to_string()).unwrap_or_default()
+    }
+}
+
+impl Overlay for FailedUnits {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn on_key_event(&mut self, key: KeyEvent) {
+        let keys = &self.config.keybindings;
+        match key {
+            k if k.code == KeyCode::Esc || keys.back.matches(&k) => self.open = false,
+            k if keys.up.matches(&k) => self.scroll(-1),
+            k if keys.down.matches(&k) => self.scroll(1),
+            k if keys.page_up.matches(&k) => self.scroll(-10),
+            k if keys.page_down.matches(&k) => self.scroll(10),
+            k if keys.refresh.matches(&k) => self.refresh(),
+            k if keys.reset_failed.matches(&k) => self.reset(false),
+            k if keys.clear_marks.matches(&k) => self.reset(true),
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open {
+            return;
+        }
+
+        let popup_width = std::cmp::min(110, area.width.saturating_sub(4));
+        let popup_height = area.height.saturating_sub(2);
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border))
+            .title(format!(" {} ({}) ", self.config.locale.tr("Failed units"), self.units().len()))
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [units_area, log_area, help_area] = Layout::vertical([
+            Constraint::Min(3),
+            Constraint::Length(9),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+
+        match &self.units {
+            None => {}
+            Some(Err(e)) => frame.render_widget(
+                Paragraph::new(e.as_str()).style(Style::default().fg(theme.error)),
+                units_area,
+            ),
+            Some(Ok(units)) if units.is_empty() => frame.render_widget(
+                Paragraph::new(self.config.locale.tr("No failed units")).style(Style::default().fg(theme.active)),
+                units_area,
+            ),
+            Some(Ok(units)) => {
+                let rows = units.iter().map(|unit| {
+                    Row::new(vec![
+                        Cell::from(unit.service().name().to_string()),
+                        Cell::from(unit.result().to_string()),
+                        Cell::from(Self::exit_status(unit)),
+                        Cell::from(unit.service().description().to_string()),
+                    ])
+                    .style(Style::default().fg(theme.failed))
+                });
+                let table = Table::new(
+                    rows,
+                    [
+                        Constraint::Percentage(30),
+                        Constraint::Length(12),
+                        Constraint::Length(11),
+                        Constraint::Min(0),
+                    ],
+                )
+                .header(
+                    Row::new(["Unit", "Result", "Exit status", "Description"].map(|header| self.config.locale.tr(header)))
+                        .style(Style::default().fg(theme.header).add_modifier(Modifier::BOLD)),
+                )
+                .row_highlight_style(
+                    Style::default()
+                        .bg(theme.selection_bg)
+                        .fg(theme.selection_fg),
+                );
+                frame.render_stateful_widget(table, units_area, &mut self.table_state);
+            }
+        }
+
+        let log: Vec<Line> = self
+            .selected()
+            .map(|unit| unit.log_tail().iter().map(|line| Line::from(line.clone())).collect())
+            .unwrap_or_default();
+        frame.render_widget(
+            Paragraph::new(log)
+                .style(Style::default().fg(theme.text))
+                .wrap(Wrap { trim: false })
+                .block(
+                    Block::default()
+                        .borders(Borders::TOP)
+                        .border_style(Style::default().fg(theme.border))
+                        .title(format!(" {} ", self.config.locale.tr("Last journal lines"))),
+                ),
+            log_area,
+        );
+
+        frame.render_widget(
+            Paragraph::new(self.help())
+                .style(Style::default().fg(theme.muted))
+                .alignment(Alignment::Center),
+            help_area,
+        );
+    }
+
+    fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        let mut lines = vec![locale.format("Failed units, {} units", &[&self.units().len().to_string()])];
+        if let Some(Err(e)) = &self.units {
+            lines.push(format!("{}: {}", locale.tr("Error"), e));
+        }
+        let selected = self.table_state.selected().unwrap_or(0);
+        for (index, unit) in self.units().iter().enumerate().skip(selected) {
+            lines.push(locale.format(
+                "Unit {} of {}{}: {}; result: {}; exit status: {}",
+                &[
+                    &(index + 1).to_string(),
+                    &self.units().len().to_string(),
+                    if index == selected { locale.tr(" (selected)") } else { "" },
+                    unit.service().name(),
+                    unit.result(),
+                    &Self::exit_status(unit),
+                ],
+            ));
+            if index == selected {
+                lines.extend(unit.log_tail().iter().map(|line| format!("{}: {}", locale.tr("Journal"), line)));
+            }
+        }
+        lines.push(self.help());
+        lines
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::HashSet;
+use std::io::{stdout, Write};
+use std::process::{Command, Stdio};
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::domain::service::Service;
+use crate::infrastructure::desktop_notification;
+use crate::terminal::app::{Actions, AppEvent};
+
+/// Rings the bell or runs a command when a watched unit fails, e.g. while the TUI sits in
+/// another tmux pane.
+pub struct FailureAlerts {
+    /// Units failed at the previous check, `None` before the first one.
+    failed: Option<HashSet<String>>,
+    focused: bool,
+    config: Rc<Config>,
+}
+
+impl FailureAlerts {
+    pub fn new(config: Rc<Config>) -> Self {
+        Self {
+            failed: None,
+            focused: true,
+            config,
+        }
+    }
+
+    /// Periodically asks the app to check for failures when the alerts are enabled.
+    pub fn spawn_background_check(&self, sender: Sender<AppEvent>) {
+        let settings = &self.config.alerts;
+        if !settings.enabled {
+            return;
+        }
+        let interval = Duration::from_secs(settings.interval.max(1));
+        thread::spawn(move || {
+            loop {
+                thread::sleep(interval);
+                if sender.send(AppEvent::Action(Actions::CheckFailures)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    /// Forgets the failed units, e.g. after switching to another host, so that the units
+    /// already failed there do not alert.
+    pub fn clear(&mut self) {
+        self.failed = None;
+    }
+
+    /// Compares the failed units with the previous check, alerting about the watched units
+    /// that failed since. Returns these units.
+    pub fn check(&mut self, services: &[Service]) -> Vec<String> {
+        let failed: HashSet<String> = services
+            .iter()
+            .filter(|service| service.state().active() == "failed")
+            .map(|service| service.name().to_string())
+            .collect();
+        let settings = &self.config.alerts;
+        let mut newly_failed: Vec<String> = match &self.failed {
+            Some(previous) => failed
+                .iter()
+                .filter(|unit| !previous.contains(*unit) && settings.is_watched(unit))
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+        newly_failed.sort();
+        self.failed = Some(failed);
+
+        let muted = settings.only_unfocused && self.focused;
+        if !newly_failed.is_empty() && !muted {
+            self.alert(&newly_failed);
+        }
+        newly_failed
+    }
+
+    fn alert(&self, units: &[String]) {
+        let settings = &self.config.alerts;
+        if settings.bell {
+            ring_bell();
+        }
+        if settings.desktop {
+            notify_desktop(format!("{} failed", units.join(", ")), String::new());
+        }
+        if !settings.command.is_empty() {
+            for unit in units {
+                let command = settings.command.clone();
+                let unit = unit.clone();
+                // Waited for on its own thread, so that a slow command does not block the TUI.
+                thread::spawn(move || {
+                    let _ = Command::new("sh")
+                        .arg("-c")
+                        .arg(command)
+                        .env("UNIT", unit)
+                        .stdin(Stdio::null())
+                        .stdout(Stdio::null())
+                        .stderr(Stdio::null())
+                        .status();
+                });
+            }
+        }
+    }
+}
+
+/// Rings the terminal bell, which tmux and most terminals flag on the pane or tab.
+pub fn ring_bell() {
+    let mut out = stdout();
+    let _ = out.write_all(b"\x07");
+    let _ = out.flush();
+}
+
+/// Shows a critical desktop notification from its own thread, a missing notification daemon
+/// only costing the notification.
+pub fn notify_desktop(summary: String, body: String) {
+    thread::spawn(move || {
+        let _ = desktop_notification::notify(&summary, &body, true);
+    });
+}
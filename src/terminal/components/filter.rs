@@ -1,20 +1,24 @@
 use ratatui::{
     crossterm::event::{KeyCode, KeyEvent, KeyEventKind},
     layout::{Constraint, Layout, Position, Rect},
-    style::{Color, Style, Stylize},
-    text::{Line, Text},
+    style::{Style, Stylize},
+    text::{Line, Span, Text},
     widgets::{Block, Paragraph},
     Frame,
 };
+use std::rc::Rc;
 use std::sync::mpsc::Sender;
 
+use crate::config::Config;
 use crate::terminal::app::{Actions, AppEvent};
+use crate::terminal::theme::Theme;
 
 pub struct Filter {
     pub input: String,
     character_index: usize,
     pub input_mode: InputMode,
     sender: Sender<AppEvent>,
+    config: Rc<Config>,
 }
 
 #[derive(PartialEq)]
@@ -24,15 +28,37 @@ pub enum InputMode {
 }
 
 impl Filter {
-    pub const fn new(sender: Sender<AppEvent>) -> Self {
+    pub fn new(sender: Sender<AppEvent>, config: Rc<Config>) -> Self {
+        let input = config.default_filter.clone();
         Self {
             sender,
-            input: String::new(),
+            character_index: input.chars().count(),
+            input,
             input_mode: InputMode::Normal,
-            character_index: 0,
+            config,
         }
     }
 
+    pub fn clear(&mut self) {
+        self.input.clear();
+        self.character_index = 0;
+    }
+
+    /// Replaces the filter without applying it, for a list already filtered with it.
+    pub fn restore_input(&mut self, text: &str) {
+        self.input = text.to_string();
+        self.character_index = self.input.chars().count();
+    }
+
+    /// Replaces the filter, e.g. from the `:filter` command, and applies it.
+    pub fn set_input(&mut self, text: &str) {
+        self.input = text.to_string();
+        self.character_index = self.input.chars().count();
+        self.sender
+            .send(AppEvent::Action(Actions::Filter(self.input.clone())))
+            .unwrap();
+    }
+
     fn move_cursor_left(&mut self) {
         let cursor_moved_left = self.character_index.saturating_sub(1);
         self.character_index = self.clamp_cursor(cursor_moved_left);
@@ -88,7 +114,7 @@ impl Filter {
     pub fn on_key_event(&mut self, key: KeyEvent) {
         match self.input_mode {
             InputMode::Normal => match key.code {
-                KeyCode::Char('i') => {
+                _ if self.config.keybindings.filter.matches(&key) => {
                     self.sender
                         .send(AppEvent::Action(Actions::UpdateIgnoreListKeys(true)))
                         .unwrap();
@@ -96,6 +122,7 @@ impl Filter {
                 }
                 KeyCode::Esc => {
                     self.input = String::new();
+                    self.character_index = 0;
                     self.sender
                         .send(AppEvent::Action(Actions::Filter(self.input.clone())))
                         .unwrap();
@@ -128,24 +155,72 @@ impl Filter {
         }
     }
 
-    pub fn draw(&self, frame: &mut Frame, area: Rect) {
+    /// Inserts pasted text at the cursor in one go, starting to edit the filter if needed.
+    pub fn on_paste(&mut self, text: &str) {
+        if self.input_mode == InputMode::Normal {
+            self.sender
+                .send(AppEvent::Action(Actions::UpdateIgnoreListKeys(true)))
+                .unwrap();
+            self.input_mode = InputMode::Editing;
+        }
+        for c in text.trim().chars().filter(|c| !c.is_control()) {
+            self.enter_char(c);
+        }
+        self.sender
+            .send(AppEvent::Action(Actions::Filter(self.input.clone())))
+            .unwrap();
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        match self.input_mode {
+            InputMode::Normal => vec![locale.format(
+                "Filter: {} (press {} to edit)",
+                &[&self.input, &self.config.keybindings.filter.to_string()],
+            )],
+            InputMode::Editing => vec![locale.format(
+                "Editing filter: {} (Enter to submit, Esc to stop)",
+                &[&self.input],
+            )],
+        }
+    }
+
+    /// The filter on a single line, for short terminals, e.g. "Filter (i): nginx".
+    pub fn draw_compact(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let locale = self.config.locale;
+        let (label, style) = match self.input_mode {
+            InputMode::Normal => (locale.format("Filter ({}): ", &[&self.config.keybindings.filter.to_string()]), Style::default().fg(theme.text)),
+            InputMode::Editing => (locale.tr("Filter (Esc to stop): ").to_string(), Style::default().fg(theme.warning)),
+        };
+        #[allow(clippy::cast_possible_truncation)]
+        let cursor = area.x + label.chars().count() as u16 + self.character_index as u16;
+        let line = Line::from(vec![Span::styled(label, Style::default().fg(theme.muted)), Span::styled(self.input.as_str(), style)]);
+        frame.render_widget(Paragraph::new(line), area);
+        if self.input_mode == InputMode::Editing {
+            frame.set_cursor_position(Position::new(cursor, area.y));
+        }
+    }
+
+    pub fn draw(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let vertical = Layout::vertical([Constraint::Length(1), Constraint::Length(3)]);
         let [help_area, input_area] = vertical.areas(area);
+        let locale = self.config.locale;
 
         let (msg, style) = match self.input_mode {
             InputMode::Normal => (
-                vec!["Press ".into(), "i".bold(), " to start filtering.".into()],
-                Style::default(),
+                vec![locale.tr("Press ").into(), self.config.keybindings.filter.to_string().bold(), locale.tr(" to start filtering.").into()],
+                Style::default().fg(theme.text),
             ),
             InputMode::Editing => (
                 vec![
-                    "Press ".into(),
+                    locale.tr("Press ").into(),
                     "Esc".bold(),
-                    " to stop filtering, ".into(),
+                    locale.tr(" to stop filtering, ").into(),
                     "Enter".bold(),
-                    " to submit filter".into(),
+                    locale.tr(" to submit filter, e.g. ").into(),
+                    "nginx state:failed enabled:no".bold(),
                 ],
-                Style::default(),
+                Style::default().fg(theme.text),
             ),
         };
         let text = Text::from(Line::from(msg)).patch_style(style);
@@ -154,10 +229,14 @@ impl Filter {
 
         let input = Paragraph::new(self.input.as_str())
             .style(match self.input_mode {
-                InputMode::Normal => Style::default(),
-                InputMode::Editing => Style::default().fg(Color::Yellow),
+                InputMode::Normal => Style::default().fg(theme.text),
+                InputMode::Editing => Style::default().fg(theme.warning),
             })
-            .block(Block::bordered().title("Input"));
+            .block(
+                Block::bordered()
+                    .border_style(Style::default().fg(theme.border))
+                    .title(locale.tr("Input")),
+            );
         frame.render_widget(input, input_area);
         match self.input_mode {
             InputMode::Normal => {}
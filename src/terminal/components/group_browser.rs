@@ -0,0 +1,316 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+
+use crate::config::Config;
+use crate::domain::service_group::{unit_groups, UnitGroup};
+use crate::errors::AppError;
+use crate::terminal::app::{Actions, AppEvent};
+use crate::terminal::components::list::ServiceAction;
+use crate::terminal::components::overlay::Overlay;
+use crate::terminal::theme::Theme;
+use crate::usecases::services_manager::ServicesManager;
+
+/// A row of the view: a group, by its index in the groups, or a unit of the one above.
+enum GroupRow {
+    Group(usize),
+    Unit(String),
+}
+
+/// The units grouped as the config says, then by the start of their names, each group folded
+/// into a header to start, stop or restart all of its units in order.
+pub struct GroupBrowser {
+    open: bool,
+    groups: Vec<UnitGroup>,
+    /// Groups whose units are shown, by name.
+    expanded: HashSet<String>,
+    /// Active state of the listed units, by name.
+    states: HashMap<String, String>,
+    error: Option<String>,
+    table_state: TableState,
+    sender: Sender<AppEvent>,
+    usecase: Rc<RefCell<ServicesManager>>,
+    config: Rc<Config>,
+}
+
+impl GroupBrowser {
+    pub fn new(sender: Sender<AppEvent>, usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
+        Self {
+            open: false,
+            groups: Vec::new(),
+            expanded: HashSet::new(),
+            states: HashMap::new(),
+            error: None,
+            table_state: TableState::default(),
+            sender,
+            usecase,
+            config,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+        self.refresh();
+        self.table_state.select(Some(0));
+    }
+
+    fn refresh(&mut self) {
+        let services = self.usecase.borrow().list_services();
+        let services = match services {
+            Ok(services) => {
+                self.error = None;
+                services
+            }
+            Err(e) => {
+                self.error = Some(self.config.locale.tr(AppError::from(e).explanation()).trim().to_string());
+                Vec::new()
+            }
+        };
+        self.states = services
+            .iter()
+            .map(|service| (service.name().to_string(), service.state().active().to_string()))
+            .collect();
+        let listed: Vec<String> = if self.config.derive_groups {
+            services.iter().map(|service| service.name().to_string()).collect()
+        } else {
+            Vec::new()
+        };
+        self.groups = unit_groups(&self.config.groups, &listed);
+
+        let len = self.rows().len();
+        if self.table_state.selected().is_some_and(|selected| selected >= len) {
+            self.table_state.select(Some(len.saturating_sub(1)));
+        }
+    }
+
+    /// The groups, each expanded one followed by its units.
+    fn rows(&self) -> Vec<GroupRow> {
+        let mut rows = Vec::new();
+        for (index, group) in self.groups.iter().enumerate() {
+            rows.push(GroupRow::Group(index));
+            if self.expanded.contains(&group.name) {
+                rows.extend(group.units.iter().cloned().map(GroupRow::Unit));
+            }
+        }
+        rows
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        let len = self.rows().len();
+        if len == 0 {
+            return;
+        }
+        let selected = self.table_state.selected().unwrap_or(0) as isize;
+        let next = (selected + delta).clamp(0, len as isize - 1);
+        self.table_state.select(Some(next as usize));
+    }
+
+    fn selected_row(&self) -> Option<GroupRow> {
+        let selected = self.table_state.selected()?;
+        self.rows().into_iter().nth(selected)
+    }
+
+    /// Shows or hides the units of the selected group, or selects the selected unit in the list.
+    fn toggle(&mut self) {
+        match self.selected_row() {
+            Some(GroupRow::Group(index)) => {
+                let name = self.groups[index].name.clone();
+                if !self.expanded.remove(&name) {
+                    self.expanded.insert(name);
+                }
+            }
+            Some(GroupRow::Unit(unit)) => self.jump(unit),
+            None => {}
+        }
+    }
+
+    fn jump(&mut self, unit: String) {
+        self.sender.send(AppEvent::Action(Actions::JumpToService(unit))).unwrap();
+        self.open = false;
+    }
+
+    /// Runs the action on the selected unit, or on every unit of the selected group one after
+    /// the other in dependency order, the order of the group deciding between unrelated units.
+    /// The list previews it in dry-run mode and asks to type the critical units it disrupts.
+    fn act(&mut self, action: ServiceAction) {
+        let units = match self.selected_row() {
+            Some(GroupRow::Group(index)) => self.groups[index].units.clone(),
+            Some(GroupRow::Unit(unit)) => vec![unit],
+            None => return,
+        };
+        self.sender.send(AppEvent::Action(Actions::RunOnUnits(action, units))).unwrap();
+        self.open = false;
+    }
+
+    fn help(&self) -> String {
+        let keys = &self.config.keybindings;
+        self.config.locale.shortcuts(&[
+            ("Units of group", "Enter".to_string()),
+            ("Start", keys.start.to_string()),
+            ("Stop", keys.stop.to_string()),
+            ("Restart", keys.restart.to_string()),
+            ("Jump to unit", keys.jump.to_string()),
+            ("Refresh", keys.refresh.to_string()),
+            ("Close", keys.back.to_string()),
+        ])
+    }
+
+    /// e.g. "3 units: 2 active, 1 failed".
+    fn summary(&self, group: &UnitGroup) -> String {
+        let count = |state: &str| group.units.iter().filter(|unit| self.states.get(*unit).is_some_and(|s| s == state)).count();
+        let locale = self.config.locale;
+        let mut parts = vec![locale.format("{} active", &[&count("active").to_string()])];
+        let failed = count("failed");
+        if failed > 0 {
+            parts.push(locale.format("{} failed", &[&failed.to_string()]));
+        }
+        locale.format("{} units: {}", &[&group.units.len().to_string(), &parts.join(", ")])
+    }
+}
+
+impl Overlay for GroupBrowser {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn on_key_event(&mut self, key: KeyEvent) {
+        let config = self.config.clone();
+        let keys = &config.keybindings;
+        match key {
+            k if k.code == KeyCode::Esc || keys.back.matches(&k) || keys.groups.matches(&k) => self.open = false,
+            k if k.code == KeyCode::Enter => self.toggle(),
+            k if keys.up.matches(&k) => self.scroll(-1),
+            k if keys.down.matches(&k) => self.scroll(1),
+            k if keys.page_up.matches(&k) => self.scroll(-10),
+            k if keys.page_down.matches(&k) => self.scroll(10),
+            k if keys.refresh.matches(&k) => self.refresh(),
+            k if keys.start.matches(&k) => self.act(ServiceAction::Start),
+            k if keys.stop.matches(&k) => self.act(ServiceAction::Stop),
+            k if keys.restart.matches(&k) => self.act(ServiceAction::Restart),
+            k if keys.jump.matches(&k) => {
+                if let Some(GroupRow::Unit(unit)) = self.selected_row() {
+                    self.jump(unit);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open {
+            return;
+        }
+
+        let popup_width = std::cmp::min(100, area.width.saturating_sub(4));
+        let popup_height = area.height.saturating_sub(2);
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border))
+            .title(format!(" {} ", self.config.locale.tr("Groups")))
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [table_area, help_area] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+
+        if let Some(e) = &self.error {
+            frame.render_widget(Paragraph::new(e.as_str()).style(Style::default().fg(theme.error)), table_area);
+        } else if self.groups.is_empty() {
+            frame.render_widget(
+                Paragraph::new(self.config.locale.tr("No groups: set groups in the config")).style(Style::default().fg(theme.muted)),
+                table_area,
+            );
+        } else {
+            let rows: Vec<Row> = self
+                .rows()
+                .into_iter()
+                .map(|row| match row {
+                    GroupRow::Group(index) => {
+                        let group = &self.groups[index];
+                        let marker = if self.expanded.contains(&group.name) { "▾" } else { "▸" };
+                        let style = if group.derived { theme.text } else { theme.accent };
+                        Row::new(vec![
+                            Cell::from(format!("{} {}", marker, group.name)),
+                            Cell::from(self.summary(group)),
+                        ])
+                        .style(Style::default().fg(style).add_modifier(Modifier::BOLD))
+                    }
+                    GroupRow::Unit(unit) => {
+                        let state = self.states.get(&unit).map(String::as_str);
+                        let color = match state {
+                            Some("active") => theme.active,
+                            Some("failed") => theme.failed,
+                            Some("activating" | "deactivating" | "reloading") => theme.activating,
+                            None => theme.muted,
+                            _ => theme.text,
+                        };
+                        let state = state.unwrap_or(self.config.locale.tr("not listed")).to_string();
+                        Row::new(vec![Cell::from(format!("    {}", unit)), Cell::from(state)])
+                            .style(Style::default().fg(color))
+                    }
+                })
+                .collect();
+            let table = Table::new(rows, [Constraint::Min(0), Constraint::Length(30)])
+                .header(
+                    Row::new(["Group", "Units"].map(|header| self.config.locale.tr(header))).style(Style::default().fg(theme.header).add_modifier(Modifier::BOLD)),
+                )
+                .style(Style::default().fg(theme.text))
+                .row_highlight_style(Style::default().bg(theme.selection_bg).fg(theme.selection_fg));
+            frame.render_stateful_widget(table, table_area, &mut self.table_state);
+        }
+
+        frame.render_widget(
+            Paragraph::new(self.help())
+                .style(Style::default().fg(theme.muted))
+                .alignment(Alignment::Center),
+            help_area,
+        );
+    }
+
+    fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        let mut lines = vec![locale.tr("Groups").to_string()];
+        if let Some(e) = &self.error {
+            lines.push(format!("{}: {}", locale.tr("Error"), e));
+        }
+        if self.groups.is_empty() {
+            lines.push(locale.tr("No groups: set groups in the config").to_string());
+        }
+        let selected = self.table_state.selected().unwrap_or(0);
+        for (index, row) in self.rows().into_iter().enumerate().skip(selected) {
+            let flag = if index == selected { locale.tr(" (selected)") } else { "" };
+            match row {
+                GroupRow::Group(group) => {
+                    let group = &self.groups[group];
+                    let state = if self.expanded.contains(&group.name) { "expanded" } else { "collapsed" };
+                    lines.push(locale.format(
+                        "Group {}{}, {}, {}",
+                        &[&group.name, flag, locale.tr(state), &self.summary(group)],
+                    ));
+                }
+                GroupRow::Unit(unit) => {
+                    let state = self.states.get(&unit).map(String::as_str).unwrap_or(locale.tr("not listed"));
+                    lines.push(locale.format("Unit {}{}: {}", &[&unit, flag, state]));
+                }
+            }
+        }
+        lines.push(self.help());
+        lines
+    }
+}
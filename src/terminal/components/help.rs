@@ -0,0 +1,201 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::rc::Rc;
+
+use crate::config::Config;
+use crate::terminal::components::overlay::Overlay;
+use crate::terminal::theme::Theme;
+
+/// Width of the action column.
+const LABEL_WIDTH: usize = 28;
+
+enum HelpRow {
+    Section(String),
+    Entry(String, String),
+}
+
+/// Scrollable overlay with the shortcuts of the current screen, then every key binding as
+/// configured, remappings included.
+pub struct Help {
+    open: bool,
+    rows: Vec<HelpRow>,
+    scroll: usize,
+    config: Rc<Config>,
+}
+
+/// The rows of a shortcut line, "Label: key | Label: key", a part without a key being a
+/// section. A key is at least one character, so `|` can be bound: "Columns: | | Sort: s".
+fn shortcut_rows(line: &str) -> Vec<HelpRow> {
+    let mut rows = Vec::new();
+    let mut rest = line;
+    while !rest.is_empty() {
+        match rest.find(": ") {
+            Some(colon) if !rest[..colon].contains(" | ") => {
+                let key_start = colon + 2;
+                let first = rest[key_start..].chars().next().map_or(0, char::len_utf8);
+                let end = rest[key_start + first..]
+                    .find(" | ")
+                    .map_or(rest.len(), |index| key_start + first + index);
+                rows.push(HelpRow::Entry(rest[..colon].to_string(), rest[key_start..end].to_string()));
+                rest = rest[end..].strip_prefix(" | ").unwrap_or("");
+            }
+            _ => {
+                let (section, next) = rest.split_once(" | ").unwrap_or((rest, ""));
+                if !section.is_empty() {
+                    rows.push(HelpRow::Section(section.to_string()));
+                }
+                rest = next;
+            }
+        }
+    }
+    rows
+}
+
+impl Help {
+    pub fn new(config: Rc<Config>) -> Self {
+        Self {
+            open: false,
+            rows: Vec::new(),
+            scroll: 0,
+            config,
+        }
+    }
+
+    /// Opens on the shortcut lines of the screen, as "Label: key | Label: key".
+    pub fn open(&mut self, screen_shortcuts: Vec<String>) {
+        let locale = self.config.locale;
+        let mut rows = vec![HelpRow::Section(locale.tr("This screen").to_string())];
+        for line in &screen_shortcuts {
+            rows.extend(shortcut_rows(line));
+        }
+
+        rows.push(HelpRow::Section(locale.tr("Every key binding").to_string()));
+        rows.extend(
+            self.config
+                .keybindings
+                .entries()
+                .into_iter()
+                .map(|(label, key)| HelpRow::Entry(locale.tr(label).to_string(), key.to_string())),
+        );
+        rows.push(HelpRow::Entry(locale.tr("Exit").to_string(), "Ctrl + c".to_string()));
+
+        let chords = &self.config.chords;
+        rows.push(HelpRow::Section(
+            locale.format("Chords, keys pressed within {} ms", &[&chords.timeout.to_string()]),
+        ));
+        rows.extend(
+            chords
+                .sequences()
+                .into_iter()
+                .map(|(action, sequence)| HelpRow::Entry(locale.tr(action.label()).to_string(), sequence.to_string())),
+        );
+
+        self.rows = rows;
+        self.scroll = 0;
+        self.open = true;
+    }
+
+    fn scroll_by(&mut self, delta: isize) {
+        let last = self.rows.len().saturating_sub(1) as isize;
+        self.scroll = (self.scroll as isize + delta).clamp(0, last) as usize;
+    }
+
+    fn help(&self) -> String {
+        let keys = &self.config.keybindings;
+        self.config.locale.shortcuts(&[
+            ("Scroll", format!("{}/{}", keys.up, keys.down)),
+            ("Close", keys.help.to_string()),
+        ])
+    }
+}
+
+impl Overlay for Help {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn on_key_event(&mut self, key: KeyEvent) {
+        let keys = &self.config.keybindings;
+        match key {
+            k if k.code == KeyCode::Esc || keys.back.matches(&k) || keys.help.matches(&k) => self.open = false,
+            k if keys.up.matches(&k) => self.scroll_by(-1),
+            k if keys.down.matches(&k) => self.scroll_by(1),
+            k if keys.page_up.matches(&k) => self.scroll_by(-10),
+            k if keys.page_down.matches(&k) => self.scroll_by(10),
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open {
+            return;
+        }
+
+        let popup_width = std::cmp::min(70, area.width.saturating_sub(4));
+        let popup_height = area.height.saturating_sub(2);
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border))
+            .title(format!(" {} ", self.config.locale.tr("Key bindings")))
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [rows_area, help_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+
+        // Keeps the last rows at the bottom of the popup instead of scrolling them away.
+        let visible = rows_area.height as usize;
+        self.scroll = self.scroll.min(self.rows.len().saturating_sub(visible));
+
+        let lines: Vec<Line> = self
+            .rows
+            .iter()
+            .skip(self.scroll)
+            .take(visible)
+            .map(|row| match row {
+                HelpRow::Section(title) => Line::from(Span::styled(
+                    title.clone(),
+                    Style::default().fg(theme.title).add_modifier(Modifier::BOLD),
+                )),
+                HelpRow::Entry(label, key) => Line::from(vec![
+                    Span::styled(format!("  {:<width$}", label, width = LABEL_WIDTH), Style::default().fg(theme.text)),
+                    Span::styled(key.clone(), Style::default().fg(theme.accent)),
+                ]),
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), rows_area);
+
+        frame.render_widget(
+            Paragraph::new(self.help())
+                .style(Style::default().fg(theme.muted))
+                .alignment(Alignment::Center),
+            help_area,
+        );
+    }
+
+    fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        let mut lines = vec![locale.tr("Key bindings").to_string()];
+        lines.extend(self.rows.iter().skip(self.scroll).map(|row| match row {
+            HelpRow::Section(title) => locale.format("Section: {}", &[title]),
+            HelpRow::Entry(label, key) => format!("{}: {}", label, key),
+        }));
+        lines.push(self.help());
+        lines
+    }
+}
@@ -0,0 +1,195 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::Line,
+    widgets::Tabs,
+    Frame,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+
+use crate::config::Config;
+use crate::domain::service::Service;
+use crate::domain::service_repository::ServiceRepository;
+use crate::infrastructure::systemd_service_adapter::{ConnectionType, SystemdServiceAdapter};
+use crate::terminal::app::AppEvent;
+use crate::terminal::theme::Theme;
+use crate::usecases::services_manager::ServicesManager;
+
+const LOCAL_HOST: &str = "local";
+
+/// A machine the TUI can switch to. The repository of the host being shown lives in the
+/// shared `ServicesManager`; the others are parked here with their last service table.
+struct Host {
+    name: String,
+    /// Color of the borders and headers while the host is shown.
+    accent: Option<Color>,
+    repository: Option<Box<dyn ServiceRepository>>,
+    services: Option<Vec<Service>>,
+    tab_index: usize,
+}
+
+/// State restored when switching to another host.
+pub struct HostSwitch {
+    pub tab_index: usize,
+    pub services: Option<Vec<Service>>,
+}
+
+pub struct HostSwitcher {
+    hosts: Vec<Host>,
+    selected: usize,
+    sender: Sender<AppEvent>,
+    usecase: Rc<RefCell<ServicesManager>>,
+    config: Rc<Config>,
+}
+
+impl HostSwitcher {
+    pub fn new(sender: Sender<AppEvent>, usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
+        let hosts = std::iter::once((LOCAL_HOST.to_string(), None))
+            .chain(
+                config
+                    .hosts
+                    .iter()
+                    .map(|host| (host.name.clone(), host.accent.map(|accent| accent.0))),
+            )
+            .map(|(name, accent)| Host {
+                name,
+                accent,
+                repository: None,
+                services: None,
+                tab_index: 0,
+            })
+            .collect();
+        Self {
+            hosts,
+            selected: 0,
+            sender,
+            usecase,
+            config,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.hosts.len() > 1
+    }
+
+    fn connect(&self, index: usize) -> Result<Box<dyn ServiceRepository>, String> {
+        let host = &self.hosts[index];
+        SystemdServiceAdapter::remote(host.name.clone(), ConnectionType::System, self.config.unit_types.clone())
+            .map(|adapter| Box::new(adapter) as Box<dyn ServiceRepository>)
+            .map_err(|e| self.config.locale.format("Could not connect to {}: {}", &[&host.name, &e.to_string()]))
+    }
+
+    /// Parks the current host with its tab and service table, then shows the next reachable one.
+    pub fn next_host(&mut self, tab_index: usize, services: Vec<Service>) -> Option<HostSwitch> {
+        let candidates: Vec<usize> = (1..self.hosts.len())
+            .map(|offset| (self.selected + offset) % self.hosts.len())
+            .collect();
+        self.switch_to_first(candidates, tab_index, services)
+    }
+
+    /// Shows the host with the given name, "local" for this machine, if it is reachable.
+    pub fn switch_to(&mut self, name: &str, tab_index: usize, services: Vec<Service>) -> Option<HostSwitch> {
+        match self.hosts.iter().position(|host| host.name == name) {
+            Some(index) if index == self.selected => None,
+            Some(index) => self.switch_to_first(vec![index], tab_index, services),
+            None => {
+                self.sender
+                    .send(AppEvent::Error(self.config.locale.format("Unknown host \"{}\", not in the hosts setting.", &[name]).into()))
+                    .unwrap();
+                None
+            }
+        }
+    }
+
+    /// Switches to the first reachable host of the candidates, reporting the unreachable ones.
+    fn switch_to_first(&mut self, candidates: Vec<usize>, tab_index: usize, services: Vec<Service>) -> Option<HostSwitch> {
+        let mut errors = Vec::new();
+        let mut target = None;
+        for index in candidates {
+            let repository = match self.hosts[index].repository.take() {
+                Some(repository) => Ok(repository),
+                None => self.connect(index),
+            };
+            match repository {
+                Ok(repository) => {
+                    target = Some((index, repository));
+                    break;
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+
+        if !errors.is_empty() {
+            self.sender.send(AppEvent::Error(errors.join("\n").into())).unwrap();
+        }
+        let (index, repository) = target?;
+
+        let previous = self.usecase.borrow_mut().replace_repository(repository);
+        let current = &mut self.hosts[self.selected];
+        current.repository = Some(previous);
+        current.services = Some(services);
+        current.tab_index = tab_index;
+
+        self.selected = index;
+        let next = &mut self.hosts[index];
+        Some(HostSwitch {
+            tab_index: next.tab_index,
+            services: next.services.take(),
+        })
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.hosts.iter().map(|host| host.name.as_str()).collect()
+    }
+
+    /// Name of the host being shown, "local" for this machine.
+    pub fn current_name(&self) -> &str {
+        &self.hosts[self.selected].name
+    }
+
+    /// The theme with the accent of the host being shown, so that hosts cannot be mistaken for
+    /// one another, e.g. production for staging.
+    pub fn themed(&self, mut theme: Theme) -> Theme {
+        if let Some(accent) = self.hosts[self.selected].accent {
+            theme.accent = accent;
+            theme.border = accent;
+            theme.header = accent;
+        }
+        theme
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        vec![self.config.locale.format(
+            "Host {} of {}: {}",
+            &[
+                &(self.selected + 1).to_string(),
+                &self.hosts.len().to_string(),
+                &self.hosts[self.selected].name,
+            ],
+        )]
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let names: Vec<Line> = self
+            .hosts
+            .iter()
+            .map(|host| Line::from(host.name.clone()))
+            .collect();
+        let selected_style = Style::default().fg(self.hosts[self.selected].accent.unwrap_or(theme.accent));
+        let tabs = Tabs::new(names)
+            .select(self.selected)
+            .style(Style::default().fg(theme.muted))
+            .highlight_style(selected_style);
+
+        let width = self
+            .hosts
+            .iter()
+            .map(|host| host.name.chars().count() as u16 + 3)
+            .sum::<u16>()
+            .min(area.width);
+        let tabs_area = Rect::new(area.x + area.width - width, area.y, width, area.height);
+        frame.render_widget(tabs, tabs_area);
+    }
+}
@@ -0,0 +1,115 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Position, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::domain::service::Service;
+use crate::terminal::components::confirm::Confirmation;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+const HELP: &str = "Start: Enter | Cancel: Esc";
+
+/// Prompt for the instance string of the selected template, or of the template of the selected
+/// instance, starting e.g. getty@tty3.service for "tty3".
+pub struct InstancePrompt {
+    service: Service,
+    input: String,
+    error: Option<String>,
+    locale: Locale,
+}
+
+impl InstancePrompt {
+    pub fn new(service: Service, locale: Locale) -> Self {
+        Self {
+            service,
+            input: String::new(),
+            error: None,
+            locale,
+        }
+    }
+
+    /// The unit to start, valid once the prompt is confirmed.
+    pub fn instance_name(&self) -> Result<String, String> {
+        self.service.instance_name(&self.input)
+    }
+
+    /// Inserts pasted text, e.g. an escaped path.
+    pub fn on_paste(&mut self, text: &str) {
+        self.input.extend(text.trim().chars().filter(|c| !c.is_control()));
+    }
+
+    pub fn on_key_event(&mut self, key: KeyEvent) -> Confirmation {
+        self.error = None;
+        match key.code {
+            KeyCode::Esc => return Confirmation::Cancelled,
+            KeyCode::Enter => match self.instance_name() {
+                Ok(_) => return Confirmation::Confirmed,
+                Err(e) => self.error = Some(e),
+            },
+            KeyCode::Char(c) => self.input.push(c),
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            _ => {}
+        }
+        Confirmation::Pending
+    }
+
+    /// e.g. "Start an instance of getty@.service".
+    fn title(&self) -> String {
+        let template = if self.service.is_template() {
+            self.service.name().to_string()
+        } else {
+            self.service.template().unwrap_or_default()
+        };
+        self.locale.format("Start an instance of {}", &[&template])
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        let locale = self.locale;
+        let mut lines = vec![self.title(), locale.format("Instance: {}", &[&self.input])];
+        match (&self.error, self.instance_name()) {
+            (Some(error), _) => lines.push(format!("{}: {}", locale.tr("Error"), error)),
+            (None, Ok(name)) => lines.push(locale.format("Starts {}", &[&name])),
+            (None, Err(_)) => {}
+        }
+        lines.push(locale.tr(HELP).to_string());
+        lines
+    }
+
+    /// A box over the bottom of the list, showing the unit the instance string makes.
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if area.height < 3 {
+            return;
+        }
+
+        let prompt_area = Rect::new(area.x, area.bottom() - 3, area.width, 3);
+        let border = if self.error.is_some() { theme.error } else { theme.highlight_border };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border))
+            .title(format!(" {} ", self.title()))
+            .title_bottom(Line::from(format!(" {} ", self.locale.tr(HELP))).right_aligned());
+        let inner = block.inner(prompt_area);
+        frame.render_widget(Clear, prompt_area);
+        frame.render_widget(block, prompt_area);
+
+        let hint = match (&self.error, self.instance_name()) {
+            (Some(error), _) => Span::styled(format!("  {}", error), Style::default().fg(theme.error)),
+            (None, Ok(name)) => Span::styled(format!("  → {}", name), Style::default().fg(theme.muted)),
+            (None, Err(_)) => Span::raw(""),
+        };
+        let line = Line::from(vec![Span::styled(self.input.as_str(), Style::default().fg(theme.text)), hint]);
+        frame.render_widget(Paragraph::new(line), inner);
+        #[allow(clippy::cast_possible_truncation)]
+        frame.set_cursor_position(Position::new(
+            (inner.x + self.input.chars().count() as u16).min(inner.right().saturating_sub(1)),
+            inner.y,
+        ));
+    }
+}
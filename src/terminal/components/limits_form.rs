@@ -0,0 +1,187 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::domain::resource_limits::ResourceLimits;
+use crate::terminal::components::confirm::Confirmation;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+const SETTINGS: [&str; 3] = ["CPUQuota", "MemoryMax", "TasksMax"];
+const PLACEHOLDERS: [&str; 3] = ["e.g. 50%", "e.g. 512M", "e.g. 100"];
+const LABEL_WIDTH: usize = 12;
+const CURRENT_WIDTH: usize = 12;
+/// Index of the runtime/persistent choice, after the settings.
+const KEEP: usize = 3;
+
+/// Form setting the CPUQuota, MemoryMax and TasksMax of a unit, next to their current values.
+pub struct ResourceLimitsForm {
+    unit: String,
+    current: ResourceLimits,
+    values: [String; 3],
+    /// Until the next reboot, rather than in a persistent drop-in.
+    runtime: bool,
+    selected: usize,
+    error: Option<String>,
+    locale: Locale,
+}
+
+impl ResourceLimitsForm {
+    pub fn new(unit: &str, current: ResourceLimits, locale: Locale) -> Self {
+        Self {
+            unit: unit.to_string(),
+            current,
+            values: Default::default(),
+            runtime: true,
+            selected: 0,
+            error: None,
+            locale,
+        }
+    }
+
+    /// The limits typed, the empty ones left as they are.
+    pub fn limits(&self) -> Result<ResourceLimits, String> {
+        ResourceLimits::parse(&self.values[0], &self.values[1], &self.values[2])
+    }
+
+    pub fn runtime(&self) -> bool {
+        self.runtime
+    }
+
+    fn current(&self, index: usize) -> String {
+        self.current
+            .summary()
+            .into_iter()
+            .find(|(setting, _)| *setting == SETTINGS[index])
+            .map(|(_, value)| value)
+            .unwrap_or_default()
+    }
+
+    fn keep(&self) -> String {
+        format!("< {} >", self.locale.tr(if self.runtime { "until reboot" } else { "persistent" }))
+    }
+
+    pub fn on_key_event(&mut self, key: KeyEvent) -> Confirmation {
+        self.error = None;
+        match key.code {
+            KeyCode::Esc => return Confirmation::Cancelled,
+            KeyCode::Enter => match self.limits() {
+                Ok(limits) if limits.is_empty() => self.error = Some(self.locale.tr("Type at least one limit").to_string()),
+                Ok(_) => return Confirmation::Confirmed,
+                Err(e) => self.error = Some(e),
+            },
+            KeyCode::Tab | KeyCode::Down => self.selected = (self.selected + 1) % (KEEP + 1),
+            KeyCode::BackTab | KeyCode::Up => self.selected = self.selected.checked_sub(1).unwrap_or(KEEP),
+            KeyCode::Left | KeyCode::Right | KeyCode::Char(' ') if self.selected == KEEP => {
+                self.runtime = !self.runtime
+            }
+            KeyCode::Char(c) if self.selected < KEEP => self.values[self.selected].push(c),
+            KeyCode::Backspace if self.selected < KEEP => {
+                self.values[self.selected].pop();
+            }
+            _ => {}
+        }
+        Confirmation::Pending
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        let locale = self.locale;
+        let selected = |index: usize| if index == self.selected { locale.tr(" (selected)") } else { "" };
+        let mut lines = vec![locale.format("Resource limits of {}, empty ones are left as they are", &[&self.unit])];
+        lines.extend(SETTINGS.iter().enumerate().map(|(index, setting)| {
+            locale.format(
+                "{}{}: current {}, new {}",
+                &[setting, selected(index), &self.current(index), &self.values[index]],
+            )
+        }));
+        lines.push(format!("{}{}: {}", locale.tr("Keep"), selected(KEEP), self.keep()));
+        if let Some(error) = &self.error {
+            lines.push(format!("{}: {}", locale.tr("Error"), error));
+        }
+        lines.push(locale.shortcuts(&[
+            ("Next field", "Tab".to_string()),
+            ("Previous field", "Shift + Tab".to_string()),
+            ("Choose", "Left/Right".to_string()),
+            ("Set", "Enter".to_string()),
+            ("Cancel", "Esc".to_string()),
+        ]));
+        lines
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_width = std::cmp::min(70, area.width.saturating_sub(4));
+        let popup_height = std::cmp::min(12, area.height.saturating_sub(2));
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let locale = self.locale;
+        let label_style = |index: usize| {
+            if index == self.selected {
+                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.muted)
+            }
+        };
+        let mut text: Vec<Line> = vec![Line::from(Span::styled(
+            format!("{:<LABEL_WIDTH$}{:<CURRENT_WIDTH$}{}", "", locale.tr("Current"), locale.tr("New")),
+            Style::default().fg(theme.header).add_modifier(Modifier::BOLD),
+        ))];
+        text.extend(SETTINGS.iter().enumerate().map(|(index, setting)| {
+            let value = if self.values[index].is_empty() {
+                Span::styled(locale.tr(PLACEHOLDERS[index]), Style::default().fg(theme.muted))
+            } else {
+                Span::raw(self.values[index].clone())
+            };
+            Line::from(vec![
+                Span::styled(format!("{:<LABEL_WIDTH$}", setting), label_style(index)),
+                Span::raw(format!("{:<CURRENT_WIDTH$}", self.current(index))),
+                value,
+            ])
+        }));
+        text.push(Line::from(vec![
+            Span::styled(format!("{:<LABEL_WIDTH$}", locale.tr("Keep")), label_style(KEEP)),
+            Span::raw(self.keep()),
+        ]));
+        text.push(Line::from(""));
+        text.push(match &self.error {
+            Some(error) => Line::from(Span::styled(error.clone(), Style::default().fg(theme.error))),
+            None => Line::from(Span::styled(
+                locale.tr("Empty limits are left as they are, \"infinity\" removes one."),
+                Style::default().fg(theme.muted),
+            )),
+        });
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            locale.tr("Tab: next | ←/→: choose | Enter: set | Esc: cancel"),
+            Style::default().fg(theme.muted),
+        )));
+
+        let form = Paragraph::new(text).style(Style::default().fg(theme.text)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.highlight_border))
+                .title(format!(" {} ", locale.format("Resource limits of {}", &[&self.unit]))),
+        );
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(form, popup_area);
+
+        if self.selected < KEEP {
+            let column = LABEL_WIDTH + CURRENT_WIDTH + self.values[self.selected].chars().count();
+            let x = popup_area.x + 1 + u16::try_from(column).unwrap_or(u16::MAX);
+            // Below the header line.
+            let y = popup_area.y + 2 + self.selected as u16;
+            if x < popup_area.right().saturating_sub(1) {
+                frame.set_cursor_position(Position::new(x, y));
+            }
+        }
+    }
+}
@@ -1,176 +1,854 @@
+use crate::infrastructure::clipboard::copy_to_clipboard;
+use crate::usecases::action_queue::FinishedAction;
 use crate::usecases::services_manager::ServicesManager;
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::usecases::timings::Stage;
+use crossterm::event::KeyEvent;
 use ratatui::layout::Rect;
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::{
-    layout::Constraint,
     widgets::{Block, Borders, Cell, Row, Table, TableState, Padding},
     Frame,
 };
-use std::error::Error;
 use std::sync::mpsc::Sender;
 use std::rc::Rc;
 use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 
+use crate::config::{Column, Config, IconConfig};
+use crate::domain::action_plan::{ActionPlan, UnitAction};
+use crate::domain::health::Health;
+use crate::domain::humanize::now_usec;
+use crate::domain::runtime_stats::RuntimeStats;
 use crate::domain::service::Service;
+use crate::domain::service_query::ServiceQuery;
+use crate::domain::unit_capabilities::UnitCapabilities;
+use crate::domain::unit_clone::UnitClone;
+use crate::domain::unit_template::UnitTemplate;
+use crate::infrastructure::export::export_to_directory;
+use crate::infrastructure::pinned_store::{load_pinned, save_pinned};
+use crate::errors::{AppError, ServiceError};
 use crate::terminal::app::{Actions, AppEvent};
+use crate::terminal::components::batch::BatchOperation;
+use crate::terminal::components::confirm::{ConfirmPrompt, Confirmation};
+use crate::terminal::components::clone_unit::CloneUnitForm;
+use crate::terminal::components::instantiate::InstancePrompt;
+use crate::terminal::components::new_unit::NewUnitForm;
+use crate::terminal::components::undo::UndoPrompt;
+use crate::terminal::components::column_picker::ColumnPicker;
+use crate::terminal::components::transient_unit::TransientUnitForm;
+use crate::terminal::components::type_ahead::TypeAhead;
+use crate::terminal::components::preview::ActionPreview;
+use crate::terminal::components::quick_actions::{QuickAction, QuickActions};
+use crate::terminal::components::signal::SignalPicker;
+use crate::terminal::components::status_bar::ActionOutcome;
+use crate::terminal::fuzzy::{fuzzy_match, FuzzyMatch};
+use crate::terminal::keychord::{ChordAction, ChordStep, KeyChords};
+use crate::terminal::state_style::{active_icon, active_style, file_icon, file_style, load_style, with_icon};
+use crate::terminal::theme::Theme;
 
 const PADDING: Padding = Padding::new(1, 1, 1, 1);
+/// Frames of the spinner of the units with a queued action, one per `SPINNER_TICK`.
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const SPINNER_TICK: Duration = Duration::from_millis(100);
 
-fn generate_rows(services: &[Service]) -> Vec<Row<'static>> {
+/// Values of the columns that take more than the unit state, fetched only when shown.
+#[derive(Default)]
+struct ColumnData {
+    /// Units depending on each unit.
+    impact: HashMap<String, usize>,
+    /// Restarts and uptime of the running and failed services.
+    runtime: HashMap<String, RuntimeStats>,
+    /// Last answer of the health probe of each probed unit, updated as the probes run.
+    health: HashMap<String, Health>,
+    /// Action queued or running on each unit, shown instead of its state until it returns.
+    pending: HashMap<String, UnitAction>,
+}
+
+fn spinner_frame() -> &'static str {
+    SPINNER[(now_usec() / SPINNER_TICK.as_micros() as u64) as usize % SPINNER.len()]
+}
+
+#[allow(clippy::too_many_arguments)]
+fn generate_rows(
+    services: &[Service],
+    columns: &[Column],
+    marked: &HashSet<String>,
+    pinned: &[String],
+    watched: &HashSet<String>,
+    noisy_units: &HashSet<String>,
+    name_matches: &HashMap<String, Vec<usize>>,
+    grouped_instances: &HashSet<String>,
+    data: &ColumnData,
+    icons: &IconConfig,
+    theme: &Theme,
+) -> Vec<Row<'static>> {
     services
         .iter()
         .map(|service| {
             let highlight_style = Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD);
-            let normal_style = Style::default().fg(Color::Gray);
+            let normal_style = Style::default().fg(theme.text);
 
-            let state_style = match service.state().active() {
-                "active" => Style::default().fg(Color::Green),
-                "activating" => Style::default().fg(Color::Yellow),
-                _ => Style::default().fg(Color::Red),
+            let state = service.state();
+            let preset_style = if state.deviates_from_preset() {
+                Style::default().fg(theme.warning).add_modifier(Modifier::BOLD)
+            } else {
+                normal_style
             };
+            let unit_file_style = if state.deviates_from_preset() { preset_style } else { file_style(state.file(), theme) };
 
-            Row::new(vec![
-                Cell::from(service.formatted_name().to_string()).style(highlight_style),
-                Cell::from(format!(
-                    "{} ({})",
-                    service.state().active(),
-                    service.state().sub()
-                ))
-                .style(state_style),
-                Cell::from(service.state().file().to_string()).style(normal_style),
-                Cell::from(service.state().load().to_string()).style(normal_style),
-                Cell::from(service.description().to_string()).style(normal_style),
-            ])
+            let row_style = if marked.contains(service.name()) {
+                Style::default().bg(theme.inactive_selection_bg)
+            } else {
+                Style::default()
+            };
+
+            let matched_style = highlight_style.fg(theme.warning).add_modifier(Modifier::UNDERLINED);
+
+            Row::new(columns.iter().map(|column| match column {
+                Column::Name => {
+                    let positions = name_matches.get(service.name()).map_or(&[][..], Vec::as_slice);
+                    let mut spans = highlighted_spans(service.formatted_name(), positions, highlight_style, matched_style);
+                    if pinned.iter().any(|unit| unit == service.name()) {
+                        spans.insert(0, Span::styled("★ ", Style::default().fg(theme.accent)));
+                    } else if grouped_instances.contains(service.name()) {
+                        spans.insert(0, Span::styled("  └ ", Style::default().fg(theme.muted)));
+                    }
+                    if watched.contains(service.name()) {
+                        spans.push(Span::styled(" ◉", Style::default().fg(theme.accent)));
+                    }
+                    if noisy_units.contains(service.name()) {
+                        spans.push(Span::styled(" ⚠", Style::default().fg(theme.warning)));
+                    }
+                    if state.need_daemon_reload() {
+                        spans.push(Span::styled(" ↻", Style::default().fg(theme.warning)));
+                    }
+                    if !service.aliases().is_empty() {
+                        spans.push(Span::styled(" ⇄", Style::default().fg(theme.muted)));
+                    }
+                    Cell::from(Line::from(spans))
+                }
+                Column::Active if data.pending.contains_key(service.name()) => {
+                    Cell::from(format!("{} {}", spinner_frame(), column_text(service, column, data)))
+                        .style(Style::default().fg(theme.accent))
+                }
+                Column::Active => Cell::from(with_icon(active_icon(state, icons), column_text(service, column, data)))
+                    .style(active_style(state, theme)),
+                Column::State => Cell::from(with_icon(file_icon(state.file(), icons), state.file().to_string()))
+                    .style(unit_file_style),
+                Column::Preset => {
+                    let preset = service.state().preset();
+                    if service.state().deviates_from_preset() {
+                        Cell::from(format!("≠ {}", preset)).style(preset_style)
+                    } else {
+                        Cell::from(preset.to_string()).style(normal_style)
+                    }
+                }
+                Column::Load => Cell::from(state.load().to_string()).style(load_style(state.load(), theme)),
+                Column::Sub => Cell::from(state.sub().to_string()).style(active_style(state, theme)),
+                Column::Description => Cell::from(service.description().to_string()).style(normal_style),
+                Column::Impact | Column::Uptime | Column::Memory | Column::Source => Cell::from(column_text(service, column, data)).style(normal_style),
+                Column::Restarts => {
+                    let restarts = data.runtime.get(service.name()).map_or(0, RuntimeStats::n_restarts);
+                    let style = if restarts > 0 { Style::default().fg(theme.warning) } else { normal_style };
+                    Cell::from(column_text(service, column, data)).style(style)
+                }
+                Column::Health => {
+                    let style = match data.health.get(service.name()) {
+                        Some(Health::Ok) => Style::default().fg(theme.active),
+                        Some(Health::Degraded) => Style::default().fg(theme.warning),
+                        Some(Health::Down) => Style::default().fg(theme.failed),
+                        None => normal_style,
+                    };
+                    Cell::from(column_text(service, column, data)).style(style)
+                }
+            }))
+            .style(row_style)
         })
         .collect()
 }
 
+/// Direction of a sorted column: the impact, restarts and memory columns put the highest first,
+/// the uptime one the most recently started.
+fn sort_arrow(column: &Column) -> &'static str {
+    match column {
+        Column::Impact | Column::Restarts | Column::Memory => "▼",
+        _ => "▲",
+    }
+}
+
+/// Splits a text into spans, styling the characters at `positions` with `matched`.
+fn highlighted_spans(text: &str, positions: &[usize], normal: Style, matched: Style) -> Vec<Span<'static>> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut current = String::new();
+    let mut current_matched = false;
+    for (index, c) in text.chars().enumerate() {
+        let is_matched = positions.contains(&index);
+        if is_matched != current_matched && !current.is_empty() {
+            let style = if current_matched { matched } else { normal };
+            spans.push(Span::styled(std::mem::take(&mut current), style));
+        }
+        current_matched = is_matched;
+        current.push(c);
+    }
+    if !current.is_empty() {
+        let style = if current_matched { matched } else { normal };
+        spans.push(Span::styled(current, style));
+    }
+    spans
+}
+
+fn column_text(service: &Service, column: &Column, data: &ColumnData) -> String {
+    let state = service.state();
+    match column {
+        Column::Name => service.formatted_name().to_string(),
+        Column::Active if data.pending.contains_key(service.name()) => data.pending[service.name()].in_progress().to_string(),
+        Column::Active if state.is_frozen() => format!("{} ({}, {})", state.active(), state.sub(), state.freezer()),
+        Column::Active => format!("{} ({})", state.active(), state.sub()),
+        Column::State => state.file().to_string(),
+        Column::Preset if state.deviates_from_preset() => format!("{}, differs from the state", state.preset()),
+        Column::Preset => state.preset().to_string(),
+        Column::Load => state.load().to_string(),
+        Column::Description => service.description().to_string(),
+        Column::Impact => data.impact.get(service.name()).copied().unwrap_or(0).to_string(),
+        Column::Uptime => data
+            .runtime
+            .get(service.name())
+            .map_or("-".to_string(), |stats| stats.formatted_uptime(now_usec())),
+        Column::Restarts => data
+            .runtime
+            .get(service.name())
+            .map_or("-".to_string(), |stats| stats.n_restarts().to_string()),
+        Column::Source => service.source().unwrap_or("-").to_string(),
+        Column::Sub => state.sub().to_string(),
+        Column::Memory => data
+            .runtime
+            .get(service.name())
+            .map_or("-".to_string(), RuntimeStats::formatted_memory),
+        Column::Health => data.health.get(service.name()).map_or("", Health::label).to_string(),
+    }
+}
+
+/// Runs a unit action, returning the queued job when there is one to track.
+fn run_action(usecase: &ServicesManager, action: ServiceAction, service: &Service) -> Result<Option<String>, ServiceError> {
+    match action {
+        ServiceAction::Start => usecase.start_service(service),
+        ServiceAction::Stop => usecase.stop_service(service),
+        ServiceAction::Restart => usecase.restart_service(service),
+        ServiceAction::Reload => usecase.reload_service(service),
+        ServiceAction::ReloadOrRestart => usecase.reload_or_restart_service(service),
+        ServiceAction::Enable => usecase.enable_service(service).map(|_| None),
+        ServiceAction::Disable => usecase.disable_service(service).map(|_| None),
+        ServiceAction::Freeze => usecase.freeze_service(service).map(|_| None),
+        ServiceAction::Thaw => usecase.thaw_service(service).map(|_| None),
+        ServiceAction::RefreshAll => Ok(None),
+    }
+}
+
+/// Tells the status bar about an action, so it can follow its job until it finishes.
+fn report_action(sender: &Sender<AppEvent>, action: ServiceAction, unit: &str, result: &Result<Option<String>, String>) {
+    let description = format!("{} of {}", action.label().to_lowercase(), unit);
+    let outcome = match result {
+        Ok(Some(job)) => ActionOutcome::Queued(job.clone()),
+        Ok(None) => ActionOutcome::Completed,
+        Err(_) => ActionOutcome::Failed,
+    };
+    sender
+        .send(AppEvent::Action(Actions::ActionPerformed(description, outcome)))
+        .unwrap();
+}
+
+#[derive(Clone, Copy, PartialEq)]
 pub enum ServiceAction {
     Start,
     Stop,
     Restart,
+    Reload,
+    ReloadOrRestart,
     Enable,
     Disable,
+    Freeze,
+    Thaw,
     RefreshAll,
 }
+
+impl ServiceAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ServiceAction::Start => "Start",
+            ServiceAction::Stop => "Stop",
+            ServiceAction::Restart => "Restart",
+            ServiceAction::Reload => "Reload",
+            ServiceAction::ReloadOrRestart => "Reload or restart",
+            ServiceAction::Enable => "Enable",
+            ServiceAction::Disable => "Disable",
+            ServiceAction::Freeze => "Freeze",
+            ServiceAction::Thaw => "Thaw",
+            ServiceAction::RefreshAll => "Refresh",
+        }
+    }
+
+    /// The action of the list running a unit action, the inverse of `unit_action`.
+    fn of(action: UnitAction) -> Self {
+        match action {
+            UnitAction::Start => ServiceAction::Start,
+            UnitAction::Stop => ServiceAction::Stop,
+            UnitAction::Restart => ServiceAction::Restart,
+            UnitAction::Reload => ServiceAction::Reload,
+            UnitAction::ReloadOrRestart => ServiceAction::ReloadOrRestart,
+            UnitAction::Enable => ServiceAction::Enable,
+            UnitAction::Disable => ServiceAction::Disable,
+            UnitAction::Freeze => ServiceAction::Freeze,
+            UnitAction::Thaw => ServiceAction::Thaw,
+        }
+    }
+
+    pub fn unit_action(&self) -> Option<UnitAction> {
+        match self {
+            ServiceAction::Start => Some(UnitAction::Start),
+            ServiceAction::Stop => Some(UnitAction::Stop),
+            ServiceAction::Restart => Some(UnitAction::Restart),
+            ServiceAction::Reload => Some(UnitAction::Reload),
+            ServiceAction::ReloadOrRestart => Some(UnitAction::ReloadOrRestart),
+            ServiceAction::Enable => Some(UnitAction::Enable),
+            ServiceAction::Disable => Some(UnitAction::Disable),
+            ServiceAction::Freeze => Some(UnitAction::Freeze),
+            ServiceAction::Thaw => Some(UnitAction::Thaw),
+            ServiceAction::RefreshAll => None,
+        }
+    }
+
+    /// Actions run by the queue of the manager, the list answering keys meanwhile.
+    fn is_queued(&self) -> bool {
+        matches!(
+            self,
+            ServiceAction::Start
+                | ServiceAction::Stop
+                | ServiceAction::Restart
+                | ServiceAction::Reload
+                | ServiceAction::ReloadOrRestart
+        )
+    }
+}
  
 pub struct TableServices {
     table: Table<'static>,
     pub table_state: TableState,
-    pub rows: Vec<Row<'static>>,
     pub services: Vec<Service>,
     filtered_services: Vec<Service>,
+    /// Rows of `filtered_services` last drawn, whose state was read again, see `check_shown_rows`.
+    shown_rows: Option<(usize, usize)>,
+    load_error: bool,
+    marked: HashSet<String>,
+    noisy_units: HashSet<String>,
+    /// Characters of each unit name matched by the filter, as char indices in the formatted name.
+    name_matches: HashMap<String, Vec<usize>>,
+    /// Instances listed right under their template, drawn indented.
+    grouped_instances: HashSet<String>,
+    /// Units kept at the top of the list whatever the filter, in the order they were pinned.
+    pinned: Vec<String>,
+    column_data: ColumnData,
+    /// Columns shown, those of the config until picked otherwise.
+    columns: Vec<Column>,
+    /// Columns scrolled past on the left, the first column staying in place.
+    column_offset: usize,
+    column_picker: Option<ColumnPicker>,
+    /// Column the list is sorted by, instead of the order of the service manager.
+    sort_column: Option<Column>,
+    /// The last filter and the indices in `services` of the units it matched, narrowed as the
+    /// filter is typed further instead of matching every unit again. Cleared with the services.
+    filter_index: Option<(ServiceQuery, Vec<usize>)>,
+    batch: Option<BatchOperation>,
+    confirm: Option<ConfirmPrompt>,
+    preview: Option<ActionPreview>,
+    /// Units of an ordered batch awaiting its preview or confirmation, acted on instead of the
+    /// marked ones.
+    ordered: Option<Vec<Service>>,
+    signal_picker: Option<SignalPicker>,
+    quick_actions: Option<QuickActions>,
+    new_unit: Option<NewUnitForm>,
+    transient_unit: Option<TransientUnitForm>,
+    clone_unit: Option<CloneUnitForm>,
+    instance_prompt: Option<InstancePrompt>,
+    undo: Option<UndoPrompt>,
+    type_ahead: Option<TypeAhead>,
+    chords: KeyChords,
+    dry_run: bool,
     old_filter_text: String,
     pub ignore_key_events: bool,
     sender: Sender<AppEvent>,
     usecase: Rc<RefCell<ServicesManager>>,
+    config: Rc<Config>,
 }
 
 impl TableServices {
-    pub fn new(sender: Sender<AppEvent>,  usecase: Rc<RefCell<ServicesManager>>) -> Self {
-        let (services, rows) = match usecase.borrow().list_services() {
-            Ok(svcs) => {
-                let rows = generate_rows(&svcs);
-                (svcs, rows)
-            }
-            Err(_) => {
-                let error_row = Row::new(vec!["Error loading services", "", "", "", ""]);
-                (vec![], vec![error_row])
-            }
+    pub fn new(sender: Sender<AppEvent>,  usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
+        let (services, load_error) = match usecase.borrow().list_services() {
+            Ok(svcs) => (svcs, false),
+            Err(_) => (vec![], true),
         };
 
+        let pinned = load_pinned().unwrap_or_else(|e| {
+            sender.send(AppEvent::Error(e.into())).unwrap();
+            Vec::new()
+        });
+
         let mut table_state = TableState::default();
         table_state.select(Some(0));
-        let table = Table::new(
-            rows.clone(),
-            [
-                Constraint::Percentage(15),
-                Constraint::Length(20),
-                Constraint::Length(10),
-                Constraint::Length(10),
-                Constraint::Min(0),
-            ],
-        )
-        .header(
-            Row::new(["Name", "Active", "State", "Load", "Description"]).style(
-                Style::default()
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD),
-            ),
-        )
-        .block( 
-            Block::default()
-                .borders(Borders::NONE)
-                .padding(PADDING),
-        )
-        .row_highlight_style(
-            Style::default()
-                .bg(Color::Blue)
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
-        )
-        .highlight_symbol(">> ");
-        Self {
+        let table = Table::default()
+            .block( 
+                Block::default()
+                    .borders(Borders::NONE)
+                    .padding(PADDING),
+            )
+            .highlight_symbol(">> ");
+        let mut table_services = Self {
             table,
             table_state,
-            rows,
             filtered_services: services.clone(),
+            shown_rows: None,
             services,
+            load_error,
+            marked: HashSet::new(),
+            noisy_units: HashSet::new(),
+            name_matches: HashMap::new(),
+            grouped_instances: HashSet::new(),
+            pinned,
+            column_data: ColumnData::default(),
+            columns: config.columns.clone(),
+            column_offset: 0,
+            column_picker: None,
+            sort_column: None,
+            filter_index: None,
+            batch: None,
+            confirm: None,
+            preview: None,
+            ordered: None,
+            signal_picker: None,
+            quick_actions: None,
+            new_unit: None,
+            transient_unit: None,
+            clone_unit: None,
+            instance_prompt: None,
+            undo: None,
+            type_ahead: None,
+            chords: KeyChords::new(Duration::from_millis(config.chords.timeout)),
+            dry_run: config.dry_run,
             sender,
             old_filter_text: String::new(),
             ignore_key_events: false,
             usecase,
+            config,
+        };
+        table_services.update_column_data();
+        if !table_services.config.default_filter.is_empty() {
+            table_services.refresh(table_services.config.default_filter.clone());
         }
+        table_services
     }
 
-    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
-        frame.render_stateful_widget(&self.table, area, &mut self.table_state);
+    /// First row shown so that the selection stays visible in `height` rows, scrolling as
+    /// little as possible from the rows shown last.
+    fn window_start(&self, height: usize) -> usize {
+        let len = self.filtered_services.len();
+        let selected = self.table_state.selected().unwrap_or(0).min(len.saturating_sub(1));
+        let mut start = self.table_state.offset();
+        if selected < start {
+            start = selected;
+        } else if selected >= start + height {
+            start = selected + 1 - height;
+        }
+        start.min(len.saturating_sub(height))
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let columns = self.visible_columns();
+        let columns = &columns;
+        // Only the rows of the viewport are built, the list having thousands of units on some hosts.
+        let height = area.height.saturating_sub(PADDING.top + PADDING.bottom + 1).max(1) as usize;
+        let start = self.window_start(height);
+        let end = (start + height).min(self.filtered_services.len());
+        if self.shown_rows != Some((start, end)) {
+            self.shown_rows = Some((start, end));
+            self.sender.send(AppEvent::Action(Actions::CheckShownRows)).unwrap();
+        }
+        let rows = if self.load_error {
+            let mut cells = vec![""; columns.len()];
+            cells[0] = self.config.locale.tr("Error loading services");
+            vec![Row::new(cells).style(Style::default().fg(theme.error))]
+        } else {
+            generate_rows(
+                &self.filtered_services[start..end],
+                columns,
+                &self.marked,
+                &self.pinned,
+                &self.usecase.borrow().watched_units().into_iter().collect(),
+                &self.noisy_units,
+                &self.name_matches,
+                &self.grouped_instances,
+                &self.column_data,
+                &self.config.icons,
+                theme,
+            )
+        };
+        let selection_bg = if self.ignore_key_events {
+            theme.inactive_selection_bg
+        } else {
+            theme.selection_bg
+        };
+
+        let table = self
+            .table
+            .clone()
+            .rows(rows)
+            .widths(columns.iter().map(|column| self.config.column_constraint(column)))
+            .header(
+                Row::new(columns.iter().enumerate().map(|(index, column)| {
+                    let name = self.config.locale.tr(column.title());
+                    let title = match self.sort_column {
+                        Some(sorted) if sorted == *column => format!("{} {}", name, sort_arrow(column)),
+                        _ => name.to_string(),
+                    };
+                    // Columns are hidden on the left.
+                    if index == 1 && self.column_offset > 0 { format!("◀ {}", title) } else { title }
+                }))
+                .style(
+                    Style::default()
+                        .fg(theme.header)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            )
+            .row_highlight_style(
+                Style::default()
+                    .bg(selection_bg)
+                    .fg(theme.selection_fg)
+                    .add_modifier(Modifier::BOLD),
+            );
+        let mut window_state =
+            TableState::default().with_selected(self.table_state.selected().map(|selected| selected.saturating_sub(start)));
+        frame.render_stateful_widget(table, area, &mut window_state);
+        *self.table_state.offset_mut() = start;
+
+        if let Some(batch) = &self.batch {
+            batch.render(frame, area, theme);
+        }
+        if let Some(preview) = &self.preview {
+            preview.render(frame, area, theme);
+        }
+        if let Some(confirm) = &self.confirm {
+            confirm.render(frame, area, theme);
+        }
+        if let Some(signal_picker) = &self.signal_picker {
+            signal_picker.render(frame, area, theme);
+        }
+        if let Some(quick_actions) = &self.quick_actions {
+            quick_actions.render(frame, area, theme);
+        }
+        if let Some(new_unit) = &self.new_unit {
+            new_unit.render(frame, area, theme);
+        }
+        if let Some(transient_unit) = &self.transient_unit {
+            transient_unit.render(frame, area, theme);
+        }
+        if let Some(clone_unit) = &self.clone_unit {
+            clone_unit.render(frame, area, theme);
+        }
+        if let Some(instance_prompt) = &self.instance_prompt {
+            instance_prompt.render(frame, area, theme);
+        }
+        if let Some(undo) = &self.undo {
+            undo.render(frame, area, theme);
+        }
+        if let Some(type_ahead) = &self.type_ahead {
+            type_ahead.render(frame, area, theme);
+        }
+        if let Some(column_picker) = &self.column_picker {
+            column_picker.render(frame, area, theme);
+        }
+    }
+
+    /// The first column, kept when scrolling horizontally, then the columns past the offset.
+    fn visible_columns(&self) -> Vec<Column> {
+        let mut columns = self.columns.clone();
+        if columns.len() > 1 {
+            columns.drain(1..1 + self.column_offset.min(columns.len() - 2));
+        }
+        columns
+    }
+
+    /// Scrolls the columns after the first one, for terminals too narrow to show them all.
+    fn scroll_columns(&mut self, right: bool) {
+        let max = self.columns.len().saturating_sub(2);
+        self.column_offset = if right { (self.column_offset + 1).min(max) } else { self.column_offset.saturating_sub(1) };
+    }
+
+    fn apply_columns(&mut self, columns: Vec<Column>) {
+        self.columns = columns;
+        self.column_offset = 0;
+        if self.sort_column.is_some_and(|sorted| !self.columns.contains(&sorted)) {
+            self.sort_column = None;
+        }
+        self.update_column_data();
+        self.refresh(self.old_filter_text.clone());
+    }
+
+    /// The services from the selected one onwards, one labelled line each, or the open popup.
+    pub fn linear_text(&self) -> Vec<String> {
+        if let Some(new_unit) = &self.new_unit {
+            return new_unit.linear_text();
+        }
+        if let Some(transient_unit) = &self.transient_unit {
+            return transient_unit.linear_text();
+        }
+        if let Some(clone_unit) = &self.clone_unit {
+            return clone_unit.linear_text();
+        }
+        if let Some(instance_prompt) = &self.instance_prompt {
+            return instance_prompt.linear_text();
+        }
+        if let Some(undo) = &self.undo {
+            return undo.linear_text();
+        }
+        if let Some(signal_picker) = &self.signal_picker {
+            return signal_picker.linear_text();
+        }
+        if let Some(column_picker) = &self.column_picker {
+            return column_picker.linear_text();
+        }
+        if let Some(type_ahead) = &self.type_ahead {
+            let mut lines = type_ahead.linear_text();
+            if let Some(service) = self.get_selected_service() {
+                lines.insert(1, self.config.locale.format("Selected: {}", &[service.name()]));
+            }
+            return lines;
+        }
+        if let Some(quick_actions) = &self.quick_actions {
+            return quick_actions.linear_text();
+        }
+        if let Some(confirm) = &self.confirm {
+            return confirm.linear_text();
+        }
+        if let Some(preview) = &self.preview {
+            return preview.linear_text();
+        }
+        if let Some(batch) = &self.batch {
+            return batch.linear_text();
+        }
+        if self.load_error {
+            return vec![self.config.locale.tr("Error loading services").to_string()];
+        }
+        if self.filtered_services.is_empty() {
+            return vec![self.config.locale.tr("No services match the filter").to_string()];
+        }
+
+        let total = self.filtered_services.len();
+        let selected = self.table_state.selected().unwrap_or(0).min(total - 1);
+        self.filtered_services
+            .iter()
+            .enumerate()
+            .skip(selected)
+            .map(|(index, service)| {
+                let mut flags = Vec::new();
+                if index == selected {
+                    flags.push("selected");
+                }
+                if self.pinned.iter().any(|unit| unit == service.name()) {
+                    flags.push("pinned");
+                }
+                if self.marked.contains(service.name()) {
+                    flags.push("marked");
+                }
+                if self.usecase.borrow().is_watched(service.name()) {
+                    flags.push("watched");
+                }
+                if self.noisy_units.contains(service.name()) {
+                    flags.push("noisy logs");
+                }
+                if service.state().need_daemon_reload() {
+                    flags.push("unit file changed on disk");
+                }
+                let aliases = self.config.locale.format("also named {}", &[&service.aliases().join(", ")]);
+                if !service.aliases().is_empty() {
+                    flags.push(&aliases);
+                }
+                let flags = if flags.is_empty() {
+                    String::new()
+                } else {
+                    let flags: Vec<&str> = flags.into_iter().map(|flag| self.config.locale.tr(flag)).collect();
+                    format!(" ({})", flags.join(", "))
+                };
+                let columns = self
+                    .columns
+                    .iter()
+                    .map(|column| {
+                        format!(
+                            "{}: {}",
+                            self.config.locale.tr(column.title()),
+                            column_text(service, column, &self.column_data)
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                self.config.locale.format(
+                    "Service {} of {}{}: {}",
+                    &[&(index + 1).to_string(), &total.to_string(), &flags, &columns],
+                )
+            })
+            .collect()
+    }
+
+    /// Units flagged as writing excessive logs.
+    pub fn set_noisy_units(&mut self, noisy_units: HashSet<String>) {
+        self.noisy_units = noisy_units;
+    }
+
+    pub fn has_popup(&self) -> bool {
+        self.batch.is_some()
+            || self.confirm.is_some()
+            || self.preview.is_some()
+            || self.signal_picker.is_some()
+            || self.quick_actions.is_some()
+            || self.new_unit.is_some()
+            || self.transient_unit.is_some()
+            || self.clone_unit.is_some()
+            || self.instance_prompt.is_some()
+            || self.undo.is_some()
+            || self.type_ahead.is_some()
+            || self.column_picker.is_some()
+    }
+
+    /// Opens the new unit form on the built-in and configured templates, with the install target
+    /// of session units when `session` is set.
+    pub fn open_new_unit(&mut self, session: bool) {
+        let mut templates = UnitTemplate::library();
+        templates.extend(self.config.templates.iter().cloned());
+        self.new_unit = Some(NewUnitForm::new(templates, session, self.config.locale));
+    }
+
+    pub fn on_paste(&mut self, text: &str) {
+        if let Some(new_unit) = &mut self.new_unit {
+            new_unit.on_paste(text);
+        }
+        if let Some(transient_unit) = &mut self.transient_unit {
+            transient_unit.on_paste(text);
+        }
+        if let Some(clone_unit) = &mut self.clone_unit {
+            clone_unit.on_paste(text);
+        }
+        if let Some(instance_prompt) = &mut self.instance_prompt {
+            instance_prompt.on_paste(text);
+        }
     }
 
     pub fn set_usecase(&mut self, usecase: Rc<RefCell<ServicesManager>>) {
         self.usecase = usecase;
-        self.table = self.table.clone().block( 
-            Block::default()
-                .borders(Borders::NONE)
-                .padding(PADDING),
-        );
-        self.rows.clear();
+        self.marked.clear();
         self.table_state.select(Some(0));
         self.services.clear();
+        self.filter_index = None;
         self.filtered_services.clear();
+        self.shown_rows = None;
         self.fetch_and_refresh(self.old_filter_text.clone());
     }
 
+    /// Shows a previously fetched service list, e.g. the cached table of another host.
+    pub fn restore_services(&mut self, services: Vec<Service>) {
+        self.marked.clear();
+        self.table_state.select(Some(0));
+        self.services = services;
+        self.filter_index = None;
+        self.load_error = false;
+        self.refresh(self.old_filter_text.clone());
+    }
+
     pub fn set_ignore_key_events(&mut self, has_ignore_key_events: bool) {
+        self.ignore_key_events = has_ignore_key_events;
         if has_ignore_key_events {
-            self.table = self.table.clone().row_highlight_style(
-                Style::default()
-                    .bg(Color::DarkGray)
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD),
-            );
-        } else {
-            self.table = self.table.clone().row_highlight_style(
-                Style::default()
-                    .bg(Color::Blue)
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD),
-            );
+            self.chords.clear();
+        }
+    }
+
+    /// Reports a queued action that returned and fetches the services again, the unit leaving
+    /// its pending state.
+    pub fn action_finished(&mut self, finished: FinishedAction) {
+        self.column_data.pending = self.usecase.borrow().pending_actions();
+        let reported = finished.result.clone().map_err(|e| self.config.locale.tr(AppError::from(e).explanation()).to_string());
+        report_action(&self.sender, ServiceAction::of(finished.action), &finished.unit, &reported);
+        self.handle_result(finished.result.map(|_| ()));
+        self.fetch_and_refresh(self.old_filter_text.clone());
+    }
+
+    /// How often to draw again while a spinner turns, if one does.
+    pub fn spinner_timeout(&self) -> Option<Duration> {
+        (!self.column_data.pending.is_empty()).then_some(SPINNER_TICK)
+    }
+
+    /// How long to wait for the next key of a pending chord, if any.
+    pub fn chord_timeout(&self) -> Option<Duration> {
+        self.chords.is_pending().then(|| self.chords.remaining())
+    }
+
+    /// Handles the keys of a chord left unfinished as single keys once it timed out.
+    pub fn expire_chord(&mut self) {
+        for key in self.chords.expire() {
+            self.on_single_key(key);
         }
-        self.ignore_key_events = has_ignore_key_events
+    }
+
+    /// The selected service in plain words, e.g. "nginx.service, 5 of 9: active (running), enabled".
+    pub fn announcement(&self) -> Option<String> {
+        let index = self.table_state.selected()?;
+        let service = self.filtered_services.get(index)?;
+        let state = service.state();
+        let mut text = self.config.locale.format(
+            "{}, {} of {}: {} ({}), {}",
+            &[
+                service.name(),
+                &(index + 1).to_string(),
+                &self.filtered_services.len().to_string(),
+                state.active(),
+                state.sub(),
+                state.file(),
+            ],
+        );
+        if self.marked.contains(service.name()) {
+            text.push_str(&format!(", {}", self.config.locale.tr("marked")));
+        }
+        Some(text)
     }
 
     pub fn get_selected_service(&self) -> Option<&Service> {
-        if let Some(selected_index) = self.table_state.selected() {
-            if let Some(service) = self.filtered_services.get(selected_index) {
-                return Some(service);
+        self.table_state
+            .selected()
+            .and_then(|selected_index| self.filtered_services.get(selected_index))
+    }
+
+    /// Copies the name of the selected unit, to paste it in another terminal.
+    fn copy_name(&self) {
+        let Some(service) = self.get_selected_service() else {
+            return;
+        };
+        let event = match copy_to_clipboard(service.name()) {
+            Ok(()) => AppEvent::Notice(self.config.locale.format("Copied {} to the clipboard", &[service.name()])),
+            Err(e) => {
+                AppEvent::Error(self.config.locale.format("Could not copy to the clipboard: {}", &[&e.to_string()]).into())
             }
+        };
+        self.sender.send(event).unwrap();
+    }
+
+    /// Selects the service with the given unit name, returning false when it is not listed.
+    pub fn select_by_name(&mut self, name: &str) -> bool {
+        match self.filtered_services.iter().position(|service| service.name() == name) {
+            Some(index) => {
+                self.table_state.select(Some(index));
+                true
+            }
+            None => false,
         }
-        None
     }
 
     pub fn set_selected_index(&mut self, index: usize) {
@@ -179,66 +857,580 @@ impl TableServices {
 
     pub fn refresh(&mut self, filter_text: String) {
         self.old_filter_text = filter_text.clone();
-        self.filtered_services = self.filter(filter_text, self.services.clone());
-        self.rows = generate_rows(&self.filtered_services.clone());
-        self.table = self.table.clone().rows(self.rows.clone());
+        self.filter(&filter_text);
+    }
+
+    /// Reads again, for the rows drawn only, what listing every unit would cost a call per unit
+    /// for: whether their unit files changed on disk and, for units with processes, their
+    /// freezer state.
+    pub fn check_shown_rows(&mut self) {
+        let Some((start, end)) = self.shown_rows else {
+            return;
+        };
+        let usecase = self.usecase.borrow();
+        let states: HashMap<String, (Option<bool>, Option<String>)> = self
+            .filtered_services
+            .get(start..end)
+            .unwrap_or_default()
+            .iter()
+            .map(|service| {
+                let need_daemon_reload = usecase.get_need_daemon_reload(service).ok();
+                // Units without processes have nothing to freeze, the listing leaves them empty.
+                let freezer = if service.state().freezer().is_empty() {
+                    None
+                } else {
+                    usecase.get_freezer_state(service).ok()
+                };
+                (service.name().to_string(), (need_daemon_reload, freezer))
+            })
+            .collect();
+        drop(usecase);
+        for service in self.services.iter_mut().chain(self.filtered_services.iter_mut()) {
+            let Some((need_daemon_reload, freezer)) = states.get(service.name()) else {
+                continue;
+            };
+            if let Some(need_daemon_reload) = need_daemon_reload {
+                service.state_mut().set_need_daemon_reload(*need_daemon_reload);
+            }
+            if let Some(freezer) = freezer {
+                service.state_mut().set_freezer(freezer.clone());
+            }
+        }
     }
 
     fn fetch_services(&mut self) {
-        if let Ok(services) = self.usecase.borrow().list_services() {
-            self.services = services
-        } else {
-            self.services = vec![]
+        self.filter_index = None;
+        match self.usecase.borrow().list_services() {
+            Ok(services) => {
+                self.services = services;
+                self.load_error = false;
+            }
+            // The previous list stays, the manager may only be slow.
+            Err(e @ (ServiceError::Timeout(_) | ServiceError::Cancelled(_))) => {
+                self.sender.send(AppEvent::Error(e.into())).unwrap();
+            }
+            Err(_) => {
+                self.services = vec![];
+                self.load_error = true;
+            }
+        }
+        self.update_column_data();
+        self.sender.send(AppEvent::Action(Actions::ServicesFetched)).unwrap();
+    }
+
+    fn update_column_data(&mut self) {
+        let columns = &self.columns;
+        if columns.contains(&Column::Impact) {
+            self.column_data.impact = self.usecase.borrow().impact_scores(&self.services);
+        }
+        if [Column::Uptime, Column::Restarts, Column::Memory].iter().any(|column| columns.contains(column)) {
+            self.column_data.runtime = self.usecase.borrow().runtime_stats(&self.services);
         }
     }
 
+    /// Sorts by the next column, then goes back to the order of the service manager.
+    fn cycle_sort(&mut self) {
+        let columns = &self.columns;
+        self.sort_column = match self.sort_column {
+            None => columns.first().copied(),
+            Some(current) => columns
+                .iter()
+                .position(|column| *column == current)
+                .and_then(|index| columns.get(index + 1))
+                .copied(),
+        };
+        self.refresh(self.old_filter_text.clone());
+    }
+
+    /// Sorts by the given column, or in the order of the service manager for None.
+    pub fn sort_by(&mut self, column: Option<Column>) {
+        self.sort_column = column;
+        self.refresh(self.old_filter_text.clone());
+    }
+
+    /// Records the answer of the health probe of a unit for the Health column.
+    pub fn set_health(&mut self, unit: String, health: Health) {
+        self.column_data.health.insert(unit, health);
+    }
+
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    pub fn sort_column(&self) -> Option<Column> {
+        self.sort_column
+    }
+
+    /// First row shown, kept with the session.
+    pub fn offset(&self) -> usize {
+        self.table_state.offset()
+    }
+
+    pub fn set_offset(&mut self, offset: usize) {
+        *self.table_state.offset_mut() = offset;
+    }
+
+    /// Shows the columns of a profile sorted by `sort`, e.g. when switching profiles.
+    pub fn set_layout(&mut self, columns: Vec<Column>, sort: Option<Column>) {
+        self.sort_column = sort;
+        self.apply_columns(columns);
+    }
+
+    pub fn reload(&mut self) {
+        self.fetch_and_refresh(self.old_filter_text.clone());
+    }
+
     fn fetch_and_refresh(&mut self, filter_text: String) {
         self.fetch_services();
         self.refresh(filter_text);
     }
 
-    fn filter(&self, filter_text: String, services: Vec<Service>) -> Vec<Service> {
-        let lower_filter = filter_text.to_lowercase();
-        services
+    /// Keeps the services whose name fuzzy-matches the filter and whose state satisfies its
+    /// `key:value` conditions, best matches first unless the list is sorted by a column.
+    fn filter(&mut self, filter_text: &str) {
+        let started = Instant::now();
+        self.filter_services(filter_text);
+        self.usecase.borrow().record_timing(Stage::Filter, started.elapsed());
+    }
+
+    fn filter_services(&mut self, filter_text: &str) {
+        let query = ServiceQuery::parse(filter_text);
+        let candidates: Vec<usize> = match self.filter_index.take() {
+            Some((previous, indices)) if query.narrows(&previous) => indices,
+            _ => (0..self.services.len()).collect(),
+        };
+        let matches: Vec<(i64, usize, Vec<usize>)> = candidates
             .into_iter()
-            .filter(|service| {
-                let name = service.formatted_name();
-                name.to_lowercase().contains(&lower_filter)
+            .filter(|index| query.matches_state(&self.services[*index]))
+            .filter_map(|index| {
+                let service = &self.services[index];
+                query
+                    .names()
+                    .filter_map(|name| {
+                        // An alias, e.g. "sshd", finds the unit it names, with nothing to highlight.
+                        fuzzy_match(name, service.formatted_name()).or_else(|| {
+                            service
+                                .formatted_aliases()
+                                .filter_map(|alias| fuzzy_match(name, alias))
+                                .max_by_key(|m| m.score)
+                                .map(|m| FuzzyMatch { positions: Vec::new(), ..m })
+                        })
+                    })
+                    .max_by_key(|m| m.score)
+                    .map(|m| (m.score, index, m.positions))
             })
-            .collect()
+            .collect();
+        let indices = matches.iter().map(|(_, index, _)| *index).collect();
+        let services = &self.services;
+        let mut matches: Vec<(i64, &Service, Vec<usize>)> = matches
+            .into_iter()
+            .map(|(score, index, positions)| (score, &services[index], positions))
+            .collect();
+        // Stable, so that equal scores keep the list order.
+        matches.sort_by_key(|(score, _, _)| std::cmp::Reverse(*score));
+        match self.sort_column {
+            Some(Column::Impact) => matches.sort_by_key(|(_, service, _)| {
+                std::cmp::Reverse(self.column_data.impact.get(service.name()).copied().unwrap_or(0))
+            }),
+            Some(Column::Restarts) => matches.sort_by_key(|(_, service, _)| {
+                std::cmp::Reverse(self.column_data.runtime.get(service.name()).map(RuntimeStats::n_restarts))
+            }),
+            Some(Column::Memory) => matches.sort_by_key(|(_, service, _)| {
+                std::cmp::Reverse(self.column_data.runtime.get(service.name()).and_then(RuntimeStats::memory_bytes))
+            }),
+            // Not running last.
+            Some(Column::Uptime) => {
+                let now = now_usec();
+                matches.sort_by_key(|(_, service, _)| {
+                    self.column_data
+                        .runtime
+                        .get(service.name())
+                        .and_then(|stats| stats.uptime_secs(now))
+                        .unwrap_or(u64::MAX)
+                })
+            }
+            Some(column) => matches.sort_by_cached_key(|(_, service, _)| {
+                column_text(service, &column, &self.column_data).to_lowercase()
+            }),
+            None => {}
+        }
+
+        // Instances follow their template when it is listed too, in the order they came in.
+        let templates: HashSet<&str> = matches
+            .iter()
+            .filter(|(_, service, _)| service.is_template())
+            .map(|(_, service, _)| service.name())
+            .collect();
+        let mut instances: HashMap<String, Vec<usize>> = HashMap::new();
+        for (index, (_, service, _)) in matches.iter().enumerate() {
+            if let Some(template) = service.template()
+                && templates.contains(template.as_str())
+            {
+                instances.entry(template).or_default().push(index);
+            }
+        }
+        self.grouped_instances = instances.values().flatten().map(|index| matches[*index].1.name().to_string()).collect();
+        let mut order = Vec::with_capacity(matches.len());
+        for (index, (_, service, _)) in matches.iter().enumerate() {
+            if self.grouped_instances.contains(service.name()) {
+                continue;
+            }
+            order.push(index);
+            if let Some(instances) = instances.get(service.name()) {
+                order.extend(instances);
+            }
+        }
+
+        self.name_matches.clear();
+        self.filtered_services = Vec::with_capacity(matches.len());
+        let mut matches: Vec<Option<(i64, &Service, Vec<usize>)>> = matches.into_iter().map(Some).collect();
+        for index in order {
+            let Some((_, service, positions)) = matches[index].take() else {
+                continue;
+            };
+            if !positions.is_empty() {
+                self.name_matches.insert(service.name().to_string(), positions);
+            }
+            self.filtered_services.push(service.clone());
+        }
+
+        // Pinned units come first whatever the filter and the sort.
+        let pinned: Vec<Service> = self
+            .pinned
+            .iter()
+            .filter_map(|unit| self.services.iter().find(|service| service.name() == unit))
+            .cloned()
+            .collect();
+        if !pinned.is_empty() {
+            self.filtered_services.retain(|service| !self.pinned.iter().any(|unit| unit == service.name()));
+            self.filtered_services.splice(0..0, pinned);
+        }
+        self.filter_index = Some((query, indices));
+        self.shown_rows = None;
     }
 
     pub fn on_key_event(&mut self, key: KeyEvent) {
+        if let Some(batch) = &self.batch {
+            if batch.is_finished() {
+                self.batch = None;
+            }
+            return;
+        }
+
+        if let Some(confirm) = &mut self.confirm {
+            match confirm.on_key_event(key) {
+                Confirmation::Pending => {}
+                Confirmation::Confirmed => {
+                    let action = confirm.action();
+                    self.usecase.borrow().confirm_critical(confirm.units());
+                    self.confirm = None;
+                    self.perform_action(action);
+                }
+                Confirmation::Cancelled => {
+                    self.confirm = None;
+                    self.ordered = None;
+                }
+            }
+            return;
+        }
+
+        if let Some(signal_picker) = &mut self.signal_picker {
+            match signal_picker.on_key_event(key) {
+                Confirmation::Pending => {}
+                Confirmation::Confirmed => {
+                    if let Some(signal_picker) = self.signal_picker.take() {
+                        self.kill(signal_picker);
+                    }
+                }
+                Confirmation::Cancelled => self.signal_picker = None,
+            }
+            return;
+        }
+
+        if let Some(new_unit) = &mut self.new_unit {
+            match new_unit.on_key_event(key) {
+                Confirmation::Pending => {}
+                Confirmation::Confirmed => {
+                    if let Some(new_unit) = self.new_unit.take() {
+                        self.create_unit(new_unit);
+                    }
+                }
+                Confirmation::Cancelled => self.new_unit = None,
+            }
+            return;
+        }
+
+        if let Some(transient_unit) = &mut self.transient_unit {
+            match transient_unit.on_key_event(key) {
+                Confirmation::Pending => {}
+                Confirmation::Confirmed => {
+                    if let Some(transient_unit) = self.transient_unit.take() {
+                        self.run_transient(transient_unit);
+                    }
+                }
+                Confirmation::Cancelled => self.transient_unit = None,
+            }
+            return;
+        }
+
+        if let Some(clone_unit) = &mut self.clone_unit {
+            match clone_unit.on_key_event(key) {
+                Confirmation::Pending => {}
+                Confirmation::Confirmed => {
+                    if let Some(clone_unit) = self.clone_unit.take() {
+                        self.clone_selected(clone_unit);
+                    }
+                }
+                Confirmation::Cancelled => self.clone_unit = None,
+            }
+            return;
+        }
+
+        if let Some(instance_prompt) = &mut self.instance_prompt {
+            match instance_prompt.on_key_event(key) {
+                Confirmation::Pending => {}
+                Confirmation::Confirmed => {
+                    if let Some(instance_prompt) = self.instance_prompt.take() {
+                        self.start_instance(instance_prompt);
+                    }
+                }
+                Confirmation::Cancelled => self.instance_prompt = None,
+            }
+            return;
+        }
+
+        if let Some(undo) = &mut self.undo {
+            match undo.on_key_event(key) {
+                Confirmation::Pending => {}
+                Confirmation::Confirmed => {
+                    self.undo = None;
+                    self.undo_last();
+                }
+                Confirmation::Cancelled => self.undo = None,
+            }
+            return;
+        }
+
+        if let Some(column_picker) = &mut self.column_picker {
+            match column_picker.on_key_event(key) {
+                Confirmation::Pending => {}
+                Confirmation::Confirmed => {
+                    let columns = column_picker.columns();
+                    self.column_picker = None;
+                    self.apply_columns(columns);
+                }
+                Confirmation::Cancelled => self.column_picker = None,
+            }
+            return;
+        }
+
+        if let Some(type_ahead) = &mut self.type_ahead {
+            match type_ahead.on_key_event(key) {
+                Confirmation::Pending => {
+                    if let Some(index) = type_ahead.find(&self.filtered_services) {
+                        self.table_state.select(Some(index));
+                    }
+                }
+                Confirmation::Confirmed => self.type_ahead = None,
+                Confirmation::Cancelled => {
+                    self.table_state.select(type_ahead.origin());
+                    self.type_ahead = None;
+                }
+            }
+            return;
+        }
+
+        if let Some(quick_actions) = &mut self.quick_actions {
+            match quick_actions.on_key_event(key) {
+                Confirmation::Pending => {}
+                Confirmation::Confirmed => {
+                    let choice = quick_actions.choice();
+                    self.quick_actions = None;
+                    self.run_quick_action(choice);
+                }
+                Confirmation::Cancelled => self.quick_actions = None,
+            }
+            return;
+        }
+
+        if let Some(preview) = &mut self.preview {
+            match preview.on_key_event(key) {
+                Confirmation::Pending => {}
+                Confirmation::Confirmed => {
+                    let action = preview.action();
+                    self.preview = None;
+                    self.confirm_and_perform(action);
+                }
+                Confirmation::Cancelled => {
+                    self.preview = None;
+                    self.ordered = None;
+                }
+            }
+            return;
+        }
+
         if self.ignore_key_events {
             return;
         }
 
-        match key.code {
-            KeyCode::Down => self.select_next(),
-            KeyCode::Up => self.select_previous(),
-            KeyCode::PageDown => self.select_page_down(),
-            KeyCode::PageUp => self.select_page_up(),
-            KeyCode::Char('r') => self.act_on_selected_service(ServiceAction::Restart),
-            KeyCode::Char('s') => self.act_on_selected_service(ServiceAction::Start),
-            KeyCode::Char('e') => self.act_on_selected_service(ServiceAction::Enable),
-            KeyCode::Char('d') => self.act_on_selected_service(ServiceAction::Disable),
-            KeyCode::Char('u') => self.act_on_selected_service(ServiceAction::RefreshAll),
-            KeyCode::Char('x') => self.act_on_selected_service(ServiceAction::Stop),
-            KeyCode::Char('v') => self.sender.send(AppEvent::Action(Actions::GoLog)).unwrap(),
-            KeyCode::Char('p') => self
+        let config = self.config.clone();
+        match self.chords.feed(key, &config.chords) {
+            ChordStep::Pending => {}
+            ChordStep::Matched(action) => self.run_chord(action),
+            ChordStep::Unmatched(keys) => {
+                for key in keys {
+                    self.on_single_key(key);
+                }
+            }
+        }
+    }
+
+    fn on_single_key(&mut self, key: KeyEvent) {
+        let config = self.config.clone();
+        let keys = &config.keybindings;
+        match key {
+            k if keys.down.matches(&k) => self.select_next(),
+            k if keys.up.matches(&k) => self.select_previous(),
+            k if keys.page_down.matches(&k) => self.select_page_down(),
+            k if keys.page_up.matches(&k) => self.select_page_up(),
+            k if keys.restart.matches(&k) => self.act_if_allowed(ServiceAction::Restart),
+            k if keys.ordered_restart.matches(&k) => self.restart_marked_in_order(),
+            k if keys.reload.matches(&k) => self.act_if_allowed(ServiceAction::Reload),
+            k if keys.start.matches(&k) => self.act_if_allowed(ServiceAction::Start),
+            k if keys.enable.matches(&k) => self.act_on_selected_service(ServiceAction::Enable),
+            k if keys.disable.matches(&k) => self.act_on_selected_service(ServiceAction::Disable),
+            k if keys.refresh.matches(&k) => self.act_on_selected_service(ServiceAction::RefreshAll),
+            k if keys.stop.matches(&k) => self.act_if_allowed(ServiceAction::Stop),
+            k if keys.freeze.matches(&k) => self.act_on_selected_service(ServiceAction::Freeze),
+            k if keys.thaw.matches(&k) => self.act_on_selected_service(ServiceAction::Thaw),
+            k if keys.view_logs.matches(&k) => self.sender.send(AppEvent::Action(Actions::GoLog)).unwrap(),
+            k if keys.properties.matches(&k) => self
                 .sender
                 .send(AppEvent::Action(Actions::GoDetails))
                 .unwrap(),
+            k if keys.path_lookup.matches(&k) => self
+                .sender
+                .send(AppEvent::Action(Actions::OpenPathLookup))
+                .unwrap(),
+            k if keys.top_talkers.matches(&k) => self
+                .sender
+                .send(AppEvent::Action(Actions::OpenTopTalkers))
+                .unwrap(),
+            k if keys.error_history.matches(&k) => self
+                .sender
+                .send(AppEvent::Action(Actions::OpenErrorHistory))
+                .unwrap(),
+            k if keys.failed_units.matches(&k) => self
+                .sender
+                .send(AppEvent::Action(Actions::OpenFailedUnits))
+                .unwrap(),
+            k if keys.targets.matches(&k) => self
+                .sender
+                .send(AppEvent::Action(Actions::OpenTargets))
+                .unwrap(),
+            k if keys.new_unit.matches(&k) => self
+                .sender
+                .send(AppEvent::Action(Actions::OpenNewUnit))
+                .unwrap(),
+            k if keys.run_transient.matches(&k) => self.transient_unit = Some(TransientUnitForm::new(self.config.locale)),
+            k if keys.instantiate.matches(&k) => self.open_instance_prompt(),
+            k if keys.clone_unit.matches(&k) => {
+                if let Some(service) = self.get_selected_service() {
+                    self.clone_unit = Some(CloneUnitForm::new(service.name(), self.config.locale));
+                }
+            }
+            k if keys.type_ahead.matches(&k) => self.type_ahead = Some(TypeAhead::new(self.table_state.selected(), self.config.locale)),
+            k if keys.pick_columns.matches(&k) => self.column_picker = Some(ColumnPicker::new(&self.columns, self.config.locale)),
+            k if keys.scroll_left.matches(&k) => self.scroll_columns(false),
+            k if keys.scroll_right.matches(&k) => self.scroll_columns(true),
+            k if keys.session_changes.matches(&k) => self
+                .sender
+                .send(AppEvent::Action(Actions::OpenSessionChanges))
+                .unwrap(),
+            k if keys.watch.matches(&k) => self.toggle_watch(),
+            k if keys.pin.matches(&k) => self.toggle_pin(),
+            k if keys.undo.matches(&k) => self.open_undo(),
+            k if keys.notifications.matches(&k) => self
+                .sender
+                .send(AppEvent::Action(Actions::OpenNotifications))
+                .unwrap(),
+            k if keys.sort.matches(&k) => self.cycle_sort(),
+            k if keys.mark.matches(&k) => self.toggle_mark(),
+            k if keys.clear_marks.matches(&k) => self.marked.clear(),
+            k if keys.cycle_theme.matches(&k) => self
+                .sender
+                .send(AppEvent::Action(Actions::CycleTheme))
+                .unwrap(),
+            k if keys.kill.matches(&k) => self.open_signal_picker(),
+            k if keys.export.matches(&k) => self.export(),
+            k if keys.toggle_dry_run.matches(&k) => self.dry_run = !self.dry_run,
+            k if keys.copy_lines.matches(&k) => self.copy_name(),
+            k if keys.next_host.matches(&k) && !config.hosts.is_empty() => self
+                .sender
+                .send(AppEvent::Action(Actions::NextHost))
+                .unwrap(),
             _ => {}
         }
     }
 
+    fn run_chord(&mut self, action: ChordAction) {
+        match action {
+            ChordAction::GoFailed => self.select_next_failed(),
+            ChordAction::GoTop => {
+                if !self.filtered_services.is_empty() {
+                    self.table_state.select(Some(0));
+                }
+            }
+            ChordAction::GoBottom => {
+                if !self.filtered_services.is_empty() {
+                    self.table_state.select(Some(self.filtered_services.len() - 1));
+                }
+            }
+            ChordAction::QuickActions => {
+                self.quick_actions = self
+                    .get_selected_service()
+                    .map(|service| QuickActions::new(service.name().to_string(), self.config.locale));
+            }
+        }
+    }
+
+    fn run_quick_action(&mut self, action: QuickAction) {
+        match action {
+            QuickAction::Service(action) => self.act_on_selected_service(action),
+            QuickAction::Kill => self.open_signal_picker(),
+            QuickAction::ViewLogs => self.sender.send(AppEvent::Action(Actions::GoLog)).unwrap(),
+            QuickAction::Properties => self.sender.send(AppEvent::Action(Actions::GoDetails)).unwrap(),
+        }
+    }
+
+    fn open_signal_picker(&mut self) {
+        self.signal_picker = self
+            .get_selected_service()
+            .map(|service| SignalPicker::new(service.name().to_string(), self.config.locale));
+    }
+
+    /// Selects the next failed service after the selected one, wrapping around.
+    fn select_next_failed(&mut self) {
+        let len = self.filtered_services.len();
+        let start = self.table_state.selected().map_or(0, |selected| selected + 1);
+        let next = (0..len)
+            .map(|offset| (start + offset) % len)
+            .find(|&index| self.filtered_services[index].state().active() == "failed");
+        if let Some(index) = next {
+            self.table_state.select(Some(index));
+        }
+    }
+
     fn select_page_down(&mut self) {
+        let len = self.filtered_services.len();
+        if len == 0 {
+            return;
+        }
         let jump = 10;
         if let Some(selected_index) = self.table_state.selected() {
             let new_index = selected_index + jump;
-            let wrapped_index = if new_index >= self.rows.len() {
-                (new_index) % self.rows.len()
+            let wrapped_index = if new_index >= len {
+                (new_index) % len
             } else {
                 new_index
             };
@@ -249,11 +1441,15 @@ impl TableServices {
     }
 
     fn select_page_up(&mut self) {
+        let len = self.filtered_services.len();
+        if len == 0 {
+            return;
+        }
         let jump = 10;
         if let Some(selected_index) = self.table_state.selected() {
             let new_index = selected_index as isize - jump as isize;
             let wrapped_index = if new_index < 0 {
-                (self.rows.len() as isize + new_index % self.rows.len() as isize) as usize
+                (len as isize + new_index % len as isize) as usize
             } else {
                 new_index as usize
             };
@@ -264,8 +1460,12 @@ impl TableServices {
     }
 
     fn select_next(&mut self) {
+        let len = self.filtered_services.len();
+        if len == 0 {
+            return;
+        }
         if let Some(selected_index) = self.table_state.selected() {
-            let next_index = if selected_index == self.rows.len() - 1 {
+            let next_index = if selected_index == len - 1 {
                 0
             } else {
                 selected_index + 1
@@ -277,9 +1477,13 @@ impl TableServices {
     }
 
     fn select_previous(&mut self) {
+        let len = self.filtered_services.len();
+        if len == 0 {
+            return;
+        }
         if let Some(selected_index) = self.table_state.selected() {
             let prev_index = if selected_index == 0 {
-                self.rows.len() - 1
+                len - 1
             } else {
                 selected_index - 1
             };
@@ -289,44 +1493,594 @@ impl TableServices {
         }
     }
 
+    fn toggle_mark(&mut self) {
+        if let Some(name) = self.get_selected_service().map(|s| s.name().to_string()) {
+            if !self.marked.remove(&name) {
+                self.marked.insert(name);
+            }
+            self.select_next();
+        }
+    }
+
+    fn start_batch(&mut self, action: ServiceAction) {
+        let services: Vec<Service> = self
+            .services
+            .iter()
+            .filter(|service| self.marked.contains(service.name()))
+            .cloned()
+            .collect();
+        self.batch = Some(BatchOperation::new(action, services, self.config.locale));
+        self.sender
+            .send(AppEvent::Action(Actions::RunBatchStep))
+            .unwrap();
+    }
+
+    /// Applies the action to the units one after the other in the order their dependencies start
+    /// them, stopping them in reverse, and gives up on the rest at the first failure; units not
+    /// in the list are looked up by name. Previewed and confirmed like any other action.
+    pub fn run_on_units(&mut self, action: ServiceAction, units: Vec<String>) {
+        let units = self.usecase.borrow().start_order(&units);
+        let mut services = Vec::new();
+        for unit in units {
+            let service = match self.services.iter().find(|service| service.name() == unit) {
+                Some(service) => Ok(service.clone()),
+                None => self.usecase.borrow().find_service(&unit),
+            };
+            match service {
+                Ok(service) => services.push(service),
+                Err(e) => self.sender.send(AppEvent::Error(AppError::from(e))).unwrap(),
+            }
+        }
+        if services.is_empty() {
+            return;
+        }
+        self.ordered = Some(services);
+        self.act_on_selected_service(action);
+    }
+
+    /// Restarts the marked services in dependency order: all stopped, then started again.
+    fn restart_marked_in_order(&mut self) {
+        if self.marked.is_empty() {
+            let notice = self.config.locale.tr("Mark the services to restart in dependency order").to_string();
+            self.sender.send(AppEvent::Notice(notice)).unwrap();
+            return;
+        }
+        let units: Vec<String> = self
+            .services
+            .iter()
+            .filter(|service| self.marked.contains(service.name()))
+            .map(|service| service.name().to_string())
+            .collect();
+        self.run_on_units(ServiceAction::Restart, units);
+    }
+
+    /// Applies the batch action to the next marked service, rescheduling itself until done.
+    pub fn run_batch_step(&mut self) {
+        let Some(batch) = &mut self.batch else {
+            return;
+        };
+
+        if let Some((action, service)) = batch.next_step() {
+            let result = run_action(&self.usecase.borrow(), action, &service)
+                .map_err(|e| self.config.locale.tr(AppError::from(e).explanation()).to_string());
+            report_action(&self.sender, action, service.name(), &result);
+            batch.record(action, &service, result.map(|_| ()));
+        }
+
+        if batch.is_finished() {
+            self.marked.clear();
+            self.fetch_and_refresh(self.old_filter_text.clone());
+        } else {
+            self.sender
+                .send(AppEvent::Action(Actions::RunBatchStep))
+                .unwrap();
+        }
+    }
+
+    /// The services whose log is shown: the marked ones merged, or the selected one.
+    pub fn log_targets(&self) -> Vec<Service> {
+        self.action_targets().into_iter().cloned().collect()
+    }
+
+    /// The units of the ordered batch to confirm, else the marked services, or the selected one
+    /// when nothing is marked.
+    fn action_targets(&self) -> Vec<&Service> {
+        if let Some(ordered) = &self.ordered {
+            ordered.iter().collect()
+        } else if self.marked.is_empty() {
+            self.get_selected_service().into_iter().collect()
+        } else {
+            self.services
+                .iter()
+                .filter(|service| self.marked.contains(service.name()))
+                .collect()
+        }
+    }
+
+    /// Critical units targeted by a disruptive action outside of the maintenance windows.
+    fn units_needing_confirmation(&self, action: ServiceAction) -> Vec<String> {
+        let Some(unit_action) = action.unit_action() else {
+            return Vec::new();
+        };
+        let targets: Vec<&str> = self.action_targets().into_iter().map(|service| service.name()).collect();
+        self.usecase.borrow().needing_confirmation(unit_action.policy_action(), &targets)
+    }
+
+    fn plan_targets(&self, action: UnitAction) -> Vec<(String, Result<ActionPlan, String>)> {
+        let usecase = self.usecase.borrow();
+        self.action_targets()
+            .into_iter()
+            .map(|service| {
+                let plan = usecase
+                    .plan_action(action, service)
+                    .map_err(|e| self.config.locale.tr(AppError::from(e).explanation()).to_string());
+                (service.name().to_string(), plan)
+            })
+            .collect()
+    }
+
     fn act_on_selected_service(&mut self, action: ServiceAction) {
-        if let Some(service) = self.get_selected_service() {
-            let binding_usecase = self.usecase.clone();
-            let usecase = binding_usecase.borrow();
-            match action {
-                ServiceAction::Start => self.handle_result(usecase.start_service(service)),
-                ServiceAction::Stop => self.handle_result(usecase.stop_service(service)),
-                ServiceAction::Restart => self.handle_result(usecase.restart_service(service)),
-                ServiceAction::Enable => self.handle_result(usecase.enable_service(service)),
-                ServiceAction::Disable => self.handle_result(usecase.disable_service(service)),
-                ServiceAction::RefreshAll => self.fetch_services(),
+        match action.unit_action() {
+            Some(unit_action) if self.dry_run => {
+                self.preview = Some(ActionPreview::new(action, self.plan_targets(unit_action), true, self.config.locale));
+            }
+            Some(unit_action) if unit_action.propagates() => {
+                let plans = self.plan_targets(unit_action);
+                let propagates = plans
+                    .iter()
+                    .any(|(_, plan)| plan.as_ref().is_ok_and(|plan| !plan.propagated_units().is_empty()));
+                if propagates {
+                    self.preview = Some(ActionPreview::new(action, plans, false, self.config.locale));
+                } else {
+                    self.confirm_and_perform(action);
+                }
+            }
+            _ => self.confirm_and_perform(action),
+        }
+    }
+
+    /// Starts or stops alerting about the state changes of the selected service.
+    fn toggle_watch(&mut self) {
+        let Some(service) = self.get_selected_service().cloned() else {
+            return;
+        };
+        let result = self.usecase.borrow_mut().toggle_watch(&service);
+        let event = match result {
+            Ok(true) => AppEvent::Notice(self.config.locale.format("Watching {}", &[service.name()])),
+            Ok(false) => AppEvent::Notice(self.config.locale.format("Stopped watching {}", &[service.name()])),
+            Err(e) => AppEvent::Error(e.into()),
+        };
+        self.sender.send(event).unwrap();
+    }
+
+    /// Pins the selected unit at the top of the list, or unpins it, saving the pins for the next runs.
+    fn toggle_pin(&mut self) {
+        let Some(name) = self.get_selected_service().map(|service| service.name().to_string()) else {
+            return;
+        };
+        let notice = match self.pinned.iter().position(|unit| *unit == name) {
+            Some(index) => {
+                self.pinned.remove(index);
+                self.config.locale.format("Unpinned {}", &[&name])
+            }
+            None => {
+                self.pinned.push(name.clone());
+                self.config.locale.format("Pinned {}", &[&name])
+            }
+        };
+        self.refresh(self.old_filter_text.clone());
+        self.select_by_name(&name);
+        let event = match save_pinned(&self.pinned) {
+            Ok(()) => AppEvent::Notice(notice),
+            Err(e) => AppEvent::Error(e.into()),
+        };
+        self.sender.send(event).unwrap();
+    }
+
+    /// What the manager accepts for the selected service, anything when it cannot be told.
+    /// Marked services are acted on as a batch, each one failing on its own.
+    fn selected_capabilities(&self) -> UnitCapabilities {
+        match self.get_selected_service() {
+            Some(service) if self.marked.is_empty() => self
+                .usecase
+                .borrow()
+                .capabilities(service)
+                .unwrap_or(UnitCapabilities::UNRESTRICTED),
+            _ => UnitCapabilities::UNRESTRICTED,
+        }
+    }
+
+    /// Acts on the selected or marked services, explaining instead why the selected service
+    /// refuses the action rather than letting the manager fail.
+    fn act_if_allowed(&mut self, action: ServiceAction) {
+        let refusal = action
+            .unit_action()
+            .and_then(|unit_action| self.selected_capabilities().refusal(unit_action));
+        match (refusal, self.get_selected_service()) {
+            (Some(refusal), Some(service)) => {
+                let notice = format!("{} {}", service.name(), refusal);
+                self.sender.send(AppEvent::Notice(notice)).unwrap();
+            }
+            _ => self.act_on_selected_service(action),
+        }
+    }
+
+    /// Acts on the selected service alone, dropping the marks that would make it a batch.
+    pub fn act_on_selected_only(&mut self, action: ServiceAction) {
+        self.marked.clear();
+        self.act_on_selected_service(action);
+    }
+
+    fn confirm_and_perform(&mut self, action: ServiceAction) {
+        let critical_units = self.units_needing_confirmation(action);
+        if critical_units.is_empty() {
+            self.perform_action(action);
+        } else {
+            self.confirm = Some(ConfirmPrompt::new(action, critical_units, self.config.locale));
+        }
+    }
+
+    fn perform_action(&mut self, action: ServiceAction) {
+        if let Some(services) = self.ordered.take() {
+            self.batch = Some(BatchOperation::ordered(action, services, self.config.locale));
+            self.sender
+                .send(AppEvent::Action(Actions::RunBatchStep))
+                .unwrap();
+            return;
+        }
+        if action != ServiceAction::RefreshAll && !self.marked.is_empty() {
+            self.start_batch(action);
+            return;
+        }
+
+        if action == ServiceAction::RefreshAll {
+            self.fetch_services();
+        } else if action.is_queued()
+            && let Some(unit_action) = action.unit_action()
+            && let Some(service) = self.get_selected_service().cloned()
+        {
+            // The list is fetched again once the action returns, see `action_finished`.
+            let result = self.usecase.borrow_mut().queue_action(unit_action, &service);
+            self.column_data.pending = self.usecase.borrow().pending_actions();
+            self.handle_result(result);
+            return;
+        } else if let Some(service) = self.get_selected_service() {
+            let result = run_action(&self.usecase.borrow(), action, service);
+            let reported = result.clone().map_err(|e| e.to_string());
+            report_action(&self.sender, action, service.name(), &reported);
+            self.handle_result(result.map(|_| ()));
+        }
+        self.fetch_and_refresh(self.old_filter_text.clone());
+    }
+
+    /// Sends the picked signal, or only describes the KillUnit call in dry-run mode.
+    fn kill(&mut self, signal_picker: SignalPicker) {
+        let Some(signal) = signal_picker.signal() else {
+            return;
+        };
+        if self.dry_run {
+            let notice = self.config.locale.format("Dry run: would send {}", &[&signal_picker.description()]);
+            self.sender.send(AppEvent::Notice(notice)).unwrap();
+            return;
+        }
+
+        if let Some(service) = self.services.iter().find(|service| service.name() == signal_picker.unit()) {
+            let result = self
+                .usecase
+                .borrow()
+                .kill_service(service, signal_picker.target(), signal);
+            self.handle_result(result);
+        }
+        self.fetch_and_refresh(self.old_filter_text.clone());
+    }
+
+    /// Writes the units of the form, or only describes them in dry-run mode.
+    fn create_unit(&mut self, form: NewUnitForm) {
+        let unit = form.unit();
+        if self.dry_run {
+            let notice = self.config.locale.format("Dry run: would write {}", &[&unit.unit_names().join(", ")]);
+            self.sender.send(AppEvent::Notice(notice)).unwrap();
+            return;
+        }
+
+        let result = self.usecase.borrow().create_unit(unit, form.enable_and_start());
+        match result {
+            Ok(paths) => self
+                .sender
+                .send(AppEvent::Notice(self.config.locale.format("Created {}", &[&paths.join(", ")])))
+                .unwrap(),
+            Err(e) => self.sender.send(AppEvent::Error(e.into())).unwrap(),
+        }
+        self.fetch_and_refresh(self.old_filter_text.clone());
+    }
+
+    /// Copies the unit file of the form and hands the copy to the editor, or creates the
+    /// instance of a template; only describes it in dry-run mode.
+    fn clone_selected(&mut self, form: CloneUnitForm) {
+        let Ok(clone) = form.clone_target() else {
+            return;
+        };
+        if self.dry_run {
+            let notice = self.config.locale.format("Dry run: would {}", &[&clone.describe(form.source())]);
+            self.sender.send(AppEvent::Notice(notice)).unwrap();
+            return;
+        }
+        let Some(service) = self.services.iter().find(|service| service.name() == form.source()).cloned() else {
+            return;
+        };
+
+        let result = self.usecase.borrow().clone_unit(&service, &clone);
+        match (result, clone) {
+            // Enabled once edited, its [Install] section may change.
+            (Ok(_), UnitClone::Copy(name)) => self
+                .sender
+                .send(AppEvent::Action(Actions::EditUnit(name, form.enable_and_start())))
+                .unwrap(),
+            (Ok(_), UnitClone::Instance(name)) if form.enable_and_start() => {
+                let result = self.usecase.borrow().enable_and_start(&name);
+                let event = match result {
+                    Ok(()) => AppEvent::Notice(self.config.locale.format("Enabled and started {}", &[&name])),
+                    Err(e) => AppEvent::Error(e.into()),
+                };
+                self.sender.send(event).unwrap();
             }
+            (Ok(_), UnitClone::Instance(name)) => self
+                .sender
+                .send(AppEvent::Notice(
+                    self.config.locale.format("{} runs from its template, no file was written", &[&name]),
+                ))
+                .unwrap(),
+            (Err(e), _) => self.sender.send(AppEvent::Error(e.into())).unwrap(),
         }
         self.fetch_and_refresh(self.old_filter_text.clone());
     }
 
-    fn handle_result(&mut self, result: Result<(), Box<dyn Error>>) {
+    /// Asks before undoing the last enable, disable or drop-in of the session.
+    fn open_undo(&mut self) {
+        let action = self.usecase.borrow().last_undoable();
+        match action {
+            Some(action) => self.undo = Some(UndoPrompt::new(action, self.config.locale)),
+            None => self
+                .sender
+                .send(AppEvent::Notice(self.config.locale.tr("Nothing to undo").to_string()))
+                .unwrap(),
+        }
+    }
+
+    fn undo_last(&mut self) {
+        if self.dry_run {
+            if let Some(action) = self.usecase.borrow().last_undoable() {
+                let notice = self.config.locale.format("Dry run: would undo the {}", &[&action.description()]);
+                self.sender.send(AppEvent::Notice(notice)).unwrap();
+            }
+            return;
+        }
+
+        let result = self.usecase.borrow().undo();
+        let event = match result {
+            Ok(description) => AppEvent::Notice(self.config.locale.format("Undid the {}", &[&description])),
+            Err(e) => AppEvent::Error(e.into()),
+        };
+        self.sender.send(event).unwrap();
+        self.fetch_and_refresh(self.old_filter_text.clone());
+    }
+
+    /// Asks for an instance of the selected template, or of the template of the selected instance.
+    fn open_instance_prompt(&mut self) {
+        let Some(service) = self.get_selected_service().cloned() else {
+            return;
+        };
+        if service.is_template() || service.template().is_some() {
+            self.instance_prompt = Some(InstancePrompt::new(service, self.config.locale));
+        } else {
+            let notice = format!("{} is not a template nor an instance of one", service.name());
+            self.sender.send(AppEvent::Notice(notice)).unwrap();
+        }
+    }
+
+    /// Starts the instance of the prompt and selects it, or only describes it in dry-run mode.
+    fn start_instance(&mut self, prompt: InstancePrompt) {
+        let Ok(name) = prompt.instance_name() else {
+            return;
+        };
+        if self.dry_run {
+            self.sender
+                .send(AppEvent::Notice(self.config.locale.format("Dry run: would start {}", &[&name])))
+                .unwrap();
+            return;
+        }
+
+        let result = self.usecase.borrow().start_instance(&name);
+        match result {
+            Ok(job) => {
+                let outcome = job.map_or(ActionOutcome::Completed, ActionOutcome::Queued);
+                self.sender
+                    .send(AppEvent::Action(Actions::ActionPerformed(format!("start of {}", name), outcome)))
+                    .unwrap();
+                self.fetch_and_refresh(self.old_filter_text.clone());
+                self.sender.send(AppEvent::Action(Actions::JumpToService(name))).unwrap();
+            }
+            Err(e) => self.sender.send(AppEvent::Error(e.into())).unwrap(),
+        }
+    }
+
+    /// Starts the command of the form as a transient service and shows its logs, or only
+    /// describes it in dry-run mode.
+    fn run_transient(&mut self, form: TransientUnitForm) {
+        let unit = form.unit();
+        if self.dry_run {
+            let notice = self
+                .config
+                .locale
+                .format("Dry run: would run {} as {}", &[unit.command.trim(), &unit.describe()]);
+            self.sender.send(AppEvent::Notice(notice)).unwrap();
+            return;
+        }
+
+        let result = self.usecase.borrow().run_transient(unit);
+        match result {
+            Ok((name, job)) => {
+                let outcome = job.map_or(ActionOutcome::Completed, ActionOutcome::Queued);
+                self.sender
+                    .send(AppEvent::Action(Actions::ActionPerformed(format!("start of {}", name), outcome)))
+                    .unwrap();
+                self.fetch_and_refresh(self.old_filter_text.clone());
+                self.sender.send(AppEvent::Action(Actions::JumpToService(name))).unwrap();
+                self.sender.send(AppEvent::Action(Actions::GoLog)).unwrap();
+            }
+            Err(e) => self.sender.send(AppEvent::Error(e.into())).unwrap(),
+        }
+    }
+
+    /// Writes the filtered services, with their properties, to the configured export directory.
+    fn export(&mut self) {
+        let settings = &self.config.export;
+        let services = self.usecase.borrow().with_properties(&self.filtered_services);
+        let result = export_to_directory(&services, settings.format, &settings.directory);
+        let event = match result {
+            Ok(path) => AppEvent::Notice(self.config.locale.format(
+                "Exported {} services to {}",
+                &[&self.filtered_services.len().to_string(), &path.display().to_string()],
+            )),
+            Err(e) => AppEvent::Error(e.into()),
+        };
+        self.sender.send(event).unwrap();
+    }
+
+    fn handle_result(&mut self, result: Result<(), ServiceError>) {
         match result {
             Ok(_) => {}
             Err(e) => {
-                self.sender.send(AppEvent::Error(e.to_string())).unwrap();
+                self.sender.send(AppEvent::Error(e.into())).unwrap();
             }
         }
     }
 
-    pub fn shortcuts(&mut self) -> Vec<Line<'_>> {
+    /// What the footer shows besides the help key: the rest of a pending chord, or what the
+    /// actions apply to when it is not only the selected service.
+    pub fn footer_hint(&self, theme: &Theme) -> Option<Line<'static>> {
+        let title = Style::default().fg(theme.title).add_modifier(Modifier::BOLD);
+        if self.chords.is_pending() {
+            return Some(Line::from(vec![
+                Span::styled(format!("{}: ", self.config.locale.tr("Chord")), title),
+                Span::raw(self.chords.completions(&self.config.chords).join(" | ")),
+            ]));
+        }
+        if self.ignore_key_events || (self.marked.is_empty() && !self.dry_run) {
+            return None;
+        }
+        Some(Line::from(Span::styled(self.actions_target(), title)))
+    }
+
+    /// What the actions apply to: the selected service, or the marked ones.
+    fn actions_target(&self) -> String {
+        let locale = self.config.locale;
+        let mut target = if self.marked.is_empty() {
+            locale.tr("Actions on the selected service").to_string()
+        } else {
+            locale
+                .tr("Actions on the {} marked services")
+                .replace("{}", &self.marked.len().to_string())
+        };
+        if self.dry_run {
+            target.push_str(&format!(" ({})", locale.tr("dry run")));
+        }
+        target
+    }
+
+    pub fn shortcuts(&mut self, theme: &Theme) -> Vec<Line<'_>> {
+        let locale = self.config.locale;
         let mut help_text: Vec<Line<'_>> = Vec::new();
+        if self.chords.is_pending() {
+            help_text.push(Line::from(Span::styled(
+                locale.tr("Waiting for the rest of the chord"),
+                Style::default()
+                    .fg(theme.title)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            help_text.push(Line::from(self.chords.completions(&self.config.chords).join(" | ")));
+            return help_text;
+        }
         if !self.ignore_key_events {
             help_text.push(Line::from(Span::styled(
-                "Actions on the selected service",
+                self.actions_target(),
                 Style::default()
-                    .fg(Color::LightMagenta)
+                    .fg(theme.title)
                     .add_modifier(Modifier::BOLD),
             )));
 
-            help_text.push(Line::from(
-                "Navigate: ↑/↓ | Switch tab: ←/→ | Start: s | Stop: x | Restart: r | Enable: e | Disable: d | Refresh all: u | View logs: v | Properties: p"
-            ));
+            let config = self.config.clone();
+            let keys = &config.keybindings;
+            let mut line = vec![Span::raw(format!(
+                "{}: {}/{} | {}: {}/{} | ",
+                locale.tr("Navigate"), keys.up, keys.down, locale.tr("Switch tab"), keys.previous_tab, keys.next_tab
+            ))];
+            // The actions the selected unit refuses are greyed out.
+            let capabilities = self.selected_capabilities();
+            let lifecycle = [
+                ("Start", &keys.start, UnitAction::Start),
+                ("Stop", &keys.stop, UnitAction::Stop),
+                ("Restart", &keys.restart, UnitAction::Restart),
+                ("Reload", &keys.reload, UnitAction::Reload),
+            ];
+            for (index, (label, key, action)) in lifecycle.into_iter().enumerate() {
+                if index > 0 {
+                    line.push(Span::raw(" | "));
+                }
+                let style = if capabilities.allows(action) {
+                    Style::default()
+                } else {
+                    Style::default().fg(theme.muted).add_modifier(Modifier::CROSSED_OUT)
+                };
+                line.push(Span::styled(format!("{}: {}", locale.tr(label), key), style));
+            }
+            let chords = &self.config.chords;
+            let mut entries = vec![
+                ("Enable", keys.enable.to_string()),
+                ("Disable", keys.disable.to_string()),
+                ("Freeze/thaw", format!("{}/{}", keys.freeze, keys.thaw)),
+                ("Kill", keys.kill.to_string()),
+                ("Refresh all", keys.refresh.to_string()),
+                ("View logs", keys.view_logs.to_string()),
+                ("Properties", keys.properties.to_string()),
+                ("Mark", keys.mark.to_string()),
+                ("Clear marks", keys.clear_marks.to_string()),
+                ("What manages a path", keys.path_lookup.to_string()),
+                ("Top talkers", keys.top_talkers.to_string()),
+                ("Errors", keys.error_history.to_string()),
+                ("Failed units", keys.failed_units.to_string()),
+                ("Targets", keys.targets.to_string()),
+                ("Session changes", keys.session_changes.to_string()),
+                ("Watch", keys.watch.to_string()),
+                ("Pin", keys.pin.to_string()),
+                ("Notifications", keys.notifications.to_string()),
+                ("New service", keys.new_unit.to_string()),
+                ("Run a command", keys.run_transient.to_string()),
+                ("Clone", keys.clone_unit.to_string()),
+                ("Start an instance", keys.instantiate.to_string()),
+                ("Undo", keys.undo.to_string()),
+                ("Jump to a unit", keys.type_ahead.to_string()),
+                ("Columns", keys.pick_columns.to_string()),
+                ("Next profile", keys.next_profile.to_string()),
+                ("Scroll columns", format!("{}/{}", keys.scroll_left, keys.scroll_right)),
+                ("Sort", keys.sort.to_string()),
+                ("Export", keys.export.to_string()),
+                ("Dry run", keys.toggle_dry_run.to_string()),
+                ("Copy name", keys.copy_lines.to_string()),
+                ("Theme", keys.cycle_theme.to_string()),
+                ("Next failed", chords.go_failed.to_string()),
+                ("Top/bottom", format!("{}/{}", chords.go_top, chords.go_bottom)),
+                ("Quick actions", chords.quick_actions.to_string()),
+                ("Pause refresh", keys.pause_refresh.to_string()),
+                ("Refresh interval", format!("{}/{}", keys.longer_refresh, keys.shorter_refresh)),
+                ("Command", keys.command.to_string()),
+            ];
+            if !self.config.hosts.is_empty() {
+                entries.push(("Next host", keys.next_host.to_string()));
+            }
+            let shortcuts = format!(" | {}", locale.shortcuts(&entries));
+            line.push(Span::raw(shortcuts));
+            help_text.push(Line::from(line));
         }
 
         help_text
@@ -0,0 +1,133 @@
+use crossterm::event::KeyEvent;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::Span,
+    widgets::Paragraph,
+    Frame,
+};
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::Config;
+use crate::terminal::app::{Actions, AppEvent};
+use crate::terminal::theme::Theme;
+
+/// Intervals the longer/shorter keys step through, in milliseconds.
+const STEPS: [u64; 7] = [1000, 2000, 5000, 10_000, 30_000, 60_000, 300_000];
+/// Interval used once resumed when the config starts the refresh paused.
+const DEFAULT_INTERVAL: u64 = 5000;
+/// How often the timer checks whether the interval elapsed, so that a shorter one applies at once.
+const TICK: Duration = Duration::from_millis(100);
+
+fn format_interval(interval: Duration) -> String {
+    let millis = interval.as_millis();
+    if millis.is_multiple_of(60_000) {
+        format!("{}m", millis / 60_000)
+    } else if millis.is_multiple_of(1000) {
+        format!("{}s", millis / 1000)
+    } else {
+        format!("{}ms", millis)
+    }
+}
+
+/// Periodic refresh of the service list, at an interval changed at runtime.
+pub struct ListRefresh {
+    interval: Arc<Mutex<Duration>>,
+    paused: bool,
+    config: Rc<Config>,
+}
+
+impl ListRefresh {
+    pub fn new(config: Rc<Config>) -> Self {
+        let paused = config.list_refresh_interval == 0;
+        let interval = if paused { DEFAULT_INTERVAL } else { config.list_refresh_interval };
+        Self {
+            interval: Arc::new(Mutex::new(Duration::from_millis(interval))),
+            paused,
+            config,
+        }
+    }
+
+    /// Sends RefreshList every interval; the app skips it while paused or when the list is hidden.
+    pub fn spawn_timer(&self, sender: Sender<AppEvent>) {
+        let interval = Arc::clone(&self.interval);
+        thread::spawn(move || {
+            let mut last_refresh = Instant::now();
+            loop {
+                thread::sleep(TICK);
+                let Ok(interval) = interval.lock().map(|interval| *interval) else {
+                    break;
+                };
+                if last_refresh.elapsed() < interval {
+                    continue;
+                }
+                last_refresh = Instant::now();
+                if sender.send(AppEvent::Action(Actions::RefreshList)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    fn interval(&self) -> Duration {
+        self.interval.lock().map(|interval| *interval).unwrap_or_default()
+    }
+
+    /// Moves to the next longer (or shorter) step from the current interval.
+    fn step(&mut self, longer: bool) {
+        let current = self.interval().as_millis() as u64;
+        let next = if longer {
+            STEPS.iter().copied().find(|step| *step > current).unwrap_or(current)
+        } else {
+            STEPS.iter().copied().rev().find(|step| *step < current).unwrap_or(current)
+        };
+        if let Ok(mut interval) = self.interval.lock() {
+            *interval = Duration::from_millis(next);
+        }
+    }
+
+    /// Handles the keys of the auto-refresh, returning whether the key was one of them.
+    pub fn on_key_event(&mut self, key: KeyEvent) -> bool {
+        let keys = &self.config.keybindings;
+        match key {
+            k if keys.pause_refresh.matches(&k) => self.paused = !self.paused,
+            k if keys.longer_refresh.matches(&k) => self.step(true),
+            k if keys.shorter_refresh.matches(&k) => self.step(false),
+            _ => return false,
+        }
+        true
+    }
+
+    fn label(&self) -> String {
+        let locale = self.config.locale;
+        if self.paused {
+            locale.tr("Auto-refresh paused").to_string()
+        } else {
+            format!("{} {}", locale.tr("Auto-refresh every"), format_interval(self.interval()))
+        }
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        vec![self.label()]
+    }
+
+    /// Draws the indicator at the start of the area, the rest of the row after the System/Session tabs.
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let label = self.label();
+        let style = if self.paused {
+            Style::default().fg(theme.warning).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.muted)
+        };
+        let width = (label.chars().count() as u16).min(area.width);
+        frame.render_widget(Paragraph::new(Span::styled(label, style)), Rect::new(area.x, area.y, width, area.height));
+    }
+}
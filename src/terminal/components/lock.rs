@@ -0,0 +1,148 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::rc::Rc;
+
+use crate::config::Config;
+use crate::terminal::theme::Theme;
+
+pub struct ScreenLock {
+    locked: bool,
+    input: String,
+    wrong_passphrase: bool,
+    config: Rc<Config>,
+}
+
+impl ScreenLock {
+    pub fn new(config: Rc<Config>) -> Self {
+        Self {
+            locked: false,
+            input: String::new(),
+            wrong_passphrase: false,
+            config,
+        }
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    pub fn lock(&mut self) {
+        if self.locked {
+            return;
+        }
+        self.locked = true;
+        self.input.clear();
+        self.wrong_passphrase = false;
+    }
+
+    fn unlock(&mut self) {
+        self.locked = false;
+        self.input.clear();
+        self.wrong_passphrase = false;
+    }
+
+    pub fn on_key_event(&mut self, key: KeyEvent) {
+        let lock_config = &self.config.lock;
+
+        if lock_config.passphrase.is_empty() {
+            if lock_config.unlock_key.matches(&key) {
+                self.unlock();
+            }
+            return;
+        }
+
+        match key.code {
+            KeyCode::Enter => {
+                if self.input == lock_config.passphrase {
+                    self.unlock();
+                } else {
+                    self.wrong_passphrase = true;
+                    self.input.clear();
+                }
+            }
+            KeyCode::Char(c) => self.input.push(c),
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Esc => self.input.clear(),
+            _ => {}
+        }
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        let lock_config = &self.config.lock;
+        let mut lines = vec![locale.tr("Locked after inactivity").to_string()];
+        if lock_config.passphrase.is_empty() {
+            lines.push(locale.format("Press {} to unlock", &[&lock_config.unlock_key.to_string()]));
+        } else {
+            lines.push(locale.format(
+                "Passphrase: {} characters typed, Enter to unlock",
+                &[&self.input.chars().count().to_string()],
+            ));
+            if self.wrong_passphrase {
+                lines.push(locale.tr("Wrong passphrase").to_string());
+            }
+        }
+        lines
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.locked {
+            return;
+        }
+
+        let popup_width = std::cmp::min(50, area.width.saturating_sub(4));
+        let popup_height = std::cmp::min(7, area.height.saturating_sub(4));
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let locale = self.config.locale;
+        let lock_config = &self.config.lock;
+        let mut text = vec![
+            Line::from(Span::styled(
+                locale.tr("Locked after inactivity"),
+                Style::default().fg(theme.warning).add_modifier(Modifier::BOLD),
+            )),
+            Line::from(""),
+        ];
+
+        if lock_config.passphrase.is_empty() {
+            text.push(Line::from(locale.format("Press {} to unlock", &[&lock_config.unlock_key.to_string()])));
+        } else {
+            text.push(Line::from(locale.format(
+                "Passphrase: {}",
+                &[&"*".repeat(self.input.chars().count())],
+            )));
+            if self.wrong_passphrase {
+                text.push(Line::from(Span::styled(
+                    locale.tr("Wrong passphrase"),
+                    Style::default().fg(theme.error),
+                )));
+            }
+        }
+
+        let lock_block = Paragraph::new(text)
+            .style(Style::default().fg(theme.text))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.warning))
+                    .title(locale.tr("Lock")),
+            )
+            .alignment(Alignment::Center);
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(lock_block, popup_area);
+    }
+}
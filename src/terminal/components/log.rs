@@ -1,10 +1,11 @@
+use chrono::Local;
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::layout::{Constraint, Direction, Layout};
 use ratatui::{
     layout::{Alignment, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Block, Borders, Paragraph, Sparkline, Wrap},
     Frame,
 };
 use std::sync::mpsc::Sender;
@@ -14,51 +15,114 @@ use std::time::Duration;
 use std::rc::Rc;
 use std::cell::RefCell;
 
+use crate::config::Config;
+use crate::domain::journal_entry::{priority_name, JournalEntry, LogFilter, PRIORITY_DEBUG, PRIORITY_ERR, PRIORITY_WARNING};
+use crate::domain::log_expression::LogExpression;
+use crate::domain::log_rate::LogRate;
 use crate::domain::service::Service;
+use crate::infrastructure::clipboard::copy_to_clipboard;
+use crate::infrastructure::export::write_log;
 use crate::terminal::app::{Actions, AppEvent};
-use crate::usecases::services_manager::ServicesManager;
+use crate::terminal::components::boot_picker::BootPicker;
+use crate::terminal::components::confirm::Confirmation;
+use crate::terminal::components::unit_tabs::UnitTab;
+use crate::terminal::components::log_export::{LogExport, LogExportRequest};
+use crate::terminal::components::log_expression_prompt::LogExpressionPrompt;
+use crate::terminal::components::search::SearchBar;
+use crate::terminal::theme::Theme;
+use crate::usecases::services_manager::{ServicesManager, LOG_LINES};
 
-enum BorderColor {
-    White,
-    Orange,
-}
-
-impl BorderColor {
-    fn to_color(&self) -> Color {
-        match self {
-            BorderColor::White => Color::White,
-            BorderColor::Orange => Color::Rgb(255, 165, 0),
-        }
-    }
-}
+/// Lines left below the view when the older entries are read, before scrolling reaches the end.
+const LOAD_MARGIN: usize = 200;
+/// Lines kept in the view at most, the older entries being left to the full journal export.
+const MAX_LOADED_LINES: usize = 10 * LOG_LINES as usize;
 
 pub struct ServiceLog {
-    log_paragraph: Option<Paragraph<'static>>,
-    log_block: Option<Block<'static>>,
-    border_color: BorderColor,
+    log_lines: Option<Vec<String>>,
+    /// Priority of each line, `None` for logs not read from the journal.
+    priorities: Vec<Option<u8>>,
+    /// Bytes of the timestamp starting each line, cut off while timestamps are hidden.
+    time_widths: Vec<usize>,
+    /// Units of a merged log, sorted, their tags colored after their position.
+    units: Vec<String>,
+    /// Position in `units` of the unit of each line, `None` outside of merged logs.
+    line_units: Vec<Option<usize>>,
+    filter: LogFilter,
+    /// Terms the lines shown contain or not, applied to each read of the journal.
+    expression: LogExpression,
+    expression_prompt: LogExpressionPrompt,
+    /// Cursor of the oldest entry shown, to read the entries before it.
+    oldest_cursor: Option<String>,
     service_name: String,
     scroll: u16,
+    /// Columns scrolled past while lines are not wrapped.
+    horizontal_scroll: u16,
+    wrap: bool,
+    line_numbers: bool,
+    timestamps: bool,
+    search: SearchBar,
+    /// Anchor and cursor of the lines being selected, as indexes in `log_lines`.
+    selection: Option<(usize, usize)>,
+    export: LogExport,
+    boot_picker: Option<BootPicker>,
+    /// Width of the text at the last render, to find the line at the top of the view.
+    text_width: usize,
+    /// Height of the text at the last render, to scroll to the bottom.
+    text_height: usize,
+    pending_jump: Option<usize>,
+    log_rate: Option<LogRate>,
     sender: Sender<AppEvent>,
     auto_refresh: Arc<Mutex<bool>>,
     usecase: Rc<RefCell<ServicesManager>>,
+    config: Rc<Config>,
+}
+
+/// Cursor to read the entries before `entries` from, none once a page came back short and the
+/// start of the journal or of the time range was reached.
+fn oldest_cursor(entries: &[JournalEntry]) -> Option<String> {
+    if entries.len() < LOG_LINES as usize {
+        return None;
+    }
+    entries.first().and_then(JournalEntry::cursor).map(str::to_string)
 }
 
 impl ServiceLog {
-    pub fn new(sender: Sender<AppEvent>,  usecase: Rc<RefCell<ServicesManager>>) -> Self {
+    pub fn new(sender: Sender<AppEvent>,  usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
         Self {
-            log_paragraph: None,
-            log_block: None,
-            border_color: BorderColor::White,
+            log_lines: None,
+            priorities: Vec::new(),
+            time_widths: Vec::new(),
+            units: Vec::new(),
+            line_units: Vec::new(),
+            filter: LogFilter::default(),
+            expression: LogExpression::default(),
+            expression_prompt: LogExpressionPrompt::default(),
+            oldest_cursor: None,
             service_name: String::new(),
             scroll: 0,
+            horizontal_scroll: 0,
+            wrap: true,
+            line_numbers: false,
+            timestamps: true,
+            search: SearchBar::default(),
+            selection: None,
+            export: LogExport::default(),
+            boot_picker: None,
+            text_width: 1,
+            text_height: 1,
+            pending_jump: None,
+            log_rate: None,
             sender,
             auto_refresh: Arc::new(Mutex::new(false)),
-            usecase
+            usecase,
+            config,
         }
     }
 
-    fn render_loading(&mut self, frame: &mut Frame, area: Rect) {
-        let block = Block::default().borders(Borders::ALL);
+    fn render_loading(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border));
 
         frame.render_widget(block.clone(), area);
 
@@ -81,26 +145,388 @@ impl ServiceLog {
             ])
             .split(vertical[1]);
 
-        let loading = Paragraph::new("Loading...").alignment(Alignment::Center);
+        let loading = Paragraph::new("Loading...")
+            .style(Style::default().fg(theme.text))
+            .alignment(Alignment::Center);
 
         frame.render_widget(loading, horizontal[1]);
     }
 
-    pub fn render(&mut self, frame: &mut Frame, area: Rect) {
-        if self.log_paragraph.is_none() || self.log_block.is_none() {
-            self.render_loading(frame, area);
+    fn render_log_rate(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let Some(log_rate) = &self.log_rate else {
+            return;
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border))
+            .title(self.config.locale.format(
+                " Lines per minute (peak {}, average {}) ",
+                &[&log_rate.peak().to_string(), &format!("{:.1}", log_rate.average())],
+            ));
+        let width = block.inner(area).width as usize;
+        let data = log_rate.per_minute();
+        let visible = &data[data.len().saturating_sub(width)..];
+
+        let sparkline = Sparkline::default()
+            .block(block)
+            .data(visible)
+            .style(Style::default().fg(theme.accent));
+        frame.render_widget(sparkline, area);
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if self.log_lines.is_none() {
+            self.render_loading(frame, area, theme);
+            return;
+        }
+
+        let area = if self.log_rate.is_some() {
+            let [rate_area, log_area] =
+                Layout::vertical([Constraint::Length(5), Constraint::Min(0)]).areas(area);
+            self.render_log_rate(frame, rate_area, theme);
+            log_area
+        } else {
+            area
+        };
+        let Some(log_lines) = &self.log_lines else {
+            return;
+        };
+
+        let is_refreshing = self.auto_refresh.lock().map(|r| *r).unwrap_or(false);
+        let border_color = if is_refreshing {
+            theme.highlight_border
+        } else {
+            theme.border
+        };
+        let filter = match self.filters() {
+            Some(filters) => format!("[{}] ", filters),
+            None => String::new(),
+        };
+        let log_block = Block::default()
+            .title(self.config.locale.format(
+                " {} logs (newest at the top) {}{}",
+                &[&self.service_name, &filter, &self.search.match_counter()],
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color))
+            .title_alignment(Alignment::Center);
+
+        let inner = log_block.inner(area);
+        frame.render_widget(log_block, area);
+
+        let [text_area, search_area] = if self.search.is_visible() {
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner)
+        } else {
+            [inner, Rect::default()]
+        };
+
+        self.text_width = text_area.width.max(1) as usize;
+        self.text_height = text_area.height.max(1) as usize;
+        if let Some(line_index) = self.pending_jump.take() {
+            let rows_before: usize = log_lines
+                .iter()
+                .enumerate()
+                .take(line_index)
+                .map(|(index, line)| self.rows(index, line))
+                .sum();
+            self.scroll = u16::try_from(rows_before).unwrap_or(u16::MAX);
+        }
+
+        let selected = self.selected_range();
+        let gutter = self.gutter_width();
+        let lines: Vec<Line> = log_lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| {
+                let mut line = self.styled_line(index, self.shown_text(index, line), theme);
+                if self.line_numbers {
+                    let number = format!("{:>width$} ", index + 1, width = gutter - 1);
+                    line.spans.insert(0, Span::styled(number, Style::default().fg(theme.muted)));
+                }
+                let line = match self.priorities.get(index).copied().flatten() {
+                    Some(priority) if priority <= PRIORITY_ERR => line.patch_style(Style::default().fg(theme.error)),
+                    Some(PRIORITY_WARNING) => line.patch_style(Style::default().fg(theme.warning)),
+                    Some(PRIORITY_DEBUG) => line.patch_style(Style::default().fg(theme.muted)),
+                    _ => line,
+                };
+                if selected.as_ref().is_some_and(|range| range.contains(&index)) {
+                    line.patch_style(Style::default().bg(theme.selection_bg).fg(theme.selection_fg))
+                } else {
+                    line
+                }
+            })
+            .collect();
+
+        let mut paragraph = Paragraph::new(lines)
+            .style(Style::default().fg(theme.text))
+            .scroll((self.scroll, if self.wrap { 0 } else { self.horizontal_scroll }));
+        if self.wrap {
+            paragraph = paragraph.wrap(Wrap { trim: false });
+        }
+
+        frame.render_widget(paragraph, text_area);
+
+        if self.search.is_visible() {
+            self.search.render(frame, search_area, theme);
+        }
+        self.export.render(frame, inner, theme);
+        self.expression_prompt.render(frame, inner, theme);
+        if let Some(boot_picker) = &self.boot_picker {
+            boot_picker.render(frame, inner, theme);
+        }
+    }
+
+    /// Log lines from the current scroll position, newest first.
+    pub fn linear_text(&mut self) -> Vec<String> {
+        if let Some(line_index) = self.pending_jump.take() {
+            self.scroll = u16::try_from(line_index).unwrap_or(u16::MAX);
+        }
+        let locale = self.config.locale;
+        let Some(log_lines) = &self.log_lines else {
+            return vec![locale.tr("Loading logs").to_string()];
+        };
+
+        let mut lines = Vec::new();
+        if let Some(log_rate) = &self.log_rate {
+            lines.push(locale.format(
+                "Lines per minute: peak {}, average {}",
+                &[&log_rate.peak().to_string(), &format!("{:.1}", log_rate.average())],
+            ));
+        }
+        if let Some(filters) = self.filters() {
+            lines.push(locale.format("Showing {}", &[&filters]));
+        }
+        if self.expression_prompt.is_open() {
+            lines.extend(self.expression_prompt.linear_text());
+        }
+        if self.export.is_open() {
+            lines.extend(self.export.linear_text());
+        }
+        if let Some(boot_picker) = &self.boot_picker {
+            lines.extend(boot_picker.linear_text());
+        }
+        if let Some(range) = self.selected_range() {
+            lines.push(locale.format(
+                "Selected lines {} to {}",
+                &[&(range.start() + 1).to_string(), &(range.end() + 1).to_string()],
+            ));
+        }
+        if self.search.is_visible() {
+            lines.push(self.search.linear_text());
+        }
+        lines.extend(
+            log_lines
+                .iter()
+                .enumerate()
+                .skip(self.scroll as usize)
+                .map(|(index, line)| {
+                    let line = self.shown_text(index, line);
+                    match self.priorities.get(index).copied().flatten() {
+                        Some(priority) if priority <= PRIORITY_WARNING => {
+                            locale.format(
+                                "Line {}, {}: {}",
+                                &[&(index + 1).to_string(), locale.tr(priority_name(priority)), line],
+                            )
+                        }
+                        _ => locale.format("Line {}: {}", &[&(index + 1).to_string(), line]),
+                    }
+                }),
+        );
+        lines
+    }
+
+    fn jump_to(&mut self, line_index: Option<usize>) {
+        if line_index.is_some() {
+            self.pending_jump = line_index;
+        }
+    }
+
+    /// A line as shown, without its timestamp while they are hidden.
+    fn shown_text<'a>(&self, index: usize, line: &'a str) -> &'a str {
+        if self.timestamps {
+            return line;
+        }
+        let width = self.time_widths.get(index).copied().unwrap_or(0);
+        line.get(width..).unwrap_or(line)
+    }
+
+    /// The line with the search matches highlighted and, in a merged log, its unit tag colored.
+    fn styled_line(&self, index: usize, text: &str, theme: &Theme) -> Line<'static> {
+        let Some(unit) = self.line_units.get(index).copied().flatten() else {
+            return self.search.highlight(index, text, theme);
+        };
+        let tag = format!("[{}] ", self.units[unit]);
+        let start = if self.timestamps { self.time_widths.get(index).copied().unwrap_or(0) } else { 0 };
+        let Some(rest) = text.get(start..).and_then(|rest| rest.strip_prefix(tag.as_str())) else {
+            return self.search.highlight(index, text, theme);
+        };
+        let colors = [theme.accent, theme.title, theme.active, theme.activating, theme.highlight_border, theme.warning];
+        let highlighted = self.search.highlight(index, rest, theme);
+        let mut spans = vec![
+            Span::raw(text[..start].to_string()),
+            Span::styled(tag, Style::default().fg(colors[unit % colors.len()]).add_modifier(Modifier::BOLD)),
+        ];
+        spans.extend(highlighted.spans);
+        Line::from(spans).style(highlighted.style)
+    }
+
+    /// Columns taken by the line numbers and the space after them, 0 while they are hidden.
+    fn gutter_width(&self) -> usize {
+        if !self.line_numbers {
+            return 0;
+        }
+        let count = self.log_lines.as_ref().map_or(0, Vec::len);
+        count.max(1).to_string().len() + 1
+    }
+
+    /// Rows the line takes in the view, one when lines are not wrapped.
+    fn rows(&self, index: usize, line: &str) -> usize {
+        if !self.wrap {
+            return 1;
+        }
+        let columns = self.gutter_width() + self.shown_text(index, line).chars().count();
+        columns.div_ceil(self.text_width).max(1)
+    }
+
+    /// Changes how lines are shown, keeping the line at the top of the view.
+    fn change_layout(&mut self, change: impl FnOnce(&mut Self)) {
+        let top = self.top_line();
+        change(self);
+        self.horizontal_scroll = 0;
+        self.pending_jump = Some(top);
+    }
+
+    fn scroll_to_bottom(&mut self) {
+        let Some(log_lines) = &self.log_lines else {
+            return;
+        };
+        let rows: usize = log_lines.iter().enumerate().map(|(index, line)| self.rows(index, line)).sum();
+        self.scroll = u16::try_from(rows.saturating_sub(self.text_height)).unwrap_or(u16::MAX);
+    }
+
+    /// Index of the line at the top of the view, from the rows scrolled past.
+    fn top_line(&self) -> usize {
+        let Some(log_lines) = &self.log_lines else {
+            return 0;
+        };
+        let mut rows = 0;
+        for (index, line) in log_lines.iter().enumerate() {
+            rows += self.rows(index, line);
+            if rows > self.scroll as usize {
+                return index;
+            }
+        }
+        log_lines.len().saturating_sub(1)
+    }
+
+    fn selected_range(&self) -> Option<std::ops::RangeInclusive<usize>> {
+        let (anchor, cursor) = self.selection?;
+        Some(anchor.min(cursor)..=anchor.max(cursor))
+    }
+
+    fn toggle_selection(&mut self) {
+        self.selection = match self.selection {
+            Some(_) => None,
+            None if self.log_lines.as_ref().is_some_and(|lines| !lines.is_empty()) => {
+                let top = self.top_line();
+                Some((top, top))
+            }
+            None => None,
+        };
+    }
+
+    fn move_cursor(&mut self, delta: isize) {
+        let (Some((anchor, cursor)), Some(log_lines)) = (self.selection, &self.log_lines) else {
+            return;
+        };
+        let last = log_lines.len().saturating_sub(1) as isize;
+        let cursor = (cursor as isize + delta).clamp(0, last) as usize;
+        self.selection = Some((anchor, cursor));
+        self.jump_to(Some(cursor));
+    }
+
+    /// The selected lines, or the one at the top of the view, oldest first.
+    fn lines_to_copy(&self) -> Vec<String> {
+        let Some(log_lines) = &self.log_lines else {
+            return Vec::new();
+        };
+        let range = self.selected_range().unwrap_or_else(|| {
+            let top = self.top_line();
+            top..=top
+        });
+        log_lines
+            .get(range)
+            .map(|lines| lines.iter().rev().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn copy_lines(&mut self) {
+        let lines = self.lines_to_copy();
+        if lines.is_empty() {
             return;
         }
+        let locale = self.config.locale;
+        let event = match copy_to_clipboard(&lines.join("\n")) {
+            Ok(()) => AppEvent::Notice(locale.format("Copied {} lines to the clipboard", &[&lines.len().to_string()])),
+            Err(e) => AppEvent::Error(locale.format("Could not copy to the clipboard: {}", &[&e.to_string()]).into()),
+        };
+        self.sender.send(event).unwrap();
+        self.selection = None;
+    }
 
-        let log_block = self.log_block.clone().unwrap();
-        let paragraph = self
-            .log_paragraph
-            .clone()
-            .unwrap()
-            .scroll((self.scroll, 0))
-            .block(log_block);
+    fn open_export(&mut self) {
+        if self.log_lines.is_none() {
+            return;
+        }
+        let file_name = format!(
+            "{}-{}.log",
+            self.service_name.replace(", ", "+"),
+            Local::now().format("%Y%m%d-%H%M%S")
+        );
+        self.export.open(self.config.export.directory.join(file_name), self.config.locale);
+    }
+
+    fn save_log(&mut self, request: LogExportRequest) {
+        if request.full && !self.units.is_empty() {
+            let error = self
+                .config
+                .locale
+                .tr("The full journal is saved one unit at a time, not from a merged log")
+                .to_string();
+            self.sender.send(AppEvent::Error(error.into())).unwrap();
+            return;
+        }
+        let lines = if request.full {
+            match self.usecase.borrow().get_full_log(&self.service_name) {
+                Ok(log) => log.lines().map(str::to_string).collect(),
+                Err(e) => {
+                    self.sender.send(AppEvent::Error(e.into())).unwrap();
+                    return;
+                }
+            }
+        } else {
+            self.log_lines
+                .iter()
+                .flatten()
+                .rev()
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let event = match write_log(&request.path, &lines) {
+            Ok(()) => AppEvent::Notice(self.config.locale.format(
+                "Saved {} lines to {}",
+                &[&lines.len().to_string(), &request.path.display().to_string()],
+            )),
+            Err(e) => AppEvent::Error(e.into()),
+        };
+        self.sender.send(event).unwrap();
+    }
 
-        frame.render_widget(paragraph, area);
+    fn update_search_matches(&mut self) {
+        if let Some(lines) = &self.log_lines {
+            self.search.update_matches(lines.iter().map(String::as_str));
+        }
     }
 
     fn toogle_auto_refresh(&mut self) {
@@ -116,53 +542,128 @@ impl ServiceLog {
     }
 
     fn set_auto_refresh(&mut self, value: bool) {
-        self.border_color = if value {
-            BorderColor::Orange
-        } else {
-            BorderColor::White
-        };
-
-        self.log_block = Some(
-            Block::default()
-                .title(format!(" {} logs (newest at the top) ", self.service_name))
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(self.border_color.to_color()))
-                .title_alignment(Alignment::Center),
-        );
-
         if let Ok(mut auto) = self.auto_refresh.lock() {
             *auto = value;
         }
     }
 
+    /// Whether keys are text typed in the search.
+    pub fn is_typing(&self) -> bool {
+        self.search.is_editing() || self.export.is_open() || self.expression_prompt.is_open() || self.boot_picker.is_some()
+    }
+
     pub fn on_key_event(&mut self, key: KeyEvent) {
-        match key.code {
-            KeyCode::Right => {
+        if self.export.is_open() {
+            if let Some(request) = self.export.on_key_event(key) {
+                self.save_log(request);
+            }
+            return;
+        }
+        if self.expression_prompt.is_open() {
+            if let Some(expression) = self.expression_prompt.on_key_event(key) {
+                self.expression = LogExpression::parse(&expression);
+                self.refetch();
+            }
+            return;
+        }
+        if let Some(boot_picker) = &mut self.boot_picker {
+            match boot_picker.on_key_event(key) {
+                Confirmation::Pending => {}
+                Confirmation::Confirmed => {
+                    self.filter.boot = boot_picker.choice();
+                    self.boot_picker = None;
+                    self.refetch();
+                }
+                Confirmation::Cancelled => self.boot_picker = None,
+            }
+            return;
+        }
+        if self.search.is_editing() {
+            if self.search.on_key_event(key) {
+                self.update_search_matches();
+                self.jump_to(self.search.current_line());
+            }
+            return;
+        }
+
+        if let Some(tab) = UnitTab::from_key(&key).filter(|tab| *tab != UnitTab::Logs) {
+            self.reset();
+            self.sender.send(AppEvent::Action(tab.action())).unwrap();
+            return;
+        }
+
+        let config = self.config.clone();
+        let keys = &config.keybindings;
+        match key {
+            k if self.selection.is_some() && (k.code == KeyCode::Esc || keys.back.matches(&k)) => {
+                self.selection = None;
+            }
+            k if self.selection.is_some() && keys.up.matches(&k) => self.move_cursor(-1),
+            k if self.selection.is_some() && keys.down.matches(&k) => self.move_cursor(1),
+            k if self.selection.is_some() && keys.page_up.matches(&k) => self.move_cursor(-10),
+            k if self.selection.is_some() && keys.page_down.matches(&k) => self.move_cursor(10),
+            k if keys.mark.matches(&k) => self.toggle_selection(),
+            k if keys.copy_lines.matches(&k) => self.copy_lines(),
+            k if keys.save_log.matches(&k) => self.open_export(),
+            k if keys.older_log.matches(&k) => self.load_older(true),
+            k if keys.search.matches(&k) => self.search.start(self.config.locale),
+            k if keys.next_match.matches(&k) => {
+                let line = self.search.next_match();
+                self.jump_to(line);
+            }
+            k if keys.previous_match.matches(&k) => {
+                let line = self.search.previous_match();
+                self.jump_to(line);
+            }
+            k if keys.next_tab.matches(&k) => {
                 self.reset();
                 self.sender
-                    .send(AppEvent::Action(Actions::GoDetails))
+                    .send(AppEvent::Action(UnitTab::Logs.next().action()))
                     .unwrap();
             }
-            KeyCode::Left => {
+            k if keys.previous_tab.matches(&k) => {
                 self.reset();
                 self.sender
-                    .send(AppEvent::Action(Actions::GoDetails))
+                    .send(AppEvent::Action(UnitTab::Logs.previous().action()))
                     .unwrap();
             }
-            KeyCode::Up => {
+            k if keys.wrap_lines.matches(&k) => self.change_layout(|log| log.wrap = !log.wrap),
+            k if keys.line_numbers.matches(&k) => self.change_layout(|log| log.line_numbers = !log.line_numbers),
+            k if keys.timestamps.matches(&k) => self.change_layout(|log| log.timestamps = !log.timestamps),
+            k if keys.log_top.matches(&k) => self.scroll = 0,
+            k if keys.log_bottom.matches(&k) => self.scroll_to_bottom(),
+            k if !self.wrap && keys.scroll_left.matches(&k) => {
+                self.horizontal_scroll = self.horizontal_scroll.saturating_sub(10);
+            }
+            k if !self.wrap && keys.scroll_right.matches(&k) => {
+                self.horizontal_scroll = self.horizontal_scroll.saturating_add(10);
+            }
+            k if keys.up.matches(&k) => {
                 self.scroll = self.scroll.saturating_sub(1);
             }
-            KeyCode::Down => {
+            k if keys.down.matches(&k) => {
                 self.scroll += 1;
+                self.load_older_near_end();
             }
-            KeyCode::PageUp => {
+            k if keys.page_up.matches(&k) => {
                 self.scroll = self.scroll.saturating_sub(10);
             }
-            KeyCode::PageDown => {
+            k if keys.page_down.matches(&k) => {
                 self.scroll += 10;
+                self.load_older_near_end();
+            }
+            k if keys.auto_refresh.matches(&k) => self.toogle_auto_refresh(),
+            k if keys.log_priority.matches(&k) => {
+                self.filter.next_min_priority();
+                self.refetch();
+            }
+            k if keys.log_since.matches(&k) => {
+                self.filter.next_since();
+                self.refetch();
             }
-            KeyCode::Char('a') => self.toogle_auto_refresh(),
-            KeyCode::Char('q') => {
+            k if keys.log_boot.matches(&k) => self.open_boot_picker(),
+            k if keys.log_expression.matches(&k) => self.expression_prompt.open(self.expression.text(), self.config.locale),
+            k if keys.back.matches(&k) => {
                 self.reset();
                 self.exit();
             }
@@ -170,24 +671,42 @@ impl ServiceLog {
         }
     }
 
-    pub fn shortcuts(&mut self) -> Vec<Line<'_>> {
+    pub fn shortcuts(&mut self, theme: &Theme) -> Vec<Line<'_>> {
         let is_refreshing = self.auto_refresh.lock().map(|r| *r).unwrap_or(false);
         let mut auto_refresh_label = "Enable auto-refresh";
         if is_refreshing {
             auto_refresh_label = "Disable auto-refresh";
         }
 
+        let locale = self.config.locale;
+        let keys = &self.config.keybindings;
         let help_text = vec![
             Line::from(vec![Span::styled(
-                "Actions",
+                locale.tr("Actions"),
                 Style::default()
-                    .fg(Color::LightMagenta)
+                    .fg(theme.title)
                     .add_modifier(Modifier::BOLD),
             )]),
-            Line::from(format!(
-                "Scroll: ↑/↓ | Switch tabs: ←/→ | {}: a | Go back: q",
-                auto_refresh_label
-            )),
+            Line::from(locale.shortcuts(&[
+                ("Scroll", format!("{}/{}", keys.up, keys.down)),
+                ("Top/bottom", format!("{}/{}", keys.log_top, keys.log_bottom)),
+                ("Switch tabs", format!("{}/{} {} 1-8", keys.previous_tab, keys.next_tab, locale.tr("or"))),
+                (auto_refresh_label, keys.auto_refresh.to_string()),
+                ("Search", keys.search.to_string()),
+                ("Next/previous match", format!("{}/{}", keys.next_match, keys.previous_match)),
+                ("Minimum priority", keys.log_priority.to_string()),
+                ("Since", keys.log_since.to_string()),
+                ("Boot", keys.log_boot.to_string()),
+                ("Filter lines", keys.log_expression.to_string()),
+                ("Older entries", keys.older_log.to_string()),
+                ("Wrap", keys.wrap_lines.to_string()),
+                ("Line numbers", keys.line_numbers.to_string()),
+                ("Timestamps", keys.timestamps.to_string()),
+                ("Select lines", keys.mark.to_string()),
+                ("Copy", keys.copy_lines.to_string()),
+                ("Save", keys.save_log.to_string()),
+                ("Go back", keys.back.to_string()),
+            ])),
         ];
 
         help_text
@@ -201,7 +720,20 @@ impl ServiceLog {
     pub fn reset(&mut self) {
         self.set_auto_refresh(false);
         self.scroll = 0;
-        self.log_paragraph = None;
+        self.log_lines = None;
+        self.priorities.clear();
+        self.time_widths.clear();
+        self.units.clear();
+        self.line_units.clear();
+        self.horizontal_scroll = 0;
+        self.oldest_cursor = None;
+        self.log_rate = None;
+        self.search.clear();
+        self.selection = None;
+        self.export = LogExport::default();
+        self.boot_picker = None;
+        self.expression_prompt.close();
+        self.pending_jump = None;
     }
 
     fn exit(&self) {
@@ -211,9 +743,10 @@ impl ServiceLog {
     pub fn auto_refresh_thread(&mut self) {
         let auto_refresh = Arc::clone(&self.auto_refresh);
         let sender = self.sender.clone();
+        let interval = Duration::from_millis(self.config.refresh_interval);
         thread::spawn(move || {
             loop {
-                thread::sleep(Duration::from_millis(1000));
+                thread::sleep(interval);
                 if let Ok(is_active) = auto_refresh.lock() {
                     if *is_active {
                         sender.send(AppEvent::Action(Actions::RefreshLog)).unwrap();
@@ -225,32 +758,147 @@ impl ServiceLog {
         });
     }
 
-    pub fn fetch_log_and_dispatch(&mut self, service: Service) {
+    /// The journal filter and the expression, e.g. "warning and above, \"error !healthcheck\"",
+    /// `None` when the whole log is shown.
+    fn filters(&self) -> Option<String> {
+        let mut filters = Vec::new();
+        if self.filter.is_active() {
+            filters.push(self.filter.describe());
+        }
+        if !self.expression.is_empty() {
+            filters.push(format!("\"{}\"", self.expression.text()));
+        }
+        (!filters.is_empty()).then(|| filters.join(", "))
+    }
+
+    /// Lists the boots of the journal to pick the one to show the log of.
+    fn open_boot_picker(&mut self) {
+        let boots = self.usecase.borrow().get_journal_boots();
+        match boots {
+            Ok(boots) => self.boot_picker = Some(BootPicker::new(boots, self.filter.boot, self.config.locale)),
+            Err(e) => self.sender.send(AppEvent::Error(e.into())).unwrap(),
+        }
+    }
+
+    /// Fetches the log again with the new filter, from the top.
+    fn refetch(&mut self) {
+        self.scroll = 0;
+        self.pending_jump = None;
+        self.sender.send(AppEvent::Action(Actions::RefreshLog)).unwrap();
+    }
+
+    /// Reads the older entries as scrolling gets close to the oldest line shown, so that only
+    /// the pages scrolled through are kept.
+    fn load_older_near_end(&mut self) {
+        let loaded = self.log_lines.as_ref().map_or(0, Vec::len);
+        if self.oldest_cursor.is_some() && loaded < MAX_LOADED_LINES && self.top_line() + LOAD_MARGIN >= loaded {
+            self.load_older(false);
+        }
+    }
+
+    /// Adds the entries logged before the oldest one shown at the bottom of the log, stopping
+    /// the auto-refresh which would replace them with the last entries. Only `older_log`
+    /// reports that there is nothing more to read.
+    fn load_older(&mut self, explicit: bool) {
+        let Some(cursor) = self.oldest_cursor.clone() else {
+            if explicit {
+                self.sender.send(AppEvent::Notice(self.config.locale.tr("No older entries").to_string())).unwrap();
+            }
+            return;
+        };
+        if self.log_lines.as_ref().is_some_and(|lines| lines.len() >= MAX_LOADED_LINES) {
+            if explicit {
+                self.sender
+                    .send(AppEvent::Notice(self.config.locale.format(
+                        "At most {} lines are kept, save the full journal with {}",
+                        &[&MAX_LOADED_LINES.to_string(), &self.config.keybindings.save_log.to_string()],
+                    )))
+                    .unwrap();
+            }
+            return;
+        }
+        let entries = self.usecase.borrow().get_older_log(&self.service_name, &cursor, &self.filter);
+        let entries = match entries {
+            Ok(entries) => entries,
+            Err(e) => {
+                self.sender.send(AppEvent::Error(e.into())).unwrap();
+                return;
+            }
+        };
+        self.oldest_cursor = oldest_cursor(&entries);
+        if entries.is_empty() {
+            if explicit {
+                self.sender.send(AppEvent::Notice(self.config.locale.tr("No older entries").to_string())).unwrap();
+            }
+            return;
+        }
+        let entries = self.matching(entries);
+        self.set_auto_refresh(false);
+        self.priorities.extend(entries.iter().rev().map(JournalEntry::priority));
+        self.time_widths.extend(entries.iter().rev().map(JournalEntry::time_width));
+        self.log_lines
+            .get_or_insert_with(Vec::new)
+            .extend(entries.iter().rev().map(JournalEntry::line));
+        self.update_search_matches();
+        if explicit {
+            self.sender
+                .send(AppEvent::Notice(
+                    self.config.locale.format("Read {} older entries", &[&entries.len().to_string()]),
+                ))
+                .unwrap();
+        }
+    }
+
+    /// Fetches the log of the service, or the log of several services merged by time.
+    pub fn fetch_log_and_dispatch(&mut self, services: Vec<Service>) {
         let event_tx = self.sender.clone();
-        if let Ok(log) = self.usecase.borrow().get_log(&service) {
-            event_tx
-                .send(AppEvent::Action(Actions::Updatelog((
-                    service.name().to_string(),
-                    log,
-                ))))
-                .expect("Failed to send Updatelog event");
+        let usecase = self.usecase.borrow();
+        // The error is shown in place of the log, e.g. without permission to read the journal.
+        let entries = match services.as_slice() {
+            [service] => {
+                self.log_rate = usecase.get_log_rate(service).ok();
+                usecase.get_log(service, &self.filter)
+            }
+            _ => {
+                self.log_rate = None;
+                usecase.get_merged_log(&services, &self.filter)
+            }
         }
+        .unwrap_or_else(|e| vec![JournalEntry::raw(&e.to_string())]);
+        let name = services.iter().map(Service::name).collect::<Vec<_>>().join(", ");
+        event_tx
+            .send(AppEvent::Action(Actions::Updatelog((name, entries))))
+            .expect("Failed to send Updatelog event");
     }
 
-    pub fn update(&mut self, service_name: String, log: String) {
-        self.service_name = service_name;
-        self.log_paragraph =
-            Some(Paragraph::new(self.reversed_log(log)).wrap(Wrap { trim: false }));
-        self.log_block = Some(
-            Block::default()
-                .title(format!(" {} logs (newest at the top) ", self.service_name))
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(self.border_color.to_color()))
-                .title_alignment(Alignment::Center),
-        );
+    /// The entries whose line matches the expression.
+    fn matching(&self, entries: Vec<JournalEntry>) -> Vec<JournalEntry> {
+        entries.into_iter().filter(|entry| self.expression.matches(&entry.line())).collect()
     }
 
-    pub fn reversed_log(&self, log: String) -> String {
-        log.lines().rev().collect::<Vec<_>>().join("\n")
+    pub fn update(&mut self, service_name: String, entries: Vec<JournalEntry>) {
+        self.service_name = service_name;
+        // The cursors of a merged log belong to different units, older pages are read one unit at a time.
+        self.oldest_cursor = if entries.iter().any(|entry| entry.unit().is_some()) { None } else { oldest_cursor(&entries) };
+        let entries = self.matching(entries);
+        self.priorities = entries.iter().rev().map(JournalEntry::priority).collect();
+        self.time_widths = entries.iter().rev().map(JournalEntry::time_width).collect();
+        let mut units: Vec<String> = entries.iter().filter_map(JournalEntry::unit).map(str::to_string).collect();
+        units.sort();
+        units.dedup();
+        self.line_units = entries
+            .iter()
+            .rev()
+            .map(|entry| entry.unit().and_then(|unit| units.iter().position(|known| known == unit)))
+            .collect();
+        self.units = units;
+        self.log_lines = Some(entries.iter().rev().map(JournalEntry::line).collect());
+        // A refresh may have fewer lines, e.g. after changing the filter.
+        let last = entries.len().saturating_sub(1);
+        self.selection = self
+            .selection
+            .filter(|_| !entries.is_empty())
+            .map(|(anchor, cursor)| (anchor.min(last), cursor.min(last)));
+        self.update_search_matches();
     }
 }
@@ -0,0 +1,101 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Position, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::path::PathBuf;
+
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+const HELP: &str = "Save: Enter | Displayed/full journal: Tab | Cancel: Esc";
+
+/// Where to save the log, and whether to save the lines displayed or the full journal of the unit.
+pub struct LogExportRequest {
+    pub path: PathBuf,
+    pub full: bool,
+}
+
+/// File path prompt saving the log of the log view.
+#[derive(Default)]
+pub struct LogExport {
+    open: bool,
+    input: String,
+    full: bool,
+    locale: Locale,
+}
+
+impl LogExport {
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Opens with the suggested path, saving the displayed lines.
+    pub fn open(&mut self, path: PathBuf, locale: Locale) {
+        self.open = true;
+        self.input = path.display().to_string();
+        self.full = false;
+        self.locale = locale;
+    }
+
+    fn what(&self) -> &str {
+        self.locale.tr(if self.full { "full journal" } else { "displayed lines" })
+    }
+
+    /// Handles a key, returning what to save once the path is confirmed.
+    pub fn on_key_event(&mut self, key: KeyEvent) -> Option<LogExportRequest> {
+        match key.code {
+            KeyCode::Esc => self.open = false,
+            KeyCode::Tab | KeyCode::BackTab => self.full = !self.full,
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Char(c) => self.input.push(c),
+            KeyCode::Enter if !self.input.trim().is_empty() => {
+                self.open = false;
+                return Some(LogExportRequest {
+                    path: PathBuf::from(self.input.trim()),
+                    full: self.full,
+                });
+            }
+            _ => {}
+        }
+        None
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        vec![
+            self.locale.format("Save the {} to: {}", &[self.what(), &self.input]),
+            self.locale.tr(HELP).to_string(),
+        ]
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open || area.height < 3 {
+            return;
+        }
+
+        let prompt_area = Rect::new(area.x, area.bottom() - 3, area.width, 3);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.highlight_border))
+            .title(self.locale.format(" Save the {} to ", &[self.what()]))
+            .title_bottom(Line::from(format!(" {} ", self.locale.tr(HELP))).right_aligned());
+        let inner = block.inner(prompt_area);
+        frame.render_widget(Clear, prompt_area);
+        frame.render_widget(block, prompt_area);
+
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(self.input.as_str(), Style::default().fg(theme.text)))),
+            inner,
+        );
+        #[allow(clippy::cast_possible_truncation)]
+        frame.set_cursor_position(Position::new(
+            (inner.x + self.input.chars().count() as u16).min(inner.right().saturating_sub(1)),
+            inner.y,
+        ));
+    }
+}
@@ -0,0 +1,83 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Position, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+const HELP: &str = "Apply: Enter (empty to clear) | Cancel: Esc";
+
+/// Prompt for the terms the lines of the log view must contain, "!" before the ones they must
+/// not, starting from the current expression.
+#[derive(Default)]
+pub struct LogExpressionPrompt {
+    open: bool,
+    input: String,
+    locale: Locale,
+}
+
+impl LogExpressionPrompt {
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    pub fn open(&mut self, expression: &str, locale: Locale) {
+        self.open = true;
+        self.input = expression.to_string();
+        self.locale = locale;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    /// The expression to apply, once Enter is pressed.
+    pub fn on_key_event(&mut self, key: KeyEvent) -> Option<String> {
+        match key.code {
+            KeyCode::Esc => self.open = false,
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Char(c) => self.input.push(c),
+            KeyCode::Enter => {
+                self.open = false;
+                return Some(self.input.clone());
+            }
+            _ => {}
+        }
+        None
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        vec![self.locale.format("Filter lines: {}", &[&self.input]), self.locale.tr(HELP).to_string()]
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open || area.height < 3 {
+            return;
+        }
+
+        let prompt_area = Rect::new(area.x, area.bottom() - 3, area.width, 3);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.highlight_border))
+            .title(self.locale.tr(" Filter lines, e.g. error !healthcheck "))
+            .title_bottom(Line::from(format!(" {} ", self.locale.tr(HELP))).right_aligned());
+        let inner = block.inner(prompt_area);
+        frame.render_widget(Clear, prompt_area);
+        frame.render_widget(block, prompt_area);
+
+        let line = Line::from(Span::styled(self.input.as_str(), Style::default().fg(theme.text)));
+        frame.render_widget(Paragraph::new(line), inner);
+        #[allow(clippy::cast_possible_truncation)]
+        frame.set_cursor_position(Position::new(
+            (inner.x + self.input.chars().count() as u16).min(inner.right().saturating_sub(1)),
+            inner.y,
+        ));
+    }
+}
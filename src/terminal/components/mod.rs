@@ -1,4 +1,58 @@
+pub mod batch;
+pub mod clone_unit;
+pub mod column_picker;
+pub mod boot_performance;
+pub mod boot_picker;
+pub mod command_line;
+pub mod crash_loops;
+pub mod confirm;
+pub mod copy_menu;
+pub mod dependencies;
+pub mod description_prompt;
+pub mod documentation;
 pub mod details;
+pub mod environment_prompt;
+pub mod error_history;
+pub mod failed_units;
+pub mod failure_alerts;
 pub mod filter;
+pub mod group_browser;
+pub mod help;
+pub mod instantiate;
+pub mod hosts;
+pub mod limits_form;
 pub mod list;
+pub mod list_refresh;
+pub mod lock;
 pub mod log;
+pub mod log_export;
+pub mod log_expression_prompt;
+pub mod new_unit;
+pub mod notifications;
+pub mod overlay;
+pub mod path_lookup;
+pub mod preview;
+pub mod processes;
+pub mod quick_actions;
+pub mod reload_diff;
+pub mod schedules;
+pub mod search;
+pub mod security_overview;
+pub mod slice_browser;
+pub mod session_changes;
+pub mod setup_wizard;
+pub mod signal;
+pub mod snapshot_diff;
+pub mod status_bar;
+pub mod system_summary;
+pub mod targets;
+pub mod timings;
+pub mod timeline;
+pub mod toasts;
+pub mod top_talkers;
+pub mod transient_unit;
+pub mod type_ahead;
+pub mod undo;
+pub mod unit_file_search;
+pub mod unit_tabs;
+pub mod unit_verify;
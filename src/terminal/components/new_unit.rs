@@ -0,0 +1,268 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::domain::unit_template::{NewUnit, UnitTemplate};
+use crate::terminal::components::confirm::Confirmation;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Field {
+    Template,
+    Name,
+    /// Index of a parameter of the template.
+    Parameter(usize),
+    EnableAndStart,
+}
+
+/// Form writing the units of a template, e.g. a service running a script, with a timer or not.
+pub struct NewUnitForm {
+    templates: Vec<UnitTemplate>,
+    template_index: usize,
+    session: bool,
+    unit: NewUnit,
+    enable_and_start: bool,
+    selected: usize,
+    error: Option<String>,
+    locale: Locale,
+}
+
+impl NewUnitForm {
+    /// `templates` must not be empty; `session` picks default.target as install target.
+    pub fn new(templates: Vec<UnitTemplate>, session: bool, locale: Locale) -> Self {
+        let unit = NewUnit::new(templates[0].clone(), session);
+        Self {
+            templates,
+            template_index: 0,
+            session,
+            unit,
+            enable_and_start: false,
+            selected: 0,
+            error: None,
+            locale,
+        }
+    }
+
+    pub fn unit(&self) -> &NewUnit {
+        &self.unit
+    }
+
+    pub fn enable_and_start(&self) -> bool {
+        self.enable_and_start
+    }
+
+    fn fields(&self) -> Vec<Field> {
+        let mut fields = vec![Field::Template, Field::Name];
+        fields.extend((0..self.unit.values.len()).map(Field::Parameter));
+        fields.push(Field::EnableAndStart);
+        fields
+    }
+
+    fn field(&self) -> Field {
+        self.fields()[self.selected]
+    }
+
+    fn label(&self, field: Field) -> &str {
+        self.locale.tr(match field {
+            Field::Template => "Template",
+            Field::Name => "Name",
+            Field::Parameter(index) => self.unit.template().parameters[index].label(),
+            Field::EnableAndStart => "Enable and start",
+        })
+    }
+
+    /// Whether the field is picked with Left/Right rather than typed.
+    fn is_choice(&self, field: Field) -> bool {
+        match field {
+            Field::Template | Field::EnableAndStart => true,
+            Field::Name => false,
+            Field::Parameter(index) => !self.unit.template().parameters[index].choices.is_empty(),
+        }
+    }
+
+    fn text_mut(&mut self) -> Option<&mut String> {
+        match self.field() {
+            Field::Name => Some(&mut self.unit.name),
+            field @ Field::Parameter(index) if !self.is_choice(field) => Some(&mut self.unit.values[index]),
+            _ => None,
+        }
+    }
+
+    fn value(&self, field: Field) -> String {
+        match field {
+            Field::Template => format!("< {} >", self.unit.template().name),
+            Field::Name => self.unit.name.clone(),
+            Field::Parameter(index) if self.is_choice(field) => format!("< {} >", self.unit.values[index]),
+            Field::Parameter(index) => self.unit.values[index].clone(),
+            Field::EnableAndStart => if self.enable_and_start { "[x]" } else { "[ ]" }.to_string(),
+        }
+    }
+
+    /// Switches to another template, keeping the name typed so far.
+    fn cycle_template(&mut self, forward: bool) {
+        let len = self.templates.len();
+        self.template_index = if forward {
+            (self.template_index + 1) % len
+        } else {
+            (self.template_index + len - 1) % len
+        };
+        let name = std::mem::take(&mut self.unit.name);
+        self.unit = NewUnit::new(self.templates[self.template_index].clone(), self.session);
+        self.unit.name = name;
+    }
+
+    fn cycle_choice(&mut self, index: usize, forward: bool) {
+        let choices = &self.unit.template().parameters[index].choices;
+        let len = choices.len();
+        let current = choices
+            .iter()
+            .position(|choice| *choice == self.unit.values[index])
+            .unwrap_or(0);
+        let next = if forward { (current + 1) % len } else { (current + len - 1) % len };
+        self.unit.values[index] = choices[next].clone();
+    }
+
+    /// Inserts pasted text in the selected text field, e.g. the path of a script.
+    pub fn on_paste(&mut self, text: &str) {
+        if let Some(value) = self.text_mut() {
+            value.extend(text.trim().chars().filter(|c| !c.is_control()));
+        }
+    }
+
+    pub fn on_key_event(&mut self, key: KeyEvent) -> Confirmation {
+        self.error = None;
+        match key.code {
+            KeyCode::Esc => return Confirmation::Cancelled,
+            KeyCode::Enter => match self.unit.validate() {
+                Ok(()) => return Confirmation::Confirmed,
+                Err(e) => self.error = Some(e),
+            },
+            KeyCode::Tab | KeyCode::Down => self.selected = (self.selected + 1) % self.fields().len(),
+            KeyCode::BackTab | KeyCode::Up => {
+                self.selected = self.selected.checked_sub(1).unwrap_or(self.fields().len() - 1)
+            }
+            KeyCode::Left | KeyCode::Right if self.field() == Field::Template => {
+                self.cycle_template(key.code == KeyCode::Right)
+            }
+            KeyCode::Left | KeyCode::Right | KeyCode::Char(' ') if self.field() == Field::EnableAndStart => {
+                self.enable_and_start = !self.enable_and_start
+            }
+            KeyCode::Left | KeyCode::Right if self.is_choice(self.field()) => {
+                if let Field::Parameter(index) = self.field() {
+                    self.cycle_choice(index, key.code == KeyCode::Right)
+                }
+            }
+            KeyCode::Char(c) => {
+                if let Some(value) = self.text_mut() {
+                    value.push(c);
+                }
+            }
+            KeyCode::Backspace => {
+                if let Some(value) = self.text_mut() {
+                    value.pop();
+                }
+            }
+            _ => {}
+        }
+        Confirmation::Pending
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        let locale = self.locale;
+        let mut lines = vec![locale.format("New units, {}", &[locale.tr(&self.unit.template().description)])];
+        lines.extend(self.fields().into_iter().enumerate().map(|(index, field)| {
+            let selected = if index == self.selected { locale.tr(" (selected)") } else { "" };
+            format!("{}{}: {}", self.label(field), selected, self.value(field))
+        }));
+        if let Some(error) = &self.error {
+            lines.push(format!("{}: {}", locale.tr("Error"), error));
+        }
+        lines.push(locale.shortcuts(&[
+            ("Next field", "Tab".to_string()),
+            ("Previous field", "Shift + Tab".to_string()),
+            ("Choose", "Left/Right".to_string()),
+            ("Create", "Enter".to_string()),
+            ("Cancel", "Esc".to_string()),
+        ]));
+        lines
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_width = std::cmp::min(80, area.width.saturating_sub(4));
+        let fields = self.fields();
+        let popup_height = std::cmp::min(fields.len() as u16 + 9, area.height.saturating_sub(2));
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let label_width = fields
+            .iter()
+            .map(|field| self.label(*field).chars().count())
+            .max()
+            .unwrap_or_default()
+            .max(16)
+            + 2;
+        let mut text: Vec<Line> = vec![
+            Line::from(Span::styled(
+                self.locale.tr(&self.unit.template().description).to_string(),
+                Style::default().fg(theme.muted),
+            )),
+            Line::from(""),
+        ];
+        text.extend(fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let label_style = if index == self.selected {
+                    Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.muted)
+                };
+                Line::from(vec![
+                    Span::styled(format!("{:<width$}", self.label(*field), width = label_width), label_style),
+                    Span::raw(self.value(*field)),
+                ])
+            }));
+        text.push(Line::from(""));
+        text.push(match &self.error {
+            Some(error) => Line::from(Span::styled(error.clone(), Style::default().fg(theme.error))),
+            None => Line::from(Span::styled(
+                self.locale.format("Writes {}", &[&self.unit.unit_names().join(", ")]),
+                Style::default().fg(theme.muted),
+            )),
+        });
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            self.locale.tr("Tab: next | ←/→: choose | Enter: create | Esc: cancel"),
+            Style::default().fg(theme.muted),
+        )));
+
+        let form = Paragraph::new(text).style(Style::default().fg(theme.text)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.highlight_border))
+                .title(format!(" {} ", self.locale.tr("New units"))),
+        );
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(form, popup_area);
+
+        if !self.is_choice(self.field()) {
+            let column = label_width + self.value(self.field()).chars().count();
+            let x = popup_area.x + 1 + u16::try_from(column).unwrap_or(u16::MAX);
+            // Below the description of the template and its blank line.
+            let y = popup_area.y + 3 + self.selected as u16;
+            if x < popup_area.right().saturating_sub(1) {
+                frame.set_cursor_position(Position::new(x, y));
+            }
+        }
+    }
+}
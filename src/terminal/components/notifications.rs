@@ -0,0 +1,228 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+
+use crate::config::Config;
+use crate::domain::unit_transition::UnitTransition;
+use crate::terminal::app::{Actions, AppEvent};
+use crate::terminal::components::overlay::Overlay;
+use crate::terminal::theme::Theme;
+
+/// Notifications kept by the panel, the oldest ones are dropped first.
+const MAX_ENTRIES: usize = 200;
+
+struct Notification {
+    transition: UnitTransition,
+    /// Not seen in the panel yet.
+    unread: bool,
+}
+
+/// State changes of the watched units during the session, newest first, the units going
+/// down highlighted.
+pub struct Notifications {
+    open: bool,
+    entries: Vec<Notification>,
+    table_state: TableState,
+    sender: Sender<AppEvent>,
+    config: Rc<Config>,
+}
+
+impl Notifications {
+    pub fn new(sender: Sender<AppEvent>, config: Rc<Config>) -> Self {
+        Self {
+            open: false,
+            entries: Vec::new(),
+            table_state: TableState::default(),
+            sender,
+            config,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+        self.table_state.select(Some(0));
+    }
+
+    /// Closes the panel, the notifications shown being read from then on.
+    fn close(&mut self) {
+        self.open = false;
+        for entry in &mut self.entries {
+            entry.unread = false;
+        }
+    }
+
+    pub fn push(&mut self, transition: UnitTransition) {
+        self.entries.insert(0, Notification { transition, unread: true });
+        self.entries.truncate(MAX_ENTRIES);
+    }
+
+    pub fn unread(&self) -> usize {
+        self.entries.iter().filter(|entry| entry.unread).count()
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        let len = self.entries.len();
+        if len == 0 {
+            return;
+        }
+        let selected = self.table_state.selected().unwrap_or(0) as isize;
+        let next = (selected + delta).clamp(0, len as isize - 1);
+        self.table_state.select(Some(next as usize));
+    }
+
+    fn selected(&self) -> Option<&Notification> {
+        self.table_state.selected().and_then(|selected| self.entries.get(selected))
+    }
+
+    fn help(&self) -> String {
+        let keys = &self.config.keybindings;
+        self.config.locale.shortcuts(&[
+            ("Scroll", format!("{}/{}", keys.up, keys.down)),
+            ("Jump to unit", "Enter".to_string()),
+            ("Clear", keys.clear_marks.to_string()),
+            ("Close", keys.back.to_string()),
+        ])
+    }
+}
+
+impl Overlay for Notifications {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn on_key_event(&mut self, key: KeyEvent) {
+        let keys = &self.config.keybindings;
+        match key {
+            k if k.code == KeyCode::Esc || keys.back.matches(&k) => self.close(),
+            k if k.code == KeyCode::Enter => {
+                if let Some(entry) = self.selected() {
+                    let unit = entry.transition.unit().to_string();
+                    self.sender.send(AppEvent::Action(Actions::JumpToService(unit))).unwrap();
+                    self.close();
+                }
+            }
+            k if keys.up.matches(&k) => self.scroll(-1),
+            k if keys.down.matches(&k) => self.scroll(1),
+            k if keys.page_up.matches(&k) => self.scroll(-10),
+            k if keys.page_down.matches(&k) => self.scroll(10),
+            k if keys.clear_marks.matches(&k) => {
+                self.entries.clear();
+                self.table_state.select(Some(0));
+            }
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open {
+            return;
+        }
+
+        let popup_width = std::cmp::min(100, area.width.saturating_sub(4));
+        let popup_height = area.height.saturating_sub(4);
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border))
+            .title(format!(" {} ", self.config.locale.format("Notifications ({} new)", &[&self.unread().to_string()])))
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [entries_area, help_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+
+        if self.entries.is_empty() {
+            frame.render_widget(
+                Paragraph::new(self.config.locale.tr("No state changes of the watched units so far"))
+                    .style(Style::default().fg(theme.muted)),
+                entries_area,
+            );
+        } else {
+            let rows = self.entries.iter().map(|entry| {
+                let transition = &entry.transition;
+                let mut style = if transition.is_alarming() {
+                    Style::default().fg(theme.failed).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text)
+                };
+                if !entry.unread {
+                    style = style.remove_modifier(Modifier::BOLD);
+                }
+                Row::new(vec![
+                    Cell::from(if entry.unread { "●" } else { "" }).style(Style::default().fg(theme.accent)),
+                    Cell::from(transition.at().format("%H:%M:%S").to_string()).style(Style::default().fg(theme.muted)),
+                    Cell::from(transition.unit().to_string()),
+                    Cell::from(transition.describe()),
+                ])
+                .style(style)
+            });
+            let table = Table::new(
+                rows,
+                [
+                    Constraint::Length(1),
+                    Constraint::Length(8),
+                    Constraint::Percentage(40),
+                    Constraint::Min(0),
+                ],
+            )
+            .header(
+                Row::new(["", "Time", "Unit", "Change"].map(|header| self.config.locale.tr(header)))
+                    .style(Style::default().fg(theme.header).add_modifier(Modifier::BOLD)),
+            )
+            .row_highlight_style(Style::default().bg(theme.selection_bg).fg(theme.selection_fg));
+            frame.render_stateful_widget(table, entries_area, &mut self.table_state);
+        }
+
+        frame.render_widget(
+            Paragraph::new(self.help())
+                .style(Style::default().fg(theme.muted))
+                .alignment(Alignment::Center),
+            help_area,
+        );
+    }
+
+    fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        let mut lines = vec![locale.format(
+            "Notifications, {} entries, {} new, newest first",
+            &[&self.entries.len().to_string(), &self.unread().to_string()],
+        )];
+        let selected = self.table_state.selected().unwrap_or(0);
+        for (index, entry) in self.entries.iter().enumerate().skip(selected) {
+            let mut flags = Vec::new();
+            if index == selected {
+                flags.push(locale.tr("selected"));
+            }
+            if entry.unread {
+                flags.push(locale.tr("new"));
+            }
+            if entry.transition.is_alarming() {
+                flags.push(locale.tr("went down"));
+            }
+            let flags = if flags.is_empty() { String::new() } else { format!(" ({})", flags.join(", ")) };
+            lines.push(format!(
+                "{} {}{}: {}",
+                entry.transition.at().format("%H:%M:%S"),
+                entry.transition.unit(),
+                flags,
+                entry.transition.describe()
+            ));
+        }
+        lines.push(self.help());
+        lines
+    }
+}
@@ -0,0 +1,16 @@
+use crossterm::event::KeyEvent;
+use ratatui::{layout::Rect, Frame};
+
+use crate::terminal::theme::Theme;
+
+/// A view drawn over the screen, e.g. the help or the failed units, which gets the keys and
+/// pastes while it is open. Its results go back to the app as an `AppEvent`.
+pub trait Overlay {
+    fn is_open(&self) -> bool;
+    fn on_key_event(&mut self, key: KeyEvent);
+    /// Most views take no text, pastes are then dropped.
+    fn on_paste(&mut self, _text: &str) {}
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme);
+    /// The view as plain lines, for the accessible mode.
+    fn linear_text(&self) -> Vec<String>;
+}
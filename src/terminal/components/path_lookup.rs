@@ -0,0 +1,224 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Position, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::config::Config;
+use crate::domain::unit_path::UnitPathReference;
+use crate::errors::AppError;
+use crate::terminal::components::overlay::Overlay;
+use crate::terminal::theme::Theme;
+use crate::usecases::services_manager::ServicesManager;
+
+/// "What manages this path?" prompt listing the units covering a filesystem path.
+pub struct PathLookup {
+    open: bool,
+    input: String,
+    results: Option<Result<Vec<UnitPathReference>, String>>,
+    table_state: TableState,
+    usecase: Rc<RefCell<ServicesManager>>,
+    config: Rc<Config>,
+}
+
+impl PathLookup {
+    pub fn new(usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
+        Self {
+            open: false,
+            input: String::new(),
+            results: None,
+            table_state: TableState::default(),
+            usecase,
+            config,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+        self.input.clear();
+        self.results = None;
+        self.table_state = TableState::default();
+    }
+
+    fn lookup(&mut self) {
+        let path = self.input.trim();
+        if path.is_empty() {
+            return;
+        }
+        self.results = Some(
+            self.usecase
+                .borrow()
+                .find_units_for_path(path)
+                .map_err(|e| self.config.locale.tr(AppError::from(e).explanation()).to_string()),
+        );
+        self.table_state.select(Some(0));
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        let len = match &self.results {
+            Some(Ok(references)) if !references.is_empty() => references.len(),
+            _ => return,
+        };
+        let selected = self.table_state.selected().unwrap_or(0) as isize;
+        let next = (selected + delta).clamp(0, len as isize - 1);
+        self.table_state.select(Some(next as usize));
+    }
+}
+
+impl Overlay for PathLookup {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn on_key_event(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => self.open = false,
+            KeyCode::Enter => self.lookup(),
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Char(c) => self.input.push(c),
+            KeyCode::Up => self.scroll(-1),
+            KeyCode::Down => self.scroll(1),
+            KeyCode::PageUp => self.scroll(-10),
+            KeyCode::PageDown => self.scroll(10),
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open {
+            return;
+        }
+
+        let popup_width = std::cmp::min(100, area.width.saturating_sub(4));
+        let popup_height = std::cmp::min(20, area.height.saturating_sub(2));
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let locale = self.config.locale;
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border))
+            .title(format!(" {} ", locale.tr("What manages this path?")))
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [input_area, results_area, help_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+
+        let input = Paragraph::new(self.input.as_str())
+            .style(Style::default().fg(theme.warning))
+            .block(
+                Block::bordered()
+                    .border_style(Style::default().fg(theme.border))
+                    .title(locale.tr("Path")),
+            );
+        frame.render_widget(input, input_area);
+        #[allow(clippy::cast_possible_truncation)]
+        frame.set_cursor_position(Position::new(
+            input_area.x + self.input.chars().count() as u16 + 1,
+            input_area.y + 1,
+        ));
+
+        match &self.results {
+            None => {}
+            Some(Err(e)) => {
+                frame.render_widget(
+                    Paragraph::new(e.as_str()).style(Style::default().fg(theme.error)),
+                    results_area,
+                );
+            }
+            Some(Ok(references)) if references.is_empty() => {
+                frame.render_widget(
+                    Paragraph::new(locale.tr("No unit manages this path.")).style(Style::default().fg(theme.muted)),
+                    results_area,
+                );
+            }
+            Some(Ok(references)) => {
+                let rows = references.iter().map(|reference| {
+                    Row::new(vec![
+                        Cell::from(reference.unit().to_string()).style(Style::default().fg(theme.accent)),
+                        Cell::from(reference.relation().to_string()),
+                        Cell::from(reference.path().to_string()),
+                    ])
+                });
+                let table = Table::new(
+                    rows,
+                    [Constraint::Percentage(35), Constraint::Length(28), Constraint::Min(0)],
+                )
+                .header(
+                    Row::new([locale.tr("Unit"), locale.tr("Relation"), locale.tr("Path")])
+                        .style(Style::default().fg(theme.header).add_modifier(Modifier::BOLD)),
+                )
+                .style(Style::default().fg(theme.text))
+                .row_highlight_style(
+                    Style::default()
+                        .bg(theme.selection_bg)
+                        .fg(theme.selection_fg),
+                );
+                frame.render_stateful_widget(table, results_area, &mut self.table_state);
+            }
+        }
+
+        let help = Paragraph::new(Line::from(locale.shortcuts(&[
+            ("Search", "Enter".to_string()),
+            ("Scroll", "↑/↓".to_string()),
+            ("Close", "Esc".to_string()),
+        ])))
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(help, help_area);
+    }
+
+    fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        let mut lines = vec![
+            locale.tr("What manages this path?").to_string(),
+            format!("{}: {}", locale.tr("Path"), self.input),
+        ];
+        match &self.results {
+            None => {}
+            Some(Err(e)) => lines.push(format!("{}: {}", locale.tr("Error"), e)),
+            Some(Ok(references)) if references.is_empty() => {
+                lines.push(locale.tr("No unit manages this path.").to_string())
+            }
+            Some(Ok(references)) => {
+                let selected = self.table_state.selected().unwrap_or(0);
+                lines.extend(references.iter().enumerate().skip(selected).map(|(index, reference)| {
+                    locale.format(
+                        "Unit {} of {}: {}; relation: {}; path: {}",
+                        &[
+                            &(index + 1).to_string(),
+                            &references.len().to_string(),
+                            reference.unit(),
+                            &reference.relation().to_string(),
+                            reference.path(),
+                        ],
+                    )
+                }));
+            }
+        }
+        lines.push(locale.shortcuts(&[
+            ("Search", "Enter".to_string()),
+            ("Scroll", "Up/Down".to_string()),
+            ("Close", "Esc".to_string()),
+        ]));
+        lines
+    }
+}
@@ -0,0 +1,169 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::domain::action_plan::ActionPlan;
+use crate::terminal::components::confirm::Confirmation;
+use crate::terminal::components::list::ServiceAction;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+/// Dry-run of an action: what would be called on each unit, confirmed before executing.
+/// Also shown outside of the dry-run mode when a stop or restart carries over to other units.
+pub struct ActionPreview {
+    action: ServiceAction,
+    plans: Vec<(String, Result<ActionPlan, String>)>,
+    dry_run: bool,
+    scroll: u16,
+    locale: Locale,
+}
+
+impl ActionPreview {
+    pub fn new(
+        action: ServiceAction,
+        plans: Vec<(String, Result<ActionPlan, String>)>,
+        dry_run: bool,
+        locale: Locale,
+    ) -> Self {
+        Self {
+            action,
+            plans,
+            dry_run,
+            scroll: 0,
+            locale,
+        }
+    }
+
+    fn verb(&self) -> String {
+        self.locale.tr(self.action.label()).to_lowercase()
+    }
+
+    fn title(&self) -> String {
+        let action = self.locale.tr(self.action.label());
+        if self.dry_run {
+            self.locale.format("Dry run: {}", &[action])
+        } else {
+            self.locale.format("{} affects other units", &[action])
+        }
+    }
+
+    pub fn action(&self) -> ServiceAction {
+        self.action
+    }
+
+    pub fn on_key_event(&mut self, key: KeyEvent) -> Confirmation {
+        match key.code {
+            KeyCode::Enter => return Confirmation::Confirmed,
+            KeyCode::Esc => return Confirmation::Cancelled,
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down => self.scroll = self.scroll.saturating_add(1),
+            _ => {}
+        }
+        Confirmation::Pending
+    }
+
+    fn lines(&self, theme: &Theme) -> Vec<Line<'static>> {
+        let locale = self.locale;
+        let mut lines = Vec::new();
+        for (unit, plan) in &self.plans {
+            lines.push(Line::from(Span::styled(
+                unit.clone(),
+                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+            )));
+            match plan {
+                Ok(plan) => {
+                    lines.push(Line::from(vec![
+                        Span::styled(format!("  {} ", locale.tr("call")), Style::default().fg(theme.muted)),
+                        Span::raw(format!("{}{}", plan.method(), plan.arguments())),
+                    ]));
+                    for unit in plan.propagated_units() {
+                        lines.push(Line::from(Span::styled(
+                            format!("  ⚠ {}", locale.format("will also {} {}", &[&self.verb(), unit])),
+                            Style::default().fg(theme.warning).add_modifier(Modifier::BOLD),
+                        )));
+                    }
+                    if plan.propagated_units().is_empty() && plan.side_effects().is_empty() {
+                        lines.push(Line::from(Span::styled(
+                            format!("  {}", locale.tr("no other unit is affected")),
+                            Style::default().fg(theme.muted),
+                        )));
+                    }
+                    for effect in plan.side_effects() {
+                        lines.push(Line::from(Span::styled(
+                            format!("  ⚠ {}", locale.tr(effect)),
+                            Style::default().fg(theme.warning),
+                        )));
+                    }
+                }
+                Err(e) => lines.push(Line::from(Span::styled(
+                    format!("  {}", locale.format("could not be previewed: {}", &[e])),
+                    Style::default().fg(theme.error),
+                ))),
+            }
+            lines.push(Line::from(""));
+        }
+        lines
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        let locale = self.locale;
+        let mut lines = vec![self.title()];
+        for (unit, plan) in &self.plans {
+            match plan {
+                Ok(plan) => {
+                    lines.push(format!("{}: {} {}{}", unit, locale.tr("call"), plan.method(), plan.arguments()));
+                    lines.extend(plan.propagated_units().iter().map(|other| {
+                        format!("{}: {}", unit, locale.format("will also {} {}", &[&self.verb(), other]))
+                    }));
+                    lines.extend(plan.side_effects().iter().map(|effect| format!("{}: {}", unit, locale.tr(effect))));
+                }
+                Err(e) => lines.push(format!("{}: {}", unit, locale.format("could not be previewed: {}", &[e]))),
+            }
+        }
+        lines.push(locale.shortcuts(&[("Execute", "Enter".to_string()), ("Cancel", "Esc".to_string())]));
+        lines
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_width = std::cmp::min(100, area.width.saturating_sub(4));
+        let popup_height = std::cmp::min(24, area.height.saturating_sub(2));
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.highlight_border))
+            .title(format!(" {} ", self.title()))
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [plan_area, help_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+
+        let plan = Paragraph::new(self.lines(theme))
+            .style(Style::default().fg(theme.text))
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0));
+        frame.render_widget(plan, plan_area);
+
+        let help = Paragraph::new(self.locale.shortcuts(&[
+            ("Execute", "Enter".to_string()),
+            ("Scroll", "↑/↓".to_string()),
+            ("Cancel", "Esc".to_string()),
+        ]))
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(help, help_area);
+    }
+}
@@ -0,0 +1,285 @@
+use crossterm::event::KeyEvent;
+use ratatui::{
+    layout::{Alignment, Constraint, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::Config;
+use crate::domain::process::{format_bytes, process_tree, UnitProcess};
+use crate::domain::service::Service;
+use crate::errors::AppError;
+use crate::terminal::app::{Actions, AppEvent};
+use crate::terminal::components::unit_tabs::UnitTab;
+use crate::terminal::theme::Theme;
+use crate::usecases::services_manager::ServicesManager;
+
+/// Live process tree of the control group of a unit, like `systemctl status` with the CPU and
+/// memory of each process.
+pub struct ServiceProcesses {
+    service: Option<Service>,
+    /// Processes in tree order with their depth.
+    processes: Vec<(usize, UnitProcess)>,
+    /// CPU usage of each process since the previous refresh, in percent of one CPU.
+    cpu: HashMap<u32, f64>,
+    /// CPU time of each process at the previous refresh, to compute the usage from.
+    previous: Option<(Instant, HashMap<u32, u64>)>,
+    error: Option<String>,
+    table_state: TableState,
+    sender: Sender<AppEvent>,
+    auto_refresh: Arc<Mutex<bool>>,
+    usecase: Rc<RefCell<ServicesManager>>,
+    config: Rc<Config>,
+}
+
+impl ServiceProcesses {
+    pub fn new(sender: Sender<AppEvent>, usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
+        Self {
+            service: None,
+            processes: Vec::new(),
+            cpu: HashMap::new(),
+            previous: None,
+            error: None,
+            table_state: TableState::default(),
+            sender,
+            auto_refresh: Arc::new(Mutex::new(false)),
+            usecase,
+            config,
+        }
+    }
+
+    pub fn update(&mut self, service: Service) {
+        self.reset();
+        self.service = Some(service);
+        self.table_state.select(Some(0));
+    }
+
+    pub fn fetch_processes(&mut self) {
+        let Some(service) = &self.service else {
+            return;
+        };
+        let processes = match self.usecase.borrow().get_unit_processes(service) {
+            Ok(processes) => processes,
+            Err(e) => {
+                self.error = Some(self.config.locale.tr(AppError::from(e).explanation()).to_string());
+                self.processes.clear();
+                return;
+            }
+        };
+        self.error = None;
+
+        let now = Instant::now();
+        let cpu_times: HashMap<u32, u64> = processes
+            .iter()
+            .map(|process| (process.pid(), process.cpu_usec()))
+            .collect();
+        self.cpu = match &self.previous {
+            Some((at, previous)) => {
+                let elapsed = now.duration_since(*at).as_micros().max(1) as f64;
+                cpu_times
+                    .iter()
+                    .filter_map(|(pid, cpu_usec)| {
+                        let used = cpu_usec.saturating_sub(*previous.get(pid)?);
+                        Some((*pid, used as f64 * 100.0 / elapsed))
+                    })
+                    .collect()
+            }
+            None => HashMap::new(),
+        };
+        self.previous = Some((now, cpu_times));
+
+        self.processes = process_tree(processes);
+        if self.table_state.selected().is_none_or(|selected| selected >= self.processes.len()) {
+            self.table_state.select(Some(self.processes.len().saturating_sub(1)));
+        }
+    }
+
+    fn cpu_text(&self, pid: u32) -> String {
+        self.cpu
+            .get(&pid)
+            .map_or("-".to_string(), |percent| format!("{:.1}%", percent))
+    }
+
+    fn select(&mut self, delta: isize) {
+        if self.processes.is_empty() {
+            return;
+        }
+        let selected = self.table_state.selected().unwrap_or(0) as isize;
+        let next = (selected + delta).clamp(0, self.processes.len() as isize - 1);
+        self.table_state.select(Some(next as usize));
+    }
+
+    fn service_name(&self) -> &str {
+        self.service.as_ref().map_or("", |service| service.name())
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border))
+            .title(self.config.locale.format(
+                " {} processes ({}) ",
+                &[self.service_name(), &self.processes.len().to_string()],
+            ))
+            .title_alignment(Alignment::Center);
+
+        if let Some(error) = &self.error {
+            frame.render_widget(
+                Paragraph::new(error.as_str()).style(Style::default().fg(theme.error)).block(block),
+                area,
+            );
+            return;
+        }
+
+        let rows = self.processes.iter().map(|(depth, process)| {
+            let branch = if *depth == 0 { String::new() } else { format!("{}└ ", "  ".repeat(depth - 1)) };
+            Row::new(vec![
+                Cell::from(process.pid().to_string()),
+                Cell::from(self.cpu_text(process.pid())),
+                Cell::from(format_bytes(process.rss_bytes())),
+                Cell::from(Line::from(vec![
+                    Span::styled(branch, Style::default().fg(theme.muted)),
+                    Span::raw(process.command().to_string()),
+                ])),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [Constraint::Length(8), Constraint::Length(7), Constraint::Length(8), Constraint::Min(0)],
+        )
+        .header(
+            Row::new(["PID", "CPU", "RSS", "Command"])
+                .style(Style::default().fg(theme.header).add_modifier(Modifier::BOLD)),
+        )
+        .style(Style::default().fg(theme.text))
+        .row_highlight_style(
+            Style::default()
+                .bg(theme.selection_bg)
+                .fg(theme.selection_fg),
+        )
+        .block(block);
+        frame.render_stateful_widget(table, area, &mut self.table_state);
+    }
+
+    /// Processes from the selected one onwards, with their depth in the tree.
+    pub fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        if let Some(error) = &self.error {
+            return vec![format!("{}: {}", locale.tr("Error"), error)];
+        }
+        if self.processes.is_empty() {
+            return vec![locale.tr("No processes").to_string()];
+        }
+        let selected = self.table_state.selected().unwrap_or(0);
+        self.processes
+            .iter()
+            .enumerate()
+            .skip(selected)
+            .map(|(index, (depth, process))| {
+                locale.format(
+                    "Process {} of {}{}: PID {}, level {}, CPU {}, RSS {}, {}",
+                    &[
+                        &(index + 1).to_string(),
+                        &self.processes.len().to_string(),
+                        if index == selected { locale.tr(" (selected)") } else { "" },
+                        &process.pid().to_string(),
+                        &(depth + 1).to_string(),
+                        &self.cpu_text(process.pid()),
+                        &format_bytes(process.rss_bytes()),
+                        process.command(),
+                    ],
+                )
+            })
+            .collect()
+    }
+
+    pub fn on_key_event(&mut self, key: KeyEvent) {
+        if let Some(tab) = UnitTab::from_key(&key).filter(|tab| *tab != UnitTab::Processes) {
+            self.reset();
+            self.sender.send(AppEvent::Action(tab.action())).unwrap();
+            return;
+        }
+
+        let config = self.config.clone();
+        let keys = &config.keybindings;
+        match key {
+            k if keys.next_tab.matches(&k) => {
+                self.reset();
+                self.sender.send(AppEvent::Action(UnitTab::Processes.next().action())).unwrap();
+            }
+            k if keys.previous_tab.matches(&k) => {
+                self.reset();
+                self.sender.send(AppEvent::Action(UnitTab::Processes.previous().action())).unwrap();
+            }
+            k if keys.up.matches(&k) => self.select(-1),
+            k if keys.down.matches(&k) => self.select(1),
+            k if keys.page_up.matches(&k) => self.select(-10),
+            k if keys.page_down.matches(&k) => self.select(10),
+            k if keys.back.matches(&k) => {
+                self.reset();
+                self.sender.send(AppEvent::Action(Actions::GoList)).unwrap();
+            }
+            _ => {}
+        }
+    }
+
+    pub fn shortcuts(&mut self, theme: &Theme) -> Vec<Line<'_>> {
+        let locale = self.config.locale;
+        let keys = &self.config.keybindings;
+        vec![
+            Line::from(vec![Span::styled(
+                locale.tr("Actions"),
+                Style::default()
+                    .fg(theme.title)
+                    .add_modifier(Modifier::BOLD),
+            )]),
+            Line::from(locale.shortcuts(&[
+                ("Navigate", format!("{}/{}", keys.up, keys.down)),
+                ("Switch tabs", format!("{}/{} {} 1-8", keys.previous_tab, keys.next_tab, locale.tr("or"))),
+                ("Go back", keys.back.to_string()),
+            ])),
+        ]
+    }
+
+    fn set_auto_refresh(&mut self, value: bool) {
+        if let Ok(mut auto) = self.auto_refresh.lock() {
+            *auto = value;
+        }
+    }
+
+    /// Refreshes the processes at the log refresh interval while the tab is shown.
+    pub fn start_auto_refresh(&mut self) {
+        self.set_auto_refresh(true);
+        let auto_refresh = Arc::clone(&self.auto_refresh);
+        let sender = self.sender.clone();
+        let interval = Duration::from_millis(self.config.refresh_interval);
+        thread::spawn(move || {
+            loop {
+                thread::sleep(interval);
+                match auto_refresh.lock() {
+                    Ok(is_active) if *is_active => {
+                        sender.send(AppEvent::Action(Actions::RefreshProcesses)).unwrap();
+                    }
+                    _ => break,
+                }
+            }
+        });
+    }
+
+    pub fn reset(&mut self) {
+        self.set_auto_refresh(false);
+        self.processes.clear();
+        self.cpu.clear();
+        self.previous = None;
+        self.error = None;
+    }
+}
@@ -0,0 +1,136 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::terminal::components::confirm::Confirmation;
+use crate::terminal::components::list::ServiceAction;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+#[derive(Clone, Copy)]
+pub enum QuickAction {
+    Service(ServiceAction),
+    Kill,
+    ViewLogs,
+    Properties,
+}
+
+const ACTIONS: [QuickAction; 12] = [
+    QuickAction::Service(ServiceAction::Start),
+    QuickAction::Service(ServiceAction::Stop),
+    QuickAction::Service(ServiceAction::Restart),
+    QuickAction::Service(ServiceAction::Reload),
+    QuickAction::Service(ServiceAction::ReloadOrRestart),
+    QuickAction::Service(ServiceAction::Enable),
+    QuickAction::Service(ServiceAction::Disable),
+    QuickAction::Service(ServiceAction::Freeze),
+    QuickAction::Service(ServiceAction::Thaw),
+    QuickAction::Kill,
+    QuickAction::ViewLogs,
+    QuickAction::Properties,
+];
+
+impl QuickAction {
+    fn label(&self) -> &'static str {
+        match self {
+            QuickAction::Service(action) => action.label(),
+            QuickAction::Kill => "Kill",
+            QuickAction::ViewLogs => "View logs",
+            QuickAction::Properties => "Properties",
+        }
+    }
+}
+
+/// Menu of everything that can be done to the selected unit, for when its key is forgotten.
+pub struct QuickActions {
+    unit: String,
+    selected: usize,
+    locale: Locale,
+}
+
+impl QuickActions {
+    pub fn new(unit: String, locale: Locale) -> Self {
+        Self { unit, selected: 0, locale }
+    }
+
+    pub fn choice(&self) -> QuickAction {
+        ACTIONS[self.selected]
+    }
+
+    pub fn on_key_event(&mut self, key: KeyEvent) -> Confirmation {
+        match key.code {
+            KeyCode::Esc => return Confirmation::Cancelled,
+            KeyCode::Enter => return Confirmation::Confirmed,
+            KeyCode::Up => self.selected = self.selected.checked_sub(1).unwrap_or(ACTIONS.len() - 1),
+            KeyCode::Down => self.selected = (self.selected + 1) % ACTIONS.len(),
+            _ => {}
+        }
+        Confirmation::Pending
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        let locale = self.locale;
+        let mut lines = vec![locale.format("Quick actions on {}", &[&self.unit])];
+        lines.extend(ACTIONS.iter().enumerate().map(|(index, action)| {
+            if index == self.selected {
+                locale.format("Action (selected): {}", &[locale.tr(action.label())])
+            } else {
+                locale.format("Action: {}", &[locale.tr(action.label())])
+            }
+        }));
+        lines.push(locale.shortcuts(&[
+            ("Choose", "Up/Down".to_string()),
+            ("Run", "Enter".to_string()),
+            ("Cancel", "Esc".to_string()),
+        ]));
+        lines
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_width = std::cmp::min(40, area.width.saturating_sub(4));
+        let popup_height = std::cmp::min(ACTIONS.len() as u16 + 4, area.height.saturating_sub(4));
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let mut text: Vec<Line> = ACTIONS
+            .iter()
+            .enumerate()
+            .map(|(index, action)| {
+                if index == self.selected {
+                    Line::from(Span::styled(
+                        format!(">> {}", self.locale.tr(action.label())),
+                        Style::default()
+                            .fg(theme.selection_fg)
+                            .bg(theme.selection_bg)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                } else {
+                    Line::from(format!("   {}", self.locale.tr(action.label())))
+                }
+            })
+            .collect();
+        text.push(Line::from(Span::styled(
+            self.locale.tr("Enter: run | Esc: cancel"),
+            Style::default().fg(theme.muted),
+        )));
+
+        let menu = Paragraph::new(text).style(Style::default().fg(theme.text)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.highlight_border))
+                .title(format!(" {} ", self.unit)),
+        );
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(menu, popup_area);
+    }
+}
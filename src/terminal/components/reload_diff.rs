@@ -0,0 +1,278 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+
+use crate::config::Config;
+use crate::domain::service::Service;
+use crate::domain::unit_file_drift::SettingDrift;
+use crate::errors::AppError;
+use crate::terminal::app::{Actions, AppEvent};
+use crate::terminal::components::list::ServiceAction;
+use crate::terminal::components::overlay::Overlay;
+use crate::terminal::theme::Theme;
+use crate::usecases::services_manager::ServicesManager;
+
+/// The settings of the unit files of a unit side by side with the values systemd loaded, to see
+/// what a daemon reload would apply, then reload and restart the unit.
+pub struct ReloadDiff {
+    open: bool,
+    service: Option<Service>,
+    settings: Option<Result<Vec<SettingDrift>, String>>,
+    /// Only the settings that differ.
+    changed_only: bool,
+    table_state: TableState,
+    sender: Sender<AppEvent>,
+    usecase: Rc<RefCell<ServicesManager>>,
+    config: Rc<Config>,
+}
+
+impl ReloadDiff {
+    pub fn new(sender: Sender<AppEvent>, usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
+        Self {
+            open: false,
+            service: None,
+            settings: None,
+            changed_only: false,
+            table_state: TableState::default(),
+            sender,
+            usecase,
+            config,
+        }
+    }
+
+    pub fn open(&mut self, service: Service) {
+        self.open = true;
+        self.changed_only = self
+            .usecase
+            .borrow()
+            .get_need_daemon_reload(&service)
+            .unwrap_or(service.state().need_daemon_reload());
+        self.service = Some(service);
+        self.refresh();
+        self.table_state.select(Some(0));
+    }
+
+    fn refresh(&mut self) {
+        let Some(service) = &self.service else {
+            return;
+        };
+        let settings = self.usecase.borrow().setting_drift(service);
+        self.settings = Some(settings.map_err(|e| self.config.locale.tr(AppError::from(e).explanation()).trim().to_string()));
+        let len = self.rows().len();
+        if self.table_state.selected().is_some_and(|selected| selected >= len) {
+            self.table_state.select(Some(len.saturating_sub(1)));
+        }
+    }
+
+    fn changed(&self) -> usize {
+        match &self.settings {
+            Some(Ok(settings)) => settings.iter().filter(|setting| setting.differs()).count(),
+            _ => 0,
+        }
+    }
+
+    fn rows(&self) -> Vec<&SettingDrift> {
+        match &self.settings {
+            Some(Ok(settings)) => settings.iter().filter(|setting| !self.changed_only || setting.differs()).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        let len = self.rows().len();
+        if len == 0 {
+            return;
+        }
+        let selected = self.table_state.selected().unwrap_or(0) as isize;
+        let next = (selected + delta).clamp(0, len as isize - 1);
+        self.table_state.select(Some(next as usize));
+    }
+
+    /// Reloads the unit files, then restarts the unit for its processes to run with them.
+    fn reload_daemon(&mut self, restart: bool) {
+        let Some(service) = self.service.clone() else {
+            return;
+        };
+        if let Err(e) = self.usecase.borrow().reload_daemon() {
+            self.sender.send(AppEvent::Error(e.into())).unwrap();
+            return;
+        }
+        if restart {
+            let units = vec![service.name().to_string()];
+            self.sender.send(AppEvent::Action(Actions::RunOnUnits(ServiceAction::Restart, units))).unwrap();
+            self.open = false;
+        } else {
+            self.sender.send(AppEvent::Notice(self.config.locale.tr("Reloaded the unit files").to_string())).unwrap();
+            self.refresh();
+        }
+        self.sender.send(AppEvent::Action(Actions::ReloadList)).unwrap();
+    }
+
+    fn help(&self) -> String {
+        let keys = &self.config.keybindings;
+        let shown = if self.changed_only { "All settings" } else { "Changed only" };
+        self.config.locale.shortcuts(&[
+            (shown, "Tab".to_string()),
+            ("Daemon-reload", keys.reload.to_string()),
+            ("Daemon-reload and restart", keys.restart.to_string()),
+            ("Refresh", keys.refresh.to_string()),
+            ("Close", keys.back.to_string()),
+        ])
+    }
+
+    /// e.g. "nginx.service: 2 settings changed on disk since it was loaded".
+    fn summary(&self) -> String {
+        let name = self.service.as_ref().map_or("", |service| service.name());
+        let locale = self.config.locale;
+        match self.changed() {
+            0 => locale.format("{}: the loaded unit matches its files", &[name]),
+            1 => locale.format("{}: 1 setting changed on disk since it was loaded", &[name]),
+            changed => locale.format(
+                "{}: {} settings changed on disk since it was loaded",
+                &[name, &changed.to_string()],
+            ),
+        }
+    }
+}
+
+impl Overlay for ReloadDiff {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn on_key_event(&mut self, key: KeyEvent) {
+        let config = self.config.clone();
+        let keys = &config.keybindings;
+        match key {
+            k if k.code == KeyCode::Esc || keys.back.matches(&k) || keys.reload_diff.matches(&k) => self.open = false,
+            k if k.code == KeyCode::Tab => {
+                self.changed_only = !self.changed_only;
+                self.table_state.select(Some(0));
+            }
+            k if keys.up.matches(&k) => self.scroll(-1),
+            k if keys.down.matches(&k) => self.scroll(1),
+            k if keys.page_up.matches(&k) => self.scroll(-10),
+            k if keys.page_down.matches(&k) => self.scroll(10),
+            k if keys.refresh.matches(&k) => self.refresh(),
+            k if keys.reload.matches(&k) => self.reload_daemon(false),
+            k if keys.restart.matches(&k) => self.reload_daemon(true),
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open {
+            return;
+        }
+
+        let popup_width = std::cmp::min(140, area.width.saturating_sub(4));
+        let popup_height = area.height.saturating_sub(4);
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let border = if self.changed() > 0 { theme.warning } else { theme.border };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border))
+            .title(format!(" {} ", self.summary()))
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [table_area, help_area] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+
+        match &self.settings {
+            None => {}
+            Some(Err(e)) => {
+                frame.render_widget(Paragraph::new(e.as_str()).style(Style::default().fg(theme.error)), table_area)
+            }
+            Some(Ok(_)) => {
+                let rows: Vec<Row> = self
+                    .rows()
+                    .into_iter()
+                    .map(|setting| {
+                        let (marker, style) = if setting.differs() {
+                            ("≠", Style::default().fg(theme.warning).add_modifier(Modifier::BOLD))
+                        } else if setting.loaded.is_none() {
+                            ("", Style::default().fg(theme.muted))
+                        } else {
+                            ("", Style::default().fg(theme.text))
+                        };
+                        Row::new(vec![
+                            Cell::from(marker),
+                            Cell::from(format!("{} {}", setting.section, setting.setting)),
+                            Cell::from(setting.on_disk_text()),
+                            Cell::from(self.config.locale.tr(&setting.loaded_text()).to_string()),
+                        ])
+                        .style(style)
+                    })
+                    .collect();
+                let table = Table::new(
+                    rows,
+                    [
+                        Constraint::Length(1),
+                        Constraint::Length(28),
+                        Constraint::Percentage(50),
+                        Constraint::Percentage(50),
+                    ],
+                )
+                .header(
+                    Row::new(["", "Setting", "On disk", "Loaded"].map(|header| self.config.locale.tr(header)))
+                        .style(Style::default().fg(theme.header).add_modifier(Modifier::BOLD)),
+                )
+                .row_highlight_style(Style::default().bg(theme.selection_bg).fg(theme.selection_fg));
+                frame.render_stateful_widget(table, table_area, &mut self.table_state);
+            }
+        }
+
+        frame.render_widget(
+            Paragraph::new(self.help())
+                .style(Style::default().fg(theme.muted))
+                .alignment(Alignment::Center),
+            help_area,
+        );
+    }
+
+    fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        let mut lines = vec![self.summary()];
+        if let Some(Err(e)) = &self.settings {
+            lines.push(format!("{}: {}", locale.tr("Error"), e));
+        }
+        let selected = self.table_state.selected().unwrap_or(0);
+        for (index, setting) in self.rows().into_iter().enumerate().skip(selected) {
+            let mut flags = Vec::new();
+            if index == selected {
+                flags.push(locale.tr("selected"));
+            }
+            if setting.differs() {
+                flags.push(locale.tr("changed"));
+            }
+            let flags = if flags.is_empty() { String::new() } else { format!(" ({})", flags.join(", ")) };
+            lines.push(locale.format(
+                "{} {}{}: on disk {}, loaded {}",
+                &[
+                    &setting.section,
+                    &setting.setting,
+                    &flags,
+                    &setting.on_disk_text(),
+                    locale.tr(&setting.loaded_text()),
+                ],
+            ));
+        }
+        lines.push(self.help());
+        lines
+    }
+}
@@ -0,0 +1,342 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+
+use crate::config::Config;
+use crate::domain::action_policy::PolicyAction;
+use crate::domain::humanize::{format_timestamp_ago, now_usec};
+use crate::domain::scheduled_action::ScheduledAction;
+use crate::domain::service::Service;
+use crate::errors::AppError;
+use crate::terminal::app::AppEvent;
+use crate::terminal::components::overlay::Overlay;
+use crate::terminal::theme::Theme;
+use crate::usecases::services_manager::ServicesManager;
+
+/// The calendar expression being typed for an action on the unit the view was opened on.
+struct SchedulePrompt {
+    action: PolicyAction,
+    input: String,
+    error: Option<String>,
+}
+
+/// Actions run on units by timers, e.g. a restart of myapp.service every night, scheduled on the
+/// unit selected in the list and cancelled from here.
+pub struct Schedules {
+    open: bool,
+    /// The unit selected in the list, the one new actions are scheduled on.
+    unit: Option<Service>,
+    scheduled: Option<Result<Vec<ScheduledAction>, String>>,
+    prompt: Option<SchedulePrompt>,
+    table_state: TableState,
+    sender: Sender<AppEvent>,
+    usecase: Rc<RefCell<ServicesManager>>,
+    config: Rc<Config>,
+}
+
+impl Schedules {
+    pub fn new(sender: Sender<AppEvent>, usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
+        Self {
+            open: false,
+            unit: None,
+            scheduled: None,
+            prompt: None,
+            table_state: TableState::default(),
+            sender,
+            usecase,
+            config,
+        }
+    }
+
+    pub fn open(&mut self, unit: Option<Service>) {
+        self.open = true;
+        self.unit = unit;
+        self.prompt = None;
+        self.refresh();
+        self.table_state.select(Some(0));
+    }
+
+    fn refresh(&mut self) {
+        let scheduled = self.usecase.borrow().list_scheduled_actions();
+        self.scheduled = Some(scheduled.map_err(|e| self.config.locale.tr(AppError::from(e).explanation()).trim().to_string()));
+        let len = self.scheduled().len();
+        if self.table_state.selected().is_some_and(|selected| selected >= len) {
+            self.table_state.select(Some(len.saturating_sub(1)));
+        }
+    }
+
+    fn scheduled(&self) -> &[ScheduledAction] {
+        match &self.scheduled {
+            Some(Ok(scheduled)) => scheduled,
+            _ => &[],
+        }
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        let len = self.scheduled().len();
+        if len == 0 {
+            return;
+        }
+        let selected = self.table_state.selected().unwrap_or(0) as isize;
+        let next = (selected + delta).clamp(0, len as isize - 1);
+        self.table_state.select(Some(next as usize));
+    }
+
+    /// Asks when to run the action on the unit of the view.
+    fn ask(&mut self, action: PolicyAction) {
+        if self.unit.is_none() {
+            let notice = self.config.locale.tr("Select a unit in the list to schedule an action on it").to_string();
+            self.sender.send(AppEvent::Notice(notice)).unwrap();
+            return;
+        }
+        self.prompt = Some(SchedulePrompt { action, input: String::new(), error: None });
+    }
+
+    /// Creates the timer, keeping the prompt open with the reason when it could not be.
+    fn schedule(&mut self) {
+        let (Some(unit), Some(prompt)) = (&self.unit, &mut self.prompt) else {
+            return;
+        };
+        let calendar = prompt.input.trim();
+        if calendar.is_empty() {
+            return;
+        }
+        let result = self.usecase.borrow().schedule_action(prompt.action, unit, calendar);
+        match result {
+            Ok(timer) => {
+                let locale = self.config.locale;
+                let notice = locale.format(
+                    "Scheduled the {} of {} at {} ({})",
+                    &[locale.tr(prompt.action.label()), unit.name(), calendar, &timer],
+                );
+                self.sender.send(AppEvent::Notice(notice)).unwrap();
+                self.prompt = None;
+                self.refresh();
+            }
+            Err(e) => prompt.error = Some(self.config.locale.tr(AppError::from(e).explanation()).trim().to_string()),
+        }
+    }
+
+    fn cancel(&mut self) {
+        let Some(scheduled) = self.table_state.selected().and_then(|selected| self.scheduled().get(selected)).cloned()
+        else {
+            return;
+        };
+        match self.usecase.borrow().cancel_scheduled_action(&scheduled.timer) {
+            Ok(()) => self
+                .sender
+                .send(AppEvent::Notice(self.config.locale.format("Cancelled the {}", &[&scheduled.describe()])))
+                .unwrap(),
+            Err(e) => self.sender.send(AppEvent::Error(e.into())).unwrap(),
+        }
+        self.refresh();
+    }
+
+    fn on_prompt_key_event(&mut self, key: KeyEvent) {
+        let Some(prompt) = &mut self.prompt else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => self.prompt = None,
+            KeyCode::Backspace => {
+                prompt.input.pop();
+                prompt.error = None;
+            }
+            KeyCode::Char(c) => {
+                prompt.input.push(c);
+                prompt.error = None;
+            }
+            KeyCode::Enter => self.schedule(),
+            _ => {}
+        }
+    }
+
+    /// e.g. "Restart nginx.service at (OnCalendar, e.g. 02:00 or Mon *-*-* 03:00)".
+    fn question(&self) -> Option<String> {
+        let (unit, prompt) = (self.unit.as_ref()?, self.prompt.as_ref()?);
+        let locale = self.config.locale;
+        let label = locale.tr(prompt.action.label());
+        let mut chars = label.chars();
+        let action: String = chars.next().into_iter().flat_map(char::to_uppercase).chain(chars).collect();
+        Some(locale.format("{} {} at (OnCalendar, e.g. 02:00 or Mon *-*-* 03:00)", &[&action, unit.name()]))
+    }
+
+    fn help(&self) -> String {
+        let locale = self.config.locale;
+        if self.prompt.is_some() {
+            return locale.shortcuts(&[("Schedule", "Enter".to_string()), ("Cancel", "Esc".to_string())]);
+        }
+        let keys = &self.config.keybindings;
+        let on = self.unit.as_ref().map_or(locale.tr("the selected unit"), |unit| unit.name());
+        format!(
+            "{} {}",
+            locale.format(
+                "Schedule on {}: start {} | stop {} | restart {} | reload {}",
+                &[
+                    on,
+                    &keys.start.to_string(),
+                    &keys.stop.to_string(),
+                    &keys.restart.to_string(),
+                    &keys.reload.to_string(),
+                ],
+            ),
+            locale.shortcuts(&[
+                ("Cancel", keys.disable.to_string()),
+                ("Refresh", keys.refresh.to_string()),
+                ("Close", keys.back.to_string()),
+            ])
+        )
+    }
+}
+
+impl Overlay for Schedules {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn on_key_event(&mut self, key: KeyEvent) {
+        if self.prompt.is_some() {
+            self.on_prompt_key_event(key);
+            return;
+        }
+        let config = self.config.clone();
+        let keys = &config.keybindings;
+        match key {
+            k if k.code == KeyCode::Esc || keys.back.matches(&k) || keys.schedules.matches(&k) => self.open = false,
+            k if keys.up.matches(&k) => self.scroll(-1),
+            k if keys.down.matches(&k) => self.scroll(1),
+            k if keys.page_up.matches(&k) => self.scroll(-10),
+            k if keys.page_down.matches(&k) => self.scroll(10),
+            k if keys.refresh.matches(&k) => self.refresh(),
+            k if keys.start.matches(&k) => self.ask(PolicyAction::Start),
+            k if keys.stop.matches(&k) => self.ask(PolicyAction::Stop),
+            k if keys.restart.matches(&k) => self.ask(PolicyAction::Restart),
+            k if keys.reload.matches(&k) => self.ask(PolicyAction::Reload),
+            k if keys.disable.matches(&k) => self.cancel(),
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open {
+            return;
+        }
+
+        let popup_width = std::cmp::min(120, area.width.saturating_sub(4));
+        let popup_height = area.height.saturating_sub(4);
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border))
+            .title(format!(" {} ", self.config.locale.tr("Scheduled actions")))
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [table_area, prompt_area, help_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(2), Constraint::Length(1)]).areas(inner);
+
+        match &self.scheduled {
+            None => {}
+            Some(Err(e)) => {
+                frame.render_widget(Paragraph::new(e.as_str()).style(Style::default().fg(theme.error)), table_area)
+            }
+            Some(Ok(scheduled)) if scheduled.is_empty() => frame.render_widget(
+                Paragraph::new(self.config.locale.tr("Nothing scheduled")).style(Style::default().fg(theme.muted)),
+                table_area,
+            ),
+            Some(Ok(scheduled)) => {
+                let now = now_usec();
+                let locale = self.config.locale;
+                let rows = scheduled.iter().map(|scheduled| {
+                    let next = if scheduled.next_elapse == 0 { theme.muted } else { theme.text };
+                    Row::new(vec![
+                        Cell::from(locale.tr(scheduled.action.label())),
+                        Cell::from(scheduled.unit.clone()),
+                        Cell::from(scheduled.calendar.clone()),
+                        Cell::from(format_timestamp_ago(scheduled.next_elapse, now)).style(Style::default().fg(next)),
+                        Cell::from(scheduled.timer.clone()).style(Style::default().fg(theme.muted)),
+                    ])
+                });
+                let table = Table::new(
+                    rows,
+                    [
+                        Constraint::Length(8),
+                        Constraint::Percentage(25),
+                        Constraint::Length(18),
+                        Constraint::Length(33),
+                        Constraint::Min(0),
+                    ],
+                )
+                .header(
+                    Row::new(["Action", "Unit", "When", "Next", "Timer"].map(|header| locale.tr(header)))
+                        .style(Style::default().fg(theme.header).add_modifier(Modifier::BOLD)),
+                )
+                .style(Style::default().fg(theme.text))
+                .row_highlight_style(Style::default().bg(theme.selection_bg).fg(theme.selection_fg));
+                frame.render_stateful_widget(table, table_area, &mut self.table_state);
+            }
+        }
+
+        if let (Some(question), Some(prompt)) = (self.question(), &self.prompt) {
+            let mut input = vec![Span::styled(format!("> {}", prompt.input), Style::default().fg(theme.text))];
+            if let Some(error) = &prompt.error {
+                input.push(Span::styled(format!("  {}", error), Style::default().fg(theme.error)));
+            }
+            let lines = vec![
+                Line::from(Span::styled(question, Style::default().fg(theme.accent).add_modifier(Modifier::BOLD))),
+                Line::from(input),
+            ];
+            frame.render_widget(Paragraph::new(lines), prompt_area);
+        }
+        frame.render_widget(
+            Paragraph::new(self.help())
+                .style(Style::default().fg(theme.muted))
+                .alignment(Alignment::Center),
+            help_area,
+        );
+    }
+
+    fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        let mut lines = vec![locale.format("Scheduled actions, {} scheduled", &[&self.scheduled().len().to_string()])];
+        if let Some(Err(e)) = &self.scheduled {
+            lines.push(format!("{}: {}", locale.tr("Error"), e));
+        }
+        if let (Some(question), Some(prompt)) = (self.question(), &self.prompt) {
+            lines.push(format!("{}: {}", question, prompt.input));
+            lines.extend(prompt.error.iter().map(|error| format!("{}: {}", locale.tr("Error"), error)));
+        }
+        let selected = self.table_state.selected().unwrap_or(0);
+        let now = now_usec();
+        for (index, scheduled) in self.scheduled().iter().enumerate().skip(selected) {
+            let flag = if index == selected { locale.tr(" (selected)") } else { "" };
+            lines.push(locale.format(
+                "{}{}, next {}, timer {}",
+                &[
+                    &scheduled.describe(),
+                    flag,
+                    &format_timestamp_ago(scheduled.next_elapse, now),
+                    &scheduled.timer,
+                ],
+            ));
+        }
+        lines.push(self.help());
+        lines
+    }
+}
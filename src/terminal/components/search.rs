@@ -0,0 +1,185 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+/// Incremental, case-insensitive search over a list of lines, similar to `/` in less.
+#[derive(Default)]
+pub struct SearchBar {
+    query: String,
+    editing: bool,
+    matches: Vec<usize>,
+    current: usize,
+    locale: Locale,
+}
+
+impl SearchBar {
+    pub fn start(&mut self, locale: Locale) {
+        self.locale = locale;
+        self.editing = true;
+        self.query.clear();
+        self.matches.clear();
+        self.current = 0;
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self {
+            locale: self.locale,
+            ..Self::default()
+        };
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.editing
+    }
+
+    /// Whether the search bar has something to show below the text.
+    pub fn is_visible(&self) -> bool {
+        self.editing || !self.query.is_empty()
+    }
+
+    /// Handles a key while typing the query. Returns true when the query changed.
+    pub fn on_key_event(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Enter => {
+                self.editing = false;
+                false
+            }
+            KeyCode::Esc => {
+                self.clear();
+                true
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                true
+            }
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Re-indexes the lines containing the query, keeping the current match when possible.
+    pub fn update_matches<'a>(&mut self, lines: impl Iterator<Item = &'a str>) {
+        self.matches.clear();
+        if !self.query.is_empty() {
+            let query = self.query.to_ascii_lowercase();
+            self.matches.extend(
+                lines
+                    .enumerate()
+                    .filter(|(_, line)| line.to_ascii_lowercase().contains(&query))
+                    .map(|(index, _)| index),
+            );
+        }
+        self.current = self.current.min(self.matches.len().saturating_sub(1));
+    }
+
+    /// Whether the line at `index` contains the query.
+    pub fn is_match(&self, index: usize) -> bool {
+        self.matches.contains(&index)
+    }
+
+    pub fn current_line(&self) -> Option<usize> {
+        self.matches.get(self.current).copied()
+    }
+
+    pub fn next_match(&mut self) -> Option<usize> {
+        if self.matches.is_empty() {
+            return None;
+        }
+        self.current = (self.current + 1) % self.matches.len();
+        self.current_line()
+    }
+
+    pub fn previous_match(&mut self) -> Option<usize> {
+        if self.matches.is_empty() {
+            return None;
+        }
+        self.current = self.current.checked_sub(1).unwrap_or(self.matches.len() - 1);
+        self.current_line()
+    }
+
+    pub fn linear_text(&self) -> String {
+        format!(
+            "{}: {} {}",
+            self.locale.tr(if self.editing { "Editing search" } else { "Search" }),
+            self.query,
+            self.match_counter()
+        )
+        .trim_end()
+        .to_string()
+    }
+
+    /// "[3/17]" style counter for block titles, empty when there is no query.
+    pub fn match_counter(&self) -> String {
+        if self.query.is_empty() {
+            String::new()
+        } else if self.matches.is_empty() {
+            format!("[{}] ", self.locale.tr("no matches"))
+        } else {
+            format!("[{}/{}] ", self.current + 1, self.matches.len())
+        }
+    }
+
+    /// Splits a line into spans, highlighting every occurrence of the query.
+    pub fn highlight(&self, index: usize, line: &str, theme: &Theme) -> Line<'static> {
+        let is_current = self.current_line() == Some(index);
+        if self.query.is_empty() || !self.matches.contains(&index) {
+            return Line::raw(line.to_string());
+        }
+
+        let match_style = Style::default().fg(theme.selection_fg).bg(if is_current {
+            theme.selection_bg
+        } else {
+            theme.warning
+        });
+        let query = self.query.to_ascii_lowercase();
+        let lower = line.to_ascii_lowercase();
+
+        let mut spans = Vec::new();
+        let mut last = 0;
+        for (start, _) in lower.match_indices(&query) {
+            if start < last {
+                continue;
+            }
+            let end = start + query.len();
+            spans.push(Span::raw(line[last..start].to_string()));
+            spans.push(Span::styled(line[start..end].to_string(), match_style));
+            last = end;
+        }
+        spans.push(Span::raw(line[last..].to_string()));
+
+        let line = Line::from(spans);
+        if is_current {
+            line.style(Style::default().add_modifier(Modifier::BOLD))
+        } else {
+            line
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let style = if self.editing {
+            Style::default().fg(theme.warning)
+        } else {
+            Style::default().fg(theme.muted)
+        };
+        frame.render_widget(Paragraph::new(format!("/{}", self.query)).style(style), area);
+
+        if self.editing {
+            #[allow(clippy::cast_possible_truncation)]
+            frame.set_cursor_position(Position::new(
+                area.x + self.query.chars().count() as u16 + 1,
+                area.y,
+            ));
+        }
+    }
+}
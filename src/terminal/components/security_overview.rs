@@ -0,0 +1,411 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+
+use crate::config::Config;
+use crate::domain::security_audit::{SecurityAudit, SecurityScore};
+use crate::errors::{AppError, ServiceError};
+use crate::terminal::app::{Actions, AppEvent};
+use crate::terminal::components::search::SearchBar;
+use crate::terminal::components::overlay::Overlay;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+use crate::usecases::services_manager::ServicesManager;
+
+/// How exposed every service is, like `systemd-analyze security`, and the findings of one.
+pub struct SecurityOverview {
+    open: bool,
+    scores: Result<Vec<SecurityScore>, String>,
+    sort_by_name: bool,
+    /// Unit whose findings are shown instead of the scores, with them.
+    audit: Option<(String, Result<SecurityAudit, String>)>,
+    search: SearchBar,
+    table_state: TableState,
+    sender: Sender<AppEvent>,
+    usecase: Rc<RefCell<ServicesManager>>,
+    config: Rc<Config>,
+}
+
+fn explanation(locale: Locale, e: ServiceError) -> String {
+    locale.tr(AppError::from(e).explanation()).trim().to_string()
+}
+
+fn level_color(level: &str, theme: &Theme) -> ratatui::style::Color {
+    match level {
+        "SAFE" | "OK" => theme.active,
+        "MEDIUM" => theme.warning,
+        _ => theme.error,
+    }
+}
+
+impl SecurityOverview {
+    pub fn new(sender: Sender<AppEvent>, usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
+        Self {
+            open: false,
+            scores: Ok(Vec::new()),
+            sort_by_name: false,
+            audit: None,
+            search: SearchBar::default(),
+            table_state: TableState::default(),
+            sender,
+            usecase,
+            config,
+        }
+    }
+
+    /// Analyzes the services of the manager shown, most exposed first.
+    pub fn open(&mut self) {
+        self.open = true;
+        self.audit = None;
+        self.search.clear();
+        self.refresh();
+    }
+
+    fn refresh(&mut self) {
+        if let Some((unit, _)) = &self.audit {
+            let unit = unit.clone();
+            self.show_findings_of(unit);
+            return;
+        }
+        self.scores = self.usecase.borrow().get_security_scores().map_err(|e| explanation(self.config.locale, e));
+        self.sort();
+        self.table_state.select(Some(0));
+    }
+
+    fn sort(&mut self) {
+        if let Ok(scores) = &mut self.scores {
+            if self.sort_by_name {
+                scores.sort_by(|a, b| a.unit.cmp(&b.unit));
+            } else {
+                scores.sort_by(|a, b| b.exposure.total_cmp(&a.exposure).then_with(|| a.unit.cmp(&b.unit)));
+            }
+        }
+        self.update_search_matches();
+    }
+
+    fn show_findings_of(&mut self, unit: String) {
+        let audit = self.usecase.borrow().get_security_audit(&unit).map_err(|e| explanation(self.config.locale, e));
+        self.audit = Some((unit, audit));
+        self.search.clear();
+        self.update_search_matches();
+        self.table_state.select(Some(0));
+    }
+
+    fn show_scores(&mut self) {
+        let unit = self.audit.take().map(|(unit, _)| unit);
+        self.update_search_matches();
+        let row = self.units().iter().position(|name| Some(*name) == unit.as_deref());
+        self.table_state.select(Some(row.unwrap_or(0)));
+    }
+
+    /// Unit of each row of the scores, or setting of each finding.
+    fn units(&self) -> Vec<&str> {
+        match &self.audit {
+            Some((_, audit)) => audit
+                .iter()
+                .flat_map(|audit| audit.sorted_findings())
+                .map(|finding| finding.setting.as_str())
+                .collect(),
+            None => self.scores.iter().flatten().map(|score| score.unit.as_str()).collect(),
+        }
+    }
+
+    fn error(&self) -> Option<&str> {
+        match &self.audit {
+            Some((_, audit)) => audit.as_ref().err(),
+            None => self.scores.as_ref().err(),
+        }
+        .map(String::as_str)
+    }
+
+    fn update_search_matches(&mut self) {
+        let units: Vec<String> = self.units().into_iter().map(str::to_string).collect();
+        self.search.update_matches(units.iter().map(String::as_str));
+    }
+
+    fn select_row(&mut self, row: Option<usize>) {
+        if row.is_some() {
+            self.table_state.select(row);
+        }
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        let len = self.units().len();
+        if len == 0 {
+            return;
+        }
+        let selected = self.table_state.selected().unwrap_or(0) as isize;
+        let next = (selected + delta).clamp(0, len as isize - 1);
+        self.table_state.select(Some(next as usize));
+    }
+
+    fn selected_unit(&self) -> Option<String> {
+        if let Some((unit, _)) = &self.audit {
+            return Some(unit.clone());
+        }
+        let selected = self.table_state.selected()?;
+        self.units().get(selected).map(|unit| unit.to_string())
+    }
+
+    fn title(&self) -> String {
+        let locale = self.config.locale;
+        let title = match &self.audit {
+            Some((unit, _)) => locale.format("Security: findings for {}", &[unit]),
+            None => locale.format(
+                "Security: exposure per service, by {}",
+                &[locale.tr(if self.sort_by_name { "name" } else { "exposure" })],
+            ),
+        };
+        format!(" {} ", title)
+    }
+
+    fn summary(&self) -> String {
+        match &self.audit {
+            Some((_, Ok(audit))) => {
+                let failed = audit.findings.iter().filter(|finding| !finding.passed).count();
+                self.config.locale.format(
+                    "Overall exposure {} {}, {} of {} checks failed",
+                    &[
+                        &format!("{:.1}", audit.exposure),
+                        &audit.level,
+                        &failed.to_string(),
+                        &audit.findings.len().to_string(),
+                    ],
+                )
+            }
+            Some((_, Err(_))) => String::new(),
+            None => {
+                let Ok(scores) = &self.scores else {
+                    return String::new();
+                };
+                let exposed = scores.iter().filter(|score| score.exposure >= 7.0).count();
+                self.config
+                    .locale
+                    .format("{} services, {} exposed or unsafe", &[&scores.len().to_string(), &exposed.to_string()])
+            }
+        }
+    }
+
+    fn help(&self) -> String {
+        let keys = &self.config.keybindings;
+        let shortcuts = match self.audit {
+            Some(_) => vec![
+                ("Search", keys.search.to_string()),
+                ("Jump to unit", keys.jump.to_string()),
+                ("Back to the services", keys.back.to_string()),
+            ],
+            None => vec![
+                ("Findings of service", "Enter".to_string()),
+                ("Sort", keys.sort.to_string()),
+                ("Search", keys.search.to_string()),
+                ("Jump to unit", keys.jump.to_string()),
+                ("Close", keys.back.to_string()),
+            ],
+        };
+        self.config.locale.shortcuts(&shortcuts)
+    }
+
+    /// (mark or exposure, name, description or level) of each row of the view shown.
+    fn rows(&self) -> Vec<(String, &str, String)> {
+        match &self.audit {
+            Some((_, audit)) => audit
+                .iter()
+                .flat_map(|audit| audit.sorted_findings())
+                .map(|finding| {
+                    let mark = if finding.passed { "✓" } else { "✗" };
+                    let exposure = finding.exposure.map(|exposure| format!("{:.1}", exposure)).unwrap_or_default();
+                    (format!("{} {:>4}", mark, exposure), finding.setting.as_str(), finding.description.clone())
+                })
+                .collect(),
+            None => self
+                .scores
+                .iter()
+                .flatten()
+                .map(|score| (format!("{:.1}", score.exposure), score.unit.as_str(), score.level.clone()))
+                .collect(),
+        }
+    }
+}
+
+impl Overlay for SecurityOverview {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn on_key_event(&mut self, key: KeyEvent) {
+        if self.search.is_editing() {
+            if self.search.on_key_event(key) {
+                self.update_search_matches();
+                self.select_row(self.search.current_line());
+            }
+            return;
+        }
+
+        let config = self.config.clone();
+        let keys = &config.keybindings;
+        match key {
+            k if (k.code == KeyCode::Esc || keys.back.matches(&k)) && self.audit.is_some() => self.show_scores(),
+            k if k.code == KeyCode::Esc || keys.back.matches(&k) || keys.security_overview.matches(&k) => {
+                self.open = false
+            }
+            k if k.code == KeyCode::Enter && self.audit.is_none() => {
+                if let Some(unit) = self.selected_unit() {
+                    self.show_findings_of(unit);
+                }
+            }
+            k if keys.up.matches(&k) => self.scroll(-1),
+            k if keys.down.matches(&k) => self.scroll(1),
+            k if keys.page_up.matches(&k) => self.scroll(-10),
+            k if keys.page_down.matches(&k) => self.scroll(10),
+            k if keys.sort.matches(&k) && self.audit.is_none() => {
+                self.sort_by_name = !self.sort_by_name;
+                self.sort();
+                self.table_state.select(Some(0));
+            }
+            k if keys.search.matches(&k) => self.search.start(self.config.locale),
+            k if keys.next_match.matches(&k) => {
+                let row = self.search.next_match();
+                self.select_row(row);
+            }
+            k if keys.previous_match.matches(&k) => {
+                let row = self.search.previous_match();
+                self.select_row(row);
+            }
+            k if keys.refresh.matches(&k) => self.refresh(),
+            k if keys.jump.matches(&k) => {
+                if let Some(unit) = self.selected_unit() {
+                    self.sender.send(AppEvent::Action(Actions::JumpToService(unit))).unwrap();
+                    self.open = false;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open {
+            return;
+        }
+
+        let popup_width = std::cmp::min(140, area.width.saturating_sub(4));
+        let popup_height = area.height.saturating_sub(2);
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border))
+            .title(format!("{}{}", self.title(), self.search.match_counter()))
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let search_height = u16::from(self.search.is_visible());
+        let [summary_area, table_area, search_area, help_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Min(0),
+            Constraint::Length(search_height),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+
+        frame.render_widget(Paragraph::new(self.summary()).style(Style::default().fg(theme.text)), summary_area);
+
+        if let Some(e) = self.error() {
+            frame.render_widget(
+                Paragraph::new(format!("{}: {}", self.config.locale.tr("Error"), e)).style(Style::default().fg(theme.error)),
+                table_area,
+            );
+        } else {
+            let findings = self.audit.is_some();
+            let rows: Vec<Row> = self
+                .rows()
+                .into_iter()
+                .enumerate()
+                .map(|(index, (exposure, name, detail))| {
+                    let line = self.search.highlight(index, name, theme);
+                    if findings {
+                        let color = if exposure.starts_with('✓') { theme.active } else { theme.error };
+                        Row::new(vec![
+                            Cell::from(exposure).style(Style::default().fg(color)),
+                            Cell::from(line),
+                            Cell::from(detail),
+                        ])
+                    } else {
+                        let color = level_color(&detail, theme);
+                        Row::new(vec![
+                            Cell::from(exposure),
+                            Cell::from(line),
+                            Cell::from(detail).style(Style::default().fg(color)),
+                        ])
+                    }
+                })
+                .collect();
+            let (header, widths) = if findings {
+                (["Exposure", "Setting", "Finding"], [Constraint::Length(9), Constraint::Length(36), Constraint::Min(0)])
+            } else {
+                (["Exposure", "Unit", "Level"], [Constraint::Length(9), Constraint::Min(0), Constraint::Length(8)])
+            };
+            let table = Table::new(rows, widths)
+                .header(Row::new(header.map(|header| self.config.locale.tr(header))).style(Style::default().fg(theme.header).add_modifier(Modifier::BOLD)))
+                .style(Style::default().fg(theme.text))
+                .row_highlight_style(
+                    Style::default()
+                        .bg(theme.selection_bg)
+                        .fg(theme.selection_fg),
+                );
+            frame.render_stateful_widget(table, table_area, &mut self.table_state);
+        }
+
+        if self.search.is_visible() {
+            self.search.render(frame, search_area, theme);
+        }
+        frame.render_widget(
+            Paragraph::new(self.help())
+                .style(Style::default().fg(theme.muted))
+                .alignment(Alignment::Center),
+            help_area,
+        );
+    }
+
+    fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        let mut lines = vec![self.title().trim().to_string(), self.summary()];
+        if let Some(e) = self.error() {
+            lines.push(format!("{}: {}", locale.tr("Error"), e));
+        }
+        if self.search.is_visible() {
+            lines.push(self.search.linear_text());
+        }
+        let rows = self.rows();
+        let selected = self.table_state.selected().unwrap_or(0);
+        let kind = if self.audit.is_some() { "Check {} of {}{}: {}, {}, {}" } else { "Service {} of {}{}: {}, {}, {}" };
+        lines.extend(rows.iter().enumerate().skip(selected).map(|(index, (exposure, name, detail))| {
+            locale.format(
+                kind,
+                &[
+                    &(index + 1).to_string(),
+                    &rows.len().to_string(),
+                    if index == selected { locale.tr(" (selected)") } else { "" },
+                    name,
+                    exposure.replace('✓', locale.tr("passed")).replace('✗', locale.tr("failed")).trim(),
+                    detail,
+                ],
+            )
+        }));
+        lines.push(self.help());
+        lines
+    }
+}
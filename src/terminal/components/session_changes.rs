@@ -0,0 +1,206 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+
+use crate::config::Config;
+use crate::domain::enablement_change::EnablementChange;
+use crate::terminal::app::{Actions, AppEvent};
+use crate::terminal::components::overlay::Overlay;
+use crate::terminal::theme::Theme;
+use crate::usecases::services_manager::ServicesManager;
+
+/// Enables and disables made during the session, reverted on demand or before quitting.
+pub struct SessionChanges {
+    open: bool,
+    /// Opened by quitting, which goes on once the changes are reverted or kept.
+    quitting: bool,
+    changes: Vec<EnablementChange>,
+    sender: Sender<AppEvent>,
+    usecase: Rc<RefCell<ServicesManager>>,
+    config: Rc<Config>,
+}
+
+impl SessionChanges {
+    pub fn new(sender: Sender<AppEvent>, usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
+        Self {
+            open: false,
+            quitting: false,
+            changes: Vec::new(),
+            sender,
+            usecase,
+            config,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+        self.quitting = false;
+        self.changes = self.usecase.borrow().enablement_changes();
+    }
+
+    /// Opens the view before quitting when the session changed units, returning whether it did.
+    /// Quitting again from the view quits, keeping the changes.
+    pub fn open_on_quit(&mut self) -> bool {
+        if self.open {
+            return false;
+        }
+        self.changes = self.usecase.borrow().enablement_changes();
+        self.open = !self.changes.is_empty();
+        self.quitting = self.open;
+        self.open
+    }
+
+    fn revert(&mut self) -> bool {
+        let result = self.usecase.borrow().revert_enablement_changes();
+        let reverted = result.is_ok();
+        match result {
+            Ok(units) if units.is_empty() => {}
+            Ok(units) => self
+                .sender
+                .send(AppEvent::Notice(self.config.locale.format("Reverted {}", &[&units.join(", ")])))
+                .unwrap(),
+            Err(e) => self.sender.send(AppEvent::Error(e.into())).unwrap(),
+        }
+        self.changes = self.usecase.borrow().enablement_changes();
+        self.sender.send(AppEvent::Action(Actions::ReloadList)).unwrap();
+        reverted
+    }
+
+    fn help(&self) -> String {
+        let keys = &self.config.keybindings;
+        let shortcuts = if self.quitting {
+            vec![
+                ("Revert and quit", "Enter".to_string()),
+                ("Quit keeping them", keys.back.to_string()),
+                ("Stay", "Esc".to_string()),
+            ]
+        } else {
+            vec![("Revert", "Enter".to_string()), ("Close", keys.back.to_string())]
+        };
+        self.config.locale.shortcuts(&shortcuts)
+    }
+}
+
+impl Overlay for SessionChanges {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Handles a key, sending `Actions::Quit` once the changes are reverted or kept on quitting.
+    fn on_key_event(&mut self, key: KeyEvent) {
+        let keys = &self.config.keybindings;
+        let quit = match key {
+            k if k.code == KeyCode::Enter => {
+                // A failed revert stays open on the changes left, even when quitting.
+                let reverted = self.revert();
+                if reverted {
+                    self.open = false;
+                }
+                reverted && self.quitting
+            }
+            k if keys.back.matches(&k) => {
+                self.open = false;
+                self.quitting
+            }
+            k if k.code == KeyCode::Esc => {
+                self.open = false;
+                false
+            }
+            _ => false,
+        };
+        if quit {
+            self.sender.send(AppEvent::Action(Actions::Quit)).unwrap();
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open {
+            return;
+        }
+
+        let popup_width = std::cmp::min(80, area.width.saturating_sub(4));
+        let popup_height = std::cmp::min(self.changes.len() as u16 + 6, area.height.saturating_sub(2));
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let locale = self.config.locale;
+        let title = if self.quitting {
+            locale.tr("Revert the changes of this session?")
+        } else {
+            locale.tr("Session changes")
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.warning))
+            .title(format!(" {} ", title))
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [changes_area, help_area] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(2)]).areas(inner);
+
+        let lines: Vec<Line> = if self.changes.is_empty() {
+            vec![Line::from(Span::styled(
+                locale.tr("No enablement changes in this session"),
+                Style::default().fg(theme.muted),
+            ))]
+        } else {
+            self.changes
+                .iter()
+                .map(|change| {
+                    Line::from(vec![
+                        Span::styled(
+                            change.unit().to_string(),
+                            Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+                        ),
+                        Span::raw(format!("  {}", locale.format("was {}", &[locale.tr(change.previous())]))),
+                    ])
+                })
+                .collect()
+        };
+        frame.render_widget(
+            Paragraph::new(lines)
+                .style(Style::default().fg(theme.text))
+                .wrap(Wrap { trim: false }),
+            changes_area,
+        );
+        frame.render_widget(
+            Paragraph::new(self.help())
+                .style(Style::default().fg(theme.muted))
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::TOP).border_style(Style::default().fg(theme.border))),
+            help_area,
+        );
+    }
+
+    fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        let mut lines = vec![locale.format("Session changes, {} units", &[&self.changes.len().to_string()])];
+        lines.extend(
+            self.changes
+                .iter()
+                .map(|change| {
+                    locale.format(
+                        "{} {}, was {}",
+                        &[locale.tr(change.action()), change.unit(), locale.tr(change.previous())],
+                    )
+                }),
+        );
+        lines.push(self.help());
+        lines
+    }
+}
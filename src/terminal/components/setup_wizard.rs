@@ -0,0 +1,254 @@
+use std::io;
+use std::path::PathBuf;
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::backend::Backend;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame, Terminal,
+};
+
+use crate::config::{InitialSettings, Keymap};
+use crate::terminal::components::confirm::Confirmation;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::{Theme, THEME_NAMES};
+
+/// A question of the setup: its title and its choices with what they mean, the default first.
+struct Question {
+    title: &'static str,
+    choices: &'static [(&'static str, &'static str)],
+}
+
+const QUESTIONS: [Question; 4] = [
+    Question {
+        title: "Theme",
+        choices: &[
+            ("Dark", "Light text on a dark terminal"),
+            ("Light", "Dark text on a light terminal"),
+            ("High contrast", "Bold, saturated colors"),
+        ],
+    },
+    Question {
+        title: "Keymap",
+        choices: &[
+            ("Default", "Arrows to move and switch tabs, i to filter"),
+            ("Vim", "j/k to move, h/l to switch tabs, / to filter, gg/G to jump"),
+        ],
+    },
+    Question {
+        title: "Services shown first",
+        choices: &[
+            ("System", "Units of the whole machine; changing them usually needs root"),
+            ("Session", "Units of your user session, e.g. syncthing or pipewire"),
+        ],
+    },
+    Question {
+        title: "Confirmations",
+        choices: &[
+            ("Act right away", "Start, stop, enable... units as soon as the key is pressed"),
+            ("Ask first", "Dry-run mode: show what each action would do and ask before executing"),
+            ("Never change anything", "Read-only: list and inspect units, refuse every action"),
+        ],
+    },
+];
+
+/// Questions asked on the first launch, before any config file exists, whose answers become the
+/// config file.
+pub struct SetupWizard {
+    path: PathBuf,
+    /// Index of the current question; `QUESTIONS.len()` for the summary.
+    step: usize,
+    answers: [usize; QUESTIONS.len()],
+    locale: Locale,
+}
+
+impl SetupWizard {
+    pub fn new(path: PathBuf, locale: Locale) -> Self {
+        Self {
+            path,
+            step: 0,
+            answers: [0; QUESTIONS.len()],
+            locale,
+        }
+    }
+
+    pub fn settings(&self) -> InitialSettings {
+        let confirmations = self.answers[3];
+        InitialSettings {
+            theme: THEME_NAMES[self.answers[0]].to_string(),
+            keymap: if self.answers[1] == 1 { Keymap::Vim } else { Keymap::Default },
+            session: self.answers[2] == 1,
+            dry_run: confirmations == 1,
+            read_only: confirmations == 2,
+        }
+    }
+
+    /// The theme being picked, so that the setup previews it.
+    fn theme(&self) -> Theme {
+        Theme::from_name(THEME_NAMES[self.answers[0]]).unwrap_or_else(Theme::dark)
+    }
+
+    pub fn on_key_event(&mut self, key: KeyEvent) -> Confirmation {
+        let choices = QUESTIONS.get(self.step).map_or(0, |question| question.choices.len());
+        match key.code {
+            KeyCode::Esc => return Confirmation::Cancelled,
+            KeyCode::Enter if self.step == QUESTIONS.len() => return Confirmation::Confirmed,
+            KeyCode::Enter | KeyCode::Right => self.step = (self.step + 1).min(QUESTIONS.len()),
+            KeyCode::Backspace | KeyCode::Left => self.step = self.step.saturating_sub(1),
+            KeyCode::Up | KeyCode::Char('k') if choices > 0 => {
+                self.answers[self.step] = self.answers[self.step].checked_sub(1).unwrap_or(choices - 1);
+            }
+            KeyCode::Down | KeyCode::Char('j') if choices > 0 => {
+                self.answers[self.step] = (self.answers[self.step] + 1) % choices;
+            }
+            _ => {}
+        }
+        Confirmation::Pending
+    }
+
+    fn summary(&self) -> Vec<(&'static str, &'static str)> {
+        QUESTIONS
+            .iter()
+            .zip(self.answers)
+            .map(|(question, answer)| (question.title, question.choices[answer].0))
+            .collect()
+    }
+
+    fn hint(&self) -> &str {
+        self.locale.tr(if self.step == QUESTIONS.len() {
+            "Write the config: Enter | Back: Left | Skip, asking again next time: Esc"
+        } else {
+            "Choose: Up/Down | Next: Enter | Back: Left | Skip, asking again next time: Esc"
+        })
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        let locale = self.locale;
+        let mut lines = vec![locale.format(
+            "Setup, step {} of {}",
+            &[&(self.step + 1).to_string(), &(QUESTIONS.len() + 1).to_string()],
+        )];
+        match QUESTIONS.get(self.step) {
+            Some(question) => {
+                lines.push(locale.tr(question.title).to_string());
+                lines.extend(question.choices.iter().enumerate().map(|(index, (label, meaning))| {
+                    let selected = if index == self.answers[self.step] { locale.tr(" (selected)") } else { "" };
+                    format!("{}{}: {}, {}", locale.tr("Choice"), selected, locale.tr(label), locale.tr(meaning))
+                }));
+            }
+            None => {
+                lines.extend(
+                    self.summary()
+                        .into_iter()
+                        .map(|(title, answer)| format!("{}: {}", locale.tr(title), locale.tr(answer))),
+                );
+                lines.push(locale.format("Written to: {}", &[&self.path.display().to_string()]));
+            }
+        }
+        lines.push(self.hint().to_string());
+        lines
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let theme = self.theme();
+        let locale = self.locale;
+        let popup_width = std::cmp::min(76, area.width.saturating_sub(4));
+        let popup_height = std::cmp::min(14, area.height.saturating_sub(2));
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let mut text = vec![
+            Line::from(Span::styled(
+                locale.tr("No config file yet: a few questions to start from, all changeable later."),
+                Style::default().fg(theme.muted),
+            )),
+            Line::from(""),
+        ];
+        match QUESTIONS.get(self.step) {
+            Some(question) => {
+                text.push(Line::from(Span::styled(
+                    locale.tr(question.title),
+                    Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+                )));
+                text.extend(question.choices.iter().enumerate().map(|(index, (label, meaning))| {
+                    if index == self.answers[self.step] {
+                        Line::from(Span::styled(
+                            format!(">> {:<22} {}", locale.tr(label), locale.tr(meaning)),
+                            Style::default()
+                                .fg(theme.selection_fg)
+                                .bg(theme.selection_bg)
+                                .add_modifier(Modifier::BOLD),
+                        ))
+                    } else {
+                        Line::from(vec![
+                            Span::raw(format!("   {:<22} ", locale.tr(label))),
+                            Span::styled(locale.tr(meaning), Style::default().fg(theme.muted)),
+                        ])
+                    }
+                }));
+            }
+            None => {
+                text.extend(self.summary().into_iter().map(|(title, answer)| {
+                    Line::from(vec![
+                        Span::styled(format!("{:<22} ", locale.tr(title)), Style::default().fg(theme.accent)),
+                        Span::raw(locale.tr(answer)),
+                    ])
+                }));
+                text.push(Line::from(""));
+                text.push(Line::from(locale.format("Written to {}", &[&self.path.display().to_string()])));
+            }
+        }
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(self.hint(), Style::default().fg(theme.muted))));
+
+        let wizard = Paragraph::new(text)
+            .style(Style::default().fg(theme.text))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.highlight_border))
+                    .title(locale.format(
+                        " Setup {}/{} ",
+                        &[&(self.step + 1).to_string(), &(QUESTIONS.len() + 1).to_string()],
+                    )),
+            )
+            .wrap(Wrap { trim: false });
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(wizard, popup_area);
+    }
+}
+
+/// Asks the questions until they are answered, or skipped with Esc (`None`), in the language of
+/// the environment since there is no `language` setting yet.
+pub fn run<B: Backend>(terminal: &mut Terminal<B>, path: PathBuf, accessible: bool) -> io::Result<Option<InitialSettings>> {
+    let mut wizard = SetupWizard::new(path, Locale::from_setting("auto").unwrap_or_default());
+    loop {
+        terminal.draw(|frame| {
+            if accessible {
+                let lines: Vec<Line> = wizard.linear_text().into_iter().map(Line::from).collect();
+                frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), frame.area());
+            } else {
+                wizard.render(frame, frame.area());
+            }
+        })?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match wizard.on_key_event(key) {
+            Confirmation::Pending => {}
+            Confirmation::Confirmed => return Ok(Some(wizard.settings())),
+            Confirmation::Cancelled => return Ok(None),
+        }
+    }
+}
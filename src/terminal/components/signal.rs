@@ -0,0 +1,177 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::domain::kill::KillTarget;
+use crate::terminal::components::confirm::Confirmation;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+/// Signals offered by the picker, with their Linux numbers.
+const SIGNALS: [(&str, i32); 5] = [
+    ("SIGTERM", 15),
+    ("SIGKILL", 9),
+    ("SIGHUP", 1),
+    ("SIGUSR1", 10),
+    ("SIGUSR2", 12),
+];
+const CUSTOM: usize = SIGNALS.len();
+
+/// Popup choosing the signal sent to a unit, and whether it goes to the main or all its processes.
+pub struct SignalPicker {
+    unit: String,
+    selected: usize,
+    custom: String,
+    target: KillTarget,
+    locale: Locale,
+}
+
+impl SignalPicker {
+    pub fn new(unit: String, locale: Locale) -> Self {
+        Self {
+            unit,
+            selected: 0,
+            custom: String::new(),
+            target: KillTarget::Main,
+            locale,
+        }
+    }
+
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+
+    pub fn target(&self) -> KillTarget {
+        self.target
+    }
+
+    /// The chosen signal number, `None` while the custom number is empty or invalid.
+    pub fn signal(&self) -> Option<i32> {
+        match SIGNALS.get(self.selected) {
+            Some((_, number)) => Some(*number),
+            None => self.custom.parse().ok().filter(|number| (1..=64).contains(number)),
+        }
+    }
+
+    fn signal_name(&self) -> String {
+        match SIGNALS.get(self.selected) {
+            Some((name, _)) => name.to_string(),
+            None => self.locale.format("signal {}", &[&self.custom]),
+        }
+    }
+
+    fn target_text(&self) -> &str {
+        self.locale.tr(match self.target {
+            KillTarget::Main => "the main process",
+            KillTarget::All => "all processes",
+        })
+    }
+
+    /// "SIGTERM to the main process of nginx.service"
+    pub fn description(&self) -> String {
+        self.locale.format("{} to {} of {}", &[&self.signal_name(), self.target_text(), &self.unit])
+    }
+
+    pub fn on_key_event(&mut self, key: KeyEvent) -> Confirmation {
+        match key.code {
+            KeyCode::Esc => return Confirmation::Cancelled,
+            KeyCode::Enter if self.signal().is_some() => return Confirmation::Confirmed,
+            KeyCode::Up => self.selected = self.selected.checked_sub(1).unwrap_or(CUSTOM),
+            KeyCode::Down => self.selected = (self.selected + 1) % (CUSTOM + 1),
+            KeyCode::Tab => self.target = self.target.toggle(),
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                self.selected = CUSTOM;
+                self.custom.push(c);
+            }
+            KeyCode::Backspace if self.selected == CUSTOM => {
+                self.custom.pop();
+            }
+            _ => {}
+        }
+        Confirmation::Pending
+    }
+
+    fn options(&self) -> Vec<String> {
+        SIGNALS
+            .iter()
+            .map(|(name, number)| format!("{} ({})", name, number))
+            .chain(std::iter::once(self.locale.format("Custom: {}", &[&self.custom])))
+            .collect()
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        let locale = self.locale;
+        let mut lines = vec![locale.format("Send a signal to {}", &[&self.unit])];
+        lines.extend(self.options().into_iter().enumerate().map(|(index, option)| {
+            if index == self.selected {
+                locale.format("Signal (selected): {}", &[&option])
+            } else {
+                locale.format("Signal: {}", &[&option])
+            }
+        }));
+        lines.push(locale.format("Processes: {}", &[self.target_text()]));
+        lines.push(locale.shortcuts(&[
+            ("Choose", "Up/Down".to_string()),
+            ("Custom number", locale.tr("digits").to_string()),
+            ("Main or all processes", "Tab".to_string()),
+            ("Send", "Enter".to_string()),
+            ("Cancel", "Esc".to_string()),
+        ]));
+        lines
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_width = std::cmp::min(50, area.width.saturating_sub(4));
+        let popup_height = std::cmp::min(14, area.height.saturating_sub(4));
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let mut text: Vec<Line> = self
+            .options()
+            .into_iter()
+            .enumerate()
+            .map(|(index, option)| {
+                if index == self.selected {
+                    Line::from(Span::styled(
+                        format!(">> {}", option),
+                        Style::default()
+                            .fg(theme.selection_fg)
+                            .bg(theme.selection_bg)
+                            .add_modifier(Modifier::BOLD),
+                    ))
+                } else {
+                    Line::from(format!("   {}", option))
+                }
+            })
+            .collect();
+        text.push(Line::from(""));
+        text.push(Line::from(vec![
+            Span::raw(format!("{} ", self.locale.tr("Send to"))),
+            Span::styled(self.target_text(), Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+        ]));
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            self.locale.tr("Digits: custom | Tab: main/all | Enter: send | Esc: cancel"),
+            Style::default().fg(theme.muted),
+        )));
+
+        let picker = Paragraph::new(text).style(Style::default().fg(theme.text)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.warning))
+                .title(format!(" {} ", self.locale.format("Kill {}", &[&self.unit]))),
+        );
+
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(picker, popup_area);
+    }
+}
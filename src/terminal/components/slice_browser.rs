@@ -0,0 +1,393 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+use std::time::Instant;
+
+use crate::config::Config;
+use crate::domain::humanize::format_span;
+use crate::domain::process::format_bytes;
+use crate::domain::service::Service;
+use crate::domain::slice::{slice_tree, Slice};
+use crate::errors::{AppError, ServiceError};
+use crate::terminal::app::{Actions, AppEvent};
+use crate::terminal::components::status_bar::ActionOutcome;
+use crate::terminal::components::overlay::Overlay;
+use crate::terminal::theme::Theme;
+use crate::usecases::services_manager::ServicesManager;
+
+/// A row of the tree: a slice, by its index in the slices, or a unit placed in the one above.
+enum TreeRow {
+    Slice(usize, usize),
+    Unit(String, usize),
+}
+
+/// Units acted upon from the view.
+#[derive(Clone, Copy)]
+enum UnitAction {
+    Start,
+    Stop,
+    Restart,
+}
+
+impl UnitAction {
+    fn label(&self) -> &'static str {
+        match self {
+            UnitAction::Start => "start",
+            UnitAction::Stop => "stop",
+            UnitAction::Restart => "restart",
+        }
+    }
+}
+
+/// The slice hierarchy (system.slice, user.slice, ...) with the CPU and memory of each slice,
+/// expanded down to the units placed in them to act on those.
+pub struct SliceBrowser {
+    open: bool,
+    slices: Result<Vec<Slice>, String>,
+    /// Slices whose units are shown.
+    expanded: HashSet<String>,
+    /// Active state of the listed units, by name.
+    states: HashMap<String, String>,
+    /// CPU time of each slice at the previous refresh, for its CPU usage since then.
+    previous_cpu: HashMap<String, u64>,
+    previous_at: Option<Instant>,
+    cpu_percents: HashMap<String, u64>,
+    table_state: TableState,
+    sender: Sender<AppEvent>,
+    usecase: Rc<RefCell<ServicesManager>>,
+    config: Rc<Config>,
+}
+
+impl SliceBrowser {
+    pub fn new(sender: Sender<AppEvent>, usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
+        Self {
+            open: false,
+            slices: Ok(Vec::new()),
+            expanded: HashSet::new(),
+            states: HashMap::new(),
+            previous_cpu: HashMap::new(),
+            previous_at: None,
+            cpu_percents: HashMap::new(),
+            table_state: TableState::default(),
+            sender,
+            usecase,
+            config,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+        self.previous_cpu.clear();
+        self.previous_at = None;
+        self.refresh();
+        self.table_state.select(Some(0));
+    }
+
+    fn refresh(&mut self) {
+        let usecase = self.usecase.borrow();
+        self.slices = usecase
+            .get_slices()
+            .map_err(|e: ServiceError| self.config.locale.tr(AppError::from(e).explanation()).trim().to_string());
+        self.states = usecase
+            .list_services()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|service| (service.name().to_string(), service.state().active().to_string()))
+            .collect();
+        drop(usecase);
+
+        // CPU time is cumulative, its usage is the difference since the previous refresh.
+        let now = Instant::now();
+        let elapsed_nsec = self.previous_at.map(|at| now.duration_since(at).as_nanos() as u64);
+        let slices = self.slices.as_deref().unwrap_or_default();
+        self.cpu_percents = slices
+            .iter()
+            .filter_map(|slice| {
+                let cpu = slice.usage.cpu_nsec?;
+                let elapsed = elapsed_nsec.filter(|elapsed| *elapsed > 0)?;
+                let used = cpu.saturating_sub(*self.previous_cpu.get(&slice.name)?);
+                Some((slice.name.clone(), used * 100 / elapsed))
+            })
+            .collect();
+        self.previous_cpu = slices
+            .iter()
+            .filter_map(|slice| Some((slice.name.clone(), slice.usage.cpu_nsec?)))
+            .collect();
+        self.previous_at = Some(now);
+
+        let len = self.rows().len();
+        if self.table_state.selected().is_some_and(|selected| selected >= len) {
+            self.table_state.select(Some(len.saturating_sub(1)));
+        }
+    }
+
+    /// The slices depth first, each expanded one followed by its units.
+    fn rows(&self) -> Vec<TreeRow> {
+        let Ok(slices) = &self.slices else {
+            return Vec::new();
+        };
+        let mut rows = Vec::new();
+        for (depth, slice) in slice_tree(slices) {
+            let index = slices.iter().position(|other| other.name == slice.name).unwrap_or(0);
+            rows.push(TreeRow::Slice(index, depth));
+            if self.expanded.contains(&slice.name) {
+                let mut units = slice.units.clone();
+                units.sort();
+                rows.extend(units.into_iter().map(|unit| TreeRow::Unit(unit, depth + 1)));
+            }
+        }
+        rows
+    }
+
+    fn slice(&self, index: usize) -> Option<&Slice> {
+        self.slices.as_ref().ok()?.get(index)
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        let len = self.rows().len();
+        if len == 0 {
+            return;
+        }
+        let selected = self.table_state.selected().unwrap_or(0) as isize;
+        let next = (selected + delta).clamp(0, len as isize - 1);
+        self.table_state.select(Some(next as usize));
+    }
+
+    fn selected_row(&self) -> Option<TreeRow> {
+        let selected = self.table_state.selected()?;
+        self.rows().into_iter().nth(selected)
+    }
+
+    /// Shows or hides the units of the selected slice, or selects the selected unit in the list.
+    fn toggle(&mut self) {
+        match self.selected_row() {
+            Some(TreeRow::Slice(index, _)) => {
+                let Some(name) = self.slice(index).map(|slice| slice.name.clone()) else {
+                    return;
+                };
+                if !self.expanded.remove(&name) {
+                    self.expanded.insert(name);
+                }
+            }
+            Some(TreeRow::Unit(unit, _)) => self.jump(unit),
+            None => {}
+        }
+    }
+
+    fn jump(&mut self, unit: String) {
+        self.sender.send(AppEvent::Action(Actions::JumpToService(unit))).unwrap();
+        self.open = false;
+    }
+
+    fn act(&mut self, action: UnitAction) {
+        let Some(TreeRow::Unit(name, _)) = self.selected_row() else {
+            return;
+        };
+        let usecase = self.usecase.borrow();
+        let result = usecase.find_service(&name).and_then(|service: Service| match action {
+            UnitAction::Start => usecase.start_service(&service),
+            UnitAction::Stop => usecase.stop_service(&service),
+            UnitAction::Restart => usecase.restart_service(&service),
+        });
+        drop(usecase);
+        let outcome = match &result {
+            Ok(Some(job)) => ActionOutcome::Queued(job.clone()),
+            Ok(None) => ActionOutcome::Completed,
+            Err(_) => ActionOutcome::Failed,
+        };
+        let description = format!("{} of {}", action.label(), name);
+        self.sender.send(AppEvent::Action(Actions::ActionPerformed(description, outcome))).unwrap();
+        if let Err(e) = result {
+            self.sender.send(AppEvent::Error(e.into())).unwrap();
+        }
+        self.refresh();
+        self.sender.send(AppEvent::Action(Actions::ReloadList)).unwrap();
+    }
+
+    fn help(&self) -> String {
+        let keys = &self.config.keybindings;
+        self.config.locale.shortcuts(&[
+            ("Units of slice", "Enter".to_string()),
+            ("Start", keys.start.to_string()),
+            ("Stop", keys.stop.to_string()),
+            ("Restart", keys.restart.to_string()),
+            ("Jump to unit", keys.jump.to_string()),
+            ("Refresh", keys.refresh.to_string()),
+            ("Close", keys.back.to_string()),
+        ])
+    }
+
+    /// (CPU, memory) of a slice, the CPU usage once refreshed and its time until then.
+    fn usage(&self, slice: &Slice) -> (String, String) {
+        let cpu = match (self.cpu_percents.get(&slice.name), slice.usage.cpu_nsec) {
+            (Some(percent), _) => format!("{}%", percent),
+            (None, Some(nsec)) => format_span(nsec / 1000),
+            (None, None) => "-".to_string(),
+        };
+        let memory = slice.usage.memory_bytes.map(format_bytes).unwrap_or_else(|| "-".to_string());
+        (cpu, memory)
+    }
+}
+
+impl Overlay for SliceBrowser {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn on_key_event(&mut self, key: KeyEvent) {
+        let config = self.config.clone();
+        let keys = &config.keybindings;
+        match key {
+            k if k.code == KeyCode::Esc || keys.back.matches(&k) || keys.slices.matches(&k) => self.open = false,
+            k if k.code == KeyCode::Enter => self.toggle(),
+            k if keys.up.matches(&k) => self.scroll(-1),
+            k if keys.down.matches(&k) => self.scroll(1),
+            k if keys.page_up.matches(&k) => self.scroll(-10),
+            k if keys.page_down.matches(&k) => self.scroll(10),
+            k if keys.refresh.matches(&k) => self.refresh(),
+            k if keys.start.matches(&k) => self.act(UnitAction::Start),
+            k if keys.stop.matches(&k) => self.act(UnitAction::Stop),
+            k if keys.restart.matches(&k) => self.act(UnitAction::Restart),
+            k if keys.jump.matches(&k) => {
+                if let Some(TreeRow::Unit(unit, _)) = self.selected_row() {
+                    self.jump(unit);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open {
+            return;
+        }
+
+        let popup_width = std::cmp::min(110, area.width.saturating_sub(4));
+        let popup_height = area.height.saturating_sub(2);
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border))
+            .title(format!(" {} ", self.config.locale.tr("Slices")))
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [table_area, help_area] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+
+        if let Err(e) = &self.slices {
+            frame.render_widget(Paragraph::new(e.as_str()).style(Style::default().fg(theme.error)), table_area);
+        } else {
+            let rows: Vec<Row> = self
+                .rows()
+                .into_iter()
+                .filter_map(|row| match row {
+                    TreeRow::Slice(index, depth) => {
+                        let slice = self.slice(index)?;
+                        let (cpu, memory) = self.usage(slice);
+                        let marker = if self.expanded.contains(&slice.name) { "▾" } else { "▸" };
+                        Some(
+                            Row::new(vec![
+                                Cell::from(format!("{}{} {}", "  ".repeat(depth), marker, slice.name)),
+                                Cell::from(cpu),
+                                Cell::from(memory),
+                                Cell::from(self.config.locale.format("{} units", &[&slice.units.len().to_string()])),
+                            ])
+                            .style(Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+                        )
+                    }
+                    TreeRow::Unit(unit, depth) => {
+                        let state = self.states.get(&unit).cloned().unwrap_or_default();
+                        let color = match state.as_str() {
+                            "active" => theme.active,
+                            "failed" => theme.failed,
+                            "activating" | "deactivating" | "reloading" => theme.activating,
+                            _ => theme.text,
+                        };
+                        Some(
+                            Row::new(vec![
+                                Cell::from(format!("{}{}", "  ".repeat(depth + 1), unit)),
+                                Cell::from(""),
+                                Cell::from(""),
+                                Cell::from(state),
+                            ])
+                            .style(Style::default().fg(color)),
+                        )
+                    }
+                })
+                .collect();
+            let table = Table::new(
+                rows,
+                [Constraint::Min(0), Constraint::Length(10), Constraint::Length(10), Constraint::Length(12)],
+            )
+            .header(
+                Row::new(["Slice", "CPU", "Memory", "Units"].map(|header| self.config.locale.tr(header)))
+                    .style(Style::default().fg(theme.header).add_modifier(Modifier::BOLD)),
+            )
+            .style(Style::default().fg(theme.text))
+            .row_highlight_style(Style::default().bg(theme.selection_bg).fg(theme.selection_fg));
+            frame.render_stateful_widget(table, table_area, &mut self.table_state);
+        }
+
+        frame.render_widget(
+            Paragraph::new(self.help())
+                .style(Style::default().fg(theme.muted))
+                .alignment(Alignment::Center),
+            help_area,
+        );
+    }
+
+    fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        let mut lines = vec![locale.tr("Slices").to_string()];
+        if let Err(e) = &self.slices {
+            lines.push(format!("{}: {}", locale.tr("Error"), e));
+        }
+        let selected = self.table_state.selected().unwrap_or(0);
+        for (index, row) in self.rows().into_iter().enumerate().skip(selected) {
+            let flag = if index == selected { locale.tr(" (selected)") } else { "" };
+            match row {
+                TreeRow::Slice(slice, depth) => {
+                    let Some(slice) = self.slice(slice) else {
+                        continue;
+                    };
+                    let (cpu, memory) = self.usage(slice);
+                    let state = if self.expanded.contains(&slice.name) { "expanded" } else { "collapsed" };
+                    lines.push(locale.format(
+                        "Slice {}{}, level {}, {}, CPU {}, memory {}, {} units",
+                        &[
+                            &slice.name,
+                            flag,
+                            &(depth + 1).to_string(),
+                            locale.tr(state),
+                            &cpu,
+                            &memory,
+                            &slice.units.len().to_string(),
+                        ],
+                    ));
+                }
+                TreeRow::Unit(unit, _) => {
+                    let state = self.states.get(&unit).map(String::as_str).unwrap_or(locale.tr("not listed"));
+                    lines.push(locale.format("Unit {}{}: {}", &[&unit, flag, state]));
+                }
+            }
+        }
+        lines.push(self.help());
+        lines
+    }
+}
@@ -0,0 +1,258 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+
+use crate::config::Config;
+use crate::domain::service::Service;
+use crate::domain::snapshot::{Snapshot, UnitChange};
+use crate::infrastructure::snapshot_store::{latest_snapshot, load_snapshot, save_snapshot, snapshots_dir};
+use crate::terminal::app::AppEvent;
+use crate::terminal::components::overlay::Overlay;
+use crate::terminal::theme::Theme;
+
+/// The snapshot compared with, and how the units differ from it.
+struct Comparison {
+    path: PathBuf,
+    taken_at: String,
+    changes: Vec<UnitChange>,
+}
+
+/// Units whose state changed since the last snapshot of the service manager shown, e.g. before
+/// and after a system upgrade, with a key to take a new snapshot.
+pub struct SnapshotDiff {
+    open: bool,
+    /// None when no snapshot was taken yet, an error when it could not be read.
+    comparison: Result<Option<Comparison>, String>,
+    /// Host and user manager the snapshots are of.
+    host: Option<String>,
+    user: bool,
+    services: Vec<Service>,
+    table_state: TableState,
+    sender: Sender<AppEvent>,
+    config: Rc<Config>,
+}
+
+impl SnapshotDiff {
+    pub fn new(sender: Sender<AppEvent>, config: Rc<Config>) -> Self {
+        Self {
+            open: false,
+            comparison: Ok(None),
+            host: None,
+            user: false,
+            services: Vec::new(),
+            table_state: TableState::default(),
+            sender,
+            config,
+        }
+    }
+
+    /// Compares the services of the host ("local" for this machine) with its last snapshot.
+    pub fn open(&mut self, host: &str, user: bool, services: Vec<Service>) {
+        self.host = (host != "local").then(|| host.to_string());
+        self.user = user;
+        self.services = services;
+        self.compare();
+        self.table_state.select(Some(0));
+        self.open = true;
+    }
+
+    fn current(&self) -> Snapshot {
+        Snapshot::new(&self.services, chrono::Local::now().to_rfc3339())
+    }
+
+    fn compare(&mut self) {
+        self.comparison = snapshots_dir()
+            .and_then(|directory| latest_snapshot(&directory, self.host.as_deref(), self.user))
+            .and_then(|path| {
+                let Some(path) = path else {
+                    return Ok(None);
+                };
+                let snapshot = load_snapshot(&path)?;
+                Ok(Some(Comparison {
+                    changes: snapshot.diff(&self.current()),
+                    taken_at: snapshot.taken_at,
+                    path,
+                }))
+            })
+            .map_err(|e| e.to_string());
+    }
+
+    fn take_snapshot(&mut self) {
+        let result = snapshots_dir()
+            .and_then(|directory| save_snapshot(&directory, self.host.as_deref(), self.user, &self.current()));
+        let event = match result {
+            Ok(path) => AppEvent::Notice(self.config.locale.format(
+                "Saved a snapshot of {} units to {}",
+                &[&self.services.len().to_string(), &path.display().to_string()],
+            )),
+            Err(e) => AppEvent::Error(e.into()),
+        };
+        self.sender.send(event).unwrap();
+        self.compare();
+        self.table_state.select(Some(0));
+    }
+
+    fn changes(&self) -> &[UnitChange] {
+        match &self.comparison {
+            Ok(Some(comparison)) => &comparison.changes,
+            _ => &[],
+        }
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        let len = self.changes().len();
+        if len == 0 {
+            return;
+        }
+        let selected = self.table_state.selected().unwrap_or(0) as isize;
+        let next = (selected + delta).clamp(0, len as isize - 1);
+        self.table_state.select(Some(next as usize));
+    }
+
+    fn summary(&self) -> String {
+        let locale = self.config.locale;
+        match &self.comparison {
+            Ok(Some(comparison)) if comparison.changes.is_empty() => {
+                locale.format("No unit changed since the snapshot of {}", &[&comparison.taken_at.to_string()])
+            }
+            Ok(Some(comparison)) => locale.format(
+                "{} units changed since the snapshot of {} ({})",
+                &[
+                    &comparison.changes.len().to_string(),
+                    &comparison.taken_at.to_string(),
+                    &comparison.path.display().to_string(),
+                ],
+            ),
+            Ok(None) => locale
+                .tr("No snapshot yet: take one before a change, e.g. a system upgrade, to compare with after it")
+                .to_string(),
+            Err(e) => locale.format("Could not read the last snapshot: {}", &[e]),
+        }
+    }
+
+    fn help(&self) -> String {
+        let keys = &self.config.keybindings;
+        self.config.locale.shortcuts(&[
+            ("Scroll", format!("{}/{}", keys.up, keys.down)),
+            ("Take a new snapshot", "Enter".to_string()),
+            ("Close", keys.back.to_string()),
+        ])
+    }
+}
+
+impl Overlay for SnapshotDiff {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn on_key_event(&mut self, key: KeyEvent) {
+        let keys = &self.config.keybindings;
+        match key {
+            k if k.code == KeyCode::Esc || keys.back.matches(&k) || keys.snapshots.matches(&k) => self.open = false,
+            k if k.code == KeyCode::Enter => self.take_snapshot(),
+            k if keys.up.matches(&k) => self.scroll(-1),
+            k if keys.down.matches(&k) => self.scroll(1),
+            k if keys.page_up.matches(&k) => self.scroll(-10),
+            k if keys.page_down.matches(&k) => self.scroll(10),
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open {
+            return;
+        }
+
+        let popup_width = std::cmp::min(120, area.width.saturating_sub(4));
+        let popup_height = area.height.saturating_sub(2);
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border))
+            .title(format!(" {} ", self.config.locale.tr("Changes since the last snapshot")))
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [summary_area, changes_area, help_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+
+        frame.render_widget(
+            Paragraph::new(Line::from(self.summary())).style(Style::default().fg(theme.text)),
+            summary_area,
+        );
+
+        let locale = self.config.locale;
+        let rows = self.changes().iter().map(|change| {
+            let color = if change.kind.is_regression() { theme.error } else { theme.active };
+            Row::new(vec![
+                Cell::from(locale.tr(change.kind.label())).style(Style::default().fg(color)),
+                Cell::from(change.unit.clone()),
+                Cell::from(change.describe()).style(Style::default().fg(theme.muted)),
+            ])
+        });
+        let table = Table::new(
+            rows,
+            [Constraint::Length(16), Constraint::Percentage(35), Constraint::Min(0)],
+        )
+        .header(
+            Row::new(["Change", "Unit", "Before -> now"].map(|header| locale.tr(header)))
+                .style(Style::default().fg(theme.header).add_modifier(Modifier::BOLD)),
+        )
+        .style(Style::default().fg(theme.text))
+        .row_highlight_style(
+            Style::default()
+                .bg(theme.selection_bg)
+                .fg(theme.selection_fg),
+        );
+        frame.render_stateful_widget(table, changes_area, &mut self.table_state);
+
+        frame.render_widget(
+            Paragraph::new(self.help())
+                .style(Style::default().fg(theme.muted))
+                .alignment(Alignment::Center),
+            help_area,
+        );
+    }
+
+    fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        let mut lines = vec![locale.tr("Changes since the last snapshot").to_string(), self.summary()];
+        let changes = self.changes();
+        let selected = self.table_state.selected().unwrap_or(0);
+        for (index, change) in changes.iter().enumerate() {
+            lines.push(locale.format(
+                "Unit {} of {}{}: {}, {}: {}",
+                &[
+                    &(index + 1).to_string(),
+                    &changes.len().to_string(),
+                    if index == selected { locale.tr(" (selected)") } else { "" },
+                    &change.unit,
+                    locale.tr(change.kind.label()),
+                    &change.describe(),
+                ],
+            ));
+        }
+        lines.push(self.help());
+        lines
+    }
+}
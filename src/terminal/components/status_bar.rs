@@ -0,0 +1,99 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use std::collections::HashMap;
+
+use crate::domain::job::JobResult;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+/// What came out of performing an action.
+pub enum ActionOutcome {
+    /// A job was queued, its result comes later through JobRemoved.
+    Queued(String),
+    /// The action took effect right away, e.g. enabling a unit.
+    Completed,
+    Failed,
+}
+
+/// Last action performed and, once its job finished, how it ended: "restart of nginx.service: done".
+/// Also tells while the connection to the service manager is being opened again.
+#[derive(Default)]
+pub struct StatusBar {
+    last: Option<(String, String)>,
+    /// Description of the actions whose job is still running, by job object path.
+    pending: HashMap<String, String>,
+    reconnecting: bool,
+}
+
+impl StatusBar {
+    pub fn is_empty(&self) -> bool {
+        self.last.is_none() && !self.reconnecting
+    }
+
+    /// Jobs of the lost connection are never reported, they are forgotten on reconnecting.
+    pub fn set_reconnecting(&mut self, reconnecting: bool) {
+        if reconnecting {
+            self.pending.clear();
+        }
+        self.reconnecting = reconnecting;
+    }
+
+    pub fn action_performed(&mut self, description: String, outcome: ActionOutcome) {
+        let state = match outcome {
+            ActionOutcome::Queued(job) => {
+                self.pending.insert(job, description.clone());
+                "queued"
+            }
+            ActionOutcome::Completed => "done",
+            ActionOutcome::Failed => "failed to queue",
+        };
+        self.last = Some((description, state.to_string()));
+    }
+
+    pub fn job_removed(&mut self, job: &JobResult) {
+        if let Some(description) = self.pending.remove(job.job()) {
+            self.last = Some((description, job.result().to_string()));
+        }
+    }
+
+    pub fn linear_text(&self, locale: Locale) -> Vec<String> {
+        self.reconnecting
+            .then(|| locale.tr("Connection lost, reconnecting…").to_string())
+            .into_iter()
+            .chain(
+                self.last
+                    .iter()
+                    .map(|(description, state)| format!("{}: {}: {}", locale.tr("Last action"), description, locale.tr(state))),
+            )
+            .collect()
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme, locale: Locale) {
+        let mut spans = Vec::new();
+        if self.reconnecting {
+            spans.push(Span::styled(
+                format!(" {} ", locale.tr("Reconnecting…")),
+                Style::default().fg(theme.warning).add_modifier(Modifier::BOLD),
+            ));
+        }
+        if let Some((description, state)) = &self.last {
+            let state_color = match state.as_str() {
+                "done" => theme.active,
+                "queued" => theme.activating,
+                _ => theme.failed,
+            };
+            spans.extend([
+                Span::styled(format!(" {}: ", locale.tr("Last action")), Style::default().fg(theme.muted)),
+                Span::styled(description.clone(), Style::default().fg(theme.text)),
+                Span::raw(": "),
+                Span::styled(locale.tr(state).to_string(), Style::default().fg(state_color).add_modifier(Modifier::BOLD)),
+            ]);
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+}
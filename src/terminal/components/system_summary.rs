@@ -0,0 +1,90 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::config::Config;
+use crate::domain::humanize::now_usec;
+use crate::domain::system_summary::SystemSummary;
+use crate::terminal::theme::Theme;
+use crate::usecases::services_manager::ServicesManager;
+
+/// Header above the list with the health of the whole manager: its state, units loaded and
+/// failed, jobs queued, virtualization and boot, read again with each refresh of the list.
+pub struct SystemSummaryBar {
+    /// None when the backend cannot tell, the header then taking no room.
+    summary: Option<SystemSummary>,
+    usecase: Rc<RefCell<ServicesManager>>,
+    config: Rc<Config>,
+}
+
+impl SystemSummaryBar {
+    pub fn new(usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
+        Self {
+            summary: None,
+            usecase,
+            config,
+        }
+    }
+
+    /// Reads the summary of the manager shown; a failure only hides the header, the list
+    /// reporting a lost connection already.
+    pub fn refresh(&mut self) {
+        self.summary = self.usecase.borrow().get_system_summary().ok();
+    }
+
+    pub fn height(&self) -> u16 {
+        u16::from(self.summary.is_some())
+    }
+
+    /// The parts of the header, each with whether it calls for attention.
+    fn parts(&self, summary: &SystemSummary) -> Vec<(String, bool)> {
+        let locale = self.config.locale;
+        vec![
+            (summary.system_state().to_string(), summary.system_state() != "running"),
+            (locale.tr("{} units").replace("{}", &summary.n_names().to_string()), false),
+            (
+                locale.tr("{} failed").replace("{}", &summary.n_failed_units().to_string()),
+                summary.n_failed_units() > 0,
+            ),
+            (locale.tr("{} jobs").replace("{}", &summary.n_jobs().to_string()), false),
+            (summary.virtualization().to_string(), false),
+            (format!("{} {}", locale.tr("booted"), summary.formatted_boot(now_usec())), false),
+        ]
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        self.summary
+            .as_ref()
+            .map(|summary| {
+                let parts: Vec<String> = self.parts(summary).into_iter().map(|(text, _)| text).collect();
+                self.config.locale.format("System: {}", &[&parts.join(", ")])
+            })
+            .into_iter()
+            .collect()
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let Some(summary) = &self.summary else {
+            return;
+        };
+        let mut spans = Vec::new();
+        for (index, (text, alert)) in self.parts(summary).into_iter().enumerate() {
+            if index > 0 {
+                spans.push(Span::styled(" · ", Style::default().fg(theme.muted)));
+            }
+            let style = match (index, alert) {
+                (0, false) => Style::default().fg(theme.active).add_modifier(Modifier::BOLD),
+                (_, true) => Style::default().fg(theme.failed).add_modifier(Modifier::BOLD),
+                _ => Style::default().fg(theme.text),
+            };
+            spans.push(Span::styled(text, style));
+        }
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+}
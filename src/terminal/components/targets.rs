@@ -0,0 +1,401 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+
+use crate::config::Config;
+use crate::domain::action_policy::PolicyAction;
+use crate::domain::power_action::PowerAction;
+use crate::domain::service::Service;
+use crate::errors::AppError;
+use crate::terminal::app::{Actions, AppEvent};
+use crate::terminal::components::status_bar::ActionOutcome;
+use crate::terminal::components::overlay::Overlay;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+use crate::usecases::services_manager::ServicesManager;
+
+/// Actions of the view that stop units, asked for confirmation first.
+enum TargetAction {
+    Isolate(Box<Service>),
+    Power(PowerAction),
+}
+
+impl TargetAction {
+    /// The target the action isolates.
+    fn unit(&self) -> &str {
+        match self {
+            TargetAction::Isolate(target) => target.name(),
+            TargetAction::Power(action) => action.target(),
+        }
+    }
+
+    fn question(&self, locale: Locale) -> String {
+        match self {
+            TargetAction::Isolate(target) => {
+                locale.format("Isolate {}, stopping every unit it does not need?", &[target.name()])
+            }
+            TargetAction::Power(action) => locale.tr(&format!("{} the machine?", action.label())).to_string(),
+        }
+    }
+}
+
+/// Target units with the default one highlighted, to change the default, isolate a target
+/// (e.g. rescue.target) or reboot and power off the machine.
+pub struct Targets {
+    open: bool,
+    targets: Option<Result<Vec<Service>, String>>,
+    default_target: Option<String>,
+    table_state: TableState,
+    pending: Option<TargetAction>,
+    /// The target of the pending action when it is critical and outside of the maintenance
+    /// windows, confirmed by typing its name in `typed`.
+    critical: Option<String>,
+    typed: String,
+    sender: Sender<AppEvent>,
+    usecase: Rc<RefCell<ServicesManager>>,
+    config: Rc<Config>,
+}
+
+impl Targets {
+    pub fn new(sender: Sender<AppEvent>, usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
+        Self {
+            open: false,
+            targets: None,
+            default_target: None,
+            table_state: TableState::default(),
+            pending: None,
+            critical: None,
+            typed: String::new(),
+            sender,
+            usecase,
+            config,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+        self.pending = None;
+        self.refresh();
+        // The default target selected first.
+        let default = self
+            .targets()
+            .iter()
+            .position(|target| Some(target.name()) == self.default_target.as_deref());
+        self.table_state.select(Some(default.unwrap_or(0)));
+    }
+
+    fn refresh(&mut self) {
+        let usecase = self.usecase.borrow();
+        let locale = self.config.locale;
+        self.targets = Some(usecase.list_targets().map_err(|e| locale.tr(AppError::from(e).explanation()).to_string()));
+        self.default_target = usecase.default_target().ok();
+        drop(usecase);
+        let len = self.targets().len();
+        if self.table_state.selected().is_some_and(|selected| selected >= len) {
+            self.table_state.select(Some(len.saturating_sub(1)));
+        }
+    }
+
+    fn targets(&self) -> &[Service] {
+        match &self.targets {
+            Some(Ok(targets)) => targets,
+            _ => &[],
+        }
+    }
+
+    fn selected(&self) -> Option<&Service> {
+        self.table_state.selected().and_then(|selected| self.targets().get(selected))
+    }
+
+    /// The default target, "unknown" when it could not be read.
+    fn default_target(&self) -> &str {
+        self.default_target.as_deref().unwrap_or(self.config.locale.tr("unknown"))
+    }
+
+    fn is_default(&self, target: &Service) -> bool {
+        self.default_target.as_deref() == Some(target.name())
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        let len = self.targets().len();
+        if len == 0 {
+            return;
+        }
+        let selected = self.table_state.selected().unwrap_or(0) as isize;
+        let next = (selected + delta).clamp(0, len as isize - 1);
+        self.table_state.select(Some(next as usize));
+    }
+
+    fn set_default(&mut self) {
+        let Some(target) = self.selected().cloned() else {
+            return;
+        };
+        match self.usecase.borrow().set_default_target(&target) {
+            Ok(()) => self
+                .sender
+                .send(AppEvent::Notice(self.config.locale.format("The machine now boots into {}", &[target.name()])))
+                .unwrap(),
+            Err(e) => self.sender.send(AppEvent::Error(e.into())).unwrap(),
+        }
+        self.refresh();
+    }
+
+    /// Asks to isolate the selected target, unless it does not allow it (AllowIsolate=no). OpenRC
+    /// runlevels are not units, any of them can be switched to.
+    fn ask_isolate(&mut self) {
+        let Some(target) = self.selected().cloned() else {
+            return;
+        };
+        let capabilities = self.usecase.borrow().capabilities(&target);
+        if target.name().ends_with(".target")
+            && let Ok(capabilities) = capabilities
+            && !capabilities.can_isolate
+        {
+            let notice = self.config.locale.format("{} cannot be isolated (AllowIsolate=no)", &[target.name()]);
+            self.sender.send(AppEvent::Notice(notice)).unwrap();
+            return;
+        }
+        self.ask(TargetAction::Isolate(Box::new(target)));
+    }
+
+    /// Asks to confirm the action, by typing the name of its target when it is critical.
+    fn ask(&mut self, action: TargetAction) {
+        let critical = self.usecase.borrow().needing_confirmation(PolicyAction::Isolate, &[action.unit()]);
+        self.critical = critical.into_iter().next();
+        self.typed.clear();
+        self.pending = Some(action);
+    }
+
+    /// The question of the pending action, with what is left to type.
+    fn question(&self, action: &TargetAction) -> String {
+        let locale = self.config.locale;
+        match &self.critical {
+            Some(unit) => locale.format(
+                "{} Outside of the maintenance windows, type {}: {}",
+                &[&action.question(locale), unit, &self.typed],
+            ),
+            None => action.question(locale),
+        }
+    }
+
+    fn perform(&mut self, action: TargetAction) {
+        let usecase = self.usecase.borrow();
+        match action {
+            TargetAction::Isolate(target) => {
+                let result = usecase.isolate_target(&target);
+                let outcome = match &result {
+                    Ok(Some(job)) => ActionOutcome::Queued(job.clone()),
+                    Ok(None) => ActionOutcome::Completed,
+                    Err(_) => ActionOutcome::Failed,
+                };
+                let description = self.config.locale.format("isolate of {}", &[target.name()]);
+                self.sender.send(AppEvent::Action(Actions::ActionPerformed(description, outcome))).unwrap();
+                if let Err(e) = result {
+                    self.sender.send(AppEvent::Error(e.into())).unwrap();
+                }
+            }
+            TargetAction::Power(action) => {
+                if let Err(e) = usecase.power(action) {
+                    self.sender.send(AppEvent::Error(e.into())).unwrap();
+                }
+            }
+        }
+        drop(usecase);
+        self.refresh();
+        self.sender.send(AppEvent::Action(Actions::ReloadList)).unwrap();
+    }
+
+    fn help(&self) -> String {
+        let locale = self.config.locale;
+        if self.pending.is_some() {
+            return locale.shortcuts(&[("Confirm", "Enter".to_string()), ("Cancel", "Esc".to_string())]);
+        }
+        let keys = &self.config.keybindings;
+        locale.shortcuts(&[
+            ("Scroll", format!("{}/{}", keys.up, keys.down)),
+            ("Isolate", "Enter".to_string()),
+            ("Set default", keys.enable.to_string()),
+            ("Reboot", keys.restart.to_string()),
+            ("Power off", keys.stop.to_string()),
+            ("Refresh", keys.refresh.to_string()),
+            ("Close", keys.back.to_string()),
+        ])
+    }
+}
+
+impl Overlay for Targets {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn on_key_event(&mut self, key: KeyEvent) {
+        let keys = &self.config.keybindings;
+        if self.pending.is_some() {
+            match key {
+                k if k.code == KeyCode::Enter => {
+                    if let Some(unit) = &self.critical {
+                        if self.typed != *unit {
+                            return;
+                        }
+                        self.usecase.borrow().confirm_critical(std::slice::from_ref(unit));
+                    }
+                    if let Some(action) = self.pending.take() {
+                        self.perform(action);
+                    }
+                }
+                k if k.code == KeyCode::Esc || (self.critical.is_none() && keys.back.matches(&k)) => {
+                    self.pending = None
+                }
+                k if self.critical.is_some() => match k.code {
+                    KeyCode::Char(c) => self.typed.push(c),
+                    KeyCode::Backspace => {
+                        self.typed.pop();
+                    }
+                    _ => {}
+                },
+                _ => {}
+            }
+            return;
+        }
+        match key {
+            k if k.code == KeyCode::Esc || keys.back.matches(&k) => self.open = false,
+            k if k.code == KeyCode::Enter => self.ask_isolate(),
+            k if keys.up.matches(&k) => self.scroll(-1),
+            k if keys.down.matches(&k) => self.scroll(1),
+            k if keys.page_up.matches(&k) => self.scroll(-10),
+            k if keys.page_down.matches(&k) => self.scroll(10),
+            k if keys.refresh.matches(&k) => self.refresh(),
+            k if keys.enable.matches(&k) => self.set_default(),
+            k if keys.restart.matches(&k) => self.ask(TargetAction::Power(PowerAction::Reboot)),
+            k if keys.stop.matches(&k) => self.ask(TargetAction::Power(PowerAction::PowerOff)),
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open {
+            return;
+        }
+
+        let popup_width = std::cmp::min(100, area.width.saturating_sub(4));
+        let popup_height = area.height.saturating_sub(4);
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border))
+            .title(format!(" {} ", self.config.locale.format("Targets, default {}", &[self.default_target()])))
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [targets_area, question_area, help_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1), Constraint::Length(1)]).areas(inner);
+
+        match &self.targets {
+            None => {}
+            Some(Err(e)) => frame.render_widget(
+                Paragraph::new(e.as_str()).style(Style::default().fg(theme.error)),
+                targets_area,
+            ),
+            Some(Ok(targets)) => {
+                let rows = targets.iter().map(|target| {
+                    let default = self.is_default(target);
+                    let style = if default {
+                        Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+                    } else if target.state().active() == "active" {
+                        Style::default().fg(theme.active)
+                    } else {
+                        Style::default().fg(theme.text)
+                    };
+                    Row::new(vec![
+                        Cell::from(if default { "★" } else { "" }),
+                        Cell::from(target.name().to_string()),
+                        Cell::from(format!("{} ({})", target.state().active(), target.state().sub())),
+                        Cell::from(target.description().to_string()),
+                    ])
+                    .style(style)
+                });
+                let table = Table::new(
+                    rows,
+                    [
+                        Constraint::Length(1),
+                        Constraint::Percentage(30),
+                        Constraint::Length(20),
+                        Constraint::Min(0),
+                    ],
+                )
+                .header(
+                    Row::new(["", "Target", "Active", "Description"].map(|header| self.config.locale.tr(header)))
+                        .style(Style::default().fg(theme.header).add_modifier(Modifier::BOLD)),
+                )
+                .row_highlight_style(Style::default().bg(theme.selection_bg).fg(theme.selection_fg));
+                frame.render_stateful_widget(table, targets_area, &mut self.table_state);
+            }
+        }
+
+        if let Some(action) = &self.pending {
+            frame.render_widget(
+                Paragraph::new(self.question(action))
+                    .style(Style::default().fg(theme.warning).add_modifier(Modifier::BOLD))
+                    .alignment(Alignment::Center),
+                question_area,
+            );
+        }
+        frame.render_widget(
+            Paragraph::new(self.help())
+                .style(Style::default().fg(theme.muted))
+                .alignment(Alignment::Center),
+            help_area,
+        );
+    }
+
+    fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        let mut lines = vec![locale.format(
+            "Targets, {} targets, default {}",
+            &[&self.targets().len().to_string(), self.default_target()],
+        )];
+        if let Some(Err(e)) = &self.targets {
+            lines.push(format!("{}: {}", locale.tr("Error"), e));
+        }
+        if let Some(action) = &self.pending {
+            lines.push(self.question(action));
+        }
+        let selected = self.table_state.selected().unwrap_or(0);
+        for (index, target) in self.targets().iter().enumerate().skip(selected) {
+            let mut flags = Vec::new();
+            if index == selected {
+                flags.push(locale.tr("selected"));
+            }
+            if self.is_default(target) {
+                flags.push(locale.tr("default"));
+            }
+            let flags = if flags.is_empty() { String::new() } else { format!(" ({})", flags.join(", ")) };
+            lines.push(format!(
+                "{}{}: {} ({}), {}",
+                target.name(),
+                flags,
+                target.state().active(),
+                target.state().sub(),
+                target.description()
+            ));
+        }
+        lines.push(self.help());
+        lines
+    }
+}
+
@@ -0,0 +1,129 @@
+use chrono::{Local, TimeZone};
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+
+use crate::domain::humanize::format_timestamp_ago;
+use crate::domain::unit_history::{HistoryEventKind, UnitHistory, HISTORY_DAYS};
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+const DAY_USEC: u64 = 86_400_000_000;
+
+const KINDS: [HistoryEventKind; 5] = [
+    HistoryEventKind::Started,
+    HistoryEventKind::Stopped,
+    HistoryEventKind::Restarted,
+    HistoryEventKind::Failed,
+    HistoryEventKind::OomKilled,
+];
+
+fn symbol(kind: HistoryEventKind) -> &'static str {
+    match kind {
+        HistoryEventKind::Started => "▲",
+        HistoryEventKind::Stopped => "▼",
+        HistoryEventKind::Restarted => "↻",
+        HistoryEventKind::Failed => "✗",
+        HistoryEventKind::OomKilled => "☠",
+    }
+}
+
+fn style(kind: HistoryEventKind, theme: &Theme) -> Style {
+    match kind {
+        HistoryEventKind::Started => Style::default().fg(theme.active),
+        HistoryEventKind::Stopped => Style::default().fg(theme.muted),
+        HistoryEventKind::Restarted => Style::default().fg(theme.warning),
+        HistoryEventKind::Failed => Style::default().fg(theme.failed),
+        HistoryEventKind::OomKilled => Style::default().fg(theme.failed).add_modifier(Modifier::BOLD),
+    }
+}
+
+/// e.g. "12 started, 3 failed, 1 OOM killed over the last 7 days".
+fn counts(history: &UnitHistory, locale: Locale) -> String {
+    let counts: Vec<String> = KINDS
+        .iter()
+        .map(|kind| (kind, history.count(*kind)))
+        .filter(|(_, count)| *count > 0)
+        .map(|(kind, count)| format!("{} {}", count, locale.tr(kind.label())))
+        .collect();
+    if counts.is_empty() {
+        return locale.format(
+            "Nothing started or stopped the unit over the last {} days",
+            &[&HISTORY_DAYS.to_string()],
+        );
+    }
+    locale.format("{} over the last {} days", &[&counts.join(", "), &HISTORY_DAYS.to_string()])
+}
+
+/// Labels of the days under the strip, e.g. "Sat 11", at the column each day starts.
+fn axis(start: u64, width: usize) -> String {
+    let mut axis = vec![' '; width];
+    for day in 0..HISTORY_DAYS {
+        let column = (day as usize * width) / HISTORY_DAYS as usize;
+        let Some(time) = Local.timestamp_micros((start + day * DAY_USEC) as i64).single() else {
+            continue;
+        };
+        let label = format!("╵{}", time.format("%a %d"));
+        // Labels not fitting before the next day are left out on narrow screens.
+        if column + label.chars().count() <= width.min((day as usize + 1) * width / HISTORY_DAYS as usize) {
+            for (offset, c) in label.chars().enumerate() {
+                axis[column + offset] = c;
+            }
+        }
+    }
+    axis.into_iter().collect()
+}
+
+/// The history tab: the counts, a strip of the last days with the worst event of each column,
+/// and the events, newest first.
+pub fn timeline_lines(history: &UnitHistory, now: u64, width: usize, theme: &Theme, locale: Locale) -> Vec<Line<'static>> {
+    let start = now.saturating_sub(HISTORY_DAYS * DAY_USEC);
+    let mut lines = vec![Line::styled(counts(history, locale), Style::default().add_modifier(Modifier::BOLD))];
+    let strip: Vec<Span> = history
+        .buckets(start, now, width)
+        .into_iter()
+        .map(|bucket| match bucket {
+            Some(kind) => Span::styled(symbol(kind), style(kind, theme)),
+            None => Span::styled("─", Style::default().fg(theme.border)),
+        })
+        .collect();
+    lines.push(Line::from(strip));
+    lines.push(Line::styled(axis(start, width), Style::default().fg(theme.muted)));
+    let legend: Vec<Span> = KINDS
+        .iter()
+        .flat_map(|kind| {
+            [
+                Span::styled(symbol(*kind), style(*kind, theme)),
+                Span::styled(format!(" {}  ", locale.tr(kind.label())), Style::default().fg(theme.muted)),
+            ]
+        })
+        .collect();
+    lines.push(Line::from(legend));
+    lines.push(Line::from(""));
+    lines.extend(history.events().iter().rev().map(|event| {
+        Line::from(vec![
+            Span::styled(
+                format!("{:<36}", format_timestamp_ago(event.timestamp(), now)),
+                Style::default().fg(theme.muted),
+            ),
+            Span::styled(format!("{} {:<11}", symbol(event.kind()), locale.tr(event.kind().label())), style(event.kind(), theme)),
+            Span::raw(event.message().to_string()),
+        ])
+    }));
+    lines
+}
+
+/// The counts then the events, newest first, for the accessible mode.
+pub fn timeline_text(history: &UnitHistory, now: u64, locale: Locale) -> Vec<String> {
+    let mut lines = vec![counts(history, locale)];
+    lines.extend(history.events().iter().rev().map(|event| {
+        format!(
+            "{}: {}, {}",
+            format_timestamp_ago(event.timestamp(), now),
+            locale.tr(event.kind().label()),
+            event.message()
+        )
+    }));
+    lines
+}
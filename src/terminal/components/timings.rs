@@ -0,0 +1,91 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use std::time::Duration;
+
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+use crate::usecases::timings::StageTimings;
+
+const OVERLAY_WIDTH: u16 = 60;
+
+fn millis(duration: Duration) -> String {
+    format!("{:.1}", duration.as_secs_f64() * 1000.0)
+}
+
+/// Debug overlay in the top right corner with the durations of the listing, properties and
+/// journal calls, the filter and the drawing, to tell what makes a host slow. It does not
+/// block input.
+#[derive(Default)]
+pub struct TimingsOverlay {
+    shown: bool,
+}
+
+impl TimingsOverlay {
+    pub fn toggle(&mut self) {
+        self.shown = !self.shown;
+    }
+
+    pub fn linear_text(&self, timings: &[StageTimings], locale: Locale) -> Vec<String> {
+        if !self.shown {
+            return Vec::new();
+        }
+        let mut lines = vec![locale.tr("Timings, in milliseconds").to_string()];
+        lines.extend(timings.iter().map(|timing| {
+            locale.format(
+                "{}: last {}, min {}, avg {}, max {}, {} runs",
+                &[
+                    locale.tr(timing.stage.label()),
+                    &millis(timing.last),
+                    &millis(timing.latency.min()),
+                    &millis(timing.latency.avg()),
+                    &millis(timing.latency.max()),
+                    &timing.latency.samples().to_string(),
+                ],
+            )
+        }));
+        lines
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme, timings: &[StageTimings], locale: Locale) {
+        if !self.shown {
+            return;
+        }
+        let header = Style::default().fg(theme.header).add_modifier(Modifier::BOLD);
+        let [last, min, avg, max, runs] = ["last", "min", "avg", "max", "runs"].map(|header| locale.tr(header));
+        let mut lines = vec![Line::from(Span::styled(
+            format!("{:<12}{:>9}{:>9}{:>9}{:>9}{:>7}", "ms", last, min, avg, max, runs),
+            header,
+        ))];
+        if timings.is_empty() {
+            lines.push(Line::from(Span::styled(locale.tr("Nothing measured yet"), Style::default().fg(theme.muted))));
+        }
+        lines.extend(timings.iter().map(|timing| {
+            Line::from(format!(
+                "{:<12}{:>9}{:>9}{:>9}{:>9}{:>7}",
+                locale.tr(timing.stage.label()),
+                millis(timing.last),
+                millis(timing.latency.min()),
+                millis(timing.latency.avg()),
+                millis(timing.latency.max()),
+                timing.latency.samples()
+            ))
+        }));
+
+        let width = OVERLAY_WIDTH.min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let overlay_area = Rect::new(area.right() - width, area.y, width, height);
+        let overlay = Paragraph::new(lines).style(Style::default().fg(theme.text)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.accent))
+                .title(locale.tr(" Timings ")),
+        );
+        frame.render_widget(Clear, overlay_area);
+        frame.render_widget(overlay, overlay_area);
+    }
+}
@@ -0,0 +1,119 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+use std::time::{Duration, Instant};
+
+use crate::errors::AppError;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+const TOAST_WIDTH: u16 = 50;
+const MAX_TOASTS: usize = 3;
+
+enum ToastKind {
+    Error,
+    Info,
+}
+
+struct Toast {
+    kind: ToastKind,
+    title: String,
+    text: String,
+    shown_at: Instant,
+}
+
+/// Short-lived notifications stacked in the bottom right corner, which do not block input.
+pub struct Toasts {
+    toasts: Vec<Toast>,
+    duration: Duration,
+    locale: Locale,
+}
+
+impl Toasts {
+    pub fn new(duration: Duration, locale: Locale) -> Self {
+        Self {
+            toasts: Vec::new(),
+            duration,
+            locale,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+
+    pub fn push_error(&mut self, error: &AppError) {
+        let title = self.locale.tr(error.title()).to_string();
+        self.push(ToastKind::Error, title, self.locale.tr(error.explanation()).to_string());
+    }
+
+    pub fn push_notice(&mut self, message: String) {
+        self.push(ToastKind::Info, self.locale.tr("Info").to_string(), message);
+    }
+
+    fn push(&mut self, kind: ToastKind, title: String, text: String) {
+        self.toasts.push(Toast {
+            kind,
+            title,
+            text,
+            shown_at: Instant::now(),
+        });
+        if self.toasts.len() > MAX_TOASTS {
+            self.toasts.remove(0);
+        }
+    }
+
+    /// Drops the toasts shown for longer than the configured duration.
+    pub fn expire(&mut self) {
+        let duration = self.duration;
+        self.toasts.retain(|toast| toast.shown_at.elapsed() < duration);
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        self.toasts
+            .iter()
+            .map(|toast| match toast.kind {
+                ToastKind::Error => format!("{} ({}): {}", self.locale.tr("Error"), toast.title, toast.text),
+                ToastKind::Info => format!("{}: {}", toast.title, toast.text),
+            })
+            .collect()
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let width = TOAST_WIDTH.min(area.width);
+        let mut bottom = area.bottom();
+        for toast in self.toasts.iter().rev() {
+            let color = match toast.kind {
+                ToastKind::Error => theme.error,
+                ToastKind::Info => theme.accent,
+            };
+            let text_width = usize::from(width.saturating_sub(2).max(1));
+            let lines = toast.text.lines().map(|line| line.chars().count().div_ceil(text_width).max(1)).sum::<usize>();
+            let height = u16::try_from(lines).unwrap_or(u16::MAX).saturating_add(2);
+            if bottom < area.y + height {
+                break;
+            }
+            bottom -= height;
+            let toast_area = Rect::new(area.right() - width, bottom, width, height);
+
+            let paragraph = Paragraph::new(toast.text.clone())
+                .style(Style::default().fg(theme.text))
+                .wrap(Wrap { trim: true })
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(color))
+                        .title(Line::from(Span::styled(
+                            format!(" {} ", toast.title),
+                            Style::default().fg(color).add_modifier(Modifier::BOLD),
+                        ))),
+                );
+            frame.render_widget(Clear, toast_area);
+            frame.render_widget(paragraph, toast_area);
+        }
+    }
+}
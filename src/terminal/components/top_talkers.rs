@@ -0,0 +1,237 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::domain::log_volume::LogVolume;
+use crate::errors::AppError;
+use crate::terminal::app::{Actions, AppEvent};
+use crate::terminal::components::overlay::Overlay;
+use crate::terminal::theme::Theme;
+use crate::usecases::services_manager::ServicesManager;
+
+/// Units ranked by journal volume over the last hour.
+pub struct TopTalkers {
+    open: bool,
+    volumes: Option<Result<Vec<LogVolume>, String>>,
+    table_state: TableState,
+    sender: Sender<AppEvent>,
+    usecase: Rc<RefCell<ServicesManager>>,
+    config: Rc<Config>,
+}
+
+impl TopTalkers {
+    pub fn new(sender: Sender<AppEvent>, usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
+        Self {
+            open: false,
+            volumes: None,
+            table_state: TableState::default(),
+            sender,
+            usecase,
+            config,
+        }
+    }
+
+    /// Periodically asks the app to re-rank the units when the background check is enabled.
+    pub fn spawn_background_check(&self, sender: Sender<AppEvent>) {
+        let settings = &self.config.top_talkers;
+        if !settings.enabled {
+            return;
+        }
+        let interval = Duration::from_secs(settings.interval.max(1));
+        thread::spawn(move || {
+            loop {
+                if sender
+                    .send(AppEvent::Action(Actions::RefreshTopTalkers))
+                    .is_err()
+                {
+                    break;
+                }
+                thread::sleep(interval);
+            }
+        });
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+        self.table_state.select(Some(0));
+        if self.volumes.is_none() {
+            self.refresh();
+        }
+    }
+
+    pub fn refresh(&mut self) {
+        self.volumes = Some(
+            self.usecase
+                .borrow()
+                .top_talkers()
+                .map_err(|e| self.config.locale.tr(AppError::from(e).explanation()).to_string()),
+        );
+    }
+
+    /// Forgets the ranking, e.g. after switching to another host.
+    pub fn clear(&mut self) {
+        self.volumes = None;
+    }
+
+    /// Units that wrote more entries than the configured threshold during the last hour.
+    pub fn noisy_units(&self) -> HashSet<String> {
+        let threshold = self.config.top_talkers.threshold;
+        match &self.volumes {
+            Some(Ok(volumes)) => volumes
+                .iter()
+                .filter(|volume| volume.entries() >= threshold)
+                .map(|volume| volume.unit().to_string())
+                .collect(),
+            _ => HashSet::new(),
+        }
+    }
+
+    fn help(&self) -> String {
+        let keys = &self.config.keybindings;
+        self.config.locale.shortcuts(&[
+            ("Scroll", format!("{}/{}", keys.up, keys.down)),
+            ("Refresh", keys.refresh.to_string()),
+            ("Close", keys.back.to_string()),
+        ])
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        let len = match &self.volumes {
+            Some(Ok(volumes)) if !volumes.is_empty() => volumes.len(),
+            _ => return,
+        };
+        let selected = self.table_state.selected().unwrap_or(0) as isize;
+        let next = (selected + delta).clamp(0, len as isize - 1);
+        self.table_state.select(Some(next as usize));
+    }
+}
+
+impl Overlay for TopTalkers {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn on_key_event(&mut self, key: KeyEvent) {
+        let keys = &self.config.keybindings;
+        match key {
+            k if k.code == KeyCode::Esc || keys.back.matches(&k) => self.open = false,
+            k if keys.up.matches(&k) => self.scroll(-1),
+            k if keys.down.matches(&k) => self.scroll(1),
+            k if keys.page_up.matches(&k) => self.scroll(-10),
+            k if keys.page_down.matches(&k) => self.scroll(10),
+            // Through the app, which marks the noisy units of the list again.
+            k if keys.refresh.matches(&k) => self.sender.send(AppEvent::Action(Actions::RefreshTopTalkers)).unwrap(),
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open {
+            return;
+        }
+
+        let popup_width = std::cmp::min(80, area.width.saturating_sub(4));
+        let popup_height = std::cmp::min(24, area.height.saturating_sub(2));
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border))
+            .title(format!(" {} ", self.config.locale.tr("Top talkers (journal entries, last hour)")))
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [results_area, help_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+
+        match &self.volumes {
+            None => {}
+            Some(Err(e)) => frame.render_widget(
+                Paragraph::new(e.as_str()).style(Style::default().fg(theme.error)),
+                results_area,
+            ),
+            Some(Ok(volumes)) => {
+                let threshold = self.config.top_talkers.threshold;
+                let rows = volumes.iter().map(|volume| {
+                    let style = if volume.entries() >= threshold {
+                        Style::default().fg(theme.warning).add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(theme.text)
+                    };
+                    Row::new(vec![
+                        Cell::from(volume.unit().to_string()),
+                        Cell::from(volume.entries().to_string()),
+                        Cell::from(format!("{:.1}", volume.entries() as f64 / 60.0)),
+                    ])
+                    .style(style)
+                });
+                let table = Table::new(
+                    rows,
+                    [Constraint::Min(0), Constraint::Length(10), Constraint::Length(10)],
+                )
+                .header(
+                    Row::new(["Unit", "Entries", "Per min"].map(|header| self.config.locale.tr(header)))
+                        .style(Style::default().fg(theme.header).add_modifier(Modifier::BOLD)),
+                )
+                .row_highlight_style(
+                    Style::default()
+                        .bg(theme.selection_bg)
+                        .fg(theme.selection_fg),
+                );
+                frame.render_stateful_widget(table, results_area, &mut self.table_state);
+            }
+        }
+
+        let help = Paragraph::new(Line::from(self.help()))
+        .style(Style::default().fg(theme.muted))
+        .alignment(Alignment::Center);
+        frame.render_widget(help, help_area);
+    }
+
+    fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        let mut lines = vec![locale.tr("Top talkers, journal entries during the last hour").to_string()];
+        match &self.volumes {
+            None => {}
+            Some(Err(e)) => lines.push(format!("{}: {}", locale.tr("Error"), e)),
+            Some(Ok(volumes)) => {
+                let threshold = self.config.top_talkers.threshold;
+                let selected = self.table_state.selected().unwrap_or(0);
+                lines.extend(volumes.iter().enumerate().skip(selected).map(|(index, volume)| {
+                    locale.format(
+                        "Unit {} of {}{}: {}; entries: {}; per minute: {}",
+                        &[
+                            &(index + 1).to_string(),
+                            &volumes.len().to_string(),
+                            if volume.entries() >= threshold { locale.tr(" (noisy)") } else { "" },
+                            volume.unit(),
+                            &volume.entries().to_string(),
+                            &format!("{:.1}", volume.entries() as f64 / 60.0),
+                        ],
+                    )
+                }));
+            }
+        }
+        lines.push(self.help());
+        lines
+    }
+}
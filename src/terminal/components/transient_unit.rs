@@ -0,0 +1,167 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::domain::transient_unit::TransientUnit;
+use crate::terminal::components::confirm::Confirmation;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+const FIELDS: [&str; 5] = ["Command", "Name", "CPUQuota", "MemoryMax", "TasksMax"];
+const PLACEHOLDERS: [&str; 5] = ["e.g. /usr/bin/rsync -a src/ dest/", "", "e.g. 50%", "e.g. 512M", "e.g. 100"];
+const LABEL_WIDTH: usize = 12;
+
+/// Form running a command as a transient service, like `systemd-run`.
+pub struct TransientUnitForm {
+    unit: TransientUnit,
+    selected: usize,
+    error: Option<String>,
+    locale: Locale,
+}
+
+impl TransientUnitForm {
+    pub fn new(locale: Locale) -> Self {
+        Self {
+            unit: TransientUnit::new(),
+            selected: 0,
+            error: None,
+            locale,
+        }
+    }
+
+    pub fn unit(&self) -> &TransientUnit {
+        &self.unit
+    }
+
+    fn text_mut(&mut self) -> &mut String {
+        match self.selected {
+            0 => &mut self.unit.command,
+            1 => &mut self.unit.name,
+            2 => &mut self.unit.cpu_quota,
+            3 => &mut self.unit.memory_max,
+            _ => &mut self.unit.tasks_max,
+        }
+    }
+
+    fn value(&self, index: usize) -> &str {
+        match index {
+            0 => &self.unit.command,
+            1 => &self.unit.name,
+            2 => &self.unit.cpu_quota,
+            3 => &self.unit.memory_max,
+            _ => &self.unit.tasks_max,
+        }
+    }
+
+    fn placeholder(&self, index: usize) -> &str {
+        if index == 1 { self.unit.default_name() } else { self.locale.tr(PLACEHOLDERS[index]) }
+    }
+
+    /// Inserts pasted text in the selected field, e.g. a command line.
+    pub fn on_paste(&mut self, text: &str) {
+        self.text_mut().extend(text.trim().chars().filter(|c| !c.is_control()));
+    }
+
+    pub fn on_key_event(&mut self, key: KeyEvent) -> Confirmation {
+        self.error = None;
+        match key.code {
+            KeyCode::Esc => return Confirmation::Cancelled,
+            KeyCode::Enter => match self.unit.validate() {
+                Ok(()) => return Confirmation::Confirmed,
+                Err(e) => self.error = Some(e),
+            },
+            KeyCode::Tab | KeyCode::Down => self.selected = (self.selected + 1) % FIELDS.len(),
+            KeyCode::BackTab | KeyCode::Up => {
+                self.selected = self.selected.checked_sub(1).unwrap_or(FIELDS.len() - 1)
+            }
+            KeyCode::Char(c) => self.text_mut().push(c),
+            KeyCode::Backspace => {
+                self.text_mut().pop();
+            }
+            _ => {}
+        }
+        Confirmation::Pending
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        let locale = self.locale;
+        let mut lines = vec![locale.tr("Run a command as a transient service").to_string()];
+        lines.extend(FIELDS.iter().enumerate().map(|(index, label)| {
+            let selected = if index == self.selected { locale.tr(" (selected)") } else { "" };
+            format!("{}{}: {}", locale.tr(label), selected, self.value(index))
+        }));
+        if let Some(error) = &self.error {
+            lines.push(format!("{}: {}", locale.tr("Error"), error));
+        }
+        lines.push(locale.tr("Next field: Tab | Previous field: Shift + Tab | Run: Enter | Cancel: Esc").to_string());
+        lines
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let locale = self.locale;
+        let popup_width = std::cmp::min(80, area.width.saturating_sub(4));
+        let popup_height = std::cmp::min(FIELDS.len() as u16 + 9, area.height.saturating_sub(2));
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let mut text: Vec<Line> = vec![
+            Line::from(Span::styled(
+                locale.tr("Runs the command in a service of its own, with the limits set."),
+                Style::default().fg(theme.muted),
+            )),
+            Line::from(""),
+        ];
+        text.extend(FIELDS.iter().enumerate().map(|(index, label)| {
+            let label_style = if index == self.selected {
+                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.muted)
+            };
+            let value = if self.value(index).is_empty() {
+                Span::styled(self.placeholder(index).to_string(), Style::default().fg(theme.muted))
+            } else {
+                Span::raw(self.value(index).to_string())
+            };
+            Line::from(vec![Span::styled(format!("{:<LABEL_WIDTH$}", locale.tr(label)), label_style), value])
+        }));
+        text.push(Line::from(""));
+        text.push(match &self.error {
+            Some(error) => Line::from(Span::styled(error.clone(), Style::default().fg(theme.error))),
+            None => Line::from(Span::styled(
+                locale.format("Starts {}, then shows its logs", &[&self.unit.describe()]),
+                Style::default().fg(theme.muted),
+            )),
+        });
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(
+            locale.tr("Tab: next | Enter: run | Esc: cancel"),
+            Style::default().fg(theme.muted),
+        )));
+
+        let form = Paragraph::new(text).style(Style::default().fg(theme.text)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.highlight_border))
+                .title(locale.tr(" Run a command ")),
+        );
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(form, popup_area);
+
+        let column = LABEL_WIDTH + self.value(self.selected).chars().count();
+        let x = popup_area.x + 1 + u16::try_from(column).unwrap_or(u16::MAX);
+        // Below the description and its blank line.
+        let y = popup_area.y + 3 + self.selected as u16;
+        if x < popup_area.right().saturating_sub(1) {
+            frame.set_cursor_position(Position::new(x, y));
+        }
+    }
+}
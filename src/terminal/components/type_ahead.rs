@@ -0,0 +1,102 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::domain::service::Service;
+use crate::terminal::components::confirm::Confirmation;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+/// Jump mode of the service list: the selection follows the first unit whose name starts with
+/// the typed prefix, like in file managers, without filtering the list.
+pub struct TypeAhead {
+    prefix: String,
+    /// Selection before jumping, restored when cancelled.
+    origin: Option<usize>,
+    /// Whether a unit starts with the prefix, the selection staying put otherwise.
+    found: bool,
+    locale: Locale,
+}
+
+impl TypeAhead {
+    pub fn new(origin: Option<usize>, locale: Locale) -> Self {
+        Self {
+            prefix: String::new(),
+            origin,
+            found: true,
+            locale,
+        }
+    }
+
+    pub fn origin(&self) -> Option<usize> {
+        self.origin
+    }
+
+    pub fn on_key_event(&mut self, key: KeyEvent) -> Confirmation {
+        match key.code {
+            KeyCode::Esc => return Confirmation::Cancelled,
+            KeyCode::Enter => return Confirmation::Confirmed,
+            KeyCode::Char(c) => self.prefix.push(c),
+            KeyCode::Backspace => {
+                self.prefix.pop();
+            }
+            _ => {}
+        }
+        Confirmation::Pending
+    }
+
+    /// Index of the first service starting with the prefix, ignoring case and the .service
+    /// suffix. `None`, and the prefix shown as not found, when none does.
+    pub fn find(&mut self, services: &[Service]) -> Option<usize> {
+        let prefix = self.prefix.to_lowercase();
+        let index = services
+            .iter()
+            .position(|service| service.formatted_name().to_lowercase().starts_with(&prefix));
+        self.found = index.is_some();
+        index
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        let mut lines = vec![self.locale.format("Jump to: {}", &[&self.prefix])];
+        if !self.found {
+            lines.push(self.locale.tr("No unit starts with it").to_string());
+        }
+        lines.push(self.locale.tr("Keep the selection: Enter | Cancel: Esc").to_string());
+        lines
+    }
+
+    /// A one-line box over the bottom of the list, leaving the selection visible.
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let width = std::cmp::min(50, area.width.saturating_sub(4));
+        let popup_area = Rect::new(
+            area.x + area.width.saturating_sub(width + 2),
+            area.y + area.height.saturating_sub(3),
+            width,
+            std::cmp::min(3, area.height),
+        );
+        let prefix_style = if self.found {
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.error).add_modifier(Modifier::BOLD)
+        };
+        let line = Line::from(vec![
+            Span::styled(self.locale.tr("Jump to: "), Style::default().fg(theme.accent)),
+            Span::styled(self.prefix.clone(), prefix_style),
+        ]);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(
+            Paragraph::new(line).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.highlight_border))
+                    .title(self.locale.tr(" Enter: keep | Esc: cancel ")),
+            ),
+            popup_area,
+        );
+    }
+}
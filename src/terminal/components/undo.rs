@@ -0,0 +1,84 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::domain::action_journal::UndoableAction;
+use crate::terminal::components::confirm::Confirmation;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+const HELP: &str = "Undo: Enter/y | Cancel: Esc/n";
+
+/// Asks before undoing the last enable, disable or drop-in of the session, listing what undoing
+/// it changes on disk.
+pub struct UndoPrompt {
+    action: UndoableAction,
+    locale: Locale,
+}
+
+impl UndoPrompt {
+    pub fn new(action: UndoableAction, locale: Locale) -> Self {
+        Self { action, locale }
+    }
+
+    pub fn on_key_event(&mut self, key: KeyEvent) -> Confirmation {
+        match key.code {
+            KeyCode::Enter | KeyCode::Char('y') => Confirmation::Confirmed,
+            KeyCode::Esc | KeyCode::Char('n') => Confirmation::Cancelled,
+            _ => Confirmation::Pending,
+        }
+    }
+
+    fn title(&self) -> String {
+        self.locale.format("Undo the {}?", &[&self.action.description()])
+    }
+
+    pub fn linear_text(&self) -> Vec<String> {
+        let mut lines = vec![self.title()];
+        lines.extend(self.action.undo_steps().into_iter().map(|step| self.locale.format("Will {}", &[&step])));
+        lines.push(self.locale.tr(HELP).to_string());
+        lines
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let steps = self.action.undo_steps();
+        let popup_width = std::cmp::min(90, area.width.saturating_sub(4));
+        let popup_height = std::cmp::min(steps.len() as u16 + 6, area.height.saturating_sub(2));
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let mut text = vec![
+            Line::from(Span::styled(self.title(), Style::default().add_modifier(Modifier::BOLD))),
+            Line::from(""),
+        ];
+        text.extend(
+            steps
+                .into_iter()
+                .map(|step| Line::from(Span::styled(format!("• {}", step), Style::default().fg(theme.muted)))),
+        );
+        text.push(Line::from(""));
+        text.push(Line::from(Span::styled(self.locale.tr(HELP), Style::default().fg(theme.muted))));
+
+        let prompt = Paragraph::new(text)
+            .style(Style::default().fg(theme.text))
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.warning))
+                    .title(self.locale.tr(" Undo "))
+                    .title_alignment(Alignment::Center),
+            );
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(prompt, popup_area);
+    }
+}
@@ -0,0 +1,258 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Position, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+
+use crate::config::Config;
+use crate::domain::unit_file::UnitFileMatch;
+use crate::errors::AppError;
+use crate::terminal::app::{Actions, AppEvent};
+use crate::terminal::components::unit_tabs::UnitTab;
+use crate::terminal::components::overlay::Overlay;
+use crate::terminal::theme::Theme;
+use crate::usecases::services_manager::ServicesManager;
+
+const HELP: &str = "Search, then open the details of the unit: Enter | Scroll: ↑/↓ | Close: Esc";
+
+/// Prompt searching the content of every unit file and drop-in, like grep.
+pub struct UnitFileSearch {
+    open: bool,
+    input: String,
+    /// The query the results are of, to tell a new search from opening a result.
+    searched: String,
+    results: Option<Result<Vec<UnitFileMatch>, String>>,
+    table_state: TableState,
+    sender: Sender<AppEvent>,
+    usecase: Rc<RefCell<ServicesManager>>,
+    config: Rc<Config>,
+}
+
+impl UnitFileSearch {
+    pub fn new(sender: Sender<AppEvent>, usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
+        Self {
+            open: false,
+            input: String::new(),
+            searched: String::new(),
+            results: None,
+            table_state: TableState::default(),
+            sender,
+            usecase,
+            config,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+        self.input.clear();
+        self.searched.clear();
+        self.results = None;
+        self.table_state = TableState::default();
+    }
+
+    fn search(&mut self) {
+        let query = self.input.trim();
+        if query.is_empty() {
+            return;
+        }
+        self.results = Some(
+            self.usecase
+                .borrow()
+                .search_unit_files(query)
+                .map_err(|e| self.config.locale.tr(AppError::from(e).explanation()).to_string()),
+        );
+        self.searched = self.input.clone();
+        self.table_state.select(Some(0));
+    }
+
+    fn matches(&self) -> &[UnitFileMatch] {
+        match &self.results {
+            Some(Ok(matches)) => matches,
+            _ => &[],
+        }
+    }
+
+    /// Selects the unit of the selected match in the list and shows its unit file.
+    fn open_selected(&mut self) {
+        let Some(unit) = self
+            .table_state
+            .selected()
+            .and_then(|selected| self.matches().get(selected))
+            .map(|found| found.unit.clone())
+        else {
+            return;
+        };
+        self.sender.send(AppEvent::Action(Actions::JumpToService(unit))).unwrap();
+        self.sender.send(AppEvent::Action(Actions::GoDetailsTab(UnitTab::UnitFile))).unwrap();
+        self.open = false;
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        let len = self.matches().len();
+        if len == 0 {
+            return;
+        }
+        let selected = self.table_state.selected().unwrap_or(0) as isize;
+        let next = (selected + delta).clamp(0, len as isize - 1);
+        self.table_state.select(Some(next as usize));
+    }
+}
+
+impl Overlay for UnitFileSearch {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn on_key_event(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => self.open = false,
+            KeyCode::Enter if self.input == self.searched => self.open_selected(),
+            KeyCode::Enter => self.search(),
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Char(c) => self.input.push(c),
+            KeyCode::Up => self.scroll(-1),
+            KeyCode::Down => self.scroll(1),
+            KeyCode::PageUp => self.scroll(-10),
+            KeyCode::PageDown => self.scroll(10),
+            _ => {}
+        }
+    }
+
+    /// Text pasted into the query.
+    fn on_paste(&mut self, text: &str) {
+        self.input.push_str(text.lines().next().unwrap_or_default());
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open {
+            return;
+        }
+
+        let popup_width = std::cmp::min(120, area.width.saturating_sub(4));
+        let popup_height = area.height.saturating_sub(2);
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let locale = self.config.locale;
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border))
+            .title(format!(" {} ", locale.tr("Search the unit files")))
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [input_area, results_area, help_area] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+
+        let input = Paragraph::new(self.input.as_str())
+            .style(Style::default().fg(theme.warning))
+            .block(
+                Block::bordered()
+                    .border_style(Style::default().fg(theme.border))
+                    .title(locale.tr("Text, e.g. ExecStart=/usr/bin/python")),
+            );
+        frame.render_widget(input, input_area);
+        #[allow(clippy::cast_possible_truncation)]
+        frame.set_cursor_position(Position::new(
+            input_area.x + self.input.chars().count() as u16 + 1,
+            input_area.y + 1,
+        ));
+
+        match &self.results {
+            None => {}
+            Some(Err(e)) => {
+                frame.render_widget(
+                    Paragraph::new(e.as_str()).style(Style::default().fg(theme.error)),
+                    results_area,
+                );
+            }
+            Some(Ok(matches)) if matches.is_empty() => {
+                frame.render_widget(
+                    Paragraph::new(locale.tr("No unit file contains this text.")).style(Style::default().fg(theme.muted)),
+                    results_area,
+                );
+            }
+            Some(Ok(matches)) => {
+                let rows = matches.iter().map(|found| {
+                    let file = found.path.rsplit('/').next().unwrap_or(&found.path);
+                    Row::new(vec![
+                        Cell::from(found.unit.clone()).style(Style::default().fg(theme.accent)),
+                        Cell::from(format!("{}:{}", file, found.line_number)).style(Style::default().fg(theme.muted)),
+                        Cell::from(found.line.clone()),
+                    ])
+                });
+                let table = Table::new(
+                    rows,
+                    [Constraint::Percentage(25), Constraint::Percentage(25), Constraint::Min(0)],
+                )
+                .header(
+                    Row::new([locale.tr("Unit"), locale.tr("File"), locale.tr("Line")])
+                        .style(Style::default().fg(theme.header).add_modifier(Modifier::BOLD)),
+                )
+                .style(Style::default().fg(theme.text))
+                .row_highlight_style(
+                    Style::default()
+                        .bg(theme.selection_bg)
+                        .fg(theme.selection_fg),
+                );
+                frame.render_stateful_widget(table, results_area, &mut self.table_state);
+            }
+        }
+
+        let help = Paragraph::new(Line::from(locale.tr(HELP)))
+            .style(Style::default().fg(theme.muted))
+            .alignment(Alignment::Center);
+        frame.render_widget(help, help_area);
+    }
+
+    fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        let mut lines = vec![
+            locale.tr("Search the unit files").to_string(),
+            format!("{}: {}", locale.tr("Text"), self.input),
+        ];
+        match &self.results {
+            None => {}
+            Some(Err(e)) => lines.push(format!("{}: {}", locale.tr("Error"), e)),
+            Some(Ok(matches)) if matches.is_empty() => {
+                lines.push(locale.tr("No unit file contains this text.").to_string())
+            }
+            Some(Ok(matches)) => {
+                let selected = self.table_state.selected().unwrap_or(0);
+                lines.extend(matches.iter().enumerate().skip(selected).map(|(index, found)| {
+                    locale.format(
+                        "Match {} of {}: {}; {} line {}: {}",
+                        &[
+                            &(index + 1).to_string(),
+                            &matches.len().to_string(),
+                            &found.unit,
+                            &found.path,
+                            &found.line_number.to_string(),
+                            &found.line,
+                        ],
+                    )
+                }));
+            }
+        }
+        lines.push(locale.tr(HELP).to_string());
+        lines
+    }
+}
@@ -0,0 +1,118 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::Tabs,
+    Frame,
+};
+
+use crate::terminal::app::Actions;
+use crate::terminal::locale::Locale;
+use crate::terminal::theme::Theme;
+
+/// The tabs shown for the selected unit, switched with next_tab/previous_tab or their number.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UnitTab {
+    Status,
+    Properties,
+    UnitFile,
+    Logs,
+    Dependencies,
+    Processes,
+    History,
+    Security,
+}
+
+const TABS: [UnitTab; 8] = [
+    UnitTab::Status,
+    UnitTab::Properties,
+    UnitTab::UnitFile,
+    UnitTab::Logs,
+    UnitTab::Dependencies,
+    UnitTab::Processes,
+    UnitTab::History,
+    UnitTab::Security,
+];
+
+impl UnitTab {
+    pub fn title(&self) -> &'static str {
+        match self {
+            UnitTab::Status => "Status",
+            UnitTab::Properties => "Properties",
+            UnitTab::UnitFile => "Unit file",
+            UnitTab::Logs => "Logs",
+            UnitTab::Dependencies => "Dependencies",
+            UnitTab::Processes => "Processes",
+            UnitTab::History => "History",
+            UnitTab::Security => "Security",
+        }
+    }
+
+    /// The tab of a title, e.g. as saved with the session.
+    pub fn from_title(title: &str) -> Option<UnitTab> {
+        TABS.into_iter().find(|tab| tab.title() == title)
+    }
+
+    fn index(&self) -> usize {
+        TABS.iter().position(|tab| tab == self).unwrap_or(0)
+    }
+
+    pub fn next(&self) -> UnitTab {
+        TABS[(self.index() + 1) % TABS.len()]
+    }
+
+    pub fn previous(&self) -> UnitTab {
+        TABS[(self.index() + TABS.len() - 1) % TABS.len()]
+    }
+
+    /// The tab of a number key, "1" for the first one.
+    pub fn from_key(key: &KeyEvent) -> Option<UnitTab> {
+        if key.modifiers != KeyModifiers::NONE && key.modifiers != KeyModifiers::SHIFT {
+            return None;
+        }
+        let KeyCode::Char(c) = key.code else {
+            return None;
+        };
+        let number = c.to_digit(10)? as usize;
+        TABS.get(number.checked_sub(1)?).copied()
+    }
+
+    /// Whether the tab is one of the details view, rather than a view of its own.
+    pub fn is_details(&self) -> bool {
+        matches!(
+            self,
+            UnitTab::Status | UnitTab::Properties | UnitTab::UnitFile | UnitTab::History | UnitTab::Security
+        )
+    }
+
+    /// The action showing the tab.
+    pub fn action(self) -> Actions {
+        match self {
+            UnitTab::Logs => Actions::GoLog,
+            UnitTab::Dependencies => Actions::GoDependencies,
+            UnitTab::Processes => Actions::GoProcesses,
+            tab => Actions::GoDetailsTab(tab),
+        }
+    }
+
+    /// The tabs bar, with the number of each tab, highlighting this one.
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme, locale: Locale) {
+        let titles = TABS
+            .iter()
+            .enumerate()
+            .map(|(index, tab)| format!("{} {}", index + 1, locale.tr(tab.title())));
+        let tabs = Tabs::new(titles)
+            .select(self.index())
+            .style(Style::default().fg(theme.muted))
+            .highlight_style(Style::default().fg(theme.warning).add_modifier(Modifier::BOLD));
+        frame.render_widget(tabs, area);
+    }
+
+    /// e.g. "Tab 4 of 8: Logs", for the accessible mode.
+    pub fn linear_text(&self, locale: Locale) -> String {
+        locale.format(
+            "Tab {} of {}: {}",
+            &[&(self.index() + 1).to_string(), &TABS.len().to_string(), locale.tr(self.title())],
+        )
+    }
+}
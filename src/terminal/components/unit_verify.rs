@@ -0,0 +1,249 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState},
+    Frame,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+
+use crate::config::Config;
+use crate::domain::unit_verification::{Severity, VerifyFinding};
+use crate::errors::AppError;
+use crate::terminal::app::{Actions, AppEvent};
+use crate::terminal::components::overlay::Overlay;
+use crate::terminal::theme::Theme;
+use crate::usecases::services_manager::ServicesManager;
+
+/// The problems `systemd-analyze verify` finds in the unit file and drop-ins of a unit, with
+/// the file and line of each, to fix a typo before a restart fails on it.
+pub struct UnitVerify {
+    open: bool,
+    unit: String,
+    findings: Option<Result<Vec<VerifyFinding>, String>>,
+    table_state: TableState,
+    sender: Sender<AppEvent>,
+    usecase: Rc<RefCell<ServicesManager>>,
+    config: Rc<Config>,
+}
+
+impl UnitVerify {
+    pub fn new(sender: Sender<AppEvent>, usecase: Rc<RefCell<ServicesManager>>, config: Rc<Config>) -> Self {
+        Self {
+            open: false,
+            unit: String::new(),
+            findings: None,
+            table_state: TableState::default(),
+            sender,
+            usecase,
+            config,
+        }
+    }
+
+    pub fn open(&mut self, name: &str) {
+        self.open = true;
+        self.unit = name.to_string();
+        self.refresh();
+        self.table_state.select(Some(0));
+    }
+
+    /// Verifies a unit just edited, showing the findings only when there are some. Returns
+    /// whether one of them keeps the unit from starting. Managers that cannot verify units
+    /// show nothing.
+    pub fn check(&mut self, name: &str) -> bool {
+        self.unit = name.to_string();
+        self.refresh();
+        let Some(Ok(findings)) = &self.findings else {
+            return false;
+        };
+        if findings.is_empty() {
+            return false;
+        }
+        self.open = true;
+        self.table_state.select(Some(0));
+        findings.iter().any(VerifyFinding::is_error)
+    }
+
+    fn refresh(&mut self) {
+        let findings = self.usecase.borrow().verify_unit(&self.unit);
+        self.findings = Some(findings.map_err(|e| self.config.locale.tr(AppError::from(e).explanation()).trim().to_string()));
+    }
+
+    fn rows(&self) -> &[VerifyFinding] {
+        match &self.findings {
+            Some(Ok(findings)) => findings,
+            _ => &[],
+        }
+    }
+
+    fn count(&self, severity: Severity) -> usize {
+        self.rows().iter().filter(|finding| finding.severity == severity).count()
+    }
+
+    fn scroll(&mut self, delta: isize) {
+        let len = self.rows().len();
+        if len == 0 {
+            return;
+        }
+        let selected = self.table_state.selected().unwrap_or(0) as isize;
+        let next = (selected + delta).clamp(0, len as isize - 1);
+        self.table_state.select(Some(next as usize));
+    }
+
+    fn help(&self) -> String {
+        let keys = &self.config.keybindings;
+        self.config.locale.shortcuts(&[
+            ("Edit the unit file", "Enter".to_string()),
+            ("Verify again", keys.refresh.to_string()),
+            ("Close", keys.back.to_string()),
+        ])
+    }
+
+    /// e.g. "web.service: 1 error, 2 warnings".
+    fn summary(&self) -> String {
+        let locale = self.config.locale;
+        if !matches!(self.findings, Some(Ok(_))) {
+            return locale.format("{}: not verified", &[&self.unit]);
+        }
+        let counts: Vec<String> = [
+            (Severity::Error, "{} error", "{} errors"),
+            (Severity::Warning, "{} warning", "{} warnings"),
+        ]
+        .into_iter()
+        .map(|(severity, one, many)| (self.count(severity), one, many))
+        .filter(|(count, _, _)| *count > 0)
+        .map(|(count, one, many)| locale.format(if count == 1 { one } else { many }, &[&count.to_string()]))
+        .collect();
+        if counts.is_empty() {
+            locale.format("{}: no problem found", &[&self.unit])
+        } else {
+            format!("{}: {}", self.unit, counts.join(", "))
+        }
+    }
+}
+
+impl Overlay for UnitVerify {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn on_key_event(&mut self, key: KeyEvent) {
+        let config = self.config.clone();
+        let keys = &config.keybindings;
+        match key {
+            k if k.code == KeyCode::Esc || keys.back.matches(&k) || keys.verify.matches(&k) => self.open = false,
+            k if k.code == KeyCode::Enter => {
+                self.open = false;
+                self.sender.send(AppEvent::Action(Actions::EditUnit(self.unit.clone(), false))).unwrap();
+            }
+            k if keys.up.matches(&k) => self.scroll(-1),
+            k if keys.down.matches(&k) => self.scroll(1),
+            k if keys.page_up.matches(&k) => self.scroll(-10),
+            k if keys.page_down.matches(&k) => self.scroll(10),
+            k if keys.refresh.matches(&k) => self.refresh(),
+            _ => {}
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if !self.open {
+            return;
+        }
+
+        let popup_width = std::cmp::min(140, area.width.saturating_sub(4));
+        // Rows of the findings, their header, the borders and the help line.
+        let popup_height = (self.rows().len() as u16 + 5).max(8).min(area.height.saturating_sub(4));
+        let popup_area = Rect::new(
+            area.x + (area.width.saturating_sub(popup_width)) / 2,
+            area.y + (area.height.saturating_sub(popup_height)) / 2,
+            popup_width,
+            popup_height,
+        );
+
+        let border = if self.count(Severity::Error) > 0 {
+            theme.error
+        } else if self.count(Severity::Warning) > 0 {
+            theme.warning
+        } else {
+            theme.border
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border))
+            .title(format!(" {} ", self.summary()))
+            .title_alignment(Alignment::Center);
+        let inner = block.inner(popup_area);
+        frame.render_widget(Clear, popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [table_area, help_area] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+
+        match &self.findings {
+            None => {}
+            Some(Err(e)) => {
+                frame.render_widget(Paragraph::new(e.as_str()).style(Style::default().fg(theme.error)), table_area)
+            }
+            Some(Ok(findings)) if findings.is_empty() => frame.render_widget(
+                Paragraph::new(self.config.locale.tr("systemd-analyze verify found nothing to fix")).style(Style::default().fg(theme.muted)),
+                table_area,
+            ),
+            Some(Ok(findings)) => {
+                let rows: Vec<Row> = findings
+                    .iter()
+                    .map(|finding| {
+                        let color = if finding.is_error() { theme.error } else { theme.warning };
+                        Row::new(vec![
+                            Cell::from(finding.severity.label()).style(Style::default().fg(color)),
+                            Cell::from(finding.location()).style(Style::default().fg(theme.accent)),
+                            Cell::from(finding.message.as_str()),
+                        ])
+                    })
+                    .collect();
+                // The locations are as wide as needed, up to half of the popup, leaving the rest to the messages.
+                let location_width = findings
+                    .iter()
+                    .map(|finding| finding.location().chars().count() as u16)
+                    .max()
+                    .unwrap_or(0)
+                    .clamp(9, table_area.width / 2);
+                let table = Table::new(
+                    rows,
+                    [Constraint::Length(8), Constraint::Length(location_width), Constraint::Min(0)],
+                )
+                .header(
+                    Row::new(["", "File:line", "Problem"].map(|header| self.config.locale.tr(header)))
+                        .style(Style::default().fg(theme.header).add_modifier(Modifier::BOLD)),
+                )
+                .style(Style::default().fg(theme.text))
+                .row_highlight_style(Style::default().bg(theme.selection_bg).fg(theme.selection_fg));
+                frame.render_stateful_widget(table, table_area, &mut self.table_state);
+            }
+        }
+
+        frame.render_widget(
+            Paragraph::new(self.help())
+                .style(Style::default().fg(theme.muted))
+                .alignment(Alignment::Center),
+            help_area,
+        );
+    }
+
+    fn linear_text(&self) -> Vec<String> {
+        let locale = self.config.locale;
+        let mut lines = vec![self.summary()];
+        if let Some(Err(e)) = &self.findings {
+            lines.push(format!("{}: {}", locale.tr("Error"), e));
+        }
+        let selected = self.table_state.selected().unwrap_or(0);
+        for (index, finding) in self.rows().iter().enumerate().skip(selected) {
+            let flag = if index == selected { locale.tr(" (selected)") } else { "" };
+            let location = finding.location();
+            let location = if location.is_empty() { String::new() } else { locale.format(" at {}", &[&location]) };
+            lines.push(format!("{}{}{}: {}", locale.tr(finding.severity.label()), flag, location, finding.message));
+        }
+        lines.push(self.help());
+        lines
+    }
+}
@@ -0,0 +1,75 @@
+/// Score of each matched character.
+const MATCH: i64 = 16;
+/// Extra score for a character right after the previous match.
+const CONSECUTIVE: i64 = 8;
+/// Extra score for a character starting the text or a word, e.g. the `d` of `nginx-debug`.
+const WORD_START: i64 = 10;
+/// Penalty per skipped character between two matches.
+const GAP: i64 = 1;
+
+/// How well a query matches a text, with the char indices of the matched characters.
+pub struct FuzzyMatch {
+    pub score: i64,
+    pub positions: Vec<usize>,
+}
+
+fn is_word_start(text: &[char], index: usize) -> bool {
+    index == 0 || matches!(text[index - 1], '-' | '_' | '.' | '@' | '/' | ' ' | ':')
+}
+
+/// Matches the characters of `query` in order, anywhere in `text`, ignoring case, keeping
+/// the placement with the best score (fzf-style): consecutive characters and word starts
+/// score more, gaps less. `None` when some character of the query is missing.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    // One lowercase char per char, so that positions stay valid in the original text.
+    let lower = |c: char| c.to_lowercase().next().unwrap_or(c);
+    let query: Vec<char> = query.chars().map(lower).collect();
+    let text: Vec<char> = text.chars().map(lower).collect();
+    if query.is_empty() {
+        return Some(FuzzyMatch { score: 0, positions: Vec::new() });
+    }
+    if query.len() > text.len() {
+        return None;
+    }
+
+    // best[i][j]: best score with query[..=i] matched and query[i] on text[j],
+    // from[i][j]: where query[i - 1] was matched for that score.
+    let mut best = vec![vec![None::<i64>; text.len()]; query.len()];
+    let mut from = vec![vec![0usize; text.len()]; query.len()];
+    for (i, &wanted) in query.iter().enumerate() {
+        for j in i..text.len() {
+            if text[j] != wanted {
+                continue;
+            }
+            let bonus = MATCH + if is_word_start(&text, j) { WORD_START } else { 0 };
+            if i == 0 {
+                best[i][j] = Some(bonus - GAP * j as i64);
+                continue;
+            }
+            for k in (i - 1)..j {
+                let Some(previous) = best[i - 1][k] else {
+                    continue;
+                };
+                let link = if k + 1 == j { CONSECUTIVE } else { -GAP * (j - k - 1) as i64 };
+                let score = previous + bonus + link;
+                if best[i][j].is_none_or(|current| score > current) {
+                    best[i][j] = Some(score);
+                    from[i][j] = k;
+                }
+            }
+        }
+    }
+
+    let last = query.len() - 1;
+    let (mut position, score) = best[last]
+        .iter()
+        .enumerate()
+        .filter_map(|(j, score)| score.map(|score| (j, score)))
+        .max_by_key(|&(j, score)| (score, std::cmp::Reverse(j)))?;
+    let mut positions = vec![position; query.len()];
+    for i in (1..query.len()).rev() {
+        position = from[i][position];
+        positions[i - 1] = position;
+    }
+    Some(FuzzyMatch { score, positions })
+}
@@ -0,0 +1,206 @@
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::Terminal;
+
+use crate::config::KeyBinding;
+
+use super::app::App;
+
+/// Keys separated by spaces, written like the keybindings of the config, e.g. "down down enter ctrl+t".
+pub fn parse_keys(script: &str) -> Result<Vec<KeyEvent>, String> {
+    script
+        .split_whitespace()
+        .map(|key| KeyBinding::try_from(key.to_string()).map(|binding| binding.key_event()))
+        .collect()
+}
+
+/// A key pressed as `parse_keys` reads it, e.g. "ctrl+t" or "G", `None` for the keys it has
+/// no name for.
+pub fn key_name(key: &KeyEvent) -> Option<String> {
+    let name = match key.code {
+        KeyCode::Char(' ') => "space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("f{}", n),
+        KeyCode::Up => "up".to_string(),
+        KeyCode::Down => "down".to_string(),
+        KeyCode::Left => "left".to_string(),
+        KeyCode::Right => "right".to_string(),
+        KeyCode::PageUp => "pageup".to_string(),
+        KeyCode::PageDown => "pagedown".to_string(),
+        KeyCode::Home => "home".to_string(),
+        KeyCode::End => "end".to_string(),
+        KeyCode::Enter => "enter".to_string(),
+        KeyCode::Esc => "esc".to_string(),
+        KeyCode::Tab => "tab".to_string(),
+        KeyCode::BackTab => "backtab".to_string(),
+        KeyCode::Backspace => "backspace".to_string(),
+        KeyCode::Delete => "delete".to_string(),
+        KeyCode::Insert => "insert".to_string(),
+        _ => return None,
+    };
+    let mut prefix = String::new();
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        prefix.push_str("ctrl+");
+    }
+    if key.modifiers.contains(KeyModifiers::ALT) {
+        prefix.push_str("alt+");
+    }
+    Some(prefix + &name)
+}
+
+/// Columns and rows of a screen, e.g. "120x40".
+pub fn parse_size(size: &str) -> Result<(u16, u16), String> {
+    size.split_once('x')
+        .and_then(|(columns, rows)| Some((columns.parse().ok()?, rows.parse().ok()?)))
+        .filter(|(columns, rows)| *columns > 0 && *rows > 0)
+        .ok_or_else(|| format!("invalid size \"{}\", expected e.g. 120x40", size))
+}
+
+/// Text of the screen, one line per row without the trailing spaces.
+pub fn screen_text(buffer: &Buffer) -> String {
+    let width = buffer.area.width as usize;
+    buffer
+        .content
+        .chunks(width.max(1))
+        .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>().trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Runs the app on an in-memory terminal of `size`, pressing `keys`, and returns the last
+/// screen drawn as text. No terminal is read or written, which makes the components
+/// checkable from scripts and the screenshots of the documentation reproducible.
+pub fn screenshot(mut app: App, keys: Vec<KeyEvent>, (columns, rows): (u16, u16)) -> Result<String> {
+    let mut terminal = Terminal::new(app.backend(TestBackend::new(columns, rows)))?;
+    app.script(keys);
+    app.run(&mut terminal)?;
+    Ok(screen_text(terminal.backend().inner().buffer()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::sync::mpsc;
+
+    use super::*;
+    use crate::config::{Config, MaintenanceConfig};
+    use crate::infrastructure::backend::Backend;
+    use crate::infrastructure::demo_service_adapter::{DemoServiceAdapter, Fixture};
+    use crate::terminal::app_state::AppState;
+    use crate::usecases::services_manager::ServicesManager;
+
+    const SIZE: (u16, u16) = (120, 30);
+
+    /// The app as `--demo --screenshot` builds it, on the units and failures of `fixture`.
+    fn demo_app(fixture: Fixture, config: Config) -> App {
+        let config = Rc::new(Config { backend: Backend::Demo, ..config });
+        let (event_tx, event_rx) = mpsc::channel();
+        let mut usecase = ServicesManager::new(Box::new(DemoServiceAdapter::new(fixture, config.unit_types.clone())));
+        usecase.set_maintenance(config.maintenance.clone());
+        let usecase = Rc::new(RefCell::new(usecase));
+        let state = AppState::new(event_tx.clone(), usecase.clone(), config.clone());
+        App::new(event_tx, event_rx, state, usecase, config)
+    }
+
+    fn shot(fixture: Fixture, config: Config, keys: &str) -> String {
+        screenshot(demo_app(fixture, config), parse_keys(keys).unwrap(), SIZE).unwrap()
+    }
+
+    fn fixture(toml: &str) -> Fixture {
+        toml::from_str(toml).unwrap()
+    }
+
+    /// The row of the list showing `unit`.
+    fn row<'a>(screen: &'a str, unit: &str) -> &'a str {
+        screen
+            .lines()
+            .find(|line| line.split_whitespace().any(|word| word == unit))
+            .unwrap_or_else(|| panic!("no row for {} in\n{}", unit, screen))
+    }
+
+    #[test]
+    fn parses_keys_like_the_keybindings() {
+        let keys = parse_keys("down  ctrl+t G space f5 |").unwrap();
+        let names: Vec<String> = keys.iter().filter_map(key_name).collect();
+        assert_eq!(names, ["down", "ctrl+t", "G", "space", "f5", "|"]);
+        assert!(parse_keys("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn refuses_unknown_keys() {
+        assert!(parse_keys("down nowhere enter").is_err());
+        assert!(parse_keys("f99").is_err());
+    }
+
+    #[test]
+    fn parses_sizes() {
+        assert_eq!(parse_size("120x40"), Ok((120, 40)));
+        assert!(parse_size("120").is_err());
+        assert!(parse_size("0x40").is_err());
+        assert!(parse_size("wide x tall").is_err());
+    }
+
+    #[test]
+    fn screenshot_lists_the_units() {
+        let screen = shot(Fixture::examples(), Config::default(), "");
+        assert_eq!(screen.lines().count(), SIZE.1 as usize);
+        assert!(row(&screen, "nginx").contains("active (running)"));
+        assert!(row(&screen, "webapp").contains("failed"));
+    }
+
+    #[test]
+    fn screenshot_shows_the_result_of_an_action() {
+        let units = r#"[[units]]
+            name = "api.service"
+            [[units]]
+            name = "db.service""#;
+        let screen = shot(fixture(units), Config::default(), "s");
+        assert!(row(&screen, "api").contains("active (running)"), "{}", screen);
+        assert!(row(&screen, "db").contains("inactive"), "{}", screen);
+        assert!(screen.contains("start of api.service: done"), "{}", screen);
+    }
+
+    #[test]
+    fn screenshot_reports_a_failing_action() {
+        let failing = r#"[[units]]
+            name = "api.service"
+            [[failures]]
+            action = "start"
+            unit = "api.service"
+            message = "Job for api.service failed because the control process exited with error code.""#;
+        let screen = shot(fixture(failing), Config::default(), "s");
+        assert!(row(&screen, "api").contains("inactive"), "{}", screen);
+        assert!(screen.contains("Job for api.service failed"), "{}", screen);
+    }
+
+    #[test]
+    fn screenshot_asks_to_confirm_a_critical_unit() {
+        let config = Config {
+            maintenance: MaintenanceConfig {
+                critical_units: vec!["nginx".to_string()],
+                ..MaintenanceConfig::default()
+            },
+            ..Config::default()
+        };
+        let units = r#"[[units]]
+            name = "nginx.service"
+            state = "active""#;
+        let screen = shot(fixture(units), config.clone(), "x");
+        assert!(screen.contains("Type nginx.service to stop"), "{}", screen);
+
+        let typed: Vec<String> = "nginx.service".chars().map(String::from).collect();
+        let screen = shot(fixture(units), config, &format!("x {} enter", typed.join(" ")));
+        assert!(row(&screen, "nginx").contains("inactive"), "{}", screen);
+    }
+
+    #[test]
+    fn screenshot_of_the_help_shows_every_key() {
+        let app = demo_app(Fixture::examples(), Config::default());
+        let screen = screenshot(app, parse_keys("?").unwrap(), (120, 80)).unwrap();
+        let columns = screen.lines().find(|line| line.contains("Columns")).expect(&screen);
+        assert!(columns.split_whitespace().any(|word| word == "|"), "{}", screen);
+    }
+}
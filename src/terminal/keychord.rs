@@ -0,0 +1,115 @@
+use crossterm::event::KeyEvent;
+use std::time::{Duration, Instant};
+
+use crate::config::{ChordBindings, KeyBinding};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChordAction {
+    GoFailed,
+    GoTop,
+    GoBottom,
+    QuickActions,
+}
+
+impl ChordAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChordAction::GoFailed => "Next failed",
+            ChordAction::GoTop => "Top",
+            ChordAction::GoBottom => "Bottom",
+            ChordAction::QuickActions => "Quick actions",
+        }
+    }
+}
+
+pub enum ChordStep {
+    /// The keys so far start a chord, wait for the next one.
+    Pending,
+    Matched(ChordAction),
+    /// No chord goes on with these keys, they are handled one by one as single-key bindings.
+    Unmatched(Vec<KeyEvent>),
+}
+
+/// Collects the keys of a chord until it is complete, can no longer match, or times out.
+pub struct KeyChords {
+    pending: Vec<KeyEvent>,
+    last_key: Instant,
+    timeout: Duration,
+}
+
+impl KeyChords {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            pending: Vec::new(),
+            last_key: Instant::now(),
+            timeout,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    fn starts_with(keys: &[KeyBinding], pending: &[KeyEvent]) -> bool {
+        keys.len() >= pending.len() && keys.iter().zip(pending).all(|(binding, key)| binding.matches(key))
+    }
+
+    pub fn feed(&mut self, key: KeyEvent, chords: &ChordBindings) -> ChordStep {
+        self.pending.push(key);
+        self.last_key = Instant::now();
+
+        let mut prefix = false;
+        for (action, sequence) in chords.sequences() {
+            let keys = sequence.keys();
+            if Self::starts_with(keys, &self.pending) {
+                if keys.len() == self.pending.len() {
+                    self.pending.clear();
+                    return ChordStep::Matched(action);
+                }
+                prefix = true;
+            }
+        }
+
+        if prefix {
+            ChordStep::Pending
+        } else {
+            ChordStep::Unmatched(std::mem::take(&mut self.pending))
+        }
+    }
+
+    /// Gives back the keys of a chord left unfinished for longer than the timeout.
+    pub fn expire(&mut self) -> Vec<KeyEvent> {
+        if self.is_pending() && self.last_key.elapsed() >= self.timeout {
+            std::mem::take(&mut self.pending)
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Time left before the pending chord expires.
+    pub fn remaining(&self) -> Duration {
+        self.timeout.saturating_sub(self.last_key.elapsed())
+    }
+
+    /// The chords that can still complete, as "rest of the keys: label".
+    pub fn completions(&self, chords: &ChordBindings) -> Vec<String> {
+        chords
+            .sequences()
+            .into_iter()
+            .filter(|(_, sequence)| {
+                sequence.keys().len() > self.pending.len() && Self::starts_with(sequence.keys(), &self.pending)
+            })
+            .map(|(action, sequence)| {
+                let rest: Vec<String> = sequence.keys()[self.pending.len()..]
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect();
+                format!("{}: {}", rest.join(" "), action.label())
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Values of the `language` setting besides "auto".
+pub const LANGUAGES: [&str; 2] = ["en", "pt"];
+
+/// Language of the shortcut bars, titles and messages. Strings are written in English in the
+/// code and looked up in the catalog of the language; those missing from it stay in English.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    English,
+    Portuguese,
+}
+
+impl Locale {
+    /// The language of a `language` setting, "auto" following LC_ALL, LC_MESSAGES or LANG.
+    pub fn from_setting(setting: &str) -> Option<Self> {
+        match setting {
+            "auto" => Some(Self::detect()),
+            "en" => Some(Self::English),
+            "pt" => Some(Self::Portuguese),
+            _ => None,
+        }
+    }
+
+    /// The first locale variable set, e.g. "pt_BR.UTF-8"; English when none is or its language
+    /// has no catalog.
+    fn detect() -> Self {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|variable| std::env::var(variable).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default();
+        let language = locale.split(['_', '.', '@']).next().unwrap_or_default();
+        Self::from_setting(language).unwrap_or_default()
+    }
+
+    /// The translation of an English string, or the string itself.
+    pub fn tr(self, text: &str) -> &str {
+        match self.catalog() {
+            Some(catalog) => catalog.get(text).map_or(text, String::as_str),
+            None => text,
+        }
+    }
+
+    /// The translation of a string with `{}` placeholders, filled with `args` in order.
+    pub fn format(self, text: &str, args: &[&str]) -> String {
+        let mut parts = self.tr(text).split("{}");
+        let mut formatted = parts.next().unwrap_or_default().to_string();
+        for (part, arg) in parts.zip(args.iter().chain(std::iter::repeat(&""))) {
+            formatted.push_str(arg);
+            formatted.push_str(part);
+        }
+        formatted
+    }
+
+    /// A shortcut bar, "Label: keys | Label: keys", with the labels translated.
+    pub fn shortcuts(self, entries: &[(&str, String)]) -> String {
+        entries
+            .iter()
+            .map(|(label, keys)| format!("{}: {}", self.tr(label), keys))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    fn catalog(self) -> Option<&'static HashMap<String, String>> {
+        static PORTUGUESE: OnceLock<HashMap<String, String>> = OnceLock::new();
+        match self {
+            Locale::English => None,
+            Locale::Portuguese => Some(PORTUGUESE.get_or_init(|| parse_catalog(include_str!("../../locales/pt.toml")))),
+        }
+    }
+}
+
+/// A catalog is a TOML table of English strings to their translation. Being part of the binary,
+/// a broken one is a build mistake and leaves the strings in English.
+fn parse_catalog(content: &str) -> HashMap<String, String> {
+    toml::from_str(content).unwrap_or_default()
+}
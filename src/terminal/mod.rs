@@ -1,2 +1,11 @@
 pub mod app;
+pub mod app_state;
 pub mod components;
+pub mod fuzzy;
+pub mod headless;
+pub mod keychord;
+pub mod locale;
+pub mod plain_backend;
+pub mod state_style;
+pub mod terminal_guard;
+pub mod theme;
@@ -0,0 +1,140 @@
+use std::io;
+
+use ratatui::{
+    backend::{Backend, ClearType, WindowSize},
+    buffer::Cell,
+    layout::{Position, Size},
+    style::{Color, Modifier},
+};
+
+/// Backend drawing through another one without colors, without glyphs outside of ASCII, or
+/// both, for terminals and screen readers that handle neither. Every screen goes through it,
+/// so the components keep drawing as usual.
+pub struct PlainBackend<B: Backend> {
+    inner: B,
+    /// Colors are dropped; backgrounds, which mark the selection, become reverse video.
+    no_color: bool,
+    /// Box drawing, arrows, state icons and accented letters are drawn with ASCII characters.
+    ascii: bool,
+}
+
+impl<B: Backend> PlainBackend<B> {
+    pub fn new(inner: B, no_color: bool, ascii: bool) -> Self {
+        Self { inner, no_color, ascii }
+    }
+
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    fn plain(&self, cell: &Cell) -> Cell {
+        let mut cell = cell.clone();
+        if self.no_color {
+            if cell.bg != Color::Reset {
+                cell.modifier.insert(Modifier::REVERSED);
+            }
+            cell.fg = Color::Reset;
+            cell.bg = Color::Reset;
+            cell.underline_color = Color::Reset;
+        }
+        if self.ascii && !cell.symbol().is_ascii() {
+            let symbol: String = cell.symbol().chars().map(ascii).collect();
+            cell.set_symbol(&symbol);
+        }
+        cell
+    }
+}
+
+/// The ASCII character closest in look or meaning to a glyph, '?' when there is none.
+fn ascii(glyph: char) -> char {
+    match glyph {
+        c if c.is_ascii() => c,
+        '─' | '━' | '═' | '╌' | '┄' => '-',
+        '│' | '┃' | '║' | '╎' | '┆' => '|',
+        '┌' | '┐' | '└' | '┘' | '├' | '┤' | '┬' | '┴' | '┼' | '╭' | '╮' | '╰' | '╯' | '╔' | '╗' | '╚'
+        | '╝' => '+',
+        '●' | '•' | '◆' => '*',
+        '○' | '◯' => 'o',
+        '◐' | '◑' | '↻' | '⟳' => '~',
+        '✗' | '✘' | '×' => 'x',
+        '✓' | '✔' => 'v',
+        '·' | '…' => '.',
+        '⊘' => '!',
+        '❄' => '#',
+        '⇄' | '↔' => '=',
+        '↑' | '▲' => '^',
+        '↓' | '▼' => 'v',
+        '←' | '◀' => '<',
+        '→' | '▶' | '›' | '»' => '>',
+        '▁' | '▂' => '_',
+        '▃' | '▄' => '.',
+        '▅' | '▆' => ':',
+        '▇' | '█' | '▉' | '▊' | '▋' | '▌' | '▍' | '▎' | '▏' => '#',
+        '░' | '▒' | '▓' => '#',
+        'á' | 'à' | 'â' | 'ã' | 'ä' => 'a',
+        'Á' | 'À' | 'Â' | 'Ã' | 'Ä' => 'A',
+        'é' | 'è' | 'ê' | 'ë' => 'e',
+        'É' | 'È' | 'Ê' | 'Ë' => 'E',
+        'í' | 'ì' | 'î' | 'ï' => 'i',
+        'Í' | 'Ì' | 'Î' | 'Ï' => 'I',
+        'ó' | 'ò' | 'ô' | 'õ' | 'ö' => 'o',
+        'Ó' | 'Ò' | 'Ô' | 'Õ' | 'Ö' => 'O',
+        'ú' | 'ù' | 'û' | 'ü' => 'u',
+        'Ú' | 'Ù' | 'Û' | 'Ü' => 'U',
+        'ç' => 'c',
+        'Ç' => 'C',
+        'ñ' => 'n',
+        'Ñ' => 'N',
+        _ => '?',
+    }
+}
+
+impl<B: Backend> Backend for PlainBackend<B> {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        let cells: Vec<(u16, u16, Cell)> = content.map(|(x, y, cell)| (x, y, self.plain(cell))).collect();
+        self.inner.draw(cells.iter().map(|(x, y, cell)| (*x, *y, cell)))
+    }
+
+    fn append_lines(&mut self, n: u16) -> io::Result<()> {
+        self.inner.append_lines(n)
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.inner.hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.inner.show_cursor()
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        self.inner.get_cursor_position()
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+        self.inner.set_cursor_position(position)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.inner.clear()
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+        self.inner.clear_region(clear_type)
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        self.inner.size()
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        self.inner.window_size()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
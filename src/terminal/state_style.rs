@@ -0,0 +1,70 @@
+use ratatui::style::{Modifier, Style};
+
+use crate::config::IconConfig;
+use crate::domain::service_state::ServiceState;
+use crate::terminal::theme::Theme;
+
+/// Color of the active state, e.g. green for running units and red for failed ones.
+pub fn active_style(state: &ServiceState, theme: &Theme) -> Style {
+    match state.active() {
+        _ if state.is_frozen() => Style::default().fg(theme.activating).add_modifier(Modifier::ITALIC),
+        "active" => Style::default().fg(theme.active),
+        "activating" | "deactivating" | "reloading" | "refreshing" => Style::default().fg(theme.activating),
+        "failed" => Style::default().fg(theme.failed),
+        _ => Style::default().fg(theme.muted),
+    }
+}
+
+/// Color of the unit file state, masked units standing out.
+pub fn file_style(file: &str, theme: &Theme) -> Style {
+    match file {
+        "enabled" | "enabled-runtime" | "alias" => Style::default().fg(theme.active),
+        "masked" | "masked-runtime" | "bad" => Style::default().fg(theme.failed),
+        "disabled" => Style::default().fg(theme.muted),
+        _ => Style::default().fg(theme.text),
+    }
+}
+
+/// Color of the load state, units that could not be loaded standing out.
+pub fn load_style(load: &str, theme: &Theme) -> Style {
+    match load {
+        "loaded" => Style::default().fg(theme.text),
+        "not-found" | "stub" => Style::default().fg(theme.warning),
+        _ => Style::default().fg(theme.failed),
+    }
+}
+
+/// Icon before the active state, `None` when icons are off.
+pub fn active_icon<'a>(state: &ServiceState, icons: &'a IconConfig) -> Option<&'a str> {
+    if !icons.show {
+        return None;
+    }
+    Some(match state.active() {
+        _ if state.is_frozen() => &icons.frozen,
+        "active" => &icons.active,
+        "activating" | "deactivating" | "reloading" | "refreshing" => &icons.activating,
+        "failed" => &icons.failed,
+        _ => &icons.inactive,
+    })
+}
+
+/// Icon before the unit file state, `None` when icons are off or the state has none, e.g. static.
+pub fn file_icon<'a>(file: &str, icons: &'a IconConfig) -> Option<&'a str> {
+    if !icons.show {
+        return None;
+    }
+    match file {
+        "enabled" | "enabled-runtime" | "alias" => Some(&icons.enabled),
+        "disabled" => Some(&icons.disabled),
+        "masked" | "masked-runtime" => Some(&icons.masked),
+        _ => None,
+    }
+}
+
+/// The state with its icon, e.g. "● active (running)".
+pub fn with_icon(icon: Option<&str>, text: String) -> String {
+    match icon {
+        Some(icon) if !icon.is_empty() => format!("{} {}", icon, text),
+        _ => text,
+    }
+}
@@ -0,0 +1,54 @@
+use crossterm::cursor::Show;
+use crossterm::event::{DisableBracketedPaste, DisableFocusChange, EnableBracketedPaste, EnableFocusChange};
+use crossterm::execute;
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use std::io::{self, stdout};
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether the terminal is taken over by a guard, so that panics outside of the TUI, e.g. in a
+/// subcommand writing to a pipe, print nothing but their report.
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Puts the terminal back the way the shell expects it: cooked mode, main screen, cursor shown,
+/// no bracketed paste nor focus events. Every step is tried even when one fails.
+fn restore_terminal() {
+    if ACTIVE.swap(false, Ordering::SeqCst) {
+        let _ = execute!(stdout(), DisableBracketedPaste, DisableFocusChange, LeaveAlternateScreen, Show);
+        let _ = disable_raw_mode();
+    }
+}
+
+/// Restores the terminal before the report of a panic is printed, then ends the process: after
+/// a panic on a background thread, the TUI would otherwise keep drawing on a restored terminal.
+pub fn install_panic_hook() {
+    let report = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let was_active = ACTIVE.load(Ordering::SeqCst);
+        restore_terminal();
+        report(info);
+        if was_active {
+            std::process::exit(101);
+        }
+    }));
+}
+
+/// Raw mode and the alternate screen, for as long as the guard lives. Dropping it, when the TUI
+/// returns, fails with an error or unwinds from a panic, restores the terminal.
+pub struct TerminalGuard;
+
+impl TerminalGuard {
+    pub fn enter() -> io::Result<Self> {
+        enable_raw_mode()?;
+        ACTIVE.store(true, Ordering::SeqCst);
+        let guard = Self;
+        execute!(stdout(), EnterAlternateScreen, EnableBracketedPaste, EnableFocusChange)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
@@ -0,0 +1,116 @@
+use ratatui::style::Color;
+
+use crate::config::ColorOverrides;
+
+pub const THEME_NAMES: [&str; 3] = ["dark", "light", "high-contrast"];
+
+/// Colors used by every component when drawing.
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub name: &'static str,
+    pub text: Color,
+    pub accent: Color,
+    pub header: Color,
+    pub title: Color,
+    pub selection_fg: Color,
+    pub selection_bg: Color,
+    pub inactive_selection_bg: Color,
+    pub active: Color,
+    pub activating: Color,
+    pub failed: Color,
+    pub border: Color,
+    pub highlight_border: Color,
+    pub error: Color,
+    pub warning: Color,
+    pub muted: Color,
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Self {
+            name: "dark",
+            text: Color::Gray,
+            accent: Color::Cyan,
+            header: Color::White,
+            title: Color::LightMagenta,
+            selection_fg: Color::White,
+            selection_bg: Color::Blue,
+            inactive_selection_bg: Color::DarkGray,
+            active: Color::Green,
+            activating: Color::Yellow,
+            failed: Color::Red,
+            border: Color::White,
+            highlight_border: Color::Rgb(255, 165, 0),
+            error: Color::Red,
+            warning: Color::Yellow,
+            muted: Color::Gray,
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            name: "light",
+            text: Color::Black,
+            accent: Color::Blue,
+            header: Color::Black,
+            title: Color::Magenta,
+            selection_fg: Color::Black,
+            selection_bg: Color::LightBlue,
+            inactive_selection_bg: Color::Gray,
+            active: Color::Rgb(0, 135, 0),
+            activating: Color::Rgb(175, 115, 0),
+            failed: Color::Red,
+            border: Color::Black,
+            highlight_border: Color::Rgb(215, 95, 0),
+            error: Color::Red,
+            warning: Color::Rgb(175, 115, 0),
+            muted: Color::DarkGray,
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Self {
+            name: "high-contrast",
+            text: Color::White,
+            accent: Color::LightCyan,
+            header: Color::White,
+            title: Color::LightYellow,
+            selection_fg: Color::Black,
+            selection_bg: Color::White,
+            inactive_selection_bg: Color::Gray,
+            active: Color::LightGreen,
+            activating: Color::LightYellow,
+            failed: Color::LightRed,
+            border: Color::White,
+            highlight_border: Color::LightYellow,
+            error: Color::LightRed,
+            warning: Color::LightYellow,
+            muted: Color::White,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" => Some(Self::high_contrast()),
+            _ => None,
+        }
+    }
+
+    /// Builds the named palette with the user-defined colors applied on top of it.
+    pub fn with_overrides(name: &str, overrides: &ColorOverrides) -> Self {
+        let mut theme = Self::from_name(name).unwrap_or_else(Self::dark);
+        overrides.apply(&mut theme);
+        theme
+    }
+
+    /// Name of the built-in palette that follows this one.
+    pub fn next_name(&self) -> &'static str {
+        let index = THEME_NAMES
+            .iter()
+            .position(|name| *name == self.name)
+            .unwrap_or(0);
+        THEME_NAMES[(index + 1) % THEME_NAMES.len()]
+    }
+}
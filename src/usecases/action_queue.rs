@@ -0,0 +1,110 @@
+use crate::domain::action_plan::UnitAction;
+use crate::domain::service_repository::ServiceRepository;
+use crate::errors::ServiceError;
+use std::sync::mpsc::{self, Sender};
+use std::thread;
+use std::time::Duration;
+
+/// An action of the queue that returned: the job the manager queued for it, if any, or why
+/// it failed.
+pub struct FinishedAction {
+    pub action: UnitAction,
+    pub unit: String,
+    pub result: Result<Option<String>, ServiceError>,
+}
+
+/// Starts, stops, restarts and reloads run one after the other on a thread of their own, so
+/// that keys pressed meanwhile are not waiting for the manager. A unit is pending from the
+/// moment an action on it is queued until its result comes back.
+pub struct ActionQueue {
+    sender: Sender<(UnitAction, String)>,
+    finished: Sender<FinishedAction>,
+    /// Pause after each action, letting the unit settle before the list is fetched again.
+    settle: Duration,
+    /// Units with an action queued or running, once per action, oldest first.
+    pending: Vec<(String, UnitAction)>,
+}
+
+impl ActionQueue {
+    /// Runs the actions on `repository`, reporting each one to `finished`.
+    pub fn start(repository: Box<dyn ServiceRepository>, finished: Sender<FinishedAction>, settle: Duration) -> Self {
+        Self {
+            sender: spawn_worker(repository, finished.clone(), settle),
+            finished,
+            settle,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Runs the next actions on another repository, e.g. after switching hosts. The actions
+    /// already queued still run on the previous one and stay pending until they return.
+    pub fn restart(&mut self, repository: Box<dyn ServiceRepository>) {
+        self.sender = spawn_worker(repository, self.finished.clone(), self.settle);
+    }
+
+    pub fn push(&mut self, action: UnitAction, unit: &str) -> Result<(), ServiceError> {
+        self.sender
+            .send((action, unit.to_string()))
+            .map_err(|_| ServiceError::Unavailable("The action queue stopped".to_string()))?;
+        self.pending.push((unit.to_string(), action));
+        Ok(())
+    }
+
+    /// Forgets an action once it returned.
+    pub fn finished(&mut self, finished: &FinishedAction) {
+        let position = self
+            .pending
+            .iter()
+            .position(|(unit, action)| *unit == finished.unit && *action == finished.action);
+        if let Some(position) = position {
+            self.pending.remove(position);
+        }
+    }
+
+    /// The oldest action still to return on each unit.
+    pub fn pending(&self) -> Vec<(String, UnitAction)> {
+        let mut pending: Vec<(String, UnitAction)> = Vec::new();
+        for (unit, action) in &self.pending {
+            if !pending.iter().any(|(shown, _)| shown == unit) {
+                pending.push((unit.clone(), *action));
+            }
+        }
+        pending
+    }
+
+    pub fn is_idle(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+/// The thread running the actions sent to the returned sender, until it is dropped.
+fn spawn_worker(repository: Box<dyn ServiceRepository>, finished: Sender<FinishedAction>, settle: Duration) -> Sender<(UnitAction, String)> {
+    let (sender, receiver) = mpsc::channel::<(UnitAction, String)>();
+    thread::spawn(move || {
+        for (action, unit) in receiver {
+            let result = run(repository.as_ref(), action, &unit);
+            if result.is_ok() {
+                thread::sleep(settle);
+            }
+            if finished.send(FinishedAction { action, unit, result }).is_err() {
+                break;
+            }
+        }
+    });
+    sender
+}
+
+fn run(repository: &dyn ServiceRepository, action: UnitAction, unit: &str) -> Result<Option<String>, ServiceError> {
+    match action {
+        UnitAction::Start => repository.start_service(unit),
+        UnitAction::Stop => repository.stop_service(unit),
+        UnitAction::Restart => repository.restart_service(unit),
+        UnitAction::Reload => repository.reload_service(unit),
+        UnitAction::ReloadOrRestart => repository.reload_or_restart_service(unit),
+        // Refused by `ServicesManager::queue_action`, enables and disables being recorded
+        // there to be undone.
+        UnitAction::Enable | UnitAction::Disable | UnitAction::Freeze | UnitAction::Thaw => Err(
+            ServiceError::Unsupported("Only starts, stops, restarts and reloads are queued".to_string()),
+        ),
+    }
+}
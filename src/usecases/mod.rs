@@ -1 +1,6 @@
+pub mod action_queue;
+pub mod result_cache;
 pub mod services_manager;
+pub mod timings;
+pub mod uptime_tracker;
+pub mod usage_sampler;
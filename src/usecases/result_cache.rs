@@ -0,0 +1,45 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use crate::errors::ServiceError;
+
+/// Last result fetched for each unit, reused while the unit's generation stays the same.
+/// Only one generation is kept per unit, so going back to an older one fetches again.
+pub struct ResultCache<T> {
+    entries: RefCell<HashMap<String, (String, T)>>,
+}
+
+impl<T: Clone> ResultCache<T> {
+    pub fn new() -> Self {
+        Self {
+            entries: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// The cached result for `unit` at `generation`, fetching and storing it on a miss.
+    /// Without a generation nothing is cached.
+    pub fn get_or_fetch(
+        &self,
+        unit: &str,
+        generation: Option<String>,
+        fetch: impl FnOnce() -> Result<T, ServiceError>,
+    ) -> Result<T, ServiceError> {
+        let Some(generation) = generation else {
+            return fetch();
+        };
+        if let Some((cached_generation, value)) = self.entries.borrow().get(unit)
+            && *cached_generation == generation
+        {
+            return Ok(value.clone());
+        }
+
+        let value = fetch()?;
+        self.entries
+            .borrow_mut()
+            .insert(unit.to_string(), (generation, value.clone()));
+        Ok(value)
+    }
+
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear();
+    }
+}
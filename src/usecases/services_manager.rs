@@ -1,77 +1,1222 @@
-use crate::domain::service::Service;
+use crate::domain::action_journal::{ActionJournal, UndoableAction};
+use crate::domain::action_plan::{ActionPlan, UnitAction};
+use crate::domain::action_policy::{ActionPolicy, PolicyAction};
+use crate::domain::boot_timing::{ChainLink, UnitActivation};
+use crate::domain::security_audit::{SecurityAudit, SecurityScore};
+use crate::domain::slice::Slice;
+use crate::domain::crash_loop::RestartSettings;
+use crate::domain::hook::{Hook, HookEvent};
+use crate::domain::humanize::now_usec;
+use crate::domain::enablement_change::EnablementChange;
+use crate::domain::failed_unit::FailedUnit;
+use crate::domain::job::JobResult;
+use crate::domain::journal_boot::ListedBoot;
+use crate::domain::journal_entry::{JournalEntry, LogFilter};
+use crate::domain::kill::KillTarget;
+use crate::domain::latency::ListTimings;
+use crate::domain::log_rate::LogRate;
+use crate::domain::log_volume::LogVolume;
+use crate::domain::power_action::PowerAction;
+use crate::domain::process::UnitProcess;
+use crate::domain::resource_limits::ResourceLimits;
+use crate::domain::resource_usage::ResourceUsage;
+use crate::domain::runtime_stats::RuntimeStats;
+use crate::domain::system_summary::SystemSummary;
+use crate::domain::scheduled_action::ScheduledAction;
+use crate::domain::service::{Service, SOURCE_SEPARATOR};
 use crate::domain::service_repository::ServiceRepository;
+use crate::domain::service_state::ServiceState;
+use crate::domain::transient_unit::TransientUnit;
+use crate::domain::unit_capabilities::UnitCapabilities;
+use crate::domain::unit_clone::UnitClone;
+use crate::domain::unit_conditions::UnitConditions;
+use crate::domain::unit_documentation::DocumentationLink;
+use crate::domain::unit_history::UnitHistory;
+use crate::domain::unit_dependencies::{impact_scores, start_order, UnitDependencies};
+use crate::domain::unit_environment::{environment_drop_in, UnitEnvironment};
+use crate::domain::unit_file::{description_drop_in, UnitFile, UnitFileMatch};
+use crate::domain::unit_alias::UnitLinks;
+use crate::domain::unit_file_drift::{setting_names, settings_on_disk, with_loaded, SettingDrift};
+use crate::domain::unit_path::{PathRelation, UnitPathReference};
+use crate::domain::unit_property::{SocketProperty, TimerProperty, UnitProperty};
+use crate::domain::unit_template::NewUnit;
+use crate::domain::unit_transition::UnitTransition;
+use crate::domain::unit_verification::VerifyFinding;
+use crate::domain::unit_triggers::UnitTriggers;
+use crate::config::MaintenanceConfig;
+use crate::errors::ServiceError;
+use crate::infrastructure::call_limits::CallLimits;
+use crate::infrastructure::hook_runner;
 use crate::infrastructure::systemd_service_adapter::ConnectionType;
-use std::error::Error;
+use crate::infrastructure::trace_log::TraceLog;
+use crate::usecases::action_queue::{ActionQueue, FinishedAction};
+use crate::usecases::result_cache::ResultCache;
+use crate::usecases::timings::{Stage, StageTimings, Timings};
+use chrono::Local;
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+use std::process::Command;
+use std::sync::mpsc::Sender;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 const SLEEP_DURATION: u64 = 200;
+/// Number of journal lines shown by the log view, like `journalctl -e`.
+pub const LOG_LINES: u32 = 1000;
+/// Journal lines kept for each unit of the failed units view.
+const FAILED_LOG_LINES: u32 = 5;
 
 pub struct ServicesManager {
     repository: Box<dyn ServiceRepository>,
+    unit_files: ResultCache<String>,
+    /// CanStart, CanStop, CanReload and CanIsolate of the units asked about, which only change
+    /// with their unit files.
+    capabilities: RefCell<HashMap<String, UnitCapabilities>>,
+    job_sender: Option<Sender<JobResult>>,
+    /// Starts, stops, restarts and reloads of single units, see `queue_action`.
+    action_queue: Option<ActionQueue>,
+    /// Units whose state changes are sent to `transition_sender`, marked from the list.
+    watched: BTreeSet<String>,
+    transition_sender: Option<Sender<UnitTransition>>,
+    /// Enables and disables of the session, one per unit, to revert them on exit.
+    enablement_changes: RefCell<Vec<EnablementChange>>,
+    /// Enables, disables and drop-ins of the session, undone newest first.
+    journal: RefCell<ActionJournal>,
+    /// Refuses every call changing units or their files, see `set_read_only`.
+    read_only: bool,
+    /// Actions allowed or denied per unit by the configuration.
+    policy: ActionPolicy,
+    /// Critical units, disrupted outside of the windows only once confirmed.
+    maintenance: MaintenanceConfig,
+    /// Critical units whose names were typed to confirm the next disruptive action on them.
+    confirmed: RefCell<BTreeSet<String>>,
+    /// Commands and webhooks run on actions and state changes.
+    hooks: Vec<Hook>,
+    /// Timeout and cancellation of the slow calls, kept for the repositories swapped in.
+    call_limits: CallLimits,
+    /// Durations of the listing, properties and journal calls, and of the steps the TUI reports.
+    timings: RefCell<Timings>,
 }
 
 impl ServicesManager {
     pub fn new(repository: Box<dyn ServiceRepository>) -> Self {
-        Self { repository }
+        Self {
+            repository,
+            unit_files: ResultCache::new(),
+            capabilities: RefCell::new(HashMap::new()),
+            job_sender: None,
+            action_queue: None,
+            watched: BTreeSet::new(),
+            transition_sender: None,
+            enablement_changes: RefCell::new(Vec::new()),
+            journal: RefCell::new(ActionJournal::default()),
+            read_only: false,
+            policy: ActionPolicy::default(),
+            maintenance: MaintenanceConfig::default(),
+            confirmed: RefCell::new(BTreeSet::new()),
+            hooks: Vec::new(),
+            call_limits: CallLimits::default(),
+            timings: RefCell::new(Timings::default()),
+        }
     }
 
-    pub fn start_service(&self, service: &Service) -> Result<(), Box<dyn Error>> {
-        self.repository.start_service(service.name())?;
-        thread::sleep(Duration::from_millis(SLEEP_DURATION));
+    /// Blocks the actions changing units or their files, e.g. to explore a production host
+    /// safely. Listing, properties and logs still work.
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    pub fn set_policy(&mut self, policy: ActionPolicy) {
+        self.policy = policy;
+    }
+
+    pub fn set_maintenance(&mut self, maintenance: MaintenanceConfig) {
+        self.maintenance = maintenance;
+    }
+
+    /// The critical units among `units` that the action would disrupt outside of the
+    /// maintenance windows, whose names must be typed before it runs.
+    pub fn needing_confirmation(&self, action: PolicyAction, units: &[&str]) -> Vec<String> {
+        if !action.is_disruptive() || self.maintenance.in_window(Local::now().naive_local()) {
+            return Vec::new();
+        }
+        units
+            .iter()
+            .filter(|unit| self.maintenance.is_critical(unit.rsplit(SOURCE_SEPARATOR).next().unwrap_or(unit)))
+            .map(|unit| unit.to_string())
+            .collect()
+    }
+
+    /// Allows the next disruptive action on each of these critical units, their names having
+    /// been typed.
+    pub fn confirm_critical(&self, units: &[String]) {
+        self.confirmed.borrow_mut().extend(units.iter().cloned());
+    }
+
+    pub fn set_hooks(&mut self, hooks: Vec<Hook>) {
+        self.hooks = hooks;
+    }
+
+    /// Runs the hooks of the event in the background.
+    fn fire(&self, event: HookEvent) {
+        for hook in self.hooks.iter().filter(|hook| hook.matches(&event)) {
+            hook_runner::run(hook, &event);
+        }
+    }
+
+    /// Runs the hooks of an action that succeeded, matched on the unit named without its source
+    /// like the policy.
+    fn performed(&self, action: PolicyAction, unit: &str) {
+        let unit = unit.rsplit(SOURCE_SEPARATOR).next().unwrap_or(unit);
+        self.fire(HookEvent::performed(action, unit));
+    }
+
+    /// Runs the hooks of the state a watched unit entered.
+    pub fn unit_changed(&self, transition: &UnitTransition) {
+        self.fire(HookEvent::entered(transition));
+    }
+
+    /// Runs the "failed" hooks of a unit found failed by a periodic check.
+    pub fn unit_failed(&self, unit: &str) {
+        self.fire(HookEvent::failed(unit));
+    }
+
+    pub fn set_call_limits(&mut self, limits: CallLimits) {
+        self.repository.set_call_limits(limits.clone());
+        self.call_limits = limits;
+    }
+
+    /// Shared with the thread reading the keys, which cancels the call in progress on Esc.
+    pub fn call_limits(&self) -> CallLimits {
+        self.call_limits.clone()
+    }
+
+    /// Fails when the policy refuses the action on the unit, named without its source.
+    fn allowed_by_policy(&self, action: PolicyAction, unit: &str) -> Result<(), ServiceError> {
+        let unit = unit.rsplit(SOURCE_SEPARATOR).next().unwrap_or(unit);
+        self.policy.check(action, unit).map_err(ServiceError::PermissionDenied)
+    }
+
+    /// Fails when the policy refuses the action on the unit, or when it would disrupt a
+    /// critical unit outside of the maintenance windows without its name having been typed.
+    /// A confirmation is used up by the action it allowed.
+    fn permitted(&self, action: PolicyAction, unit: &str) -> Result<(), ServiceError> {
+        self.allowed_by_policy(action, unit)?;
+        if self.needing_confirmation(action, &[unit]).is_empty() || self.confirmed.borrow_mut().remove(unit) {
+            return Ok(());
+        }
+        Err(ServiceError::PermissionDenied(format!(
+            "{} is critical: outside of the maintenance windows, confirm the {} by typing its name",
+            unit,
+            action.label()
+        )))
+    }
+
+    /// Fails in read-only mode, before the action reaches the repository.
+    fn writable(&self, action: &str) -> Result<(), ServiceError> {
+        if self.read_only {
+            return Err(ServiceError::PermissionDenied(format!("Read-only mode: cannot {}", action)));
+        }
         Ok(())
     }
 
-    pub fn stop_service(&self, service: &Service) -> Result<(), Box<dyn Error>> {
-        self.repository.stop_service(service.name())?;
+    /// Starts the service, returning the queued job when there is one to track.
+    pub fn start_service(&self, service: &Service) -> Result<Option<String>, ServiceError> {
+        self.writable("start units")?;
+        self.permitted(PolicyAction::Start, service.name())?;
+        let job = self.repository.start_service(service.name())?;
+        self.performed(PolicyAction::Start, service.name());
         thread::sleep(Duration::from_millis(SLEEP_DURATION));
-        Ok(())
+        Ok(job)
+    }
+
+    /// Starts an instance of a template by name, e.g. "getty@tty3.service", not listed until then.
+    pub fn start_instance(&self, name: &str) -> Result<Option<String>, ServiceError> {
+        self.writable("start units")?;
+        self.permitted(PolicyAction::Start, name)?;
+        let job = self.repository.start_service(name)?;
+        self.performed(PolicyAction::Start, name);
+        thread::sleep(Duration::from_millis(SLEEP_DURATION));
+        Ok(job)
     }
 
-    pub fn restart_service(&self, service: &Service) -> Result<(), Box<dyn Error>> {
-        self.repository.restart_service(service.name())?;
+    pub fn stop_service(&self, service: &Service) -> Result<Option<String>, ServiceError> {
+        self.writable("stop units")?;
+        self.permitted(PolicyAction::Stop, service.name())?;
+        let job = self.repository.stop_service(service.name())?;
+        self.performed(PolicyAction::Stop, service.name());
         thread::sleep(Duration::from_millis(SLEEP_DURATION));
+        Ok(job)
+    }
+
+    pub fn restart_service(&self, service: &Service) -> Result<Option<String>, ServiceError> {
+        self.writable("restart units")?;
+        self.permitted(PolicyAction::Restart, service.name())?;
+        let job = self.repository.restart_service(service.name())?;
+        self.performed(PolicyAction::Restart, service.name());
+        thread::sleep(Duration::from_millis(SLEEP_DURATION));
+        Ok(job)
+    }
+
+    pub fn reload_service(&self, service: &Service) -> Result<Option<String>, ServiceError> {
+        self.writable("reload units")?;
+        self.permitted(PolicyAction::Reload, service.name())?;
+        let job = self.repository.reload_service(service.name())?;
+        self.performed(PolicyAction::Reload, service.name());
+        thread::sleep(Duration::from_millis(SLEEP_DURATION));
+        Ok(job)
+    }
+
+    pub fn reload_or_restart_service(&self, service: &Service) -> Result<Option<String>, ServiceError> {
+        self.writable("reload or restart units")?;
+        self.permitted(PolicyAction::Restart, service.name())?;
+        let job = self.repository.reload_or_restart_service(service.name())?;
+        self.performed(PolicyAction::Restart, service.name());
+        thread::sleep(Duration::from_millis(SLEEP_DURATION));
+        Ok(job)
+    }
+
+    /// What the manager accepts for the unit, remembered until the unit files change.
+    pub fn capabilities(&self, service: &Service) -> Result<UnitCapabilities, ServiceError> {
+        let name = service.name();
+        if let Some(capabilities) = self.capabilities.borrow().get(name) {
+            return Ok(*capabilities);
+        }
+        let capabilities = self.repository.get_unit_capabilities(name)?;
+        self.capabilities.borrow_mut().insert(name.to_string(), capabilities);
+        Ok(capabilities)
+    }
+
+    /// Reports the state changes of the watched units to `sender`.
+    pub fn watch_transitions(&mut self, sender: Sender<UnitTransition>) {
+        self.transition_sender = Some(sender);
+    }
+
+    pub fn is_watched(&self, name: &str) -> bool {
+        self.watched.contains(name)
+    }
+
+    pub fn watched_units(&self) -> Vec<String> {
+        self.watched.iter().cloned().collect()
+    }
+
+    /// Starts or stops watching the unit, returning whether it is now watched.
+    pub fn toggle_watch(&mut self, service: &Service) -> Result<bool, ServiceError> {
+        let name = service.name().to_string();
+        let watched = if self.watched.remove(&name) {
+            false
+        } else {
+            self.watched.insert(name.clone())
+        };
+        if let Err(e) = self.rewatch() {
+            // Left as it was, rather than claiming a watch that does not run.
+            if watched {
+                self.watched.remove(&name);
+            } else {
+                self.watched.insert(name);
+            }
+            return Err(e);
+        }
+        Ok(watched)
+    }
+
+    /// Watches exactly these units, in place of the ones watched so far.
+    pub fn watch_units(&mut self, units: Vec<String>) -> Result<(), ServiceError> {
+        self.watched = units.into_iter().collect();
+        self.rewatch()
+    }
+
+    fn rewatch(&mut self) -> Result<(), ServiceError> {
+        match self.transition_sender.clone() {
+            Some(sender) => self.repository.watch_units(&self.watched_units(), sender),
+            None => Ok(()),
+        }
+    }
+
+    /// Reports finished jobs to `sender`, following the repository across host switches.
+    pub fn watch_jobs(&mut self, sender: Sender<JobResult>) -> Result<(), ServiceError> {
+        self.job_sender = Some(sender.clone());
+        self.repository.watch_jobs(sender)
+    }
+
+    /// Runs the queued actions on a thread of their own, each reported to `sender` once it
+    /// returned.
+    pub fn watch_actions(&mut self, sender: Sender<FinishedAction>) -> Result<(), ServiceError> {
+        let worker = self.repository.worker()?;
+        self.action_queue = Some(ActionQueue::start(worker, sender, Duration::from_millis(SLEEP_DURATION)));
         Ok(())
     }
 
-    pub fn enable_service(&self, service: &Service) -> Result<(), Box<dyn Error>> {
-        self.repository.enable_service(service.name())?;
+    /// Queues a start, stop, restart or reload of the unit behind the ones already asked,
+    /// returning at once; the read-only mode and the policy are checked before queuing.
+    pub fn queue_action(&mut self, action: UnitAction, service: &Service) -> Result<(), ServiceError> {
+        let Some((what, policy_action)) = queued_policy(action) else {
+            return Err(ServiceError::Unsupported("Only starts, stops, restarts and reloads are queued".to_string()));
+        };
+        self.writable(what)?;
+        self.permitted(policy_action, service.name())?;
+        match &mut self.action_queue {
+            Some(queue) => queue.push(action, service.name()),
+            None => Err(ServiceError::Unavailable("The action queue is not running".to_string())),
+        }
+    }
+
+    /// Takes a returned action off the pending ones, running the hooks of the action when it
+    /// succeeded.
+    pub fn action_finished(&mut self, finished: &FinishedAction) {
+        if let Some(queue) = &mut self.action_queue {
+            queue.finished(finished);
+        }
+        if finished.result.is_ok()
+            && let Some((_, policy_action)) = queued_policy(finished.action)
+        {
+            self.performed(policy_action, &finished.unit);
+        }
+    }
+
+    /// The oldest action still to return on each unit with one queued or running.
+    pub fn pending_actions(&self) -> HashMap<String, UnitAction> {
+        self.action_queue.as_ref().map(ActionQueue::pending).unwrap_or_default().into_iter().collect()
+    }
+
+    pub fn has_pending_actions(&self) -> bool {
+        self.action_queue.as_ref().is_some_and(|queue| !queue.is_idle())
+    }
+
+    /// Points the queue at the current repository, once it was replaced or reconnected.
+    fn restart_action_queue(&mut self) {
+        if let Some(queue) = &mut self.action_queue
+            && let Ok(worker) = self.repository.worker()
+        {
+            queue.restart(worker);
+        }
+    }
+
+    pub fn enable_service(&self, service: &Service) -> Result<(), ServiceError> {
+        self.writable("enable units")?;
+        self.permitted(PolicyAction::Enable, service.name())?;
+        let changes = self.repository.enable_service(service.name())?;
+        self.performed(PolicyAction::Enable, service.name());
+        self.record_enablement_change(service.name(), service.state().file(), true);
+        self.journal.borrow_mut().push(UndoableAction::Enabled {
+            unit: service.name().to_string(),
+            changes,
+        });
         thread::sleep(Duration::from_millis(SLEEP_DURATION));
         self.repository.reload_daemon()?;
         Ok(())
     }
 
-    pub fn disable_service(&self, service: &Service) -> Result<(), Box<dyn Error>> {
-        self.repository.disable_service(service.name())?;
+    pub fn disable_service(&self, service: &Service) -> Result<(), ServiceError> {
+        self.writable("disable units")?;
+        self.permitted(PolicyAction::Disable, service.name())?;
+        let changes = self.repository.disable_service(service.name())?;
+        self.performed(PolicyAction::Disable, service.name());
+        self.record_enablement_change(service.name(), service.state().file(), false);
+        self.journal.borrow_mut().push(UndoableAction::Disabled {
+            unit: service.name().to_string(),
+            changes,
+        });
         thread::sleep(Duration::from_millis(SLEEP_DURATION));
         self.repository.reload_daemon()?;
         Ok(())
     }
 
-    pub fn list_services(&self) -> Result<Vec<Service>, Box<dyn Error>> {
-        let mut services = self.repository.list_services()?;
+    /// Keeps the state the unit had before its first change of the session, `state` being its
+    /// unit file state before this one.
+    fn record_enablement_change(&self, unit: &str, state: &str, enabled: bool) {
+        let mut changes = self.enablement_changes.borrow_mut();
+        let previous = match changes.iter().position(|change| change.unit() == unit) {
+            Some(index) => changes.remove(index).previous().to_string(),
+            None => state.to_string(),
+        };
+        changes.push(EnablementChange::new(unit.to_string(), previous, enabled));
+    }
+
+    /// The action `undo` would undo.
+    pub fn last_undoable(&self) -> Option<UndoableAction> {
+        self.journal.borrow().last().cloned()
+    }
+
+    /// Undoes the last enable, disable or drop-in of the session, then reloads the manager.
+    /// Returns what was undone, the action being kept when undoing it fails.
+    pub fn undo(&self) -> Result<String, ServiceError> {
+        self.writable("undo changes")?;
+        let Some(action) = self.last_undoable() else {
+            return Err("Nothing to undo".into());
+        };
+        let undoing = match &action {
+            UndoableAction::Enabled { .. } => PolicyAction::Disable,
+            UndoableAction::Disabled { .. } => PolicyAction::Enable,
+            UndoableAction::DropIn { .. } => PolicyAction::Edit,
+        };
+        self.permitted(undoing, action.unit())?;
+        match &action {
+            UndoableAction::Enabled { unit, changes } if changes.is_empty() => {
+                self.repository.disable_service(unit)?;
+            }
+            UndoableAction::Disabled { unit, changes } if changes.is_empty() => {
+                self.repository.enable_service(unit)?;
+            }
+            UndoableAction::Enabled { unit, changes } | UndoableAction::Disabled { unit, changes } => {
+                self.repository.revert_unit_file_changes(unit, changes)?;
+            }
+            UndoableAction::DropIn { unit, path, previous: None } => self.repository.remove_drop_in(unit, path)?,
+            UndoableAction::DropIn { unit, path, previous: Some(content) } => {
+                let file_name = path.rsplit('/').next().unwrap_or(path);
+                self.repository.write_drop_in(unit, file_name, content)?;
+            }
+        }
+        self.journal.borrow_mut().pop();
+        match &action {
+            UndoableAction::Enabled { unit, .. } => self.record_enablement_change(unit, "", false),
+            UndoableAction::Disabled { unit, .. } => self.record_enablement_change(unit, "", true),
+            UndoableAction::DropIn { .. } => {}
+        }
+        self.repository.reload_daemon()?;
+        self.clear_cache();
+        Ok(action.description())
+    }
+
+    /// Enables and disables of the session that changed the state of their unit.
+    pub fn enablement_changes(&self) -> Vec<EnablementChange> {
+        self.enablement_changes
+            .borrow()
+            .iter()
+            .filter(|change| !change.is_noop())
+            .cloned()
+            .collect()
+    }
+
+    /// Puts the units changed during the session back in their previous state, returning the
+    /// reverted units. Changes that cannot be reverted are kept, and reported in the error.
+    pub fn revert_enablement_changes(&self) -> Result<Vec<String>, ServiceError> {
+        self.writable("revert changes")?;
+        let mut reverted = Vec::new();
+        let mut errors = Vec::new();
+        for change in self.enablement_changes() {
+            let result = match change.revert_enables() {
+                Some(true) => self
+                    .permitted(PolicyAction::Enable, change.unit())
+                    .and_then(|_| self.repository.enable_service(change.unit())),
+                Some(false) => self
+                    .permitted(PolicyAction::Disable, change.unit())
+                    .and_then(|_| self.repository.disable_service(change.unit())),
+                None => Err(format!("{} was {}, which enabling or disabling cannot restore", change.unit(), change.previous()).into()),
+            };
+            match result {
+                Ok(_) => {
+                    self.journal.borrow_mut().forget_enablement(change.unit());
+                    reverted.push(change.unit().to_string())
+                }
+                Err(e) => errors.push(format!("{}: {}", change.unit(), e)),
+            }
+        }
+        self.enablement_changes
+            .borrow_mut()
+            .retain(|change| !change.is_noop() && !reverted.iter().any(|unit| unit == change.unit()));
+        if !reverted.is_empty() {
+            self.repository.reload_daemon()?;
+        }
+        if errors.is_empty() {
+            Ok(reverted)
+        } else {
+            Err(errors.join("; ").into())
+        }
+    }
+
+    pub fn freeze_service(&self, service: &Service) -> Result<(), ServiceError> {
+        self.writable("freeze units")?;
+        self.permitted(PolicyAction::Freeze, service.name())?;
+        self.repository.freeze_service(service.name())?;
+        self.performed(PolicyAction::Freeze, service.name());
+        thread::sleep(Duration::from_millis(SLEEP_DURATION));
+        Ok(())
+    }
+
+    pub fn thaw_service(&self, service: &Service) -> Result<(), ServiceError> {
+        self.writable("thaw units")?;
+        self.permitted(PolicyAction::Thaw, service.name())?;
+        self.repository.thaw_service(service.name())?;
+        self.performed(PolicyAction::Thaw, service.name());
+        thread::sleep(Duration::from_millis(SLEEP_DURATION));
+        Ok(())
+    }
+
+    pub fn reset_failed_unit(&self, service: &Service) -> Result<(), ServiceError> {
+        self.writable("reset failed units")?;
+        self.permitted(PolicyAction::ResetFailed, service.name())?;
+        self.repository.reset_failed_unit(service.name())?;
+        self.performed(PolicyAction::ResetFailed, service.name());
+        Ok(())
+    }
+
+    /// Resets every failed unit the policy allows, at once when it allows them all. Returns the
+    /// units left failed because the policy refused them.
+    pub fn reset_failed(&self) -> Result<Vec<String>, ServiceError> {
+        self.writable("reset failed units")?;
+        let (allowed, denied): (Vec<Service>, Vec<Service>) = self
+            .list_services()?
+            .into_iter()
+            .filter(|service| service.state().active() == "failed")
+            .partition(|service| self.permitted(PolicyAction::ResetFailed, service.name()).is_ok());
+        if denied.is_empty() {
+            self.repository.reset_failed()?;
+        } else {
+            for service in &allowed {
+                self.repository.reset_failed_unit(service.name())?;
+            }
+        }
+        for service in &allowed {
+            self.performed(PolicyAction::ResetFailed, service.name());
+        }
+        Ok(denied.iter().map(|service| service.name().to_string()).collect())
+    }
+
+    pub fn list_targets(&self) -> Result<Vec<Service>, ServiceError> {
+        self.repository.list_targets()
+    }
+
+    pub fn default_target(&self) -> Result<String, ServiceError> {
+        self.repository.get_default_target()
+    }
+
+    pub fn set_default_target(&self, target: &Service) -> Result<(), ServiceError> {
+        self.writable("change the default target")?;
+        // Only the next boot changes, nothing is stopped now.
+        self.allowed_by_policy(PolicyAction::Isolate, target.name())?;
+        self.repository.set_default_target(target.name())
+    }
+
+    pub fn isolate_target(&self, target: &Service) -> Result<Option<String>, ServiceError> {
+        self.writable("isolate targets")?;
+        self.permitted(PolicyAction::Isolate, target.name())?;
+        let job = self.repository.isolate_target(target.name())?;
+        self.performed(PolicyAction::Isolate, target.name());
+        Ok(job)
+    }
+
+    /// Reboots or powers off the machine, which the policy and the maintenance windows treat as
+    /// isolating reboot.target or poweroff.target.
+    pub fn power(&self, action: PowerAction) -> Result<(), ServiceError> {
+        self.writable("power off or reboot")?;
+        self.permitted(PolicyAction::Isolate, action.target())?;
+        // The hooks run before the machine goes down, not after.
+        self.performed(PolicyAction::Isolate, action.target());
+        self.repository.power(action)
+    }
+
+    /// Failed units with their properties and last journal lines, for the failed units view.
+    pub fn failed_units(&self) -> Result<Vec<FailedUnit>, ServiceError> {
+        let failed: Vec<Service> = self
+            .list_services()?
+            .into_iter()
+            .filter(|service| service.state().active() == "failed")
+            .collect();
+        Ok(self
+            .with_properties(&failed)
+            .into_iter()
+            .map(|service| {
+                // The view still helps without the journal, e.g. without permission to read it.
+                let log = self.get_log_lines(&service, FAILED_LOG_LINES).unwrap_or_default();
+                FailedUnit::new(service, log.lines().map(str::to_string).collect())
+            })
+            .collect())
+    }
+
+    /// Sends `signal` to the main or all processes of the service, like `systemctl kill`.
+    pub fn kill_service(&self, service: &Service, target: KillTarget, signal: i32) -> Result<(), ServiceError> {
+        self.writable("kill units")?;
+        self.permitted(PolicyAction::Kill, service.name())?;
+        self.repository.kill_service(service.name(), target, signal)?;
+        self.performed(PolicyAction::Kill, service.name());
+        thread::sleep(Duration::from_millis(SLEEP_DURATION));
+        Ok(())
+    }
+
+    /// Appends every duration recorded from now on to the trace log.
+    pub fn set_trace(&mut self, trace: TraceLog) {
+        self.timings.get_mut().set_trace(trace);
+    }
+
+    pub fn record_timing(&self, stage: Stage, duration: Duration) {
+        self.timings.borrow_mut().record(stage, duration);
+    }
+
+    pub fn timings(&self) -> Vec<StageTimings> {
+        self.timings.borrow().summary()
+    }
+
+    /// Runs a call and records how long it took.
+    fn timed<T>(&self, stage: Stage, call: impl FnOnce() -> T) -> T {
+        let started = Instant::now();
+        let result = call();
+        self.record_timing(stage, started.elapsed());
+        result
+    }
+
+    pub fn list_services(&self) -> Result<Vec<Service>, ServiceError> {
+        let mut services = self.timed(Stage::ListUnits, || self.repository.list_services())?;
         services.sort_by_key(|a| a.name().to_lowercase());
         Ok(services)
     }
 
-    #[allow(dead_code)]
-    pub fn update_properties(&self, service: &mut Service) -> Result<(), Box<dyn Error>> {
-        let props = self.repository.get_service_property(service.name())?;
+    pub fn time_listing(&self) -> Result<ListTimings, ServiceError> {
+        self.repository.time_listing()
+    }
+
+    pub fn update_properties(&self, service: &mut Service) -> Result<(), ServiceError> {
+        // Not cached, a single GetAll costs less than telling whether any property changed.
+        let props = self.timed(Stage::Properties, || self.repository.get_unit_property(service.name()))?;
         service.update_properties(props);
         Ok(())
     }
 
-    pub fn get_log(&self, service: &Service) -> Result<String, Box<dyn Error>> {
-        self.repository.get_service_log(service.name())
+    /// Copies of the services with their properties loaded, for exports. Services whose
+    /// properties cannot be read, e.g. on backends without them, are exported without.
+    pub fn with_properties(&self, services: &[Service]) -> Vec<Service> {
+        services
+            .iter()
+            .map(|service| {
+                let mut service = service.clone();
+                let _ = self.update_properties(&mut service);
+                service
+            })
+            .collect()
+    }
+
+    /// Writes the unit files of the template and reloads the manager, then enables and starts its
+    /// main unit when asked to. Returns the paths of the unit files.
+    pub fn create_unit(&self, unit: &NewUnit, enable_and_start: bool) -> Result<Vec<String>, ServiceError> {
+        self.writable("create units")?;
+        unit.validate()?;
+        let files = unit.render();
+        for (name, _) in &files {
+            self.permitted(PolicyAction::Edit, name)?;
+        }
+        let mut paths = Vec::new();
+        for (name, content) in files {
+            paths.push(self.repository.create_unit(&name, &content)?);
+        }
+        self.repository.reload_daemon()?;
+        if enable_and_start {
+            self.enable_and_start(&unit.start_unit())?;
+        }
+        Ok(paths)
+    }
+
+    /// Makes the clone of the unit: a copy of its unit file, without the drop-ins, then reloads
+    /// the manager. Instances of templates need no file. Returns the path written, if any.
+    pub fn clone_unit(&self, service: &Service, clone: &UnitClone) -> Result<Option<String>, ServiceError> {
+        self.writable("clone units")?;
+        self.permitted(PolicyAction::Edit, service.name())?;
+        let UnitClone::Copy(name) = clone else {
+            return Ok(None);
+        };
+        self.permitted(PolicyAction::Edit, name)?;
+        let files = self.unit_files(service)?;
+        let Some(file) = files.iter().find(|file| !file.drop_in && !file.path.is_empty()) else {
+            return Err(format!("{} has no unit file to copy", service.name()).into());
+        };
+        let mut content = format!("# Copied from {}\n", file.path);
+        for line in &file.lines {
+            content.push_str(&line.text);
+            content.push('\n');
+        }
+        let path = self.repository.create_unit(name, &content)?;
+        self.repository.reload_daemon()?;
+        Ok(Some(path))
+    }
+
+    /// Command opening the unit file in the editor of the user, see `ServiceRepository`.
+    pub fn edit_unit_command(&self, name: &str) -> Result<Command, ServiceError> {
+        self.writable("edit units")?;
+        self.permitted(PolicyAction::Edit, name)?;
+        self.repository.edit_unit_command(name)
+    }
+
+    /// Command starting a shell in a directory of the host of the unit, see `ServiceRepository`.
+    pub fn shell_command(&self, name: &str, directory: &str) -> Result<Command, ServiceError> {
+        self.repository.shell_command(name, directory)
+    }
+
+    /// Enables and starts a unit made from the list, e.g. a clone once edited.
+    pub fn enable_and_start(&self, name: &str) -> Result<(), ServiceError> {
+        self.writable("enable and start units")?;
+        self.permitted(PolicyAction::Enable, name)?;
+        self.permitted(PolicyAction::Start, name)?;
+        // A unit just made is not listed yet, and so was not enabled before.
+        let state = self.find_service(name).map(|service| service.state().file().to_string());
+        let changes = self.repository.enable_service(name)?;
+        self.performed(PolicyAction::Enable, name);
+        self.record_enablement_change(name, state.as_deref().unwrap_or("disabled"), true);
+        self.journal.borrow_mut().push(UndoableAction::Enabled {
+            unit: name.to_string(),
+            changes,
+        });
+        self.repository.reload_daemon()?;
+        self.repository.start_service(name)?;
+        self.performed(PolicyAction::Start, name);
+        thread::sleep(Duration::from_millis(SLEEP_DURATION));
+        Ok(())
+    }
+
+    pub fn get_environment(&self, service: &Service) -> Result<UnitEnvironment, ServiceError> {
+        self.repository.get_unit_environment(service.name())
+    }
+
+    /// Sets a "KEY=VALUE" variable of the unit in a drop-in of its own, then reloads the manager.
+    /// The unit only sees it once restarted. Returns the path of the drop-in.
+    pub fn set_environment(&self, service: &Service, assignment: &str) -> Result<String, ServiceError> {
+        self.writable("change environments")?;
+        self.permitted(PolicyAction::Edit, service.name())?;
+        let (file_name, content) = environment_drop_in(service.name(), assignment)?;
+        let previous = self.drop_in_content(service, &file_name);
+        let path = self.repository.write_drop_in(service.name(), &file_name, &content)?;
+        self.journal.borrow_mut().push(UndoableAction::DropIn {
+            unit: service.name().to_string(),
+            path: path.clone(),
+            previous,
+        });
+        self.repository.reload_daemon()?;
+        self.clear_cache();
+        Ok(path)
+    }
+
+    /// Replaces the Description= of the unit with a drop-in, then reloads the manager so the list
+    /// shows it. Returns the path of the drop-in.
+    pub fn set_description(&self, service: &Service, description: &str) -> Result<String, ServiceError> {
+        self.writable("change descriptions")?;
+        self.permitted(PolicyAction::Edit, service.name())?;
+        let (file_name, content) = description_drop_in(description)?;
+        let previous = self.drop_in_content(service, &file_name);
+        let path = self.repository.write_drop_in(service.name(), &file_name, &content)?;
+        self.journal.borrow_mut().push(UndoableAction::DropIn {
+            unit: service.name().to_string(),
+            path: path.clone(),
+            previous,
+        });
+        self.repository.reload_daemon()?;
+        self.clear_cache();
+        Ok(path)
+    }
+
+    /// Content of the drop-in of the unit named `file_name`, `None` when it has none.
+    fn drop_in_content(&self, service: &Service, file_name: &str) -> Option<String> {
+        let unit = service.name().rsplit(SOURCE_SEPARATOR).next().unwrap_or(service.name());
+        let suffix = format!("/{}.d/{}", unit, file_name);
+        let files = self.unit_files(service).ok()?;
+        let file = files.iter().find(|file| file.drop_in && file.path.ends_with(&suffix))?;
+        Some(file.lines.iter().map(|line| format!("{}\n", line.text)).collect())
+    }
+
+    pub fn get_resource_limits(&self, service: &Service) -> Result<ResourceLimits, ServiceError> {
+        self.repository.get_resource_limits(service.name())
+    }
+
+    pub fn set_resource_limits(&self, service: &Service, limits: &ResourceLimits, runtime: bool) -> Result<(), ServiceError> {
+        self.writable("change resource limits")?;
+        self.permitted(PolicyAction::Edit, service.name())?;
+        self.repository.set_resource_limits(service.name(), limits, runtime)?;
+        self.clear_cache();
+        Ok(())
+    }
+
+    /// Runs the command as a transient service, returning its name and the job starting it.
+    pub fn run_transient(&self, unit: &TransientUnit) -> Result<(String, Option<String>), ServiceError> {
+        self.writable("run commands")?;
+        unit.validate()?;
+        let name = unit.unit_name();
+        self.permitted(PolicyAction::Start, &name)?;
+        let job = self.repository.start_transient_unit(&name, &unit.argv()?, &unit.limits()?)?;
+        Ok((name, job))
+    }
+
+    pub fn get_log(&self, service: &Service, filter: &LogFilter) -> Result<Vec<JournalEntry>, ServiceError> {
+        self.timed(Stage::Journal, || self.repository.get_journal_entries(service.name(), LOG_LINES, filter))
+    }
+
+    /// The last entries of the services interleaved by time, each tagged with its unit.
+    pub fn get_merged_log(&self, services: &[Service], filter: &LogFilter) -> Result<Vec<JournalEntry>, ServiceError> {
+        let mut entries = Vec::new();
+        for service in services {
+            let unit_entries = self.repository.get_journal_entries(service.name(), LOG_LINES, filter)?;
+            entries.extend(unit_entries.into_iter().map(|entry| entry.tagged(service.name())));
+        }
+        // Stable, the entries of a unit logged within the same microsecond keep their order.
+        entries.sort_by_key(JournalEntry::timestamp);
+        let excess = entries.len().saturating_sub(LOG_LINES as usize);
+        entries.drain(..excess);
+        Ok(entries)
+    }
+
+    pub fn get_journal_boots(&self) -> Result<Vec<ListedBoot>, ServiceError> {
+        self.repository.get_journal_boots()
+    }
+
+    /// The entries logged before the one at `cursor`, a page of the log view long.
+    pub fn get_older_log(&self, name: &str, cursor: &str, filter: &LogFilter) -> Result<Vec<JournalEntry>, ServiceError> {
+        self.repository.get_older_journal_entries(name, cursor, LOG_LINES, filter)
+    }
+
+    /// Every log line of the unit, oldest first.
+    pub fn get_full_log(&self, name: &str) -> Result<String, ServiceError> {
+        self.repository.get_service_log(name, u32::MAX)
+    }
+
+    pub fn get_log_lines(&self, service: &Service, lines: u32) -> Result<String, ServiceError> {
+        self.repository.get_service_log(service.name(), lines)
+    }
+
+    /// Looks a unit up by its exact or short name ("nginx" also finds "nginx.service"). Units that
+    /// are not loaded are still returned, with an unknown state, so they can be acted upon.
+    pub fn find_service(&self, name: &str) -> Result<Service, ServiceError> {
+        let unit_name = if name.contains('.') {
+            name.to_string()
+        } else {
+            format!("{}.service", name)
+        };
+
+        let services = self.list_services()?;
+        let service = services
+            .iter()
+            .find(|service| service.name() == name)
+            .or_else(|| services.iter().find(|service| service.name() == unit_name))
+            .cloned()
+            .unwrap_or_else(|| {
+                let unknown = || "unknown".to_string();
+                Service::new(
+                    unit_name,
+                    String::new(),
+                    ServiceState::new(unknown(), unknown(), unknown(), unknown(), unknown(), unknown()),
+                )
+            });
+        Ok(service)
     }
 
-    pub fn change_repository_connection(&mut self, connection_type: ConnectionType) -> Result<(), Box<dyn Error>> {
+    pub fn get_log_rate(&self, service: &Service) -> Result<LogRate, ServiceError> {
+        let timestamps = self.repository.get_log_timestamps(service.name())?;
+        Ok(LogRate::from_timestamps(&timestamps, now_usec()))
+    }
+
+    /// Units ranked by the number of journal entries they wrote during the last hour.
+    pub fn top_talkers(&self) -> Result<Vec<LogVolume>, ServiceError> {
+        let units = self.repository.get_journal_units_since(60)?;
+        Ok(LogVolume::rank(units.iter().map(String::as_str)))
+    }
+
+    /// Swaps the repository for another one, e.g. when switching hosts, returning the previous one.
+    pub fn replace_repository(&mut self, mut repository: Box<dyn ServiceRepository>) -> Box<dyn ServiceRepository> {
+        repository.set_call_limits(self.call_limits.clone());
+        self.clear_cache();
+        // Changes can only be reverted through the connection they were made on.
+        self.enablement_changes.borrow_mut().clear();
+        self.journal.borrow_mut().clear();
+        // Watches are set on the units of one host, the previous one is kept to switch back.
+        self.watched.clear();
+        let _ = self.rewatch();
+        let previous = std::mem::replace(&mut self.repository, repository);
+        if let Some(sender) = self.job_sender.clone() {
+            // Jobs of the new host are still reported if watching them fails.
+            let _ = self.repository.watch_jobs(sender);
+        }
+        self.restart_action_queue();
+        previous
+    }
+
+    pub fn change_repository_connection(&mut self, connection_type: ConnectionType) -> Result<(), ServiceError> {
+        self.clear_cache();
+        self.enablement_changes.borrow_mut().clear();
+        self.journal.borrow_mut().clear();
+        self.watched.clear();
+        let _ = self.rewatch();
         self.repository.change_connection(connection_type)?;
+        self.restart_action_queue();
         Ok(())
     }
 
-    pub fn systemctl_cat(&self, service: &Service) -> Result<String, Box<dyn Error>> {
-        self.repository.systemctl_cat(service.name())
+    pub fn connection_lost(&self) -> bool {
+        self.repository.connection_lost()
+    }
+
+    /// Opens another connection once the previous one was lost, e.g. after a restart of dbus.
+    /// The session changes are kept, they were made on the same machine.
+    pub fn reconnect(&mut self) -> Result<(), ServiceError> {
+        self.clear_cache();
+        self.repository.reconnect()?;
+        self.restart_action_queue();
+        Ok(())
+    }
+
+    fn clear_cache(&self) {
+        self.unit_files.clear();
+        self.capabilities.borrow_mut().clear();
+    }
+
+    pub fn systemctl_cat(&self, service: &Service) -> Result<String, ServiceError> {
+        let name = service.name();
+        self.unit_files
+            .get_or_fetch(name, self.repository.unit_generation(name), || self.repository.systemctl_cat(name))
+    }
+
+    /// The unit file and its drop-ins, each noting the settings a later one overrides.
+    pub fn unit_files(&self, service: &Service) -> Result<Vec<UnitFile>, ServiceError> {
+        let content = self.systemctl_cat(service)?;
+        let paths = self.repository.get_unit_file_paths(service.name())?;
+        Ok(UnitFile::split(&content, &paths))
+    }
+
+    /// Alias names of the unit and the symlinks to its unit file.
+    pub fn unit_links(&self, service: &Service) -> Result<UnitLinks, ServiceError> {
+        self.repository.get_unit_links(service.name())
+    }
+
+    /// The settings of the unit files next to the values systemd loaded, which differ for the
+    /// settings edited since (NeedDaemonReload=yes).
+    pub fn setting_drift(&self, service: &Service) -> Result<Vec<SettingDrift>, ServiceError> {
+        let settings = settings_on_disk(&self.unit_files(service)?);
+        let loaded = self.repository.get_loaded_settings(service.name(), &setting_names(&settings))?;
+        Ok(with_loaded(settings, &loaded))
+    }
+
+    /// Problems of the unit file and drop-ins on disk, e.g. a misspelled setting, see
+    /// `ServiceRepository::verify_unit`. Takes the name since a unit just created is not listed yet.
+    pub fn verify_unit(&self, name: &str) -> Result<Vec<VerifyFinding>, ServiceError> {
+        self.repository.verify_unit(name)
+    }
+
+    /// Reloads every unit file, like `systemctl daemon-reload`.
+    pub fn reload_daemon(&self) -> Result<(), ServiceError> {
+        self.writable("reload the unit files")?;
+        self.repository.reload_daemon()?;
+        self.clear_cache();
+        Ok(())
+    }
+
+    pub fn get_unit_dependencies(&self, name: &str) -> Result<UnitDependencies, ServiceError> {
+        self.repository.get_unit_dependencies(name)
+    }
+
+    pub fn get_unit_processes(&self, service: &Service) -> Result<Vec<UnitProcess>, ServiceError> {
+        self.repository.get_unit_processes(service.name())
+    }
+
+    pub fn get_state_change_timestamp(&self, service: &Service) -> Result<u64, ServiceError> {
+        self.repository.get_state_change_timestamp(service.name())
+    }
+
+    pub fn get_need_daemon_reload(&self, service: &Service) -> Result<bool, ServiceError> {
+        self.repository.get_need_daemon_reload(service.name())
+    }
+
+    pub fn get_freezer_state(&self, service: &Service) -> Result<String, ServiceError> {
+        self.repository.get_freezer_state(service.name())
+    }
+
+    pub fn get_slices(&self) -> Result<Vec<Slice>, ServiceError> {
+        self.repository.get_slices()
+    }
+
+    pub fn get_boot_time(&self) -> Result<String, ServiceError> {
+        self.repository.get_boot_time()
+    }
+
+    pub fn get_system_summary(&self) -> Result<SystemSummary, ServiceError> {
+        self.repository.get_system_summary()
+    }
+
+    pub fn get_boot_blame(&self) -> Result<Vec<UnitActivation>, ServiceError> {
+        self.repository.get_boot_blame()
+    }
+
+    pub fn get_critical_chain(&self, unit: Option<&str>) -> Result<Vec<ChainLink>, ServiceError> {
+        self.repository.get_critical_chain(unit)
+    }
+
+    pub fn get_security_scores(&self) -> Result<Vec<SecurityScore>, ServiceError> {
+        self.repository.get_security_scores()
+    }
+
+    pub fn get_security_audit(&self, name: &str) -> Result<SecurityAudit, ServiceError> {
+        self.repository.get_security_audit(name)
+    }
+
+    pub fn get_restart_settings(&self, name: &str) -> Result<RestartSettings, ServiceError> {
+        self.repository.get_restart_settings(name)
+    }
+
+    pub fn get_unit_timers(&self, service: &Service) -> Result<Vec<(String, TimerProperty)>, ServiceError> {
+        self.repository.get_unit_timers(service.name())
+    }
+
+    pub fn get_unit_conditions(&self, service: &Service) -> Result<UnitConditions, ServiceError> {
+        self.repository.get_unit_conditions(service.name())
+    }
+
+    pub fn get_unit_history(&self, service: &Service) -> Result<UnitHistory, ServiceError> {
+        self.repository.get_unit_history(service.name())
+    }
+
+    pub fn get_oom_entries(&self, service: &Service) -> Result<Vec<JournalEntry>, ServiceError> {
+        self.repository.get_oom_entries(service.name())
+    }
+
+    pub fn get_unit_documentation(&self, service: &Service) -> Result<Vec<DocumentationLink>, ServiceError> {
+        let references = self.repository.get_unit_documentation(service.name())?;
+        Ok(references.iter().map(|reference| DocumentationLink::parse(reference)).collect())
+    }
+
+    pub fn get_unit_sockets(&self, service: &Service) -> Result<Vec<(String, SocketProperty)>, ServiceError> {
+        self.repository.get_unit_sockets(service.name())
+    }
+
+    pub fn get_unit_triggers(&self, service: &Service) -> Result<UnitTriggers, ServiceError> {
+        self.repository.get_unit_triggers(service.name())
+    }
+
+    /// Starts a socket activating a service, returning its queued job.
+    pub fn start_socket(&self, socket: &str) -> Result<Option<String>, ServiceError> {
+        self.writable("start sockets")?;
+        self.permitted(PolicyAction::Start, socket)?;
+        let job = self.repository.start_service(socket)?;
+        self.performed(PolicyAction::Start, socket);
+        thread::sleep(Duration::from_millis(SLEEP_DURATION));
+        Ok(job)
+    }
+
+    /// Stops a socket activating a service, which then is not started on new connections anymore.
+    pub fn stop_socket(&self, socket: &str) -> Result<Option<String>, ServiceError> {
+        self.writable("stop sockets")?;
+        self.permitted(PolicyAction::Stop, socket)?;
+        let job = self.repository.stop_service(socket)?;
+        self.performed(PolicyAction::Stop, socket);
+        thread::sleep(Duration::from_millis(SLEEP_DURATION));
+        Ok(job)
+    }
+
+    /// Starts the service a timer activates, or the timed service itself, without waiting for
+    /// the timer to elapse. Returns the started unit and its queued job.
+    pub fn run_now(&self, service: &Service) -> Result<(String, Option<String>), ServiceError> {
+        self.writable("start units")?;
+        let unit = match service.properties() {
+            Some(UnitProperty::Timer(timer)) => timer.unit().to_string(),
+            _ => service.name().to_string(),
+        };
+        self.permitted(PolicyAction::Start, &unit)?;
+        let job = self.repository.start_service(&unit)?;
+        self.performed(PolicyAction::Start, &unit);
+        thread::sleep(Duration::from_millis(SLEEP_DURATION));
+        Ok((unit, job))
+    }
+
+    /// The units in the order their dependencies start them, e.g. a database before the service
+    /// requiring it. Units whose dependencies cannot be read keep their place in `units`.
+    pub fn start_order(&self, units: &[String]) -> Vec<String> {
+        let dependencies: HashMap<String, UnitDependencies> = units
+            .iter()
+            .filter_map(|unit| Some((unit.clone(), self.repository.get_unit_dependencies(unit).ok()?)))
+            .collect();
+        start_order(units, &dependencies)
+    }
+
+    /// Number of units depending on each of the services, recursively, for the impact column.
+    /// Costs a dependency lookup per service; services whose dependencies cannot be read are
+    /// left out of the graph.
+    pub fn impact_scores(&self, services: &[Service]) -> HashMap<String, usize> {
+        let pulled_in: HashMap<String, Vec<String>> = services
+            .iter()
+            .filter_map(|service| {
+                let dependencies = self.repository.get_unit_dependencies(service.name()).ok()?;
+                Some((service.name().to_string(), dependencies.pulled_in().cloned().collect()))
+            })
+            .collect();
+        impact_scores(&pulled_in)
+    }
+
+    /// Restarts and uptime of the services that are running or failed, for the list columns.
+    /// Costs a few D-Bus calls per service; services that cannot be read are left out.
+    pub fn runtime_stats(&self, services: &[Service]) -> HashMap<String, RuntimeStats> {
+        services
+            .iter()
+            .filter(|service| {
+                service.name().ends_with(".service")
+                    && matches!(service.state().active(), "active" | "activating" | "reloading" | "failed")
+            })
+            .filter_map(|service| {
+                let stats = self.repository.get_runtime_stats(service.name()).ok()?;
+                Some((service.name().to_string(), stats))
+            })
+            .collect()
+    }
+
+    /// CPU time and memory of the unit, for the usage graphs.
+    pub fn get_resource_usage(&self, name: &str) -> Result<ResourceUsage, ServiceError> {
+        self.repository.get_resource_usage(name)
+    }
+
+    pub fn plan_action(&self, action: UnitAction, service: &Service) -> Result<ActionPlan, ServiceError> {
+        self.repository.plan_action(action, service.name())
+    }
+
+    /// Runs the action on the service at every time the calendar expression matches, returning
+    /// the timer. The policy is checked now, as the timer runs the action without asking.
+    pub fn schedule_action(&self, action: PolicyAction, service: &Service, calendar: &str) -> Result<String, ServiceError> {
+        self.writable("schedule actions")?;
+        // The timer may well fire within a maintenance window.
+        self.allowed_by_policy(action, service.name())?;
+        self.repository.schedule_action(action, service.name(), calendar)
+    }
+
+    pub fn list_scheduled_actions(&self) -> Result<Vec<ScheduledAction>, ServiceError> {
+        self.repository.list_scheduled_actions()
+    }
+
+    pub fn cancel_scheduled_action(&self, timer: &str) -> Result<(), ServiceError> {
+        self.writable("cancel scheduled actions")?;
+        self.repository.cancel_scheduled_action(timer)
+    }
+
+    /// Lines of the unit files and drop-ins containing the text, ignoring case, by unit.
+    pub fn search_unit_files(&self, query: &str) -> Result<Vec<UnitFileMatch>, ServiceError> {
+        self.repository.search_unit_files(query)
+    }
+
+    /// Finds the mount, automount, path and service units managing the given path.
+    pub fn find_units_for_path(&self, path: &str) -> Result<Vec<UnitPathReference>, ServiceError> {
+        let query = Path::new(path);
+        let mut references: Vec<UnitPathReference> = self
+            .repository
+            .list_unit_paths()?
+            .into_iter()
+            .filter(|reference| reference.covers(query))
+            .collect();
+
+        // Most specific mount first, then watches and executables by name.
+        references.sort_by(|a, b| {
+            let rank = |r: &UnitPathReference| match r.relation() {
+                PathRelation::Mount | PathRelation::Automount => 0,
+                PathRelation::Watch(_) => 1,
+                PathRelation::Exec => 2,
+            };
+            rank(a)
+                .cmp(&rank(b))
+                .then_with(|| b.path().len().cmp(&a.path().len()))
+                .then_with(|| a.unit().cmp(b.unit()))
+        });
+        Ok(references)
     }
 }
 
+/// What the read-only mode calls a queued action and the policy action checked for it, `None`
+/// for the actions that are not queued.
+fn queued_policy(action: UnitAction) -> Option<(&'static str, PolicyAction)> {
+    match action {
+        UnitAction::Start => Some(("start units", PolicyAction::Start)),
+        UnitAction::Stop => Some(("stop units", PolicyAction::Stop)),
+        UnitAction::Restart => Some(("restart units", PolicyAction::Restart)),
+        UnitAction::Reload => Some(("reload units", PolicyAction::Reload)),
+        UnitAction::ReloadOrRestart => Some(("reload or restart units", PolicyAction::Restart)),
+        UnitAction::Enable | UnitAction::Disable | UnitAction::Freeze | UnitAction::Thaw => None,
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+use crate::domain::latency::Latency;
+use crate::infrastructure::trace_log::TraceLog;
+
+/// Runs of each step kept for the timings overlay, the older ones being dropped.
+const KEPT_SAMPLES: usize = 100;
+
+/// Step of the refresh pipeline whose duration is measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stage {
+    /// Listing the units, e.g. the ListUnits and ListUnitFiles calls of systemd.
+    ListUnits,
+    /// Fetching the properties of a unit, cached ones left out.
+    Properties,
+    /// Reading the journal of a unit.
+    Journal,
+    /// Matching the listed units against the filter and sorting them.
+    Filter,
+    /// Drawing a screen.
+    Render,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 5] = [Stage::ListUnits, Stage::Properties, Stage::Journal, Stage::Filter, Stage::Render];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Stage::ListUnits => "list units",
+            Stage::Properties => "properties",
+            Stage::Journal => "journal",
+            Stage::Filter => "filter",
+            Stage::Render => "render",
+        }
+    }
+}
+
+/// The last duration of a step and the spread of its recent ones.
+pub struct StageTimings {
+    pub stage: Stage,
+    pub last: Duration,
+    pub latency: Latency,
+}
+
+/// Durations of the recent runs of each step, also appended to the trace log when there is one.
+#[derive(Default)]
+pub struct Timings {
+    samples: HashMap<Stage, VecDeque<Duration>>,
+    trace: Option<TraceLog>,
+}
+
+impl Timings {
+    pub fn set_trace(&mut self, trace: TraceLog) {
+        self.trace = Some(trace);
+    }
+
+    pub fn record(&mut self, stage: Stage, duration: Duration) {
+        let samples = self.samples.entry(stage).or_default();
+        if samples.len() == KEPT_SAMPLES {
+            samples.pop_front();
+        }
+        samples.push_back(duration);
+        if let Some(trace) = &mut self.trace {
+            trace.write(stage.label(), duration);
+        }
+    }
+
+    /// The steps run so far, in the order of the pipeline.
+    pub fn summary(&self) -> Vec<StageTimings> {
+        Stage::ALL
+            .iter()
+            .filter_map(|stage| {
+                let samples: Vec<Duration> = self.samples.get(stage)?.iter().copied().collect();
+                Some(StageTimings {
+                    stage: *stage,
+                    last: *samples.last()?,
+                    latency: Latency::from_samples(&samples),
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,76 @@
+use chrono::Utc;
+use std::error::Error;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::config::names_unit;
+use crate::domain::service::Service;
+use crate::domain::uptime::{Availability, UptimeLog};
+use crate::infrastructure::uptime_store::{load_uptime, save_uptime, uptime_path};
+
+/// How often the observations are written to the state directory, besides on exit.
+const SAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Follows the availability of the watched units from the services fetched by the list.
+pub struct UptimeTracker {
+    /// Units to track, nothing is tracked when empty.
+    units: Vec<String>,
+    log: UptimeLog,
+    path: Option<PathBuf>,
+    last_save: Instant,
+}
+
+fn key(host: &str, unit: &str) -> String {
+    format!("{}/{}", host, unit)
+}
+
+impl UptimeTracker {
+    /// Starts from the saved observations, or from none when they cannot be read.
+    pub fn new(units: Vec<String>) -> (Self, Option<Box<dyn Error>>) {
+        let path = if units.is_empty() { None } else { uptime_path() };
+        let (log, error) = match path.as_deref().map(load_uptime) {
+            Some(Ok(log)) => (log, None),
+            Some(Err(e)) => (UptimeLog::default(), Some(e)),
+            None => (UptimeLog::default(), None),
+        };
+        let tracker = Self {
+            units,
+            log,
+            path,
+            last_save: Instant::now(),
+        };
+        (tracker, error)
+    }
+
+    /// Records the state of the tracked units among `services`, fetched from `host`. A unit
+    /// counts as up while it is active or reloading.
+    pub fn observe(&mut self, host: &str, services: &[Service]) -> Result<(), Box<dyn Error>> {
+        if self.units.is_empty() {
+            return Ok(());
+        }
+        let now = Utc::now().timestamp();
+        let tracked = services
+            .iter()
+            .filter(|service| self.units.iter().any(|name| names_unit(name, service.name())));
+        for service in tracked {
+            let up = matches!(service.state().active(), "active" | "reloading");
+            self.log.observe(&key(host, service.name()), up, now);
+        }
+        if self.last_save.elapsed() >= SAVE_INTERVAL {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    pub fn availability(&self, host: &str, unit: &str) -> Option<Availability> {
+        self.log.availability(&key(host, unit))
+    }
+
+    pub fn save(&mut self) -> Result<(), Box<dyn Error>> {
+        self.last_save = Instant::now();
+        match &self.path {
+            Some(path) => save_uptime(path, &self.log),
+            None => Ok(()),
+        }
+    }
+}
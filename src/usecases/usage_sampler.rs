@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::config::{names_unit, UsageConfig};
+use crate::domain::resource_usage::UsageHistory;
+use crate::domain::service::Service;
+use crate::usecases::services_manager::ServicesManager;
+
+/// Keeps the CPU and memory samples of the units over the last minutes, for the graphs of the
+/// details.
+pub struct UsageSampler {
+    /// Units sampled in the background, besides the one shown in the details.
+    units: Vec<String>,
+    histories: HashMap<String, UsageHistory>,
+    /// Samples kept per unit, enough for the configured minutes.
+    capacity: usize,
+    started: Instant,
+}
+
+fn key(host: &str, unit: &str) -> String {
+    format!("{}/{}", host, unit)
+}
+
+impl UsageSampler {
+    pub fn new(config: &UsageConfig) -> Self {
+        let interval = config.interval.max(1);
+        Self {
+            units: config.units.clone(),
+            histories: HashMap::new(),
+            capacity: (config.minutes * 60 / interval) as usize + 1,
+            started: Instant::now(),
+        }
+    }
+
+    pub fn interval(config: &UsageConfig) -> Duration {
+        Duration::from_secs(config.interval.max(1))
+    }
+
+    /// Samples the configured units among `services` and the unit shown in the details, on
+    /// `host`. Units whose usage cannot be read, e.g. inactive ones, are skipped.
+    pub fn sample(&mut self, usecase: &ServicesManager, host: &str, services: &[Service], shown: Option<&str>) {
+        let mut names: Vec<&str> = services
+            .iter()
+            .map(|service| service.name())
+            .filter(|name| self.units.iter().any(|unit| names_unit(unit, name)))
+            .collect();
+        if let Some(shown) = shown
+            && !names.contains(&shown)
+        {
+            names.push(shown);
+        }
+        let at_usec = self.started.elapsed().as_micros() as u64;
+        for name in names {
+            let Ok(usage) = usecase.get_resource_usage(name) else {
+                continue;
+            };
+            self.histories
+                .entry(key(host, name))
+                .or_insert_with(|| UsageHistory::new(self.capacity))
+                .push(at_usec, usage);
+        }
+    }
+
+    pub fn history(&self, host: &str, unit: &str) -> Option<&UsageHistory> {
+        self.histories.get(&key(host, unit))
+    }
+}
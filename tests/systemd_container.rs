@@ -0,0 +1,177 @@
+//! End-to-end tests of the systemd adapter against a real systemd running in a container, driven
+//! through the scripting subcommands. They need podman (or docker, with `CONTAINER_ENGINE=docker`)
+//! and a binary linked against a glibc no newer than the one of the image:
+//!
+//!     cargo test --features container-tests --test systemd_container
+#![cfg(feature = "container-tests")]
+
+use std::env;
+use std::process::{Command, Output};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const IMAGE: &str = "services-manager-tui-systemd";
+const UNIT: &str = "harness-sleeper.service";
+const BINARY: &str = "/usr/local/bin/systemd-manager-tui";
+/// How long systemd gets to boot, and a job to reach the expected state.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+fn engine() -> String {
+    env::var("CONTAINER_ENGINE").unwrap_or_else(|_| "podman".to_string())
+}
+
+fn run(command: &mut Command) -> Output {
+    let output = command
+        .output()
+        .unwrap_or_else(|e| panic!("could not run {:?}: {}", command, e));
+    assert!(
+        output.status.success(),
+        "{:?} failed: {}",
+        command,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output
+}
+
+/// A container booted with systemd as PID 1 and the TUI binary copied in, removed on drop.
+struct SystemdContainer {
+    engine: String,
+    id: String,
+}
+
+impl SystemdContainer {
+    fn start() -> Self {
+        let engine = engine();
+        let context = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/container");
+        run(Command::new(&engine).args(["build", "-q", "-t", IMAGE, context]));
+
+        let mut command = Command::new(&engine);
+        command.args(["run", "-d", "--rm"]);
+        if engine.ends_with("docker") {
+            // Docker has no --systemd, systemd needs the cgroup tree and a writable /run.
+            command.args(["--privileged", "--cgroupns=host", "-v", "/sys/fs/cgroup:/sys/fs/cgroup:rw"]);
+            command.args(["--tmpfs", "/run", "--tmpfs", "/tmp"]);
+        } else {
+            command.arg("--systemd=always");
+        }
+        let output = run(command.arg(IMAGE));
+        let container = Self {
+            engine,
+            id: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        };
+
+        let destination = format!("{}:{}", container.id, BINARY);
+        run(Command::new(&container.engine).args(["cp", env!("CARGO_BIN_EXE_systemd-manager-tui"), &destination]));
+        container.wait_until("boot", || {
+            // "degraded" is fine, some units of the image may not start in a container.
+            let state = container.exec(&["systemctl", "is-system-running"]);
+            matches!(state.trim(), "running" | "degraded")
+        });
+        container
+    }
+
+    /// Runs a command in the container, returning its standard output whatever its exit status.
+    fn exec(&self, args: &[&str]) -> String {
+        let output = Command::new(&self.engine)
+            .arg("exec")
+            .arg(&self.id)
+            .args(args)
+            .output()
+            .unwrap_or_else(|e| panic!("could not run {} exec: {}", self.engine, e));
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    }
+
+    /// Runs a subcommand of the TUI binary in the container, which must succeed.
+    fn tui(&self, args: &[&str]) -> String {
+        let output = run(Command::new(&self.engine).arg("exec").arg(&self.id).arg(BINARY).args(args));
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    }
+
+    fn wait_until(&self, what: &str, condition: impl Fn() -> bool) {
+        let started = Instant::now();
+        while !condition() {
+            assert!(started.elapsed() < TIMEOUT, "timed out waiting for {}", what);
+            thread::sleep(Duration::from_millis(200));
+        }
+    }
+
+    /// Waits for `systemctl <check> <unit>` to print the expected state.
+    fn wait_for(&self, check: &str, expected: &str) {
+        self.wait_until(&format!("{} to be {}", UNIT, expected), || {
+            self.exec(&["systemctl", check, UNIT]).trim() == expected
+        });
+    }
+}
+
+impl Drop for SystemdContainer {
+    fn drop(&mut self) {
+        let _ = Command::new(&self.engine).args(["rm", "-f", &self.id]).output();
+    }
+}
+
+fn listed_unit(container: &SystemdContainer) -> serde_json::Value {
+    let services: Vec<serde_json::Value> =
+        serde_json::from_str(&container.tui(&["list", "--json"])).expect("list --json prints JSON");
+    services
+        .into_iter()
+        .find(|service| service["name"] == UNIT)
+        .unwrap_or_else(|| panic!("{} is not listed", UNIT))
+}
+
+#[test]
+fn lists_units_with_their_state() {
+    let container = SystemdContainer::start();
+    let unit = listed_unit(&container);
+    assert_eq!(unit["state"]["active"], "inactive");
+    assert_eq!(unit["state"]["file"], "disabled");
+    assert_eq!(unit["description"], "Unit driven by the container tests");
+}
+
+#[test]
+fn starts_restarts_and_stops_a_unit() {
+    let container = SystemdContainer::start();
+    container.tui(&["start", "harness-sleeper"]);
+    container.wait_for("is-active", "active");
+    assert_eq!(listed_unit(&container)["state"]["sub"], "running");
+
+    let pid = container.exec(&["systemctl", "show", "-P", "MainPID", UNIT]);
+    container.tui(&["restart", UNIT]);
+    container.wait_until("a new main process", || {
+        let current = container.exec(&["systemctl", "show", "-P", "MainPID", UNIT]);
+        current != pid && current.trim() != "0"
+    });
+
+    container.tui(&["stop", UNIT]);
+    container.wait_for("is-active", "inactive");
+}
+
+#[test]
+fn enables_and_disables_a_unit() {
+    let container = SystemdContainer::start();
+    container.tui(&["enable", UNIT]);
+    container.wait_for("is-enabled", "enabled");
+    assert_eq!(listed_unit(&container)["state"]["file"], "enabled");
+
+    container.tui(&["disable", UNIT]);
+    container.wait_for("is-enabled", "disabled");
+    assert_eq!(listed_unit(&container)["state"]["file"], "disabled");
+}
+
+#[test]
+fn exports_the_properties_of_a_unit() {
+    let container = SystemdContainer::start();
+    container.tui(&["start", UNIT]);
+    container.wait_for("is-active", "active");
+
+    let exported: Vec<serde_json::Value> =
+        serde_json::from_str(&container.tui(&["export", "--filter", "harness-sleeper"])).expect("export prints JSON");
+    let [unit] = exported.as_slice() else {
+        panic!("expected only {}, got {:?}", UNIT, exported);
+    };
+    assert_eq!(unit["name"], UNIT);
+    assert!(
+        unit["properties"].is_object(),
+        "no properties exported: {}",
+        unit
+    );
+}